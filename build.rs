@@ -1,12 +1,107 @@
-use std::env;
+use std::{env, path::PathBuf, process::Command};
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(mac_catalyst)");
+
     if let Ok(target_env) = env::var("TARGET") {
-        match target_env.as_str() {
-            "x86_64-apple-ios-macabi" => {
-                println!("cargo:rustc-cfg=mac_catalyst");
-            }
-            _ => {}
+        if target_env == "x86_64-apple-ios-macabi" {
+            println!("cargo:rustc-cfg=mac_catalyst");
         }
     }
+
+    let is_apple = env::var("CARGO_CFG_TARGET_VENDOR").as_deref() == Ok("apple");
+    if is_apple && env::var_os("CARGO_FEATURE_OBJC").is_some() {
+        compile_exception_shim();
+    }
+}
+
+/// Compiles `src/objc/exception.m`, which Rust cannot express because it
+/// catches Objective-C exceptions.
+///
+/// This invokes the C compiler directly to avoid a build dependency.
+fn compile_exception_shim() {
+    let source = "src/objc/exception.m";
+    println!("cargo:rerun-if-changed={}", source);
+    println!("cargo:rerun-if-env-changed=CC");
+    println!("cargo:rerun-if-env-changed=AR");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR not set"));
+    let object = out_dir.join("exception.o");
+    let library = out_dir.join("libfruity_exception.a");
+
+    let target = env::var("TARGET").expect("TARGET not set");
+    let cc = env::var("CC").unwrap_or_else(|_| "clang".into());
+    let ar = env::var("AR").unwrap_or_else(|_| "ar".into());
+
+    let mut compile = Command::new(&cc);
+    compile
+        .args(["-c", "-O2", "-fobjc-exceptions", "-fPIC"])
+        .arg(format!("--target={}", clang_target(&target)))
+        .arg(source)
+        .arg("-o")
+        .arg(&object);
+
+    if let Some(sdk_root) = sdk_root(&target) {
+        compile.arg("-isysroot").arg(sdk_root);
+    }
+
+    run(compile, &cc);
+
+    let mut archive = Command::new(&ar);
+    archive.arg("crs").arg(&library).arg(&object);
+    run(archive, &ar);
+
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rustc-link-lib=static=fruity_exception");
+}
+
+/// Returns the Clang target for a Rust target, which differ for Mac Catalyst
+/// and simulators.
+fn clang_target(target: &str) -> String {
+    match target {
+        "x86_64-apple-ios-macabi" => "x86_64-apple-ios13.1-macabi".into(),
+        "aarch64-apple-ios-macabi" => "arm64-apple-ios14.0-macabi".into(),
+        "aarch64-apple-ios-sim" => "arm64-apple-ios-simulator".into(),
+        "x86_64-apple-ios" => "x86_64-apple-ios-simulator".into(),
+        _ => target.replacen("aarch64", "arm64", 1),
+    }
+}
+
+/// Returns the SDK path for `target` from `SDKROOT` or `xcrun`, if either is
+/// available.
+fn sdk_root(target: &str) -> Option<String> {
+    println!("cargo:rerun-if-env-changed=SDKROOT");
+    if let Ok(sdk_root) = env::var("SDKROOT") {
+        return Some(sdk_root);
+    }
+
+    let sdk = if target.ends_with("-darwin") || target.ends_with("-macabi") {
+        "macosx"
+    } else if target.ends_with("-sim") || target.starts_with("x86_64-apple-ios") {
+        "iphonesimulator"
+    } else if target.contains("-ios") {
+        "iphoneos"
+    } else {
+        return None;
+    };
+
+    let output = Command::new("xcrun")
+        .args(["--sdk", sdk, "--show-sdk-path"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .ok()
+            .map(|path| path.trim().to_owned())
+    } else {
+        None
+    }
+}
+
+fn run(mut command: Command, program: &str) {
+    let status = command
+        .status()
+        .unwrap_or_else(|error| panic!("Failed to run `{}`: {}", program, error));
+    assert!(status.success(), "`{}` failed with {}", program, status);
 }
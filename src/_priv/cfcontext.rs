@@ -0,0 +1,24 @@
+//! Callbacks for Core Foundation contexts whose info is a `sync::Arc<T>`, such
+//! as those of timers, message ports, and sockets.
+
+use super::abort_on_panic;
+use crate::core::Arc;
+use crate::core_foundation::CFString;
+use std::{ffi::c_void, sync};
+
+/// Retains `info`, which must have come from `sync::Arc::<T>::as_ptr`.
+pub unsafe extern "C" fn retain<T>(info: *const c_void) -> *const c_void {
+    sync::Arc::increment_strong_count(info.cast::<T>());
+    info
+}
+
+/// Releases `info`, which must have come from `sync::Arc::<T>::as_ptr`.
+pub unsafe extern "C" fn release<T>(info: *const c_void) {
+    // Dropping the value may run arbitrary code.
+    abort_on_panic(|| drop(sync::Arc::from_raw(info.cast::<T>())));
+}
+
+/// Describes `info` as a Rust closure.
+pub unsafe extern "C" fn copy_description(_info: *const c_void) -> *const CFString {
+    Arc::into_raw(CFString::from_str("<Rust closure>"))
+}
@@ -88,3 +88,97 @@ pub const fn is_ascii(bytes: &[u8]) -> bool {
         i += 1;
     }
 }
+
+// Emits a constant `CFString` for `$s`, evaluating to a pointer to it. This is
+// shared by `ns_string!` and `cf_string!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _cfstring_ptr {
+    ($s:expr) => {{
+        // This is defined in CoreFoundation, but we don't emit a link attribute
+        // here because it is already linked via Foundation or CoreFoundation.
+        //
+        // Although this is a "private" (underscored) symbol, it is directly
+        // referenced in Objective-C binaries. So it's safe for us to reference.
+        extern "C" {
+            static __CFConstantStringClassReference: $crate::_priv::c_void;
+        }
+
+        // Remove any trailing null early.
+        const INPUT: &[u8] = $crate::_priv::cfstring::trim_trailing_nul($s);
+
+        if $crate::_priv::cfstring::is_ascii(INPUT) {
+            // The ASCII bytes with a trailing null byte.
+            #[repr(C)]
+            struct Ascii {
+                data: [u8; INPUT.len()],
+                nul: u8,
+            }
+
+            const ASCII: Ascii = Ascii {
+                data: unsafe { *INPUT.as_ptr().cast::<[u8; INPUT.len()]>() },
+                nul: 0,
+            };
+
+            const ASCII_ARRAY: &[u8; INPUT.len() + 1] =
+                unsafe { $crate::_priv::std::mem::transmute(&ASCII) };
+
+            #[link_section = "__DATA,__cfstring,regular"]
+            static CFSTRING: $crate::_priv::cfstring::CFStringAscii =
+                $crate::_priv::cfstring::CFStringAscii::new(
+                    unsafe { &__CFConstantStringClassReference },
+                    ASCII_ARRAY.as_ptr(),
+                    // The length does not include the trailing null.
+                    INPUT.len(),
+                );
+
+            CFSTRING.as_ptr()
+        } else {
+            // The full UTF-16 contents along with the written length.
+            const UTF16_FULL: (&[u16; INPUT.len()], usize) = {
+                let mut out = [0u16; INPUT.len()];
+                let mut iter = $crate::_priv::cfstring::utf16::EncodeUtf16Iter::new(INPUT);
+                let mut written = 0;
+
+                while let Some((state, chars)) = iter.next() {
+                    iter = state;
+                    out[written] = chars.repr[0];
+                    written += 1;
+
+                    if chars.len > 1 {
+                        out[written] = chars.repr[1];
+                        written += 1;
+                    }
+                }
+
+                (&{ out }, written)
+            };
+
+            // The written UTF-16 contents with a trailing null code point.
+            #[repr(C)]
+            struct Utf16 {
+                data: [u16; UTF16_FULL.1],
+                nul: u16,
+            }
+
+            const UTF16: Utf16 = Utf16 {
+                data: unsafe { *UTF16_FULL.0.as_ptr().cast::<[u16; UTF16_FULL.1]>() },
+                nul: 0,
+            };
+
+            const UTF16_ARRAY: &[u16; UTF16_FULL.1 + 1] =
+                unsafe { $crate::_priv::std::mem::transmute(&UTF16) };
+
+            #[link_section = "__DATA,__cfstring,regular"]
+            static CFSTRING: $crate::_priv::cfstring::CFStringUtf16 =
+                $crate::_priv::cfstring::CFStringUtf16::new(
+                    unsafe { &__CFConstantStringClassReference },
+                    UTF16_ARRAY.as_ptr(),
+                    // The length does not include the trailing null.
+                    UTF16_FULL.1,
+                );
+
+            CFSTRING.as_ptr()
+        }
+    }};
+}
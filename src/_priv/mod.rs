@@ -1,5 +1,34 @@
 pub use std;
 pub use std::ffi::c_void;
 
-#[cfg(feature = "foundation")]
+#[cfg(feature = "objc")]
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process,
+};
+
+#[cfg(feature = "objc")]
+pub use crate::objc::sel::atomic::AtomicSel;
+
+#[cfg(any(feature = "foundation", feature = "core_foundation"))]
 pub mod cfstring;
+
+#[cfg(feature = "core_foundation")]
+pub(crate) mod cfcontext;
+
+#[cfg(all(test, any(feature = "foundation", feature = "core_foundation")))]
+pub(crate) mod temp_dir;
+
+/// Runs `f`, aborting the process if it panics.
+///
+/// This must wrap any Rust code called from C or Objective-C, such as
+/// callbacks, blocks, and method implementations, since unwinding into
+/// non-Rust frames is undefined behavior. The panic hook runs before aborting,
+/// so the panic message is still printed.
+#[cfg(feature = "objc")]
+pub(crate) fn abort_on_panic<F: FnOnce() -> R, R>(f: F) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(_) => process::abort(),
+    }
+}
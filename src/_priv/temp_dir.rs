@@ -0,0 +1,21 @@
+use std::{env, fs, path::PathBuf, process};
+
+/// A temporary directory for tests, which is removed when dropped.
+pub(crate) struct TempDir(pub PathBuf);
+
+impl TempDir {
+    /// Creates an empty directory whose name starts with `name`, which must be
+    /// unique among tests.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("fruity-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path.canonicalize().unwrap())
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
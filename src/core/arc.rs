@@ -1,5 +1,6 @@
 use super::ObjectType;
 use std::{
+    error::Error,
     fmt,
     hash::{Hash, Hasher},
     mem,
@@ -72,6 +73,13 @@ impl<T: ObjectType + fmt::Debug> fmt::Debug for Arc<T> {
     }
 }
 
+impl<T: ObjectType + Error> Error for Arc<T> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (**self).source()
+    }
+}
+
 impl<T: ObjectType> fmt::Pointer for Arc<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -94,6 +102,25 @@ impl<T: ObjectType> Arc<T> {
         ObjectType::retain(obj)
     }
 
+    /// Returns a mutable reference to the object if `this` is its only
+    /// reference.
+    ///
+    /// This is how mutating methods that take `&mut self` are called on
+    /// objects such as
+    /// `CFMutableString`.
+    ///
+    /// Returns `None` if the object may be shared, which is determined by
+    /// [`ObjectType::is_unique`].
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if T::is_unique(this) {
+            // SAFETY: `this` is the only reference to the object.
+            Some(unsafe { this.obj.as_mut() })
+        } else {
+            None
+        }
+    }
+
     /// Constructs an `Arc<T>` from a raw pointer.
     ///
     /// # Safety
@@ -1,5 +1,6 @@
 // This macro is intentionally undocumented to ensure it is not publicly
 // exported.
+#[allow(unused_macros)]
 macro_rules! subclass {
     (
         $(#[$meta:meta])+
@@ -20,6 +21,11 @@ macro_rules! subclass {
             unsafe fn release(obj: std::ptr::NonNull<Self>) {
                 <$b>::release(obj.cast());
             }
+
+            #[inline]
+            fn is_unique(obj: &Self) -> bool {
+                <$b>::is_unique(&obj.0)
+            }
         }
 
         impl $(<$lifetime>)? std::ops::Deref for $a $(<$lifetime>)? {
@@ -59,10 +65,74 @@ macro_rules! subclass {
             }
         }
     };
+    (
+        $(#[$meta:meta])+
+        $vis:vis class $a:ident <$($t:ident),+> : $b:ty ;
+    ) => {
+        $(#[$meta])+
+        #[repr(C)]
+        $vis struct $a<$($t),+>($b, std::marker::PhantomData<($($t,)+)>);
+
+        impl<$($t),+> $crate::core::ObjectType for $a<$($t),+> {
+            #[inline]
+            fn retain(obj: &Self) -> $crate::core::Arc<Self> {
+                let obj = $crate::core::Arc::retain(&obj.0);
+                unsafe { $crate::core::Arc::cast_unchecked(obj) }
+            }
+
+            #[inline]
+            unsafe fn release(obj: std::ptr::NonNull<Self>) {
+                <$b>::release(obj.cast());
+            }
+
+            #[inline]
+            fn is_unique(obj: &Self) -> bool {
+                <$b>::is_unique(&obj.0)
+            }
+        }
+
+        impl<$($t),+> std::ops::Deref for $a<$($t),+> {
+            type Target = $b;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<$($t),+> AsRef<$a<$($t),+>> for $a<$($t),+> {
+            #[inline]
+            fn as_ref(&self) -> &Self {
+                self
+            }
+        }
+
+        impl<$($t),+> AsMut<$a<$($t),+>> for $a<$($t),+> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut Self {
+                self
+            }
+        }
+
+        impl<$($t,)+ AsRefT> AsRef<AsRefT> for $a<$($t),+> where $b: AsRef<AsRefT> {
+            #[inline]
+            fn as_ref(&self) -> &AsRefT {
+                self.0.as_ref()
+            }
+        }
+
+        impl<$($t,)+ AsMutT> AsMut<AsMutT> for $a<$($t),+> where $b: AsMut<AsMutT> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut AsMutT {
+                self.0.as_mut()
+            }
+        }
+    };
 }
 
 // This macro is intentionally undocumented to ensure it is not publicly
 // exported.
+#[allow(unused_macros)]
 macro_rules! object_wrapper {
     (
         $(#[$meta:meta])+
@@ -82,6 +152,11 @@ macro_rules! object_wrapper {
             unsafe fn release(obj: std::ptr::NonNull<Self>) {
                 <$target>::release(obj.cast::<$target>())
             }
+
+            #[inline]
+            fn is_unique(obj: &Self) -> bool {
+                <$target>::is_unique(&obj.0)
+            }
         }
 
         impl $(<$lifetime>)? From<$crate::core::Arc<$target>> for $crate::core::Arc<$wrapper $(<$lifetime>)?> {
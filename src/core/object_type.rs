@@ -34,4 +34,14 @@ pub trait ObjectType: Sized {
     /// program will either abort, read/write unowned memory, or trigger
     /// undefined behavior.
     unsafe fn release(obj: NonNull<Self>);
+
+    /// Returns `true` if the object is known to have a retain count of 1.
+    ///
+    /// This is used by [`Arc::get_mut`] to determine whether mutable access is
+    /// exclusive. The default implementation conservatively returns `false`.
+    #[inline]
+    fn is_unique(obj: &Self) -> bool {
+        let _ = obj;
+        false
+    }
 }
@@ -0,0 +1,380 @@
+use super::{CFIndex, CFOptionFlags, CFString, CFType};
+use crate::core::Arc;
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    ffi::c_void,
+    ptr::{self, NonNull},
+};
+
+cf_subclass! {
+    /// An object that allocates and deallocates memory for Core Foundation
+    /// objects.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfallocator?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfallocatorref?language=objc)
+    #[derive(Debug, PartialEq, Hash)]
+    pub class CFAllocator: CFType;
+    type_id = CFAllocatorGetTypeID;
+}
+
+#[allow(non_upper_case_globals)]
+extern "C" {
+    static kCFAllocatorSystemDefault: &'static CFAllocator;
+    static kCFAllocatorMalloc: &'static CFAllocator;
+    static kCFAllocatorMallocZone: &'static CFAllocator;
+    static kCFAllocatorNull: &'static CFAllocator;
+    static kCFAllocatorUseContext: &'static CFAllocator;
+}
+
+/// Well-known allocators.
+impl CFAllocator {
+    /// Returns the current default allocator, which is usually
+    /// [`system_default`](Self::system_default).
+    ///
+    /// Creation functions in this crate that take an `Option<&CFAllocator>`
+    /// use this allocator when given `None`, which corresponds to the
+    /// [`kCFAllocatorDefault`](https://developer.apple.com/documentation/corefoundation/kcfallocatordefault)
+    /// (`NULL`) constant.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521174-cfallocatorgetdefault).
+    #[inline]
+    #[doc(alias = "CFAllocatorGetDefault")]
+    #[doc(alias = "kCFAllocatorDefault")]
+    pub fn current_default() -> &'static CFAllocator {
+        extern "C" {
+            fn CFAllocatorGetDefault() -> &'static CFAllocator;
+        }
+        unsafe { CFAllocatorGetDefault() }
+    }
+
+    /// The default system allocator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfallocatorsystemdefault).
+    #[inline]
+    #[doc(alias = "kCFAllocatorSystemDefault")]
+    pub fn system_default() -> &'static CFAllocator {
+        unsafe { kCFAllocatorSystemDefault }
+    }
+
+    /// An allocator that uses `malloc`, `realloc`, and `free`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfallocatormalloc).
+    #[inline]
+    #[doc(alias = "kCFAllocatorMalloc")]
+    pub fn malloc() -> &'static CFAllocator {
+        unsafe { kCFAllocatorMalloc }
+    }
+
+    /// An allocator that explicitly uses the default malloc zone.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfallocatormalloczone).
+    #[inline]
+    #[doc(alias = "kCFAllocatorMallocZone")]
+    pub fn malloc_zone() -> &'static CFAllocator {
+        unsafe { kCFAllocatorMallocZone }
+    }
+
+    /// An allocator that does not allocate or deallocate memory.
+    ///
+    /// This is useful in situations where the contents of an object should
+    /// not be freed, such as with `*NoCopy` creation functions.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfallocatornull).
+    #[inline]
+    #[doc(alias = "kCFAllocatorNull")]
+    pub fn null() -> &'static CFAllocator {
+        unsafe { kCFAllocatorNull }
+    }
+
+    /// A special allocator argument to
+    /// [`CFAllocatorCreate`](https://developer.apple.com/documentation/corefoundation/1521290-cfallocatorcreate)
+    /// that means the allocator should allocate itself using its own context.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfallocatorusecontext).
+    #[inline]
+    #[doc(alias = "kCFAllocatorUseContext")]
+    pub fn use_context() -> &'static CFAllocator {
+        unsafe { kCFAllocatorUseContext }
+    }
+}
+
+/// Creating allocators.
+impl CFAllocator {
+    /// Creates an allocator that is backed by `alloc`.
+    ///
+    /// Core Foundation does not tell the allocator the size of memory being
+    /// deallocated, so every allocation is prefixed with a header that stores
+    /// its size. All allocations are aligned to 16 bytes, which matches the
+    /// guarantee made by `malloc` on Apple platforms.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521290-cfallocatorcreate).
+    #[doc(alias = "CFAllocatorCreate")]
+    pub fn new<A: GlobalAlloc + Send + Sync + 'static>(alloc: A) -> Arc<CFAllocator> {
+        let info = Box::into_raw(Box::new(alloc)) as *mut c_void;
+
+        let mut context = CFAllocatorContext {
+            version: 0,
+            info,
+            retain: None,
+            release: Some(global_alloc::release::<A>),
+            copy_description: None,
+            allocate: Some(global_alloc::allocate::<A>),
+            reallocate: Some(global_alloc::reallocate::<A>),
+            deallocate: Some(global_alloc::deallocate::<A>),
+            preferred_size: None,
+        };
+
+        unsafe {
+            let allocator = CFAllocatorCreate(None, &mut context);
+            assert!(!allocator.is_null(), "Could not create CFAllocator");
+            Arc::from_raw(allocator)
+        }
+    }
+}
+
+/// Allocating memory.
+impl CFAllocator {
+    /// Allocates memory of `size` bytes using `self`.
+    ///
+    /// Returns `None` if allocation failed.
+    ///
+    /// # Safety
+    ///
+    /// The returned memory must be deallocated by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521228-cfallocatorallocate).
+    #[inline]
+    #[doc(alias = "CFAllocatorAllocate")]
+    pub unsafe fn allocate(&self, size: CFIndex, hint: CFOptionFlags) -> Option<NonNull<c_void>> {
+        extern "C" {
+            fn CFAllocatorAllocate(
+                allocator: &CFAllocator,
+                size: CFIndex,
+                hint: CFOptionFlags,
+            ) -> *mut c_void;
+        }
+        NonNull::new(CFAllocatorAllocate(self, size, hint))
+    }
+
+    /// Reallocates `ptr` to be `new_size` bytes using `self`.
+    ///
+    /// If `ptr` is null, this behaves like [`allocate`](Self::allocate).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or have been allocated by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521163-cfallocatorreallocate).
+    #[inline]
+    #[doc(alias = "CFAllocatorReallocate")]
+    pub unsafe fn reallocate(
+        &self,
+        ptr: *mut c_void,
+        new_size: CFIndex,
+        hint: CFOptionFlags,
+    ) -> Option<NonNull<c_void>> {
+        extern "C" {
+            fn CFAllocatorReallocate(
+                allocator: &CFAllocator,
+                ptr: *mut c_void,
+                new_size: CFIndex,
+                hint: CFOptionFlags,
+            ) -> *mut c_void;
+        }
+        NonNull::new(CFAllocatorReallocate(self, ptr, new_size, hint))
+    }
+
+    /// Deallocates `ptr` using `self`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by `self` and must not be used after
+    /// this call.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521150-cfallocatordeallocate).
+    #[inline]
+    #[doc(alias = "CFAllocatorDeallocate")]
+    pub unsafe fn deallocate(&self, ptr: NonNull<c_void>) {
+        extern "C" {
+            fn CFAllocatorDeallocate(allocator: &CFAllocator, ptr: NonNull<c_void>);
+        }
+        CFAllocatorDeallocate(self, ptr)
+    }
+
+    /// Returns the actual size `self` would allocate for a request of `size`
+    /// bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521328-cfallocatorgetpreferredsizeforsi).
+    #[inline]
+    #[doc(alias = "CFAllocatorGetPreferredSizeForSize")]
+    pub fn preferred_size_for_size(&self, size: CFIndex, hint: CFOptionFlags) -> CFIndex {
+        extern "C" {
+            fn CFAllocatorGetPreferredSizeForSize(
+                allocator: &CFAllocator,
+                size: CFIndex,
+                hint: CFOptionFlags,
+            ) -> CFIndex;
+        }
+        unsafe { CFAllocatorGetPreferredSizeForSize(self, size, hint) }
+    }
+}
+
+#[repr(C)]
+struct CFAllocatorContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+    allocate: Option<
+        unsafe extern "C" fn(size: CFIndex, hint: CFOptionFlags, info: *mut c_void) -> *mut c_void,
+    >,
+    reallocate: Option<
+        unsafe extern "C" fn(
+            ptr: *mut c_void,
+            new_size: CFIndex,
+            hint: CFOptionFlags,
+            info: *mut c_void,
+        ) -> *mut c_void,
+    >,
+    deallocate: Option<unsafe extern "C" fn(ptr: *mut c_void, info: *mut c_void)>,
+    preferred_size: Option<
+        unsafe extern "C" fn(size: CFIndex, hint: CFOptionFlags, info: *mut c_void) -> CFIndex,
+    >,
+}
+
+extern "C" {
+    fn CFAllocatorCreate(
+        allocator: Option<&CFAllocator>,
+        context: *mut CFAllocatorContext,
+    ) -> *const CFAllocator;
+}
+
+/// Callbacks for `CFAllocator::new`.
+mod global_alloc {
+    use super::*;
+
+    // Large enough to hold the size and keep the user pointer aligned like
+    // `malloc`.
+    const HEADER: usize = 16;
+    const ALIGN: usize = 16;
+
+    #[inline]
+    fn layout(size: usize) -> Option<Layout> {
+        Layout::from_size_align(size.checked_add(HEADER)?, ALIGN).ok()
+    }
+
+    pub unsafe extern "C" fn release<A: GlobalAlloc>(info: *const c_void) {
+        drop(Box::from_raw(info as *mut A));
+    }
+
+    pub unsafe extern "C" fn allocate<A: GlobalAlloc>(
+        size: CFIndex,
+        _hint: CFOptionFlags,
+        info: *mut c_void,
+    ) -> *mut c_void {
+        let alloc = &*(info as *const A);
+        let layout = match layout(size as usize) {
+            Some(layout) => layout,
+            None => return ptr::null_mut(),
+        };
+
+        let base = alloc.alloc(layout);
+        if base.is_null() {
+            return ptr::null_mut();
+        }
+
+        (base as *mut usize).write(size as usize);
+        base.add(HEADER).cast()
+    }
+
+    pub unsafe extern "C" fn reallocate<A: GlobalAlloc>(
+        ptr: *mut c_void,
+        new_size: CFIndex,
+        _hint: CFOptionFlags,
+        info: *mut c_void,
+    ) -> *mut c_void {
+        let alloc = &*(info as *const A);
+        let base = (ptr as *mut u8).sub(HEADER);
+        let old_size = (base as *const usize).read();
+
+        if layout(new_size as usize).is_none() {
+            return ptr::null_mut();
+        }
+
+        let base = alloc.realloc(
+            base,
+            Layout::from_size_align_unchecked(old_size + HEADER, ALIGN),
+            new_size as usize + HEADER,
+        );
+        if base.is_null() {
+            return ptr::null_mut();
+        }
+
+        (base as *mut usize).write(new_size as usize);
+        base.add(HEADER).cast()
+    }
+
+    pub unsafe extern "C" fn deallocate<A: GlobalAlloc>(ptr: *mut c_void, info: *mut c_void) {
+        let alloc = &*(info as *const A);
+        let base = (ptr as *mut u8).sub(HEADER);
+        let size = (base as *const usize).read();
+
+        alloc.dealloc(
+            base,
+            Layout::from_size_align_unchecked(size + HEADER, ALIGN),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        alloc::System,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            CFAllocator::current_default(),
+            CFAllocator::system_default()
+        );
+        assert_ne!(CFAllocator::null(), CFAllocator::system_default());
+    }
+
+    #[test]
+    fn custom() {
+        static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counting;
+
+        unsafe impl GlobalAlloc for Counting {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                ALLOCATED.fetch_add(1, Ordering::SeqCst);
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                ALLOCATED.fetch_sub(1, Ordering::SeqCst);
+                System.dealloc(ptr, layout)
+            }
+        }
+
+        let allocator = CFAllocator::new(Counting);
+        let before = ALLOCATED.load(Ordering::SeqCst);
+
+        unsafe {
+            let ptr = allocator.allocate(64, 0).unwrap();
+            assert_eq!(ptr.as_ptr() as usize % 16, 0);
+            assert_eq!(ALLOCATED.load(Ordering::SeqCst), before + 1);
+
+            let ptr = allocator.reallocate(ptr.as_ptr(), 4096, 0).unwrap();
+            allocator.deallocate(ptr);
+        }
+
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), before);
+    }
+}
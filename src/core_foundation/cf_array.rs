@@ -0,0 +1,557 @@
+use super::{CFAllocator, CFComparisonResult, CFIndex, CFRange, CFType};
+use crate::core::{Arc, ObjectType};
+use std::{
+    any::Any,
+    cmp::{self, Ordering},
+    ffi::c_void,
+    fmt,
+    ops::Index,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+subclass! {
+    /// An immutable array of objects of type `T`.
+    ///
+    /// The untyped `CFArray<CFType>` form can hold heterogeneous objects,
+    /// which can be recovered with
+    /// [`CFType::downcast_ref`](struct.CFType.html#method.downcast_ref).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfarray?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfarrayref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFArray<T>: CFType;
+}
+
+// Only the untyped form can be downcast to because elements are not checked.
+cf_type_object!(CFArray<CFType>, CFArrayGetTypeID);
+
+#[repr(C)]
+pub(crate) struct CFArrayCallBacks {
+    version: CFIndex,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+    equal: *const c_void,
+}
+
+extern "C" {
+    pub(crate) static kCFTypeArrayCallBacks: CFArrayCallBacks;
+
+    fn CFArrayGetCount(the_array: *const CFType) -> CFIndex;
+    fn CFArrayGetValueAtIndex(the_array: *const CFType, idx: CFIndex) -> *const c_void;
+    fn CFArrayGetValues(the_array: *const CFType, range: CFRange, values: *mut *const c_void);
+}
+
+impl<T: ObjectType + fmt::Debug> fmt::Debug for CFArray<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ObjectType> Index<usize> for CFArray<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        match self.get(index as CFIndex) {
+            Some(value) => value,
+            None => panic!(
+                "Index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+        }
+    }
+}
+
+impl<'a, T: ObjectType> IntoIterator for &'a CFArray<T> {
+    type Item = &'a T;
+    type IntoIter = CFArrayIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType> CFArray<T> {
+    /// Creates an array containing retained `values`.
+    ///
+    /// This uses
+    /// [`kCFTypeArrayCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypearraycallbacks).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388741-cfarraycreate).
+    #[inline]
+    #[doc(alias = "CFArrayCreate")]
+    pub fn from_slice(values: &[&T]) -> Arc<Self> {
+        extern "C" {
+            fn CFArrayCreate(
+                allocator: Option<&CFAllocator>,
+                values: *const *const c_void,
+                num_values: CFIndex,
+                call_backs: *const CFArrayCallBacks,
+            ) -> *const CFType;
+        }
+
+        unsafe {
+            let array = CFArrayCreate(
+                None,
+                values.as_ptr().cast(),
+                values.len() as CFIndex,
+                &kCFTypeArrayCallBacks,
+            );
+            assert!(!array.is_null(), "Could not create CFArray");
+            Arc::from_raw(array.cast())
+        }
+    }
+
+    /// Returns the number of values in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388772-cfarraygetcount).
+    #[inline]
+    #[doc(alias = "CFArrayGetCount")]
+    pub fn len(&self) -> CFIndex {
+        unsafe { CFArrayGetCount(&self.0) }
+    }
+
+    /// Returns `true` if `self` contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at `index`, or `None` if it is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388767-cfarraygetvalueatindex).
+    #[inline]
+    #[doc(alias = "CFArrayGetValueAtIndex")]
+    pub fn get(&self, index: CFIndex) -> Option<&T> {
+        if 0 <= index && index < self.len() {
+            unsafe { Some(&*CFArrayGetValueAtIndex(&self.0, index).cast()) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the values in `self`.
+    ///
+    /// Values are retrieved in chunks using
+    /// [`CFArrayGetValues`](https://developer.apple.com/documentation/corefoundation/1388744-cfarraygetvalues).
+    #[inline]
+    pub fn iter(&self) -> CFArrayIter<'_, T> {
+        CFArrayIter {
+            array: self,
+            next: 0,
+            end: self.len(),
+            buf: [ptr::null(); CHUNK_LEN],
+            buf_index: 0,
+            buf_len: 0,
+        }
+    }
+
+    /// Returns `self` as an array of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &CFArray<CFType> {
+        // SAFETY: All elements are `CFType` instances.
+        unsafe { &*(self as *const Self as *const CFArray<CFType>) }
+    }
+}
+
+impl CFArray<CFType> {
+    /// Casts `self` to an array of elements of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// All elements of `self` must be instances of `T`.
+    #[inline]
+    pub unsafe fn cast_unchecked<T: ObjectType>(&self) -> &CFArray<T> {
+        &*(self as *const Self as *const CFArray<T>)
+    }
+}
+
+// The number of values retrieved at once by `CFArrayIter`.
+const CHUNK_LEN: usize = 32;
+
+/// An iterator over the values of a [`CFArray`].
+///
+/// This is created by [`CFArray::iter`].
+pub struct CFArrayIter<'a, T: ObjectType> {
+    array: &'a CFArray<T>,
+    next: CFIndex,
+    end: CFIndex,
+    buf: [*const c_void; CHUNK_LEN],
+    buf_index: usize,
+    buf_len: usize,
+}
+
+impl<'a, T: ObjectType> Iterator for CFArrayIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.buf_index == self.buf_len {
+            if self.next >= self.end {
+                return None;
+            }
+
+            let len = cmp::min(CHUNK_LEN as CFIndex, self.end - self.next);
+            let range = CFRange::new(self.next, len);
+            unsafe { CFArrayGetValues(&self.array.0, range, self.buf.as_mut_ptr()) };
+
+            self.next += len;
+            self.buf_index = 0;
+            self.buf_len = len as usize;
+        }
+
+        let value = self.buf[self.buf_index];
+        self.buf_index += 1;
+
+        // SAFETY: The array retains its values for at least `'a`.
+        Some(unsafe { &*value.cast() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: ObjectType> ExactSizeIterator for CFArrayIter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        (self.end - self.next) as usize + (self.buf_len - self.buf_index)
+    }
+}
+
+subclass! {
+    /// A mutable array of objects of type `T`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created array via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutablearray?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutablearrayref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableArray<T>: CFArray<T>;
+}
+
+impl<T: ObjectType + fmt::Debug> fmt::Debug for CFMutableArray<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFArray<T>).fmt(f)
+    }
+}
+
+impl<T: ObjectType> CFMutableArray<T> {
+    /// Creates an empty mutable array.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty mutable array that can contain at most `capacity`
+    /// values, where 0 means it has no limit.
+    ///
+    /// This uses
+    /// [`kCFTypeArrayCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypearraycallbacks).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388751-cfarraycreatemutable).
+    #[inline]
+    #[doc(alias = "CFArrayCreateMutable")]
+    pub fn with_capacity(capacity: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFArrayCreateMutable(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+                call_backs: *const CFArrayCallBacks,
+            ) -> *const CFType;
+        }
+
+        unsafe {
+            let array = CFArrayCreateMutable(None, capacity, &kCFTypeArrayCallBacks);
+            assert!(!array.is_null(), "Could not create CFMutableArray");
+            Arc::from_raw(array.cast())
+        }
+    }
+
+    /// Appends `value` to the end of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388797-cfarrayappendvalue).
+    #[inline]
+    #[doc(alias = "CFArrayAppendValue")]
+    pub fn push(&mut self, value: &T) {
+        extern "C" {
+            fn CFArrayAppendValue(the_array: &CFType, value: *const c_void);
+        }
+        unsafe { CFArrayAppendValue(self, (value as *const T).cast()) }
+    }
+
+    /// Inserts `value` at `index`, shifting all values after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388783-cfarrayinsertvalueatindex).
+    #[inline]
+    #[doc(alias = "CFArrayInsertValueAtIndex")]
+    pub fn insert(&mut self, index: usize, value: &T) {
+        extern "C" {
+            fn CFArrayInsertValueAtIndex(the_array: &CFType, idx: CFIndex, value: *const c_void);
+        }
+
+        let len = self.len() as usize;
+        assert!(
+            index <= len,
+            "Insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        unsafe { CFArrayInsertValueAtIndex(self, index as CFIndex, (value as *const T).cast()) }
+    }
+
+    /// Removes and returns the value at `index`, shifting all values after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388808-cfarrayremovevalueatindex).
+    #[inline]
+    #[doc(alias = "CFArrayRemoveValueAtIndex")]
+    pub fn remove(&mut self, index: usize) -> Arc<T> {
+        extern "C" {
+            fn CFArrayRemoveValueAtIndex(the_array: &CFType, idx: CFIndex);
+        }
+
+        let value = Arc::retain(&self[index]);
+        unsafe { CFArrayRemoveValueAtIndex(self, index as CFIndex) };
+        value
+    }
+
+    /// Removes all values from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388780-cfarrayremoveallvalues).
+    #[inline]
+    #[doc(alias = "CFArrayRemoveAllValues")]
+    pub fn clear(&mut self) {
+        extern "C" {
+            fn CFArrayRemoveAllValues(the_array: &CFType);
+        }
+        unsafe { CFArrayRemoveAllValues(self) }
+    }
+
+    /// Sorts the values of `self` with `compare`.
+    ///
+    /// If `compare` panics, sorting stops early and the panic is resumed
+    /// after control returns from Core Foundation. The order of `self` is
+    /// then unspecified.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1388785-cfarraysortvalues).
+    #[doc(alias = "CFArraySortValues")]
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        type Comparator = unsafe extern "C" fn(
+            val1: *const c_void,
+            val2: *const c_void,
+            context: *mut c_void,
+        ) -> CFComparisonResult;
+
+        extern "C" {
+            fn CFArraySortValues(
+                the_array: &CFType,
+                range: CFRange,
+                comparator: Comparator,
+                context: *mut c_void,
+            );
+        }
+
+        struct Context<F> {
+            compare: F,
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        unsafe extern "C" fn comparator<T, F>(
+            val1: *const c_void,
+            val2: *const c_void,
+            context: *mut c_void,
+        ) -> CFComparisonResult
+        where
+            F: FnMut(&T, &T) -> Ordering,
+        {
+            let context = &mut *context.cast::<Context<F>>();
+
+            // Unwinding into Core Foundation is undefined behavior.
+            if context.panic.is_some() {
+                return CFComparisonResult::EqualTo;
+            }
+
+            let compare = &mut context.compare;
+            let (val1, val2) = (&*val1.cast::<T>(), &*val2.cast::<T>());
+
+            match panic::catch_unwind(AssertUnwindSafe(|| compare(val1, val2))) {
+                Ok(ordering) => ordering.into(),
+                Err(panic) => {
+                    context.panic = Some(panic);
+                    CFComparisonResult::EqualTo
+                }
+            }
+        }
+
+        let mut context = Context {
+            compare,
+            panic: None,
+        };
+
+        unsafe {
+            CFArraySortValues(
+                self,
+                CFRange::new(0, self.len()),
+                comparator::<T, F>,
+                (&mut context as *mut Context<F>).cast(),
+            );
+        }
+
+        if let Some(panic) = context.panic {
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFNumber, CFString};
+
+    #[test]
+    fn empty() {
+        let array = CFArray::<CFString>::from_slice(&[]);
+        assert_eq!(array.len(), 0);
+        assert!(array.is_empty());
+        assert!(array.get(0).is_none());
+        assert_eq!(array.iter().count(), 0);
+    }
+
+    #[test]
+    fn index() {
+        let a = CFString::from_str("a");
+        let b = CFString::from_str("b");
+        let array = CFArray::from_slice(&[&*a, &*b]);
+
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[0], *a);
+        assert_eq!(array[1], *b);
+        assert!(array.get(-1).is_none());
+        assert!(array.get(2).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let array = CFArray::<CFString>::from_slice(&[]);
+        let _ = &array[0];
+    }
+
+    #[test]
+    fn iter_chunked() {
+        let strings: Vec<Arc<CFString>> = (0..10_000)
+            .map(|i| CFString::from_str(&i.to_string()))
+            .collect();
+        let refs: Vec<&CFString> = strings.iter().map(|s| &**s).collect();
+
+        let array = CFArray::from_slice(&refs);
+        assert_eq!(array.len(), 10_000);
+
+        let mut iter = array.iter();
+        assert_eq!(iter.len(), 10_000);
+
+        for (i, value) in (&mut iter).enumerate() {
+            assert_eq!(value.to_string(), i.to_string());
+        }
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn untyped_downcast() {
+        let string = CFString::from_str("string");
+        let array = CFArray::from_slice(&[&*string]);
+
+        let untyped: Arc<CFType> = CFType::retain(&array);
+        let untyped = untyped.downcast::<CFArray<CFType>>().unwrap();
+
+        let value = untyped[0].downcast_ref::<CFString>().unwrap();
+        assert_eq!(*value, *"string");
+    }
+
+    #[test]
+    fn mutate() {
+        let strings: Vec<Arc<CFString>> =
+            (0..5).map(|i| CFString::from_str(&i.to_string())).collect();
+
+        let mut array = CFMutableArray::<CFString>::new();
+        let a = Arc::get_mut(&mut array).unwrap();
+
+        for s in &strings {
+            a.push(s);
+        }
+        assert_eq!(a.len(), 5);
+
+        // Removing shifts subsequent values down.
+        let removed = a.remove(1);
+        assert_eq!(*removed, *"1");
+        assert_eq!(a.len(), 4);
+        assert_eq!(a[1], *"2");
+        assert_eq!(a[3], *"4");
+
+        a.insert(0, &strings[1]);
+        assert_eq!(a[0], *"1");
+        assert_eq!(a[1], *"0");
+
+        a.insert(5, &strings[0]);
+        assert_eq!(a[5], *"0");
+
+        a.clear();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn sort_by() {
+        let numbers: Vec<Arc<CFNumber>> = [3, -7, 0, 3, 12, -7, -1]
+            .iter()
+            .map(|&n| CFNumber::from_i64(n))
+            .collect();
+
+        let mut array = CFMutableArray::<CFNumber>::new();
+        let a = Arc::get_mut(&mut array).unwrap();
+        for n in &numbers {
+            a.push(n);
+        }
+
+        a.sort_by(|x, y| x.compare(y).into());
+
+        let sorted: Vec<i64> = a.iter().map(|n| n.to_i64().unwrap()).collect();
+        assert_eq!(sorted, [-7, -7, -1, 0, 3, 3, 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "comparator")]
+    fn sort_by_panic() {
+        let a = CFString::from_str("a");
+        let b = CFString::from_str("b");
+
+        let mut array = CFMutableArray::<CFString>::new();
+        let m = Arc::get_mut(&mut array).unwrap();
+        m.push(&a);
+        m.push(&b);
+
+        m.sort_by(|_, _| panic!("comparator"));
+    }
+}
@@ -0,0 +1,396 @@
+use super::{Boolean, CFAllocator, CFDictionary, CFIndex, CFRange, CFString, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// An immutable string with attributes associated with ranges of its
+    /// characters.
+    ///
+    /// Attributes are stored as dictionaries keyed by attribute name, and each
+    /// index of the string belongs to a run of characters sharing the same
+    /// attributes.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfattributedstring?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfattributedstringref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFAttributedString: CFType;
+    type_id = CFAttributedStringGetTypeID;
+}
+
+impl fmt::Debug for CFAttributedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CFAttributedString")
+            .field(&self.string())
+            .finish()
+    }
+}
+
+/// Asserts that `index` is a UTF-16 code unit index within `len`.
+#[inline]
+fn check_index(index: CFIndex, len: CFIndex) {
+    assert!(
+        0 <= index && index < len,
+        "Index {} is out of bounds for length {}",
+        index,
+        len
+    );
+}
+
+impl CFAttributedString {
+    /// Creates an attributed string from `string` with `attributes` applied to
+    /// all of its characters.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringcreate(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringCreate")]
+    pub fn new(string: &CFString, attributes: &CFDictionary<CFString, CFType>) -> Arc<Self> {
+        extern "C" {
+            fn CFAttributedStringCreate(
+                alloc: Option<&CFAllocator>,
+                str: &CFString,
+                attributes: &CFDictionary<CFString, CFType>,
+            ) -> Option<Arc<CFAttributedString>>;
+        }
+
+        unsafe { CFAttributedStringCreate(None, string, attributes) }
+            .expect("Could not create CFAttributedString")
+    }
+
+    /// Returns the characters of `self` without attributes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringgetstring(_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringGetString")]
+    pub fn string(&self) -> &CFString {
+        extern "C" {
+            fn CFAttributedStringGetString(a_str: &CFAttributedString) -> &CFString;
+        }
+        unsafe { CFAttributedStringGetString(self) }
+    }
+
+    /// Returns the number of UTF-16 code units in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringgetlength(_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringGetLength")]
+    #[doc(alias = "length")]
+    pub fn len(&self) -> CFIndex {
+        extern "C" {
+            fn CFAttributedStringGetLength(a_str: &CFAttributedString) -> CFIndex;
+        }
+        unsafe { CFAttributedStringGetLength(self) }
+    }
+
+    /// Returns `true` if `self` contains no characters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the attributes of the character at the UTF-16 code unit index
+    /// `index`, along with the range of the run sharing those attributes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringgetattributes(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringGetAttributes")]
+    pub fn attributes_at(&self, index: CFIndex) -> (Arc<CFDictionary<CFString, CFType>>, CFRange) {
+        extern "C" {
+            fn CFAttributedStringGetAttributes(
+                a_str: &CFAttributedString,
+                loc: CFIndex,
+                effective_range: *mut CFRange,
+            ) -> &CFDictionary<CFString, CFType>;
+        }
+
+        check_index(index, self.len());
+
+        let mut range = CFRange::default();
+        let attributes = unsafe { CFAttributedStringGetAttributes(self, index, &mut range) };
+        (Arc::retain(attributes), range)
+    }
+
+    /// Returns the value of the attribute `name` of the character at the
+    /// UTF-16 code unit index `index`, along with the range of the run sharing
+    /// that value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringgetattribute(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringGetAttribute")]
+    pub fn attribute_at(&self, index: CFIndex, name: &CFString) -> (Option<Arc<CFType>>, CFRange) {
+        extern "C" {
+            fn CFAttributedStringGetAttribute<'a>(
+                a_str: &'a CFAttributedString,
+                loc: CFIndex,
+                attr_name: &CFString,
+                effective_range: *mut CFRange,
+            ) -> Option<&'a CFType>;
+        }
+
+        check_index(index, self.len());
+
+        let mut range = CFRange::default();
+        let value = unsafe { CFAttributedStringGetAttribute(self, index, name, &mut range) };
+        (value.map(Arc::retain), range)
+    }
+}
+
+subclass! {
+    /// A mutable string with attributes associated with ranges of its
+    /// characters.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created string via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutableattributedstring?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutableattributedstringref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableAttributedString: CFAttributedString;
+}
+
+impl fmt::Debug for CFMutableAttributedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFAttributedString).fmt(f)
+    }
+}
+
+impl CFMutableAttributedString {
+    /// Creates an empty mutable attributed string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringcreatemutable(_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringCreateMutable")]
+    pub fn new() -> Arc<Self> {
+        extern "C" {
+            fn CFAttributedStringCreateMutable(
+                alloc: Option<&CFAllocator>,
+                max_length: CFIndex,
+            ) -> Option<Arc<CFMutableAttributedString>>;
+        }
+
+        unsafe { CFAttributedStringCreateMutable(None, 0) }
+            .expect("Could not create CFMutableAttributedString")
+    }
+
+    /// Creates a mutable attributed string from copying the characters and
+    /// attributes of `string`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringcreatemutablecopy(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringCreateMutableCopy")]
+    pub fn from_attributed(string: &CFAttributedString) -> Arc<Self> {
+        extern "C" {
+            fn CFAttributedStringCreateMutableCopy(
+                alloc: Option<&CFAllocator>,
+                max_length: CFIndex,
+                a_str: &CFAttributedString,
+            ) -> Option<Arc<CFMutableAttributedString>>;
+        }
+
+        unsafe { CFAttributedStringCreateMutableCopy(None, 0, string) }
+            .expect("Could not create CFMutableAttributedString")
+    }
+
+    /// Replaces the UTF-16 code units of `self` in `range` with `replacement`.
+    ///
+    /// The new characters take on the attributes of the first replaced
+    /// character, or of the preceding character if `range` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringreplacestring(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringReplaceString")]
+    pub fn replace_string(&mut self, range: impl Into<CFRange>, replacement: &CFString) {
+        extern "C" {
+            fn CFAttributedStringReplaceString(
+                a_str: &mut CFMutableAttributedString,
+                range: CFRange,
+                replacement: &CFString,
+            );
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+
+        unsafe { CFAttributedStringReplaceString(self, range, replacement) }
+    }
+
+    /// Sets the attribute `name` to `value` for the characters in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringsetattribute(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringSetAttribute")]
+    pub fn set_attribute(&mut self, range: impl Into<CFRange>, name: &CFString, value: &CFType) {
+        extern "C" {
+            fn CFAttributedStringSetAttribute(
+                a_str: &mut CFMutableAttributedString,
+                range: CFRange,
+                attr_name: &CFString,
+                value: &CFType,
+            );
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+
+        unsafe { CFAttributedStringSetAttribute(self, range, name, value) }
+    }
+
+    /// Sets `attributes` for the characters in `range`.
+    ///
+    /// If `clear_other` is `true`, attributes not in `attributes` are removed
+    /// from the range. Otherwise, they are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringsetattributes(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringSetAttributes")]
+    pub fn set_attributes(
+        &mut self,
+        range: impl Into<CFRange>,
+        attributes: &CFDictionary<CFString, CFType>,
+        clear_other: bool,
+    ) {
+        extern "C" {
+            fn CFAttributedStringSetAttributes(
+                a_str: &mut CFMutableAttributedString,
+                range: CFRange,
+                replacement: &CFDictionary<CFString, CFType>,
+                clear_other_attributes: Boolean,
+            );
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+
+        unsafe { CFAttributedStringSetAttributes(self, range, attributes, clear_other as Boolean) }
+    }
+
+    /// Removes the attribute `name` from the characters in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfattributedstringremoveattribute(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFAttributedStringRemoveAttribute")]
+    pub fn remove_attribute(&mut self, range: impl Into<CFRange>, name: &CFString) {
+        extern "C" {
+            fn CFAttributedStringRemoveAttribute(
+                a_str: &mut CFMutableAttributedString,
+                range: CFRange,
+                attr_name: &CFString,
+            );
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+
+        unsafe { CFAttributedStringRemoveAttribute(self, range, name) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFNumber;
+
+    #[test]
+    fn new() {
+        let name = CFString::from_str("name");
+        let value = CFString::from_str("value");
+        let attributes = CFDictionary::from_pairs(&[(&*name, &**value)]);
+        let string = CFAttributedString::new(&CFString::from_str("hello"), &attributes);
+
+        assert_eq!(string.len(), 5);
+        assert_eq!(*string.string(), *"hello");
+
+        let (found, range) = string.attributes_at(4);
+        assert_eq!(range, CFRange::new(0, 5));
+        assert_eq!(*found.get(&name).unwrap(), **value);
+    }
+
+    #[test]
+    fn attribute_runs() {
+        let name = CFString::from_str("weight");
+        let (light, bold) = (CFNumber::from_i32(300), CFNumber::from_i32(700));
+
+        let mut string = CFMutableAttributedString::new();
+        let s = Arc::get_mut(&mut string).unwrap();
+        s.replace_string(0..0usize, &CFString::from_str("hello world"));
+        s.set_attribute(0..5usize, &name, &light);
+        s.set_attribute(CFRange::new(5, 6), &name, &bold);
+
+        for &(index, location, length, weight) in &[
+            (0, 0, 5, &light),
+            (4, 0, 5, &light),
+            (5, 5, 6, &bold),
+            (10, 5, 6, &bold),
+        ] {
+            let (attributes, range) = s.attributes_at(index);
+            assert_eq!(range, CFRange::new(location, length), "index {}", index);
+            assert_eq!(*attributes.get(&name).unwrap(), ***weight);
+        }
+
+        // Clearing other attributes removes "weight" from the second run.
+        let other = CFString::from_str("other");
+        let attributes = CFDictionary::from_pairs(&[(&*other, &**other)]);
+        s.set_attributes(5..11usize, &attributes, true);
+        assert!(s.attribute_at(5, &name).0.is_none());
+        assert_eq!(s.attribute_at(4, &name).1, CFRange::new(0, 5));
+
+        // Keeping other attributes adds "other" to the first run.
+        s.set_attributes(0..5usize, &attributes, false);
+        let (found, range) = s.attributes_at(0);
+        assert_eq!(range, CFRange::new(0, 5));
+        assert_eq!(found.len(), 2);
+
+        s.remove_attribute(0..11usize, &other);
+        assert_eq!(s.attributes_at(10).0.len(), 0);
+    }
+
+    #[test]
+    fn replace_string() {
+        let name = CFString::from_str("name");
+        let attributes = CFDictionary::from_pairs(&[(&*name, &**name)]);
+        let string = CFAttributedString::new(&CFString::from_str("hello"), &attributes);
+
+        let mut copy = CFMutableAttributedString::from_attributed(&string);
+        let s = Arc::get_mut(&mut copy).unwrap();
+        s.replace_string(1..5usize, &CFString::from_str("owdy"));
+
+        assert_eq!(*s.string(), *"howdy");
+        assert_eq!(s.attributes_at(4).1, CFRange::new(0, 5));
+        assert_eq!(*string.string(), *"hello");
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let mut string = CFMutableAttributedString::new();
+        let s = Arc::get_mut(&mut string).unwrap();
+        s.replace_string(0..1usize, &CFString::from_str("x"));
+    }
+}
@@ -0,0 +1,358 @@
+use super::{sys, Boolean, CFAllocator, CFComparisonResult, CFIndex, CFString, CFType};
+use crate::_priv::abort_on_panic;
+use crate::core::{Arc, ObjectType};
+use std::{cmp::Ordering, ffi::c_void, fmt, marker::PhantomData, mem, ptr, vec};
+
+subclass! {
+    /// A priority queue of objects of type `T`, ordered from the minimum
+    /// value.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created heap via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfbinaryheap?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfbinaryheapref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFBinaryHeap<T>: CFType;
+}
+
+// Only the untyped form can be downcast to because values are not checked.
+cf_type_object!(CFBinaryHeap<CFType>, CFBinaryHeapGetTypeID);
+
+#[repr(C)]
+struct CFBinaryHeapCallBacks {
+    version: CFIndex,
+    retain: Option<unsafe extern "C" fn(Option<&CFAllocator>, *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(Option<&CFAllocator>, *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(*const c_void) -> *const CFString>,
+    compare: Option<
+        unsafe extern "C" fn(*const c_void, *const c_void, *mut c_void) -> CFComparisonResult,
+    >,
+}
+
+#[repr(C)]
+struct CFBinaryHeapCompareContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+}
+
+extern "C" {
+    fn CFBinaryHeapGetCount(heap: &CFType) -> CFIndex;
+    fn CFBinaryHeapGetMinimumIfPresent(heap: &CFType, value: *mut *const c_void) -> Boolean;
+    fn CFBinaryHeapContainsValue(heap: &CFType, value: *const c_void) -> Boolean;
+    fn CFBinaryHeapGetValues(heap: &CFType, values: *mut *const c_void);
+}
+
+/// Value callbacks for objects compared by a context info of type
+/// `fn(&T, &T) -> Ordering`.
+mod callbacks {
+    use super::*;
+
+    pub unsafe extern "C" fn retain(
+        _allocator: Option<&CFAllocator>,
+        value: *const c_void,
+    ) -> *const c_void {
+        sys::CFRetain(value.cast()).cast()
+    }
+
+    pub unsafe extern "C" fn release(_allocator: Option<&CFAllocator>, value: *const c_void) {
+        sys::CFRelease(value.cast())
+    }
+
+    pub unsafe extern "C" fn copy_description(value: *const c_void) -> *const CFString {
+        sys::CFCopyDescription(value.cast())
+    }
+
+    pub unsafe extern "C" fn compare<T>(
+        a: *const c_void,
+        b: *const c_void,
+        info: *mut c_void,
+    ) -> CFComparisonResult {
+        let compare = mem::transmute::<*mut c_void, fn(&T, &T) -> Ordering>(info);
+        let (a, b) = (&*a.cast::<T>(), &*b.cast::<T>());
+
+        // The comparator is arbitrary Rust code.
+        abort_on_panic(|| compare(a, b)).into()
+    }
+}
+
+impl<T: ObjectType + fmt::Debug> fmt::Debug for CFBinaryHeap<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: ObjectType> IntoIterator for &'a CFBinaryHeap<T> {
+    type Item = &'a T;
+    type IntoIter = CFBinaryHeapIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType> CFBinaryHeap<T> {
+    /// Creates an empty heap that retains its values and orders them by
+    /// `compare`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapcreate(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapCreate")]
+    pub fn new(compare: fn(&T, &T) -> Ordering) -> Arc<Self> {
+        extern "C" {
+            fn CFBinaryHeapCreate(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+                call_backs: *const CFBinaryHeapCallBacks,
+                compare_context: *const CFBinaryHeapCompareContext,
+            ) -> *const CFType;
+        }
+
+        let call_backs = CFBinaryHeapCallBacks {
+            version: 0,
+            retain: Some(callbacks::retain),
+            release: Some(callbacks::release),
+            copy_description: Some(callbacks::copy_description),
+            compare: Some(callbacks::compare::<T>),
+        };
+
+        // A function pointer needs no memory management.
+        let context = CFBinaryHeapCompareContext {
+            version: 0,
+            info: compare as *mut c_void,
+            retain: None,
+            release: None,
+            copy_description: None,
+        };
+
+        unsafe {
+            let heap = CFBinaryHeapCreate(None, 0, &call_backs, &context);
+            assert!(!heap.is_null(), "Could not create CFBinaryHeap");
+            Arc::from_raw(heap.cast())
+        }
+    }
+
+    /// Returns the number of values in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapgetcount(_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapGetCount")]
+    pub fn len(&self) -> CFIndex {
+        unsafe { CFBinaryHeapGetCount(self) }
+    }
+
+    /// Returns `true` if `self` contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the minimum value, or `None` if `self` is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapgetminimumifpresent(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapGetMinimumIfPresent")]
+    pub fn peek(&self) -> Option<&T> {
+        let mut value = ptr::null();
+        unsafe {
+            if CFBinaryHeapGetMinimumIfPresent(self, &mut value) != 0 {
+                value.cast::<T>().as_ref()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if `self` contains a value that compares equal to
+    /// `value`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapcontainsvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapContainsValue")]
+    pub fn contains(&self, value: &T) -> bool {
+        unsafe { CFBinaryHeapContainsValue(self, (value as *const T).cast()) != 0 }
+    }
+
+    /// Returns an iterator over the values in `self`, from the minimum value.
+    ///
+    /// All values are retrieved at once using
+    /// [`CFBinaryHeapGetValues`](https://developer.apple.com/documentation/corefoundation/cfbinaryheapgetvalues(_:_:)).
+    #[doc(alias = "CFBinaryHeapGetValues")]
+    pub fn iter(&self) -> CFBinaryHeapIter<'_, T> {
+        let len = self.len() as usize;
+        let mut values = vec![ptr::null(); len];
+
+        if len != 0 {
+            unsafe { CFBinaryHeapGetValues(self, values.as_mut_ptr()) };
+        }
+
+        CFBinaryHeapIter {
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Adds `value` to `self`, retaining it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapaddvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapAddValue")]
+    pub fn push(&mut self, value: &T) {
+        extern "C" {
+            fn CFBinaryHeapAddValue(heap: &CFType, value: *const c_void);
+        }
+        unsafe { CFBinaryHeapAddValue(self, (value as *const T).cast()) }
+    }
+
+    /// Removes the minimum value, returning it if `self` was not empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapremoveminimumvalue(_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapRemoveMinimumValue")]
+    pub fn pop(&mut self) -> Option<Arc<T>> {
+        extern "C" {
+            fn CFBinaryHeapRemoveMinimumValue(heap: &CFType);
+        }
+
+        let value = Arc::retain(self.peek()?);
+        unsafe { CFBinaryHeapRemoveMinimumValue(self) };
+        Some(value)
+    }
+
+    /// Removes all values from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbinaryheapremoveallvalues(_:)).
+    #[inline]
+    #[doc(alias = "CFBinaryHeapRemoveAllValues")]
+    pub fn clear(&mut self) {
+        extern "C" {
+            fn CFBinaryHeapRemoveAllValues(heap: &CFType);
+        }
+        unsafe { CFBinaryHeapRemoveAllValues(self) }
+    }
+
+    /// Returns `self` as a heap of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &CFBinaryHeap<CFType> {
+        // SAFETY: All values are `CFType` instances.
+        unsafe { &*(self as *const Self as *const CFBinaryHeap<CFType>) }
+    }
+}
+
+impl CFBinaryHeap<CFType> {
+    /// Casts `self` to a heap of values of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// All values of `self` must be instances of `T`, and its comparator must
+    /// accept them. No values of other types may be pushed to the untyped
+    /// heap afterward.
+    #[inline]
+    pub unsafe fn cast_unchecked<T: ObjectType>(&self) -> &CFBinaryHeap<T> {
+        &*(self as *const Self as *const CFBinaryHeap<T>)
+    }
+}
+
+/// An iterator over the values of a [`CFBinaryHeap`], from the minimum value.
+///
+/// This is created by [`CFBinaryHeap::iter`].
+pub struct CFBinaryHeapIter<'a, T> {
+    values: vec::IntoIter<*const c_void>,
+    marker: PhantomData<&'a CFBinaryHeap<T>>,
+}
+
+impl<'a, T: ObjectType> Iterator for CFBinaryHeapIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let value = self.values.next()?;
+
+        // SAFETY: The heap retains its values for at least `'a`.
+        unsafe { Some(&*value.cast()) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T: ObjectType> ExactSizeIterator for CFBinaryHeapIter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFNumber;
+
+    fn compare(a: &CFNumber, b: &CFNumber) -> Ordering {
+        a.compare(b).into_ordering()
+    }
+
+    #[test]
+    fn push_pop_sorted() {
+        let values = [42, 7, 19, -3, 100, 7, 0, 58];
+        let numbers: Vec<Arc<CFNumber>> = values.iter().map(|&v| CFNumber::from_i32(v)).collect();
+
+        let mut heap = CFBinaryHeap::new(compare);
+        let h = Arc::get_mut(&mut heap).unwrap();
+        for number in &numbers {
+            h.push(number);
+        }
+
+        assert_eq!(h.len(), values.len() as CFIndex);
+        assert_eq!(h.peek().unwrap().to_i32(), Some(-3));
+        assert!(h.contains(&CFNumber::from_i32(19)));
+        assert!(!h.contains(&CFNumber::from_i32(20)));
+
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+
+        let iterated: Vec<i32> = h.iter().map(|n| n.to_i32().unwrap()).collect();
+        assert_eq!(iterated, sorted);
+
+        let mut popped = Vec::new();
+        while let Some(number) = h.pop() {
+            popped.push(number.to_i32().unwrap());
+        }
+        assert_eq!(popped, sorted);
+        assert!(h.is_empty());
+        assert!(h.peek().is_none());
+    }
+
+    #[test]
+    fn reversed() {
+        let mut heap = CFBinaryHeap::new(|a: &CFNumber, b: &CFNumber| compare(b, a));
+        let h = Arc::get_mut(&mut heap).unwrap();
+        for v in 0..10 {
+            h.push(&CFNumber::from_i32(v));
+        }
+
+        assert_eq!(h.pop().unwrap().to_i32(), Some(9));
+        assert_eq!(h.peek().unwrap().to_i32(), Some(8));
+
+        h.clear();
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn untyped_downcast() {
+        let mut heap = CFBinaryHeap::new(compare);
+        Arc::get_mut(&mut heap)
+            .unwrap()
+            .push(&CFNumber::from_i32(1));
+
+        let untyped: Arc<CFType> = CFType::retain(&heap);
+        let untyped = untyped.downcast::<CFBinaryHeap<CFType>>().unwrap();
+        assert_eq!(untyped.len(), 1);
+
+        let typed = unsafe { untyped.cast_unchecked::<CFNumber>() };
+        assert_eq!(typed.peek().unwrap().to_i32(), Some(1));
+    }
+}
@@ -0,0 +1,439 @@
+use super::{CFAllocator, CFIndex, CFRange, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+type CFBit = u32;
+
+cf_subclass! {
+    /// An immutable, fixed-size array of bits.
+    ///
+    /// Bits are numbered from the most significant bit of the first byte, so
+    /// bit 0 of `0b1000_0000` is set.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfbitvector?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfbitvectorref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFBitVector: CFType;
+    type_id = CFBitVectorGetTypeID;
+}
+
+impl fmt::Debug for CFBitVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bits: String = (0..self.len())
+            .map(|i| if self.get(i) { '1' } else { '0' })
+            .collect();
+        f.debug_tuple("CFBitVector").field(&bits).finish()
+    }
+}
+
+/// Asserts that `index` is a bit index within `len`.
+#[inline]
+fn check_index(index: CFIndex, len: CFIndex) {
+    assert!(
+        0 <= index && index < len,
+        "Index {} is out of bounds for length {}",
+        index,
+        len
+    );
+}
+
+impl CFBitVector {
+    /// Creates a bit vector from the first `num_bits` bits of `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` contains fewer than `num_bits` bits.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorcreate(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorCreate")]
+    pub fn from_bytes(bytes: &[u8], num_bits: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFBitVectorCreate(
+                allocator: Option<&CFAllocator>,
+                bytes: *const u8,
+                num_bits: CFIndex,
+            ) -> Option<Arc<CFBitVector>>;
+        }
+
+        let available = bytes.len() as CFIndex * 8;
+        assert!(
+            0 <= num_bits && num_bits <= available,
+            "Bit count {} is out of bounds for {} bits",
+            num_bits,
+            available
+        );
+
+        unsafe { CFBitVectorCreate(None, bytes.as_ptr(), num_bits) }
+            .expect("Could not create CFBitVector")
+    }
+
+    /// Returns the number of bits in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetcount(_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetCount")]
+    pub fn len(&self) -> CFIndex {
+        extern "C" {
+            fn CFBitVectorGetCount(bv: &CFBitVector) -> CFIndex;
+        }
+        unsafe { CFBitVectorGetCount(self) }
+    }
+
+    /// Returns `true` if `self` contains no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the bit at `index` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetbitatindex(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetBitAtIndex")]
+    pub fn get(&self, index: CFIndex) -> bool {
+        extern "C" {
+            fn CFBitVectorGetBitAtIndex(bv: &CFBitVector, idx: CFIndex) -> CFBit;
+        }
+
+        check_index(index, self.len());
+        unsafe { CFBitVectorGetBitAtIndex(self, index) != 0 }
+    }
+
+    /// Returns the number of set bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetcountofbit(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetCountOfBit")]
+    pub fn count_ones_in(&self, range: impl Into<CFRange>) -> CFIndex {
+        extern "C" {
+            fn CFBitVectorGetCountOfBit(bv: &CFBitVector, range: CFRange, value: CFBit) -> CFIndex;
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+        unsafe { CFBitVectorGetCountOfBit(self, range, 1) }
+    }
+
+    /// Returns the index of the first set bit in `range`, or `None` if there
+    /// is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetfirstindexofbit(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetFirstIndexOfBit")]
+    pub fn first_set_bit_in(&self, range: impl Into<CFRange>) -> Option<CFIndex> {
+        extern "C" {
+            fn CFBitVectorGetFirstIndexOfBit(
+                bv: &CFBitVector,
+                range: CFRange,
+                value: CFBit,
+            ) -> CFIndex;
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+        match unsafe { CFBitVectorGetFirstIndexOfBit(self, range, 1) } {
+            -1 => None, // kCFNotFound
+            index => Some(index),
+        }
+    }
+
+    /// Returns the index of the last set bit in `range`, or `None` if there
+    /// is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetlastindexofbit(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetLastIndexOfBit")]
+    pub fn last_set_bit_in(&self, range: impl Into<CFRange>) -> Option<CFIndex> {
+        extern "C" {
+            fn CFBitVectorGetLastIndexOfBit(
+                bv: &CFBitVector,
+                range: CFRange,
+                value: CFBit,
+            ) -> CFIndex;
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+        match unsafe { CFBitVectorGetLastIndexOfBit(self, range, 1) } {
+            -1 => None, // kCFNotFound
+            index => Some(index),
+        }
+    }
+
+    /// Copies the bits in `range` into `buffer`, starting from the most
+    /// significant bit of its first byte.
+    ///
+    /// Bits of the last written byte past the end of `range` are unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or `buffer` is too small to hold
+    /// its bits.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorgetbits(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorGetBits")]
+    pub fn get_bits(&self, range: impl Into<CFRange>, buffer: &mut [u8]) {
+        extern "C" {
+            fn CFBitVectorGetBits(bv: &CFBitVector, range: CFRange, bytes: *mut u8);
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+
+        let needed = (range.length as usize).div_ceil(8);
+        assert!(
+            buffer.len() >= needed,
+            "Buffer of {} bytes is too small for {} bits",
+            buffer.len(),
+            range.length
+        );
+
+        unsafe { CFBitVectorGetBits(self, range, buffer.as_mut_ptr()) }
+    }
+}
+
+subclass! {
+    /// A mutable, growable array of bits.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created bit vector via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutablebitvector?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutablebitvectorref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableBitVector: CFBitVector;
+}
+
+impl fmt::Debug for CFMutableBitVector {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFBitVector).fmt(f)
+    }
+}
+
+impl CFMutableBitVector {
+    /// Creates an empty mutable bit vector.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorcreatemutable(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorCreateMutable")]
+    pub fn new() -> Arc<Self> {
+        extern "C" {
+            fn CFBitVectorCreateMutable(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+            ) -> Option<Arc<CFMutableBitVector>>;
+        }
+
+        unsafe { CFBitVectorCreateMutable(None, 0) }.expect("Could not create CFMutableBitVector")
+    }
+
+    /// Creates a mutable bit vector from copying the bits of `bv`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorcreatemutablecopy(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorCreateMutableCopy")]
+    pub fn from_bit_vector(bv: &CFBitVector) -> Arc<Self> {
+        extern "C" {
+            fn CFBitVectorCreateMutableCopy(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+                bv: &CFBitVector,
+            ) -> Option<Arc<CFMutableBitVector>>;
+        }
+
+        unsafe { CFBitVectorCreateMutableCopy(None, 0, bv) }
+            .expect("Could not create CFMutableBitVector")
+    }
+
+    /// Sets the number of bits in `self` to `count`, where new bits are unset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is negative.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorsetcount(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorSetCount")]
+    pub fn set_count(&mut self, count: CFIndex) {
+        extern "C" {
+            fn CFBitVectorSetCount(bv: &mut CFMutableBitVector, count: CFIndex);
+        }
+
+        assert!(count >= 0, "Bit count {} is negative", count);
+        unsafe { CFBitVectorSetCount(self, count) }
+    }
+
+    /// Sets the bit at `index` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorsetbitatindex(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorSetBitAtIndex")]
+    pub fn set(&mut self, index: CFIndex, value: bool) {
+        extern "C" {
+            fn CFBitVectorSetBitAtIndex(bv: &mut CFMutableBitVector, idx: CFIndex, value: CFBit);
+        }
+
+        check_index(index, self.len());
+        unsafe { CFBitVectorSetBitAtIndex(self, index, value as CFBit) }
+    }
+
+    /// Sets all bits in `range` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorsetbits(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorSetBits")]
+    pub fn set_range(&mut self, range: impl Into<CFRange>, value: bool) {
+        extern "C" {
+            fn CFBitVectorSetBits(bv: &mut CFMutableBitVector, range: CFRange, value: CFBit);
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+        unsafe { CFBitVectorSetBits(self, range, value as CFBit) }
+    }
+
+    /// Flips the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorflipbitatindex(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorFlipBitAtIndex")]
+    pub fn flip(&mut self, index: CFIndex) {
+        extern "C" {
+            fn CFBitVectorFlipBitAtIndex(bv: &mut CFMutableBitVector, idx: CFIndex);
+        }
+
+        check_index(index, self.len());
+        unsafe { CFBitVectorFlipBitAtIndex(self, index) }
+    }
+
+    /// Flips all bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbitvectorflipbits(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBitVectorFlipBits")]
+    pub fn flip_range(&mut self, range: impl Into<CFRange>) {
+        extern "C" {
+            fn CFBitVectorFlipBits(bv: &mut CFMutableBitVector, range: CFRange);
+        }
+
+        let range = range.into();
+        range.assert_within(self.len());
+        unsafe { CFBitVectorFlipBits(self, range) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; 2] = [0b1010_1100, 0b0101_0011];
+
+    #[test]
+    fn from_bytes() {
+        let bv = CFBitVector::from_bytes(&BYTES, 16);
+        assert_eq!(bv.len(), 16);
+        assert!(bv.get(0));
+        assert!(!bv.get(1));
+        assert!(bv.get(15));
+
+        assert_eq!(bv.count_ones_in(0..16usize), 8);
+        assert_eq!(bv.count_ones_in(CFRange::new(4, 4)), 2);
+        assert_eq!(bv.first_set_bit_in(1..16usize), Some(2));
+        assert_eq!(bv.first_set_bit_in(6..9usize), None);
+        assert_eq!(bv.last_set_bit_in(0..14usize), Some(11));
+
+        // Only the leading bits are used.
+        let short = CFBitVector::from_bytes(&BYTES, 3);
+        assert_eq!(short.len(), 3);
+        assert_eq!(short.count_ones_in(0..3usize), 2);
+    }
+
+    #[test]
+    fn get_bits_unaligned() {
+        let bv = CFBitVector::from_bytes(&BYTES, 16);
+
+        // Bits 3 through 11 are 0110_0010 1.
+        let mut buffer = [0; 2];
+        bv.get_bits(3..12usize, &mut buffer);
+        assert_eq!(buffer[0], 0b0110_0010);
+        assert_eq!(buffer[1] & 0b1000_0000, 0b1000_0000);
+
+        let mut buffer = [0; 1];
+        bv.get_bits(9..16usize, &mut buffer);
+        assert_eq!(buffer[0] & 0b1111_1110, 0b1010_0110);
+    }
+
+    #[test]
+    fn mutate() {
+        let mut bv = CFMutableBitVector::new();
+        let b = Arc::get_mut(&mut bv).unwrap();
+
+        b.set_count(10);
+        assert_eq!(b.count_ones_in(0..10usize), 0);
+
+        b.set(3, true);
+        b.flip_range(0..4usize);
+        assert_eq!(b.first_set_bit_in(0..10usize), Some(0));
+        assert!(!b.get(3));
+        assert_eq!(b.count_ones_in(0..10usize), 3);
+
+        b.flip(9);
+        b.set_range(4..6usize, true);
+        assert_eq!(b.count_ones_in(0..10usize), 6);
+
+        b.set_count(2);
+        assert_eq!(b.len(), 2);
+
+        let copy = CFMutableBitVector::from_bit_vector(b);
+        assert_eq!(*copy, *b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 16 is out of bounds for length 16")]
+    fn index_out_of_bounds() {
+        CFBitVector::from_bytes(&BYTES, 16).get(16);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for length 16")]
+    fn range_out_of_bounds() {
+        CFBitVector::from_bytes(&BYTES, 16).count_ones_in(8..17usize);
+    }
+}
@@ -0,0 +1,216 @@
+use super::{CFAllocator, CFDictionary, CFString, CFType, CFURL};
+use crate::core::Arc;
+use std::{ffi::c_void, fmt};
+
+cf_subclass! {
+    /// A bundle of code and resources on disk, such as an application,
+    /// framework, or plugin.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfbundle?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfbundleref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFBundle: CFType;
+    type_id = CFBundleGetTypeID;
+}
+
+impl fmt::Debug for CFBundle {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFBundle")
+            .field("identifier", &self.identifier())
+            .field("bundle_url", &self.bundle_url())
+            .finish()
+    }
+}
+
+/// Getting bundles.
+impl CFBundle {
+    /// Returns the bundle containing the current executable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlegetmainbundle()).
+    #[inline]
+    #[doc(alias = "CFBundleGetMainBundle")]
+    pub fn main() -> Option<&'static CFBundle> {
+        extern "C" {
+            fn CFBundleGetMainBundle() -> Option<&'static CFBundle>;
+        }
+        unsafe { CFBundleGetMainBundle() }
+    }
+
+    /// Returns the already-loaded bundle with `identifier`, such as
+    /// `com.apple.CoreFoundation`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlegetbundlewithidentifier(_:)).
+    #[inline]
+    #[doc(alias = "CFBundleGetBundleWithIdentifier")]
+    pub fn with_identifier(identifier: &CFString) -> Option<Arc<CFBundle>> {
+        extern "C" {
+            fn CFBundleGetBundleWithIdentifier(bundle_id: &CFString) -> Option<&CFBundle>;
+        }
+
+        // The result follows the get rule, so it must be retained.
+        unsafe { CFBundleGetBundleWithIdentifier(identifier) }.map(Arc::retain)
+    }
+
+    /// Creates a bundle for the directory at `url`.
+    ///
+    /// Returns `None` if `url` does not refer to a bundle.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlecreate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBundleCreate")]
+    pub fn with_url(url: &CFURL) -> Option<Arc<CFBundle>> {
+        extern "C" {
+            fn CFBundleCreate(
+                allocator: Option<&CFAllocator>,
+                bundle_url: &CFURL,
+            ) -> Option<Arc<CFBundle>>;
+        }
+        unsafe { CFBundleCreate(None, url) }
+    }
+}
+
+/// Getting bundle properties.
+impl CFBundle {
+    /// Returns the contents of the bundle's `Info.plist` file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlegetinfodictionary(_:)).
+    #[inline]
+    #[doc(alias = "CFBundleGetInfoDictionary")]
+    pub fn info_dictionary(&self) -> &CFDictionary<CFString, CFType> {
+        extern "C" {
+            fn CFBundleGetInfoDictionary(bundle: &CFBundle) -> &CFDictionary<CFString, CFType>;
+        }
+        unsafe { CFBundleGetInfoDictionary(self) }
+    }
+
+    /// Returns the `CFBundleIdentifier` of the bundle, if it has one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlegetidentifier(_:)).
+    #[inline]
+    #[doc(alias = "CFBundleGetIdentifier")]
+    pub fn identifier(&self) -> Option<&CFString> {
+        extern "C" {
+            fn CFBundleGetIdentifier(bundle: &CFBundle) -> Option<&CFString>;
+        }
+        unsafe { CFBundleGetIdentifier(self) }
+    }
+
+    /// Returns the location of the bundle.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlecopybundleurl(_:)).
+    #[inline]
+    #[doc(alias = "CFBundleCopyBundleURL")]
+    pub fn bundle_url(&self) -> Option<Arc<CFURL>> {
+        extern "C" {
+            fn CFBundleCopyBundleURL(bundle: &CFBundle) -> Option<Arc<CFURL>>;
+        }
+        unsafe { CFBundleCopyBundleURL(self) }
+    }
+
+    /// Returns the location of the bundle's executable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlecopyexecutableurl(_:)).
+    #[inline]
+    #[doc(alias = "CFBundleCopyExecutableURL")]
+    pub fn executable_url(&self) -> Option<Arc<CFURL>> {
+        extern "C" {
+            fn CFBundleCopyExecutableURL(bundle: &CFBundle) -> Option<Arc<CFURL>>;
+        }
+        unsafe { CFBundleCopyExecutableURL(self) }
+    }
+}
+
+/// Locating resources and code.
+impl CFBundle {
+    /// Returns the location of the resource named `name`, optionally with the
+    /// file extension `resource_type` and in the subdirectory `sub_dir`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlecopyresourceurl(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFBundleCopyResourceURL")]
+    pub fn resource_url(
+        &self,
+        name: &CFString,
+        resource_type: Option<&CFString>,
+        sub_dir: Option<&CFString>,
+    ) -> Option<Arc<CFURL>> {
+        extern "C" {
+            fn CFBundleCopyResourceURL(
+                bundle: &CFBundle,
+                resource_name: &CFString,
+                resource_type: Option<&CFString>,
+                sub_dir_name: Option<&CFString>,
+            ) -> Option<Arc<CFURL>>;
+        }
+        unsafe { CFBundleCopyResourceURL(self, name, resource_type, sub_dir) }
+    }
+
+    /// Returns a pointer to the function named `name` in the bundle's
+    /// executable, or null if it could not be found.
+    ///
+    /// The bundle's code is loaded if it is not already. The result must be
+    /// cast to the correct function type before being called.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbundlegetfunctionpointerforname(_:_:)).
+    #[inline]
+    #[doc(alias = "CFBundleGetFunctionPointerForName")]
+    pub fn function_pointer_for_name(&self, name: &CFString) -> *const c_void {
+        extern "C" {
+            fn CFBundleGetFunctionPointerForName(
+                bundle: &CFBundle,
+                function_name: &CFString,
+            ) -> *const c_void;
+        }
+        unsafe { CFBundleGetFunctionPointerForName(self, name) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFTypeID, CFTypeObject};
+
+    fn core_foundation() -> Arc<CFBundle> {
+        CFBundle::with_identifier(&CFString::from_str("com.apple.CoreFoundation")).unwrap()
+    }
+
+    #[test]
+    fn main_bundle() {
+        let main = CFBundle::main().unwrap();
+
+        let executable = main.executable_url().unwrap().to_path_buf().unwrap();
+        assert!(executable.exists());
+        assert!(main.bundle_url().is_some());
+    }
+
+    #[test]
+    fn framework() {
+        let bundle = core_foundation();
+
+        assert!(*bundle.identifier().unwrap() == "com.apple.CoreFoundation");
+        assert!(!bundle.info_dictionary().is_empty());
+
+        let url = bundle.bundle_url().unwrap();
+        assert_eq!(*CFBundle::with_url(&url).unwrap(), *bundle);
+
+        let missing = CFString::from_str("fruity-missing-resource");
+        assert!(bundle.resource_url(&missing, None, None).is_none());
+    }
+
+    #[test]
+    fn function_pointer() {
+        let bundle = core_foundation();
+
+        let name = CFString::from_str("CFBundleGetTypeID");
+        let ptr = bundle.function_pointer_for_name(&name);
+        assert!(!ptr.is_null());
+
+        let get_type_id: extern "C" fn() -> CFTypeID = unsafe { std::mem::transmute(ptr) };
+        assert_eq!(get_type_id(), CFBundle::type_id());
+
+        let missing = CFString::from_str("fruity_missing_function");
+        assert!(bundle.function_pointer_for_name(&missing).is_null());
+    }
+}
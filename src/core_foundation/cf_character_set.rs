@@ -0,0 +1,251 @@
+use super::{Boolean, CFAllocator, CFRange, CFString, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// An immutable set of Unicode characters.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfcharacterset?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfcharactersetref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFCharacterSet: CFType;
+    type_id = CFCharacterSetGetTypeID;
+}
+
+/// Character sets predefined by Core Foundation.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetpredefinedset).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFCharacterSetPredefinedSet {
+    /// Control characters (Unicode general categories Cc and Cf).
+    Control = 1,
+    /// Spaces and tabs.
+    Whitespace = 2,
+    /// Spaces, tabs, and newline characters.
+    WhitespaceAndNewline = 3,
+    /// Decimal digits (Unicode general category Nd).
+    DecimalDigit = 4,
+    /// Letters and ideographs (Unicode general categories L and M).
+    Letter = 5,
+    /// Lowercase letters (Unicode general category Ll).
+    LowercaseLetter = 6,
+    /// Uppercase letters (Unicode general categories Lu and Lt).
+    UppercaseLetter = 7,
+    /// Non-base characters (Unicode general category M).
+    NonBase = 8,
+    /// Composed characters that can be decomposed.
+    Decomposable = 9,
+    /// Letters and digits (Unicode general categories L, M, and N).
+    AlphaNumeric = 10,
+    /// Punctuation characters (Unicode general category P).
+    Punctuation = 11,
+    /// Illegal characters, which are not defined by Unicode.
+    Illegal = 12,
+    /// Titlecase letters (Unicode general category Lt).
+    CapitalizedLetter = 13,
+    /// Symbols (Unicode general category S).
+    Symbol = 14,
+    /// Newline characters.
+    Newline = 15,
+}
+
+impl fmt::Debug for CFCharacterSet {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFType).fmt(f)
+    }
+}
+
+/// Getting predefined character sets.
+impl CFCharacterSet {
+    /// Returns the predefined character set for `set`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetgetpredefined(_:)).
+    #[inline]
+    #[doc(alias = "CFCharacterSetGetPredefined")]
+    pub fn predefined(set: CFCharacterSetPredefinedSet) -> &'static CFCharacterSet {
+        extern "C" {
+            fn CFCharacterSetGetPredefined(
+                set_identifier: CFCharacterSetPredefinedSet,
+            ) -> &'static CFCharacterSet;
+        }
+        unsafe { CFCharacterSetGetPredefined(set) }
+    }
+
+    /// Returns the set of spaces and tabs.
+    #[inline]
+    pub fn whitespace() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::Whitespace)
+    }
+
+    /// Returns the set of spaces, tabs, and newline characters.
+    #[inline]
+    pub fn whitespace_and_newline() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::WhitespaceAndNewline)
+    }
+
+    /// Returns the set of letters and digits.
+    #[inline]
+    pub fn alphanumeric() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::AlphaNumeric)
+    }
+
+    /// Returns the set of decimal digits.
+    #[inline]
+    pub fn decimal_digit() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::DecimalDigit)
+    }
+
+    /// Returns the set of letters and ideographs.
+    #[inline]
+    pub fn letter() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::Letter)
+    }
+
+    /// Returns the set of punctuation characters.
+    #[inline]
+    pub fn punctuation() -> &'static CFCharacterSet {
+        Self::predefined(CFCharacterSetPredefinedSet::Punctuation)
+    }
+}
+
+/// Creating character sets.
+impl CFCharacterSet {
+    /// Creates a set containing the characters in `string`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetcreatewithcharactersinstring(_:_:)).
+    #[inline]
+    #[doc(alias = "CFCharacterSetCreateWithCharactersInString")]
+    pub fn from_string(string: &CFString) -> Arc<Self> {
+        extern "C" {
+            fn CFCharacterSetCreateWithCharactersInString(
+                alloc: Option<&CFAllocator>,
+                the_string: &CFString,
+            ) -> Option<Arc<CFCharacterSet>>;
+        }
+        unsafe { CFCharacterSetCreateWithCharactersInString(None, string) }
+            .expect("Could not create CFCharacterSet")
+    }
+
+    /// Creates a set containing the Unicode scalar values in `range`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetcreatewithcharactersinrange(_:_:)).
+    #[inline]
+    #[doc(alias = "CFCharacterSetCreateWithCharactersInRange")]
+    pub fn from_range(range: CFRange) -> Arc<Self> {
+        extern "C" {
+            fn CFCharacterSetCreateWithCharactersInRange(
+                alloc: Option<&CFAllocator>,
+                the_range: CFRange,
+            ) -> Option<Arc<CFCharacterSet>>;
+        }
+        unsafe { CFCharacterSetCreateWithCharactersInRange(None, range) }
+            .expect("Could not create CFCharacterSet")
+    }
+
+    /// Creates a set containing every character that is not in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetcreateinvertedset(_:_:)).
+    #[inline]
+    #[doc(alias = "CFCharacterSetCreateInvertedSet")]
+    pub fn inverted(&self) -> Arc<Self> {
+        extern "C" {
+            fn CFCharacterSetCreateInvertedSet(
+                alloc: Option<&CFAllocator>,
+                the_set: &CFCharacterSet,
+            ) -> Option<Arc<CFCharacterSet>>;
+        }
+        unsafe { CFCharacterSetCreateInvertedSet(None, self) }
+            .expect("Could not create CFCharacterSet")
+    }
+}
+
+/// Querying character sets.
+impl CFCharacterSet {
+    /// Returns `true` if `self` contains `c`.
+    ///
+    /// Unlike `CFCharacterSetIsCharacterMember`, this supports characters
+    /// outside of the Basic Multilingual Plane.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfcharactersetislongcharactermember(_:_:)).
+    #[inline]
+    #[doc(alias = "CFCharacterSetIsLongCharacterMember")]
+    pub fn contains(&self, c: char) -> bool {
+        extern "C" {
+            fn CFCharacterSetIsLongCharacterMember(
+                the_set: &CFCharacterSet,
+                the_char: u32, // UTF32Char
+            ) -> Boolean;
+        }
+        unsafe { CFCharacterSetIsLongCharacterMember(self, c as u32) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFIndex;
+
+    #[test]
+    fn predefined() {
+        assert!(CFCharacterSet::whitespace().contains(' '));
+        assert!(CFCharacterSet::whitespace().contains('\t'));
+        assert!(!CFCharacterSet::whitespace().contains('\n'));
+        assert!(CFCharacterSet::whitespace_and_newline().contains('\n'));
+
+        assert!(CFCharacterSet::decimal_digit().contains('7'));
+        assert!(CFCharacterSet::decimal_digit().contains('٣'));
+        assert!(!CFCharacterSet::decimal_digit().contains('x'));
+
+        assert!(CFCharacterSet::alphanumeric().contains('x'));
+        assert!(CFCharacterSet::alphanumeric().contains('7'));
+        assert!(!CFCharacterSet::alphanumeric().contains('!'));
+
+        assert!(CFCharacterSet::punctuation().contains('!'));
+        assert!(!CFCharacterSet::punctuation().contains('a'));
+    }
+
+    #[test]
+    fn supplementary_plane() {
+        let letter = CFCharacterSet::letter();
+
+        // U+1D400 MATHEMATICAL BOLD CAPITAL A.
+        assert!(letter.contains('\u{1D400}'));
+        // U+1F600 GRINNING FACE.
+        assert!(!letter.contains('😀'));
+
+        let symbols = CFCharacterSet::predefined(CFCharacterSetPredefinedSet::Symbol);
+        assert!(symbols.contains('😀'));
+    }
+
+    #[test]
+    fn from_string() {
+        let set = CFCharacterSet::from_string(&CFString::from_str("ab😀"));
+        assert!(set.contains('a'));
+        assert!(set.contains('b'));
+        assert!(set.contains('😀'));
+        assert!(!set.contains('c'));
+    }
+
+    #[test]
+    fn from_range() {
+        let set = CFCharacterSet::from_range(CFRange::new('a' as CFIndex, 6));
+        assert!(set.contains('a'));
+        assert!(set.contains('f'));
+        assert!(!set.contains('g'));
+
+        let emoji = CFCharacterSet::from_range(CFRange::new(0x1F600, 0x50));
+        assert!(emoji.contains('😀'));
+        assert!(!emoji.contains('a'));
+    }
+
+    #[test]
+    fn inverted() {
+        let set = CFCharacterSet::decimal_digit().inverted();
+        assert!(!set.contains('7'));
+        assert!(set.contains('x'));
+        assert!(set.contains('😀'));
+    }
+}
@@ -0,0 +1,383 @@
+use super::{CFAllocator, CFIndex, CFType};
+use crate::core::{Arc, ObjectType};
+use std::{fmt, ops::Deref, ptr::NonNull, slice};
+
+/// An immutable byte buffer.
+///
+/// Unlike other types in this module, `CFData` dereferences to `[u8]` rather
+/// than [`CFType`](struct.CFType.html). Its `CFType` methods can be reached
+/// through [`AsRef`].
+///
+/// Documentation:
+/// [Swift](https://developer.apple.com/documentation/corefoundation/cfdata?language=swift) |
+/// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfdataref?language=objc)
+#[repr(C)]
+#[derive(PartialEq, Hash)]
+pub struct CFData(CFType);
+
+cf_type_object!(CFData, CFDataGetTypeID);
+
+impl ObjectType for CFData {
+    #[inline]
+    fn retain(obj: &Self) -> Arc<Self> {
+        let obj = Arc::retain(&obj.0);
+        unsafe { Arc::cast_unchecked(obj) }
+    }
+
+    #[inline]
+    unsafe fn release(obj: NonNull<Self>) {
+        CFType::release(obj.cast());
+    }
+
+    #[inline]
+    fn is_unique(obj: &Self) -> bool {
+        CFType::is_unique(&obj.0)
+    }
+}
+
+impl AsRef<CFData> for CFData {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl AsMut<CFData> for CFData {
+    #[inline]
+    fn as_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+impl AsRef<CFType> for CFData {
+    #[inline]
+    fn as_ref(&self) -> &CFType {
+        &self.0
+    }
+}
+
+impl AsMut<CFType> for CFData {
+    #[inline]
+    fn as_mut(&mut self) -> &mut CFType {
+        &mut self.0
+    }
+}
+
+impl Deref for CFData {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for CFData {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl PartialEq<[u8]> for CFData {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl fmt::Debug for CFData {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl From<&[u8]> for Arc<CFData> {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        CFData::from_bytes(bytes)
+    }
+}
+
+impl CFData {
+    /// Creates an immutable data object by copying `bytes`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatacreate(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDataCreate")]
+    pub fn from_bytes(bytes: &[u8]) -> Arc<Self> {
+        extern "C" {
+            fn CFDataCreate(
+                allocator: Option<&CFAllocator>,
+                bytes: *const u8,
+                length: CFIndex,
+            ) -> *const CFData;
+        }
+
+        unsafe {
+            let data = CFDataCreate(None, bytes.as_ptr(), bytes.len() as CFIndex);
+            assert!(!data.is_null(), "Could not create CFData");
+            Arc::from_raw(data)
+        }
+    }
+
+    /// Creates an immutable data object that references `bytes` without
+    /// copying.
+    ///
+    /// This uses
+    /// [`CFAllocator::null`](struct.CFAllocator.html#method.null) as the
+    /// deallocator, so `bytes` is never freed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatacreatewithbytesnocopy(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDataCreateWithBytesNoCopy")]
+    pub fn from_static(bytes: &'static [u8]) -> Arc<Self> {
+        extern "C" {
+            fn CFDataCreateWithBytesNoCopy(
+                allocator: Option<&CFAllocator>,
+                bytes: *const u8,
+                length: CFIndex,
+                bytes_deallocator: Option<&CFAllocator>,
+            ) -> *const CFData;
+        }
+
+        unsafe {
+            let data = CFDataCreateWithBytesNoCopy(
+                None,
+                bytes.as_ptr(),
+                bytes.len() as CFIndex,
+                Some(CFAllocator::null()),
+            );
+            assert!(!data.is_null(), "Could not create CFData");
+            Arc::from_raw(data)
+        }
+    }
+
+    /// Returns the number of bytes in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatagetlength(_:)).
+    #[inline]
+    #[doc(alias = "CFDataGetLength")]
+    pub fn len(&self) -> CFIndex {
+        extern "C" {
+            fn CFDataGetLength(the_data: &CFData) -> CFIndex;
+        }
+        unsafe { CFDataGetLength(self) }
+    }
+
+    /// Returns `true` if `self` contains no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a slice over the bytes of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatagetbyteptr(_:)).
+    #[inline]
+    #[doc(alias = "CFDataGetBytePtr")]
+    pub fn as_slice(&self) -> &[u8] {
+        extern "C" {
+            fn CFDataGetBytePtr(the_data: &CFData) -> *const u8;
+        }
+
+        unsafe {
+            // Empty data may be backed by a null pointer, but slices must be
+            // non-null.
+            let ptr = CFDataGetBytePtr(self);
+            let len = self.len();
+            if ptr.is_null() || len == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(ptr, len as usize)
+            }
+        }
+    }
+}
+
+subclass! {
+    /// A mutable byte buffer.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutabledata?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutabledataref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableData: CFData;
+}
+
+impl PartialEq<[u8]> for CFMutableData {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl fmt::Debug for CFMutableData {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl CFMutableData {
+    /// Creates an empty mutable data object.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty mutable data object that can contain at most
+    /// `capacity` bytes, where 0 means it has no limit.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatacreatemutable(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDataCreateMutable")]
+    pub fn with_capacity(capacity: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFDataCreateMutable(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+            ) -> *const CFMutableData;
+        }
+
+        unsafe {
+            let data = CFDataCreateMutable(None, capacity);
+            assert!(!data.is_null(), "Could not create CFMutableData");
+            Arc::from_raw(data)
+        }
+    }
+
+    /// Creates a mutable data object with no capacity limit by copying
+    /// `bytes`.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Arc<Self> {
+        let mut data = Self::new();
+        Arc::get_mut(&mut data).unwrap().extend_from_slice(bytes);
+        data
+    }
+
+    /// Appends `bytes` to the end of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdataappendbytes(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDataAppendBytes")]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        extern "C" {
+            fn CFDataAppendBytes(the_data: &CFData, bytes: *const u8, length: CFIndex);
+        }
+        unsafe { CFDataAppendBytes(self, bytes.as_ptr(), bytes.len() as CFIndex) }
+    }
+
+    /// Resizes `self` to `len` bytes, filling any new bytes with zeros.
+    ///
+    /// If `self` was created with a fixed capacity, exceeding it aborts the
+    /// process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatasetlength(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDataSetLength")]
+    pub fn set_len(&mut self, len: CFIndex) {
+        extern "C" {
+            fn CFDataSetLength(the_data: &CFData, length: CFIndex);
+        }
+        unsafe { CFDataSetLength(self, len) }
+    }
+
+    /// Returns a mutable slice over the bytes of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatagetmutablebyteptr(_:)).
+    #[inline]
+    #[doc(alias = "CFDataGetMutableBytePtr")]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        extern "C" {
+            fn CFDataGetMutableBytePtr(the_data: &CFData) -> *mut u8;
+        }
+
+        unsafe {
+            let ptr = CFDataGetMutableBytePtr(self);
+            let len = self.len();
+            if ptr.is_null() || len == 0 {
+                &mut []
+            } else {
+                slice::from_raw_parts_mut(ptr, len as usize)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes() {
+        let bytes = b"hello\0world";
+        let data = CFData::from_bytes(bytes);
+
+        assert_eq!(data.len(), bytes.len() as CFIndex);
+        assert_eq!(&**data, &bytes[..]);
+        assert_eq!(*data, bytes[..]);
+        assert_eq!(data[6..], *b"world");
+
+        assert_eq!(*CFData::from_bytes(bytes), *data);
+        assert_eq!(*CFData::from_static(bytes), *data);
+    }
+
+    #[test]
+    fn from_static() {
+        static BYTES: [u8; 4] = [1, 2, 3, 4];
+        let data = CFData::from_static(&BYTES);
+
+        // The bytes are referenced rather than copied.
+        assert_eq!(data.as_slice().as_ptr(), BYTES.as_ptr());
+    }
+
+    #[test]
+    fn empty() {
+        let empty: &[u8] = &[];
+
+        for data in &[CFData::from_bytes(empty), CFData::from_static(empty)] {
+            assert!(data.is_empty());
+            assert_eq!(data.as_slice(), empty);
+        }
+
+        let mut data = CFMutableData::new();
+        assert!(data.is_empty());
+        assert_eq!(Arc::get_mut(&mut data).unwrap().as_mut_slice(), empty);
+    }
+
+    #[test]
+    fn mutate() {
+        let mut data = CFMutableData::from_bytes(b"abc");
+        let data_mut = Arc::get_mut(&mut data).unwrap();
+
+        data_mut.extend_from_slice(b"def");
+        assert_eq!(*data_mut, b"abcdef"[..]);
+
+        data_mut.as_mut_slice()[0] = b'A';
+        assert_eq!(*data_mut, b"Abcdef"[..]);
+
+        data_mut.set_len(8);
+        assert_eq!(*data_mut, b"Abcdef\0\0"[..]);
+
+        data_mut.set_len(2);
+        assert_eq!(*data_mut, b"Ab"[..]);
+
+        let shared = data.clone();
+        assert!(Arc::get_mut(&mut data).is_none());
+        drop(shared);
+    }
+
+    #[test]
+    fn downcast() {
+        let data = CFData::from_bytes(b"data");
+        let cf_type: &CFType = (*data).as_ref();
+
+        assert!(cf_type.is::<CFData>());
+        assert_eq!(
+            cf_type.downcast_ref::<CFData>().unwrap().as_slice(),
+            b"data"
+        );
+    }
+}
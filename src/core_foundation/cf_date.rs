@@ -0,0 +1,300 @@
+use super::{CFAllocator, CFComparisonResult, CFType};
+use crate::core::Arc;
+use std::{
+    cmp::Ordering,
+    ffi::c_void,
+    fmt, ptr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A type used to represent a specific point in time relative to the absolute
+/// reference date of 1 Jan 2001 00:00:00 GMT.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfabsolutetime).
+pub type CFAbsoluteTime = f64;
+
+/// A type used to represent elapsed time in seconds.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimeinterval).
+pub type CFTimeInterval = f64;
+
+/// The number of seconds between the Unix epoch (1 Jan 1970 00:00:00 GMT) and
+/// the absolute reference date.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfabsolutetimeintervalsince1970).
+#[allow(non_upper_case_globals)]
+pub const kCFAbsoluteTimeIntervalSince1970: CFTimeInterval = 978307200.0;
+
+/// The number of seconds between 1 Jan 1904 00:00:00 GMT and the absolute
+/// reference date.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfabsolutetimeintervalsince1904).
+#[allow(non_upper_case_globals)]
+pub const kCFAbsoluteTimeIntervalSince1904: CFTimeInterval = 3061152000.0;
+
+/// `kCFAbsoluteTimeIntervalSince1970` as an integer for exact conversions.
+const UNIX_EPOCH_OFFSET: i64 = 978307200;
+
+/// Returns the current system absolute time.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfabsolutetimegetcurrent()).
+#[inline]
+#[allow(non_snake_case)]
+pub fn CFAbsoluteTimeGetCurrent() -> CFAbsoluteTime {
+    extern "C" {
+        fn CFAbsoluteTimeGetCurrent() -> CFAbsoluteTime;
+    }
+    unsafe { CFAbsoluteTimeGetCurrent() }
+}
+
+cf_subclass! {
+    /// A point in time, independent of any particular calendrical system or
+    /// time zone.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfdate?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfdateref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFDate: CFType;
+    type_id = CFDateGetTypeID;
+}
+
+impl Eq for CFDate {}
+
+impl PartialOrd for CFDate {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CFDate {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other).into()
+    }
+}
+
+impl fmt::Debug for CFDate {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.copy_description(), f)
+    }
+}
+
+impl From<SystemTime> for Arc<CFDate> {
+    #[inline]
+    fn from(time: SystemTime) -> Self {
+        CFDate::from_system_time(time)
+    }
+}
+
+impl CFDate {
+    /// Creates a date for the current time.
+    #[inline]
+    pub fn now() -> Arc<Self> {
+        Self::from_absolute_time(CFAbsoluteTimeGetCurrent())
+    }
+
+    /// Creates a date for `time` seconds relative to the absolute reference
+    /// date.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatecreate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateCreate")]
+    pub fn from_absolute_time(time: CFAbsoluteTime) -> Arc<Self> {
+        extern "C" {
+            fn CFDateCreate(allocator: Option<&CFAllocator>, at: CFAbsoluteTime) -> *const CFDate;
+        }
+
+        unsafe {
+            let date = CFDateCreate(None, time);
+            assert!(!date.is_null(), "Could not create CFDate");
+            Arc::from_raw(date)
+        }
+    }
+
+    /// Creates a date for the same point in time as `time`.
+    #[inline]
+    pub fn from_system_time(time: SystemTime) -> Arc<Self> {
+        // Convert whole seconds as integers so that only the fractional part
+        // is subject to floating point rounding.
+        let (unix_secs, unix_nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => (after.as_secs() as i64, after.subsec_nanos() as i64),
+            Err(before) => {
+                let before = before.duration();
+                (-(before.as_secs() as i64), -(before.subsec_nanos() as i64))
+            }
+        };
+
+        let secs = (unix_secs - UNIX_EPOCH_OFFSET) as f64;
+        Self::from_absolute_time(secs + unix_nanos as f64 * 1e-9)
+    }
+
+    /// Returns the point in time of `self` relative to the absolute reference
+    /// date.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdategetabsolutetime(_:)).
+    #[inline]
+    #[doc(alias = "CFDateGetAbsoluteTime")]
+    pub fn absolute_time(&self) -> CFAbsoluteTime {
+        extern "C" {
+            fn CFDateGetAbsoluteTime(the_date: &CFDate) -> CFAbsoluteTime;
+        }
+        unsafe { CFDateGetAbsoluteTime(self) }
+    }
+
+    /// Returns the point in time of `self` as a `SystemTime`.
+    ///
+    /// This returns `None` if `SystemTime` cannot represent `self`, such as if
+    /// its absolute time is NaN or infinite.
+    #[inline]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let time = self.absolute_time();
+        if !time.is_finite() {
+            return None;
+        }
+
+        // `as` saturates, so out of range values fail the checked operations.
+        let secs = time.floor();
+        let unix_secs = (secs as i64).checked_add(UNIX_EPOCH_OFFSET)?;
+
+        let whole = if unix_secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(unix_secs as u64))?
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(unix_secs.unsigned_abs()))?
+        };
+
+        // The fractional part is always in `0.0..1.0` because of `floor`.
+        whole.checked_add(Duration::from_secs_f64(time - secs))
+    }
+
+    /// Returns the number of seconds between `self` and `other`.
+    ///
+    /// The result is positive if `self` is later than `other`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdategettimeintervalsincedate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateGetTimeIntervalSinceDate")]
+    pub fn time_interval_since(&self, other: &CFDate) -> CFTimeInterval {
+        extern "C" {
+            fn CFDateGetTimeIntervalSinceDate(
+                the_date: &CFDate,
+                other_date: &CFDate,
+            ) -> CFTimeInterval;
+        }
+        unsafe { CFDateGetTimeIntervalSinceDate(self, other) }
+    }
+
+    /// Returns the amount of time elapsed since `self`.
+    ///
+    /// If `self` is in the future, this returns a zero duration. This returns
+    /// `None` if `Duration` cannot represent the elapsed time, such as if the
+    /// absolute time of `self` is NaN or negative infinity.
+    #[inline]
+    pub fn elapsed(&self) -> Option<Duration> {
+        let elapsed = CFAbsoluteTimeGetCurrent() - self.absolute_time();
+
+        // `Duration::from_secs_f64` panics if the value does not fit.
+        if elapsed.is_nan() || elapsed >= u64::MAX as f64 {
+            None
+        } else if elapsed > 0.0 {
+            Some(Duration::from_secs_f64(elapsed))
+        } else {
+            Some(Duration::from_secs(0))
+        }
+    }
+
+    /// Compares the points in time of `self` and `other`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdatecompare(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateCompare")]
+    pub fn compare(&self, other: &CFDate) -> CFComparisonResult {
+        extern "C" {
+            fn CFDateCompare(
+                the_date: &CFDate,
+                other_date: &CFDate,
+                context: *mut c_void,
+            ) -> CFComparisonResult;
+        }
+        unsafe { CFDateCompare(self, other, ptr::null_mut()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abs_diff(a: SystemTime, b: SystemTime) -> Duration {
+        match a.duration_since(b) {
+            Ok(diff) => diff,
+            Err(diff) => diff.duration(),
+        }
+    }
+
+    #[test]
+    fn reference_date() {
+        let date = CFDate::from_absolute_time(0.0);
+        let expected = UNIX_EPOCH + Duration::from_secs(UNIX_EPOCH_OFFSET as u64);
+
+        assert_eq!(date.absolute_time(), 0.0);
+        assert_eq!(date.to_system_time(), Some(expected));
+        assert_eq!(*CFDate::from_system_time(expected), *date);
+        assert_eq!(
+            CFDate::from_system_time(UNIX_EPOCH).absolute_time(),
+            -kCFAbsoluteTimeIntervalSince1970
+        );
+    }
+
+    #[test]
+    fn round_trip_now() {
+        let now = SystemTime::now();
+        let date = CFDate::from_system_time(now);
+
+        assert!(abs_diff(date.to_system_time().unwrap(), now) < Duration::from_micros(1));
+        assert!(date.elapsed().unwrap() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn before_unix_epoch() {
+        let time = UNIX_EPOCH - Duration::new(86400 * 365, 250_000_000);
+        let date: Arc<CFDate> = time.into();
+
+        let expected = -(86400.0 * 365.0 + 0.25) - kCFAbsoluteTimeIntervalSince1970;
+        assert!((date.absolute_time() - expected).abs() < 1e-6);
+        assert!(abs_diff(date.to_system_time().unwrap(), time) < Duration::from_micros(1));
+    }
+
+    #[test]
+    fn compare() {
+        let earlier = CFDate::from_absolute_time(-1.5);
+        let later = CFDate::from_absolute_time(10.0);
+
+        assert!(*earlier < *later);
+        assert_eq!(earlier.compare(&later), CFComparisonResult::LessThan);
+        assert_eq!(later.time_interval_since(&earlier), 11.5);
+        assert_eq!(*CFDate::from_absolute_time(10.0), *later);
+        assert_eq!(
+            CFDate::from_absolute_time(1e12).elapsed(),
+            Some(Duration::from_secs(0))
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        for &time in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e300, -1e300] {
+            assert_eq!(CFDate::from_absolute_time(time).to_system_time(), None);
+        }
+
+        assert_eq!(CFDate::from_absolute_time(f64::NAN).elapsed(), None);
+        assert_eq!(
+            CFDate::from_absolute_time(f64::NEG_INFINITY).elapsed(),
+            None
+        );
+        assert_eq!(
+            CFDate::from_absolute_time(f64::INFINITY).elapsed(),
+            Some(Duration::from_secs(0))
+        );
+    }
+}
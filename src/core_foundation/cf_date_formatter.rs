@@ -0,0 +1,326 @@
+use super::{
+    CFAllocator, CFBoolean, CFDate, CFIndex, CFLocale, CFRange, CFString, CFTimeZone, CFType,
+};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// Converts between dates and their textual representations.
+    ///
+    /// Formatters are configured through `&mut self`, which can be obtained
+    /// from a newly created formatter via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfdateformatter?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfdateformatterref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFDateFormatter: CFType;
+    type_id = CFDateFormatterGetTypeID;
+}
+
+/// Predefined date and time format styles, whose exact format depends on the
+/// locale.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformatterstyle).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFDateFormatterStyle {
+    /// No output.
+    NoStyle = 0,
+    /// Short style, such as "11/23/37" or "3:30 PM".
+    Short = 1,
+    /// Medium style, such as "Nov 23, 1937" or "3:30:32 PM".
+    Medium = 2,
+    /// Long style, such as "November 23, 1937" or "3:30:32 PM PST".
+    Long = 3,
+    /// Full style, such as "Tuesday, April 12, 1952 AD" or
+    /// "3:30:42 PM Pacific Standard Time".
+    Full = 4,
+}
+
+/// A property of a [`CFDateFormatter`](struct.CFDateFormatter.html) along
+/// with its value.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformatterkey).
+#[derive(Copy, Clone, Debug)]
+pub enum CFDateFormatterProperty<'a> {
+    /// The time zone used for formatting and parsing.
+    #[doc(alias = "kCFDateFormatterTimeZone")]
+    TimeZone(&'a CFTimeZone),
+    /// Whether heuristics are used when parsing ambiguous strings.
+    #[doc(alias = "kCFDateFormatterIsLenient")]
+    IsLenient(bool),
+    /// The date used for fields that are missing from parsed strings.
+    #[doc(alias = "kCFDateFormatterDefaultDate")]
+    DefaultDate(&'a CFDate),
+    /// The earliest date that two-digit years are parsed as being in.
+    #[doc(alias = "kCFDateFormatterTwoDigitStartDate")]
+    TwoDigitStartDate(&'a CFDate),
+    /// Whether relative terms such as "today" are used when formatting.
+    #[doc(alias = "kCFDateFormatterDoesRelativeDateFormattingKey")]
+    DoesRelativeDateFormatting(bool),
+    /// The symbol for times before noon.
+    #[doc(alias = "kCFDateFormatterAMSymbol")]
+    AMSymbol(&'a CFString),
+    /// The symbol for times after noon.
+    #[doc(alias = "kCFDateFormatterPMSymbol")]
+    PMSymbol(&'a CFString),
+}
+
+impl CFDateFormatterProperty<'_> {
+    #[inline]
+    fn key(&self) -> &'static CFString {
+        extern "C" {
+            static kCFDateFormatterTimeZone: &'static CFString;
+            static kCFDateFormatterIsLenient: &'static CFString;
+            static kCFDateFormatterDefaultDate: &'static CFString;
+            static kCFDateFormatterTwoDigitStartDate: &'static CFString;
+            static kCFDateFormatterDoesRelativeDateFormattingKey: &'static CFString;
+            static kCFDateFormatterAMSymbol: &'static CFString;
+            static kCFDateFormatterPMSymbol: &'static CFString;
+        }
+
+        unsafe {
+            match self {
+                Self::TimeZone(_) => kCFDateFormatterTimeZone,
+                Self::IsLenient(_) => kCFDateFormatterIsLenient,
+                Self::DefaultDate(_) => kCFDateFormatterDefaultDate,
+                Self::TwoDigitStartDate(_) => kCFDateFormatterTwoDigitStartDate,
+                Self::DoesRelativeDateFormatting(_) => {
+                    kCFDateFormatterDoesRelativeDateFormattingKey
+                }
+                Self::AMSymbol(_) => kCFDateFormatterAMSymbol,
+                Self::PMSymbol(_) => kCFDateFormatterPMSymbol,
+            }
+        }
+    }
+
+    #[inline]
+    fn value(&self) -> &CFType {
+        match *self {
+            Self::TimeZone(value) => value,
+            Self::IsLenient(value) | Self::DoesRelativeDateFormatting(value) => {
+                CFBoolean::new(value)
+            }
+            Self::DefaultDate(value) | Self::TwoDigitStartDate(value) => value,
+            Self::AMSymbol(value) | Self::PMSymbol(value) => value,
+        }
+    }
+}
+
+impl fmt::Debug for CFDateFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFDateFormatter")
+            .field("locale", &self.locale())
+            .field("date_format", &self.date_format())
+            .finish()
+    }
+}
+
+/// Creating and configuring date formatters.
+impl CFDateFormatter {
+    /// Creates a formatter for `locale` with the given styles.
+    ///
+    /// If `locale` is `None`, the system locale is used.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattercreate(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterCreate")]
+    pub fn new(
+        locale: Option<&CFLocale>,
+        date_style: CFDateFormatterStyle,
+        time_style: CFDateFormatterStyle,
+    ) -> Arc<Self> {
+        extern "C" {
+            fn CFDateFormatterCreate(
+                allocator: Option<&CFAllocator>,
+                locale: Option<&CFLocale>,
+                date_style: CFDateFormatterStyle,
+                time_style: CFDateFormatterStyle,
+            ) -> Option<Arc<CFDateFormatter>>;
+        }
+        unsafe { CFDateFormatterCreate(None, locale, date_style, time_style) }
+            .expect("Could not create CFDateFormatter")
+    }
+
+    /// Returns the locale of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattergetlocale(_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterGetLocale")]
+    pub fn locale(&self) -> &CFLocale {
+        extern "C" {
+            fn CFDateFormatterGetLocale(formatter: &CFDateFormatter) -> &CFLocale;
+        }
+        unsafe { CFDateFormatterGetLocale(self) }
+    }
+
+    /// Returns the format pattern of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattergetformat(_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterGetFormat")]
+    pub fn date_format(&self) -> &CFString {
+        extern "C" {
+            fn CFDateFormatterGetFormat(formatter: &CFDateFormatter) -> &CFString;
+        }
+        unsafe { CFDateFormatterGetFormat(self) }
+    }
+
+    /// Sets the format pattern of `self`, overriding its styles.
+    ///
+    /// The pattern uses the
+    /// [Unicode date format patterns](https://unicode.org/reports/tr35/tr35-dates.html#Date_Format_Patterns),
+    /// such as `yyyy-MM-dd'T'HH:mm:ssXXXXX`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattersetformat(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterSetFormat")]
+    pub fn set_format(&mut self, format: &CFString) {
+        extern "C" {
+            fn CFDateFormatterSetFormat(formatter: &CFDateFormatter, format_string: &CFString);
+        }
+        unsafe { CFDateFormatterSetFormat(self, format) }
+    }
+
+    /// Sets a property of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattersetproperty(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterSetProperty")]
+    pub fn set_property(&mut self, property: CFDateFormatterProperty) {
+        extern "C" {
+            fn CFDateFormatterSetProperty(
+                formatter: &CFDateFormatter,
+                key: &CFString,
+                value: &CFType,
+            );
+        }
+        unsafe { CFDateFormatterSetProperty(self, property.key(), property.value()) }
+    }
+}
+
+/// Formatting and parsing dates.
+impl CFDateFormatter {
+    /// Returns `date` formatted by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattercreatestringwithdate(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterCreateStringWithDate")]
+    pub fn format(&self, date: &CFDate) -> Arc<CFString> {
+        extern "C" {
+            fn CFDateFormatterCreateStringWithDate(
+                allocator: Option<&CFAllocator>,
+                formatter: &CFDateFormatter,
+                date: &CFDate,
+            ) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFDateFormatterCreateStringWithDate(None, self, date) }
+            .expect("Could not format CFDate")
+    }
+
+    /// Parses all of `string` as a date, or returns `None` if it is not
+    /// entirely in the format of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdateformattercreatedatefromstring(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDateFormatterCreateDateFromString")]
+    pub fn parse(&self, string: &CFString) -> Option<Arc<CFDate>> {
+        extern "C" {
+            fn CFDateFormatterCreateDateFromString(
+                allocator: Option<&CFAllocator>,
+                formatter: &CFDateFormatter,
+                string: &CFString,
+                rangep: *mut CFRange,
+            ) -> Option<Arc<CFDate>>;
+        }
+
+        // On input, the range is the part of `string` to parse. On output, it
+        // is the part that was actually parsed.
+        let len: CFIndex = string.len();
+        let mut range = CFRange::new(0, len);
+        let date = unsafe { CFDateFormatterCreateDateFromString(None, self, string, &mut range) };
+
+        if range.length == len {
+            date
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc3339() -> Arc<CFDateFormatter> {
+        let locale = CFLocale::with_identifier(&CFString::from_str("en_US_POSIX"));
+        let utc = CFTimeZone::with_name(&CFString::from_str("UTC")).unwrap();
+
+        let mut formatter = CFDateFormatter::new(
+            Some(&locale),
+            CFDateFormatterStyle::NoStyle,
+            CFDateFormatterStyle::NoStyle,
+        );
+        let f = Arc::get_mut(&mut formatter).unwrap();
+        f.set_format(&CFString::from_str("yyyy-MM-dd'T'HH:mm:ssXXXXX"));
+        f.set_property(CFDateFormatterProperty::TimeZone(&utc));
+        f.set_property(CFDateFormatterProperty::IsLenient(false));
+        formatter
+    }
+
+    #[test]
+    fn format() {
+        let formatter = rfc3339();
+        assert_eq!(*formatter.date_format(), *"yyyy-MM-dd'T'HH:mm:ssXXXXX");
+        assert_eq!(*formatter.locale().identifier(), *"en_US_POSIX");
+
+        let date = CFDate::from_absolute_time(86400.0 + 3661.0);
+        assert_eq!(*formatter.format(&date), *"2001-01-02T01:01:01Z");
+    }
+
+    #[test]
+    fn parse() {
+        let formatter = rfc3339();
+
+        let date = formatter
+            .parse(&CFString::from_str("2001-01-02T01:01:01Z"))
+            .unwrap();
+        assert_eq!(date.absolute_time(), 86400.0 + 3661.0);
+
+        let date = formatter
+            .parse(&CFString::from_str("2001-01-01T01:00:00+01:00"))
+            .unwrap();
+        assert_eq!(date.absolute_time(), 0.0);
+
+        // Round trip.
+        let date = CFDate::from_absolute_time(123456789.0);
+        assert_eq!(*formatter.parse(&formatter.format(&date)).unwrap(), *date);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let formatter = rfc3339();
+        assert!(formatter.parse(&CFString::from_str("not a date")).is_none());
+        assert!(formatter
+            .parse(&CFString::from_str("2001-01-01T00:00:00Z trailing"))
+            .is_none());
+    }
+
+    #[test]
+    fn styles() {
+        let mut formatter = CFDateFormatter::new(
+            Some(&CFLocale::with_identifier(&CFString::from_str("en_US"))),
+            CFDateFormatterStyle::Long,
+            CFDateFormatterStyle::NoStyle,
+        );
+        let utc = CFTimeZone::with_name(&CFString::from_str("UTC")).unwrap();
+        Arc::get_mut(&mut formatter)
+            .unwrap()
+            .set_property(CFDateFormatterProperty::TimeZone(&utc));
+
+        let date = CFDate::from_absolute_time(0.0);
+        assert_eq!(*formatter.format(&date), *"January 1, 2001");
+    }
+}
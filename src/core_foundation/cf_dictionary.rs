@@ -0,0 +1,407 @@
+use super::{CFAllocator, CFIndex, CFType};
+use crate::core::{Arc, ObjectType};
+use std::{ffi::c_void, fmt, marker::PhantomData, ptr, vec};
+
+subclass! {
+    /// An immutable dictionary mapping keys of type `K` to values of type `V`.
+    ///
+    /// The untyped `CFDictionary<CFType, CFType>` form can hold heterogeneous
+    /// keys and values, which can be recovered with
+    /// [`CFType::downcast_ref`](struct.CFType.html#method.downcast_ref).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfdictionary?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfdictionaryref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFDictionary<K, V>: CFType;
+}
+
+// Only the untyped form can be downcast to because entries are not checked.
+cf_type_object!(CFDictionary<CFType, CFType>, CFDictionaryGetTypeID);
+
+#[repr(C)]
+pub(crate) struct CFDictionaryKeyCallBacks {
+    version: CFIndex,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+    equal: *const c_void,
+    hash: *const c_void,
+}
+
+#[repr(C)]
+pub(crate) struct CFDictionaryValueCallBacks {
+    version: CFIndex,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+    equal: *const c_void,
+}
+
+extern "C" {
+    pub(crate) static kCFTypeDictionaryKeyCallBacks: CFDictionaryKeyCallBacks;
+    pub(crate) static kCFTypeDictionaryValueCallBacks: CFDictionaryValueCallBacks;
+
+    fn CFDictionaryGetCount(the_dict: &CFType) -> CFIndex;
+    fn CFDictionaryGetValue(the_dict: &CFType, key: *const c_void) -> *const c_void;
+    fn CFDictionaryContainsKey(the_dict: &CFType, key: *const c_void) -> super::Boolean;
+    fn CFDictionaryGetKeysAndValues(
+        the_dict: &CFType,
+        keys: *mut *const c_void,
+        values: *mut *const c_void,
+    );
+}
+
+impl<K, V> fmt::Debug for CFDictionary<K, V>
+where
+    K: ObjectType + fmt::Debug,
+    V: ObjectType + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: ObjectType, V: ObjectType> IntoIterator for &'a CFDictionary<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = CFDictionaryIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: ObjectType, V: ObjectType> CFDictionary<K, V> {
+    /// Creates a dictionary containing retained keys and values from `pairs`.
+    ///
+    /// This uses
+    /// [`kCFTypeDictionaryKeyCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypedictionarykeycallbacks)
+    /// and
+    /// [`kCFTypeDictionaryValueCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypedictionaryvaluecallbacks).
+    /// If a key appears more than once, the last value is used.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarycreate(_:_:_:_:_:_:)).
+    #[doc(alias = "CFDictionaryCreate")]
+    pub fn from_pairs(pairs: &[(&K, &V)]) -> Arc<Self> {
+        extern "C" {
+            fn CFDictionaryCreate(
+                allocator: Option<&CFAllocator>,
+                keys: *const *const c_void,
+                values: *const *const c_void,
+                num_values: CFIndex,
+                key_call_backs: *const CFDictionaryKeyCallBacks,
+                value_call_backs: *const CFDictionaryValueCallBacks,
+            ) -> *const CFType;
+        }
+
+        let keys: Vec<*const c_void> = pairs.iter().map(|&(k, _)| (k as *const K).cast()).collect();
+        let values: Vec<*const c_void> =
+            pairs.iter().map(|&(_, v)| (v as *const V).cast()).collect();
+
+        unsafe {
+            let dict = CFDictionaryCreate(
+                None,
+                keys.as_ptr(),
+                values.as_ptr(),
+                pairs.len() as CFIndex,
+                &kCFTypeDictionaryKeyCallBacks,
+                &kCFTypeDictionaryValueCallBacks,
+            );
+            assert!(!dict.is_null(), "Could not create CFDictionary");
+            Arc::from_raw(dict.cast())
+        }
+    }
+
+    /// Returns the number of key-value pairs in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarygetcount(_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryGetCount")]
+    pub fn len(&self) -> CFIndex {
+        unsafe { CFDictionaryGetCount(self) }
+    }
+
+    /// Returns `true` if `self` contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value for `key`, or `None` if there is none.
+    ///
+    /// Keys are compared using `CFEqual`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarygetvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryGetValue")]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe {
+            let value = CFDictionaryGetValue(self, (key as *const K).cast());
+            value.cast::<V>().as_ref()
+        }
+    }
+
+    /// Returns `true` if `self` contains a value for `key`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarycontainskey(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryContainsKey")]
+    pub fn contains_key(&self, key: &K) -> bool {
+        unsafe { CFDictionaryContainsKey(self, (key as *const K).cast()) != 0 }
+    }
+
+    /// Returns an iterator over the key-value pairs in `self`.
+    ///
+    /// All pairs are retrieved at once using
+    /// [`CFDictionaryGetKeysAndValues`](https://developer.apple.com/documentation/corefoundation/cfdictionarygetkeysandvalues(_:_:_:)).
+    /// The order of pairs is unspecified.
+    #[doc(alias = "CFDictionaryGetKeysAndValues")]
+    pub fn iter(&self) -> CFDictionaryIter<'_, K, V> {
+        let len = self.len() as usize;
+        let mut keys = vec![ptr::null(); len];
+        let mut values = vec![ptr::null(); len];
+
+        if len != 0 {
+            unsafe { CFDictionaryGetKeysAndValues(self, keys.as_mut_ptr(), values.as_mut_ptr()) };
+        }
+
+        CFDictionaryIter {
+            keys: keys.into_iter(),
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the keys in `self`.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values in `self`.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns `self` as a dictionary of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &CFDictionary<CFType, CFType> {
+        // SAFETY: All keys and values are `CFType` instances.
+        unsafe { &*(self as *const Self as *const CFDictionary<CFType, CFType>) }
+    }
+}
+
+impl CFDictionary<CFType, CFType> {
+    /// Casts `self` to a dictionary with keys of type `K` and values of type
+    /// `V`.
+    ///
+    /// # Safety
+    ///
+    /// All keys and values of `self` must be instances of `K` and `V`
+    /// respectively.
+    #[inline]
+    pub unsafe fn cast_unchecked<K: ObjectType, V: ObjectType>(&self) -> &CFDictionary<K, V> {
+        &*(self as *const Self as *const CFDictionary<K, V>)
+    }
+}
+
+/// An iterator over the key-value pairs of a [`CFDictionary`].
+///
+/// This is created by [`CFDictionary::iter`].
+pub struct CFDictionaryIter<'a, K, V> {
+    keys: vec::IntoIter<*const c_void>,
+    values: vec::IntoIter<*const c_void>,
+    marker: PhantomData<&'a CFDictionary<K, V>>,
+}
+
+impl<'a, K: ObjectType, V: ObjectType> Iterator for CFDictionaryIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+        let value = self.values.next()?;
+
+        // SAFETY: The dictionary retains its entries for at least `'a`.
+        unsafe { Some((&*key.cast(), &*value.cast())) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<K: ObjectType, V: ObjectType> ExactSizeIterator for CFDictionaryIter<'_, K, V> {}
+
+subclass! {
+    /// A mutable dictionary mapping keys of type `K` to values of type `V`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created dictionary via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutabledictionary?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutabledictionaryref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableDictionary<K, V>: CFDictionary<K, V>;
+}
+
+impl<K, V> fmt::Debug for CFMutableDictionary<K, V>
+where
+    K: ObjectType + fmt::Debug,
+    V: ObjectType + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFDictionary<K, V>).fmt(f)
+    }
+}
+
+impl<K: ObjectType, V: ObjectType> CFMutableDictionary<K, V> {
+    /// Creates an empty mutable dictionary.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty mutable dictionary that can contain at most
+    /// `capacity` pairs, where 0 means it has no limit.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarycreatemutable(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryCreateMutable")]
+    pub fn with_capacity(capacity: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFDictionaryCreateMutable(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+                key_call_backs: *const CFDictionaryKeyCallBacks,
+                value_call_backs: *const CFDictionaryValueCallBacks,
+            ) -> *const CFType;
+        }
+
+        unsafe {
+            let dict = CFDictionaryCreateMutable(
+                None,
+                capacity,
+                &kCFTypeDictionaryKeyCallBacks,
+                &kCFTypeDictionaryValueCallBacks,
+            );
+            assert!(!dict.is_null(), "Could not create CFMutableDictionary");
+            Arc::from_raw(dict.cast())
+        }
+    }
+
+    /// Sets the value for `key` to `value`, replacing any existing value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionarysetvalue(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFDictionarySetValue")]
+    pub fn insert(&mut self, key: &K, value: &V) {
+        extern "C" {
+            fn CFDictionarySetValue(the_dict: &CFType, key: *const c_void, value: *const c_void);
+        }
+        unsafe { CFDictionarySetValue(self, (key as *const K).cast(), (value as *const V).cast()) }
+    }
+
+    /// Removes the value for `key`, returning it if there was one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionaryremovevalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryRemoveValue")]
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        extern "C" {
+            fn CFDictionaryRemoveValue(the_dict: &CFType, key: *const c_void);
+        }
+
+        let value = Arc::retain(self.get(key)?);
+        unsafe { CFDictionaryRemoveValue(self, (key as *const K).cast()) };
+        Some(value)
+    }
+
+    /// Removes all key-value pairs from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfdictionaryremoveallvalues(_:)).
+    #[inline]
+    #[doc(alias = "CFDictionaryRemoveAllValues")]
+    pub fn clear(&mut self) {
+        extern "C" {
+            fn CFDictionaryRemoveAllValues(the_dict: &CFType);
+        }
+        unsafe { CFDictionaryRemoveAllValues(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFString;
+
+    #[test]
+    fn from_pairs() {
+        let (a, b, c) = (
+            CFString::from_str("a"),
+            CFString::from_str("b"),
+            CFString::from_str("c"),
+        );
+        let dict = CFDictionary::from_pairs(&[(&*a, &*b), (&*b, &*c)]);
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!(*dict.get(&a).unwrap(), *"b");
+        assert_eq!(*dict.get(&CFString::from_str("b")).unwrap(), *"c");
+        assert!(dict.get(&c).is_none());
+        assert!(dict.contains_key(&a));
+        assert!(!dict.contains_key(&c));
+
+        let mut pairs: Vec<(String, String)> = dict
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        pairs.sort();
+        assert_eq!(pairs, [("a".into(), "b".into()), ("b".into(), "c".into())]);
+    }
+
+    #[test]
+    fn empty() {
+        let dict = CFDictionary::<CFString, CFString>::from_pairs(&[]);
+        assert!(dict.is_empty());
+        assert_eq!(dict.iter().count(), 0);
+    }
+
+    #[test]
+    fn mutate() {
+        let key = CFString::from_str("key");
+        let mut dict = CFMutableDictionary::<CFString, CFString>::new();
+        let d = Arc::get_mut(&mut dict).unwrap();
+
+        d.insert(&key, &CFString::from_str("value"));
+        assert_eq!(*d.get(&key).unwrap(), *"value");
+
+        d.insert(&key, &CFString::from_str("replaced"));
+        assert_eq!(d.len(), 1);
+
+        let removed = d.remove(&key).unwrap();
+        assert_eq!(*removed, *"replaced");
+        assert!(d.remove(&key).is_none());
+
+        d.insert(&key, &removed);
+        d.clear();
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn untyped_downcast() {
+        let key = CFString::from_str("key");
+        let dict = CFDictionary::from_pairs(&[(&*key, &*key)]);
+
+        let untyped: Arc<CFType> = CFType::retain(&dict);
+        let untyped = untyped.downcast::<CFDictionary<CFType, CFType>>().unwrap();
+
+        let value = untyped.get(&key).unwrap();
+        assert_eq!(*value.downcast_ref::<CFString>().unwrap(), *"key");
+    }
+}
@@ -0,0 +1,325 @@
+use super::{CFAllocator, CFDictionary, CFIndex, CFString, CFType};
+use crate::core::Arc;
+use std::{error::Error, fmt};
+
+#[cfg(feature = "foundation")]
+use crate::foundation::NSError;
+
+cf_subclass! {
+    /// Information about an error condition including a domain, a
+    /// domain-specific error code, and application-specific information.
+    ///
+    /// This is toll-free bridged with
+    /// [`NSError`](../foundation/struct.NSError.html).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cferror?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cferrorref?language=objc)
+    ///
+    /// # Formatting
+    ///
+    /// The [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html)
+    /// implementation writes the result of
+    /// [`description`](#method.description).
+    #[derive(PartialEq, Hash)]
+    pub class CFError: CFType;
+    type_id = CFErrorGetTypeID;
+}
+
+/// An automatically-reference-counted pointer to a `CFError`.
+///
+/// Documentation:
+/// [Swift](https://developer.apple.com/documentation/corefoundation/cferror?language=swift) |
+/// [Objective-C](https://developer.apple.com/documentation/corefoundation/cferrorref?language=objc)
+pub type CFErrorRef = Arc<CFError>;
+
+impl fmt::Debug for CFError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFError")
+            .field("domain", &self.domain())
+            .field("code", &self.code())
+            .field("description", &self.description())
+            .finish()
+    }
+}
+
+impl fmt::Display for CFError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl Error for CFError {}
+
+macro_rules! domain {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $value:literal
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        #[doc(alias = $value)]
+        pub fn $fn() -> &'static CFString {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static CFString;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+/// Error domains.
+impl CFError {
+    domain! {
+        /// POSIX/BSD errors.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcferrordomainposix).
+        posix_domain "kCFErrorDomainPOSIX"
+    }
+
+    domain! {
+        /// Carbon `OSStatus` errors.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcferrordomainosstatus).
+        os_status_domain "kCFErrorDomainOSStatus"
+    }
+
+    domain! {
+        /// Mach errors.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcferrordomainmach).
+        mach_domain "kCFErrorDomainMach"
+    }
+
+    domain! {
+        /// Cocoa errors.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcferrordomaincocoa).
+        cocoa_domain "kCFErrorDomainCocoa"
+    }
+}
+
+impl CFError {
+    /// Creates an error with `domain`, `code`, and optional `user_info`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorcreate(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFErrorCreate")]
+    pub fn new(
+        domain: &CFString,
+        code: CFIndex,
+        user_info: Option<&CFDictionary<CFString, CFType>>,
+    ) -> Arc<Self> {
+        extern "C" {
+            fn CFErrorCreate(
+                allocator: Option<&CFAllocator>,
+                domain: &CFString,
+                code: CFIndex,
+                user_info: Option<&CFDictionary<CFString, CFType>>,
+            ) -> Option<Arc<CFError>>;
+        }
+        unsafe { CFErrorCreate(None, domain, code, user_info) }.expect("Could not create CFError")
+    }
+
+    /// Converts the result of a function that reports failure through a
+    /// `CFErrorRef` out-parameter.
+    ///
+    /// `error` is the out-parameter passed to the function and `ok` is whether
+    /// the function reported success. Any error written to `error` is taken,
+    /// so it is also released on success.
+    ///
+    /// Functions are not required to provide an error when they fail, so the
+    /// error is `None` if `ok` is `false` but no error was written to `error`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut error = None;
+    /// let ok = unsafe { SomeFunctionWithError(&mut error) };
+    /// CFError::check(&mut error, ok).map_err(|error| error.unwrap_or(fallback))?;
+    /// ```
+    #[inline]
+    pub fn check(error: &mut Option<CFErrorRef>, ok: bool) -> Result<(), Option<CFErrorRef>> {
+        match error.take() {
+            _ if ok => Ok(()),
+            error => Err(error),
+        }
+    }
+}
+
+/// Getting error properties.
+impl CFError {
+    /// Returns the error domain.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorgetdomain(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorGetDomain")]
+    pub fn domain(&self) -> &CFString {
+        extern "C" {
+            fn CFErrorGetDomain(err: &CFError) -> &CFString;
+        }
+        unsafe { CFErrorGetDomain(self) }
+    }
+
+    /// Returns the error code.
+    ///
+    /// Note that errors are domain-specific.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorgetcode(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorGetCode")]
+    pub fn code(&self) -> CFIndex {
+        extern "C" {
+            fn CFErrorGetCode(err: &CFError) -> CFIndex;
+        }
+        unsafe { CFErrorGetCode(self) }
+    }
+
+    /// Returns the user info dictionary, which is empty if the error was
+    /// created without one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorcopyuserinfo(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorCopyUserInfo")]
+    pub fn user_info(&self) -> Arc<CFDictionary<CFString, CFType>> {
+        extern "C" {
+            fn CFErrorCopyUserInfo(err: &CFError) -> Option<Arc<CFDictionary<CFString, CFType>>>;
+        }
+        unsafe { CFErrorCopyUserInfo(self) }.expect("Could not copy CFError user info")
+    }
+}
+
+/// Getting error user info.
+impl CFError {
+    /// Returns a human-presentable description of the error.
+    ///
+    /// If there is no localized description in the user info dictionary, a
+    /// default string is constructed from the domain and code.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorcopydescription(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorCopyDescription")]
+    pub fn description(&self) -> Arc<CFString> {
+        extern "C" {
+            fn CFErrorCopyDescription(err: &CFError) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFErrorCopyDescription(self) }.expect("Could not copy CFError description")
+    }
+
+    /// Returns a human-presentable failure reason for the error.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorcopyfailurereason(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorCopyFailureReason")]
+    pub fn failure_reason(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFErrorCopyFailureReason(err: &CFError) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFErrorCopyFailureReason(self) }
+    }
+
+    /// Returns a human-presentable recovery suggestion for the error.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cferrorcopyrecoverysuggestion(_:)).
+    #[inline]
+    #[doc(alias = "CFErrorCopyRecoverySuggestion")]
+    pub fn recovery_suggestion(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFErrorCopyRecoverySuggestion(err: &CFError) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFErrorCopyRecoverySuggestion(self) }
+    }
+}
+
+/// Toll-free bridging.
+impl CFError {
+    /// Returns this error as an `NSError`, which is the same object.
+    ///
+    /// # Feature Flag
+    ///
+    /// This method requires the **`foundation`**
+    /// [feature flag](../index.html#feature-flags).
+    #[cfg(feature = "foundation")]
+    #[inline]
+    pub fn as_ns_error(&self) -> &NSError<'static> {
+        unsafe { &*(self as *const Self).cast() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFData, CFOptionFlags, CFPropertyListFormat};
+
+    #[test]
+    fn properties() {
+        let domain = CFString::from_str("com.example.fruity");
+        let error = CFError::new(&domain, 42, None);
+
+        assert_eq!(*error.domain(), *domain);
+        assert_eq!(error.code(), 42);
+        assert!(error.user_info().is_empty());
+        assert!(error.failure_reason().is_none());
+        assert!(!error.to_string().is_empty());
+    }
+
+    #[test]
+    fn user_info() {
+        let key = crate::cf_string!("NSLocalizedFailureReason");
+        let reason = CFString::from_str("It broke");
+        let user_info = CFDictionary::<CFString, CFType>::from_pairs(&[(key, &reason)]);
+
+        let error = CFError::new(CFError::posix_domain(), 1, Some(&user_info));
+
+        assert_eq!(error.user_info().len(), 1);
+        assert!(*error.failure_reason().unwrap() == "It broke");
+    }
+
+    #[test]
+    fn check() {
+        extern "C" {
+            fn CFPropertyListCreateWithData(
+                allocator: Option<&CFAllocator>,
+                data: &CFData,
+                options: CFOptionFlags,
+                format: *mut CFPropertyListFormat,
+                error: *mut Option<CFErrorRef>,
+            ) -> Option<Arc<CFType>>;
+        }
+
+        let parse = |bytes: &[u8]| {
+            let data = CFData::from_bytes(bytes);
+            let mut error = None;
+            let plist = unsafe {
+                CFPropertyListCreateWithData(None, &data, 0, std::ptr::null_mut(), &mut error)
+            };
+            CFError::check(&mut error, plist.is_some()).map(|_| plist.unwrap())
+        };
+
+        assert!(parse(b"(1, 2)").is_ok());
+
+        let error = parse(b"<?xml version=\"1.0\"?><plist")
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(*error.domain(), *CFError::cocoa_domain());
+    }
+
+    #[test]
+    fn check_missing_error() {
+        assert!(matches!(CFError::check(&mut None, false), Err(None)));
+        assert!(matches!(CFError::check(&mut None, true), Ok(())));
+    }
+
+    #[test]
+    #[cfg(feature = "foundation")]
+    fn bridge() {
+        let error = CFError::new(CFError::posix_domain(), 2, None);
+        let ns_error = error.as_ns_error();
+
+        assert_eq!(ns_error.code(), 2);
+        assert_eq!(ns_error.as_cf_error(), &*error);
+    }
+}
@@ -0,0 +1,208 @@
+use super::{CFAllocator, CFString, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// Information about linguistic, cultural, and technological conventions
+    /// for use in formatting data for presentation.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cflocale?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cflocaleref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFLocale: CFType;
+    type_id = CFLocaleGetTypeID;
+}
+
+impl fmt::Debug for CFLocale {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CFLocale").field(&self.identifier()).finish()
+    }
+}
+
+impl fmt::Display for CFLocale {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.identifier().fmt(f)
+    }
+}
+
+macro_rules! locale_keys {
+    ($(
+        $(#[$docs:meta])+
+        $variant:ident = $name:ident;
+    )+) => {
+        /// Keys for values of a [`CFLocale`](struct.CFLocale.html).
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalekey).
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum CFLocaleKey {
+            $(
+                $(#[$docs])+
+                $variant,
+            )+
+        }
+
+        impl CFLocaleKey {
+            /// Returns the string constant for this key, such as
+            /// `kCFLocaleLanguageCode`.
+            #[inline]
+            pub fn as_cf_string(self) -> &'static CFString {
+                extern "C" {
+                    $(static $name: &'static CFString;)+
+                }
+                unsafe {
+                    match self {
+                        $(Self::$variant => $name,)+
+                    }
+                }
+            }
+        }
+    };
+}
+
+locale_keys! {
+    /// The locale identifier, as a `CFString`.
+    Identifier = kCFLocaleIdentifier;
+    /// The language code, as a `CFString`.
+    LanguageCode = kCFLocaleLanguageCode;
+    /// The country code, as a `CFString`.
+    CountryCode = kCFLocaleCountryCode;
+    /// The script code, as a `CFString`.
+    ScriptCode = kCFLocaleScriptCode;
+    /// The variant code, as a `CFString`.
+    VariantCode = kCFLocaleVariantCode;
+    /// The exemplar character set, as a `CFCharacterSet`.
+    ExemplarCharacterSet = kCFLocaleExemplarCharacterSet;
+    /// The calendar, as a `CFCalendar`.
+    Calendar = kCFLocaleCalendar;
+    /// The calendar identifier, as a `CFString`.
+    CalendarIdentifier = kCFLocaleCalendarIdentifier;
+    /// The collation identifier, as a `CFString`.
+    CollationIdentifier = kCFLocaleCollationIdentifier;
+    /// Whether the locale uses the metric system, as a `CFBoolean`.
+    UsesMetricSystem = kCFLocaleUsesMetricSystem;
+    /// The measurement system, as a `CFString`.
+    MeasurementSystem = kCFLocaleMeasurementSystem;
+    /// The decimal separator, as a `CFString`.
+    DecimalSeparator = kCFLocaleDecimalSeparator;
+    /// The grouping separator, as a `CFString`.
+    GroupingSeparator = kCFLocaleGroupingSeparator;
+    /// The currency symbol, as a `CFString`.
+    CurrencySymbol = kCFLocaleCurrencySymbol;
+    /// The ISO 4217 currency code, as a `CFString`.
+    CurrencyCode = kCFLocaleCurrencyCode;
+}
+
+/// Getting locales.
+impl CFLocale {
+    /// Returns a copy of the user's current locale.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalecopycurrent()).
+    #[inline]
+    #[doc(alias = "CFLocaleCopyCurrent")]
+    pub fn current() -> Arc<CFLocale> {
+        extern "C" {
+            fn CFLocaleCopyCurrent() -> Option<Arc<CFLocale>>;
+        }
+        unsafe { CFLocaleCopyCurrent() }.expect("Could not get current CFLocale")
+    }
+
+    /// Returns the root locale, which has no language or country.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalegetsystem()).
+    #[inline]
+    #[doc(alias = "CFLocaleGetSystem")]
+    pub fn system() -> &'static CFLocale {
+        extern "C" {
+            fn CFLocaleGetSystem() -> &'static CFLocale;
+        }
+        unsafe { CFLocaleGetSystem() }
+    }
+
+    /// Creates a locale for `identifier`, such as `en_US`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalecreate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFLocaleCreate")]
+    pub fn with_identifier(identifier: &CFString) -> Arc<CFLocale> {
+        extern "C" {
+            fn CFLocaleCreate(
+                allocator: Option<&CFAllocator>,
+                locale_identifier: &CFString,
+            ) -> Option<Arc<CFLocale>>;
+        }
+        unsafe { CFLocaleCreate(None, identifier) }.expect("Could not create CFLocale")
+    }
+}
+
+/// Getting locale properties.
+impl CFLocale {
+    /// Returns the identifier of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalegetidentifier(_:)).
+    #[inline]
+    #[doc(alias = "CFLocaleGetIdentifier")]
+    pub fn identifier(&self) -> &CFString {
+        extern "C" {
+            fn CFLocaleGetIdentifier(locale: &CFLocale) -> &CFString;
+        }
+        unsafe { CFLocaleGetIdentifier(self) }
+    }
+
+    /// Returns the value for `key`, or `None` if `self` has none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cflocalegetvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFLocaleGetValue")]
+    pub fn object_for_key(&self, key: CFLocaleKey) -> Option<Arc<CFType>> {
+        extern "C" {
+            fn CFLocaleGetValue<'a>(locale: &'a CFLocale, key: &CFString) -> Option<&'a CFType>;
+        }
+
+        // The result follows the get rule, so it must be retained.
+        unsafe { CFLocaleGetValue(self, key.as_cf_string()) }.map(Arc::retain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFBoolean;
+
+    #[test]
+    fn current_round_trip() {
+        let current = CFLocale::current();
+        let copy = CFLocale::with_identifier(current.identifier());
+        assert_eq!(*copy.identifier(), *current.identifier());
+        assert_eq!(copy.to_string(), current.identifier().to_string());
+    }
+
+    #[test]
+    fn object_for_key() {
+        let locale = CFLocale::with_identifier(&CFString::from_str("fr_FR"));
+
+        let get_string = |key| {
+            let value = locale.object_for_key(key).unwrap();
+            value.downcast_ref::<CFString>().unwrap().to_string()
+        };
+        assert_eq!(get_string(CFLocaleKey::Identifier), "fr_FR");
+        assert_eq!(get_string(CFLocaleKey::LanguageCode), "fr");
+        assert_eq!(get_string(CFLocaleKey::CountryCode), "FR");
+        assert_eq!(get_string(CFLocaleKey::CurrencyCode), "EUR");
+        assert_eq!(get_string(CFLocaleKey::DecimalSeparator), ",");
+
+        let metric = locale
+            .object_for_key(CFLocaleKey::UsesMetricSystem)
+            .unwrap();
+        assert!(metric.downcast_ref::<CFBoolean>().unwrap().value());
+    }
+
+    #[test]
+    fn system() {
+        let system = CFLocale::system();
+        assert!(system.identifier().is_empty());
+        assert!(system.object_for_key(CFLocaleKey::CountryCode).is_none());
+    }
+}
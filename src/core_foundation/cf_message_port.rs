@@ -0,0 +1,409 @@
+use super::{
+    Boolean, CFAllocator, CFData, CFIndex, CFRunLoop, CFRunLoopSource, CFString, CFTimeInterval,
+    CFType,
+};
+use crate::_priv::{abort_on_panic, cfcontext};
+use crate::core::Arc;
+use std::{error::Error, ffi::c_void, fmt, ptr, sync, time::Duration};
+
+cf_subclass! {
+    /// A communications channel between threads or processes on the same
+    /// machine.
+    ///
+    /// A local port receives messages from remote ports with the same name
+    /// once its [run loop source](#method.create_run_loop_source) is added to
+    /// a run loop.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmessageport?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmessageportref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMessagePort: CFType;
+    type_id = CFMessagePortGetTypeID;
+}
+
+/// An error returned by
+/// [`CFMessagePort::send_request`](struct.CFMessagePort.html#method.send_request).
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportsendrequest(_:_:_:_:_:_:_:)).
+#[repr(i32)] // SInt32
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFMessagePortError {
+    /// The message could not be sent before the send timeout.
+    #[doc(alias = "kCFMessagePortSendTimeout")]
+    SendTimeout = -1,
+    /// No reply was received before the receive timeout.
+    #[doc(alias = "kCFMessagePortReceiveTimeout")]
+    ReceiveTimeout = -2,
+    /// The port is invalid.
+    #[doc(alias = "kCFMessagePortIsInvalid")]
+    IsInvalid = -3,
+    /// An error occurred while sending the message.
+    #[doc(alias = "kCFMessagePortTransportError")]
+    TransportError = -4,
+    /// The port became invalid while waiting for a reply.
+    #[doc(alias = "kCFMessagePortBecameInvalidError")]
+    BecameInvalid = -5,
+}
+
+impl CFMessagePortError {
+    #[inline]
+    fn from_code(code: i32) -> Self {
+        match code {
+            -1 => Self::SendTimeout,
+            -2 => Self::ReceiveTimeout,
+            -3 => Self::IsInvalid,
+            -5 => Self::BecameInvalid,
+            _ => Self::TransportError,
+        }
+    }
+}
+
+impl fmt::Display for CFMessagePortError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Self::SendTimeout => "timed out sending message",
+            Self::ReceiveTimeout => "timed out waiting for reply",
+            Self::IsInvalid => "message port is invalid",
+            Self::TransportError => "message transport failed",
+            Self::BecameInvalid => "message port became invalid",
+        };
+        message.fmt(f)
+    }
+}
+
+impl Error for CFMessagePortError {}
+
+impl fmt::Debug for CFMessagePort {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFMessagePort")
+            .field("name", &self.name())
+            .field("is_remote", &self.is_remote())
+            .field("is_valid", &self.is_valid())
+            .finish()
+    }
+}
+
+#[repr(C)]
+struct CFMessagePortContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+}
+
+type CFMessagePortCallBack = unsafe extern "C" fn(
+    local: &CFMessagePort,
+    msgid: i32,
+    data: Option<&CFData>,
+    info: *mut c_void,
+) -> Option<Arc<CFData>>;
+
+/// Calls the handler of a local port whose context info is a `sync::Arc<F>`.
+unsafe extern "C" fn call<F>(
+    _local: &CFMessagePort,
+    msgid: i32,
+    data: Option<&CFData>,
+    info: *mut c_void,
+) -> Option<Arc<CFData>>
+where
+    F: Fn(i32, &CFData) -> Option<Arc<CFData>>,
+{
+    let handler = &*info.cast::<F>();
+
+    // Ownership of the reply is transferred to Core Foundation.
+    abort_on_panic(|| match data {
+        Some(data) => handler(msgid, data),
+        None => handler(msgid, &CFData::from_bytes(&[])),
+    })
+}
+
+/// Creating message ports.
+impl CFMessagePort {
+    /// Creates a local port named `name` that replies to each message with
+    /// the result of `handler`, which is given the message ID and data.
+    ///
+    /// Returns `None` if a local port named `name` already exists.
+    ///
+    /// `handler` must be `Send + Sync` because the port's run loop source may
+    /// be added to the run loop of any thread. If it panics, the process is
+    /// aborted since unwinding into Core Foundation is undefined behavior.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportcreatelocal(_:_:_:_:_:)).
+    #[doc(alias = "CFMessagePortCreateLocal")]
+    pub fn local<F>(name: &CFString, handler: F) -> Option<Arc<Self>>
+    where
+        F: Fn(i32, &CFData) -> Option<Arc<CFData>> + Send + Sync + 'static,
+    {
+        extern "C" {
+            fn CFMessagePortCreateLocal(
+                allocator: Option<&CFAllocator>,
+                name: &CFString,
+                callout: CFMessagePortCallBack,
+                context: &CFMessagePortContext,
+                should_free_info: &mut Boolean,
+            ) -> Option<Arc<CFMessagePort>>;
+        }
+
+        let info = sync::Arc::new(handler);
+        let context = CFMessagePortContext {
+            version: 0,
+            info: sync::Arc::as_ptr(&info) as *mut c_void,
+            retain: Some(cfcontext::retain::<F>),
+            release: Some(cfcontext::release::<F>),
+            copy_description: Some(cfcontext::copy_description),
+        };
+
+        // The port retains `info` through the context, so our reference can
+        // be dropped once it is created.
+        let mut should_free_info = 0;
+        let port = unsafe {
+            CFMessagePortCreateLocal(None, name, call::<F>, &context, &mut should_free_info)
+        };
+
+        // If a port with the same name exists in this process, it is returned
+        // instead without using `handler`.
+        if should_free_info != 0 {
+            None
+        } else {
+            port
+        }
+    }
+
+    /// Creates a port for sending messages to the local port named `name`.
+    ///
+    /// Returns `None` if there is no local port named `name`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportcreateremote(_:_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortCreateRemote")]
+    pub fn remote(name: &CFString) -> Option<Arc<Self>> {
+        extern "C" {
+            fn CFMessagePortCreateRemote(
+                allocator: Option<&CFAllocator>,
+                name: &CFString,
+            ) -> Option<Arc<CFMessagePort>>;
+        }
+        unsafe { CFMessagePortCreateRemote(None, name) }
+    }
+}
+
+/// Using message ports.
+impl CFMessagePort {
+    /// Returns the name of `self`, or `None` if it has none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportgetname(_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortGetName")]
+    pub fn name(&self) -> Option<&CFString> {
+        extern "C" {
+            fn CFMessagePortGetName(ms: &CFMessagePort) -> Option<&CFString>;
+        }
+        unsafe { CFMessagePortGetName(self) }
+    }
+
+    /// Returns `true` if `self` was created with
+    /// [`remote`](#method.remote).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportisremote(_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortIsRemote")]
+    pub fn is_remote(&self) -> bool {
+        extern "C" {
+            fn CFMessagePortIsRemote(ms: &CFMessagePort) -> Boolean;
+        }
+        unsafe { CFMessagePortIsRemote(self) != 0 }
+    }
+
+    /// Returns `true` if `self` has not been invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportisvalid(_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortIsValid")]
+    pub fn is_valid(&self) -> bool {
+        extern "C" {
+            fn CFMessagePortIsValid(ms: &CFMessagePort) -> Boolean;
+        }
+        unsafe { CFMessagePortIsValid(self) != 0 }
+    }
+
+    /// Stops `self` from sending or receiving messages. For a local port,
+    /// this also releases its name.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportinvalidate(_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortInvalidate")]
+    pub fn invalidate(&self) {
+        extern "C" {
+            fn CFMessagePortInvalidate(ms: &CFMessagePort);
+        }
+        unsafe { CFMessagePortInvalidate(self) }
+    }
+
+    /// Creates a run loop source for receiving messages on the local port
+    /// `self`, with priority `order`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportcreaterunloopsource(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFMessagePortCreateRunLoopSource")]
+    pub fn create_run_loop_source(&self, order: CFIndex) -> Arc<CFRunLoopSource> {
+        extern "C" {
+            fn CFMessagePortCreateRunLoopSource(
+                allocator: Option<&CFAllocator>,
+                local: &CFMessagePort,
+                order: CFIndex,
+            ) -> Option<Arc<CFRunLoopSource>>;
+        }
+        unsafe { CFMessagePortCreateRunLoopSource(None, self, order) }
+            .expect("Could not create CFRunLoopSource")
+    }
+
+    /// Sends `data` with `msgid` to the remote port `self`.
+    ///
+    /// If `receive_timeout` is `Some`, this waits for and returns the reply by
+    /// running the current run loop in its default mode. Otherwise, this
+    /// returns `None` once the message is sent.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfmessageportsendrequest(_:_:_:_:_:_:_:)).
+    #[doc(alias = "CFMessagePortSendRequest")]
+    pub fn send_request(
+        &self,
+        msgid: i32,
+        data: &CFData,
+        send_timeout: Duration,
+        receive_timeout: Option<Duration>,
+    ) -> Result<Option<Arc<CFData>>, CFMessagePortError> {
+        extern "C" {
+            fn CFMessagePortSendRequest(
+                remote: &CFMessagePort,
+                msgid: i32,
+                data: &CFData,
+                send_timeout: CFTimeInterval,
+                rcv_timeout: CFTimeInterval,
+                reply_mode: Option<&CFString>,
+                return_data: *mut Option<Arc<CFData>>,
+            ) -> i32;
+        }
+
+        let mut reply = None;
+        let (receive_timeout, reply_mode, return_data) = match receive_timeout {
+            Some(timeout) => (
+                timeout.as_secs_f64(),
+                Some(CFRunLoop::default_mode()),
+                &mut reply as *mut Option<Arc<CFData>>,
+            ),
+            None => (0.0, None, ptr::null_mut()),
+        };
+
+        let code = unsafe {
+            CFMessagePortSendRequest(
+                self,
+                msgid,
+                data,
+                send_timeout.as_secs_f64(),
+                receive_timeout,
+                reply_mode,
+                return_data,
+            )
+        };
+
+        match code {
+            0 => Ok(reply),
+            code => Err(CFMessagePortError::from_code(code)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFRunLoopRunResult;
+    use std::{process, sync::mpsc, thread};
+
+    fn port_name(suffix: &str) -> Arc<CFString> {
+        CFString::from_str(&format!(
+            "com.example.fruity.tests.{}.{}",
+            process::id(),
+            suffix
+        ))
+    }
+
+    #[test]
+    fn round_trip() {
+        let name = port_name("round-trip");
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn({
+            let name = name.clone();
+            move || {
+                let local = CFMessagePort::local(&name, |msgid, data| {
+                    let mut reply = data.to_vec();
+                    reply.reverse();
+                    reply.push(msgid as u8);
+                    Some(CFData::from_bytes(&reply))
+                })
+                .unwrap();
+                assert!(!local.is_remote());
+
+                let source = local.create_run_loop_source(0);
+                source.add_to(&CFRunLoop::current(), CFRunLoop::default_mode());
+                sender.send(CFRunLoop::current()).unwrap();
+
+                let result = CFRunLoop::run_in_mode(
+                    CFRunLoop::default_mode(),
+                    Duration::from_secs(30),
+                    false,
+                );
+                local.invalidate();
+                result
+            }
+        });
+
+        let run_loop = receiver.recv().unwrap();
+        let remote = CFMessagePort::remote(&name).unwrap();
+        assert!(remote.is_remote());
+        assert_eq!(*remote.name().unwrap(), *name);
+
+        let reply = remote
+            .send_request(
+                7,
+                &CFData::from_bytes(b"ping"),
+                Duration::from_secs(5),
+                Some(Duration::from_secs(5)),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(&**reply, b"gnip\x07");
+
+        run_loop.stop();
+        assert_eq!(handle.join().unwrap(), CFRunLoopRunResult::Stopped);
+
+        // The remote port is invalidated asynchronously once the local port
+        // is gone, so the exact error depends on timing.
+        let result = remote.send_request(
+            7,
+            &CFData::from_bytes(b"ping"),
+            Duration::from_millis(100),
+            Some(Duration::from_millis(100)),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn duplicate_name() {
+        let name = port_name("duplicate");
+        let local = CFMessagePort::local(&name, |_, _| None).unwrap();
+        assert!(CFMessagePort::local(&name, |_, _| None).is_none());
+
+        local.invalidate();
+        assert!(!local.is_valid());
+    }
+
+    #[test]
+    fn missing_remote() {
+        assert!(CFMessagePort::remote(&port_name("missing")).is_none());
+    }
+}
@@ -0,0 +1,377 @@
+use super::{Boolean, CFDictionary, CFIndex, CFString, CFType};
+use crate::_priv::abort_on_panic;
+use std::{
+    collections::BTreeMap,
+    ffi::c_void,
+    fmt,
+    sync::{
+        self,
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+cf_subclass! {
+    /// A notification dispatch table through which notifications are
+    /// broadcast to registered observers.
+    ///
+    /// All notification centers are process-wide singletons.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfnotificationcenter?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfnotificationcenterref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFNotificationCenter: CFType;
+    type_id = CFNotificationCenterGetTypeID;
+}
+
+impl fmt::Debug for CFNotificationCenter {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFType).fmt(f)
+    }
+}
+
+type CFNotificationCallback = unsafe extern "C" fn(
+    center: &CFNotificationCenter,
+    observer: *mut c_void,
+    name: &CFString,
+    object: *const c_void,
+    user_info: Option<&CFDictionary<CFString, CFType>>,
+);
+
+/// `CFNotificationSuspensionBehaviorDeliverImmediately`.
+const DELIVER_IMMEDIATELY: CFIndex = 4;
+
+type Callback = sync::Arc<dyn Fn(&CFString, Option<&CFDictionary<CFString, CFType>>) + Send + Sync>;
+
+// The closures of all observers, keyed by their observer pointers.
+//
+// Closures are looked up for every notification rather than passed as the
+// observer itself, so that a notification posted on another thread while its
+// observer is being removed either finds nothing or keeps the closure alive
+// until it returns.
+static CALLBACKS: Mutex<BTreeMap<usize, Callback>> = Mutex::new(BTreeMap::new());
+
+// The last observer pointer given to an observer. Zero is never used.
+static LAST_OBSERVER: AtomicUsize = AtomicUsize::new(0);
+
+#[inline]
+fn callbacks() -> sync::MutexGuard<'static, BTreeMap<usize, Callback>> {
+    // Closures are called outside of the lock, so it cannot be poisoned by
+    // them.
+    CALLBACKS.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+unsafe extern "C" fn call(
+    _center: &CFNotificationCenter,
+    observer: *mut c_void,
+    name: &CFString,
+    _object: *const c_void,
+    user_info: Option<&CFDictionary<CFString, CFType>>,
+) {
+    let callback = callbacks().get(&(observer as usize)).cloned();
+    if let Some(callback) = callback {
+        abort_on_panic(|| callback(name, user_info));
+    }
+}
+
+/// Getting notification centers.
+impl CFNotificationCenter {
+    /// Returns the center for notifications within the current process.
+    ///
+    /// Observers are called synchronously on the posting thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnotificationcentergetlocalcenter()).
+    #[inline]
+    #[doc(alias = "CFNotificationCenterGetLocalCenter")]
+    pub fn local() -> &'static CFNotificationCenter {
+        extern "C" {
+            fn CFNotificationCenterGetLocalCenter() -> &'static CFNotificationCenter;
+        }
+        unsafe { CFNotificationCenterGetLocalCenter() }
+    }
+
+    /// Returns the center for system-wide Darwin notifications.
+    ///
+    /// Darwin notifications have no object or user info. Observers are called
+    /// on the main thread, which must be running its run loop.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnotificationcentergetdarwinnotifycenter()).
+    #[inline]
+    #[doc(alias = "CFNotificationCenterGetDarwinNotifyCenter")]
+    pub fn darwin_notify() -> &'static CFNotificationCenter {
+        extern "C" {
+            fn CFNotificationCenterGetDarwinNotifyCenter() -> &'static CFNotificationCenter;
+        }
+        unsafe { CFNotificationCenterGetDarwinNotifyCenter() }
+    }
+
+    /// Returns the center for notifications between processes.
+    ///
+    /// Observers are called on the main thread, which must be running its run
+    /// loop.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnotificationcentergetdistributedcenter()).
+    #[cfg(target_os = "macos")]
+    #[inline]
+    #[doc(alias = "CFNotificationCenterGetDistributedCenter")]
+    pub fn distributed() -> &'static CFNotificationCenter {
+        extern "C" {
+            fn CFNotificationCenterGetDistributedCenter() -> &'static CFNotificationCenter;
+        }
+        unsafe { CFNotificationCenterGetDistributedCenter() }
+    }
+}
+
+/// Posting and observing notifications.
+impl CFNotificationCenter {
+    /// Posts a notification named `name` to observers of `self`.
+    ///
+    /// If `deliver_immediately` is `true`, the notification is delivered to
+    /// all observers even if they are suspended. This only applies to the
+    /// distributed center.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnotificationcenterpostnotification(_:_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFNotificationCenterPostNotification")]
+    pub fn post(
+        &self,
+        name: &CFString,
+        object: Option<&CFType>,
+        user_info: Option<&CFDictionary<CFString, CFType>>,
+        deliver_immediately: bool,
+    ) {
+        extern "C" {
+            fn CFNotificationCenterPostNotification(
+                center: &CFNotificationCenter,
+                name: &CFString,
+                object: Option<&CFType>,
+                user_info: Option<&CFDictionary<CFString, CFType>>,
+                deliver_immediately: Boolean,
+            );
+        }
+        unsafe {
+            CFNotificationCenterPostNotification(
+                self,
+                name,
+                object,
+                user_info,
+                deliver_immediately as Boolean,
+            )
+        }
+    }
+
+    /// Registers `f` to be called with the name and user info of each
+    /// notification named `name`, or of all notifications if `name` is `None`.
+    ///
+    /// The observer is removed when the returned token is dropped.
+    ///
+    /// Observers of the Darwin and distributed centers are always notified
+    /// immediately, even while the application is in the background. If `f`
+    /// panics, the process is aborted since unwinding into Core Foundation is
+    /// undefined behavior.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnotificationcenteraddobserver(_:_:_:_:_:_:)).
+    #[doc(alias = "CFNotificationCenterAddObserver")]
+    pub fn add_observer<F>(&'static self, name: Option<&CFString>, f: F) -> ObserverToken
+    where
+        F: Fn(&CFString, Option<&CFDictionary<CFString, CFType>>) + Send + Sync + 'static,
+    {
+        extern "C" {
+            fn CFNotificationCenterAddObserver(
+                center: &CFNotificationCenter,
+                observer: *const c_void,
+                call_back: CFNotificationCallback,
+                name: Option<&CFString>,
+                object: *const c_void,
+                suspension_behavior: CFIndex,
+            );
+        }
+
+        // The observer pointer is only used by the center to identify the
+        // observer, and is never dereferenced.
+        let observer = LAST_OBSERVER.fetch_add(1, Ordering::Relaxed) + 1;
+        callbacks().insert(observer, sync::Arc::new(f));
+
+        unsafe {
+            CFNotificationCenterAddObserver(
+                self,
+                observer as *const c_void,
+                call,
+                name,
+                std::ptr::null(),
+                DELIVER_IMMEDIATELY,
+            );
+        }
+
+        ObserverToken {
+            center: self,
+            observer,
+        }
+    }
+}
+
+/// A registered [`CFNotificationCenter`](struct.CFNotificationCenter.html)
+/// observer, which is removed when this is dropped.
+///
+/// The closure is dropped once the observer is removed and any calls to it on
+/// other threads have returned.
+#[must_use = "the observer is removed when the token is dropped"]
+pub struct ObserverToken {
+    center: &'static CFNotificationCenter,
+    observer: usize,
+}
+
+// Notification centers are thread-safe.
+unsafe impl Send for ObserverToken {}
+unsafe impl Sync for ObserverToken {}
+
+impl fmt::Debug for ObserverToken {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ObserverToken")
+            .field("center", &self.center)
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
+
+impl Drop for ObserverToken {
+    #[inline]
+    #[doc(alias = "CFNotificationCenterRemoveEveryObserver")]
+    fn drop(&mut self) {
+        extern "C" {
+            fn CFNotificationCenterRemoveEveryObserver(
+                center: &CFNotificationCenter,
+                observer: *const c_void,
+            );
+        }
+
+        unsafe {
+            CFNotificationCenterRemoveEveryObserver(self.center, self.observer as *const c_void);
+        }
+
+        let callback = callbacks().remove(&self.observer);
+
+        // Drop the closure outside of the lock, in case it owns another token.
+        drop(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{mpsc, Arc},
+        thread,
+    };
+
+    #[test]
+    fn local() {
+        let center = CFNotificationCenter::local();
+        let name = CFString::from_str("com.example.fruity.local");
+        let other = CFString::from_str("com.example.fruity.other");
+
+        let key = CFString::from_str("key");
+        let value = CFString::from_str("value");
+        let user_info = CFDictionary::<CFString, CFType>::from_pairs(&[(&key, &value)]);
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let token = center.add_observer(Some(&name), {
+            let count = count.clone();
+            let expected = name.clone();
+            move |name, user_info| {
+                assert_eq!(*name, *expected);
+                assert_eq!(user_info.unwrap().len(), 1);
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        center.post(&name, None, Some(&user_info), true);
+        center.post(&other, None, Some(&user_info), true);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        drop(token);
+        center.post(&name, None, Some(&user_info), true);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn zero_sized_observers() {
+        static FIRST: AtomicUsize = AtomicUsize::new(0);
+        static SECOND: AtomicUsize = AtomicUsize::new(0);
+
+        let center = CFNotificationCenter::local();
+        let name = CFString::from_str("com.example.fruity.zero_sized");
+
+        // Neither closure captures anything, so both are zero-sized.
+        let first = center.add_observer(Some(&name), |_, _| {
+            FIRST.fetch_add(1, Ordering::SeqCst);
+        });
+        let second = center.add_observer(Some(&name), |_, _| {
+            SECOND.fetch_add(1, Ordering::SeqCst);
+        });
+
+        center.post(&name, None, None, true);
+        assert_eq!(FIRST.load(Ordering::SeqCst), 1);
+        assert_eq!(SECOND.load(Ordering::SeqCst), 1);
+
+        // Removing one observer leaves the other registered.
+        drop(first);
+        center.post(&name, None, None, true);
+        assert_eq!(FIRST.load(Ordering::SeqCst), 1);
+        assert_eq!(SECOND.load(Ordering::SeqCst), 2);
+
+        drop(second);
+        center.post(&name, None, None, true);
+        assert_eq!(SECOND.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn remove_during_delivery() {
+        let center = CFNotificationCenter::local();
+
+        let (entered_tx, entered_rx) = mpsc::channel();
+        let (resume_tx, resume_rx) = mpsc::channel::<()>();
+        let (entered_tx, resume_rx) = (Mutex::new(entered_tx), Mutex::new(resume_rx));
+        let captured = String::from("captured");
+
+        let name = CFString::from_str("com.example.fruity.remove_during_delivery");
+        let token = center.add_observer(Some(&name), move |_, _| {
+            entered_tx.lock().unwrap().send(()).unwrap();
+            resume_rx.lock().unwrap().recv().unwrap();
+
+            // The closure is still alive after its token is dropped.
+            assert_eq!(captured, "captured");
+        });
+
+        let poster = thread::spawn(|| {
+            let name = CFString::from_str("com.example.fruity.remove_during_delivery");
+            CFNotificationCenter::local().post(&name, None, None, true);
+        });
+
+        entered_rx.recv().unwrap();
+        drop(token);
+        resume_tx.send(()).unwrap();
+        poster.join().unwrap();
+    }
+
+    #[test]
+    fn darwin_notify() {
+        let center = CFNotificationCenter::darwin_notify();
+        let name = CFString::from_str("com.example.fruity.darwin");
+
+        // Darwin notifications are delivered to the main thread's run loop,
+        // which cannot be run from a test thread, so only check that
+        // registering, posting, and removing do not fail. Delivery is tested
+        // by `tests/darwin_notify.rs`.
+        let token = center.add_observer(Some(&name), |_, user_info| {
+            assert!(user_info.is_none());
+        });
+        center.post(&name, None, None, true);
+        drop(token);
+
+        assert_ne!(*center, *CFNotificationCenter::local());
+    }
+}
@@ -0,0 +1,380 @@
+use super::{Boolean, CFAllocator, CFComparisonResult, CFIndex, CFType};
+use crate::core::Arc;
+use std::{cmp::Ordering, ffi::c_void, fmt, mem::MaybeUninit, ptr};
+
+cf_subclass! {
+    /// An object wrapper for primitive scalar numeric values.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfnumber?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfnumberref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFNumber: CFType;
+    type_id = CFNumberGetTypeID;
+}
+
+/// Flags used to specify the number type a `CFNumber` stores or is converted
+/// to.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbertype).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum CFNumberType {
+    SInt8 = 1,
+    SInt16 = 2,
+    SInt32 = 3,
+    SInt64 = 4,
+    Float32 = 5,
+    Float64 = 6,
+    Char = 7,
+    Short = 8,
+    Int = 9,
+    Long = 10,
+    LongLong = 11,
+    Float = 12,
+    Double = 13,
+    CFIndex = 14,
+    NSInteger = 15,
+    CGFloat = 16,
+}
+
+extern "C" {
+    fn CFNumberCreate(
+        allocator: Option<&CFAllocator>,
+        the_type: CFNumberType,
+        value_ptr: *const c_void,
+    ) -> *const CFNumber;
+
+    fn CFNumberGetValue(
+        number: &CFNumber,
+        the_type: CFNumberType,
+        value_ptr: *mut c_void,
+    ) -> Boolean;
+}
+
+impl PartialOrd for CFNumber {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.compare(other).into())
+    }
+}
+
+impl fmt::Debug for CFNumber {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for CFNumber {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_float_type() {
+            self.to_f64_lossy().fmt(f)
+        } else {
+            self.to_i64_lossy().fmt(f)
+        }
+    }
+}
+
+macro_rules! from_impls {
+    ($($ty:ty, $ctor:ident, $getter:ident, $number_type:ident;)+) => {
+        $(
+            impl From<$ty> for Arc<CFNumber> {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    CFNumber::$ctor(value)
+                }
+            }
+        )+
+
+        /// Scalar constructors.
+        impl CFNumber {
+            $(
+                #[doc = concat!("Creates a number object from `", stringify!($ty), "`.")]
+                #[inline]
+                pub fn $ctor(value: $ty) -> Arc<Self> {
+                    unsafe { Self::create(CFNumberType::$number_type, &value as *const $ty as _) }
+                }
+            )+
+        }
+
+        /// Accessing numeric values.
+        impl CFNumber {
+            $(
+                #[doc = concat!("Returns the value of `self` as `", stringify!($ty), "`, or `None`")]
+                /// if the conversion is lossy or out of range.
+                #[inline]
+                pub fn $getter(&self) -> Option<$ty> {
+                    unsafe { self.get(CFNumberType::$number_type) }
+                }
+            )+
+        }
+    };
+}
+
+from_impls! {
+    i8, from_i8, to_i8, SInt8;
+    i16, from_i16, to_i16, SInt16;
+    i32, from_i32, to_i32, SInt32;
+    i64, from_i64, to_i64, SInt64;
+    f32, from_f32, to_f32, Float32;
+    f64, from_f64, to_f64, Float64;
+    CFIndex, from_cf_index, to_cf_index, CFIndex;
+}
+
+impl CFNumber {
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbercreate(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFNumberCreate")]
+    unsafe fn create(number_type: CFNumberType, value: *const c_void) -> Arc<Self> {
+        let number = CFNumberCreate(None, number_type, value);
+        assert!(!number.is_null(), "Could not create CFNumber");
+        Arc::from_raw(number)
+    }
+
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbergetvalue(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFNumberGetValue")]
+    unsafe fn get<T>(&self, number_type: CFNumberType) -> Option<T> {
+        let (value, exact) = self.get_lossy::<T>(number_type);
+        if exact {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn get_lossy<T>(&self, number_type: CFNumberType) -> (T, bool) {
+        let mut value = MaybeUninit::<T>::uninit();
+        let exact = CFNumberGetValue(self, number_type, value.as_mut_ptr().cast()) != 0;
+
+        // `CFNumberGetValue` always writes the closest value.
+        (value.assume_init(), exact)
+    }
+
+    /// Returns the value of `self` as `i64`, which may be lossy.
+    #[inline]
+    pub fn to_i64_lossy(&self) -> i64 {
+        unsafe { self.get_lossy(CFNumberType::SInt64).0 }
+    }
+
+    /// Returns the value of `self` as `f64`, which may be lossy.
+    #[inline]
+    pub fn to_f64_lossy(&self) -> f64 {
+        unsafe { self.get_lossy(CFNumberType::Float64).0 }
+    }
+}
+
+/// Number properties.
+impl CFNumber {
+    /// Returns the type used by `self` to store its value.
+    ///
+    /// This may not be the same type the number was created with.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbergettype(_:)).
+    #[inline]
+    #[doc(alias = "CFNumberGetType")]
+    pub fn number_type(&self) -> CFNumberType {
+        extern "C" {
+            fn CFNumberGetType(number: &CFNumber) -> CFNumberType;
+        }
+        unsafe { CFNumberGetType(self) }
+    }
+
+    /// Returns the number of bytes used by `self` to store its value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbergetbytesize(_:)).
+    #[inline]
+    #[doc(alias = "CFNumberGetByteSize")]
+    pub fn byte_size(&self) -> CFIndex {
+        extern "C" {
+            fn CFNumberGetByteSize(number: &CFNumber) -> CFIndex;
+        }
+        unsafe { CFNumberGetByteSize(self) }
+    }
+
+    /// Returns `true` if `self` stores a floating point value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumberisfloattype(_:)).
+    #[inline]
+    #[doc(alias = "CFNumberIsFloatType")]
+    pub fn is_float_type(&self) -> bool {
+        extern "C" {
+            fn CFNumberIsFloatType(number: &CFNumber) -> Boolean;
+        }
+        unsafe { CFNumberIsFloatType(self) != 0 }
+    }
+
+    /// Compares the values of `self` and `other`, even if they are of
+    /// different types.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfnumbercompare(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFNumberCompare")]
+    pub fn compare(&self, other: &CFNumber) -> CFComparisonResult {
+        extern "C" {
+            fn CFNumberCompare(
+                number: &CFNumber,
+                other_number: &CFNumber,
+                context: *mut c_void,
+            ) -> CFComparisonResult;
+        }
+        unsafe { CFNumberCompare(self, other, ptr::null_mut()) }
+    }
+}
+
+cf_subclass! {
+    /// A boolean value object.
+    ///
+    /// There are only two instances:
+    /// [`true_value`](#method.true_value) and
+    /// [`false_value`](#method.false_value).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfboolean?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfbooleanref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFBoolean: CFType;
+    type_id = CFBooleanGetTypeID;
+}
+
+impl fmt::Debug for CFBoolean {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value().fmt(f)
+    }
+}
+
+impl fmt::Display for CFBoolean {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value().fmt(f)
+    }
+}
+
+impl From<bool> for &'static CFBoolean {
+    #[inline]
+    fn from(value: bool) -> Self {
+        CFBoolean::new(value)
+    }
+}
+
+impl From<&CFBoolean> for bool {
+    #[inline]
+    fn from(value: &CFBoolean) -> Self {
+        value.value()
+    }
+}
+
+impl CFBoolean {
+    /// Returns the instance for `value`.
+    #[inline]
+    pub fn new(value: bool) -> &'static CFBoolean {
+        if value {
+            Self::true_value()
+        } else {
+            Self::false_value()
+        }
+    }
+
+    /// Returns the instance whose value is `true`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfbooleantrue).
+    #[inline]
+    #[doc(alias = "kCFBooleanTrue")]
+    pub fn true_value() -> &'static CFBoolean {
+        extern "C" {
+            static kCFBooleanTrue: &'static CFBoolean;
+        }
+        unsafe { kCFBooleanTrue }
+    }
+
+    /// Returns the instance whose value is `false`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfbooleanfalse).
+    #[inline]
+    #[doc(alias = "kCFBooleanFalse")]
+    pub fn false_value() -> &'static CFBoolean {
+        extern "C" {
+            static kCFBooleanFalse: &'static CFBoolean;
+        }
+        unsafe { kCFBooleanFalse }
+    }
+
+    /// Returns the value of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfbooleangetvalue(_:)).
+    #[inline]
+    #[doc(alias = "CFBooleanGetValue")]
+    pub fn value(&self) -> bool {
+        extern "C" {
+            fn CFBooleanGetValue(boolean: &CFBoolean) -> Boolean;
+        }
+        unsafe { CFBooleanGetValue(self) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(CFNumber::from_i8(-8).to_i8(), Some(-8));
+        assert_eq!(CFNumber::from_i16(-16).to_i16(), Some(-16));
+        assert_eq!(CFNumber::from_i32(i32::MIN).to_i32(), Some(i32::MIN));
+        assert_eq!(CFNumber::from_i64(i64::MAX).to_i64(), Some(i64::MAX));
+        assert_eq!(CFNumber::from_f32(1.5).to_f32(), Some(1.5));
+        assert_eq!(CFNumber::from_f64(0.1).to_f64(), Some(0.1));
+        assert_eq!(CFNumber::from_cf_index(-1).to_cf_index(), Some(-1));
+
+        let number: Arc<CFNumber> = 42i32.into();
+        assert_eq!(number.to_i64(), Some(42));
+        assert!(!number.is_float_type());
+        assert!(CFNumber::from_f32(1.0).is_float_type());
+    }
+
+    #[test]
+    fn lossy() {
+        // 2^53 + 1 is the smallest positive integer a `f64` cannot represent.
+        let big = CFNumber::from_i64((1 << 53) + 1);
+        assert_eq!(big.to_f64(), None);
+        assert_eq!(
+            CFNumber::from_i64(1 << 53).to_f64(),
+            Some(9007199254740992.0)
+        );
+
+        let float = CFNumber::from_f64(1.5);
+        assert_eq!(float.to_i64(), None);
+        assert_eq!(float.to_i64_lossy(), 1);
+
+        assert_eq!(CFNumber::from_i32(300).to_i8(), None);
+    }
+
+    #[test]
+    fn compare() {
+        let int = CFNumber::from_i64(2);
+        let float = CFNumber::from_f64(2.5);
+
+        assert!(*int < *float);
+        assert!(*float > *int);
+        assert_eq!(*CFNumber::from_f64(2.0), *int);
+        assert_eq!(
+            int.compare(&CFNumber::from_f32(2.0)),
+            CFComparisonResult::EqualTo
+        );
+    }
+
+    #[test]
+    fn boolean() {
+        assert!(CFBoolean::true_value().value());
+        assert!(!CFBoolean::false_value().value());
+        assert_eq!(CFBoolean::new(true), CFBoolean::true_value());
+        assert_ne!(CFBoolean::true_value(), CFBoolean::false_value());
+        assert!(CFBoolean::true_value().is::<CFBoolean>());
+        assert!(!CFBoolean::true_value().is::<CFNumber>());
+    }
+}
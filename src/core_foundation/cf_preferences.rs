@@ -0,0 +1,207 @@
+use super::{Boolean, CFIndex, CFString, CFType};
+use crate::core::Arc;
+
+/// Functions for reading and writing application preferences.
+///
+/// Application IDs are typically bundle identifiers such as
+/// `com.example.app`, or
+/// [`current_application`](#method.current_application).
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/preferences_utilities).
+#[derive(Debug)]
+pub enum CFPreferences {}
+
+macro_rules! constant {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $value:literal
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        #[doc(alias = $value)]
+        pub fn $fn() -> &'static CFString {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static CFString;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+/// Domain constants.
+impl CFPreferences {
+    constant! {
+        /// The application ID of the current application.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfpreferencescurrentapplication).
+        current_application "kCFPreferencesCurrentApplication"
+    }
+
+    constant! {
+        /// Preferences that apply to any user.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfpreferencesanyuser).
+        any_user "kCFPreferencesAnyUser"
+    }
+
+    constant! {
+        /// Preferences that apply to the current user.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfpreferencescurrentuser).
+        current_user "kCFPreferencesCurrentUser"
+    }
+
+    constant! {
+        /// Preferences that apply to any host.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfpreferencesanyhost).
+        any_host "kCFPreferencesAnyHost"
+    }
+
+    constant! {
+        /// Preferences that apply to the current host.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfpreferencescurrenthost).
+        current_host "kCFPreferencesCurrentHost"
+    }
+}
+
+/// Application preferences.
+impl CFPreferences {
+    /// Returns the value for `key` in the preferences of `app_id`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpreferencescopyappvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFPreferencesCopyAppValue")]
+    pub fn copy_app_value(key: &CFString, app_id: &CFString) -> Option<Arc<CFType>> {
+        extern "C" {
+            fn CFPreferencesCopyAppValue(
+                key: &CFString,
+                application_id: &CFString,
+            ) -> Option<Arc<CFType>>;
+        }
+        unsafe { CFPreferencesCopyAppValue(key, app_id) }
+    }
+
+    /// Sets the value for `key` in the preferences of `app_id`, or removes it
+    /// if `value` is `None`.
+    ///
+    /// The change is not written to disk until
+    /// [`app_synchronize`](#method.app_synchronize) is called.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpreferencessetappvalue(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFPreferencesSetAppValue")]
+    pub fn set_app_value(key: &CFString, value: Option<&CFType>, app_id: &CFString) {
+        extern "C" {
+            fn CFPreferencesSetAppValue(
+                key: &CFString,
+                value: Option<&CFType>,
+                application_id: &CFString,
+            );
+        }
+        unsafe { CFPreferencesSetAppValue(key, value, app_id) }
+    }
+
+    /// Writes pending changes to the preferences of `app_id` to disk, and
+    /// reads the latest preferences from disk.
+    ///
+    /// Returns `true` on success.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpreferencesappsynchronize(_:)).
+    #[inline]
+    #[doc(alias = "CFPreferencesAppSynchronize")]
+    pub fn app_synchronize(app_id: &CFString) -> bool {
+        extern "C" {
+            fn CFPreferencesAppSynchronize(application_id: &CFString) -> Boolean;
+        }
+        unsafe { CFPreferencesAppSynchronize(app_id) != 0 }
+    }
+
+    /// Returns the boolean value for `key` in the preferences of `app_id`, or
+    /// `None` if it does not exist or is not a boolean.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpreferencesgetappbooleanvalue(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFPreferencesGetAppBooleanValue")]
+    pub fn app_bool(key: &CFString, app_id: &CFString) -> Option<bool> {
+        extern "C" {
+            fn CFPreferencesGetAppBooleanValue(
+                key: &CFString,
+                application_id: &CFString,
+                key_exists_and_has_valid_format: &mut Boolean,
+            ) -> Boolean;
+        }
+
+        let mut is_valid = 0;
+        let value = unsafe { CFPreferencesGetAppBooleanValue(key, app_id, &mut is_valid) };
+
+        if is_valid != 0 {
+            Some(value != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the integer value for `key` in the preferences of `app_id`, or
+    /// `None` if it does not exist or is not an integer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpreferencesgetappintegervalue(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFPreferencesGetAppIntegerValue")]
+    pub fn app_integer(key: &CFString, app_id: &CFString) -> Option<CFIndex> {
+        extern "C" {
+            fn CFPreferencesGetAppIntegerValue(
+                key: &CFString,
+                application_id: &CFString,
+                key_exists_and_has_valid_format: &mut Boolean,
+            ) -> CFIndex;
+        }
+
+        let mut is_valid = 0;
+        let value = unsafe { CFPreferencesGetAppIntegerValue(key, app_id, &mut is_valid) };
+
+        if is_valid != 0 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFBoolean, CFNumber};
+    use std::process;
+
+    #[test]
+    fn round_trip() {
+        let app_id = CFString::from_str(&format!("com.example.fruity.tests.{}", process::id()));
+        let flag = CFString::from_str("flag");
+        let count = CFString::from_str("count");
+        let name = CFString::from_str("name");
+
+        assert_eq!(CFPreferences::app_bool(&flag, &app_id), None);
+        assert_eq!(CFPreferences::app_integer(&count, &app_id), None);
+
+        let value = CFString::from_str("fruity");
+        CFPreferences::set_app_value(&flag, Some(CFBoolean::true_value()), &app_id);
+        CFPreferences::set_app_value(&count, Some(&CFNumber::from_i64(42)), &app_id);
+        CFPreferences::set_app_value(&name, Some(&value), &app_id);
+
+        assert_eq!(CFPreferences::app_bool(&flag, &app_id), Some(true));
+        assert_eq!(CFPreferences::app_integer(&count, &app_id), Some(42));
+        assert_eq!(CFPreferences::app_integer(&name, &app_id), None);
+
+        let copied = CFPreferences::copy_app_value(&name, &app_id).unwrap();
+        assert_eq!(*copied, **value);
+
+        for key in &[&flag, &count, &name] {
+            CFPreferences::set_app_value(key, None, &app_id);
+        }
+        assert!(CFPreferences::app_synchronize(&app_id));
+        assert!(CFPreferences::copy_app_value(&name, &app_id).is_none());
+    }
+}
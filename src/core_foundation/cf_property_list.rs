@@ -0,0 +1,193 @@
+use super::{Boolean, CFAllocator, CFData, CFError, CFErrorRef, CFIndex, CFOptionFlags, CFType};
+use crate::core::Arc;
+
+/// Functions for converting property list objects to and from serialized
+/// data.
+///
+/// A property list is a `CFType` that is one of `CFString`, `CFData`,
+/// `CFBoolean`, `CFNumber`, `CFDate`, or a `CFArray` or `CFDictionary` of
+/// property lists, where dictionary keys are `CFString`s.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylist).
+#[derive(Debug)]
+pub enum CFPropertyList {}
+
+/// The formats that property lists can be serialized as.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylistformat).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFPropertyListFormat {
+    /// The legacy OpenStep format, which can only be read.
+    OpenStep = 1,
+    /// The XML format, version 1.0.
+    XMLv1 = 100,
+    /// The binary format, version 1.0.
+    BinaryV1 = 200,
+}
+
+/// `kCFPropertyListImmutable` from `CFPropertyListMutabilityOptions`.
+const IMMUTABLE: CFOptionFlags = 0;
+
+/// `NSPropertyListReadCorruptError` and `NSPropertyListWriteInvalidError`,
+/// which are reported in the Cocoa error domain.
+const READ_CORRUPT_ERROR: CFIndex = 3840;
+const WRITE_INVALID_ERROR: CFIndex = 3852;
+
+/// Returns the error of a failed call, or one with `code` in the Cocoa error
+/// domain if the call did not provide one.
+#[inline]
+fn error_or(error: Option<CFErrorRef>, code: CFIndex) -> CFErrorRef {
+    error.unwrap_or_else(|| CFError::new(CFError::cocoa_domain(), code, None))
+}
+
+impl CFPropertyList {
+    /// Parses a property list from `data` in any supported format.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylistcreatewithdata(_:_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFPropertyListCreateWithData")]
+    pub fn from_data(data: &CFData) -> Result<Arc<CFType>, CFErrorRef> {
+        Self::from_data_with_format(data).map(|(plist, _)| plist)
+    }
+
+    /// Parses a property list from `data` in any supported format, returning
+    /// the format that it was in.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylistcreatewithdata(_:_:_:_:_:)).
+    #[doc(alias = "CFPropertyListCreateWithData")]
+    pub fn from_data_with_format(
+        data: &CFData,
+    ) -> Result<(Arc<CFType>, CFPropertyListFormat), CFErrorRef> {
+        extern "C" {
+            fn CFPropertyListCreateWithData(
+                allocator: Option<&CFAllocator>,
+                data: &CFData,
+                options: CFOptionFlags,
+                format: *mut CFPropertyListFormat,
+                error: *mut Option<CFErrorRef>,
+            ) -> Option<Arc<CFType>>;
+        }
+
+        let mut format = CFPropertyListFormat::BinaryV1;
+        let mut error = None;
+        let plist =
+            unsafe { CFPropertyListCreateWithData(None, data, IMMUTABLE, &mut format, &mut error) };
+
+        CFError::check(&mut error, plist.is_some())
+            .map_err(|error| error_or(error, READ_CORRUPT_ERROR))?;
+        Ok((plist.unwrap(), format))
+    }
+
+    /// Serializes `plist` as `format`.
+    ///
+    /// Writing [`OpenStep`](enum.CFPropertyListFormat.html#variant.OpenStep)
+    /// is not supported and always fails.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylistcreatedata(_:_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFPropertyListCreateData")]
+    pub fn to_data(
+        plist: &CFType,
+        format: CFPropertyListFormat,
+    ) -> Result<Arc<CFData>, CFErrorRef> {
+        extern "C" {
+            fn CFPropertyListCreateData(
+                allocator: Option<&CFAllocator>,
+                property_list: &CFType,
+                format: CFPropertyListFormat,
+                options: CFOptionFlags,
+                error: *mut Option<CFErrorRef>,
+            ) -> Option<Arc<CFData>>;
+        }
+
+        let mut error = None;
+        let data = unsafe { CFPropertyListCreateData(None, plist, format, 0, &mut error) };
+
+        CFError::check(&mut error, data.is_some())
+            .map_err(|error| error_or(error, WRITE_INVALID_ERROR))?;
+        Ok(data.unwrap())
+    }
+
+    /// Returns `true` if `plist` is a valid property list that can be
+    /// serialized as `format`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfpropertylistisvalid(_:_:)).
+    #[inline]
+    #[doc(alias = "CFPropertyListIsValid")]
+    pub fn is_valid(plist: &CFType, format: CFPropertyListFormat) -> bool {
+        extern "C" {
+            fn CFPropertyListIsValid(plist: &CFType, format: CFPropertyListFormat) -> Boolean;
+        }
+        unsafe { CFPropertyListIsValid(plist, format) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{
+        CFArray, CFBoolean, CFDate, CFDictionary, CFNumber, CFString, CFUUID,
+    };
+
+    fn sample() -> Arc<CFDictionary<CFString, CFType>> {
+        let name = CFString::from_str("fruity");
+        let count = CFNumber::from_i64(42);
+        let ratio = CFNumber::from_f64(0.5);
+        let date = CFDate::from_absolute_time(123456.75);
+        let data = CFData::from_bytes(b"\x00\x01\xFF");
+        let data: &CFType = (*data).as_ref();
+
+        let inner = CFArray::<CFType>::from_slice(&[&count, CFBoolean::true_value()]);
+        let nested = CFArray::<CFType>::from_slice(&[&name, &inner]);
+
+        let keys = ["name", "count", "ratio", "date", "data", "nested"].map(CFString::from_str);
+        CFDictionary::<CFString, CFType>::from_pairs(&[
+            (&keys[0], &name),
+            (&keys[1], &count),
+            (&keys[2], &ratio),
+            (&keys[3], &date),
+            (&keys[4], data),
+            (&keys[5], &nested),
+        ])
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = sample();
+        let root: &CFType = &root;
+
+        for &format in &[CFPropertyListFormat::BinaryV1, CFPropertyListFormat::XMLv1] {
+            assert!(CFPropertyList::is_valid(root, format));
+
+            let data = CFPropertyList::to_data(root, format).unwrap();
+            let (parsed, parsed_format) = CFPropertyList::from_data_with_format(&data).unwrap();
+
+            assert_eq!(parsed_format, format);
+            assert_eq!(*parsed, *root);
+            assert!(parsed.is::<CFDictionary<CFType, CFType>>());
+        }
+    }
+
+    #[test]
+    fn open_step() {
+        let data = CFData::from_bytes(b"{ key = (a, b); }");
+        let (parsed, format) = CFPropertyList::from_data_with_format(&data).unwrap();
+
+        assert_eq!(format, CFPropertyListFormat::OpenStep);
+        assert!(CFPropertyList::to_data(&parsed, CFPropertyListFormat::OpenStep).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let uuid = CFUUID::new();
+        assert!(!CFPropertyList::is_valid(
+            &uuid,
+            CFPropertyListFormat::BinaryV1
+        ));
+        assert!(CFPropertyList::to_data(&uuid, CFPropertyListFormat::BinaryV1).is_err());
+
+        let garbage = CFData::from_bytes(b"<?xml version=\"1.0\"?><plist");
+        assert!(CFPropertyList::from_data(&garbage).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use super::CFIndex;
+use std::ops::Range;
+
+/// A structure representing a range of sequential items in a container, such
+/// as characters in a buffer or elements in a collection.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrange).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CFRange {
+    /// An integer representing the starting location of the range.
+    ///
+    /// For type compatibility with the rest of the system, `CFIndex` is used.
+    pub location: CFIndex,
+
+    /// An integer representing the number of items in the range.
+    ///
+    /// For type compatibility with the rest of the system, `CFIndex` is used.
+    pub length: CFIndex,
+}
+
+impl From<Range<CFIndex>> for CFRange {
+    #[inline]
+    fn from(range: Range<CFIndex>) -> Self {
+        Self {
+            location: range.start,
+            length: range.end.wrapping_sub(range.start),
+        }
+    }
+}
+
+impl From<Range<usize>> for CFRange {
+    #[inline]
+    fn from(range: Range<usize>) -> Self {
+        Self::from(range.start as CFIndex..range.end as CFIndex)
+    }
+}
+
+impl From<CFRange> for Range<CFIndex> {
+    #[inline]
+    fn from(range: CFRange) -> Self {
+        range.location..range.end()
+    }
+}
+
+impl CFRange {
+    /// Creates a range from `location` with `length` items.
+    ///
+    /// This is equivalent to
+    /// [`CFRangeMake`](https://developer.apple.com/documentation/corefoundation/1543292-cfrangemake).
+    #[inline]
+    #[doc(alias = "CFRangeMake")]
+    pub const fn new(location: CFIndex, length: CFIndex) -> Self {
+        Self { location, length }
+    }
+
+    /// Returns the index after the last item in the range.
+    #[inline]
+    pub const fn end(&self) -> CFIndex {
+        self.location.wrapping_add(self.length)
+    }
+
+    /// Panics if `self` does not lie within a container of `len` items.
+    #[inline]
+    pub(crate) fn assert_within(self, len: CFIndex) {
+        assert!(
+            0 <= self.location && 0 <= self.length && self.end() <= len,
+            "Range {:?} is out of bounds for length {}",
+            self,
+            len
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::{Boolean, CFString, CFTimeInterval, CFType};
+use crate::core::Arc;
+use std::{fmt, time::Duration};
+
+cf_subclass! {
+    /// An event loop that monitors sources of input and dispatches control
+    /// when they become ready for processing.
+    ///
+    /// Each thread has exactly one run loop, which is created on first use.
+    /// Run loops can only be run from their own thread, but can be stopped or
+    /// woken up from any thread.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfrunloop?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfrunloopref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFRunLoop: CFType;
+    type_id = CFRunLoopGetTypeID;
+}
+
+/// The reason that [`CFRunLoop::run_in_mode`](struct.CFRunLoop.html#method.run_in_mode)
+/// returned.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooprunresult).
+#[repr(i32)] // SInt32
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFRunLoopRunResult {
+    /// The mode has no sources or timers.
+    Finished = 1,
+    /// The run loop was stopped with
+    /// [`CFRunLoop::stop`](struct.CFRunLoop.html#method.stop).
+    Stopped = 2,
+    /// The time interval elapsed.
+    TimedOut = 3,
+    /// A source was processed while returning after a source was handled.
+    HandledSource = 4,
+}
+
+impl fmt::Debug for CFRunLoop {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFRunLoop")
+            .field("is_waiting", &self.is_waiting())
+            .finish()
+    }
+}
+
+/// Run loop modes.
+impl CFRunLoop {
+    /// The mode in which run loops handle input sources by default.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfrunloopdefaultmode).
+    #[inline]
+    #[doc(alias = "kCFRunLoopDefaultMode")]
+    pub fn default_mode() -> &'static CFString {
+        extern "C" {
+            static kCFRunLoopDefaultMode: &'static CFString;
+        }
+        unsafe { kCFRunLoopDefaultMode }
+    }
+
+    /// A pseudo-mode for adding sources, timers, and observers to all modes
+    /// in the set of common modes.
+    ///
+    /// This cannot be used to run a run loop.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/kcfrunloopcommonmodes).
+    #[inline]
+    #[doc(alias = "kCFRunLoopCommonModes")]
+    pub fn common_modes() -> &'static CFString {
+        extern "C" {
+            static kCFRunLoopCommonModes: &'static CFString;
+        }
+        unsafe { kCFRunLoopCommonModes }
+    }
+}
+
+/// Getting run loops.
+impl CFRunLoop {
+    /// Returns the run loop for the current thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopgetcurrent()).
+    #[inline]
+    #[doc(alias = "CFRunLoopGetCurrent")]
+    pub fn current() -> Arc<CFRunLoop> {
+        extern "C" {
+            fn CFRunLoopGetCurrent() -> &'static CFRunLoop;
+        }
+
+        // The run loop is destroyed when its thread exits, so it must be
+        // retained to safely outlive the current thread.
+        Arc::retain(unsafe { CFRunLoopGetCurrent() })
+    }
+
+    /// Returns the run loop for the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopgetmain()).
+    #[inline]
+    #[doc(alias = "CFRunLoopGetMain")]
+    pub fn main() -> &'static CFRunLoop {
+        extern "C" {
+            fn CFRunLoopGetMain() -> &'static CFRunLoop;
+        }
+        unsafe { CFRunLoopGetMain() }
+    }
+}
+
+/// Running run loops.
+impl CFRunLoop {
+    /// Runs the current thread's run loop in the default mode until it is
+    /// stopped or has no sources or timers.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooprun()).
+    #[inline]
+    #[doc(alias = "CFRunLoopRun")]
+    pub fn run() {
+        extern "C" {
+            fn CFRunLoopRun();
+        }
+        unsafe { CFRunLoopRun() }
+    }
+
+    /// Runs the current thread's run loop in `mode` for at most `duration`.
+    ///
+    /// If `return_after_source_handled` is `true`, this returns after
+    /// processing a single source.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopruninmode(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopRunInMode")]
+    pub fn run_in_mode(
+        mode: &CFString,
+        duration: Duration,
+        return_after_source_handled: bool,
+    ) -> CFRunLoopRunResult {
+        extern "C" {
+            fn CFRunLoopRunInMode(
+                mode: &CFString,
+                seconds: CFTimeInterval,
+                return_after_source_handled: Boolean,
+            ) -> CFRunLoopRunResult;
+        }
+        unsafe {
+            CFRunLoopRunInMode(
+                mode,
+                duration.as_secs_f64(),
+                return_after_source_handled as Boolean,
+            )
+        }
+    }
+
+    /// Forces `self` to stop running.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopstop(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopStop")]
+    pub fn stop(&self) {
+        extern "C" {
+            fn CFRunLoopStop(rl: &CFRunLoop);
+        }
+        unsafe { CFRunLoopStop(self) }
+    }
+
+    /// Wakes `self` if it is waiting for a source or timer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopwakeup(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopWakeUp")]
+    pub fn wake_up(&self) {
+        extern "C" {
+            fn CFRunLoopWakeUp(rl: &CFRunLoop);
+        }
+        unsafe { CFRunLoopWakeUp(self) }
+    }
+
+    /// Returns `true` if `self` is running and waiting for a source or timer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopiswaiting(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopIsWaiting")]
+    pub fn is_waiting(&self) -> bool {
+        extern "C" {
+            fn CFRunLoopIsWaiting(rl: &CFRunLoop) -> Boolean;
+        }
+        unsafe { CFRunLoopIsWaiting(self) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{
+        CFAbsoluteTimeGetCurrent, CFAllocator, CFHashCode, CFIndex, CFRunLoopSource, CFRunLoopTimer,
+    };
+    use std::{
+        ffi::c_void,
+        ptr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc,
+        },
+        thread,
+    };
+
+    fn add_timer<F>(delay: CFTimeInterval, f: F) -> Arc<CFRunLoopTimer>
+    where
+        F: FnMut(&CFRunLoopTimer) + Send + 'static,
+    {
+        let timer = CFRunLoopTimer::new(CFAbsoluteTimeGetCurrent() + delay, 0.0, f);
+        timer.add_to(&CFRunLoop::current(), CFRunLoop::default_mode());
+        timer
+    }
+
+    #[repr(C)]
+    struct CFRunLoopSourceContext {
+        version: CFIndex,
+        info: *mut c_void,
+        retain: *const c_void,
+        release: *const c_void,
+        copy_description: *const c_void,
+        equal: *const c_void,
+        hash: Option<extern "C" fn(info: *const c_void) -> CFHashCode>,
+        schedule: *const c_void,
+        cancel: *const c_void,
+        perform: extern "C" fn(info: *mut c_void),
+    }
+
+    extern "C" {
+        fn CFRunLoopSourceCreate(
+            allocator: Option<&CFAllocator>,
+            order: CFIndex,
+            context: &CFRunLoopSourceContext,
+        ) -> Option<Arc<CFRunLoopSource>>;
+
+        fn CFRunLoopSourceSignal(source: &CFRunLoopSource);
+    }
+
+    #[test]
+    fn finished() {
+        let result = thread::spawn(|| {
+            CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(1), false)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(result, CFRunLoopRunResult::Finished);
+    }
+
+    #[test]
+    fn timed_out() {
+        let result = thread::spawn(|| {
+            let _timer = add_timer(60.0, |_| {});
+            CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_millis(10), false)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(result, CFRunLoopRunResult::TimedOut);
+    }
+
+    #[test]
+    fn stop_from_timer() {
+        static FIRED: AtomicBool = AtomicBool::new(false);
+
+        let result = thread::spawn(|| {
+            let _timer = add_timer(0.0, |_| {
+                FIRED.store(true, Ordering::SeqCst);
+                CFRunLoop::current().stop();
+            });
+            let _keep_alive = add_timer(60.0, |_| {});
+            CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(5), false)
+        })
+        .join()
+        .unwrap();
+
+        assert!(FIRED.load(Ordering::SeqCst));
+        assert_eq!(result, CFRunLoopRunResult::Stopped);
+    }
+
+    #[test]
+    fn handled_source() {
+        static PERFORMED: AtomicBool = AtomicBool::new(false);
+
+        extern "C" fn perform(_: *mut c_void) {
+            PERFORMED.store(true, Ordering::SeqCst);
+        }
+
+        let result = thread::spawn(|| unsafe {
+            let context = CFRunLoopSourceContext {
+                version: 0,
+                info: ptr::null_mut(),
+                retain: ptr::null(),
+                release: ptr::null(),
+                copy_description: ptr::null(),
+                equal: ptr::null(),
+                hash: None,
+                schedule: ptr::null(),
+                cancel: ptr::null(),
+                perform,
+            };
+            let source = CFRunLoopSourceCreate(None, 0, &context).unwrap();
+            let run_loop = CFRunLoop::current();
+            source.add_to(&run_loop, CFRunLoop::default_mode());
+            CFRunLoopSourceSignal(&source);
+
+            assert!(!run_loop.is_waiting());
+            let result =
+                CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(5), true);
+
+            source.invalidate();
+            assert!(!source.is_valid());
+            result
+        })
+        .join()
+        .unwrap();
+
+        assert!(PERFORMED.load(Ordering::SeqCst));
+        assert_eq!(result, CFRunLoopRunResult::HandledSource);
+    }
+
+    #[test]
+    fn stop_from_other_thread() {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let _keep_alive = add_timer(60.0, |_| {});
+            sender.send(CFRunLoop::current()).unwrap();
+            CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(30), false)
+        });
+
+        let run_loop = receiver.recv().unwrap();
+        while !run_loop.is_waiting() {
+            thread::yield_now();
+        }
+        run_loop.wake_up();
+        run_loop.stop();
+
+        assert_eq!(handle.join().unwrap(), CFRunLoopRunResult::Stopped);
+    }
+}
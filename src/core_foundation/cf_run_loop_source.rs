@@ -0,0 +1,88 @@
+use super::{Boolean, CFIndex, CFRunLoop, CFString, CFType};
+use std::fmt;
+
+cf_subclass! {
+    /// An input source for a run loop, which is created by objects such as
+    /// [`CFMessagePort`](struct.CFMessagePort.html).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfrunloopsource?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfrunloopsourceref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFRunLoopSource: CFType;
+    type_id = CFRunLoopSourceGetTypeID;
+}
+
+impl fmt::Debug for CFRunLoopSource {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFRunLoopSource")
+            .field("is_valid", &self.is_valid())
+            .field("order", &self.order())
+            .finish()
+    }
+}
+
+impl CFRunLoopSource {
+    /// Adds `self` to `run_loop` in `mode`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopaddsource(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopAddSource")]
+    pub fn add_to(&self, run_loop: &CFRunLoop, mode: &CFString) {
+        extern "C" {
+            fn CFRunLoopAddSource(rl: &CFRunLoop, source: &CFRunLoopSource, mode: &CFString);
+        }
+        unsafe { CFRunLoopAddSource(run_loop, self, mode) }
+    }
+
+    /// Removes `self` from `mode` of `run_loop`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopremovesource(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopRemoveSource")]
+    pub fn remove_from(&self, run_loop: &CFRunLoop, mode: &CFString) {
+        extern "C" {
+            fn CFRunLoopRemoveSource(rl: &CFRunLoop, source: &CFRunLoopSource, mode: &CFString);
+        }
+        unsafe { CFRunLoopRemoveSource(run_loop, self, mode) }
+    }
+
+    /// Stops `self` from ever firing again and removes it from all run loop
+    /// modes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopsourceinvalidate(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopSourceInvalidate")]
+    pub fn invalidate(&self) {
+        extern "C" {
+            fn CFRunLoopSourceInvalidate(source: &CFRunLoopSource);
+        }
+        unsafe { CFRunLoopSourceInvalidate(self) }
+    }
+
+    /// Returns `true` if `self` has not been invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopsourceisvalid(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopSourceIsValid")]
+    pub fn is_valid(&self) -> bool {
+        extern "C" {
+            fn CFRunLoopSourceIsValid(source: &CFRunLoopSource) -> Boolean;
+        }
+        unsafe { CFRunLoopSourceIsValid(self) != 0 }
+    }
+
+    /// Returns the priority of `self` relative to other sources, where lower
+    /// values are processed first.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopsourcegetorder(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopSourceGetOrder")]
+    pub fn order(&self) -> CFIndex {
+        extern "C" {
+            fn CFRunLoopSourceGetOrder(source: &CFRunLoopSource) -> CFIndex;
+        }
+        unsafe { CFRunLoopSourceGetOrder(self) }
+    }
+}
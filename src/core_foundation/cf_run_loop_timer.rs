@@ -0,0 +1,270 @@
+use super::{
+    Boolean, CFAbsoluteTime, CFAllocator, CFIndex, CFOptionFlags, CFRunLoop, CFString,
+    CFTimeInterval, CFType,
+};
+use crate::_priv::{abort_on_panic, cfcontext};
+use crate::core::Arc;
+use std::{cell::RefCell, ffi::c_void, fmt, sync};
+
+cf_subclass! {
+    /// A specialized run loop source that fires at a preset time in the
+    /// future.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfrunlooptimer?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfrunlooptimerref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFRunLoopTimer: CFType;
+    type_id = CFRunLoopTimerGetTypeID;
+}
+
+impl fmt::Debug for CFRunLoopTimer {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFRunLoopTimer")
+            .field("is_valid", &self.is_valid())
+            .field("next_fire_date", &self.next_fire_date())
+            .field("interval", &self.interval())
+            .finish()
+    }
+}
+
+#[repr(C)]
+struct CFRunLoopTimerContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+}
+
+type CFRunLoopTimerCallBack = unsafe extern "C" fn(timer: &CFRunLoopTimer, info: *mut c_void);
+
+/// Calls the closure of a timer whose context info is a
+/// `sync::Arc<RefCell<F>>`.
+unsafe extern "C" fn call<F: FnMut(&CFRunLoopTimer)>(timer: &CFRunLoopTimer, info: *mut c_void) {
+    let callback = &*info.cast::<RefCell<F>>();
+
+    // Core Foundation does not fire a timer while its callback is running,
+    // but guard against reentrancy anyway since it would alias `&mut F`.
+    if let Ok(mut callback) = callback.try_borrow_mut() {
+        abort_on_panic(|| (*callback)(timer));
+    }
+}
+
+impl CFRunLoopTimer {
+    /// Creates a timer that calls `f` at `fire_date` and then every
+    /// `interval` seconds, or only once if `interval` is 0.
+    ///
+    /// The timer must be added to a run loop with
+    /// [`add_to`](#method.add_to) to fire. `f` is dropped once the timer is
+    /// invalidated or deallocated.
+    ///
+    /// `f` must be `Send` because the timer may be added to the run loop of
+    /// any thread. If `f` panics, the process is aborted since unwinding into
+    /// Core Foundation is undefined behavior.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimercreate(_:_:_:_:_:_:_:)).
+    #[doc(alias = "CFRunLoopTimerCreate")]
+    pub fn new<F>(fire_date: CFAbsoluteTime, interval: CFTimeInterval, f: F) -> Arc<Self>
+    where
+        F: FnMut(&CFRunLoopTimer) + Send + 'static,
+    {
+        extern "C" {
+            fn CFRunLoopTimerCreate(
+                allocator: Option<&CFAllocator>,
+                fire_date: CFAbsoluteTime,
+                interval: CFTimeInterval,
+                flags: CFOptionFlags,
+                order: CFIndex,
+                callout: CFRunLoopTimerCallBack,
+                context: &CFRunLoopTimerContext,
+            ) -> Option<Arc<CFRunLoopTimer>>;
+        }
+
+        let info = sync::Arc::new(RefCell::new(f));
+        let context = CFRunLoopTimerContext {
+            version: 0,
+            info: sync::Arc::as_ptr(&info) as *mut c_void,
+            retain: Some(cfcontext::retain::<RefCell<F>>),
+            release: Some(cfcontext::release::<RefCell<F>>),
+            copy_description: Some(cfcontext::copy_description),
+        };
+
+        // The timer retains `info` through the context, so our reference can
+        // be dropped once it is created.
+        let timer =
+            unsafe { CFRunLoopTimerCreate(None, fire_date, interval, 0, 0, call::<F>, &context) };
+        timer.expect("Could not create CFRunLoopTimer")
+    }
+
+    /// Adds `self` to `run_loop` in `mode`.
+    ///
+    /// A timer can only be added to one run loop at a time, but may be added
+    /// to multiple modes of that run loop.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunloopaddtimer(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopAddTimer")]
+    pub fn add_to(&self, run_loop: &CFRunLoop, mode: &CFString) {
+        extern "C" {
+            fn CFRunLoopAddTimer(rl: &CFRunLoop, timer: &CFRunLoopTimer, mode: &CFString);
+        }
+        unsafe { CFRunLoopAddTimer(run_loop, self, mode) }
+    }
+
+    /// Stops `self` from ever firing again and removes it from all run loop
+    /// modes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimerinvalidate(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerInvalidate")]
+    pub fn invalidate(&self) {
+        extern "C" {
+            fn CFRunLoopTimerInvalidate(timer: &CFRunLoopTimer);
+        }
+        unsafe { CFRunLoopTimerInvalidate(self) }
+    }
+
+    /// Returns `true` if `self` has not been invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimerisvalid(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerIsValid")]
+    pub fn is_valid(&self) -> bool {
+        extern "C" {
+            fn CFRunLoopTimerIsValid(timer: &CFRunLoopTimer) -> Boolean;
+        }
+        unsafe { CFRunLoopTimerIsValid(self) != 0 }
+    }
+
+    /// Returns the next time at which `self` will fire.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimergetnextfiredate(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerGetNextFireDate")]
+    pub fn next_fire_date(&self) -> CFAbsoluteTime {
+        extern "C" {
+            fn CFRunLoopTimerGetNextFireDate(timer: &CFRunLoopTimer) -> CFAbsoluteTime;
+        }
+        unsafe { CFRunLoopTimerGetNextFireDate(self) }
+    }
+
+    /// Sets the next time at which `self` will fire.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimersetnextfiredate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerSetNextFireDate")]
+    pub fn set_next_fire_date(&self, fire_date: CFAbsoluteTime) {
+        extern "C" {
+            fn CFRunLoopTimerSetNextFireDate(timer: &CFRunLoopTimer, fire_date: CFAbsoluteTime);
+        }
+        unsafe { CFRunLoopTimerSetNextFireDate(self, fire_date) }
+    }
+
+    /// Returns the firing interval of `self`, which is 0 if it does not
+    /// repeat.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimergetinterval(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerGetInterval")]
+    pub fn interval(&self) -> CFTimeInterval {
+        extern "C" {
+            fn CFRunLoopTimerGetInterval(timer: &CFRunLoopTimer) -> CFTimeInterval;
+        }
+        unsafe { CFRunLoopTimerGetInterval(self) }
+    }
+
+    /// Returns `true` if `self` repeats.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfrunlooptimerdoesrepeat(_:)).
+    #[inline]
+    #[doc(alias = "CFRunLoopTimerDoesRepeat")]
+    pub fn repeats(&self) -> bool {
+        extern "C" {
+            fn CFRunLoopTimerDoesRepeat(timer: &CFRunLoopTimer) -> Boolean;
+        }
+        unsafe { CFRunLoopTimerDoesRepeat(self) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFAbsoluteTimeGetCurrent, CFRunLoopRunResult};
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    struct SetOnDrop(sync::Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn repeating() {
+        let count = sync::Arc::new(AtomicUsize::new(0));
+        let dropped = sync::Arc::new(AtomicBool::new(false));
+
+        let (result, timer) = thread::spawn({
+            let count = count.clone();
+            let guard = SetOnDrop(dropped.clone());
+
+            move || {
+                let timer = CFRunLoopTimer::new(CFAbsoluteTimeGetCurrent(), 0.01, move |timer| {
+                    let _guard = &guard;
+                    if count.fetch_add(1, Ordering::SeqCst) + 1 == 3 {
+                        timer.invalidate();
+                    }
+                });
+                assert!(timer.repeats());
+                assert!(timer.is_valid());
+
+                timer.add_to(&CFRunLoop::current(), CFRunLoop::default_mode());
+                let result = CFRunLoop::run_in_mode(
+                    CFRunLoop::default_mode(),
+                    Duration::from_secs(5),
+                    false,
+                );
+                (result, timer)
+            }
+        })
+        .join()
+        .unwrap();
+
+        // The loop finishes once the only timer is invalidated.
+        assert_eq!(result, CFRunLoopRunResult::Finished);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+        assert!(!timer.is_valid());
+
+        drop(timer);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn never_added() {
+        let dropped = sync::Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+
+        let timer = CFRunLoopTimer::new(0.0, 0.0, move |_| {
+            let _guard = &guard;
+        });
+        assert!(!timer.repeats());
+        assert!(!format!("{:?}", timer.copy_description()).is_empty());
+
+        drop(timer);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn set_next_fire_date() {
+        let timer = CFRunLoopTimer::new(0.0, 0.0, |_| {});
+        timer.set_next_fire_date(1000.0);
+        assert_eq!(timer.next_fire_date(), 1000.0);
+    }
+}
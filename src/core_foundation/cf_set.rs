@@ -0,0 +1,368 @@
+use super::{Boolean, CFAllocator, CFIndex, CFType};
+use crate::core::{Arc, ObjectType};
+use std::{ffi::c_void, fmt, marker::PhantomData, ptr, vec};
+
+subclass! {
+    /// An immutable unordered collection of distinct objects of type `T`.
+    ///
+    /// The untyped `CFSet<CFType>` form can hold heterogeneous objects, which
+    /// can be recovered with
+    /// [`CFType::downcast_ref`](struct.CFType.html#method.downcast_ref).
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfset?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfsetref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFSet<T>: CFType;
+}
+
+// Only the untyped form can be downcast to because elements are not checked.
+cf_type_object!(CFSet<CFType>, CFSetGetTypeID);
+
+#[repr(C)]
+pub(crate) struct CFSetCallBacks {
+    version: CFIndex,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+    equal: *const c_void,
+    hash: *const c_void,
+}
+
+extern "C" {
+    pub(crate) static kCFTypeSetCallBacks: CFSetCallBacks;
+
+    fn CFSetGetCount(the_set: &CFType) -> CFIndex;
+    fn CFSetGetValue(the_set: &CFType, value: *const c_void) -> *const c_void;
+    fn CFSetContainsValue(the_set: &CFType, value: *const c_void) -> Boolean;
+    fn CFSetGetValues(the_set: &CFType, values: *mut *const c_void);
+}
+
+impl<T: ObjectType + fmt::Debug> fmt::Debug for CFSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: ObjectType> IntoIterator for &'a CFSet<T> {
+    type Item = &'a T;
+    type IntoIter = CFSetIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType> CFSet<T> {
+    /// Creates a set containing retained `values`.
+    ///
+    /// This uses
+    /// [`kCFTypeSetCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypesetcallbacks),
+    /// so values that are equal according to `CFEqual` are only stored once.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetcreate(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetCreate")]
+    pub fn from_slice(values: &[&T]) -> Arc<Self> {
+        extern "C" {
+            fn CFSetCreate(
+                allocator: Option<&CFAllocator>,
+                values: *const *const c_void,
+                num_values: CFIndex,
+                call_backs: *const CFSetCallBacks,
+            ) -> *const CFType;
+        }
+
+        unsafe {
+            let set = CFSetCreate(
+                None,
+                values.as_ptr().cast(),
+                values.len() as CFIndex,
+                &kCFTypeSetCallBacks,
+            );
+            assert!(!set.is_null(), "Could not create CFSet");
+            Arc::from_raw(set.cast())
+        }
+    }
+
+    /// Returns the number of values in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetgetcount(_:)).
+    #[inline]
+    #[doc(alias = "CFSetGetCount")]
+    pub fn len(&self) -> CFIndex {
+        unsafe { CFSetGetCount(self) }
+    }
+
+    /// Returns `true` if `self` contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value in `self` that is equal to `value`, or `None` if
+    /// there is none.
+    ///
+    /// Values are compared using `CFEqual`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetgetvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetGetValue")]
+    pub fn get(&self, value: &T) -> Option<&T> {
+        unsafe {
+            let value = CFSetGetValue(self, (value as *const T).cast());
+            value.cast::<T>().as_ref()
+        }
+    }
+
+    /// Returns `true` if `self` contains a value equal to `value`.
+    ///
+    /// Values are compared using `CFEqual`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetcontainsvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetContainsValue")]
+    pub fn contains(&self, value: &T) -> bool {
+        unsafe { CFSetContainsValue(self, (value as *const T).cast()) != 0 }
+    }
+
+    /// Returns an iterator over the values in `self`.
+    ///
+    /// All values are retrieved at once using
+    /// [`CFSetGetValues`](https://developer.apple.com/documentation/corefoundation/cfsetgetvalues(_:_:)).
+    /// The order of values is unspecified.
+    #[doc(alias = "CFSetGetValues")]
+    pub fn iter(&self) -> CFSetIter<'_, T> {
+        let len = self.len() as usize;
+        let mut values = vec![ptr::null(); len];
+
+        if len != 0 {
+            unsafe { CFSetGetValues(self, values.as_mut_ptr()) };
+        }
+
+        CFSetIter {
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns `self` as a set of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &CFSet<CFType> {
+        // SAFETY: All elements are `CFType` instances.
+        unsafe { &*(self as *const Self as *const CFSet<CFType>) }
+    }
+}
+
+impl CFSet<CFType> {
+    /// Casts `self` to a set of elements of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// All elements of `self` must be instances of `T`.
+    #[inline]
+    pub unsafe fn cast_unchecked<T: ObjectType>(&self) -> &CFSet<T> {
+        &*(self as *const Self as *const CFSet<T>)
+    }
+}
+
+/// An iterator over the values of a [`CFSet`].
+///
+/// This is created by [`CFSet::iter`].
+pub struct CFSetIter<'a, T> {
+    values: vec::IntoIter<*const c_void>,
+    marker: PhantomData<&'a CFSet<T>>,
+}
+
+impl<'a, T: ObjectType> Iterator for CFSetIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let value = self.values.next()?;
+
+        // SAFETY: The set retains its values for at least `'a`.
+        unsafe { Some(&*value.cast()) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T: ObjectType> ExactSizeIterator for CFSetIter<'_, T> {}
+
+subclass! {
+    /// A mutable unordered collection of distinct objects of type `T`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created set via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutableset?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutablesetref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableSet<T>: CFSet<T>;
+}
+
+impl<T: ObjectType + fmt::Debug> fmt::Debug for CFMutableSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFSet<T>).fmt(f)
+    }
+}
+
+impl<T: ObjectType> CFMutableSet<T> {
+    /// Creates an empty mutable set.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty mutable set that can contain at most `capacity`
+    /// values, where 0 means it has no limit.
+    ///
+    /// This uses
+    /// [`kCFTypeSetCallBacks`](https://developer.apple.com/documentation/corefoundation/kcftypesetcallbacks).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetcreatemutable(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetCreateMutable")]
+    pub fn with_capacity(capacity: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFSetCreateMutable(
+                allocator: Option<&CFAllocator>,
+                capacity: CFIndex,
+                call_backs: *const CFSetCallBacks,
+            ) -> *const CFType;
+        }
+
+        unsafe {
+            let set = CFSetCreateMutable(None, capacity, &kCFTypeSetCallBacks);
+            assert!(!set.is_null(), "Could not create CFMutableSet");
+            Arc::from_raw(set.cast())
+        }
+    }
+
+    /// Adds `value` to `self` if it does not already contain an equal value.
+    ///
+    /// Returns `true` if `value` was added.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetaddvalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetAddValue")]
+    pub fn insert(&mut self, value: &T) -> bool {
+        extern "C" {
+            fn CFSetAddValue(the_set: &CFType, value: *const c_void);
+        }
+
+        if self.contains(value) {
+            return false;
+        }
+        unsafe { CFSetAddValue(self, (value as *const T).cast()) };
+        true
+    }
+
+    /// Removes the value equal to `value`, returning it if there was one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetremovevalue(_:_:)).
+    #[inline]
+    #[doc(alias = "CFSetRemoveValue")]
+    pub fn remove(&mut self, value: &T) -> Option<Arc<T>> {
+        extern "C" {
+            fn CFSetRemoveValue(the_set: &CFType, value: *const c_void);
+        }
+
+        let removed = Arc::retain(self.get(value)?);
+        unsafe { CFSetRemoveValue(self, (value as *const T).cast()) };
+        Some(removed)
+    }
+
+    /// Removes all values from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsetremoveallvalues(_:)).
+    #[inline]
+    #[doc(alias = "CFSetRemoveAllValues")]
+    pub fn clear(&mut self) {
+        extern "C" {
+            fn CFSetRemoveAllValues(the_set: &CFType);
+        }
+        unsafe { CFSetRemoveAllValues(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFString;
+
+    #[test]
+    fn from_slice() {
+        let (a, b, c) = (
+            CFString::from_str("a"),
+            CFString::from_str("b"),
+            CFString::from_str("c"),
+        );
+        let set = CFSet::from_slice(&[&*a, &*b, &*a]);
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a));
+        assert!(set.contains(&CFString::from_str("b")));
+        assert!(!set.contains(&c));
+
+        let mut values: Vec<String> = set.iter().map(|s| s.to_string()).collect();
+        values.sort();
+        assert_eq!(values, ["a", "b"]);
+    }
+
+    #[test]
+    fn empty() {
+        let set = CFSet::<CFString>::from_slice(&[]);
+        assert!(set.is_empty());
+        assert_eq!(set.iter().count(), 0);
+    }
+
+    #[test]
+    fn dedupe_equal() {
+        // Distinct instances that are equal according to `CFEqual`. The
+        // string is long enough to not be stored as a tagged pointer.
+        let a = CFString::from_str("fruity set deduplication");
+        let b = CFString::from_str("fruity set deduplication");
+        assert_ne!(&*a as *const CFString, &*b as *const CFString);
+
+        let mut set = CFMutableSet::<CFString>::new();
+        let s = Arc::get_mut(&mut set).unwrap();
+
+        assert!(s.insert(&a));
+        assert!(!s.insert(&b));
+        assert_eq!(s.len(), 1);
+
+        // The original instance is kept.
+        assert!(ptr::eq(s.get(&b).unwrap(), &*a));
+
+        let removed = s.remove(&b).unwrap();
+        assert!(ptr::eq(&*removed, &*a));
+        assert!(s.remove(&b).is_none());
+        assert!(s.is_empty());
+
+        s.insert(&a);
+        s.clear();
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn untyped_downcast() {
+        let string = CFString::from_str("string");
+        let set = CFSet::from_slice(&[&*string]);
+
+        let untyped: Arc<CFType> = CFType::retain(&set);
+        let untyped = untyped.downcast::<CFSet<CFType>>().unwrap();
+        assert!(untyped.contains(&string));
+
+        let value = untyped.iter().next().unwrap();
+        assert_eq!(*value.downcast_ref::<CFString>().unwrap(), *"string");
+    }
+}
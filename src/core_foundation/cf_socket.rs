@@ -0,0 +1,677 @@
+use super::{
+    Boolean, CFAllocator, CFData, CFIndex, CFOptionFlags, CFRunLoopSource, CFString,
+    CFTimeInterval, CFType,
+};
+use crate::_priv::{abort_on_panic, cfcontext};
+use crate::core::Arc;
+use std::{
+    convert::TryInto,
+    error::Error,
+    ffi::c_void,
+    fmt, io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    ops,
+    os::raw::c_int,
+    sync,
+    time::Duration,
+};
+
+cf_subclass! {
+    /// A BSD socket whose activity is reported through a run loop source.
+    ///
+    /// The socket's [run loop source](#method.create_run_loop_source) must be
+    /// added to a run loop for its callback to be called.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfsocket?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfsocketref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFSocket: CFType;
+    type_id = CFSocketGetTypeID;
+}
+
+/// A native BSD socket file descriptor.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketnativehandle).
+pub type CFSocketNativeHandle = c_int;
+
+/// How incoming activity on a [`CFSocket`](struct.CFSocket.html) is reported
+/// to its callback.
+///
+/// These kinds of `CFSocketCallBackType` are values rather than flags, so a
+/// socket can only use one of them.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcallbacktype).
+#[repr(usize)] // CFOptionFlags
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[doc(alias = "CFSocketCallBackType")]
+pub enum CFSocketReadCallBack {
+    /// Data is available to read, which the callback must read itself.
+    #[doc(alias = "kCFSocketReadCallBack")]
+    Read = 1,
+    /// A new connection was accepted on a listening socket.
+    #[doc(alias = "kCFSocketAcceptCallBack")]
+    Accept = 2,
+    /// Incoming data was read in the background.
+    #[doc(alias = "kCFSocketDataCallBack")]
+    Data = 3,
+}
+
+/// The kinds of outgoing activity on a [`CFSocket`](struct.CFSocket.html)
+/// that cause its callback to be called, which can be combined using `|`.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcallbacktype).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[doc(alias = "CFSocketCallBackType")]
+pub struct CFSocketCallBackFlags(CFOptionFlags);
+
+impl CFSocketCallBackFlags {
+    /// No flags.
+    pub const NONE: Self = Self(0);
+
+    /// A background connection attempt finished.
+    #[doc(alias = "kCFSocketConnectCallBack")]
+    pub const CONNECT: Self = Self(4);
+
+    /// The socket can be written to.
+    #[doc(alias = "kCFSocketWriteCallBack")]
+    pub const WRITE: Self = Self(8);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for CFSocketCallBackFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for CFSocketCallBackFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for CFSocketCallBackFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// An activity reported to a [`CFSocket`](struct.CFSocket.html) callback.
+#[derive(Debug)]
+pub enum CFSocketEvent<'a> {
+    /// Data is available to read.
+    Read,
+    /// A new connection was accepted.
+    ///
+    /// The callback takes ownership of `handle` and must close it.
+    Accept {
+        /// The socket of the new connection.
+        handle: CFSocketNativeHandle,
+        /// The address of the connecting peer.
+        address: Option<SocketAddr>,
+    },
+    /// Incoming data was read, which is empty once the peer has closed the
+    /// connection.
+    Data {
+        /// The data that was read.
+        data: &'a CFData,
+        /// The address that the data was received from.
+        address: Option<SocketAddr>,
+    },
+    /// A background connection attempt finished.
+    Connect(io::Result<()>),
+    /// The socket can be written to.
+    Write,
+}
+
+/// An error returned by [`CFSocket`](struct.CFSocket.html) operations.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketerror).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFSocketError {
+    /// The operation failed.
+    #[doc(alias = "kCFSocketError")]
+    Error = -1,
+    /// The operation timed out.
+    #[doc(alias = "kCFSocketTimeout")]
+    Timeout = -2,
+}
+
+impl CFSocketError {
+    #[inline]
+    fn check(code: CFIndex) -> Result<(), Self> {
+        match code {
+            0 => Ok(()),
+            -2 => Err(Self::Timeout),
+            _ => Err(Self::Error),
+        }
+    }
+}
+
+impl fmt::Display for CFSocketError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Error => "socket operation failed".fmt(f),
+            Self::Timeout => "socket operation timed out".fmt(f),
+        }
+    }
+}
+
+impl Error for CFSocketError {}
+
+impl fmt::Debug for CFSocket {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFSocket")
+            .field("native", &self.native())
+            .field("address", &self.address())
+            .field("is_valid", &self.is_valid())
+            .finish()
+    }
+}
+
+// Darwin address families.
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 30;
+
+/// Returns `address` as the bytes of a `sockaddr_in` or `sockaddr_in6`.
+fn sockaddr_data(address: &SocketAddr) -> Arc<CFData> {
+    let mut bytes = Vec::with_capacity(28);
+    match address {
+        SocketAddr::V4(address) => {
+            bytes.extend_from_slice(&[16, AF_INET]);
+            bytes.extend_from_slice(&address.port().to_be_bytes());
+            bytes.extend_from_slice(&address.ip().octets());
+            bytes.extend_from_slice(&[0; 8]);
+        }
+        SocketAddr::V6(address) => {
+            bytes.extend_from_slice(&[28, AF_INET6]);
+            bytes.extend_from_slice(&address.port().to_be_bytes());
+            bytes.extend_from_slice(&address.flowinfo().to_ne_bytes());
+            bytes.extend_from_slice(&address.ip().octets());
+            bytes.extend_from_slice(&address.scope_id().to_ne_bytes());
+        }
+    }
+    CFData::from_bytes(&bytes)
+}
+
+/// Parses the bytes of a `sockaddr_in` or `sockaddr_in6`.
+fn parse_sockaddr(bytes: &[u8]) -> Option<SocketAddr> {
+    let u32_at = |i: usize| Some(u32::from_ne_bytes(bytes.get(i..i + 4)?.try_into().ok()?));
+
+    match *bytes.get(1)? {
+        AF_INET if bytes.len() >= 8 => {
+            let port = u16::from_be_bytes([bytes[2], bytes[3]]);
+            let ip = Ipv4Addr::new(bytes[4], bytes[5], bytes[6], bytes[7]);
+            Some(SocketAddrV4::new(ip, port).into())
+        }
+        AF_INET6 if bytes.len() >= 28 => {
+            let port = u16::from_be_bytes([bytes[2], bytes[3]]);
+            let ip: [u8; 16] = bytes[8..24].try_into().ok()?;
+            let ip = Ipv6Addr::from(ip);
+            Some(SocketAddrV6::new(ip, port, u32_at(4)?, u32_at(24)?).into())
+        }
+        _ => None,
+    }
+}
+
+#[repr(C)]
+struct CFSocketContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+}
+
+type CFSocketCallBack = unsafe extern "C" fn(
+    s: &CFSocket,
+    call_back_type: CFOptionFlags,
+    address: Option<&CFData>,
+    data: *const c_void,
+    info: *mut c_void,
+);
+
+/// Calls the closure of a socket whose context info is a `sync::Arc<F>`.
+unsafe extern "C" fn call<F>(
+    socket: &CFSocket,
+    call_back_type: CFOptionFlags,
+    address: Option<&CFData>,
+    data: *const c_void,
+    info: *mut c_void,
+) where
+    F: Fn(&CFSocket, CFSocketEvent),
+{
+    let f = &*info.cast::<F>();
+    let address = address.and_then(|address| parse_sockaddr(address));
+
+    let event = match call_back_type {
+        1 => CFSocketEvent::Read,
+        2 => CFSocketEvent::Accept {
+            handle: *data.cast::<CFSocketNativeHandle>(),
+            address,
+        },
+        3 => CFSocketEvent::Data {
+            data: &*data.cast::<CFData>(),
+            address,
+        },
+        // `data` points to an error code if the connection failed.
+        4 => match data.cast::<i32>().as_ref() {
+            Some(&code) => CFSocketEvent::Connect(Err(io::Error::from_raw_os_error(code))),
+            None => CFSocketEvent::Connect(Ok(())),
+        },
+        8 => CFSocketEvent::Write,
+        _ => return,
+    };
+
+    abort_on_panic(|| f(socket, event));
+}
+
+#[inline]
+fn call_back_types(
+    read: Option<CFSocketReadCallBack>,
+    flags: CFSocketCallBackFlags,
+) -> CFOptionFlags {
+    read.map_or(0, |read| read as CFOptionFlags) | flags.0
+}
+
+/// Creating sockets.
+impl CFSocket {
+    /// Creates a socket that calls `f` for incoming activity of `read`, if
+    /// any, and outgoing activity of `flags`.
+    ///
+    /// If `protocol_family`, `socket_type`, or `protocol` is 0, the default
+    /// of `PF_INET`, `SOCK_STREAM`, or TCP respectively is used.
+    ///
+    /// `f` must be `Send + Sync` because the socket's run loop source may be
+    /// added to the run loop of any thread. If it panics, the process is
+    /// aborted since unwinding into Core Foundation is undefined behavior.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcreate(_:_:_:_:_:_:_:)).
+    #[doc(alias = "CFSocketCreate")]
+    pub fn new<F>(
+        protocol_family: i32,
+        socket_type: i32,
+        protocol: i32,
+        read: Option<CFSocketReadCallBack>,
+        flags: CFSocketCallBackFlags,
+        f: F,
+    ) -> Option<Arc<Self>>
+    where
+        F: Fn(&CFSocket, CFSocketEvent) + Send + Sync + 'static,
+    {
+        extern "C" {
+            fn CFSocketCreate(
+                allocator: Option<&CFAllocator>,
+                protocol_family: i32,
+                socket_type: i32,
+                protocol: i32,
+                call_back_types: CFOptionFlags,
+                callout: CFSocketCallBack,
+                context: &CFSocketContext,
+            ) -> Option<Arc<CFSocket>>;
+        }
+
+        Self::with_context(f, |callout, context| unsafe {
+            CFSocketCreate(
+                None,
+                protocol_family,
+                socket_type,
+                protocol,
+                call_back_types(read, flags),
+                callout,
+                context,
+            )
+        })
+    }
+
+    /// Creates a socket for the existing `handle` that calls `f` for incoming
+    /// activity of `read`, if any, and outgoing activity of `flags`.
+    ///
+    /// The socket takes ownership of `handle` and closes it once invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcreatewithnative(_:_:_:_:_:)).
+    #[doc(alias = "CFSocketCreateWithNative")]
+    pub fn with_native<F>(
+        handle: CFSocketNativeHandle,
+        read: Option<CFSocketReadCallBack>,
+        flags: CFSocketCallBackFlags,
+        f: F,
+    ) -> Option<Arc<Self>>
+    where
+        F: Fn(&CFSocket, CFSocketEvent) + Send + Sync + 'static,
+    {
+        extern "C" {
+            fn CFSocketCreateWithNative(
+                allocator: Option<&CFAllocator>,
+                sock: CFSocketNativeHandle,
+                call_back_types: CFOptionFlags,
+                callout: CFSocketCallBack,
+                context: &CFSocketContext,
+            ) -> Option<Arc<CFSocket>>;
+        }
+
+        Self::with_context(f, |callout, context| unsafe {
+            CFSocketCreateWithNative(None, handle, call_back_types(read, flags), callout, context)
+        })
+    }
+
+    fn with_context<F>(
+        f: F,
+        create: impl FnOnce(CFSocketCallBack, &CFSocketContext) -> Option<Arc<Self>>,
+    ) -> Option<Arc<Self>>
+    where
+        F: Fn(&CFSocket, CFSocketEvent) + Send + Sync + 'static,
+    {
+        let info = sync::Arc::new(f);
+        let context = CFSocketContext {
+            version: 0,
+            info: sync::Arc::as_ptr(&info) as *mut c_void,
+            retain: Some(cfcontext::retain::<F>),
+            release: Some(cfcontext::release::<F>),
+            copy_description: Some(cfcontext::copy_description),
+        };
+
+        // The socket retains `info` through the context, so our reference
+        // can be dropped once it is created.
+        create(call::<F>, &context)
+    }
+}
+
+/// Using sockets.
+impl CFSocket {
+    /// Binds `self` to `address` and starts listening for connections.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketsetaddress(_:_:)).
+    #[inline]
+    #[doc(alias = "CFSocketSetAddress")]
+    pub fn set_address(&self, address: SocketAddr) -> Result<(), CFSocketError> {
+        extern "C" {
+            fn CFSocketSetAddress(s: &CFSocket, address: &CFData) -> CFIndex;
+        }
+        CFSocketError::check(unsafe { CFSocketSetAddress(self, &sockaddr_data(&address)) })
+    }
+
+    /// Connects `self` to `address`.
+    ///
+    /// If `timeout` is `None`, the connection is made in the background and
+    /// reported as [`CFSocketEvent::Connect`](enum.CFSocketEvent.html#variant.Connect).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketconnecttoaddress(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFSocketConnectToAddress")]
+    pub fn connect_to_address(
+        &self,
+        address: SocketAddr,
+        timeout: Option<Duration>,
+    ) -> Result<(), CFSocketError> {
+        extern "C" {
+            fn CFSocketConnectToAddress(
+                s: &CFSocket,
+                address: &CFData,
+                timeout: CFTimeInterval,
+            ) -> CFIndex;
+        }
+
+        // A negative timeout connects in the background.
+        let timeout = timeout.map_or(-1.0, |timeout| timeout.as_secs_f64());
+        CFSocketError::check(unsafe {
+            CFSocketConnectToAddress(self, &sockaddr_data(&address), timeout)
+        })
+    }
+
+    /// Sends `data` through `self`, to `address` if it is not connected.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketsenddata(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFSocketSendData")]
+    pub fn send(
+        &self,
+        address: Option<SocketAddr>,
+        data: &CFData,
+        timeout: Duration,
+    ) -> Result<(), CFSocketError> {
+        extern "C" {
+            fn CFSocketSendData(
+                s: &CFSocket,
+                address: Option<&CFData>,
+                data: &CFData,
+                timeout: CFTimeInterval,
+            ) -> CFIndex;
+        }
+
+        let address = address.as_ref().map(sockaddr_data);
+        CFSocketError::check(unsafe {
+            CFSocketSendData(self, address.as_deref(), data, timeout.as_secs_f64())
+        })
+    }
+
+    /// Returns the local address of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcopyaddress(_:)).
+    #[inline]
+    #[doc(alias = "CFSocketCopyAddress")]
+    pub fn address(&self) -> Option<SocketAddr> {
+        extern "C" {
+            fn CFSocketCopyAddress(s: &CFSocket) -> Option<Arc<CFData>>;
+        }
+        parse_sockaddr(&unsafe { CFSocketCopyAddress(self) }?)
+    }
+
+    /// Returns the address of the peer that `self` is connected to.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcopypeeraddress(_:)).
+    #[inline]
+    #[doc(alias = "CFSocketCopyPeerAddress")]
+    pub fn peer_address(&self) -> Option<SocketAddr> {
+        extern "C" {
+            fn CFSocketCopyPeerAddress(s: &CFSocket) -> Option<Arc<CFData>>;
+        }
+        parse_sockaddr(&unsafe { CFSocketCopyPeerAddress(self) }?)
+    }
+
+    /// Returns the native socket of `self`, or -1 if it has been
+    /// invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketgetnative(_:)).
+    #[inline]
+    #[doc(alias = "CFSocketGetNative")]
+    pub fn native(&self) -> CFSocketNativeHandle {
+        extern "C" {
+            fn CFSocketGetNative(s: &CFSocket) -> CFSocketNativeHandle;
+        }
+        unsafe { CFSocketGetNative(self) }
+    }
+
+    /// Creates a run loop source for the activity of `self`, with priority
+    /// `order`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketcreaterunloopsource(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFSocketCreateRunLoopSource")]
+    pub fn create_run_loop_source(&self, order: CFIndex) -> Arc<CFRunLoopSource> {
+        extern "C" {
+            fn CFSocketCreateRunLoopSource(
+                allocator: Option<&CFAllocator>,
+                s: &CFSocket,
+                order: CFIndex,
+            ) -> Option<Arc<CFRunLoopSource>>;
+        }
+        unsafe { CFSocketCreateRunLoopSource(None, self, order) }
+            .expect("Could not create CFRunLoopSource")
+    }
+
+    /// Stops `self` from sending or receiving data and closes its native
+    /// socket.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketinvalidate(_:)).
+    #[inline]
+    #[doc(alias = "CFSocketInvalidate")]
+    pub fn invalidate(&self) {
+        extern "C" {
+            fn CFSocketInvalidate(s: &CFSocket);
+        }
+        unsafe { CFSocketInvalidate(self) }
+    }
+
+    /// Returns `true` if `self` has not been invalidated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfsocketisvalid(_:)).
+    #[inline]
+    #[doc(alias = "CFSocketIsValid")]
+    pub fn is_valid(&self) -> bool {
+        extern "C" {
+            fn CFSocketIsValid(s: &CFSocket) -> Boolean;
+        }
+        unsafe { CFSocketIsValid(self) != 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::{CFRunLoop, CFRunLoopRunResult};
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        os::unix::io::{FromRawFd, IntoRawFd},
+        sync::{mpsc, Mutex},
+        thread,
+    };
+
+    fn run(socket: &CFSocket) -> CFRunLoopRunResult {
+        let source = socket.create_run_loop_source(0);
+        source.add_to(&CFRunLoop::current(), CFRunLoop::default_mode());
+        let result =
+            CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(5), false);
+        source.invalidate();
+        result
+    }
+
+    #[test]
+    fn sockaddr() {
+        let addresses: [SocketAddr; 3] = [
+            "127.0.0.1:8080".parse().unwrap(),
+            "[::1]:443".parse().unwrap(),
+            SocketAddrV6::new(Ipv6Addr::LOCALHOST, 1, 2, 3).into(),
+        ];
+        for address in &addresses {
+            assert_eq!(parse_sockaddr(&sockaddr_data(address)), Some(*address));
+        }
+        assert_eq!(parse_sockaddr(&[]), None);
+    }
+
+    #[test]
+    fn call_back_types() {
+        let read = Some(CFSocketReadCallBack::Accept);
+        let flags = CFSocketCallBackFlags::CONNECT | CFSocketCallBackFlags::WRITE;
+        assert_eq!(super::call_back_types(read, flags), 2 | 4 | 8);
+        assert_eq!(
+            super::call_back_types(None, CFSocketCallBackFlags::WRITE),
+            8
+        );
+        assert_eq!(super::call_back_types(None, CFSocketCallBackFlags::NONE), 0);
+
+        assert!(flags.contains(CFSocketCallBackFlags::WRITE));
+        assert!(!CFSocketCallBackFlags::CONNECT.contains(CFSocketCallBackFlags::WRITE));
+    }
+
+    #[test]
+    fn accept() {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+
+        let read = Some(CFSocketReadCallBack::Accept);
+        let flags = CFSocketCallBackFlags::NONE;
+        let socket = CFSocket::new(0, 0, 0, read, flags, move |_, event| {
+            if let CFSocketEvent::Accept { handle, address } = event {
+                let stream = unsafe { TcpStream::from_raw_fd(handle) };
+                sender.lock().unwrap().send((stream, address)).unwrap();
+                CFRunLoop::current().stop();
+            }
+        })
+        .unwrap();
+
+        socket.set_address("127.0.0.1:0".parse().unwrap()).unwrap();
+        let address = socket.address().unwrap();
+        assert_ne!(address.port(), 0);
+
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"hello").unwrap();
+            stream.local_addr().unwrap()
+        });
+
+        assert_eq!(run(&socket), CFRunLoopRunResult::Stopped);
+        let client_address = client.join().unwrap();
+
+        let (mut stream, peer) = receiver.recv().unwrap();
+        assert_eq!(peer, Some(client_address));
+
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "hello");
+
+        socket.invalidate();
+        assert!(!socket.is_valid());
+        assert_eq!(socket.native(), -1);
+    }
+
+    #[test]
+    fn data_and_send() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        let received = sync::Arc::new(Mutex::new(Vec::new()));
+        let read = Some(CFSocketReadCallBack::Data);
+        let flags = CFSocketCallBackFlags::NONE;
+        let socket = CFSocket::with_native(server.into_raw_fd(), read, flags, {
+            let received = received.clone();
+            move |socket, event| {
+                if let CFSocketEvent::Data { data, .. } = event {
+                    if data.is_empty() {
+                        // The peer closed the connection.
+                        CFRunLoop::current().stop();
+                    } else {
+                        received.lock().unwrap().extend_from_slice(data);
+                        let reply = CFData::from_bytes(b"pong");
+                        socket.send(None, &reply, Duration::from_secs(1)).unwrap();
+                    }
+                }
+            }
+        })
+        .unwrap();
+
+        let client_thread = thread::spawn(move || {
+            client.write_all(b"ping").unwrap();
+            let mut reply = [0; 4];
+            client.read_exact(&mut reply).unwrap();
+            reply
+        });
+
+        // The client closes the connection once it has read the reply.
+        assert_eq!(run(&socket), CFRunLoopRunResult::Stopped);
+        let reply = client_thread.join().unwrap();
+
+        assert_eq!(&reply, b"pong");
+        assert_eq!(&*received.lock().unwrap(), b"ping");
+        socket.invalidate();
+    }
+}
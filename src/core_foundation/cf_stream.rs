@@ -0,0 +1,435 @@
+use super::{Boolean, CFAllocator, CFError, CFErrorRef, CFIndex, CFType, CFURL};
+use crate::core::Arc;
+use std::{fmt, io};
+
+cf_subclass! {
+    /// A readable stream of bytes.
+    ///
+    /// The stream must be opened with [`open`](#method.open) before it can be
+    /// read from. Reads through [`io::Read`](https://doc.rust-lang.org/std/io/trait.Read.html)
+    /// block until bytes are available.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfreadstream?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfreadstreamref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFReadStream: CFType;
+    type_id = CFReadStreamGetTypeID;
+}
+
+cf_subclass! {
+    /// A writable stream of bytes.
+    ///
+    /// The stream must be opened with [`open`](#method.open) before it can be
+    /// written to. Writes through [`io::Write`](https://doc.rust-lang.org/std/io/trait.Write.html)
+    /// block until the stream can accept bytes.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfwritestream?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfwritestreamref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFWriteStream: CFType;
+    type_id = CFWriteStreamGetTypeID;
+}
+
+/// The state of a [`CFReadStream`](struct.CFReadStream.html) or
+/// [`CFWriteStream`](struct.CFWriteStream.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstreamstatus).
+#[repr(isize)] // CFIndex
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFStreamStatus {
+    /// The stream has not been opened.
+    NotOpen = 0,
+    /// The stream is being opened.
+    Opening = 1,
+    /// The stream is open.
+    Open = 2,
+    /// The stream is being read from.
+    Reading = 3,
+    /// The stream is being written to.
+    Writing = 4,
+    /// There are no more bytes to read, or no more bytes can be written.
+    AtEnd = 5,
+    /// The stream has been closed.
+    Closed = 6,
+    /// An error occurred on the stream.
+    Error = 7,
+}
+
+/// Converts the error of a failed stream operation.
+fn io_error(error: Option<CFErrorRef>) -> io::Error {
+    match error {
+        Some(error) if *error.domain() == *CFError::posix_domain() => {
+            io::Error::from_raw_os_error(error.code() as i32)
+        }
+        Some(error) => io::Error::other(error.description().to_string()),
+        None => io::Error::other("stream operation failed"),
+    }
+}
+
+impl fmt::Debug for CFReadStream {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFReadStream")
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+impl fmt::Debug for CFWriteStream {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFWriteStream")
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+/// Creating streams.
+impl CFReadStream {
+    /// Creates a stream for reading the file at `url`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamcreatewithfile(_:_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamCreateWithFile")]
+    pub fn with_file(url: &CFURL) -> Arc<Self> {
+        extern "C" {
+            fn CFReadStreamCreateWithFile(
+                alloc: Option<&CFAllocator>,
+                file_url: &CFURL,
+            ) -> Option<Arc<CFReadStream>>;
+        }
+        unsafe { CFReadStreamCreateWithFile(None, url) }.expect("Could not create CFReadStream")
+    }
+
+    /// Creates a pair of streams where bytes written to the write stream can
+    /// be read from the read stream.
+    ///
+    /// At most `buffer_size` bytes are buffered between the two streams.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstreamcreateboundpair(_:_:_:_:)).
+    #[doc(alias = "CFStreamCreateBoundPair")]
+    pub fn bound_pair(buffer_size: CFIndex) -> (Arc<CFReadStream>, Arc<CFWriteStream>) {
+        extern "C" {
+            fn CFStreamCreateBoundPair(
+                alloc: Option<&CFAllocator>,
+                read_stream: *mut Option<Arc<CFReadStream>>,
+                write_stream: *mut Option<Arc<CFWriteStream>>,
+                transfer_buffer_size: CFIndex,
+            );
+        }
+
+        let mut read_stream = None;
+        let mut write_stream = None;
+        unsafe { CFStreamCreateBoundPair(None, &mut read_stream, &mut write_stream, buffer_size) };
+
+        match (read_stream, write_stream) {
+            (Some(read_stream), Some(write_stream)) => (read_stream, write_stream),
+            _ => panic!("Could not create bound CFStream pair"),
+        }
+    }
+}
+
+/// Using read streams.
+impl CFReadStream {
+    /// Opens `self` for reading, returning `false` on failure.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamopen(_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamOpen")]
+    pub fn open(&self) -> bool {
+        extern "C" {
+            fn CFReadStreamOpen(stream: &CFReadStream) -> Boolean;
+        }
+        unsafe { CFReadStreamOpen(self) != 0 }
+    }
+
+    /// Closes `self`, after which it cannot be reopened.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamclose(_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamClose")]
+    pub fn close(&self) {
+        extern "C" {
+            fn CFReadStreamClose(stream: &CFReadStream);
+        }
+        unsafe { CFReadStreamClose(self) }
+    }
+
+    /// Returns the current state of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamgetstatus(_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamGetStatus")]
+    pub fn status(&self) -> CFStreamStatus {
+        extern "C" {
+            fn CFReadStreamGetStatus(stream: &CFReadStream) -> CFStreamStatus;
+        }
+        unsafe { CFReadStreamGetStatus(self) }
+    }
+
+    /// Returns the error that occurred on `self`, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamcopyerror(_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamCopyError")]
+    pub fn error(&self) -> Option<CFErrorRef> {
+        extern "C" {
+            fn CFReadStreamCopyError(stream: &CFReadStream) -> Option<CFErrorRef>;
+        }
+        unsafe { CFReadStreamCopyError(self) }
+    }
+
+    /// Returns `true` if bytes can be read from `self` without blocking.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfreadstreamhasbytesavailable(_:)).
+    #[inline]
+    #[doc(alias = "CFReadStreamHasBytesAvailable")]
+    pub fn has_bytes_available(&self) -> bool {
+        extern "C" {
+            fn CFReadStreamHasBytesAvailable(stream: &CFReadStream) -> Boolean;
+        }
+        unsafe { CFReadStreamHasBytesAvailable(self) != 0 }
+    }
+}
+
+impl io::Read for &CFReadStream {
+    #[doc(alias = "CFReadStreamRead")]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        extern "C" {
+            fn CFReadStreamRead(
+                stream: &CFReadStream,
+                buffer: *mut u8,
+                buffer_length: CFIndex,
+            ) -> CFIndex;
+        }
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Returns 0 at the end of the stream and -1 on error.
+        match unsafe { CFReadStreamRead(self, buf.as_mut_ptr(), buf.len() as CFIndex) } {
+            -1 => Err(io_error(self.error())),
+            read => Ok(read as usize),
+        }
+    }
+}
+
+impl io::Read for CFReadStream {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+/// Creating streams.
+impl CFWriteStream {
+    /// Creates a stream for writing to the file at `url`, which replaces its
+    /// contents.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamcreatewithfile(_:_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamCreateWithFile")]
+    pub fn with_file(url: &CFURL) -> Arc<Self> {
+        extern "C" {
+            fn CFWriteStreamCreateWithFile(
+                alloc: Option<&CFAllocator>,
+                file_url: &CFURL,
+            ) -> Option<Arc<CFWriteStream>>;
+        }
+        unsafe { CFWriteStreamCreateWithFile(None, url) }.expect("Could not create CFWriteStream")
+    }
+}
+
+/// Using write streams.
+impl CFWriteStream {
+    /// Opens `self` for writing, returning `false` on failure.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamopen(_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamOpen")]
+    pub fn open(&self) -> bool {
+        extern "C" {
+            fn CFWriteStreamOpen(stream: &CFWriteStream) -> Boolean;
+        }
+        unsafe { CFWriteStreamOpen(self) != 0 }
+    }
+
+    /// Closes `self`, after which it cannot be reopened.
+    ///
+    /// Closing the write stream of a bound pair ends its read stream.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamclose(_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamClose")]
+    pub fn close(&self) {
+        extern "C" {
+            fn CFWriteStreamClose(stream: &CFWriteStream);
+        }
+        unsafe { CFWriteStreamClose(self) }
+    }
+
+    /// Returns the current state of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamgetstatus(_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamGetStatus")]
+    pub fn status(&self) -> CFStreamStatus {
+        extern "C" {
+            fn CFWriteStreamGetStatus(stream: &CFWriteStream) -> CFStreamStatus;
+        }
+        unsafe { CFWriteStreamGetStatus(self) }
+    }
+
+    /// Returns the error that occurred on `self`, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamcopyerror(_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamCopyError")]
+    pub fn error(&self) -> Option<CFErrorRef> {
+        extern "C" {
+            fn CFWriteStreamCopyError(stream: &CFWriteStream) -> Option<CFErrorRef>;
+        }
+        unsafe { CFWriteStreamCopyError(self) }
+    }
+
+    /// Returns `true` if bytes can be written to `self` without blocking.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfwritestreamcanacceptbytes(_:)).
+    #[inline]
+    #[doc(alias = "CFWriteStreamCanAcceptBytes")]
+    pub fn can_accept_bytes(&self) -> bool {
+        extern "C" {
+            fn CFWriteStreamCanAcceptBytes(stream: &CFWriteStream) -> Boolean;
+        }
+        unsafe { CFWriteStreamCanAcceptBytes(self) != 0 }
+    }
+}
+
+impl io::Write for &CFWriteStream {
+    #[doc(alias = "CFWriteStreamWrite")]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        extern "C" {
+            fn CFWriteStreamWrite(
+                stream: &CFWriteStream,
+                buffer: *const u8,
+                buffer_length: CFIndex,
+            ) -> CFIndex;
+        }
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Writes may be partial. Returns 0 if the stream is full and -1 on
+        // error.
+        match unsafe { CFWriteStreamWrite(self, buf.as_ptr(), buf.len() as CFIndex) } {
+            -1 => Err(io_error(self.error())),
+            written => Ok(written as usize),
+        }
+    }
+
+    /// This does nothing since writes are not buffered.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for CFWriteStream {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use std::{
+        fs,
+        io::{Read, Write},
+        thread,
+    };
+
+    #[test]
+    fn bound_pair() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+
+        // A small buffer forces partial writes and reads.
+        let (reader, writer) = CFReadStream::bound_pair(64);
+        assert_eq!(reader.status(), CFStreamStatus::NotOpen);
+        assert!(reader.open());
+        assert!(writer.open());
+        assert_eq!(reader.status(), CFStreamStatus::Open);
+
+        let handle = thread::spawn({
+            let data = data.clone();
+            move || {
+                (&*writer).write_all(&data).unwrap();
+                writer.close();
+            }
+        });
+
+        let mut received = Vec::new();
+        (&*reader).read_to_end(&mut received).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(received.len(), data.len());
+        assert!(received == data);
+        assert_eq!(reader.status(), CFStreamStatus::AtEnd);
+
+        // Reads at the end of the stream return 0.
+        assert_eq!((&*reader).read(&mut [0; 8]).unwrap(), 0);
+        reader.close();
+        assert_eq!(reader.status(), CFStreamStatus::Closed);
+    }
+
+    #[test]
+    fn file() {
+        let dir = TempDir::new("cf-stream-file");
+        let path = dir.0.join("file");
+        let url = CFURL::from_file_path(&path, false);
+
+        let mut writer = CFWriteStream::with_file(&url);
+        let writer = Arc::get_mut(&mut writer).unwrap();
+        assert!(writer.open());
+        writer.write_all(b"hello, ").unwrap();
+        writer.write_all(b"world").unwrap();
+        writer.close();
+
+        let mut reader = CFReadStream::with_file(&url);
+        let reader = Arc::get_mut(&mut reader).unwrap();
+        assert!(reader.open());
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+        reader.close();
+
+        assert_eq!(contents, "hello, world");
+        assert_eq!(fs::read(&path).unwrap(), b"hello, world");
+    }
+
+    #[test]
+    fn missing_file() {
+        let dir = TempDir::new("cf-stream-missing-file");
+        let path = dir.0.join("missing");
+        let reader = CFReadStream::with_file(&CFURL::from_file_path(&path, false));
+
+        assert!(!reader.open());
+        assert_eq!(reader.status(), CFStreamStatus::Error);
+
+        let error = reader.error().unwrap();
+        assert_eq!(*error.domain(), *CFError::posix_domain());
+
+        let error = (&*reader).read(&mut [0; 8]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,115 @@
+/// Creates a [`CFString`](core_foundation/struct.CFString.html) from a static
+/// string.
+///
+/// # Feature Flag
+///
+/// This macro is defined in [`core_foundation`](core_foundation/index.html),
+/// which requires the **`core_foundation`**
+/// [feature flag](index.html#feature-flags).
+///
+/// # Examples
+///
+/// This macro takes a either a `"string"` literal or `const` string slice as
+/// the argument:
+///
+/// ```
+/// let hello = fruity::cf_string!("hello");
+/// assert_eq!(hello.to_string(), "hello");
+///
+/// const WORLD: &str = "world";
+/// let world = fruity::cf_string!(WORLD);
+/// assert_eq!(world.to_string(), WORLD);
+/// ```
+///
+/// The result of this macro can even be used to create `static` values:
+///
+/// ```
+/// # use fruity::core_foundation::CFString;
+/// static WORLD: &CFString = fruity::cf_string!("world");
+///
+/// assert_eq!(WORLD.to_string(), "world");
+/// ```
+///
+/// # Unicode Strings
+///
+/// Like with [`ns_string!`](macro.ns_string.html), non-ASCII strings and
+/// strings with interior null bytes are transcoded to UTF-16:
+///
+/// ```
+/// # use fruity::core_foundation::CFString;
+/// static HELLO_RU: &CFString = fruity::cf_string!("Привет");
+///
+/// assert_eq!(HELLO_RU.to_string(), "Привет");
+/// ```
+///
+/// # Runtime Cost
+///
+/// None.
+///
+/// The result is equivalent to `CFSTR("string")` in C. Retaining and releasing
+/// the result does nothing.
+///
+/// Because of that, this should be preferred over
+/// [`CFString::from_str`](core_foundation/struct.CFString.html#method.from_str)
+/// where possible.
+#[macro_export]
+macro_rules! cf_string {
+    ($s:expr) => {{
+        let cfstring_ptr: *const $crate::_priv::c_void = $crate::_cfstring_ptr!($s);
+
+        union Cast<T: 'static> {
+            pointer: *const T,
+            reference: &'static T,
+        }
+
+        #[allow(unused_unsafe)]
+        let cf_string: &$crate::core_foundation::CFString = unsafe {
+            Cast {
+                pointer: cfstring_ptr.cast(),
+            }
+            .reference
+        };
+
+        cf_string
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::CFString;
+    use crate::core::Arc;
+
+    #[test]
+    fn cf_string() {
+        macro_rules! test {
+            ($($s:expr,)+) => {$({
+                static STRING: &CFString = cf_string!($s);
+                assert_eq!(STRING.to_string(), $s);
+                assert_eq!(*STRING, *CFString::from_str($s));
+            })+};
+        }
+
+        test! {
+            "",
+            "asdf",
+            "a\0b",
+            "🦀",
+            "🏳️‍🌈",
+            "𝄞music",
+            "ääääh",
+            "讓每個人都能打造出。",
+        }
+    }
+
+    #[test]
+    fn retain_release() {
+        let string = cf_string!("constant");
+        let count = string.retain_count();
+
+        let retained = Arc::retain(string);
+        drop(retained);
+
+        assert_eq!(string.retain_count(), count);
+        assert_eq!(string.to_string(), "constant");
+    }
+}
@@ -0,0 +1,509 @@
+use super::{Boolean, CFIndex, CFRange, CFType};
+use crate::core::Arc;
+use std::{ffi::CStr, fmt, os::raw::c_char, ptr, str};
+
+#[macro_use]
+mod macros;
+
+cf_subclass! {
+    /// A reference to an immutable string object.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfstring?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfstringref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFString: CFType;
+    type_id = CFStringGetTypeID;
+}
+
+impl PartialEq<str> for CFString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        match self.as_str() {
+            Some(s) => s == other,
+            None => self.with_utf8(|s| s == other),
+        }
+    }
+}
+
+impl PartialEq<CFString> for str {
+    #[inline]
+    fn eq(&self, other: &CFString) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<&str> for CFString {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Debug for CFString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.with_utf8(|s| fmt::Debug::fmt(s, f))
+    }
+}
+
+impl fmt::Display for CFString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.with_utf8(|s| fmt::Display::fmt(s, f))
+    }
+}
+
+type CFStringEncoding = u32;
+
+#[allow(non_upper_case_globals)]
+const kCFStringEncodingUTF8: CFStringEncoding = 0x08000100;
+
+impl CFString {
+    /// Creates an immutable string object from copying a slice.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1543419-cfstringcreatewithbytes).
+    #[inline]
+    #[doc(alias = "CFStringCreateWithBytes")]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Arc<Self> {
+        extern "C" {
+            fn CFStringCreateWithBytes(
+                alloc: Option<&super::CFAllocator>,
+                bytes: *const u8,
+                num_bytes: CFIndex,
+                encoding: CFStringEncoding,
+                is_external_representation: Boolean,
+            ) -> *const CFString;
+        }
+
+        unsafe {
+            let string = CFStringCreateWithBytes(
+                None,
+                s.as_ptr(),
+                s.len() as CFIndex,
+                kCFStringEncodingUTF8,
+                0,
+            );
+            assert!(!string.is_null(), "Could not create CFString");
+            Arc::from_raw(string)
+        }
+    }
+
+    /// Returns the number of UTF-16 code units in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1542853-cfstringgetlength).
+    #[inline]
+    #[doc(alias = "CFStringGetLength")]
+    #[doc(alias = "length")]
+    pub fn len(&self) -> CFIndex {
+        extern "C" {
+            fn CFStringGetLength(the_string: &CFString) -> CFIndex;
+        }
+        unsafe { CFStringGetLength(self) }
+    }
+
+    /// Returns `true` if `self` contains no characters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Getting contents as [UTF-8](https://en.wikipedia.org/wiki/UTF-8).
+impl CFString {
+    /// Returns the contents of `self` as a native UTF-8 string slice, or `None`
+    /// if the internal storage of `self` does not allow this to be returned
+    /// efficiently.
+    ///
+    /// This is retrieved using
+    /// [`CFStringGetCStringPtr`](https://developer.apple.com/documentation/corefoundation/1542133-cfstringgetcstringptr)
+    /// and
+    /// [`kCFStringEncodingUTF8`](https://developer.apple.com/documentation/corefoundation/cfstringbuiltinencodings/kcfstringencodingutf8).
+    /// Strings with interior null bytes are never returned because they
+    /// cannot be represented by a C string.
+    ///
+    /// Unlike [`NSString::as_str`](crate::foundation::NSString::as_str), this
+    /// is safe because the string cannot be mutated while it is borrowed.
+    #[inline]
+    #[doc(alias = "CFStringGetCStringPtr")]
+    pub fn as_str(&self) -> Option<&str> {
+        // `NSString` is toll-free bridged and declares this with its own type.
+        #[allow(clashing_extern_declarations)]
+        extern "C" {
+            fn CFStringGetCStringPtr(
+                the_string: &CFString,
+                encoding: CFStringEncoding,
+            ) -> *const c_char;
+        }
+
+        unsafe {
+            let ptr = CFStringGetCStringPtr(self, kCFStringEncodingUTF8);
+            if ptr.is_null() {
+                return None;
+            }
+
+            let s = str::from_utf8_unchecked(CStr::from_ptr(ptr).to_bytes());
+
+            // The C string is only a complete representation if `self` does
+            // not have interior null bytes.
+            if s.encode_utf16().count() as CFIndex == self.len() {
+                Some(s)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Calls `f` with the contents of `self` as UTF-8, transcoding into a
+    /// temporary buffer only if the internal storage of `self` is not already
+    /// UTF-8.
+    pub(crate) fn with_utf8<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&str) -> T,
+    {
+        if let Some(s) = self.as_str() {
+            return f(s);
+        }
+
+        extern "C" {
+            fn CFStringGetBytes(
+                the_string: &CFString,
+                range: CFRange,
+                encoding: CFStringEncoding,
+                loss_byte: u8,
+                is_external_representation: Boolean,
+                buffer: *mut u8,
+                max_buf_len: CFIndex,
+                used_buf_len: *mut CFIndex,
+            ) -> CFIndex;
+        }
+
+        let range = CFRange::new(0, self.len());
+
+        // Unpaired surrogates cannot be represented in UTF-8, so
+        // `CFStringGetBytes` replaces them with `?`. The result is still
+        // decoded lossily rather than relying on that behavior.
+        let mut len: CFIndex = 0;
+        unsafe {
+            CFStringGetBytes(
+                self,
+                range,
+                kCFStringEncodingUTF8,
+                b'?',
+                0,
+                ptr::null_mut(),
+                0,
+                &mut len,
+            );
+        }
+
+        let mut buf = Vec::<u8>::with_capacity(len as usize);
+        unsafe {
+            CFStringGetBytes(
+                self,
+                range,
+                kCFStringEncodingUTF8,
+                b'?',
+                0,
+                buf.as_mut_ptr(),
+                len,
+                &mut len,
+            );
+            buf.set_len(len as usize);
+        }
+
+        f(&String::from_utf8_lossy(&buf))
+    }
+}
+
+subclass! {
+    /// A reference to a mutable string object.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a newly
+    /// created string via [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfmutablestring?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfmutablestringref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFMutableString: CFString;
+}
+
+impl PartialEq<str> for CFMutableString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        (self as &CFString) == other
+    }
+}
+
+impl fmt::Debug for CFMutableString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFString).fmt(f)
+    }
+}
+
+impl fmt::Display for CFMutableString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &CFString).fmt(f)
+    }
+}
+
+impl CFMutableString {
+    /// Creates an empty mutable string object.
+    ///
+    /// `max_length` is the maximum number of UTF-16 code units the string can
+    /// contain, where 0 means it has no limit. It is only a hint and the
+    /// string starts out empty regardless.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1541642-cfstringcreatemutable).
+    #[inline]
+    #[doc(alias = "CFStringCreateMutable")]
+    pub fn new(max_length: CFIndex) -> Arc<Self> {
+        extern "C" {
+            fn CFStringCreateMutable(
+                alloc: Option<&super::CFAllocator>,
+                max_length: CFIndex,
+            ) -> *const CFMutableString;
+        }
+
+        unsafe {
+            let string = CFStringCreateMutable(None, max_length);
+            assert!(!string.is_null(), "Could not create CFMutableString");
+            Arc::from_raw(string)
+        }
+    }
+
+    /// Creates a mutable string object from copying a slice.
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Arc<Self> {
+        let mut string = Self::new(0);
+        Arc::get_mut(&mut string)
+            .expect("A new CFMutableString should not be shared")
+            .append_str(s);
+        string
+    }
+
+    /// Appends the contents of `s` to `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1542410-cfstringappendcharacters).
+    #[inline]
+    #[doc(alias = "CFStringAppendCharacters")]
+    pub fn append_str(&mut self, s: &str) {
+        extern "C" {
+            fn CFStringAppendCharacters(
+                the_string: &mut CFMutableString,
+                chars: *const u16,
+                num_chars: CFIndex,
+            );
+        }
+
+        // Transcoding to UTF-16 allows interior null bytes, unlike
+        // `CFStringAppendCString`.
+        let chars: Vec<u16> = s.encode_utf16().collect();
+        unsafe { CFStringAppendCharacters(self, chars.as_ptr(), chars.len() as CFIndex) }
+    }
+
+    /// Appends the contents of `s` to `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1541840-cfstringappend).
+    #[inline]
+    #[doc(alias = "CFStringAppend")]
+    pub fn append_cf(&mut self, s: &CFString) {
+        extern "C" {
+            fn CFStringAppend(the_string: &mut CFMutableString, appended_string: &CFString);
+        }
+        unsafe { CFStringAppend(self, s) }
+    }
+
+    /// Inserts `s` into `self` at the UTF-16 code unit index `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1542586-cfstringinsert).
+    #[inline]
+    #[doc(alias = "CFStringInsert")]
+    pub fn insert(&mut self, index: CFIndex, s: &CFString) {
+        extern "C" {
+            fn CFStringInsert(str: &mut CFMutableString, idx: CFIndex, inserted_str: &CFString);
+        }
+
+        let len = self.len();
+        assert!(
+            0 <= index && index <= len,
+            "Insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        unsafe { CFStringInsert(self, index, s) }
+    }
+
+    /// Replaces the UTF-16 code units of `self` in `range` with `replacement`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1542879-cfstringreplace).
+    #[inline]
+    #[doc(alias = "CFStringReplace")]
+    pub fn replace(&mut self, range: CFRange, replacement: &CFString) {
+        extern "C" {
+            fn CFStringReplace(
+                the_string: &mut CFMutableString,
+                range: CFRange,
+                replacement: &CFString,
+            );
+        }
+
+        let len = self.len();
+        assert!(
+            0 <= range.location && 0 <= range.length && range.end() <= len,
+            "Range {:?} is out of bounds for length {}",
+            range,
+            len
+        );
+
+        unsafe { CFStringReplace(self, range, replacement) }
+    }
+
+    /// Removes whitespace from the beginning and end of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1542810-cfstringtrimwhitespace).
+    #[inline]
+    #[doc(alias = "CFStringTrimWhitespace")]
+    pub fn trim_whitespace(&mut self) {
+        extern "C" {
+            fn CFStringTrimWhitespace(the_string: &mut CFMutableString);
+        }
+        unsafe { CFStringTrimWhitespace(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let cases = [
+            "",
+            "abc",
+            "a\0b",
+            "\0",
+            "café",
+            "日本語",
+            "😀 face",
+            "a\u{10FFFF}",
+        ];
+
+        for &case in &cases {
+            let string = CFString::from_str(case);
+            assert_eq!(string.len(), case.encode_utf16().count() as CFIndex);
+            assert_eq!(string.to_string(), case);
+            assert_eq!(*string, *case);
+
+            if let Some(s) = string.as_str() {
+                assert_eq!(s, case);
+            }
+        }
+    }
+
+    #[test]
+    fn interior_nul() {
+        let string = CFString::from_str("a\0b");
+        assert_eq!(string.len(), 3);
+        assert_eq!(string.as_str(), None);
+        assert_eq!(string.to_string(), "a\0b");
+        assert_ne!(*string, *"a");
+    }
+
+    #[test]
+    fn surrogate_pair() {
+        let string = CFString::from_str("😀");
+        assert_eq!(string.len(), 2);
+        assert_eq!(format!("{:?}", string), "\"😀\"");
+    }
+
+    #[test]
+    fn eq() {
+        let a = CFString::from_str("hello");
+        let b = CFString::from_str("hello");
+        assert_eq!(*a, *b);
+        assert!(*a == "hello");
+        assert!(*a != "world");
+    }
+
+    fn mutable(s: &str) -> Arc<CFMutableString> {
+        CFMutableString::from_str(s)
+    }
+
+    #[test]
+    fn append() {
+        let mut string = CFMutableString::new(0);
+        let s = Arc::get_mut(&mut string).unwrap();
+
+        s.append_str("héllo");
+        s.append_cf(&CFString::from_str(", "));
+        s.append_str("wörld 🌍");
+        s.append_str("\0!");
+
+        assert_eq!(string.to_string(), "héllo, wörld 🌍\0!");
+        assert_eq!(
+            string.len(),
+            "héllo, wörld 🌍\0!".encode_utf16().count() as CFIndex
+        );
+    }
+
+    #[test]
+    fn insert_replace() {
+        let mut string = mutable("ac");
+        let s = Arc::get_mut(&mut string).unwrap();
+
+        s.insert(1, &CFString::from_str("b"));
+        assert_eq!(*s, *"abc");
+
+        s.replace(CFRange::new(0, 2), &CFString::from_str("xyz"));
+        assert_eq!(*s, *"xyzc");
+    }
+
+    #[test]
+    fn replace_surrogate_pair() {
+        // The emoji is two UTF-16 code units at indices 1 and 2.
+        let mut string = mutable("a😀b");
+        let s = Arc::get_mut(&mut string).unwrap();
+        assert_eq!(s.len(), 4);
+
+        // Replacing the low surrogate leaves an unpaired high surrogate, which
+        // should not panic when converted.
+        s.replace(CFRange::new(2, 2), &CFString::from_str("c"));
+        assert_eq!(s.len(), 3);
+
+        let result = s.to_string();
+        assert!(result.starts_with('a'));
+        assert!(result.ends_with('c'));
+    }
+
+    #[test]
+    fn trim_whitespace() {
+        let mut string = mutable(" \t hi there \n");
+        Arc::get_mut(&mut string).unwrap().trim_whitespace();
+        assert_eq!(*string, *"hi there");
+    }
+
+    #[test]
+    fn get_mut_shared() {
+        let mut string = mutable("shared");
+        let clone = string.clone();
+        assert!(Arc::get_mut(&mut string).is_none());
+
+        drop(clone);
+        assert!(Arc::get_mut(&mut string).is_some());
+    }
+}
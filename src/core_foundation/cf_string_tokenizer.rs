@@ -0,0 +1,470 @@
+use super::{CFAllocator, CFIndex, CFLocale, CFOptionFlags, CFRange, CFString, CFType};
+use crate::core::Arc;
+use std::{fmt, ops};
+
+cf_subclass! {
+    /// Breaks a string into tokens such as words, sentences, or paragraphs.
+    ///
+    /// Tokens are visited with [`tokens`](#method.tokens) or by moving to
+    /// specific tokens with
+    /// [`advance_to_next_token`](#method.advance_to_next_token) and
+    /// [`go_to_token_at_index`](#method.go_to_token_at_index), which take
+    /// `&mut self` and can be called on a newly created tokenizer via
+    /// [`Arc::get_mut`].
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfstringtokenizer?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfstringtokenizerref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFStringTokenizer: CFType;
+    type_id = CFStringTokenizerGetTypeID;
+}
+
+impl fmt::Debug for CFStringTokenizer {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFStringTokenizer")
+            .field("current_token_range", &self.current_token_range())
+            .finish()
+    }
+}
+
+macro_rules! option_flags {
+    ($ty:ident) => {
+        impl $ty {
+            /// Returns `true` if all flags of `other` are set in `self`.
+            #[inline]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl ops::BitOr for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl ops::BitOrAssign for $ty {
+            #[inline]
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl ops::BitAnd for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+    };
+}
+
+/// The unit that a [`CFStringTokenizer`](struct.CFStringTokenizer.html)
+/// breaks a string into.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizercreate(_:_:_:_:_:)).
+#[repr(usize)] // CFOptionFlags
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFStringTokenizerUnit {
+    /// Words, skipping whitespace and punctuation.
+    #[doc(alias = "kCFStringTokenizerUnitWord")]
+    Word = 0,
+    /// Sentences.
+    #[doc(alias = "kCFStringTokenizerUnitSentence")]
+    Sentence = 1,
+    /// Paragraphs.
+    #[doc(alias = "kCFStringTokenizerUnitParagraph")]
+    Paragraph = 2,
+    /// The spans between line break opportunities.
+    #[doc(alias = "kCFStringTokenizerUnitLineBreak")]
+    LineBreak = 3,
+    /// Words, including whitespace and punctuation between them.
+    #[doc(alias = "kCFStringTokenizerUnitWordBoundary")]
+    WordBoundary = 4,
+}
+
+/// The attributes that a [`CFStringTokenizer`](struct.CFStringTokenizer.html)
+/// provides for its tokens, which can be combined using `|`.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizercreate(_:_:_:_:_:)).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CFStringTokenizerAttributes(CFOptionFlags);
+
+option_flags!(CFStringTokenizerAttributes);
+
+impl CFStringTokenizerAttributes {
+    /// No attributes.
+    pub const NONE: Self = Self(0);
+
+    /// Provides the Latin transcription of tokens.
+    #[doc(alias = "kCFStringTokenizerAttributeLatinTranscription")]
+    pub const LATIN_TRANSCRIPTION: Self = Self(CFStringTokenizerAttribute::LatinTranscription as _);
+
+    /// Provides the language of tokens.
+    #[doc(alias = "kCFStringTokenizerAttributeLanguage")]
+    pub const LANGUAGE: Self = Self(CFStringTokenizerAttribute::Language as _);
+}
+
+/// Describes a token found by a
+/// [`CFStringTokenizer`](struct.CFStringTokenizer.html).
+///
+/// [`NORMAL`](#associatedconstant.NORMAL) is combined with flags describing
+/// the contents of the token.
+///
+/// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizertokentype).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CFStringTokenizerTokenType(pub CFOptionFlags);
+
+option_flags!(CFStringTokenizerTokenType);
+
+impl CFStringTokenizerTokenType {
+    /// There is no token.
+    #[doc(alias = "kCFStringTokenizerTokenNone")]
+    pub const NONE: Self = Self(0);
+
+    /// There is a token.
+    #[doc(alias = "kCFStringTokenizerTokenNormal")]
+    pub const NORMAL: Self = Self(1);
+
+    /// The token contains sub-tokens.
+    #[doc(alias = "kCFStringTokenizerTokenHasSubTokensMask")]
+    pub const HAS_SUB_TOKENS: Self = Self(1 << 1);
+
+    /// The token contains sub-tokens derived from its characters.
+    #[doc(alias = "kCFStringTokenizerTokenHasDerivedSubTokensMask")]
+    pub const HAS_DERIVED_SUB_TOKENS: Self = Self(1 << 2);
+
+    /// The token contains numbers.
+    #[doc(alias = "kCFStringTokenizerTokenHasHasNumbersMask")]
+    pub const HAS_NUMBERS: Self = Self(1 << 3);
+
+    /// The token contains whitespace or punctuation.
+    #[doc(alias = "kCFStringTokenizerTokenHasNonLettersMask")]
+    pub const HAS_NON_LETTERS: Self = Self(1 << 4);
+
+    /// The token is a Chinese or Japanese word.
+    #[doc(alias = "kCFStringTokenizerTokenIsCJWordMask")]
+    pub const IS_CJ_WORD: Self = Self(1 << 5);
+
+    /// Returns `true` if this is not [`NONE`](#associatedconstant.NONE).
+    #[inline]
+    pub const fn is_token(self) -> bool {
+        self.0 != Self::NONE.0
+    }
+}
+
+/// An attribute of the current token of a
+/// [`CFStringTokenizer`](struct.CFStringTokenizer.html).
+///
+/// The tokenizer must be created with the corresponding
+/// [`CFStringTokenizerAttributes`](struct.CFStringTokenizerAttributes.html)
+/// flag for the attribute to be available.
+#[repr(usize)] // CFOptionFlags
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CFStringTokenizerAttribute {
+    /// The Latin transcription of the token.
+    #[doc(alias = "kCFStringTokenizerAttributeLatinTranscription")]
+    LatinTranscription = 1 << 16,
+    /// The language of the token, as a BCP 47 tag.
+    #[doc(alias = "kCFStringTokenizerAttributeLanguage")]
+    Language = 1 << 17,
+}
+
+impl CFStringTokenizer {
+    /// Creates a tokenizer over the UTF-16 code units of `string` in `range`,
+    /// which breaks it into `unit`s and provides `attributes` for each token.
+    ///
+    /// `locale` affects unit boundaries, such as line breaks, and defaults to
+    /// the root locale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizercreate(_:_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerCreate")]
+    pub fn new(
+        string: &CFString,
+        range: impl Into<CFRange>,
+        unit: CFStringTokenizerUnit,
+        attributes: CFStringTokenizerAttributes,
+        locale: Option<&CFLocale>,
+    ) -> Arc<Self> {
+        extern "C" {
+            fn CFStringTokenizerCreate(
+                alloc: Option<&CFAllocator>,
+                string: &CFString,
+                range: CFRange,
+                options: CFOptionFlags,
+                locale: Option<&CFLocale>,
+            ) -> Option<Arc<CFStringTokenizer>>;
+        }
+
+        let range = range.into();
+        range.assert_within(string.len());
+
+        let options = unit as CFOptionFlags | attributes.0;
+        unsafe { CFStringTokenizerCreate(None, string, range, options, locale) }
+            .expect("Could not create CFStringTokenizer")
+    }
+
+    /// Returns the best guess for the language of `string` in `range`, as a
+    /// BCP 47 tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizercopybeststringlanguage(_:_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerCopyBestStringLanguage")]
+    pub fn best_language(string: &CFString, range: impl Into<CFRange>) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFStringTokenizerCopyBestStringLanguage(
+                string: &CFString,
+                range: CFRange,
+            ) -> Option<Arc<CFString>>;
+        }
+
+        let range = range.into();
+        range.assert_within(string.len());
+
+        unsafe { CFStringTokenizerCopyBestStringLanguage(string, range) }
+    }
+
+    /// Sets the string to tokenize to the UTF-16 code units of `string` in
+    /// `range`, and resets the current token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizersetstring(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerSetString")]
+    pub fn set_string(&mut self, string: &CFString, range: impl Into<CFRange>) {
+        extern "C" {
+            fn CFStringTokenizerSetString(
+                tokenizer: &mut CFStringTokenizer,
+                string: &CFString,
+                range: CFRange,
+            );
+        }
+
+        let range = range.into();
+        range.assert_within(string.len());
+
+        unsafe { CFStringTokenizerSetString(self, string, range) }
+    }
+
+    /// Advances to the next token and returns its type, which is
+    /// [`NONE`](struct.CFStringTokenizerTokenType.html#associatedconstant.NONE)
+    /// once there are no more tokens.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizeradvancetonexttoken(_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerAdvanceToNextToken")]
+    pub fn advance_to_next_token(&mut self) -> CFStringTokenizerTokenType {
+        extern "C" {
+            fn CFStringTokenizerAdvanceToNextToken(
+                tokenizer: &mut CFStringTokenizer,
+            ) -> CFStringTokenizerTokenType;
+        }
+        unsafe { CFStringTokenizerAdvanceToNextToken(self) }
+    }
+
+    /// Moves to the token containing the UTF-16 code unit index `index` and
+    /// returns its type, which is
+    /// [`NONE`](struct.CFStringTokenizerTokenType.html#associatedconstant.NONE)
+    /// if there is no such token.
+    ///
+    /// This finds the word under a cursor position when tokenizing by
+    /// [`Word`](enum.CFStringTokenizerUnit.html#variant.Word).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizergototokenatindex(_:_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerGoToTokenAtIndex")]
+    pub fn go_to_token_at_index(&mut self, index: CFIndex) -> CFStringTokenizerTokenType {
+        extern "C" {
+            fn CFStringTokenizerGoToTokenAtIndex(
+                tokenizer: &mut CFStringTokenizer,
+                index: CFIndex,
+            ) -> CFStringTokenizerTokenType;
+        }
+        unsafe { CFStringTokenizerGoToTokenAtIndex(self, index) }
+    }
+
+    /// Returns the range of the current token, which has a location of
+    /// `kCFNotFound` if there is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizergetcurrenttokenrange(_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerGetCurrentTokenRange")]
+    pub fn current_token_range(&self) -> CFRange {
+        extern "C" {
+            fn CFStringTokenizerGetCurrentTokenRange(tokenizer: &CFStringTokenizer) -> CFRange;
+        }
+        unsafe { CFStringTokenizerGetCurrentTokenRange(self) }
+    }
+
+    /// Returns `attribute` of the current token, or `None` if there is no
+    /// current token or it does not have the attribute.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfstringtokenizercopycurrenttokenattribute(_:_:)).
+    #[inline]
+    #[doc(alias = "CFStringTokenizerCopyCurrentTokenAttribute")]
+    pub fn copy_current_token_attribute(
+        &self,
+        attribute: CFStringTokenizerAttribute,
+    ) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFStringTokenizerCopyCurrentTokenAttribute(
+                tokenizer: &CFStringTokenizer,
+                attribute: CFOptionFlags,
+            ) -> Option<Arc<CFString>>;
+        }
+
+        // Both attributes are strings.
+        unsafe { CFStringTokenizerCopyCurrentTokenAttribute(self, attribute as CFOptionFlags) }
+    }
+
+    /// Returns an iterator over the ranges and types of the tokens after the
+    /// current token.
+    #[inline]
+    pub fn tokens(&mut self) -> CFStringTokenizerTokens<'_> {
+        CFStringTokenizerTokens { tokenizer: self }
+    }
+}
+
+/// An iterator over the tokens of a
+/// [`CFStringTokenizer`](struct.CFStringTokenizer.html).
+///
+/// This is created by
+/// [`CFStringTokenizer::tokens`](struct.CFStringTokenizer.html#method.tokens).
+#[derive(Debug)]
+pub struct CFStringTokenizerTokens<'a> {
+    tokenizer: &'a mut CFStringTokenizer,
+}
+
+impl Iterator for CFStringTokenizerTokens<'_> {
+    type Item = (CFRange, CFStringTokenizerTokenType);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let token_type = self.tokenizer.advance_to_next_token();
+        if token_type.is_token() {
+            Some((self.tokenizer.current_token_range(), token_type))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Hello, world! 你好世界。";
+
+    fn tokens(unit: CFStringTokenizerUnit) -> Vec<(CFRange, CFStringTokenizerTokenType)> {
+        let string = CFString::from_str(TEXT);
+        let attributes = CFStringTokenizerAttributes::NONE;
+        let mut tokenizer =
+            CFStringTokenizer::new(&string, 0..string.len(), unit, attributes, None);
+        let tokens = Arc::get_mut(&mut tokenizer).unwrap().tokens().collect();
+        tokens
+    }
+
+    #[test]
+    fn words() {
+        let words = tokens(CFStringTokenizerUnit::Word);
+
+        // Punctuation and whitespace are skipped.
+        let ranges: Vec<CFRange> = words.iter().map(|&(range, _)| range).collect();
+        assert_eq!(ranges[..2], [CFRange::new(0, 5), CFRange::new(7, 5)]);
+        assert!(words[..2]
+            .iter()
+            .all(|&(_, t)| !t.contains(CFStringTokenizerTokenType::IS_CJ_WORD)));
+
+        // The Chinese words are split by dictionary and cover the ideographs.
+        let cjk = &words[2..];
+        assert!(!cjk.is_empty());
+        assert_eq!(cjk[0].0.location, 14);
+        assert_eq!(cjk.last().unwrap().0.end(), 18);
+        for pair in cjk.windows(2) {
+            assert_eq!(pair[0].0.end(), pair[1].0.location);
+        }
+        assert!(cjk
+            .iter()
+            .all(|&(_, t)| t.contains(CFStringTokenizerTokenType::IS_CJ_WORD)));
+    }
+
+    #[test]
+    fn sentences() {
+        let sentences = tokens(CFStringTokenizerUnit::Sentence);
+        let ranges: Vec<CFRange> = sentences.iter().map(|&(range, _)| range).collect();
+        assert_eq!(ranges, [CFRange::new(0, 14), CFRange::new(14, 5)]);
+    }
+
+    #[test]
+    fn paragraphs() {
+        let paragraphs = tokens(CFStringTokenizerUnit::Paragraph);
+        let ranges: Vec<CFRange> = paragraphs.iter().map(|&(range, _)| range).collect();
+        assert_eq!(ranges, [CFRange::new(0, 19)]);
+    }
+
+    #[test]
+    fn word_at_index() {
+        let string = CFString::from_str(TEXT);
+        let mut tokenizer = CFStringTokenizer::new(
+            &string,
+            0..string.len(),
+            CFStringTokenizerUnit::Word,
+            CFStringTokenizerAttributes::NONE,
+            None,
+        );
+        let tokenizer = Arc::get_mut(&mut tokenizer).unwrap();
+
+        assert!(tokenizer.go_to_token_at_index(9).is_token());
+        assert_eq!(tokenizer.current_token_range(), CFRange::new(7, 5));
+
+        // Continuing iteration picks up after the current token.
+        let next = tokenizer.tokens().next().unwrap();
+        assert_eq!(next.0.location, 14);
+
+        // There is no word at the comma.
+        assert!(!tokenizer.go_to_token_at_index(5).is_token());
+    }
+
+    #[test]
+    fn language() {
+        let string = CFString::from_str("Bonjour tout le monde, comment allez-vous ?");
+        let language = CFStringTokenizer::best_language(&string, 0..string.len()).unwrap();
+        assert_eq!(*language, *"fr");
+
+        let attributes = CFStringTokenizerAttributes::LANGUAGE
+            | CFStringTokenizerAttributes::LATIN_TRANSCRIPTION;
+        assert!(attributes.contains(CFStringTokenizerAttributes::LANGUAGE));
+        assert!(!CFStringTokenizerAttributes::LANGUAGE
+            .contains(CFStringTokenizerAttributes::LATIN_TRANSCRIPTION));
+
+        let unit = CFStringTokenizerUnit::Word;
+        let mut tokenizer =
+            CFStringTokenizer::new(&string, 0..string.len(), unit, attributes, None);
+        let tokenizer = Arc::get_mut(&mut tokenizer).unwrap();
+        assert!(tokenizer.advance_to_next_token().is_token());
+        assert!(tokenizer
+            .copy_current_token_attribute(CFStringTokenizerAttribute::Language)
+            .is_some());
+    }
+}
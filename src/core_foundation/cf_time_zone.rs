@@ -0,0 +1,157 @@
+use super::{Boolean, CFAbsoluteTime, CFAllocator, CFString, CFTimeInterval, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// Information about a geopolitical time zone.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cftimezone?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cftimezoneref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFTimeZone: CFType;
+    type_id = CFTimeZoneGetTypeID;
+}
+
+impl fmt::Debug for CFTimeZone {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CFTimeZone").field(&self.name()).finish()
+    }
+}
+
+impl fmt::Display for CFTimeZone {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// Getting time zones.
+impl CFTimeZone {
+    /// Returns a copy of the time zone of the system.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonecopysystem()).
+    #[inline]
+    #[doc(alias = "CFTimeZoneCopySystem")]
+    pub fn system() -> Arc<CFTimeZone> {
+        extern "C" {
+            fn CFTimeZoneCopySystem() -> Option<Arc<CFTimeZone>>;
+        }
+        unsafe { CFTimeZoneCopySystem() }.expect("Could not get system CFTimeZone")
+    }
+
+    /// Returns a copy of the default time zone for the current process, which
+    /// is the system time zone unless it has been changed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonecopydefault()).
+    #[inline]
+    #[doc(alias = "CFTimeZoneCopyDefault")]
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Arc<CFTimeZone> {
+        extern "C" {
+            fn CFTimeZoneCopyDefault() -> Option<Arc<CFTimeZone>>;
+        }
+        unsafe { CFTimeZoneCopyDefault() }.expect("Could not get default CFTimeZone")
+    }
+
+    /// Returns the time zone for `name`, which may be a tz database name
+    /// (such as `America/New_York`) or an abbreviation (such as `EST`).
+    ///
+    /// Returns `None` if `name` is not a known time zone.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonecreatewithname(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFTimeZoneCreateWithName")]
+    pub fn with_name(name: &CFString) -> Option<Arc<CFTimeZone>> {
+        extern "C" {
+            fn CFTimeZoneCreateWithName(
+                allocator: Option<&CFAllocator>,
+                name: &CFString,
+                try_abbrev: Boolean,
+            ) -> Option<Arc<CFTimeZone>>;
+        }
+        unsafe { CFTimeZoneCreateWithName(None, name, true as Boolean) }
+    }
+}
+
+/// Getting time zone properties.
+impl CFTimeZone {
+    /// Returns the tz database name of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonegetname(_:)).
+    #[inline]
+    #[doc(alias = "CFTimeZoneGetName")]
+    pub fn name(&self) -> &CFString {
+        extern "C" {
+            fn CFTimeZoneGetName(tz: &CFTimeZone) -> &CFString;
+        }
+        unsafe { CFTimeZoneGetName(self) }
+    }
+
+    /// Returns the difference in seconds between `self` and GMT at `at`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonegetsecondsfromgmt(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTimeZoneGetSecondsFromGMT")]
+    pub fn seconds_from_gmt(&self, at: CFAbsoluteTime) -> CFTimeInterval {
+        extern "C" {
+            fn CFTimeZoneGetSecondsFromGMT(tz: &CFTimeZone, at: CFAbsoluteTime) -> CFTimeInterval;
+        }
+        unsafe { CFTimeZoneGetSecondsFromGMT(self, at) }
+    }
+
+    /// Returns the abbreviation of `self` at `at`, such as `EST` or `EDT`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftimezonecopyabbreviation(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTimeZoneCopyAbbreviation")]
+    pub fn abbreviation(&self, at: CFAbsoluteTime) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFTimeZoneCopyAbbreviation(
+                tz: &CFTimeZone,
+                at: CFAbsoluteTime,
+            ) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFTimeZoneCopyAbbreviation(self, at) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_foundation::CFAbsoluteTimeGetCurrent;
+
+    #[test]
+    fn utc() {
+        let utc = CFTimeZone::with_name(&CFString::from_str("UTC")).unwrap();
+        let now = CFAbsoluteTimeGetCurrent();
+
+        assert_eq!(utc.seconds_from_gmt(now), 0.0);
+        assert_eq!(utc.seconds_from_gmt(0.0), 0.0);
+    }
+
+    #[test]
+    fn daylight_saving() {
+        let new_york = CFTimeZone::with_name(&CFString::from_str("America/New_York")).unwrap();
+        assert_eq!(*new_york.name(), *"America/New_York");
+
+        // 2001-01-15 and 2001-07-15, relative to 2001-01-01.
+        let (winter, summer) = (14.0 * 86400.0, 195.0 * 86400.0);
+        assert_eq!(new_york.seconds_from_gmt(winter), -5.0 * 3600.0);
+        assert_eq!(new_york.seconds_from_gmt(summer), -4.0 * 3600.0);
+        assert_eq!(*new_york.abbreviation(winter).unwrap(), *"EST");
+        assert_eq!(*new_york.abbreviation(summer).unwrap(), *"EDT");
+    }
+
+    #[test]
+    fn unknown() {
+        assert!(CFTimeZone::with_name(&CFString::from_str("Nowhere/Fruity")).is_none());
+    }
+
+    #[test]
+    fn system_and_default() {
+        assert!(!CFTimeZone::system().name().is_empty());
+        assert!(!CFTimeZone::default().name().is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+use super::{sys, CFAllocator, CFIndex, CFString, CFType};
+use crate::core::Arc;
+use std::{ffi::c_void, fmt, ptr};
+
+cf_subclass! {
+    /// A node in a tree, which retains its children and an optional
+    /// [`CFType`](struct.CFType.html) payload.
+    ///
+    /// Nodes are shared by their parents, so methods that restructure the tree
+    /// take `&self`. Core Foundation does not synchronize trees, so a tree
+    /// must not be restructured from multiple threads at once.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cftree?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cftreeref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFTree: CFType;
+    type_id = CFTreeGetTypeID;
+}
+
+impl fmt::Debug for CFTree {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CFTree")
+            .field("info", &self.info())
+            .field("children", &self.children())
+            .finish()
+    }
+}
+
+#[repr(C)]
+struct CFTreeContext {
+    version: CFIndex,
+    info: *mut c_void,
+    retain: Option<unsafe extern "C" fn(info: *const c_void) -> *const c_void>,
+    release: Option<unsafe extern "C" fn(info: *const c_void)>,
+    copy_description: Option<unsafe extern "C" fn(info: *const c_void) -> *const CFString>,
+}
+
+impl CFTreeContext {
+    /// Returns a context whose info is `info`, retained by the tree.
+    #[inline]
+    fn new(info: Option<&CFType>) -> Self {
+        match info {
+            Some(info) => Self {
+                version: 0,
+                info: info as *const CFType as *mut c_void,
+                retain: Some(payload::retain),
+                release: Some(payload::release),
+                copy_description: Some(payload::copy_description),
+            },
+            None => Self {
+                version: 0,
+                info: ptr::null_mut(),
+                retain: None,
+                release: None,
+                copy_description: None,
+            },
+        }
+    }
+}
+
+/// Callbacks for a tree whose context info is a `CFType`.
+mod payload {
+    use super::*;
+
+    pub unsafe extern "C" fn retain(info: *const c_void) -> *const c_void {
+        sys::CFRetain(info.cast()).cast()
+    }
+
+    pub unsafe extern "C" fn release(info: *const c_void) {
+        sys::CFRelease(info.cast())
+    }
+
+    pub unsafe extern "C" fn copy_description(info: *const c_void) -> *const CFString {
+        sys::CFCopyDescription(info.cast())
+    }
+}
+
+/// Creating trees.
+impl CFTree {
+    /// Creates a node with no parent or children that retains `info`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreecreate(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeCreate")]
+    pub fn new(info: Option<&CFType>) -> Arc<Self> {
+        extern "C" {
+            fn CFTreeCreate(
+                allocator: Option<&CFAllocator>,
+                context: *const CFTreeContext,
+            ) -> Option<Arc<CFTree>>;
+        }
+
+        let context = CFTreeContext::new(info);
+        unsafe { CFTreeCreate(None, &context) }.expect("Could not create CFTree")
+    }
+}
+
+/// Getting and setting the payload.
+impl CFTree {
+    /// Returns the payload of `self`.
+    ///
+    /// This is `None` if the tree was created outside of this crate with info
+    /// that is not known to be a `CFType`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetcontext(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetContext")]
+    pub fn info(&self) -> Option<Arc<CFType>> {
+        extern "C" {
+            fn CFTreeGetContext(tree: &CFTree, context: *mut CFTreeContext);
+        }
+
+        let mut context = CFTreeContext::new(None);
+        unsafe {
+            CFTreeGetContext(self, &mut context);
+
+            // Only `CFType` payloads are set by safe code. Trees created
+            // elsewhere may have other info, which is not exposed.
+            let expected: unsafe extern "C" fn(*const c_void) -> *const c_void = payload::retain;
+            if context.retain.map(|f| f as usize) != Some(expected as usize) {
+                return None;
+            }
+            context.info.cast::<CFType>().as_ref().map(Arc::retain)
+        }
+    }
+
+    /// Replaces the payload of `self` with `info`, releasing the old payload.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreesetcontext(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeSetContext")]
+    pub fn set_info(&self, info: Option<&CFType>) {
+        extern "C" {
+            fn CFTreeSetContext(tree: &CFTree, context: *const CFTreeContext);
+        }
+
+        let context = CFTreeContext::new(info);
+        unsafe { CFTreeSetContext(self, &context) }
+    }
+}
+
+/// Navigating the tree.
+impl CFTree {
+    /// Returns the parent of `self`, or `None` if it is a root.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetparent(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetParent")]
+    pub fn parent(&self) -> Option<Arc<CFTree>> {
+        extern "C" {
+            fn CFTreeGetParent(tree: &CFTree) -> Option<&CFTree>;
+        }
+        unsafe { CFTreeGetParent(self) }.map(Arc::retain)
+    }
+
+    /// Returns the first child of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetfirstchild(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetFirstChild")]
+    pub fn first_child(&self) -> Option<Arc<CFTree>> {
+        extern "C" {
+            fn CFTreeGetFirstChild(tree: &CFTree) -> Option<&CFTree>;
+        }
+        unsafe { CFTreeGetFirstChild(self) }.map(Arc::retain)
+    }
+
+    /// Returns the sibling after `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetnextsibling(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetNextSibling")]
+    pub fn next_sibling(&self) -> Option<Arc<CFTree>> {
+        extern "C" {
+            fn CFTreeGetNextSibling(tree: &CFTree) -> Option<&CFTree>;
+        }
+        unsafe { CFTreeGetNextSibling(self) }.map(Arc::retain)
+    }
+
+    /// Returns the root of the tree containing `self`, which may be `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreefindroot(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeFindRoot")]
+    pub fn root(&self) -> Arc<CFTree> {
+        extern "C" {
+            fn CFTreeFindRoot(tree: &CFTree) -> &CFTree;
+        }
+        Arc::retain(unsafe { CFTreeFindRoot(self) })
+    }
+
+    /// Returns the number of children of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetchildcount(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetChildCount")]
+    pub fn child_count(&self) -> CFIndex {
+        extern "C" {
+            fn CFTreeGetChildCount(tree: &CFTree) -> CFIndex;
+        }
+        unsafe { CFTreeGetChildCount(self) }
+    }
+
+    /// Returns the child of `self` at `index`, or `None` if it is out of
+    /// bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetchildatindex(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeGetChildAtIndex")]
+    pub fn child_at(&self, index: CFIndex) -> Option<Arc<CFTree>> {
+        extern "C" {
+            fn CFTreeGetChildAtIndex(tree: &CFTree, idx: CFIndex) -> Option<&CFTree>;
+        }
+
+        if 0 <= index && index < self.child_count() {
+            unsafe { CFTreeGetChildAtIndex(self, index) }.map(Arc::retain)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the children of `self` in order.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreegetchildren(_:_:)).
+    #[doc(alias = "CFTreeGetChildren")]
+    pub fn children(&self) -> Vec<Arc<CFTree>> {
+        extern "C" {
+            fn CFTreeGetChildren(tree: &CFTree, children: *mut *const CFTree);
+        }
+
+        let len = self.child_count() as usize;
+        let mut children = vec![ptr::null(); len];
+
+        if len != 0 {
+            unsafe { CFTreeGetChildren(self, children.as_mut_ptr()) };
+        }
+
+        children
+            .into_iter()
+            .map(|child| Arc::retain(unsafe { &*child }))
+            .collect()
+    }
+
+    /// Calls `f` with `self` and then each of its descendants, depth-first.
+    ///
+    /// Children are retained before `f` is called on them, so `f` may
+    /// restructure the tree. Nodes added by `f` below a node that has not yet
+    /// been visited are visited.
+    pub fn apply<F: FnMut(&CFTree)>(&self, mut f: F) {
+        fn visit(tree: &CFTree, f: &mut dyn FnMut(&CFTree)) {
+            f(tree);
+            for child in tree.children() {
+                visit(&child, f);
+            }
+        }
+        visit(self, &mut f);
+    }
+}
+
+/// Restructuring the tree.
+impl CFTree {
+    #[inline]
+    fn assert_can_adopt(&self, node: &CFTree) {
+        assert!(
+            node.parent().is_none(),
+            "Node {:?} already has a parent",
+            node
+        );
+        assert!(
+            !ptr::eq(&*self.root(), node),
+            "Node {:?} cannot be added to its own descendant",
+            node
+        );
+    }
+
+    /// Adds `child` as the last child of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` already has a parent or is the root of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreeappendchild(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeAppendChild")]
+    pub fn append_child(&self, child: &CFTree) {
+        extern "C" {
+            fn CFTreeAppendChild(tree: &CFTree, new_child: &CFTree);
+        }
+
+        self.assert_can_adopt(child);
+        unsafe { CFTreeAppendChild(self, child) }
+    }
+
+    /// Adds `child` as the first child of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` already has a parent or is the root of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreeprependchild(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreePrependChild")]
+    pub fn prepend_child(&self, child: &CFTree) {
+        extern "C" {
+            fn CFTreePrependChild(tree: &CFTree, new_child: &CFTree);
+        }
+
+        self.assert_can_adopt(child);
+        unsafe { CFTreePrependChild(self, child) }
+    }
+
+    /// Adds `sibling` to the parent of `self`, directly after `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` has no parent, or if `sibling` already has a parent
+    /// or is the root of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreeinsertsibling(_:_:)).
+    #[inline]
+    #[doc(alias = "CFTreeInsertSibling")]
+    pub fn insert_sibling(&self, sibling: &CFTree) {
+        extern "C" {
+            fn CFTreeInsertSibling(tree: &CFTree, new_sibling: &CFTree);
+        }
+
+        assert!(
+            self.parent().is_some(),
+            "Root node {:?} cannot have siblings",
+            self
+        );
+        self.assert_can_adopt(sibling);
+        unsafe { CFTreeInsertSibling(self, sibling) }
+    }
+
+    /// Detaches `self` and its descendants from its parent.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreeremove(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeRemove")]
+    pub fn remove(&self) {
+        extern "C" {
+            fn CFTreeRemove(tree: &CFTree);
+        }
+        unsafe { CFTreeRemove(self) }
+    }
+
+    /// Detaches all children of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cftreeremoveallchildren(_:)).
+    #[inline]
+    #[doc(alias = "CFTreeRemoveAllChildren")]
+    pub fn remove_all_children(&self) {
+        extern "C" {
+            fn CFTreeRemoveAllChildren(tree: &CFTree);
+        }
+        unsafe { CFTreeRemoveAllChildren(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str) -> Arc<CFTree> {
+        CFTree::new(Some(&CFString::from_str(name)))
+    }
+
+    fn name(tree: &CFTree) -> String {
+        let info = tree.info().unwrap();
+        info.downcast_ref::<CFString>().unwrap().to_string()
+    }
+
+    fn names(tree: &CFTree) -> Vec<String> {
+        let mut names = Vec::new();
+        tree.apply(|node| names.push(name(node)));
+        names
+    }
+
+    #[test]
+    fn three_levels() {
+        let root = node("root");
+        let (a, b) = (node("a"), node("b"));
+        let (a1, a2) = (node("a1"), node("a2"));
+
+        root.append_child(&b);
+        root.prepend_child(&a);
+        a.append_child(&a1);
+        a.append_child(&a2);
+
+        assert_eq!(names(&root), ["root", "a", "a1", "a2", "b"]);
+        assert_eq!(root.child_count(), 2);
+        assert!(ptr::eq(&*root.first_child().unwrap(), &*a));
+        assert!(ptr::eq(&*a.next_sibling().unwrap(), &*b));
+        assert!(b.next_sibling().is_none());
+        assert!(ptr::eq(&*a2.parent().unwrap(), &*a));
+        assert!(ptr::eq(&*a2.root(), &*root));
+        assert!(ptr::eq(&*root.child_at(1).unwrap(), &*b));
+        assert!(root.child_at(2).is_none());
+
+        a1.insert_sibling(&node("x"));
+        assert_eq!(names(&a), ["a", "a1", "x", "a2"]);
+
+        // Detaching a subtree keeps its own structure.
+        a.remove();
+        assert!(a.parent().is_none());
+        assert!(ptr::eq(&*a2.root(), &*a));
+        assert_eq!(names(&root), ["root", "b"]);
+        assert_eq!(names(&a), ["a", "a1", "x", "a2"]);
+
+        a.remove_all_children();
+        assert!(a1.parent().is_none());
+        assert_eq!(a.child_count(), 0);
+    }
+
+    #[test]
+    fn info() {
+        let tree = CFTree::new(None);
+        assert!(tree.info().is_none());
+
+        let payload = CFString::from_str("a payload that is not a tagged pointer");
+        tree.set_info(Some(&payload));
+        assert_eq!(*tree.info().unwrap(), **payload);
+
+        // The tree retains the payload.
+        let retained = payload.retain_count();
+        drop(tree);
+        assert_eq!(payload.retain_count(), retained - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be added to its own descendant")]
+    fn cycle() {
+        let (root, child) = (node("root"), node("child"));
+        root.append_child(&child);
+        child.append_child(&root);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{sys, CFHashCode, CFIndex};
+use super::{sys, CFAllocator, CFHashCode, CFIndex, CFString, CFTypeObject};
 use crate::core::{Arc, ObjectType};
 use std::{cell::UnsafeCell, fmt, hash, ptr::NonNull};
 
@@ -37,6 +37,11 @@ impl ObjectType for CFType {
     unsafe fn release(obj: NonNull<Self>) {
         sys::CFRelease(obj.as_ptr());
     }
+
+    #[inline]
+    fn is_unique(obj: &Self) -> bool {
+        obj.retain_count() == 1
+    }
 }
 
 // This type is used globally, so we must be able to share it across threads.
@@ -68,7 +73,7 @@ impl hash::Hash for CFType {
 impl fmt::Debug for CFType {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        (self as *const Self).fmt(f)
+        self.copy_description().with_utf8(|s| f.write_str(s))
     }
 }
 
@@ -99,9 +104,47 @@ impl CFType {
         unsafe { sys::CFGetTypeID(self) }
     }
 
-    // TODO: `CFGetAllocator`
+    /// Returns `true` if `self` is an instance of `T`.
+    #[inline]
+    pub fn is<T: CFTypeObject>(&self) -> bool {
+        self.get_type_id() == T::type_id()
+    }
+
+    /// Returns `self` as an instance of `T` if its type matches.
+    #[inline]
+    pub fn downcast_ref<T: CFTypeObject>(&self) -> Option<&T> {
+        if self.is::<T>() {
+            // SAFETY: `CFTypeObject` guarantees that all instances with the
+            // same type ID are valid instances of `T`.
+            Some(unsafe { &*(self as *const Self as *const T) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the allocator used to allocate `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521134-cfgetallocator).
+    #[inline]
+    #[doc(alias = "CFGetAllocator")]
+    pub fn allocator(&self) -> Option<&CFAllocator> {
+        unsafe { sys::CFGetAllocator(self).as_ref() }
+    }
 
-    // TODO: `CFCopyDescription`
+    /// Returns a textual description of `self`.
+    ///
+    /// This is useful for debugging and is what the
+    /// [`Debug`](https://doc.rust-lang.org/std/fmt/trait.Debug.html)
+    /// implementation of this type writes. The format of the result is not
+    /// guaranteed to be stable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/1521252-cfcopydescription).
+    #[inline]
+    #[doc(alias = "CFCopyDescription")]
+    pub fn copy_description(&self) -> Arc<CFString> {
+        // The result follows the create rule, so it is already retained.
+        unsafe { Arc::from_raw(sys::CFCopyDescription(self)) }
+    }
 }
 
 /// An automatically-reference-counted pointer to a type-erased Core Foundation
@@ -111,3 +154,80 @@ impl CFType {
 /// [Swift](https://developer.apple.com/documentation/corefoundation/cftyperef?language=swift) |
 /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cftyperef?language=objc)
 pub type CFTypeRef = Arc<CFType>;
+
+impl Arc<CFType> {
+    /// Attempts to convert `self` into an instance of `T`, returning `self` if
+    /// its type does not match.
+    ///
+    /// This does not change the reference count of the object.
+    #[inline]
+    pub fn downcast<T: CFTypeObject>(self) -> Result<Arc<T>, Self> {
+        if self.is::<T>() {
+            // SAFETY: `CFTypeObject` guarantees that all instances with the
+            // same type ID are valid instances of `T`.
+            Ok(unsafe { Arc::cast_unchecked(self) })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_description() {
+        let description = null().copy_description();
+        assert_ne!(description.len(), 0);
+        assert!(!description.to_string().is_empty());
+        assert!(!format!("{:?}", null()).is_empty());
+    }
+
+    #[test]
+    fn allocator() {
+        let description = null().copy_description();
+        assert!(description.allocator().is_some());
+    }
+
+    fn null() -> &'static CFType {
+        extern "C" {
+            static kCFNull: &'static CFType;
+        }
+        unsafe { kCFNull }
+    }
+
+    cf_subclass! {
+        /// `CFNull`, which is not yet otherwise exposed.
+        #[derive(Debug)]
+        class TestNull: CFType;
+        type_id = CFNullGetTypeID;
+    }
+
+    #[test]
+    fn downcast() {
+        let string: Arc<CFType> = CFType::retain(&null().copy_description());
+        assert_eq!(string.retain_count(), 2);
+
+        let string = string.downcast::<CFString>().unwrap();
+        assert_eq!(string.retain_count(), 2);
+        assert!(!string.is_empty());
+
+        assert!(null().downcast_ref::<TestNull>().is_some());
+        assert!(null().downcast_ref::<CFString>().is_none());
+    }
+
+    #[test]
+    fn downcast_mismatch() {
+        let description = null().copy_description();
+        let string: Arc<CFType> = CFType::retain(&description);
+        let ptr = &*string as *const CFType;
+
+        let string = string.downcast::<TestNull>().unwrap_err();
+        assert_eq!(&*string as *const CFType, ptr);
+        assert_eq!(string.retain_count(), 2);
+
+        drop(string);
+        assert_eq!(description.retain_count(), 1);
+    }
+}
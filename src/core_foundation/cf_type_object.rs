@@ -0,0 +1,17 @@
+use super::{CFType, CFTypeID};
+use crate::core::ObjectType;
+
+/// A type that represents instances of a specific Core Foundation opaque type.
+///
+/// This is used to safely [`downcast`](crate::core::Arc::downcast) a
+/// [`CFType`] to a concrete type.
+///
+/// # Safety
+///
+/// All values whose [`get_type_id`](CFType::get_type_id) is equal to
+/// [`type_id`](Self::type_id) must be valid instances of `Self`. `Self` must
+/// also have the same memory representation as [`CFType`].
+pub unsafe trait CFTypeObject: ObjectType + AsRef<CFType> {
+    /// Returns the unique identifier of the opaque type represented by `Self`.
+    fn type_id() -> CFTypeID;
+}
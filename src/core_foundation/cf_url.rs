@@ -0,0 +1,329 @@
+use super::{Boolean, CFAllocator, CFIndex, CFString, CFType};
+use crate::core::Arc;
+use std::{
+    ffi::OsStr,
+    fmt,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+};
+
+cf_subclass! {
+    /// A reference to a local file or network resource.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfurl?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfurlref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFURL: CFType;
+    type_id = CFURLGetTypeID;
+}
+
+/// `kCFURLPOSIXPathStyle` from `CFURLPathStyle`.
+const POSIX_PATH_STYLE: CFIndex = 0;
+
+/// The largest buffer `CFURL::to_path_buf` tries before giving up.
+const MAX_PATH_BUFFER_LEN: usize = 1 << 16;
+
+impl fmt::Debug for CFURL {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.string().fmt(f)
+    }
+}
+
+impl fmt::Display for CFURL {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.string().fmt(f)
+    }
+}
+
+/// Creating URLs.
+impl CFURL {
+    /// Creates a file URL from a local file system path.
+    ///
+    /// The path is passed to Core Foundation as raw bytes, so paths that are
+    /// not valid UTF-8 are preserved. Relative paths are resolved against the
+    /// current working directory.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcreatefromfilesystemrepresentation(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFURLCreateFromFileSystemRepresentation")]
+    #[doc(alias = "CFURLCreateWithFileSystemPath")]
+    pub fn from_file_path<P: AsRef<Path>>(path: P, is_directory: bool) -> Arc<Self> {
+        extern "C" {
+            fn CFURLCreateFromFileSystemRepresentation(
+                allocator: Option<&CFAllocator>,
+                buffer: *const u8,
+                buf_len: CFIndex,
+                is_directory: Boolean,
+            ) -> Option<Arc<CFURL>>;
+        }
+
+        let bytes = path.as_ref().as_os_str().as_bytes();
+        let url = unsafe {
+            CFURLCreateFromFileSystemRepresentation(
+                None,
+                bytes.as_ptr(),
+                bytes.len() as CFIndex,
+                is_directory as Boolean,
+            )
+        };
+        url.expect("Could not create CFURL")
+    }
+
+    /// Creates a URL by parsing `string`, optionally relative to `base`.
+    ///
+    /// Returns `None` if `string` is not a valid URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcreatewithstring(_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFURLCreateWithString")]
+    pub fn from_string(string: &CFString, base: Option<&CFURL>) -> Option<Arc<Self>> {
+        extern "C" {
+            fn CFURLCreateWithString(
+                allocator: Option<&CFAllocator>,
+                url_string: &CFString,
+                base_url: Option<&CFURL>,
+            ) -> Option<Arc<CFURL>>;
+        }
+        unsafe { CFURLCreateWithString(None, string, base) }
+    }
+
+    /// Returns the absolute form of `self`, resolved against its base URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopyabsoluteurl(_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyAbsoluteURL")]
+    pub fn absolute_url(&self) -> Option<Arc<CFURL>> {
+        extern "C" {
+            fn CFURLCopyAbsoluteURL(relative_url: &CFURL) -> Option<Arc<CFURL>>;
+        }
+        unsafe { CFURLCopyAbsoluteURL(self) }
+    }
+
+    /// Returns a copy of `self` with `component` appended to its path.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcreatecopyappendingpathcomponent(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "CFURLCreateCopyAppendingPathComponent")]
+    pub fn url_by_appending_path_component(
+        &self,
+        component: &CFString,
+        is_directory: bool,
+    ) -> Option<Arc<CFURL>> {
+        extern "C" {
+            fn CFURLCreateCopyAppendingPathComponent(
+                allocator: Option<&CFAllocator>,
+                url: &CFURL,
+                path_component: &CFString,
+                is_directory: Boolean,
+            ) -> Option<Arc<CFURL>>;
+        }
+        unsafe {
+            CFURLCreateCopyAppendingPathComponent(None, self, component, is_directory as Boolean)
+        }
+    }
+}
+
+/// Accessing components.
+impl CFURL {
+    /// Returns the string used to create `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlgetstring(_:)).
+    #[inline]
+    #[doc(alias = "CFURLGetString")]
+    pub fn string(&self) -> &CFString {
+        extern "C" {
+            fn CFURLGetString(an_url: &CFURL) -> &CFString;
+        }
+        unsafe { CFURLGetString(self) }
+    }
+
+    /// Returns the URL that `self` is relative to, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlgetbaseurl(_:)).
+    #[inline]
+    #[doc(alias = "CFURLGetBaseURL")]
+    pub fn base_url(&self) -> Option<&CFURL> {
+        extern "C" {
+            fn CFURLGetBaseURL(an_url: &CFURL) -> Option<&CFURL>;
+        }
+        unsafe { CFURLGetBaseURL(self) }
+    }
+
+    /// Returns the scheme of `self`, such as `https` or `file`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopyscheme(_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyScheme")]
+    pub fn scheme(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFURLCopyScheme(an_url: &CFURL) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFURLCopyScheme(self) }
+    }
+
+    /// Returns the host name of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopyhostname(_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyHostName")]
+    pub fn host(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFURLCopyHostName(an_url: &CFURL) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFURLCopyHostName(self) }
+    }
+
+    /// Returns the path of `self` without resolving against its base URL.
+    ///
+    /// Percent escapes are not replaced.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopypath(_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyPath")]
+    pub fn path(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFURLCopyPath(an_url: &CFURL) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFURLCopyPath(self) }
+    }
+
+    /// Returns the last component of the path of `self`, with percent escapes
+    /// replaced.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopylastpathcomponent(_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyLastPathComponent")]
+    pub fn last_path_component(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFURLCopyLastPathComponent(url: &CFURL) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFURLCopyLastPathComponent(self) }
+    }
+
+    /// Returns the POSIX file system path of `self` as a string.
+    ///
+    /// Prefer [`to_path_buf`](#method.to_path_buf) for file system access,
+    /// since it handles paths that are not valid UTF-8.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlcopyfilesystempath(_:_:)).
+    #[inline]
+    #[doc(alias = "CFURLCopyFileSystemPath")]
+    pub fn file_system_path(&self) -> Option<Arc<CFString>> {
+        extern "C" {
+            fn CFURLCopyFileSystemPath(
+                an_url: &CFURL,
+                path_style: CFIndex,
+            ) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFURLCopyFileSystemPath(self, POSIX_PATH_STYLE) }
+    }
+
+    /// Returns the local file system path of `self`, resolved against its
+    /// base URL.
+    ///
+    /// Returns `None` if `self` cannot be represented as a file system path,
+    /// such as for non-file URLs.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfurlgetfilesystemrepresentation(_:_:_:_:)).
+    #[doc(alias = "CFURLGetFileSystemRepresentation")]
+    pub fn to_path_buf(&self) -> Option<PathBuf> {
+        extern "C" {
+            fn CFURLGetFileSystemRepresentation(
+                url: &CFURL,
+                resolve_against_base: Boolean,
+                buffer: *mut u8,
+                max_buf_len: CFIndex,
+            ) -> Boolean;
+        }
+
+        // There is no way to query the required length, so grow the buffer
+        // until the representation fits.
+        let mut buffer = vec![0u8; 1024];
+        loop {
+            let success = unsafe {
+                CFURLGetFileSystemRepresentation(
+                    self,
+                    1,
+                    buffer.as_mut_ptr(),
+                    buffer.len() as CFIndex,
+                )
+            };
+
+            if success != 0 {
+                let len = buffer.iter().position(|&b| b == 0)?;
+                buffer.truncate(len);
+                return Some(OsStr::from_bytes(&buffer).into());
+            }
+
+            if buffer.len() >= MAX_PATH_BUFFER_LEN {
+                return None;
+            }
+            buffer.resize(buffer.len() * 2, 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Arc<CFURL> {
+        CFURL::from_string(&CFString::from_str(s), None).unwrap()
+    }
+
+    #[test]
+    fn components() {
+        let url = url("https://example.com/a/b%20c?d=e");
+
+        assert!(*url.scheme().unwrap() == "https");
+        assert!(*url.host().unwrap() == "example.com");
+        assert!(*url.path().unwrap() == "/a/b%20c");
+        assert!(*url.last_path_component().unwrap() == "b c");
+        assert!(*url.string() == "https://example.com/a/b%20c?d=e");
+        assert_eq!(url.to_path_buf(), None);
+    }
+
+    #[test]
+    fn relative() {
+        let base = url("https://example.com/a/b");
+        let relative = CFURL::from_string(&CFString::from_str("c"), Some(&base)).unwrap();
+
+        assert_eq!(*relative.base_url().unwrap(), *base);
+        assert!(*relative.absolute_url().unwrap().string() == "https://example.com/a/c");
+
+        let appended = base
+            .url_by_appending_path_component(&CFString::from_str("d e"), false)
+            .unwrap();
+        assert!(*appended.string() == "https://example.com/a/b/d%20e");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(CFURL::from_string(&CFString::from_str("a b"), None).is_none());
+    }
+
+    #[test]
+    fn file_path() {
+        let path = Path::new("/tmp/fruity test");
+        let url = CFURL::from_file_path(path, false);
+
+        assert!(*url.scheme().unwrap() == "file");
+        assert!(*url.last_path_component().unwrap() == "fruity test");
+        assert!(*url.file_system_path().unwrap() == "/tmp/fruity test");
+        assert_eq!(url.to_path_buf().unwrap(), path);
+
+        let dir = CFURL::from_file_path("/tmp", true);
+        assert!(*dir.string() == "file:///tmp/");
+    }
+
+    #[test]
+    fn non_utf8_file_path() {
+        let path = Path::new(OsStr::from_bytes(b"/tmp/fruity\xFF"));
+        let url = CFURL::from_file_path(path, false);
+
+        assert_eq!(url.to_path_buf().unwrap(), path);
+    }
+}
@@ -0,0 +1,162 @@
+use super::{CFAllocator, CFString, CFType};
+use crate::core::Arc;
+use std::fmt;
+
+cf_subclass! {
+    /// A universally unique identifier (UUID).
+    ///
+    /// Instances are uniqued by Core Foundation, so creating a UUID from the
+    /// same bytes twice may return the same object.
+    ///
+    /// Documentation:
+    /// [Swift](https://developer.apple.com/documentation/corefoundation/cfuuid?language=swift) |
+    /// [Objective-C](https://developer.apple.com/documentation/corefoundation/cfuuidref?language=objc)
+    #[derive(PartialEq, Hash)]
+    pub class CFUUID: CFType;
+    type_id = CFUUIDGetTypeID;
+}
+
+/// The layout of `CFUUIDBytes`, which is passed by value.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct CFUUIDBytes([u8; 16]);
+
+impl Eq for CFUUID {}
+
+impl fmt::Debug for CFUUID {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_string().fmt(f)
+    }
+}
+
+impl From<[u8; 16]> for Arc<CFUUID> {
+    #[inline]
+    fn from(bytes: [u8; 16]) -> Self {
+        CFUUID::from_bytes(bytes)
+    }
+}
+
+impl From<&CFUUID> for [u8; 16] {
+    #[inline]
+    fn from(uuid: &CFUUID) -> Self {
+        uuid.bytes()
+    }
+}
+
+impl CFUUID {
+    /// Generates a new random UUID.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfuuidcreate(_:)).
+    #[inline]
+    #[doc(alias = "CFUUIDCreate")]
+    pub fn new() -> Arc<Self> {
+        extern "C" {
+            fn CFUUIDCreate(alloc: Option<&CFAllocator>) -> Option<Arc<CFUUID>>;
+        }
+        unsafe { CFUUIDCreate(None) }.expect("Could not create CFUUID")
+    }
+
+    /// Creates a UUID from its 16 raw bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfuuidcreatefromuuidbytes(_:_:)).
+    #[inline]
+    #[doc(alias = "CFUUIDCreateFromUUIDBytes")]
+    pub fn from_bytes(bytes: [u8; 16]) -> Arc<Self> {
+        extern "C" {
+            fn CFUUIDCreateFromUUIDBytes(
+                alloc: Option<&CFAllocator>,
+                bytes: CFUUIDBytes,
+            ) -> Option<Arc<CFUUID>>;
+        }
+        unsafe { CFUUIDCreateFromUUIDBytes(None, CFUUIDBytes(bytes)) }
+            .expect("Could not create CFUUID")
+    }
+
+    /// Parses a UUID from its hyphenated string form, such as
+    /// `"68753A44-4D6F-1226-9C60-0050E4C00067"`.
+    ///
+    /// Returns `None` if `string` could not be parsed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfuuidcreatefromstring(_:_:)).
+    #[inline]
+    #[doc(alias = "CFUUIDCreateFromString")]
+    pub fn from_string(string: &CFString) -> Option<Arc<Self>> {
+        extern "C" {
+            fn CFUUIDCreateFromString(
+                alloc: Option<&CFAllocator>,
+                uuid_str: &CFString,
+            ) -> Option<Arc<CFUUID>>;
+        }
+        unsafe { CFUUIDCreateFromString(None, string) }
+    }
+
+    /// Returns the canonical string form of `self`, consisting of uppercase
+    /// hexadecimal digits separated by hyphens.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfuuidcreatestring(_:_:)).
+    #[inline]
+    #[doc(alias = "CFUUIDCreateString")]
+    pub fn to_string(&self) -> Arc<CFString> {
+        extern "C" {
+            fn CFUUIDCreateString(
+                alloc: Option<&CFAllocator>,
+                uuid: &CFUUID,
+            ) -> Option<Arc<CFString>>;
+        }
+        unsafe { CFUUIDCreateString(None, self) }.expect("Could not create CFString")
+    }
+
+    /// Returns the 16 raw bytes of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/corefoundation/cfuuidgetuuidbytes(_:)).
+    #[inline]
+    #[doc(alias = "CFUUIDGetUUIDBytes")]
+    pub fn bytes(&self) -> [u8; 16] {
+        extern "C" {
+            fn CFUUIDGetUUIDBytes(uuid: &CFUUID) -> CFUUIDBytes;
+        }
+        unsafe { CFUUIDGetUUIDBytes(self) }.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; 16] = [
+        0x68, 0x75, 0x3A, 0x44, 0x4D, 0x6F, 0x12, 0x26, 0x9C, 0x60, 0x00, 0x50, 0xE4, 0xC0, 0x00,
+        0x67,
+    ];
+
+    #[test]
+    fn bytes_round_trip() {
+        let uuid = CFUUID::from_bytes(BYTES);
+        assert_eq!(uuid.bytes(), BYTES);
+
+        let random = CFUUID::new();
+        assert_eq!(CFUUID::from_bytes(random.bytes()).bytes(), random.bytes());
+        assert_ne!(*random, *uuid);
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let uuid = CFUUID::from_bytes(BYTES);
+        let string = uuid.to_string();
+        assert!(*string == "68753A44-4D6F-1226-9C60-0050E4C00067");
+
+        let parsed = CFUUID::from_string(&string).unwrap();
+        assert_eq!(parsed.bytes(), BYTES);
+
+        let lowercase = CFString::from_str("68753a44-4d6f-1226-9c60-0050e4c00067");
+        assert_eq!(CFUUID::from_string(&lowercase).unwrap().bytes(), BYTES);
+    }
+
+    #[test]
+    fn structural_eq() {
+        let a = CFUUID::from_bytes(BYTES);
+        let b = CFUUID::from_string(&a.to_string()).unwrap();
+
+        assert_eq!(*a, *b);
+    }
+}
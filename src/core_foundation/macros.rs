@@ -0,0 +1,32 @@
+// This macro is intentionally undocumented to ensure it is not publicly
+// exported.
+macro_rules! cf_type_object {
+    ($obj:ty, $get_type_id:ident) => {
+        unsafe impl $crate::core_foundation::CFTypeObject for $obj {
+            #[inline]
+            fn type_id() -> $crate::core_foundation::CFTypeID {
+                extern "C" {
+                    fn $get_type_id() -> $crate::core_foundation::CFTypeID;
+                }
+                unsafe { $get_type_id() }
+            }
+        }
+    };
+}
+
+// This macro is intentionally undocumented to ensure it is not publicly
+// exported.
+macro_rules! cf_subclass {
+    (
+        $(#[$meta:meta])+
+        $vis:vis class $a:ident : $b:ty ;
+        type_id = $get_type_id:ident ;
+    ) => {
+        subclass! {
+            $(#[$meta])+
+            $vis class $a : $b ;
+        }
+
+        cf_type_object!($a, $get_type_id);
+    };
+}
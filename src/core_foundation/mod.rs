@@ -9,12 +9,79 @@
 
 #![cfg(feature = "core_foundation")]
 
+#[macro_use]
+mod macros;
+
 pub mod sys;
 
+mod cf_allocator;
+mod cf_array;
+mod cf_attributed_string;
+mod cf_binary_heap;
+mod cf_bit_vector;
+mod cf_bundle;
+mod cf_character_set;
+mod cf_data;
+mod cf_date;
+mod cf_date_formatter;
+mod cf_dictionary;
+mod cf_error;
+mod cf_locale;
+mod cf_message_port;
+mod cf_notification_center;
+mod cf_number;
+mod cf_preferences;
+mod cf_property_list;
+mod cf_range;
+mod cf_run_loop;
+mod cf_run_loop_source;
+mod cf_run_loop_timer;
+mod cf_set;
+mod cf_socket;
+mod cf_stream;
+mod cf_string;
+mod cf_string_tokenizer;
+mod cf_time_zone;
+mod cf_tree;
 mod cf_type;
+mod cf_type_object;
+mod cf_url;
+mod cf_uuid;
 mod cmp;
 
+pub use cf_allocator::*;
+pub use cf_array::*;
+pub use cf_attributed_string::*;
+pub use cf_binary_heap::*;
+pub use cf_bit_vector::*;
+pub use cf_bundle::*;
+pub use cf_character_set::*;
+pub use cf_data::*;
+pub use cf_date::*;
+pub use cf_date_formatter::*;
+pub use cf_dictionary::*;
+pub use cf_error::*;
+pub use cf_locale::*;
+pub use cf_message_port::*;
+pub use cf_notification_center::*;
+pub use cf_number::*;
+pub use cf_preferences::*;
+pub use cf_property_list::*;
+pub use cf_range::*;
+pub use cf_run_loop::*;
+pub use cf_run_loop_source::*;
+pub use cf_run_loop_timer::*;
+pub use cf_set::*;
+pub use cf_socket::*;
+pub use cf_stream::*;
+pub use cf_string::*;
+pub use cf_string_tokenizer::*;
+pub use cf_time_zone::*;
+pub use cf_tree::*;
 pub use cf_type::*;
+pub use cf_type_object::*;
+pub use cf_url::*;
+pub use cf_uuid::*;
 pub use cmp::*;
 
 /// A constant that indicates that a search operation did not succeed in
@@ -1,6 +1,6 @@
 //! Raw unsafe C functions exposed by `CoreFoundation.framework`.
 
-use super::{Boolean, CFHashCode, CFIndex, CFType, CFTypeID};
+use super::{Boolean, CFAllocator, CFHashCode, CFIndex, CFString, CFType, CFTypeID};
 
 #[allow(missing_docs)]
 #[link(name = "CoreFoundation", kind = "framework")]
@@ -13,4 +13,8 @@ extern "C" {
 
     pub fn CFHash(cf: *const CFType) -> CFHashCode;
     pub fn CFGetTypeID(cf: *const CFType) -> CFTypeID;
+
+    pub fn CFCopyDescription(cf: *const CFType) -> *const CFString;
+
+    pub fn CFGetAllocator(cf: *const CFType) -> *const CFAllocator;
 }
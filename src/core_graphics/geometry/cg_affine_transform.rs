@@ -1,5 +1,8 @@
 use super::{CGFloat, CGPoint, CGRect, CGSize};
 
+#[cfg(feature = "objc")]
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind};
+
 /// An affine transformation matrix for use in drawing 2D graphics.
 ///
 /// See [documentation](https://developer.apple.com/documentation/coregraphics/cgaffinetransform).
@@ -21,6 +24,17 @@ pub struct CGAffineTransform {
     pub ty: CGFloat,
 }
 
+#[cfg(feature = "objc")]
+unsafe impl Encode for CGAffineTransform {
+    const ENCODING: Encoding<'static> =
+        Encoding::Struct("CGAffineTransform", &[CGFloat::ENCODING; 6]);
+}
+
+#[cfg(feature = "objc")]
+unsafe impl MsgReturn for CGAffineTransform {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 // For the sake of testing, NaN is considered equal to itself.
 #[cfg(test)]
 impl PartialEq for CGAffineTransform {
@@ -1,5 +1,8 @@
 use super::{CGAffineTransform, CGFloat};
 
+#[cfg(feature = "objc")]
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind};
+
 /// A point in a two-dimensional coordinate system.
 ///
 /// See [documentation](https://developer.apple.com/documentation/coregraphics/cgpoint).
@@ -12,6 +15,17 @@ pub struct CGPoint {
     pub y: CGFloat,
 }
 
+#[cfg(feature = "objc")]
+unsafe impl Encode for CGPoint {
+    const ENCODING: Encoding<'static> =
+        Encoding::Struct("CGPoint", &[CGFloat::ENCODING, CGFloat::ENCODING]);
+}
+
+#[cfg(feature = "objc")]
+unsafe impl MsgReturn for CGPoint {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 impl From<(CGFloat, CGFloat)> for CGPoint {
     #[inline]
     fn from((x, y): (CGFloat, CGFloat)) -> Self {
@@ -1,6 +1,9 @@
 use super::{CGAffineTransform, CGFloat, CGPoint, CGSize};
 use std::mem;
 
+#[cfg(feature = "objc")]
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind};
+
 /// The location and dimensions of a rectangle.
 ///
 /// See [documentation](https://developer.apple.com/documentation/coregraphics/CGRect).
@@ -13,6 +16,17 @@ pub struct CGRect {
     pub size: CGSize,
 }
 
+#[cfg(feature = "objc")]
+unsafe impl Encode for CGRect {
+    const ENCODING: Encoding<'static> =
+        Encoding::Struct("CGRect", &[CGPoint::ENCODING, CGSize::ENCODING]);
+}
+
+#[cfg(feature = "objc")]
+unsafe impl MsgReturn for CGRect {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 /// Rectangle construction.
 impl CGRect {
     /// A rectangle with zero origin and size.
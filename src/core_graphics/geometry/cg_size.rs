@@ -1,5 +1,8 @@
 use super::{CGAffineTransform, CGFloat};
 
+#[cfg(feature = "objc")]
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind};
+
 /// Width and height values.
 ///
 /// See [documentation](https://developer.apple.com/documentation/coregraphics/cgsize).
@@ -12,6 +15,17 @@ pub struct CGSize {
     pub width: CGFloat,
 }
 
+#[cfg(feature = "objc")]
+unsafe impl Encode for CGSize {
+    const ENCODING: Encoding<'static> =
+        Encoding::Struct("CGSize", &[CGFloat::ENCODING, CGFloat::ENCODING]);
+}
+
+#[cfg(feature = "objc")]
+unsafe impl MsgReturn for CGSize {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 impl From<(CGFloat, CGFloat)> for CGSize {
     #[inline]
     fn from((height, width): (CGFloat, CGFloat)) -> Self {
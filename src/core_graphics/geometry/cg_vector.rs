@@ -1,5 +1,8 @@
 use super::CGFloat;
 
+#[cfg(feature = "objc")]
+use crate::objc::{MsgReturn, MsgReturnKind};
+
 /// A two-dimensional vector.
 ///
 /// See [documentation](https://developer.apple.com/documentation/coregraphics/cgvector).
@@ -12,6 +15,11 @@ pub struct CGVector {
     pub dy: CGFloat,
 }
 
+#[cfg(feature = "objc")]
+unsafe impl MsgReturn for CGVector {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 impl From<(CGFloat, CGFloat)> for CGVector {
     #[inline]
     fn from((dx, dy): (CGFloat, CGFloat)) -> Self {
@@ -1,3 +1,4 @@
+use crate::objc::{MsgReturn, MsgReturnKind};
 use std::cmp::Ordering;
 
 #[cfg(feature = "core_foundation")]
@@ -17,6 +18,10 @@ pub enum NSComparisonResult {
     OrderedDescending = 1,
 }
 
+unsafe impl MsgReturn for NSComparisonResult {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
 impl From<Ordering> for NSComparisonResult {
     #[inline]
     fn from(ordering: Ordering) -> Self {
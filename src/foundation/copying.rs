@@ -0,0 +1,144 @@
+use super::{
+    NSArray, NSData, NSDictionary, NSMutableArray, NSMutableData, NSMutableDictionary,
+    NSMutableSet, NSMutableString, NSMutableURLRequest, NSSet, NSString, NSURLRequest,
+};
+use crate::core::{Arc, ObjectType};
+use crate::objc::NSObject;
+
+/// An object that can be copied as an immutable object using
+/// [`NSCopying`](https://developer.apple.com/documentation/foundation/nscopying).
+///
+/// Copies of collections are shallow: they retain the same elements rather
+/// than copying them.
+///
+/// Copying an immutable object usually returns the same object retained,
+/// because it cannot change.
+pub trait Copying {
+    /// The type of the copy, which is the immutable counterpart of `Self`.
+    type Output: ObjectType;
+
+    /// Returns an immutable copy of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/1418807-copy).
+    #[doc(alias = "copy")]
+    #[doc(alias = "copyWithZone")]
+    #[doc(alias = "copyWithZone:")]
+    fn copy_object(&self) -> Arc<Self::Output>;
+}
+
+/// An object that can be copied as a mutable object using
+/// [`NSMutableCopying`](https://developer.apple.com/documentation/foundation/nsmutablecopying).
+///
+/// Copies of collections are shallow: they retain the same elements rather
+/// than copying them.
+pub trait MutableCopying {
+    /// The type of the copy, which is the mutable counterpart of `Self`.
+    type Output: ObjectType;
+
+    /// Returns a new mutable copy of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/1418978-mutablecopy).
+    #[doc(alias = "mutableCopy")]
+    #[doc(alias = "mutableCopyWithZone")]
+    #[doc(alias = "mutableCopyWithZone:")]
+    fn mutable_copy(&self) -> Arc<Self::Output>;
+}
+
+// Implements both traits for an immutable class and its mutable subclass.
+macro_rules! impl_copying {
+    ([$($generics:tt)*] $immutable:ty, $mutable:ty) => {
+        impl<$($generics)*> Copying for $immutable {
+            type Output = $immutable;
+
+            #[inline]
+            fn copy_object(&self) -> Arc<Self::Output> {
+                unsafe { Arc::cast_unchecked(NSObject::copy(self)) }
+            }
+        }
+
+        impl<$($generics)*> Copying for $mutable {
+            type Output = $immutable;
+
+            #[inline]
+            fn copy_object(&self) -> Arc<Self::Output> {
+                unsafe { Arc::cast_unchecked(NSObject::copy(self)) }
+            }
+        }
+
+        impl<$($generics)*> MutableCopying for $immutable {
+            type Output = $mutable;
+
+            #[inline]
+            fn mutable_copy(&self) -> Arc<Self::Output> {
+                unsafe { Arc::cast_unchecked(NSObject::mutable_copy(self)) }
+            }
+        }
+
+        impl<$($generics)*> MutableCopying for $mutable {
+            type Output = $mutable;
+
+            #[inline]
+            fn mutable_copy(&self) -> Arc<Self::Output> {
+                unsafe { Arc::cast_unchecked(NSObject::mutable_copy(self)) }
+            }
+        }
+    };
+}
+
+impl_copying!(['data] NSString<'data>, NSMutableString<'data>);
+impl_copying!([] NSData, NSMutableData);
+impl_copying!([T] NSArray<T>, NSMutableArray<T>);
+impl_copying!([K, V] NSDictionary<K, V>, NSMutableDictionary<K, V>);
+impl_copying!([T] NSSet<T>, NSMutableSet<T>);
+impl_copying!([] NSURLRequest, NSMutableURLRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::ClassType;
+
+    #[test]
+    fn array() {
+        let values = [NSString::from_str("a"), NSString::from_str("b")];
+        let values: Vec<&NSString> = values.iter().map(|value| &**value).collect();
+
+        let mut original: Arc<NSMutableArray<NSString>> =
+            NSArray::from_slice(&values).mutable_copy();
+        let copy: Arc<NSArray<NSString>> = original.copy_object();
+        assert!(!copy.is_kind_of_class(NSMutableArray::<NSObject>::class()));
+
+        // Mutating the original does not affect the copy.
+        Arc::get_mut(&mut original)
+            .unwrap()
+            .push(&NSString::from_str("c"));
+        assert_eq!(original.len(), 3);
+        assert_eq!(copy.len(), 2);
+
+        // The copy shares its elements with the original.
+        assert!(std::ptr::eq(&copy[0], &original[0]));
+    }
+
+    #[test]
+    fn string() {
+        let mut string = NSString::from_str("fruity").mutable_copy();
+        let copy: Arc<NSString> = string.copy_object();
+
+        Arc::get_mut(&mut string).unwrap().push_str("!");
+        assert_eq!(*string, *"fruity!");
+        assert_eq!(*copy, *"fruity");
+    }
+
+    #[test]
+    fn immutable() {
+        // Copies of immutable objects are usually the same object.
+        let data = NSData::from_slice(b"fruity");
+        assert!(std::ptr::eq(&*data, &*data.copy_object()));
+
+        let mut mutable: Arc<NSMutableData> = data.mutable_copy();
+        assert!(!std::ptr::eq::<NSData>(&*data, &**mutable));
+
+        Arc::get_mut(&mut mutable).unwrap().as_mut_slice()[0] = b'F';
+        assert_eq!(data.as_slice(), b"fruity");
+        assert_eq!(mutable.copy_object().as_slice(), b"Fruity");
+    }
+}
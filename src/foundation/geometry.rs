@@ -1,4 +1,5 @@
 use crate::core_graphics::{CGFloat, CGPoint, CGRect, CGRectEdge, CGSize};
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind};
 
 /// A point in a Cartesian coordinate system.
 ///
@@ -47,6 +48,14 @@ pub struct NSEdgeInsets {
     pub right: CGFloat,
 }
 
+unsafe impl Encode for NSEdgeInsets {
+    const ENCODING: Encoding<'static> = Encoding::Struct("NSEdgeInsets", &[CGFloat::ENCODING; 4]);
+}
+
+unsafe impl MsgReturn for NSEdgeInsets {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 impl From<(CGFloat, CGFloat, CGFloat, CGFloat)> for NSEdgeInsets {
     #[inline]
     fn from((top, left, bottom, right): (CGFloat, CGFloat, CGFloat, CGFloat)) -> Self {
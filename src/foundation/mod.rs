@@ -15,24 +15,102 @@ mod ns_string;
 
 pub mod error_codes;
 
+#[cfg(feature = "serde")]
+pub mod serde;
+
 mod cmp;
+mod copying;
 mod geometry;
+mod ns_array;
+mod ns_bundle;
+mod ns_calendar;
+mod ns_character_set;
+mod ns_data;
+mod ns_date;
+mod ns_dictionary;
+mod ns_enumerator;
 mod ns_error;
 mod ns_exception;
+mod ns_file_handle;
+mod ns_file_manager;
+mod ns_formatter;
+mod ns_json_serialization;
+mod ns_key_value_coding;
+mod ns_key_value_observing;
+mod ns_keyed_archiver;
+mod ns_locale;
+mod ns_notification;
 mod ns_null;
 mod ns_number;
+mod ns_operation;
+mod ns_predicate;
+mod ns_process_info;
+mod ns_property_list_serialization;
 mod ns_range;
+mod ns_regular_expression;
+mod ns_run_loop;
+mod ns_scanner;
+mod ns_set;
+mod ns_stream;
+mod ns_thread;
+mod ns_time_zone;
+mod ns_timer;
+mod ns_url;
+mod ns_url_components;
+mod ns_url_request;
+mod ns_url_response;
+mod ns_url_session;
+mod ns_user_defaults;
+mod ns_uuid;
 mod ns_value;
+mod observer_token;
 
 pub use cmp::*;
+pub use copying::*;
 pub use geometry::*;
+pub use ns_array::*;
+pub use ns_bundle::*;
+pub use ns_calendar::*;
+pub use ns_character_set::*;
+pub use ns_data::*;
+pub use ns_date::*;
+pub use ns_dictionary::*;
+pub use ns_enumerator::*;
 pub use ns_error::*;
 pub use ns_exception::*;
+pub use ns_file_handle::*;
+pub use ns_file_manager::*;
+pub use ns_formatter::*;
+pub use ns_json_serialization::*;
+pub use ns_key_value_observing::*;
+pub use ns_keyed_archiver::*;
+pub use ns_locale::*;
+pub use ns_notification::*;
 pub use ns_null::*;
 pub use ns_number::*;
+pub use ns_operation::*;
+pub use ns_predicate::*;
+pub use ns_process_info::*;
+pub use ns_property_list_serialization::*;
 pub use ns_range::*;
+pub use ns_regular_expression::*;
+pub use ns_run_loop::*;
+pub use ns_scanner::*;
+pub use ns_set::*;
+pub use ns_stream::*;
 pub use ns_string::*;
+pub use ns_thread::*;
+pub use ns_time_zone::*;
+pub use ns_timer::*;
+pub use ns_url::*;
+pub use ns_url_components::*;
+pub use ns_url_request::*;
+pub use ns_url_response::*;
+pub use ns_url_session::*;
+pub use ns_user_defaults::*;
+pub use ns_uuid::*;
 pub use ns_value::*;
+pub use observer_token::*;
 
 /// A number of seconds.
 ///
@@ -0,0 +1,728 @@
+use super::{NSComparisonResult, NSEnumerator, NSNotFound, NSPredicate, NSRange};
+use crate::core::{Arc, ObjectType};
+use crate::objc::{
+    retain_autoreleased, ClassType, DowncastTarget, NSFastEnumeration, NSObject, NSUInteger, Sel,
+    StackBlock, BOOL,
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    cmp::{self, Ordering},
+    fmt,
+    ops::Index,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+objc_subclass! {
+    /// A static ordered collection of objects of type `T`.
+    ///
+    /// The untyped `NSArray<NSObject>` form can hold heterogeneous objects. It
+    /// can be checked against a specific element type with
+    /// [`downcast`](#method.downcast).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray).
+    #[derive(PartialEq)]
+    pub class NSArray<T>: NSObject<'static>;
+}
+
+downcast_target!(NSArray<NSObject<'static>>);
+
+impl<T: ObjectType + fmt::Debug + 'static> fmt::Debug for NSArray<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ObjectType + 'static> Index<usize> for NSArray<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "Index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
+unsafe impl<T: ObjectType + 'static> NSFastEnumeration<'static> for NSArray<T> {
+    type Item = T;
+}
+
+impl<'a, T: ObjectType + 'static> IntoIterator for &'a NSArray<T> {
+    type Item = &'a T;
+    type IntoIter = NSArrayIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType + 'static> NSArray<T> {
+    /// Creates an array containing retained `values`.
+    ///
+    /// This is equivalent to `arrayWithObjects:count:`, but the result is not
+    /// autoreleased.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1460068-initwithobjects).
+    #[doc(alias = "arrayWithObjects")]
+    #[doc(alias = "arrayWithObjects:count:")]
+    #[doc(alias = "initWithObjects:count:")]
+    pub fn from_slice(values: &[&T]) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                objects: *const *const (),
+                count: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithObjects:count:);
+        let objects = values.as_ptr().cast();
+        unsafe {
+            let array = objc_msgSend(Arc::cast_unchecked(value), sel, objects, values.len());
+            Arc::cast_unchecked(array)
+        }
+    }
+
+    /// Returns the number of objects in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1409982-count).
+    #[inline]
+    #[doc(alias = "count")]
+    pub fn len(&self) -> usize {
+        unsafe { _msg_send_any![self, count] }
+    }
+
+    /// Returns `true` if `self` contains no objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the object at `index`, or `None` if it is out of bounds.
+    ///
+    /// Unlike `objectAtIndex:`, this never raises an exception.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1417555-objectatindex).
+    #[inline]
+    #[doc(alias = "objectAtIndex")]
+    #[doc(alias = "objectAtIndex:")]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            unsafe {
+                let value: *const T = _msg_send_any![self, objectAtIndex: index];
+                Some(&*value)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first object in `self`, or `None` if it is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1412852-firstobject).
+    #[inline]
+    #[doc(alias = "firstObject")]
+    pub fn first(&self) -> Option<&T> {
+        unsafe {
+            let value: *const T = _msg_send_any![self, firstObject];
+            value.as_ref()
+        }
+    }
+
+    /// Returns the last object in `self`, or `None` if it is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1414319-lastobject).
+    #[inline]
+    #[doc(alias = "lastObject")]
+    pub fn last(&self) -> Option<&T> {
+        unsafe {
+            let value: *const T = _msg_send_any![self, lastObject];
+            value.as_ref()
+        }
+    }
+
+    /// Returns `true` if an object in `self` is equal to `value`.
+    ///
+    /// Equality is determined by `isEqual:`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1414563-containsobject).
+    #[inline]
+    #[doc(alias = "containsObject")]
+    #[doc(alias = "containsObject:")]
+    pub fn contains(&self, value: &T) -> bool {
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, containsObject: value => BOOL] }.into()
+    }
+
+    /// Returns the lowest index of an object in `self` equal to `value`.
+    ///
+    /// Equality is determined by `isEqual:`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1417076-indexofobject).
+    #[inline]
+    #[doc(alias = "indexOfObject")]
+    #[doc(alias = "indexOfObject:")]
+    pub fn index_of(&self, value: &T) -> Option<usize> {
+        let value = value as *const T;
+        let index: NSUInteger = unsafe { _msg_send_any![self, indexOfObject: value] };
+        if index == NSNotFound as NSUInteger {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    /// Returns an iterator over the objects in `self`.
+    ///
+    /// Objects are retrieved in chunks using
+    /// [`getObjects:range:`](https://developer.apple.com/documentation/foundation/nsarray/1408859-getobjects).
+    #[inline]
+    pub fn iter(&self) -> NSArrayIter<'_, T> {
+        NSArrayIter {
+            array: self,
+            next: 0,
+            end: self.len(),
+            buf: [ptr::null(); CHUNK_LEN],
+            buf_index: 0,
+            buf_len: 0,
+        }
+    }
+
+    /// Returns an enumerator over the objects in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/objectenumerator).
+    #[inline]
+    #[doc(alias = "objectEnumerator")]
+    pub fn object_enumerator(&self) -> Arc<NSEnumerator<T>> {
+        let enumerator = unsafe { retain_autoreleased(|| _msg_send_any![self, objectEnumerator]) };
+        enumerator.expect("Failed to create enumerator")
+    }
+
+    /// Returns an enumerator over the objects in `self` in reverse order.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/reverseobjectenumerator).
+    #[inline]
+    #[doc(alias = "reverseObjectEnumerator")]
+    pub fn reverse_object_enumerator(&self) -> Arc<NSEnumerator<T>> {
+        let enumerator =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, reverseObjectEnumerator]) };
+        enumerator.expect("Failed to create enumerator")
+    }
+
+    /// Returns a vector of retained objects in `self`.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<Arc<T>> {
+        self.iter().map(|value| Arc::retain(value)).collect()
+    }
+
+    /// Returns `self` as an array of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &NSArray<NSObject<'static>> {
+        // SAFETY: Foundation arrays can only contain `NSObject` instances.
+        unsafe { &*(self as *const Self as *const NSArray<NSObject<'static>>) }
+    }
+
+    /// Returns a new array of the objects in `self` that satisfy `predicate`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsarray/1416355-filteredarrayusingpredicate).
+    #[inline]
+    #[doc(alias = "filteredArrayUsingPredicate")]
+    #[doc(alias = "filteredArrayUsingPredicate:")]
+    pub fn filtered(&self, predicate: &NSPredicate) -> Arc<Self> {
+        let array = unsafe {
+            retain_autoreleased(|| _msg_send_any![self, filteredArrayUsingPredicate: predicate])
+        };
+        array.expect("Could not filter array")
+    }
+}
+
+impl NSArray<NSObject<'static>> {
+    /// Returns the object at `index`, or `None` if it is out of bounds or
+    /// is [`NSNull`](struct.NSNull.html).
+    #[inline]
+    pub fn get_non_null(&self, index: usize) -> Option<&NSObject<'static>> {
+        self.get(index)?.non_null()
+    }
+
+    /// Casts `self` to an array of elements of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// All elements of `self` must be instances of `T`.
+    #[inline]
+    pub unsafe fn cast_unchecked<T: ObjectType + 'static>(&self) -> &NSArray<T> {
+        &*(self as *const Self as *const NSArray<T>)
+    }
+
+    /// Returns `self` as an array of `T` if all of its elements are instances
+    /// of `T` or of a subclass of `T`.
+    ///
+    /// This checks every element using
+    /// [`NSObject::is_kind_of_class`](../objc/struct.NSObject.html#method.is_kind_of_class).
+    #[inline]
+    pub fn downcast<T: DowncastTarget<'static>>(&self) -> Option<&NSArray<T>> {
+        let class = <T as ClassType>::class();
+        if self.iter().all(|value| value.is_kind_of_class(class)) {
+            Some(unsafe { self.cast_unchecked() })
+        } else {
+            None
+        }
+    }
+}
+
+// The number of objects retrieved at once by `NSArrayIter`.
+const CHUNK_LEN: usize = 32;
+
+/// An iterator over the objects of an [`NSArray`].
+///
+/// This is created by [`NSArray::iter`].
+pub struct NSArrayIter<'a, T: ObjectType + 'static> {
+    array: &'a NSArray<T>,
+    next: usize,
+    end: usize,
+    buf: [*const T; CHUNK_LEN],
+    buf_index: usize,
+    buf_len: usize,
+}
+
+impl<'a, T: ObjectType + 'static> Iterator for NSArrayIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.buf_index == self.buf_len {
+            if self.next >= self.end {
+                return None;
+            }
+
+            let len = cmp::min(CHUNK_LEN, self.end - self.next);
+            let range = NSRange::new(self.next, len);
+            let buf = self.buf.as_mut_ptr();
+            unsafe { _msg_send_any![self.array, getObjects: buf range: range => ()] };
+
+            self.next += len;
+            self.buf_index = 0;
+            self.buf_len = len;
+        }
+
+        let value = self.buf[self.buf_index];
+        self.buf_index += 1;
+
+        // SAFETY: The array retains its objects for at least `'a`.
+        Some(unsafe { &*value })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: ObjectType + 'static> ExactSizeIterator for NSArrayIter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        (self.end - self.next) + (self.buf_len - self.buf_index)
+    }
+}
+
+objc_subclass! {
+    /// A dynamic ordered collection of objects of type `T`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a uniquely
+    /// referenced array via [`Arc::get_mut`]. This ensures that no references
+    /// returned by [`NSArray`] methods outlive the objects they point to.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray).
+    #[derive(PartialEq)]
+    pub class NSMutableArray<T>: NSArray<T>;
+}
+
+downcast_target!(NSMutableArray<NSObject<'static>>);
+
+impl<T: ObjectType + fmt::Debug + 'static> fmt::Debug for NSMutableArray<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &NSArray<T>).fmt(f)
+    }
+}
+
+impl<T: ObjectType + 'static> NSMutableArray<T> {
+    /// Creates an empty mutable array.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates an empty mutable array with initial storage for `capacity`
+    /// objects.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1415992-initwithcapacity).
+    #[inline]
+    #[doc(alias = "initWithCapacity")]
+    #[doc(alias = "arrayWithCapacity")]
+    pub fn with_capacity(capacity: usize) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                capacity: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithCapacity:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, capacity)) }
+    }
+
+    /// Appends `value` to the end of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1411017-addobject).
+    #[inline]
+    #[doc(alias = "addObject")]
+    #[doc(alias = "addObject:")]
+    pub fn push(&mut self, value: &T) {
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, addObject: value] }
+    }
+
+    /// Inserts `value` at `index`, shifting all objects after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1416296-insertobject).
+    #[inline]
+    #[doc(alias = "insertObject")]
+    #[doc(alias = "insertObject:atIndex:")]
+    pub fn insert(&mut self, index: usize, value: &T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "Insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, insertObject: value atIndex: index] }
+    }
+
+    /// Removes and returns the object at `index`, shifting all objects after
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1410154-removeobjectatindex).
+    #[inline]
+    #[doc(alias = "removeObjectAtIndex")]
+    #[doc(alias = "removeObjectAtIndex:")]
+    pub fn remove(&mut self, index: usize) -> Arc<T> {
+        let value = Arc::retain(&self[index]);
+        unsafe { _msg_send_any![self, removeObjectAtIndex: index] }
+        value
+    }
+
+    /// Removes and returns the last object, or `None` if `self` is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1413073-removelastobject).
+    #[inline]
+    #[doc(alias = "removeLastObject")]
+    pub fn remove_last(&mut self) -> Option<Arc<T>> {
+        let value = Arc::retain(self.last()?);
+        unsafe { _msg_send_any![self, removeLastObject] }
+        Some(value)
+    }
+
+    /// Removes all objects from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1410689-removeallobjects).
+    #[inline]
+    #[doc(alias = "removeAllObjects")]
+    pub fn clear(&mut self) {
+        unsafe { _msg_send_any![self, removeAllObjects] }
+    }
+
+    /// Replaces the object at `index` with `value`, returning the previous
+    /// object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1415712-replaceobjectatindex).
+    #[inline]
+    #[doc(alias = "replaceObjectAtIndex")]
+    #[doc(alias = "replaceObjectAtIndex:withObject:")]
+    pub fn replace(&mut self, index: usize, value: &T) -> Arc<T> {
+        let old = Arc::retain(&self[index]);
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, replaceObjectAtIndex: index withObject: value] }
+        old
+    }
+
+    /// Sorts the objects of `self` with `compare`.
+    ///
+    /// `compare` is passed to Foundation as a block.
+    ///
+    /// If `compare` panics, the remaining comparisons report equality and the
+    /// panic is resumed after control returns from Foundation. The order of
+    /// `self` is then unspecified.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablearray/1415065-sortusingcomparator).
+    #[doc(alias = "sortUsingComparator")]
+    #[doc(alias = "sortUsingComparator:")]
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering,
+    {
+        let panic = RefCell::<Option<Box<dyn Any + Send>>>::new(None);
+
+        let comparator = |a: *const T, b: *const T| -> NSComparisonResult {
+            // Unwinding into Foundation is undefined behavior.
+            if panic.borrow().is_some() {
+                return NSComparisonResult::OrderedSame;
+            }
+
+            let (a, b) = unsafe { (&*a, &*b) };
+            match panic::catch_unwind(AssertUnwindSafe(|| compare(a, b))) {
+                Ok(ordering) => ordering.into(),
+                Err(payload) => {
+                    *panic.borrow_mut() = Some(payload);
+                    NSComparisonResult::OrderedSame
+                }
+            }
+        };
+
+        // SAFETY: The comparator is only called before this method returns.
+        let block = StackBlock::<(*const T, *const T), _>::without_signature(&comparator);
+        unsafe { _msg_send_any![self, sortUsingComparator: &block => ()] };
+
+        if let Some(payload) = panic.into_inner() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSNumber, NSString};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn empty() {
+        let array = NSArray::<NSString>::from_slice(&[]);
+        assert_eq!(array.len(), 0);
+        assert!(array.is_empty());
+        assert!(array.first().is_none());
+        assert!(array.last().is_none());
+        assert_eq!(array.iter().count(), 0);
+        assert!(array.to_vec().is_empty());
+    }
+
+    #[test]
+    fn index() {
+        let a = NSString::from_str("a");
+        let b = NSString::from_str("b");
+        let c = NSString::from_str("c");
+        let array = NSArray::from_slice(&[&*a, &*b]);
+
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[0], *a);
+        assert_eq!(array[1], *b);
+        assert_eq!(array.first(), Some(&*a));
+        assert_eq!(array.last(), Some(&*b));
+
+        assert!(array.contains(&a));
+        assert!(!array.contains(&c));
+        assert_eq!(array.index_of(&b), Some(1));
+        assert_eq!(array.index_of(&c), None);
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let a = NSString::from_str("a");
+        let array = NSArray::from_slice(&[&*a]);
+        assert!(array.get(1).is_none());
+        assert!(array.get(usize::MAX).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let array = NSArray::<NSString>::from_slice(&[]);
+        let _ = &array[0];
+    }
+
+    #[test]
+    fn iter_large() {
+        let strings: Vec<Arc<NSString>> = (0..1_000)
+            .map(|i| NSString::from_str(&i.to_string()))
+            .collect();
+        let refs: Vec<&NSString> = strings.iter().cycle().take(100_000).map(|s| &**s).collect();
+
+        let array = NSArray::from_slice(&refs);
+        assert_eq!(array.len(), 100_000);
+
+        let start = Instant::now();
+        let mut iter = array.iter();
+        assert_eq!(iter.len(), 100_000);
+        for (i, value) in (&mut iter).enumerate() {
+            assert!(ptr::eq(value, refs[i]));
+        }
+        assert_eq!(iter.len(), 0);
+
+        // Chunked retrieval should make this far faster than the bound.
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn iter_fast_large() {
+        let number = NSNumber::from_integer(7);
+        let refs: Vec<&NSNumber> = vec![&*number; 1_000_000];
+        let array = NSArray::from_slice(&refs);
+
+        let batches = crate::objc::fast_enumeration::batch_count();
+        assert_eq!(
+            array
+                .iter_fast()
+                .filter(|&value| ptr::eq(value, &*number))
+                .count(),
+            1_000_000
+        );
+
+        // Elements are retrieved in batches of at least the buffer length,
+        // plus a final request that returns none.
+        let batches = crate::objc::fast_enumeration::batch_count() - batches;
+        assert!(batches <= 1_000_000 / 16 + 1, "{} batches", batches);
+    }
+
+    #[test]
+    #[should_panic(expected = "mutated while being enumerated")]
+    fn iter_fast_mutated() {
+        let strings: Vec<Arc<NSString>> = (0..100)
+            .map(|i| NSString::from_str(&i.to_string()))
+            .collect();
+        let mut array = NSMutableArray::<NSString>::new();
+        for string in &strings {
+            Arc::get_mut(&mut array).unwrap().push(string);
+        }
+
+        // Mutations must go through `&mut`, so this uses a message directly to
+        // simulate Objective-C code mutating the array.
+        for value in array.iter_fast() {
+            unsafe { _msg_send_any![&**array, addObject: value => ()] };
+        }
+    }
+
+    #[test]
+    fn mutate() {
+        let strings: Vec<Arc<NSString>> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|s| NSString::from_str(s))
+            .collect();
+        let [a, b, c, d] = [&*strings[0], &*strings[1], &*strings[2], &*strings[3]];
+
+        let mut array = NSMutableArray::with_capacity(4);
+        let array = Arc::get_mut(&mut array).unwrap();
+
+        array.push(b);
+        array.push(d);
+        array.insert(0, a);
+        array.insert(2, c);
+        assert!(array.iter().eq([a, b, c, d].iter().copied()));
+
+        assert_eq!(*array.replace(3, a), *d);
+        assert_eq!(*array.remove(0), *a);
+        assert_eq!(array.remove_last().as_deref(), Some(a));
+        assert_eq!(array.len(), 2);
+
+        array.clear();
+        assert!(array.is_empty());
+        assert!(array.remove_last().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Insertion index (is 1) should be <= len (is 0)")]
+    fn insert_out_of_bounds() {
+        let a = NSString::from_str("a");
+        let mut array = NSMutableArray::new();
+        Arc::get_mut(&mut array).unwrap().insert(1, &*a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut array = NSMutableArray::<NSString>::new();
+        Arc::get_mut(&mut array).unwrap().remove(0);
+    }
+
+    #[test]
+    fn sort_by() {
+        let strings: Vec<Arc<NSString>> = ["delta", "Bravo", "alpha", "Charlie"]
+            .iter()
+            .map(|s| NSString::from_str(s))
+            .collect();
+
+        let mut array = NSMutableArray::<NSString>::new();
+        let array = Arc::get_mut(&mut array).unwrap();
+        for string in &strings {
+            array.push(string);
+        }
+
+        array.sort_by(|a, b| a.case_insensitive_compare(b).into());
+
+        let sorted: Vec<String> = array.iter().map(|s| s.to_string()).collect();
+        assert_eq!(sorted, ["alpha", "Bravo", "Charlie", "delta"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "comparator panic")]
+    fn sort_by_panic() {
+        let a = NSString::from_str("a");
+        let b = NSString::from_str("b");
+
+        let mut array = NSMutableArray::<NSString>::new();
+        let array = Arc::get_mut(&mut array).unwrap();
+        array.push(&a);
+        array.push(&b);
+
+        array.sort_by(|_, _| panic!("comparator panic"));
+    }
+
+    #[test]
+    fn untyped_downcast() {
+        let string = NSString::from_str("a");
+        let number: Arc<NSNumber> = 1i32.into();
+
+        let strings = NSArray::from_slice(&[&*string]);
+        let untyped = strings.as_untyped();
+        assert!(untyped.downcast::<NSString>().is_some());
+        assert_eq!(untyped.downcast::<NSString>().unwrap()[0], *string);
+
+        let objects: [&NSObject; 2] = [&string, &number];
+        let mixed = NSArray::from_slice(&objects);
+        assert!(mixed.downcast::<NSString>().is_none());
+        assert!(mixed.downcast::<NSObject>().is_some());
+        assert_eq!(mixed.to_vec().len(), 2);
+    }
+}
@@ -0,0 +1,307 @@
+use super::{NSDictionary, NSString, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, Class, ClassType, NSObject, Sel, BOOL};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+objc_subclass! {
+    /// A representation of the code and resources stored in a bundle
+    /// directory on disk.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle).
+    #[derive(PartialEq)]
+    pub class NSBundle: NSObject<'static>;
+}
+
+impl fmt::Debug for NSBundle {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSBundle")
+            .field(&self.bundle_path())
+            .finish()
+    }
+}
+
+impl NSBundle {
+    /// Returns the bundle that contains the current executable.
+    ///
+    /// For an executable that is not in a bundle, this is the directory
+    /// containing it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1410786-mainbundle).
+    #[inline]
+    #[doc(alias = "mainBundle")]
+    pub fn main() -> Arc<Self> {
+        let bundle = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), mainBundle]) };
+        bundle.expect("No main bundle")
+    }
+
+    /// Returns the bundle at the directory `path`, or `None` if it does not
+    /// exist.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1409352-initwithurl).
+    #[doc(alias = "bundleWithURL")]
+    #[doc(alias = "bundleWithURL:")]
+    #[doc(alias = "initWithURL:")]
+    pub fn from_path(path: &Path) -> Option<Arc<Self>> {
+        let url = NSURL::file_url(path, true);
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(obj: Arc<NSBundle>, sel: Sel, url: &NSURL) -> Option<Arc<NSBundle>>;
+        }
+
+        let sel = selector!(initWithURL:);
+        unsafe { objc_msgSend(value, sel, &url) }
+    }
+
+    /// Returns the bundle that defines `class`, or the main bundle if
+    /// `class` was not loaded from a bundle.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1417717-bundleforclass).
+    #[inline]
+    #[doc(alias = "bundleForClass")]
+    #[doc(alias = "bundleForClass:")]
+    pub fn for_class(class: &Class) -> Arc<Self> {
+        let bundle =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), bundleForClass: class]) };
+        bundle.expect("No bundle for class")
+    }
+
+    /// Returns the full path of the bundle directory.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1415654-bundleurl).
+    #[inline]
+    #[doc(alias = "bundleURL")]
+    #[doc(alias = "bundlePath")]
+    pub fn bundle_path(&self) -> PathBuf {
+        let url: Option<Arc<NSURL>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, bundleURL]) };
+        url.and_then(|url| url.to_path_buf())
+            .expect("Bundle has no path")
+    }
+
+    /// Returns the `CFBundleIdentifier` of the bundle, such as
+    /// `"com.apple.Foundation"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1418023-bundleidentifier).
+    #[inline]
+    #[doc(alias = "bundleIdentifier")]
+    pub fn bundle_identifier(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, bundleIdentifier]) }
+    }
+
+    /// Returns the contents of the bundle's `Info.plist` file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1413477-infodictionary).
+    #[inline]
+    #[doc(alias = "infoDictionary")]
+    pub fn info_dictionary(
+        &self,
+    ) -> Option<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, infoDictionary]) }
+    }
+
+    /// Returns the value for `key` in the bundle's `Info.plist` file,
+    /// preferring a localized value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1408696-objectforinfodictionarykey).
+    #[inline]
+    #[doc(alias = "objectForInfoDictionaryKey")]
+    #[doc(alias = "objectForInfoDictionaryKey:")]
+    pub fn object_for_info_key(&self, key: &str) -> Option<Arc<NSObject<'static>>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, objectForInfoDictionaryKey: &*key]) }
+    }
+}
+
+/// Resources.
+impl NSBundle {
+    /// Returns the URL of the resource `name` with extension `ext`, or `None`
+    /// if it does not exist.
+    ///
+    /// If `ext` is `None` or empty, `name` must include any extension.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1411540-urlforresource).
+    #[inline]
+    #[doc(alias = "URLForResource")]
+    #[doc(alias = "URLForResource:withExtension:")]
+    pub fn url_for_resource(&self, name: &str, ext: Option<&str>) -> Option<Arc<NSURL>> {
+        let name = NSString::from_str(name);
+        let ext = ext.map(NSString::from_str);
+        let ext = ext.as_deref();
+        unsafe {
+            retain_autoreleased(|| _msg_send_any![self, URLForResource: &*name withExtension: ext])
+        }
+    }
+
+    /// Returns the path of the resource `name` with extension `ext`, or
+    /// `None` if it does not exist.
+    ///
+    /// If `ext` is `None` or empty, `name` must include any extension.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1410989-pathforresource).
+    #[inline]
+    #[doc(alias = "pathForResource")]
+    #[doc(alias = "pathForResource:ofType:")]
+    pub fn path_for_resource(&self, name: &str, ext: Option<&str>) -> Option<PathBuf> {
+        self.url_for_resource(name, ext)?.to_path_buf()
+    }
+
+    /// Returns the localized string for `key` in `table`, which defaults to
+    /// `Localizable.strings`.
+    ///
+    /// If `key` is not found, this returns `value` if it is not empty, or
+    /// `key` otherwise.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1417694-localizedstringforkey).
+    #[inline]
+    #[doc(alias = "localizedStringForKey")]
+    #[doc(alias = "localizedStringForKey:value:table:")]
+    pub fn localized_string(
+        &self,
+        key: &str,
+        value: Option<&str>,
+        table: Option<&str>,
+    ) -> Arc<NSString<'static>> {
+        let key = NSString::from_str(key);
+        let value = value.map(NSString::from_str);
+        let table = table.map(NSString::from_str);
+        let (value, table) = (value.as_deref(), table.as_deref());
+
+        let string = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, localizedStringForKey: &*key value: value table: table],
+            )
+        };
+        string.expect("Bundle returned no localized string")
+    }
+}
+
+/// Loading code.
+impl NSBundle {
+    /// Dynamically loads the bundle's executable code, returning `true` if it
+    /// is loaded.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1415927-load).
+    #[inline]
+    pub fn load(&self) -> bool {
+        unsafe { _msg_send_any![self, load => BOOL] }.into()
+    }
+
+    /// Returns `true` if the bundle's executable code is loaded.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1411317-isloaded).
+    #[inline]
+    #[doc(alias = "isLoaded")]
+    pub fn is_loaded(&self) -> bool {
+        unsafe { _msg_send_any![self, isLoaded => BOOL] }.into()
+    }
+
+    /// Returns the bundle's principal class, loading the executable code if
+    /// needed.
+    ///
+    /// This is the `NSPrincipalClass` in `Info.plist`, or else the first
+    /// class in the executable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsbundle/1407299-principalclass).
+    #[inline]
+    #[doc(alias = "principalClass")]
+    pub fn principal_class(&self) -> Option<&'static Class> {
+        unsafe {
+            let class: *const Class = _msg_send_any![self, principalClass];
+            class.as_ref()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use std::{env, fs};
+
+    /// Creates a bundle with resources but no executable in `dir`.
+    fn fixture(dir: &TempDir) -> PathBuf {
+        let root = dir.0.join("Fixture.bundle");
+        let contents = root.join("Contents");
+        let lproj = contents.join("Resources/en.lproj");
+        fs::create_dir_all(&lproj).unwrap();
+
+        fs::write(
+            contents.join("Info.plist"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>org.example.fixture</string>
+    <key>CFBundleDevelopmentRegion</key>
+    <string>en</string>
+</dict>
+</plist>
+"#,
+        )
+        .unwrap();
+        fs::write(contents.join("Resources/hello.txt"), "hello").unwrap();
+        fs::write(
+            lproj.join("Localizable.strings"),
+            "\"greeting\" = \"Hello\";\n",
+        )
+        .unwrap();
+
+        root
+    }
+
+    #[test]
+    fn foundation() {
+        let bundle = NSBundle::for_class(NSObject::class());
+        assert_eq!(
+            *bundle.bundle_identifier().unwrap(),
+            *"com.apple.Foundation"
+        );
+
+        let info = bundle.info_dictionary().unwrap();
+        let key = NSString::from_str("CFBundleIdentifier");
+        assert!(info.get(&key).is_some());
+        assert!(bundle.object_for_info_key("CFBundleIdentifier").is_some());
+        assert!(bundle.object_for_info_key("NotAKey").is_none());
+        assert!(bundle.is_loaded());
+
+        let main = NSBundle::main().bundle_path().canonicalize().unwrap();
+        let exe = env::current_exe().unwrap().canonicalize().unwrap();
+        assert_eq!(main, exe.parent().unwrap());
+    }
+
+    #[test]
+    fn resources() {
+        let dir = TempDir::new("ns-bundle-resources");
+        let bundle = NSBundle::from_path(&fixture(&dir)).unwrap();
+        assert_eq!(*bundle.bundle_identifier().unwrap(), *"org.example.fixture");
+
+        let path = bundle.path_for_resource("hello", Some("txt")).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "hello");
+        assert!(bundle.path_for_resource("hello.txt", None).is_some());
+        assert!(bundle.url_for_resource("missing", Some("txt")).is_none());
+
+        assert_eq!(*bundle.localized_string("greeting", None, None), *"Hello");
+        assert_eq!(
+            *bundle.localized_string("missing", Some("Fallback"), None),
+            *"Fallback"
+        );
+        assert_eq!(*bundle.localized_string("missing", None, None), *"missing");
+
+        // The fixture has no executable.
+        assert!(!bundle.load());
+        assert!(bundle.principal_class().is_none());
+    }
+
+    #[test]
+    fn missing() {
+        let dir = TempDir::new("ns-bundle-missing");
+        assert!(NSBundle::from_path(&dir.0.join("Missing.bundle")).is_none());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::core::Arc;
+use crate::objc::{ClassType, NSInteger, NSIntegerMax, NSObject};
+use std::fmt;
+
+/// The value of a date component that has not been set.
+///
+/// [`NSDateComponents`](struct.NSDateComponents.html) getters return `None`
+/// instead of this value.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsdatecomponentundefined).
+#[allow(non_upper_case_globals)]
+pub const NSDateComponentUndefined: NSInteger = NSIntegerMax;
+
+objc_subclass! {
+    /// A date or time specified in terms of units (such as year, month, day,
+    /// hour, and minute) to be evaluated in a calendar system and time zone.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdatecomponents).
+    #[derive(PartialEq)]
+    pub class NSDateComponents: NSObject<'static>;
+}
+
+impl fmt::Debug for NSDateComponents {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NSDateComponents")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .field("weekday", &self.weekday())
+            .finish()
+    }
+}
+
+#[inline]
+fn defined(value: NSInteger) -> Option<NSInteger> {
+    if value == NSDateComponentUndefined {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+macro_rules! component {
+    (
+        $(#[$docs:meta])+
+        $get:ident, $set:ident, $setter:ident
+    ) => {
+        $(#[$docs])+
+        ///
+        /// Returns `None` if the component is not set.
+        #[inline]
+        pub fn $get(&self) -> Option<NSInteger> {
+            defined(unsafe { _msg_send_any![self, $get] })
+        }
+
+        $(#[$docs])+
+        ///
+        /// Passing `None` clears the component.
+        #[inline]
+        pub fn $set(&mut self, value: Option<NSInteger>) {
+            let value = value.unwrap_or(NSDateComponentUndefined);
+            unsafe { _msg_send_any![self, $setter: value] }
+        }
+    };
+}
+
+impl NSDateComponents {
+    /// Creates an instance with no components set.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    component! {
+        /// The number of years.
+        year, set_year, setYear
+    }
+
+    component! {
+        /// The number of months.
+        month, set_month, setMonth
+    }
+
+    component! {
+        /// The number of days.
+        day, set_day, setDay
+    }
+
+    component! {
+        /// The number of hours.
+        hour, set_hour, setHour
+    }
+
+    component! {
+        /// The number of minutes.
+        minute, set_minute, setMinute
+    }
+
+    component! {
+        /// The number of seconds.
+        second, set_second, setSecond
+    }
+
+    component! {
+        /// The day of the week, where 1 is the first day of the week in the
+        /// calendar (Sunday in the Gregorian calendar).
+        weekday, set_weekday, setWeekday
+    }
+}
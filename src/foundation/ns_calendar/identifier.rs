@@ -0,0 +1,84 @@
+ns_string_wrapper! {
+    /// The identifier of an [`NSCalendar`](struct.NSCalendar.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifier).
+    #[derive(Ord, PartialOrd, Eq, PartialEq)]
+    pub wrapper NSCalendarIdentifier;
+}
+
+macro_rules! identifier {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $value:literal
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        #[doc(alias = $value)]
+        pub fn $fn() -> &'static NSCalendarIdentifier {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static NSCalendarIdentifier;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+impl NSCalendarIdentifier {
+    identifier! {
+        /// The common calendar in Europe, the Western Hemisphere, and
+        /// elsewhere.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifiergregorian).
+        gregorian "NSCalendarIdentifierGregorian"
+    }
+
+    identifier! {
+        /// The ISO 8601 calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifieriso8601).
+        iso8601 "NSCalendarIdentifierISO8601"
+    }
+
+    identifier! {
+        /// The Buddhist calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierbuddhist).
+        buddhist "NSCalendarIdentifierBuddhist"
+    }
+
+    identifier! {
+        /// The Chinese calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierchinese).
+        chinese "NSCalendarIdentifierChinese"
+    }
+
+    identifier! {
+        /// The Hebrew calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierhebrew).
+        hebrew "NSCalendarIdentifierHebrew"
+    }
+
+    identifier! {
+        /// The Islamic calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierislamic).
+        islamic "NSCalendarIdentifierIslamic"
+    }
+
+    identifier! {
+        /// The Japanese calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierjapanese).
+        japanese "NSCalendarIdentifierJapanese"
+    }
+
+    identifier! {
+        /// The Persian calendar.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendaridentifierpersian).
+        persian "NSCalendarIdentifierPersian"
+    }
+}
@@ -0,0 +1,227 @@
+use super::{NSDate, NSTimeZone};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger, Sel};
+use std::fmt;
+
+mod date_components;
+mod identifier;
+mod unit;
+
+pub use date_components::*;
+pub use identifier::NSCalendarIdentifier;
+pub use unit::NSCalendarUnit;
+
+objc_subclass! {
+    /// A definition of the relationships between calendar units and absolute
+    /// points in time.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar).
+    #[derive(PartialEq)]
+    pub class NSCalendar: NSObject<'static>;
+}
+
+impl fmt::Debug for NSCalendar {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSCalendar")
+            .field(&self.identifier())
+            .finish()
+    }
+}
+
+impl NSCalendar {
+    /// Returns the logical calendar for the current user.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1413722-currentcalendar).
+    #[inline]
+    #[doc(alias = "currentCalendar")]
+    pub fn current() -> Arc<Self> {
+        let calendar =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), currentCalendar]) };
+        calendar.expect("No current calendar")
+    }
+
+    /// Creates a calendar for `identifier`, or returns `None` if the
+    /// identifier is unknown.
+    ///
+    /// The calendar uses the system time zone.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1413827-initwithcalendaridentifier).
+    #[doc(alias = "calendarWithIdentifier")]
+    #[doc(alias = "calendarWithIdentifier:")]
+    #[doc(alias = "initWithCalendarIdentifier:")]
+    pub fn from_identifier(identifier: &NSCalendarIdentifier) -> Option<Arc<Self>> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSCalendar>,
+                sel: Sel,
+                identifier: &NSCalendarIdentifier,
+            ) -> Option<Arc<NSCalendar>>;
+        }
+
+        let sel = selector!(initWithCalendarIdentifier:);
+        unsafe { objc_msgSend(value, sel, identifier) }
+    }
+
+    /// Returns the identifier of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1407114-calendaridentifier).
+    #[inline]
+    #[doc(alias = "calendarIdentifier")]
+    pub fn identifier(&self) -> Arc<NSCalendarIdentifier> {
+        let identifier =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, calendarIdentifier]) };
+        identifier.expect("Calendar has no identifier")
+    }
+
+    /// Returns the time zone used by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1411212-timezone).
+    #[inline]
+    #[doc(alias = "timeZone")]
+    pub fn time_zone(&self) -> Arc<NSTimeZone> {
+        let zone = unsafe { retain_autoreleased(|| _msg_send_any![self, timeZone]) };
+        zone.expect("Calendar has no time zone")
+    }
+
+    /// Sets the time zone used by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1411212-timezone).
+    #[inline]
+    #[doc(alias = "setTimeZone")]
+    #[doc(alias = "setTimeZone:")]
+    pub fn set_time_zone(&mut self, time_zone: &NSTimeZone) {
+        unsafe { _msg_send_any![self, setTimeZone: time_zone] }
+    }
+}
+
+/// Calendrical calculations.
+impl NSCalendar {
+    /// Returns the `units` components of `date` in the time zone of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1414841-components).
+    #[inline]
+    #[doc(alias = "components:fromDate:")]
+    pub fn components(&self, units: NSCalendarUnit, date: &NSDate) -> Arc<NSDateComponents> {
+        let components = unsafe {
+            retain_autoreleased(|| _msg_send_any![self, components: units fromDate: date])
+        };
+        components.expect("Calendar returned no components")
+    }
+
+    /// Returns the date that `components` specify in the time zone of `self`,
+    /// or `None` if they do not specify a valid date.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1414272-datefromcomponents).
+    #[inline]
+    #[doc(alias = "dateFromComponents")]
+    #[doc(alias = "dateFromComponents:")]
+    pub fn date_from_components(&self, components: &NSDateComponents) -> Option<Arc<NSDate>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, dateFromComponents: components]) }
+    }
+
+    /// Returns `date` moved by `components`, or `None` if the result cannot
+    /// be represented.
+    ///
+    /// Components are added in calendar terms, so adding one day preserves
+    /// the wall-clock time even across a daylight saving time transition.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscalendar/1411940-datebyaddingcomponents).
+    #[inline]
+    #[doc(alias = "dateByAddingComponents:toDate:options:")]
+    pub fn date_by_adding_components(
+        &self,
+        components: &NSDateComponents,
+        date: &NSDate,
+    ) -> Option<Arc<NSDate>> {
+        let options: NSUInteger = 0;
+        unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    self,
+                    dateByAddingComponents: components
+                    toDate: date
+                    options: options
+                ]
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_york() -> Arc<NSCalendar> {
+        let mut calendar = NSCalendar::from_identifier(NSCalendarIdentifier::gregorian()).unwrap();
+        let zone = NSTimeZone::from_name("America/New_York").unwrap();
+        Arc::get_mut(&mut calendar).unwrap().set_time_zone(&zone);
+        calendar
+    }
+
+    fn local_date(
+        calendar: &NSCalendar,
+        (year, month, day): (isize, isize, isize),
+        hour: isize,
+    ) -> Arc<NSDate> {
+        let mut components = NSDateComponents::new();
+        let c = Arc::get_mut(&mut components).unwrap();
+        c.set_year(Some(year));
+        c.set_month(Some(month));
+        c.set_day(Some(day));
+        c.set_hour(Some(hour));
+        calendar.date_from_components(&components).unwrap()
+    }
+
+    #[test]
+    fn components() {
+        let calendar = new_york();
+        assert_eq!(*calendar.identifier(), *NSCalendarIdentifier::gregorian());
+        assert_eq!(*calendar.time_zone().name(), *"America/New_York");
+        assert!(!NSCalendar::current().identifier().to_string().is_empty());
+
+        // 2021-01-01 00:00:00 UTC.
+        let date = NSDate::from_time_interval_since_1970(1_609_459_200.0);
+        let units = NSCalendarUnit::YEAR
+            | NSCalendarUnit::MONTH
+            | NSCalendarUnit::DAY
+            | NSCalendarUnit::HOUR
+            | NSCalendarUnit::WEEKDAY;
+        let components = calendar.components(units, &date);
+
+        assert_eq!(components.year(), Some(2020));
+        assert_eq!(components.month(), Some(12));
+        assert_eq!(components.day(), Some(31));
+        assert_eq!(components.hour(), Some(19));
+        assert_eq!(components.weekday(), Some(5)); // Thursday
+        assert_eq!(components.minute(), None);
+
+        let round_trip = calendar.date_from_components(&components).unwrap();
+        assert_eq!(round_trip.time_interval_since(&date), 0.0);
+    }
+
+    #[test]
+    fn same_time_tomorrow_across_dst() {
+        let calendar = new_york();
+
+        // Clocks skip from 02:00 to 03:00 on 2021-03-14.
+        let today = local_date(&calendar, (2021, 3, 13), 12);
+
+        let mut one_day = NSDateComponents::new();
+        Arc::get_mut(&mut one_day).unwrap().set_day(Some(1));
+        let tomorrow = calendar
+            .date_by_adding_components(&one_day, &today)
+            .unwrap();
+
+        let units = NSCalendarUnit::DAY | NSCalendarUnit::HOUR;
+        let components = calendar.components(units, &tomorrow);
+        assert_eq!(components.day(), Some(14));
+        assert_eq!(components.hour(), Some(12));
+
+        // The calendar day is only 23 hours long.
+        assert_eq!(tomorrow.time_interval_since(&today), 23.0 * 3600.0);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::objc::NSUInteger;
+use std::ops;
+
+/// Calendar units such as year, month, and day, used to select which
+/// [`NSDateComponents`](struct.NSDateComponents.html) fields are computed.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nscalendarunit).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSCalendarUnit(pub NSUInteger);
+
+impl NSCalendarUnit {
+    /// No units.
+    pub const NONE: Self = Self(0);
+
+    /// The era.
+    pub const ERA: Self = Self(1 << 1);
+
+    /// The year.
+    pub const YEAR: Self = Self(1 << 2);
+
+    /// The month.
+    pub const MONTH: Self = Self(1 << 3);
+
+    /// The day of the month.
+    pub const DAY: Self = Self(1 << 4);
+
+    /// The hour.
+    pub const HOUR: Self = Self(1 << 5);
+
+    /// The minute.
+    pub const MINUTE: Self = Self(1 << 6);
+
+    /// The second.
+    pub const SECOND: Self = Self(1 << 7);
+
+    /// The day of the week.
+    pub const WEEKDAY: Self = Self(1 << 9);
+
+    /// The ordinal of the weekday within the month.
+    pub const WEEKDAY_ORDINAL: Self = Self(1 << 10);
+
+    /// The quarter.
+    pub const QUARTER: Self = Self(1 << 11);
+
+    /// The week of the month.
+    pub const WEEK_OF_MONTH: Self = Self(1 << 12);
+
+    /// The week of the year.
+    pub const WEEK_OF_YEAR: Self = Self(1 << 13);
+
+    /// The year that the week of the year belongs to.
+    pub const YEAR_FOR_WEEK_OF_YEAR: Self = Self(1 << 14);
+
+    /// The nanosecond.
+    pub const NANOSECOND: Self = Self(1 << 15);
+
+    /// The calendar.
+    pub const CALENDAR: Self = Self(1 << 20);
+
+    /// The time zone.
+    pub const TIME_ZONE: Self = Self(1 << 21);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSCalendarUnit {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSCalendarUnit {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSCalendarUnit {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
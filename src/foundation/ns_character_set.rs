@@ -0,0 +1,183 @@
+use super::NSString;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, BOOL};
+
+objc_subclass! {
+    /// An immutable set of Unicode characters, used in search operations.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset).
+    #[derive(PartialEq)]
+    pub class NSCharacterSet: NSObject<'static>;
+}
+
+macro_rules! predefined {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $sel:ident
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        pub fn $fn() -> Arc<Self> {
+            let set = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), $sel]) };
+            set.expect("No predefined character set")
+        }
+    };
+}
+
+/// Predefined sets.
+impl NSCharacterSet {
+    predefined! {
+        /// Returns a set of Unicode general category Z* characters and tab.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1413366-whitespacecharacterset).
+        whitespace whitespaceCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Z* characters, U+000A
+        /// through U+000D, and U+0085.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1412030-whitespaceandnewlinecharacterset).
+        whitespace_and_newline whitespaceAndNewlineCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of U+000A through U+000D, U+0085, U+2028, and U+2029.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1409080-newlinecharacterset).
+        newline newlineCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Nd characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1408102-decimaldigitcharacterset).
+        decimal_digit decimalDigitCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category L* and M* characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1411726-lettercharacterset).
+        letter letterCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Lu and Lt characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1417185-uppercaselettercharacterset).
+        uppercase_letter uppercaseLetterCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Ll characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1408141-lowercaselettercharacterset).
+        lowercase_letter lowercaseLetterCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category L*, M*, and N*
+        /// characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1416155-alphanumericcharacterset).
+        alphanumeric alphanumericCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category P* characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1416583-punctuationcharacterset).
+        punctuation punctuationCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category S* characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1409958-symbolcharacterset).
+        symbol symbolCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Cc and Cf characters.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1417038-controlcharacterset).
+        control controlCharacterSet
+    }
+
+    predefined! {
+        /// Returns a set of Unicode general category Cn characters, which are
+        /// not assigned a meaning.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1415012-illegalcharacterset).
+        illegal illegalCharacterSet
+    }
+}
+
+impl NSCharacterSet {
+    /// Returns a set of the characters in `string`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1497278-charactersetwithcharactersinstri).
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "characterSetWithCharactersInString")]
+    #[doc(alias = "characterSetWithCharactersInString:")]
+    pub fn from_str(string: &str) -> Arc<Self> {
+        let string = NSString::from_str(string);
+        let set = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), characterSetWithCharactersInString: &*string],
+            )
+        };
+        set.expect("Could not create character set")
+    }
+
+    /// Returns a set of the characters not in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1417716-invertedset).
+    #[inline]
+    #[doc(alias = "invertedSet")]
+    pub fn inverted(&self) -> Arc<Self> {
+        let set = unsafe { retain_autoreleased(|| _msg_send_any![self, invertedSet]) };
+        set.expect("Could not invert character set")
+    }
+
+    /// Returns `true` if `self` contains `c`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscharacterset/1410619-longcharacterismember).
+    #[inline]
+    #[doc(alias = "longCharacterIsMember")]
+    #[doc(alias = "longCharacterIsMember:")]
+    pub fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        unsafe { _msg_send_any![self, longCharacterIsMember: c => BOOL] }.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predefined() {
+        assert!(NSCharacterSet::whitespace().contains(' '));
+        assert!(!NSCharacterSet::whitespace().contains('\n'));
+        assert!(NSCharacterSet::whitespace_and_newline().contains('\n'));
+        assert!(NSCharacterSet::decimal_digit().contains('٣'));
+        assert!(NSCharacterSet::letter().contains('é'));
+        assert!(!NSCharacterSet::letter().contains('1'));
+        assert!(NSCharacterSet::symbol().contains('😀'));
+    }
+
+    #[test]
+    fn from_str() {
+        let set = NSCharacterSet::from_str("=;😀");
+        assert!(set.contains('='));
+        assert!(set.contains('😀'));
+        assert!(!set.contains('a'));
+
+        let inverted = set.inverted();
+        assert!(!inverted.contains('='));
+        assert!(inverted.contains('a'));
+    }
+}
@@ -0,0 +1,378 @@
+use super::NSRange;
+use crate::core::Arc;
+use crate::objc::{ClassType, NSObject, NSUInteger, RcBlock, Sel};
+use std::{cell::Cell, ffi::c_void, fmt, slice};
+
+objc_subclass! {
+    /// A static byte buffer in memory.
+    ///
+    /// Unlike `CFData`, this does not dereference to `[u8]`, since it
+    /// dereferences to `NSObject` like other class types, and
+    /// [`NSMutableData`](struct.NSMutableData.html) dereferences to it. Its
+    /// bytes are reached with [`as_slice`](#method.as_slice) or [`AsRef`].
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdata).
+    pub class NSData: NSObject<'static>;
+}
+
+impl AsRef<[u8]> for NSData {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for NSData {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl NSData {
+    /// Creates a data object containing a copy of `bytes`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdata/1412793-initwithbytes).
+    #[doc(alias = "dataWithBytes")]
+    #[doc(alias = "dataWithBytes:length:")]
+    #[doc(alias = "initWithBytes:length:")]
+    pub fn from_slice(bytes: &[u8]) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                bytes: *const c_void,
+                length: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithBytes:length:);
+        let ptr = bytes.as_ptr().cast();
+        unsafe {
+            Arc::cast_unchecked(objc_msgSend(
+                Arc::cast_unchecked(value),
+                sel,
+                ptr,
+                bytes.len(),
+            ))
+        }
+    }
+
+    /// Creates a data object that takes ownership of `bytes` without copying.
+    ///
+    /// The vector is dropped when the data object is deallocated.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdata/2090043-initwithbytesnocopy).
+    #[inline]
+    #[doc(alias = "initWithBytesNoCopy")]
+    #[doc(alias = "initWithBytesNoCopy:length:deallocator:")]
+    pub fn from_vec(bytes: Vec<u8>) -> Arc<Self> {
+        // SAFETY: Moving a `Vec` does not move its buffer.
+        unsafe { Self::from_owner(bytes) }
+    }
+
+    /// Creates a data object over the bytes of `owner` without copying, and
+    /// drops `owner` when the data object is deallocated.
+    ///
+    /// # Safety
+    ///
+    /// The bytes referenced by `owner` must not move when `owner` is moved.
+    unsafe fn from_owner<T>(owner: T) -> Arc<Self>
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        let value: Arc<Self> = Self::class().alloc();
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                bytes: *mut c_void,
+                length: NSUInteger,
+                deallocator: *const c_void,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let bytes = owner.as_ref();
+        let ptr = bytes.as_ptr() as *mut c_void;
+        let len = bytes.len();
+
+        // The data object releases the block when deallocated, which drops
+        // `owner` even if the deallocator is never called.
+        let owner = Cell::new(Some(owner));
+        let deallocator = RcBlock::<(*mut c_void, NSUInteger), _>::without_signature(
+            move |_: *mut c_void, _: NSUInteger| drop(owner.take()),
+        );
+
+        let sel = selector!(initWithBytesNoCopy:length:deallocator:);
+        let data = objc_msgSend(
+            Arc::cast_unchecked(value),
+            sel,
+            ptr,
+            len,
+            deallocator.as_ptr(),
+        );
+        Arc::cast_unchecked(data)
+    }
+
+    /// Returns the number of bytes in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdata/1416769-length).
+    #[inline]
+    #[doc(alias = "length")]
+    pub fn len(&self) -> usize {
+        unsafe { _msg_send_any![self, length] }
+    }
+
+    /// Returns `true` if `self` contains no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the bytes of `self`, which may be null if empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdata/1410616-bytes).
+    #[inline]
+    #[doc(alias = "bytes")]
+    pub fn as_ptr(&self) -> *const u8 {
+        unsafe { _msg_send_any![self, bytes] }
+    }
+
+    /// Returns the bytes of `self` without copying.
+    #[inline]
+    #[doc(alias = "bytes")]
+    pub fn as_slice(&self) -> &[u8] {
+        let len = self.len();
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.as_ptr(), len) }
+    }
+
+    /// Returns a copy of the bytes of `self`.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+}
+
+objc_subclass! {
+    /// A dynamic byte buffer in memory.
+    ///
+    /// Like [`NSData`](struct.NSData.html), this does not dereference to its
+    /// bytes. They are mutated with [`as_mut_slice`](#method.as_mut_slice) or
+    /// [`AsMut`].
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata).
+    pub class NSMutableData: NSData;
+}
+
+impl AsRef<[u8]> for NSMutableData {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsMut<[u8]> for NSMutableData {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl fmt::Debug for NSMutableData {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &NSData).fmt(f)
+    }
+}
+
+impl NSMutableData {
+    /// Creates an empty mutable data object.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates an empty mutable data object with initial storage for
+    /// `capacity` bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata/1410162-initwithcapacity).
+    #[doc(alias = "initWithCapacity")]
+    #[doc(alias = "dataWithCapacity")]
+    pub fn with_capacity(capacity: usize) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                capacity: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithCapacity:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, capacity)) }
+    }
+
+    /// Returns the bytes of `self` for mutation without copying.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata/1414659-mutablebytes).
+    #[inline]
+    #[doc(alias = "mutableBytes")]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        let len = self.len();
+        if len == 0 {
+            return &mut [];
+        }
+        unsafe {
+            let ptr: *mut u8 = _msg_send_any![self, mutableBytes];
+            slice::from_raw_parts_mut(ptr, len)
+        }
+    }
+
+    /// Appends a copy of `bytes` to the end of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata/1407704-appendbytes).
+    #[inline]
+    #[doc(alias = "appendBytes")]
+    #[doc(alias = "appendBytes:length:")]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let ptr: *const c_void = bytes.as_ptr().cast();
+        unsafe {
+            _msg_send_any![
+                self,
+                appendBytes: ptr
+                length: bytes.len()
+            ]
+        }
+    }
+
+    /// Sets the number of bytes in `self`, filling any added bytes with zeros.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata/1408155-length).
+    #[inline]
+    #[doc(alias = "setLength")]
+    #[doc(alias = "setLength:")]
+    pub fn set_len(&mut self, len: usize) {
+        unsafe { _msg_send_any![self, setLength: len] }
+    }
+
+    /// Sets the bytes in `range` to zero.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledata/1415532-resetbytesinrange).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    #[inline]
+    #[doc(alias = "resetBytesInRange")]
+    #[doc(alias = "resetBytesInRange:")]
+    pub fn reset_range(&mut self, range: impl Into<NSRange>) {
+        let range = range.into();
+        let len = self.len();
+        assert!(
+            range.location <= len && range.length <= len - range.location,
+            "Range {}..{} is out of bounds for length {}",
+            range.location,
+            range.location.saturating_add(range.length),
+            len
+        );
+        unsafe { _msg_send_any![self, resetBytesInRange: range] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc as StdArc,
+    };
+
+    #[test]
+    fn from_slice() {
+        let data = NSData::from_slice(b"hello");
+        assert_eq!(data.len(), 5);
+        assert_eq!(data.as_slice(), b"hello");
+        assert_eq!(data.to_vec(), b"hello".to_vec());
+
+        let empty = NSData::from_slice(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_slice(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn from_vec_no_copy() {
+        let bytes = vec![1, 2, 3, 4];
+        let ptr = bytes.as_ptr();
+
+        let data = NSData::from_vec(bytes);
+        assert_eq!(data.as_ptr(), ptr);
+        assert_eq!(data.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_vec_frees() {
+        struct Tracked(Vec<u8>, StdArc<AtomicBool>);
+
+        impl AsRef<[u8]> for Tracked {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.1.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = StdArc::new(AtomicBool::new(false));
+        let data = unsafe { NSData::from_owner(Tracked(vec![0; 1 << 20], dropped.clone())) };
+        let copy = data.clone();
+
+        drop(data);
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        assert_eq!(copy.len(), 1 << 20);
+        drop(copy);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn mutable() {
+        let mut data = NSMutableData::with_capacity(8);
+        let data = Arc::get_mut(&mut data).unwrap();
+
+        data.extend_from_slice(b"abc");
+        data.extend_from_slice(b"def");
+        assert_eq!(data.as_slice(), b"abcdef");
+
+        data.as_mut_slice()[0] = b'A';
+        data.reset_range(1..3);
+        assert_eq!(data.as_slice(), b"A\0\0def");
+
+        data.set_len(8);
+        assert_eq!(data.as_slice(), b"A\0\0def\0\0");
+
+        data.set_len(1);
+        assert_eq!(data.as_slice(), b"A");
+    }
+
+    #[test]
+    #[should_panic]
+    fn reset_out_of_bounds() {
+        let mut data = NSMutableData::new();
+        let data = Arc::get_mut(&mut data).unwrap();
+        data.extend_from_slice(b"abc");
+        data.reset_range(2..4);
+    }
+}
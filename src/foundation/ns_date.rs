@@ -0,0 +1,278 @@
+use super::{NSComparisonResult, NSTimeInterval};
+use crate::core::Arc;
+use crate::objc::{ClassType, NSObject, Sel, BOOL};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    ops::{Add, Sub},
+    time::{Duration, SystemTime},
+};
+
+objc_subclass! {
+    /// A representation of a specific point in time, independent of any
+    /// calendar or time zone.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate).
+    pub class NSDate: NSObject<'static>;
+}
+
+impl PartialEq for NSDate {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { _msg_send_any_cached![self, isEqualToDate: other => BOOL] }.into()
+    }
+}
+
+impl Eq for NSDate {}
+
+impl PartialOrd for NSDate {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NSDate {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other).into()
+    }
+}
+
+impl From<SystemTime> for Arc<NSDate> {
+    #[inline]
+    fn from(time: SystemTime) -> Self {
+        let interval = match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64(),
+            Err(error) => -error.duration().as_secs_f64(),
+        };
+        NSDate::from_time_interval_since_1970(interval)
+    }
+}
+
+impl TryFrom<&NSDate> for SystemTime {
+    type Error = SystemTimeRangeError;
+
+    #[inline]
+    fn try_from(date: &NSDate) -> Result<Self, Self::Error> {
+        let interval = date.time_interval_since_1970();
+
+        // `Duration::from_secs_f64` panics if the value does not fit.
+        if !interval.is_finite() || interval.abs() >= u64::MAX as f64 {
+            return Err(SystemTimeRangeError(()));
+        }
+
+        let duration = Duration::from_secs_f64(interval.abs());
+        let time = if interval < 0.0 {
+            SystemTime::UNIX_EPOCH.checked_sub(duration)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_add(duration)
+        };
+        time.ok_or(SystemTimeRangeError(()))
+    }
+}
+
+impl Add<Duration> for &NSDate {
+    type Output = Arc<NSDate>;
+
+    #[inline]
+    fn add(self, duration: Duration) -> Self::Output {
+        NSDate::from_time_interval_since(duration.as_secs_f64(), self)
+    }
+}
+
+impl Sub<Duration> for &NSDate {
+    type Output = Arc<NSDate>;
+
+    #[inline]
+    fn sub(self, duration: Duration) -> Self::Output {
+        NSDate::from_time_interval_since(-duration.as_secs_f64(), self)
+    }
+}
+
+impl NSDate {
+    /// Returns a date object set to the current date and time.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1409876-init).
+    #[inline]
+    #[doc(alias = "date")]
+    #[doc(alias = "init")]
+    pub fn now() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Returns a date object representing a date in the distant past.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1409449-distantpast).
+    #[inline]
+    #[doc(alias = "distantPast")]
+    pub fn distant_past() -> Arc<Self> {
+        crate::objc::autoreleasepool(|_| unsafe {
+            let date: *const Self = _msg_send_any![Self::class(), distantPast];
+            Arc::retain(&*date)
+        })
+    }
+
+    /// Returns a date object representing a date in the distant future.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1415385-distantfuture).
+    #[inline]
+    #[doc(alias = "distantFuture")]
+    pub fn distant_future() -> Arc<Self> {
+        crate::objc::autoreleasepool(|_| unsafe {
+            let date: *const Self = _msg_send_any![Self::class(), distantFuture];
+            Arc::retain(&*date)
+        })
+    }
+
+    /// Returns a date object set to the given number of seconds from 00:00:00
+    /// UTC on 1 January 1970.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1408795-initwithtimeintervalsince1970).
+    #[doc(alias = "dateWithTimeIntervalSince1970")]
+    #[doc(alias = "dateWithTimeIntervalSince1970:")]
+    #[doc(alias = "initWithTimeIntervalSince1970:")]
+    pub fn from_time_interval_since_1970(interval: NSTimeInterval) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                interval: NSTimeInterval,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithTimeIntervalSince1970:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, interval)) }
+    }
+
+    /// Returns a date object set to the given number of seconds from `date`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1417167-initwithtimeinterval).
+    #[doc(alias = "dateByAddingTimeInterval")]
+    #[doc(alias = "dateByAddingTimeInterval:")]
+    #[doc(alias = "initWithTimeInterval:sinceDate:")]
+    pub fn from_time_interval_since(interval: NSTimeInterval, date: &NSDate) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                interval: NSTimeInterval,
+                date: &NSDate,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithTimeInterval:sinceDate:);
+        unsafe {
+            Arc::cast_unchecked(objc_msgSend(
+                Arc::cast_unchecked(value),
+                sel,
+                interval,
+                date,
+            ))
+        }
+    }
+
+    /// Returns the number of seconds between `self` and 00:00:00 UTC on
+    /// 1 January 1970.
+    ///
+    /// This is negative if `self` is earlier.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1407504-timeintervalsince1970).
+    #[inline]
+    #[doc(alias = "timeIntervalSince1970")]
+    pub fn time_interval_since_1970(&self) -> NSTimeInterval {
+        unsafe { _msg_send_any![self, timeIntervalSince1970] }
+    }
+
+    /// Returns the number of seconds between `self` and `other`.
+    ///
+    /// This is negative if `self` is earlier than `other`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1413501-timeintervalsincedate).
+    #[inline]
+    #[doc(alias = "timeIntervalSinceDate")]
+    #[doc(alias = "timeIntervalSinceDate:")]
+    pub fn time_interval_since(&self, other: &NSDate) -> NSTimeInterval {
+        unsafe { _msg_send_any![self, timeIntervalSinceDate: other] }
+    }
+
+    /// Indicates the temporal ordering of `self` relative to `other`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdate/1414201-compare).
+    #[inline]
+    pub fn compare(&self, other: &NSDate) -> NSComparisonResult {
+        unsafe { _msg_send_any![self, compare: other] }
+    }
+}
+
+/// The error returned when converting an [`NSDate`](struct.NSDate.html) to a
+/// [`SystemTime`](https://doc.rust-lang.org/std/time/struct.SystemTime.html)
+/// that cannot represent it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SystemTimeRangeError(());
+
+impl fmt::Display for SystemTimeRangeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("date is out of range for `SystemTime`")
+    }
+}
+
+impl Error for SystemTimeRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_epoch() {
+        let date: Arc<NSDate> = SystemTime::UNIX_EPOCH.into();
+        assert_eq!(date.time_interval_since_1970(), 0.0);
+        assert_eq!(SystemTime::try_from(&*date), Ok(SystemTime::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn pre_1970() {
+        // 1905-01-01 00:00:00 UTC.
+        let time = SystemTime::UNIX_EPOCH - Duration::from_secs(2_051_222_400);
+
+        let date: Arc<NSDate> = time.into();
+        assert_eq!(date.time_interval_since_1970(), -2_051_222_400.0);
+        assert_eq!(SystemTime::try_from(&*date), Ok(time));
+    }
+
+    #[test]
+    fn ordering() {
+        let a = NSDate::now();
+        let b = &*a + Duration::from_micros(1);
+        let c = &*b + Duration::from_micros(1);
+
+        assert!(*a < *b);
+        assert!(*b < *c);
+        assert_eq!(c.compare(&a), NSComparisonResult::OrderedDescending);
+
+        let delta = c.time_interval_since(&a);
+        assert!((delta - 2e-6).abs() < 1e-7, "{}", delta);
+
+        let a_again = &*c - Duration::from_micros(2);
+        assert!(a_again.time_interval_since(&a).abs() < 1e-7);
+    }
+
+    #[test]
+    fn distant() {
+        let past = NSDate::distant_past();
+        let future = NSDate::distant_future();
+        let now = NSDate::now();
+
+        assert!(*past < *now);
+        assert!(*now < *future);
+    }
+}
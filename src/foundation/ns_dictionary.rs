@@ -0,0 +1,479 @@
+use super::{NSArray, NSEnumerator};
+use crate::core::{Arc, ObjectType};
+use crate::objc::{
+    autoreleasepool, retain_autoreleased, ClassType, DowncastTarget, NSFastEnumeration, NSObject,
+    NSUInteger, Sel,
+};
+use std::{fmt, marker::PhantomData, ptr, vec};
+
+objc_subclass! {
+    /// A static collection mapping keys of type `K` to values of type `V`.
+    ///
+    /// Keys are copied using
+    /// [`NSCopying`](https://developer.apple.com/documentation/foundation/nscopying)
+    /// when inserted, so `K` should be an immutable type like
+    /// [`NSString`](struct.NSString.html).
+    ///
+    /// The `NSDictionary<K, NSObject>` form is common for dictionaries with
+    /// heterogeneous values, such as
+    /// [`NSError`](struct.NSError.html) user info. Its values can be checked
+    /// against a specific type with [`get_as`](#method.get_as).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary).
+    #[derive(PartialEq)]
+    pub class NSDictionary<K, V>: NSObject<'static>;
+}
+
+downcast_target!(NSDictionary<NSObject<'static>, NSObject<'static>>);
+
+impl<K, V> fmt::Debug for NSDictionary<K, V>
+where
+    K: ObjectType + fmt::Debug + 'static,
+    V: ObjectType + fmt::Debug + 'static,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Enumerates the keys of the dictionary.
+unsafe impl<K, V> NSFastEnumeration<'static> for NSDictionary<K, V>
+where
+    K: ObjectType + 'static,
+    V: ObjectType + 'static,
+{
+    type Item = K;
+}
+
+impl<'a, K, V> IntoIterator for &'a NSDictionary<K, V>
+where
+    K: ObjectType + 'static,
+    V: ObjectType + 'static,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = NSDictionaryIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: ObjectType + 'static, V: ObjectType + 'static> NSDictionary<K, V> {
+    /// Creates a dictionary containing copied keys and retained values from
+    /// `pairs`.
+    ///
+    /// This is equivalent to `dictionaryWithObjects:forKeys:count:`, but the
+    /// result is not autoreleased. If a key appears more than once, the last
+    /// value is used.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/1410396-initwithobjects).
+    #[doc(alias = "dictionaryWithObjects")]
+    #[doc(alias = "dictionaryWithObjects:forKeys:count:")]
+    #[doc(alias = "initWithObjects:forKeys:count:")]
+    pub fn from_pairs(pairs: &[(&K, &V)]) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                objects: *const *const (),
+                keys: *const *const (),
+                count: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let keys: Vec<*const K> = pairs.iter().map(|&(k, _)| k as *const K).collect();
+        let values: Vec<*const V> = pairs.iter().map(|&(_, v)| v as *const V).collect();
+
+        let sel = selector!(initWithObjects:forKeys:count:);
+        unsafe {
+            let obj = Arc::cast_unchecked(value);
+            let (values, keys) = (values.as_ptr().cast(), keys.as_ptr().cast());
+            let dict = objc_msgSend(obj, sel, values, keys, pairs.len());
+            Arc::cast_unchecked(dict)
+        }
+    }
+
+    /// Returns the number of key-value pairs in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/1409632-count).
+    #[inline]
+    #[doc(alias = "count")]
+    pub fn len(&self) -> usize {
+        unsafe { _msg_send_any![self, count] }
+    }
+
+    /// Returns `true` if `self` contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value for `key`, or `None` if there is none.
+    ///
+    /// Keys are compared using `isEqual:`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/1414347-objectforkey).
+    #[inline]
+    #[doc(alias = "objectForKey")]
+    #[doc(alias = "objectForKey:")]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let key = key as *const K;
+        unsafe {
+            let value: *const V = _msg_send_any![self, objectForKey: key];
+            value.as_ref()
+        }
+    }
+
+    /// Returns `true` if `self` contains a value for `key`.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the key-value pairs in `self`.
+    ///
+    /// All pairs are retrieved at once using
+    /// [`getObjects:andKeys:count:`](https://developer.apple.com/documentation/foundation/nsdictionary/1415262-getobjects).
+    /// The order of pairs is unspecified.
+    #[doc(alias = "getObjects")]
+    #[doc(alias = "getObjects:andKeys:count:")]
+    pub fn iter(&self) -> NSDictionaryIter<'_, K, V> {
+        let len = self.len();
+        let mut keys: Vec<*const K> = vec![ptr::null(); len];
+        let mut values: Vec<*const V> = vec![ptr::null(); len];
+
+        if len != 0 {
+            let (k, v) = (keys.as_mut_ptr(), values.as_mut_ptr());
+            unsafe { _msg_send_any![self, getObjects: v andKeys: k count: len => ()] };
+        }
+
+        NSDictionaryIter {
+            keys: keys.into_iter(),
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an enumerator over the keys of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/keyenumerator).
+    #[inline]
+    #[doc(alias = "keyEnumerator")]
+    pub fn key_enumerator(&self) -> Arc<NSEnumerator<K>> {
+        let enumerator = unsafe { retain_autoreleased(|| _msg_send_any![self, keyEnumerator]) };
+        enumerator.expect("Failed to create enumerator")
+    }
+
+    /// Returns an enumerator over the values of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/objectenumerator).
+    #[inline]
+    #[doc(alias = "objectEnumerator")]
+    pub fn object_enumerator(&self) -> Arc<NSEnumerator<V>> {
+        let enumerator = unsafe { retain_autoreleased(|| _msg_send_any![self, objectEnumerator]) };
+        enumerator.expect("Failed to create enumerator")
+    }
+
+    /// Returns a new array containing the keys of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/1408663-allkeys).
+    #[inline]
+    #[doc(alias = "allKeys")]
+    pub fn keys(&self) -> Arc<NSArray<K>> {
+        autoreleasepool(|_| unsafe {
+            let keys: *const NSArray<K> = _msg_send_any![self, allKeys];
+            Arc::retain(&*keys)
+        })
+    }
+
+    /// Returns a new array containing the values of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdictionary/1408915-allvalues).
+    #[inline]
+    #[doc(alias = "allValues")]
+    pub fn values(&self) -> Arc<NSArray<V>> {
+        autoreleasepool(|_| unsafe {
+            let values: *const NSArray<V> = _msg_send_any![self, allValues];
+            Arc::retain(&*values)
+        })
+    }
+
+    /// Returns `self` as a dictionary of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &NSDictionary<NSObject<'static>, NSObject<'static>> {
+        // SAFETY: Foundation dictionaries can only contain `NSObject` instances.
+        unsafe { &*(self as *const Self as *const _) }
+    }
+}
+
+impl<K: ObjectType + 'static> NSDictionary<K, NSObject<'static>> {
+    /// Returns the value for `key`, or `None` if there is none or it is
+    /// [`NSNull`](struct.NSNull.html).
+    #[inline]
+    pub fn get_non_null(&self, key: &K) -> Option<&NSObject<'static>> {
+        self.get(key)?.non_null()
+    }
+
+    /// Returns the value for `key` if it is an instance of `V` or of a
+    /// subclass of `V`.
+    ///
+    /// This checks the value using
+    /// [`NSObject::is_kind_of_class`](../objc/struct.NSObject.html#method.is_kind_of_class).
+    #[inline]
+    pub fn get_as<V: DowncastTarget<'static>>(&self, key: &K) -> Option<&V> {
+        let value = self.get(key)?;
+        if value.is_kind_of_class(<V as ClassType>::class()) {
+            Some(unsafe { &*(value as *const NSObject as *const V) })
+        } else {
+            None
+        }
+    }
+}
+
+impl NSDictionary<NSObject<'static>, NSObject<'static>> {
+    /// Casts `self` to a dictionary with keys of type `K` and values of type
+    /// `V`.
+    ///
+    /// # Safety
+    ///
+    /// All keys and values of `self` must be instances of `K` and `V`
+    /// respectively.
+    #[inline]
+    pub unsafe fn cast_unchecked<K, V>(&self) -> &NSDictionary<K, V>
+    where
+        K: ObjectType + 'static,
+        V: ObjectType + 'static,
+    {
+        &*(self as *const Self as *const NSDictionary<K, V>)
+    }
+}
+
+/// An iterator over the key-value pairs of an [`NSDictionary`].
+///
+/// This is created by [`NSDictionary::iter`].
+pub struct NSDictionaryIter<'a, K, V> {
+    keys: vec::IntoIter<*const K>,
+    values: vec::IntoIter<*const V>,
+    marker: PhantomData<&'a NSDictionary<K, V>>,
+}
+
+impl<'a, K: ObjectType + 'static, V: ObjectType + 'static> Iterator for NSDictionaryIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+        let value = self.values.next()?;
+
+        // SAFETY: The dictionary retains its entries for at least `'a`.
+        unsafe { Some((&*key, &*value)) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<K: ObjectType + 'static, V: ObjectType + 'static> ExactSizeIterator
+    for NSDictionaryIter<'_, K, V>
+{
+}
+
+objc_subclass! {
+    /// A dynamic collection mapping keys of type `K` to values of type `V`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a uniquely
+    /// referenced dictionary via [`Arc::get_mut`].
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledictionary).
+    #[derive(PartialEq)]
+    pub class NSMutableDictionary<K, V>: NSDictionary<K, V>;
+}
+
+downcast_target!(NSMutableDictionary<NSObject<'static>, NSObject<'static>>);
+
+impl<K, V> fmt::Debug for NSMutableDictionary<K, V>
+where
+    K: ObjectType + fmt::Debug + 'static,
+    V: ObjectType + fmt::Debug + 'static,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &NSDictionary<K, V>).fmt(f)
+    }
+}
+
+impl<K: ObjectType + 'static, V: ObjectType + 'static> NSMutableDictionary<K, V> {
+    /// Creates an empty mutable dictionary.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates an empty mutable dictionary with initial storage for
+    /// `capacity` key-value pairs.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledictionary/1416478-initwithcapacity).
+    #[inline]
+    #[doc(alias = "initWithCapacity")]
+    #[doc(alias = "dictionaryWithCapacity")]
+    pub fn with_capacity(capacity: usize) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                capacity: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithCapacity:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, capacity)) }
+    }
+
+    /// Sets the value for a copy of `key`, replacing any existing value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledictionary/1411616-setobject).
+    #[inline]
+    #[doc(alias = "setObject")]
+    #[doc(alias = "setObject:forKey:")]
+    pub fn insert(&mut self, key: &K, value: &V) {
+        let (key, value) = (key as *const K, value as *const V);
+        unsafe { _msg_send_any![self, setObject: value forKey: key] }
+    }
+
+    /// Removes the value for `key`, returning it if there was one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledictionary/1416518-removeobjectforkey).
+    #[inline]
+    #[doc(alias = "removeObjectForKey")]
+    #[doc(alias = "removeObjectForKey:")]
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        let value = Arc::retain(self.get(key)?);
+        let key = key as *const K;
+        unsafe { _msg_send_any![self, removeObjectForKey: key] }
+        Some(value)
+    }
+
+    /// Removes all key-value pairs from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutabledictionary/1416320-removeallobjects).
+    #[inline]
+    #[doc(alias = "removeAllObjects")]
+    pub fn clear(&mut self) {
+        unsafe { _msg_send_any![self, removeAllObjects] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSNull, NSNumber, NSString};
+
+    #[test]
+    fn from_pairs() {
+        let (a, b, c) = (
+            NSString::from_str("a"),
+            NSString::from_str("b"),
+            NSString::from_str("c"),
+        );
+        let dict = NSDictionary::from_pairs(&[(&*a, &*b), (&*b, &*c)]);
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!(*dict.get(&a).unwrap(), *"b");
+        assert_eq!(*dict.get(&NSString::from_str("b")).unwrap(), *"c");
+        assert!(dict.get(&c).is_none());
+        assert!(dict.contains_key(&a));
+        assert!(!dict.contains_key(&c));
+
+        let mut pairs: Vec<(String, String)> = dict
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        pairs.sort();
+        assert_eq!(pairs, [("a".into(), "b".into()), ("b".into(), "c".into())]);
+
+        let mut keys: Vec<String> = dict.keys().iter().map(|k| k.to_string()).collect();
+        keys.sort();
+        assert_eq!(keys, ["a", "b"]);
+        assert_eq!(dict.values().len(), 2);
+    }
+
+    #[test]
+    fn empty() {
+        let dict = NSDictionary::<NSString, NSString>::from_pairs(&[]);
+        assert!(dict.is_empty());
+        assert_eq!(dict.iter().count(), 0);
+        assert!(dict.keys().is_empty());
+    }
+
+    #[test]
+    fn mutate() {
+        let key = NSString::from_str("key");
+        let mut dict = NSMutableDictionary::<NSString, NSString>::with_capacity(1);
+        let d = Arc::get_mut(&mut dict).unwrap();
+
+        d.insert(&key, &NSString::from_str("value"));
+        assert_eq!(*d.get(&key).unwrap(), *"value");
+
+        d.insert(&key, &NSString::from_str("replaced"));
+        assert_eq!(d.len(), 1);
+
+        let removed = d.remove(&key).unwrap();
+        assert_eq!(*removed, *"replaced");
+        assert!(d.remove(&key).is_none());
+
+        d.insert(&key, &removed);
+        d.clear();
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn heterogeneous_values() {
+        let (name, count) = (NSString::from_str("name"), NSString::from_str("count"));
+        let value = NSString::from_str("value");
+        let number: Arc<NSNumber> = 42i32.into();
+
+        let mut dict = NSMutableDictionary::<NSString, NSObject>::new();
+        let d = Arc::get_mut(&mut dict).unwrap();
+        d.insert(&name, &value);
+        d.insert(&count, &number);
+
+        assert_eq!(*d.get_as::<NSString>(&name).unwrap(), *"value");
+        assert!(d.get_as::<NSNumber>(&name).is_none());
+        assert!(d.get_as::<NSNumber>(&count).is_some());
+        assert!(d
+            .get_as::<NSString>(&NSString::from_str("missing"))
+            .is_none());
+
+        let null = NSString::from_str("null");
+        d.insert(&null, NSNull::null());
+        assert!(d.get(&null).is_some());
+        assert!(d.get_non_null(&null).is_none());
+        assert!(d.get_non_null(&name).is_some());
+    }
+
+    #[test]
+    fn iter_fast_keys() {
+        let keys: Vec<Arc<NSString>> = (0..100)
+            .map(|i| NSString::from_str(&i.to_string()))
+            .collect();
+        let value = NSNumber::from_integer(1);
+        let pairs: Vec<(&NSString, &NSNumber)> = keys.iter().map(|key| (&**key, &*value)).collect();
+
+        let dict = NSDictionary::from_pairs(&pairs);
+        let mut read: Vec<String> = dict.iter_fast().map(|key| key.to_string()).collect();
+        read.sort_by_key(|key| key.parse::<usize>().unwrap());
+
+        let expected: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        assert_eq!(read, expected);
+    }
+}
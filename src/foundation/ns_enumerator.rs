@@ -0,0 +1,97 @@
+use super::NSArray;
+use crate::core::{Arc, ObjectType};
+use crate::objc::{retain_autoreleased, NSObject};
+
+objc_subclass! {
+    /// An object that enumerates objects of type `T` one at a time.
+    ///
+    /// `&NSEnumerator<T>` is an [`Iterator`] that calls `nextObject` until it
+    /// returns `nil`. This is slower than iterating over a collection
+    /// directly, so enumerators are mainly useful for APIs that only return
+    /// one, such as
+    /// [`NSArray::reverse_object_enumerator`](struct.NSArray.html#method.reverse_object_enumerator).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsenumerator).
+    pub class NSEnumerator<T>: NSObject<'static>;
+}
+
+downcast_target!(NSEnumerator<NSObject<'static>>);
+
+impl<T: ObjectType + 'static> Iterator for &NSEnumerator<T> {
+    type Item = Arc<T>;
+
+    /// Returns the next object, which is retained because enumerators may
+    /// create objects rather than return ones owned by a collection.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsenumerator/nextobject).
+    #[inline]
+    #[doc(alias = "nextObject")]
+    fn next(&mut self) -> Option<Arc<T>> {
+        let enumerator: &NSEnumerator<T> = self;
+        unsafe { retain_autoreleased(|| _msg_send_any![enumerator, nextObject]) }
+    }
+}
+
+impl<T: ObjectType + 'static> NSEnumerator<T> {
+    /// Returns the objects that have not yet been enumerated, which finishes
+    /// the enumeration.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsenumerator/allobjects).
+    #[inline]
+    #[doc(alias = "allObjects")]
+    pub fn all_objects(&self) -> Arc<NSArray<T>> {
+        let objects = unsafe { retain_autoreleased(|| _msg_send_any![self, allObjects]) };
+        objects.expect("Failed to get remaining objects")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSDictionary, NSNumber, NSString};
+
+    fn strings(values: &[&str]) -> Vec<Arc<NSString<'static>>> {
+        values
+            .iter()
+            .map(|value| NSString::from_str(value))
+            .collect()
+    }
+
+    #[test]
+    fn dictionary_keys() {
+        let keys = strings(&["a", "b", "c"]);
+        let value = NSNumber::from_integer(1);
+        let pairs: Vec<(&NSString, &NSNumber)> = keys.iter().map(|key| (&**key, &*value)).collect();
+        let dict = NSDictionary::from_pairs(&pairs);
+
+        let mut read: Vec<String> = dict.key_enumerator().map(|key| key.to_string()).collect();
+        read.sort();
+        assert_eq!(read, ["a", "b", "c"]);
+
+        assert_eq!(dict.object_enumerator().count(), 3);
+    }
+
+    #[test]
+    fn array_reverse() {
+        let values = strings(&["a", "b", "c", "d"]);
+        let refs: Vec<&NSString> = values.iter().map(|value| &**value).collect();
+        let array = NSArray::from_slice(&refs);
+
+        let enumerator = array.reverse_object_enumerator();
+        let mut iter = &*enumerator;
+        assert_eq!(*iter.next().unwrap(), *"d");
+
+        // The rest are returned in reverse order, and then none remain.
+        let rest = enumerator.all_objects();
+        assert_eq!(rest.len(), 3);
+        assert_eq!(rest[0], *"c");
+        assert_eq!(rest[2], *"a");
+        assert!(iter.next().is_none());
+
+        let forward: Vec<Arc<NSString>> = array.object_enumerator().collect();
+        assert!(forward
+            .iter()
+            .zip(&refs)
+            .all(|(a, &b)| std::ptr::eq(&**a, b)));
+    }
+}
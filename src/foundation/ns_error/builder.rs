@@ -0,0 +1,168 @@
+use super::{NSError, NSErrorDomain, NSErrorRecoveryAttempting, NSErrorUserInfoKey};
+use crate::core::Arc;
+use crate::foundation::{NSArray, NSDictionary, NSString};
+use crate::objc::{NSInteger, NSObject, ObjCObject};
+
+/// Configures and creates an [`NSError`](struct.NSError.html).
+///
+/// This is returned by [`NSError::builder`](struct.NSError.html#method.builder).
+#[must_use = "This does nothing until `build` is called"]
+#[derive(Clone)]
+pub struct NSErrorBuilder<'a> {
+    domain: &'a NSErrorDomain,
+    code: NSInteger,
+    user_info: Vec<(&'a NSErrorUserInfoKey, Arc<NSObject<'static>>)>,
+}
+
+impl<'a> NSErrorBuilder<'a> {
+    /// Creates an [`NSError`](struct.NSError.html) builder for `domain` and a
+    /// domain-specific `code`.
+    #[inline]
+    pub fn new(domain: &'a NSErrorDomain, code: NSInteger) -> Self {
+        Self {
+            domain,
+            code,
+            user_info: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`NSError`](struct.NSError.html) with the configuration
+    /// specified by this builder.
+    pub fn build(&self) -> Arc<NSError<'static>> {
+        let pairs: Vec<(&NSString<'static>, &NSObject<'static>)> = self
+            .user_info
+            .iter()
+            .map(|(key, value)| (&key.0, &**value))
+            .collect();
+
+        let user_info = NSDictionary::from_pairs(&pairs);
+        NSError::new(self.domain, self.code, Some(&user_info))
+    }
+}
+
+/// Configuring user info.
+///
+/// Setting a key more than once replaces its previous value.
+impl<'a> NSErrorBuilder<'a> {
+    /// Sets the user info value for `key`.
+    #[inline]
+    pub fn user_info_value(
+        mut self,
+        key: &'a NSErrorUserInfoKey,
+        value: &NSObject<'static>,
+    ) -> Self {
+        let value = Arc::retain(value);
+        match self.user_info.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.user_info.push((key, value)),
+        }
+        self
+    }
+
+    fn string(self, key: &'static NSErrorUserInfoKey, value: &str) -> Self {
+        self.user_info_value(key, &NSString::from_str(value))
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::localized_description`](struct.NSError.html#method.localized_description).
+    #[inline]
+    pub fn localized_description(self, description: &str) -> Self {
+        self.string(NSErrorUserInfoKey::localized_description(), description)
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::localized_failure_reason`](struct.NSError.html#method.localized_failure_reason).
+    #[inline]
+    pub fn failure_reason(self, reason: &str) -> Self {
+        self.string(NSErrorUserInfoKey::localized_failure_reason(), reason)
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::localized_recovery_suggestion`](struct.NSError.html#method.localized_recovery_suggestion).
+    #[inline]
+    pub fn recovery_suggestion(self, suggestion: &str) -> Self {
+        self.string(
+            NSErrorUserInfoKey::localized_recovery_suggestion(),
+            suggestion,
+        )
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::localized_recovery_options`](struct.NSError.html#method.localized_recovery_options).
+    pub fn recovery_options(self, options: &[&str]) -> Self {
+        let options: Vec<Arc<NSString>> = options.iter().map(|o| NSString::from_str(o)).collect();
+        let options: Vec<&NSString> = options.iter().map(|o| &**o).collect();
+
+        let array = NSArray::from_slice(&options);
+        self.user_info_value(NSErrorUserInfoKey::localized_recovery_options(), &array)
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::recovery_attempter`](struct.NSError.html#method.recovery_attempter),
+    /// such as one created by
+    /// [`NSErrorRecoveryAttempting::from_fn`](struct.NSErrorRecoveryAttempting.html#method.from_fn).
+    #[inline]
+    pub fn recovery_attempter(self, attempter: &NSErrorRecoveryAttempting<'static>) -> Self {
+        // User info values are only retained, which any object supports.
+        let attempter: &NSObject = unsafe { &*(&attempter.0 as *const ObjCObject).cast() };
+        self.user_info_value(NSErrorUserInfoKey::recovery_attempter(), attempter)
+    }
+
+    /// Sets the value returned by
+    /// [`NSError::underlying_error`](struct.NSError.html#method.underlying_error).
+    #[inline]
+    pub fn underlying(self, error: &NSError<'static>) -> Self {
+        self.user_info_value(NSErrorUserInfoKey::underlying_error(), error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let underlying = NSError::new(NSErrorDomain::posix(), 13, None);
+        let error = NSError::builder(NSErrorDomain::cocoa(), 513)
+            .localized_description("Old description")
+            .localized_description("You don't have permission.")
+            .failure_reason("The folder is read-only.")
+            .recovery_suggestion("Choose another folder.")
+            .recovery_options(&["Choose", "Cancel"])
+            .underlying(&underlying)
+            .build();
+
+        assert_eq!(*error.domain(), *NSErrorDomain::cocoa());
+        assert_eq!(error.code(), 513);
+        assert_eq!(error.user_info().len(), 5);
+
+        assert_eq!(
+            *error.localized_description(),
+            *"You don't have permission."
+        );
+        assert_eq!(
+            *error.localized_failure_reason().unwrap(),
+            *"The folder is read-only."
+        );
+        assert_eq!(
+            *error.localized_recovery_suggestion().unwrap(),
+            *"Choose another folder."
+        );
+
+        let options = error.localized_recovery_options().unwrap();
+        assert_eq!(options.len(), 2);
+        assert_eq!(*options.get(0).unwrap(), *"Choose");
+        assert_eq!(*options.get(1).unwrap(), *"Cancel");
+
+        let key = NSErrorUserInfoKey::underlying_error();
+        assert!(error.user_info().get(&key.0).is_some());
+        assert_eq!(error.underlying_error().unwrap().code(), 13);
+    }
+
+    #[test]
+    fn build_empty() {
+        let error = NSError::builder(NSErrorDomain::posix(), 2).build();
+        assert!(error.user_info().is_empty());
+        assert!(error.underlying_error().is_none());
+    }
+}
@@ -0,0 +1,32 @@
+use super::NSError;
+use crate::core::Arc;
+use std::iter::FusedIterator;
+
+/// An iterator over an [`NSError`](struct.NSError.html) and its underlying
+/// errors.
+///
+/// This is returned by [`NSError::chain`](struct.NSError.html#method.chain).
+#[derive(Clone)]
+pub struct NSErrorChain {
+    next: Option<Arc<NSError<'static>>>,
+}
+
+impl NSErrorChain {
+    #[inline]
+    pub(super) fn new(error: Arc<NSError<'static>>) -> Self {
+        Self { next: Some(error) }
+    }
+}
+
+impl Iterator for NSErrorChain {
+    type Item = Arc<NSError<'static>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let error = self.next.take()?;
+        self.next = error.underlying_error();
+        Some(error)
+    }
+}
+
+impl FusedIterator for NSErrorChain {}
@@ -0,0 +1,263 @@
+use super::{NSError, NSErrorDomain};
+use crate::foundation::error_codes;
+use crate::objc::NSInteger;
+
+/// Defines an enum over error codes in `error_codes`, with an `Other` variant
+/// for unknown codes.
+macro_rules! code_enum {
+    (
+        $(#[$meta:meta])+
+        pub enum $name:ident {
+            $($variant:ident = $code:ident,)+
+        }
+    ) => {
+        $(#[$meta])+
+        #[non_exhaustive]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", stringify!($code), "`.")]
+                $variant,
+            )+
+
+            /// A code without a variant.
+            ///
+            /// This is never returned by
+            /// [`from_code`](#method.from_code) for a code with a variant.
+            Other(NSInteger),
+        }
+
+        impl From<NSInteger> for $name {
+            #[inline]
+            fn from(code: NSInteger) -> Self {
+                Self::from_code(code)
+            }
+        }
+
+        impl From<$name> for NSInteger {
+            #[inline]
+            fn from(code: $name) -> Self {
+                code.code()
+            }
+        }
+
+        impl $name {
+            /// Returns the variant for `code`, or `Other` if there is none.
+            #[inline]
+            pub fn from_code(code: NSInteger) -> Self {
+                match code {
+                    $(error_codes::$code => Self::$variant,)+
+                    code => Self::Other(code),
+                }
+            }
+
+            /// Returns the raw error code.
+            #[inline]
+            pub fn code(self) -> NSInteger {
+                match self {
+                    $(Self::$variant => error_codes::$code,)+
+                    Self::Other(code) => code,
+                }
+            }
+        }
+    };
+}
+
+code_enum! {
+    /// A well-known code of an error in
+    /// [`NSErrorDomain::cocoa`](struct.NSErrorDomain.html#method.cocoa).
+    ///
+    /// This is returned by
+    /// [`NSError::cocoa_code`](struct.NSError.html#method.cocoa_code).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/1448136-nserror_codes).
+    pub enum CocoaErrorCode {
+        FileNoSuchFile = NSFileNoSuchFileError,
+        FileLocking = NSFileLockingError,
+        FileReadUnknown = NSFileReadUnknownError,
+        FileReadNoPermission = NSFileReadNoPermissionError,
+        FileReadInvalidFileName = NSFileReadInvalidFileNameError,
+        FileReadCorruptFile = NSFileReadCorruptFileError,
+        FileReadNoSuchFile = NSFileReadNoSuchFileError,
+        FileReadInapplicableStringEncoding = NSFileReadInapplicableStringEncodingError,
+        FileReadUnsupportedScheme = NSFileReadUnsupportedSchemeError,
+        FileReadTooLarge = NSFileReadTooLargeError,
+        FileReadUnknownStringEncoding = NSFileReadUnknownStringEncodingError,
+        FileWriteUnknown = NSFileWriteUnknownError,
+        FileWriteNoPermission = NSFileWriteNoPermissionError,
+        FileWriteInvalidFileName = NSFileWriteInvalidFileNameError,
+        FileWriteFileExists = NSFileWriteFileExistsError,
+        FileWriteInapplicableStringEncoding = NSFileWriteInapplicableStringEncodingError,
+        FileWriteUnsupportedScheme = NSFileWriteUnsupportedSchemeError,
+        FileWriteOutOfSpace = NSFileWriteOutOfSpaceError,
+        FileWriteVolumeReadOnly = NSFileWriteVolumeReadOnlyError,
+        FileManagerUnmountUnknown = NSFileManagerUnmountUnknownError,
+        FileManagerUnmountBusy = NSFileManagerUnmountBusyError,
+        KeyValueValidation = NSKeyValueValidationError,
+        Formatting = NSFormattingError,
+        UserCancelled = NSUserCancelledError,
+        FeatureUnsupported = NSFeatureUnsupportedError,
+        ExecutableNotLoadable = NSExecutableNotLoadableError,
+        ExecutableArchitectureMismatch = NSExecutableArchitectureMismatchError,
+        ExecutableRuntimeMismatch = NSExecutableRuntimeMismatchError,
+        ExecutableLoad = NSExecutableLoadError,
+        ExecutableLink = NSExecutableLinkError,
+        PropertyListReadCorrupt = NSPropertyListReadCorruptError,
+        PropertyListReadUnknownVersion = NSPropertyListReadUnknownVersionError,
+        PropertyListReadStream = NSPropertyListReadStreamError,
+        PropertyListWriteStream = NSPropertyListWriteStreamError,
+        PropertyListWriteInvalid = NSPropertyListWriteInvalidError,
+        XPCConnectionInterrupted = NSXPCConnectionInterrupted,
+        XPCConnectionInvalid = NSXPCConnectionInvalid,
+        XPCConnectionReplyInvalid = NSXPCConnectionReplyInvalid,
+        UbiquitousFileUnavailable = NSUbiquitousFileUnavailableError,
+        UbiquitousFileNotUploadedDueToQuota = NSUbiquitousFileNotUploadedDueToQuotaError,
+        UbiquitousFileUbiquityServerNotAvailable = NSUbiquitousFileUbiquityServerNotAvailable,
+        UserActivityHandoffFailed = NSUserActivityHandoffFailedError,
+        UserActivityConnectionUnavailable = NSUserActivityConnectionUnavailableError,
+        UserActivityRemoteApplicationTimedOut = NSUserActivityRemoteApplicationTimedOutError,
+        UserActivityHandoffUserInfoTooLarge = NSUserActivityHandoffUserInfoTooLargeError,
+        CoderReadCorrupt = NSCoderReadCorruptError,
+        CoderValueNotFound = NSCoderValueNotFoundError,
+        CoderInvalidValue = NSCoderInvalidValueError,
+        BundleOnDemandResourceOutOfSpace = NSBundleOnDemandResourceOutOfSpaceError,
+        BundleOnDemandResourceExceededMaximumSize = NSBundleOnDemandResourceExceededMaximumSizeError,
+        BundleOnDemandResourceInvalidTag = NSBundleOnDemandResourceInvalidTagError,
+        CloudSharingNetworkFailure = NSCloudSharingNetworkFailureError,
+        CloudSharingQuotaExceeded = NSCloudSharingQuotaExceededError,
+        CloudSharingTooManyParticipants = NSCloudSharingTooManyParticipantsError,
+        CloudSharingConflict = NSCloudSharingConflictError,
+        CloudSharingNoPermission = NSCloudSharingNoPermissionError,
+        CloudSharingOther = NSCloudSharingOtherError,
+        CompressionFailed = NSCompressionFailedError,
+        DecompressionFailed = NSDecompressionFailedError,
+    }
+}
+
+code_enum! {
+    /// A well-known code of an error in
+    /// [`NSErrorDomain::ns_url`](struct.NSErrorDomain.html#method.ns_url).
+    ///
+    /// This is returned by
+    /// [`NSError::url_code`](struct.NSError.html#method.url_code).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/1508628-url_loading_system_error_codes).
+    pub enum URLErrorCode {
+        Unknown = NSURLErrorUnknown,
+        Cancelled = NSURLErrorCancelled,
+        BadURL = NSURLErrorBadURL,
+        TimedOut = NSURLErrorTimedOut,
+        UnsupportedURL = NSURLErrorUnsupportedURL,
+        CannotFindHost = NSURLErrorCannotFindHost,
+        CannotConnectToHost = NSURLErrorCannotConnectToHost,
+        NetworkConnectionLost = NSURLErrorNetworkConnectionLost,
+        DNSLookupFailed = NSURLErrorDNSLookupFailed,
+        HTTPTooManyRedirects = NSURLErrorHTTPTooManyRedirects,
+        ResourceUnavailable = NSURLErrorResourceUnavailable,
+        NotConnectedToInternet = NSURLErrorNotConnectedToInternet,
+        RedirectToNonExistentLocation = NSURLErrorRedirectToNonExistentLocation,
+        BadServerResponse = NSURLErrorBadServerResponse,
+        UserCancelledAuthentication = NSURLErrorUserCancelledAuthentication,
+        UserAuthenticationRequired = NSURLErrorUserAuthenticationRequired,
+        ZeroByteResource = NSURLErrorZeroByteResource,
+        CannotDecodeRawData = NSURLErrorCannotDecodeRawData,
+        CannotDecodeContentData = NSURLErrorCannotDecodeContentData,
+        CannotParseResponse = NSURLErrorCannotParseResponse,
+        AppTransportSecurityRequiresSecureConnection = NSURLErrorAppTransportSecurityRequiresSecureConnection,
+        FileDoesNotExist = NSURLErrorFileDoesNotExist,
+        FileIsDirectory = NSURLErrorFileIsDirectory,
+        NoPermissionsToReadFile = NSURLErrorNoPermissionsToReadFile,
+        FileOutsideSafeArea = NSURLErrorFileOutsideSafeArea,
+        SecureConnectionFailed = NSURLErrorSecureConnectionFailed,
+        ServerCertificateHasBadDate = NSURLErrorServerCertificateHasBadDate,
+        ServerCertificateUntrusted = NSURLErrorServerCertificateUntrusted,
+        ServerCertificateHasUnknownRoot = NSURLErrorServerCertificateHasUnknownRoot,
+        ServerCertificateNotYetValid = NSURLErrorServerCertificateNotYetValid,
+        ClientCertificateRejected = NSURLErrorClientCertificateRejected,
+        ClientCertificateRequired = NSURLErrorClientCertificateRequired,
+        CannotLoadFromNetwork = NSURLErrorCannotLoadFromNetwork,
+        CannotCreateFile = NSURLErrorCannotCreateFile,
+        CannotOpenFile = NSURLErrorCannotOpenFile,
+        CannotCloseFile = NSURLErrorCannotCloseFile,
+        CannotWriteToFile = NSURLErrorCannotWriteToFile,
+        CannotRemoveFile = NSURLErrorCannotRemoveFile,
+        CannotMoveFile = NSURLErrorCannotMoveFile,
+        DownloadDecodingFailedMidStream = NSURLErrorDownloadDecodingFailedMidStream,
+        DownloadDecodingFailedToComplete = NSURLErrorDownloadDecodingFailedToComplete,
+        InternationalRoamingOff = NSURLErrorInternationalRoamingOff,
+        CallIsActive = NSURLErrorCallIsActive,
+        DataNotAllowed = NSURLErrorDataNotAllowed,
+        RequestBodyStreamExhausted = NSURLErrorRequestBodyStreamExhausted,
+        BackgroundSessionRequiresSharedContainer = NSURLErrorBackgroundSessionRequiresSharedContainer,
+        BackgroundSessionInUseByAnotherProcess = NSURLErrorBackgroundSessionInUseByAnotherProcess,
+        BackgroundSessionWasDisconnected = NSURLErrorBackgroundSessionWasDisconnected,
+    }
+}
+
+/// Matching error codes.
+impl NSError<'_> {
+    /// Returns the code of `self` if it is in the Cocoa error domain.
+    #[inline]
+    pub fn cocoa_code(&self) -> Option<CocoaErrorCode> {
+        if *self.domain() == *NSErrorDomain::cocoa() {
+            Some(CocoaErrorCode::from_code(self.code()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the code of `self` if it is in the URL loading error domain.
+    #[inline]
+    pub fn url_code(&self) -> Option<URLErrorCode> {
+        if *self.domain() == *NSErrorDomain::ns_url() {
+            Some(URLErrorCode::from_code(self.code()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cocoa_code() {
+        let error = NSError::new(NSErrorDomain::cocoa(), 4, None);
+        assert_eq!(error.cocoa_code(), Some(CocoaErrorCode::FileNoSuchFile));
+        assert_eq!(error.url_code(), None);
+
+        let error = NSError::new(NSErrorDomain::cocoa(), 3072, None);
+        assert!(matches!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::UserCancelled)
+        ));
+
+        let error = NSError::new(NSErrorDomain::cocoa(), 123_456, None);
+        assert_eq!(error.cocoa_code(), Some(CocoaErrorCode::Other(123_456)));
+
+        let error = NSError::new(NSErrorDomain::posix(), 4, None);
+        assert_eq!(error.cocoa_code(), None);
+    }
+
+    #[test]
+    fn url_code() {
+        let error = NSError::new(NSErrorDomain::ns_url(), -1001, None);
+        assert_eq!(error.url_code(), Some(URLErrorCode::TimedOut));
+        assert_eq!(error.cocoa_code(), None);
+
+        let error = NSError::new(NSErrorDomain::ns_url(), -1009, None);
+        assert_eq!(error.url_code(), Some(URLErrorCode::NotConnectedToInternet));
+    }
+
+    #[test]
+    fn round_trip() {
+        for &code in &[4, 513, 3072, 4865, -5, 0] {
+            assert_eq!(CocoaErrorCode::from_code(code).code(), code);
+        }
+        for &code in &[-1, -999, -1003, -3007, 1] {
+            assert_eq!(URLErrorCode::from(code).code(), code);
+        }
+        assert_eq!(NSInteger::from(URLErrorCode::Cancelled), -999);
+    }
+}
@@ -0,0 +1,166 @@
+use super::{NSError, NSErrorDomain};
+use crate::core::Arc;
+use crate::foundation::error_codes;
+use crate::objc::NSInteger;
+use std::{convert::TryFrom, io, os::raw::c_int};
+
+/// Converting to and from I/O errors.
+impl NSError<'static> {
+    /// Returns `self` or the first underlying error as an `io::Error`, if it
+    /// is in the POSIX domain or is a common Cocoa file error.
+    ///
+    /// POSIX errors become OS errors with the same code. Cocoa file errors
+    /// keep their description and get the closest
+    /// [`ErrorKind`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html).
+    ///
+    /// This is also available as
+    /// `From<Arc<NSError>> for io::Error`, which falls back to
+    /// [`ErrorKind::Other`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other).
+    pub fn to_io_error(&self) -> Option<io::Error> {
+        self.chain().find_map(|error| error.to_io_error_shallow())
+    }
+
+    fn to_io_error_shallow(&self) -> Option<io::Error> {
+        let domain = self.domain();
+        let code = self.code();
+
+        if *domain == *NSErrorDomain::posix() {
+            let code = c_int::try_from(code).ok()?;
+            return Some(io::Error::from_raw_os_error(code));
+        }
+
+        if *domain != *NSErrorDomain::cocoa() {
+            return None;
+        }
+
+        let kind = match code {
+            error_codes::NSFileNoSuchFileError | error_codes::NSFileReadNoSuchFileError => {
+                io::ErrorKind::NotFound
+            }
+            error_codes::NSFileReadNoPermissionError
+            | error_codes::NSFileWriteNoPermissionError => io::ErrorKind::PermissionDenied,
+            error_codes::NSFileWriteFileExistsError => io::ErrorKind::AlreadyExists,
+            error_codes::NSFileReadInvalidFileNameError
+            | error_codes::NSFileWriteInvalidFileNameError => io::ErrorKind::InvalidInput,
+            error_codes::NSFeatureUnsupportedError => io::ErrorKind::Unsupported,
+            error_codes::NSFileReadUnknownError | error_codes::NSFileWriteUnknownError => {
+                io::ErrorKind::Other
+            }
+            _ => return None,
+        };
+        Some(io::Error::new(kind, self.to_string()))
+    }
+}
+
+impl From<io::Error> for Arc<NSError<'static>> {
+    /// Converts OS errors to the POSIX domain, and other errors to the Cocoa
+    /// domain with their description.
+    fn from(error: io::Error) -> Self {
+        if let Some(code) = error.raw_os_error() {
+            return NSError::new(NSErrorDomain::posix(), code as NSInteger, None);
+        }
+
+        let code = match error.kind() {
+            io::ErrorKind::NotFound => error_codes::NSFileNoSuchFileError,
+            io::ErrorKind::PermissionDenied => error_codes::NSFileReadNoPermissionError,
+            io::ErrorKind::AlreadyExists => error_codes::NSFileWriteFileExistsError,
+            io::ErrorKind::Unsupported => error_codes::NSFeatureUnsupportedError,
+            _ => error_codes::NSFileReadUnknownError,
+        };
+
+        NSError::builder(NSErrorDomain::cocoa(), code)
+            .localized_description(&error.to_string())
+            .build()
+    }
+}
+
+impl From<Arc<NSError<'static>>> for io::Error {
+    #[inline]
+    fn from(error: Arc<NSError<'static>>) -> Self {
+        error
+            .to_io_error()
+            .unwrap_or_else(|| io::Error::other(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_io_error() {
+        for &errno in &[libc_errno::ENOENT, libc_errno::EACCES, libc_errno::EEXIST] {
+            let error: Arc<NSError> = io::Error::from_raw_os_error(errno).into();
+            assert_eq!(*error.domain(), *NSErrorDomain::posix());
+            assert_eq!(error.code(), errno as NSInteger);
+
+            let io_error = error.to_io_error().unwrap();
+            assert_eq!(io_error.raw_os_error(), Some(errno));
+        }
+
+        let error: Arc<NSError> = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert_eq!(*error.domain(), *NSErrorDomain::cocoa());
+        assert_eq!(error.code(), error_codes::NSFileNoSuchFileError);
+        assert_eq!(*error.localized_description(), *"missing");
+
+        let io_error = error.to_io_error().unwrap();
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+        assert_eq!(io_error.to_string(), "missing");
+    }
+
+    #[test]
+    fn to_io_error() {
+        let cases = [
+            (
+                error_codes::NSFileReadNoSuchFileError,
+                io::ErrorKind::NotFound,
+            ),
+            (
+                error_codes::NSFileWriteNoPermissionError,
+                io::ErrorKind::PermissionDenied,
+            ),
+            (
+                error_codes::NSFileWriteFileExistsError,
+                io::ErrorKind::AlreadyExists,
+            ),
+        ];
+        for &(code, kind) in &cases {
+            let error = NSError::new(NSErrorDomain::cocoa(), code, None);
+            assert_eq!(error.to_io_error().unwrap().kind(), kind);
+        }
+
+        // Unrelated errors only convert with `From`.
+        let error = NSError::new(
+            NSErrorDomain::cocoa(),
+            error_codes::NSUserCancelledError,
+            None,
+        );
+        assert!(error.to_io_error().is_none());
+        assert_eq!(io::Error::from(error).kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn nested() {
+        let posix = NSError::new(
+            NSErrorDomain::posix(),
+            libc_errno::ENOSPC as NSInteger,
+            None,
+        );
+        let error = NSError::builder(NSErrorDomain::ns_url(), -3000)
+            .underlying(&posix)
+            .build();
+
+        let io_error = error.to_io_error().unwrap();
+        assert_eq!(io_error.raw_os_error(), Some(libc_errno::ENOSPC));
+    }
+
+    // Values shared by Apple platforms, to avoid depending on `libc`.
+    mod libc_errno {
+        use std::os::raw::c_int;
+
+        pub const ENOENT: c_int = 2;
+        pub const EACCES: c_int = 13;
+        pub const EEXIST: c_int = 17;
+        pub const ENOSPC: c_int = 28;
+    }
+}
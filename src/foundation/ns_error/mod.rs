@@ -1,12 +1,24 @@
-use super::NSString;
+use super::{NSArray, NSDictionary, NSString, NSURL};
 use crate::core::Arc;
-use crate::objc::{NSInteger, NSObject};
-use std::fmt;
+use crate::objc::{
+    autoreleasepool, retain_autoreleased, ClassType, DowncastTarget, NSInteger, NSObject,
+};
+use std::{error::Error, fmt, ptr};
 
+#[cfg(feature = "core_foundation")]
+use crate::core_foundation::CFError;
+
+mod builder;
+mod chain;
+mod code;
 mod domain;
+mod io;
 mod recovery_attempting;
 mod user_info_key;
 
+pub use builder::*;
+pub use chain::*;
+pub use code::*;
 pub use domain::*;
 pub use recovery_attempting::*;
 pub use user_info_key::*;
@@ -23,11 +35,32 @@ objc_subclass! {
     ///
     /// The [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html)
     /// implementation writes the result of
-    /// [`localized_description`](#method.localized_description).
+    /// [`localized_description`](#method.localized_description), and the
+    /// [`Debug`](https://doc.rust-lang.org/std/fmt/trait.Debug.html)
+    /// implementation writes the result of `-description`, which includes the
+    /// domain, code, and user info.
+    ///
+    /// # Error Handling
+    ///
+    /// `NSError` implements
+    /// [`Error`](https://doc.rust-lang.org/std/error/trait.Error.html), whose
+    /// [`source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source)
+    /// is the [underlying error](#method.underlying_error). This allows
+    /// `Arc<NSError>` to be used with `?` and error-reporting libraries.
     pub class NSError<'data>: NSObject<'data>;
 }
 
-// TODO: `fmt::Debug`
+impl fmt::Debug for NSError<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description: Option<Arc<NSString>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, description]) };
+        match description {
+            Some(description) => description.fmt(f),
+            None => f.write_str("NSError"),
+        }
+    }
+}
 
 impl fmt::Display for NSError<'_> {
     #[inline]
@@ -36,8 +69,80 @@ impl fmt::Display for NSError<'_> {
     }
 }
 
-impl NSError<'_> {
-    // TODO: `new(domain: &NSErrorDomain, code: NSInteger, user_info: &NSDictionary<NSErrorUserInfoKey, id>) -> Arc<Self>`
+impl Error for NSError<'static> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        // The user info dictionary is immutable and retained by `self`, so
+        // its values live as long as `self` does.
+        let user_info: *const NSDictionary<NSString<'static>, NSObject<'static>> =
+            unsafe { _msg_send_any![self, userInfo] };
+        let user_info = unsafe { user_info.as_ref()? };
+
+        let key = NSErrorUserInfoKey::underlying_error();
+        let underlying: &NSError<'static> = user_info.get_as(&key.0)?;
+        Some(underlying)
+    }
+}
+
+/// Calls `f` with an `NSError **` out-parameter and converts its result to a
+/// `Result`.
+///
+/// Following Cocoa conventions, `f` returns `None` (for `nil` or `NO`) on
+/// failure, in which case the error is retained. Otherwise, the error is
+/// ignored even if it was set. `f` is called within an autorelease pool, so
+/// any autoreleased objects it returns must be retained.
+///
+/// # Safety
+///
+/// `f` must set the error when it fails.
+pub(crate) unsafe fn with_error_out<T, F>(f: F) -> Result<T, Arc<NSError<'static>>>
+where
+    F: FnOnce(*mut *mut NSError<'static>) -> Option<T>,
+{
+    autoreleasepool(|_| {
+        let mut error: *mut NSError<'static> = ptr::null_mut();
+        match f(&mut error) {
+            Some(value) => Ok(value),
+            None => match error.as_ref() {
+                Some(error) => Err(Arc::retain(error)),
+                None => panic!("Operation failed without an error"),
+            },
+        }
+    })
+}
+
+/// Creating errors.
+impl NSError<'static> {
+    /// Creates an error in `domain` with a domain-specific `code` and
+    /// optional user info, whose keys are usually
+    /// [`NSErrorUserInfoKey`](struct.NSErrorUserInfoKey.html) values.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/init(domain:code:userinfo:)).
+    #[doc(alias = "errorWithDomain")]
+    #[doc(alias = "errorWithDomain:code:userInfo:")]
+    pub fn new(
+        domain: &NSErrorDomain,
+        code: NSInteger,
+        user_info: Option<&NSDictionary<NSString<'static>, NSObject<'static>>>,
+    ) -> Arc<Self> {
+        let error = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    Self::class(),
+                    errorWithDomain: domain
+                    code: code
+                    userInfo: user_info
+                ]
+            })
+        };
+        error.expect("Failed to create error")
+    }
+
+    /// Returns a value that can be used to configure and create an error with
+    /// common user info.
+    #[inline]
+    pub fn builder(domain: &NSErrorDomain, code: NSInteger) -> NSErrorBuilder<'_> {
+        NSErrorBuilder::new(domain, code)
+    }
 }
 
 /// Getting error properties.
@@ -57,10 +162,30 @@ impl NSError<'_> {
     /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/1413924-domain).
     #[inline]
     pub fn domain(&self) -> Arc<NSErrorDomain> {
-        unsafe { _msg_send_any![self, domain] }
+        let domain = unsafe { retain_autoreleased(|| _msg_send_any![self, domain]) };
+        domain.expect("Error has no domain")
     }
 
-    // TODO: `userInfo`
+    /// Returns the user info dictionary, whose keys are usually
+    /// [`NSErrorUserInfoKey`](struct.NSErrorUserInfoKey.html) values.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/userinfo).
+    #[inline]
+    #[doc(alias = "userInfo")]
+    pub fn user_info(&self) -> Arc<NSDictionary<NSString<'static>, NSObject<'static>>> {
+        let user_info = unsafe { retain_autoreleased(|| _msg_send_any![self, userInfo]) };
+        user_info.expect("Error has no user info")
+    }
+
+    /// Returns the user info value for `key` if it is an instance of `T` or of
+    /// a subclass of `T`.
+    #[inline]
+    pub fn user_info_value<T: DowncastTarget<'static>>(
+        &self,
+        key: &NSErrorUserInfoKey,
+    ) -> Option<Arc<T>> {
+        self.user_info().get_as::<T>(&key.0).map(Arc::retain)
+    }
 }
 
 /// Getting error user info.
@@ -72,11 +197,13 @@ impl NSError<'_> {
     /// If it doesn't exist, a default string is constructed from the domain and
     /// code.
     ///
-    /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/1413924-domain).
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/localizeddescription).
     #[inline]
     #[doc(alias = "localizedDescription")]
-    pub fn localized_description(&self) -> Arc<NSString> {
-        unsafe { _msg_send_any![self, localizedDescription] }
+    pub fn localized_description(&self) -> Arc<NSString<'static>> {
+        let description =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, localizedDescription]) };
+        description.expect("Error has no localized description")
     }
 
     /// Returns a string containing the localized explanation of the reason for
@@ -88,11 +215,23 @@ impl NSError<'_> {
     /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/1412752-localizedfailurereason).
     #[inline]
     #[doc(alias = "localizedFailureReason")]
-    pub fn localized_failure_reason(&self) -> Option<Arc<NSString>> {
-        unsafe { _msg_send_any![self, localizedFailureReason] }
+    pub fn localized_failure_reason(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, localizedFailureReason]) }
     }
 
-    // TODO: `localizedRecoveryOptions`
+    /// Returns the localized titles of buttons for recovering from the error,
+    /// whose indices are passed to
+    /// [`NSErrorRecoveryAttempting::attempt_recovery`](struct.NSErrorRecoveryAttempting.html#method.attempt_recovery).
+    ///
+    /// This is the object in the user info dictionary for
+    /// [`NSErrorUserInfoKey::localized_recovery_options`](struct.NSErrorUserInfoKey.html#method.localized_recovery_options).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/localizedrecoveryoptions).
+    #[inline]
+    #[doc(alias = "localizedRecoveryOptions")]
+    pub fn localized_recovery_options(&self) -> Option<Arc<NSArray<NSString<'static>>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, localizedRecoveryOptions]) }
+    }
 
     /// Returns a string containing the localized recovery suggestion for the
     /// error.
@@ -103,8 +242,8 @@ impl NSError<'_> {
     /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/1407500-localizedrecoverysuggestion).
     #[inline]
     #[doc(alias = "localizedRecoverySuggestion")]
-    pub fn localized_recovery_suggestion(&self) -> Option<Arc<NSString>> {
-        unsafe { _msg_send_any![self, localizedRecoverySuggestion] }
+    pub fn localized_recovery_suggestion(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, localizedRecoverySuggestion]) }
     }
 
     /// Returns the object in the user info dictionary corresponding to
@@ -114,7 +253,7 @@ impl NSError<'_> {
     #[inline]
     #[doc(alias = "recoveryAttempter")]
     pub fn recovery_attempter(&self) -> Option<Arc<NSErrorRecoveryAttempting>> {
-        unsafe { _msg_send_any![self, recoveryAttempter] }
+        unsafe { retain_autoreleased(|| _msg_send_any![self, recoveryAttempter]) }
     }
 
     /// Returns the object in the user info dictionary corresponding to
@@ -123,8 +262,62 @@ impl NSError<'_> {
     /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/1414718-helpanchor).
     #[inline]
     #[doc(alias = "helpAnchor")]
-    pub fn help_anchor(&self) -> Option<Arc<NSString>> {
-        unsafe { _msg_send_any![self, helpAnchor] }
+    pub fn help_anchor(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, helpAnchor]) }
+    }
+
+    /// Returns the error that caused this one.
+    ///
+    /// This is the object in the user info dictionary for
+    /// [`NSErrorUserInfoKey::underlying_error`](struct.NSErrorUserInfoKey.html#method.underlying_error).
+    #[inline]
+    pub fn underlying_error(&self) -> Option<Arc<NSError<'static>>> {
+        self.user_info_value(NSErrorUserInfoKey::underlying_error())
+    }
+
+    /// Returns the URL of the resource involved in the error.
+    ///
+    /// This is the object in the user info dictionary for
+    /// [`NSErrorUserInfoKey::ns_url`](struct.NSErrorUserInfoKey.html#method.ns_url).
+    #[inline]
+    pub fn url(&self) -> Option<Arc<NSURL>> {
+        self.user_info_value(NSErrorUserInfoKey::ns_url())
+    }
+
+    /// Returns the file path involved in the error.
+    ///
+    /// This is the object in the user info dictionary for
+    /// [`NSErrorUserInfoKey::file_path`](struct.NSErrorUserInfoKey.html#method.file_path).
+    #[inline]
+    pub fn file_path(&self) -> Option<Arc<NSString<'static>>> {
+        self.user_info_value(NSErrorUserInfoKey::file_path())
+    }
+}
+
+impl NSError<'static> {
+    /// Returns an iterator over `self` followed by each
+    /// [underlying error](#method.underlying_error).
+    ///
+    /// Unlike [`Error::source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source),
+    /// this yields owned errors.
+    #[inline]
+    pub fn chain(&self) -> NSErrorChain {
+        NSErrorChain::new(Arc::retain(self))
+    }
+}
+
+/// Toll-free bridging.
+impl NSError<'static> {
+    /// Returns this error as a `CFError`, which is the same object.
+    ///
+    /// # Feature Flag
+    ///
+    /// This method requires the **`core_foundation`**
+    /// [feature flag](../index.html#feature-flags).
+    #[cfg(feature = "core_foundation")]
+    #[inline]
+    pub fn as_cf_error(&self) -> &CFError {
+        unsafe { &*(self as *const Self).cast() }
     }
 }
 
@@ -134,3 +327,126 @@ impl NSError<'_> {
     // - `userInfoValueProviderForDomain:`
     // - `setUserInfoValueProviderForDomain:provider:`
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(
+        domain: &NSErrorDomain,
+        code: NSInteger,
+        user_info: &NSDictionary<NSString<'static>, NSObject<'static>>,
+    ) -> Arc<NSError<'static>> {
+        NSError::new(domain, code, Some(user_info))
+    }
+
+    #[test]
+    fn user_info() {
+        let description = NSString::from_str("The file could not be saved.");
+        let reason = NSString::from_str("The disk is full.");
+        let suggestion = NSString::from_str("Free up space and try again.");
+        let try_again = NSString::from_str("Try Again");
+        let cancel = NSString::from_str("Cancel");
+        let options = NSArray::from_slice(&[&*try_again, &*cancel]);
+        let anchor = NSString::from_str("saving");
+        let path = NSString::from_str("/tmp/file.txt");
+        let url = NSURL::from_str("file:///tmp/file.txt").unwrap();
+        let underlying = error(NSErrorDomain::posix(), 28, &NSDictionary::from_pairs(&[]));
+
+        let user_info = NSDictionary::from_pairs(&[
+            (
+                &NSErrorUserInfoKey::localized_description().0,
+                &**description,
+            ),
+            (&NSErrorUserInfoKey::localized_failure_reason().0, &**reason),
+            (
+                &NSErrorUserInfoKey::localized_recovery_suggestion().0,
+                &**suggestion,
+            ),
+            (
+                &NSErrorUserInfoKey::localized_recovery_options().0,
+                &**options,
+            ),
+            (&NSErrorUserInfoKey::help_anchor().0, &**anchor),
+            (&NSErrorUserInfoKey::file_path().0, &**path),
+            (&NSErrorUserInfoKey::ns_url().0, &**url),
+            (&NSErrorUserInfoKey::underlying_error().0, &**underlying),
+        ]);
+        let error = error(NSErrorDomain::cocoa(), 640, &user_info);
+
+        assert_eq!(*error.domain(), *NSErrorDomain::cocoa());
+        assert_eq!(error.code(), 640);
+        assert_eq!(error.user_info().len(), 8);
+
+        assert_eq!(*error.localized_description(), *description);
+        assert_eq!(*error.localized_failure_reason().unwrap(), *reason);
+        assert_eq!(*error.localized_recovery_suggestion().unwrap(), *suggestion);
+        assert_eq!(*error.help_anchor().unwrap(), *anchor);
+        assert_eq!(*error.file_path().unwrap(), *path);
+        assert_eq!(*error.url().unwrap(), *url);
+
+        let options = error.localized_recovery_options().unwrap();
+        assert_eq!(options.len(), 2);
+        assert_eq!(*options.get(0).unwrap(), *try_again);
+        assert_eq!(*options.get(1).unwrap(), *cancel);
+
+        let underlying = error.underlying_error().unwrap();
+        assert_eq!(*underlying.domain(), *NSErrorDomain::posix());
+        assert_eq!(underlying.code(), 28);
+        assert!(underlying.underlying_error().is_none());
+
+        // Values of the wrong type are not returned.
+        let url: Option<Arc<NSURL>> = error.user_info_value(NSErrorUserInfoKey::file_path());
+        assert!(url.is_none());
+    }
+
+    #[test]
+    fn empty_user_info() {
+        let error = error(NSErrorDomain::cocoa(), 4, &NSDictionary::from_pairs(&[]));
+
+        assert!(error.user_info().is_empty());
+        assert!(!error.localized_description().is_empty());
+        assert!(error.localized_recovery_options().is_none());
+        assert!(error.help_anchor().is_none());
+        assert!(error.underlying_error().is_none());
+        assert!(error.url().is_none());
+    }
+
+    #[test]
+    fn chain() {
+        let inner_description = NSString::from_str("No space left on device");
+        let inner = error(
+            NSErrorDomain::posix(),
+            28,
+            &NSDictionary::from_pairs(&[(
+                &NSErrorUserInfoKey::localized_description().0,
+                &**inner_description,
+            )]),
+        );
+
+        let outer_description = NSString::from_str("The file could not be saved.");
+        let outer = error(
+            NSErrorDomain::cocoa(),
+            640,
+            &NSDictionary::from_pairs(&[
+                (
+                    &NSErrorUserInfoKey::localized_description().0,
+                    &**outer_description,
+                ),
+                (&NSErrorUserInfoKey::underlying_error().0, &**inner),
+            ]),
+        );
+
+        assert_eq!(outer.to_string(), "The file could not be saved.");
+        assert!(format!("{:?}", outer).contains("NSCocoaErrorDomain"));
+
+        let codes: Vec<NSInteger> = outer.chain().map(|error| error.code()).collect();
+        assert_eq!(codes, [640, 28]);
+
+        // `Arc<NSError>` works as a boxed error, such as with `?`.
+        let boxed: Box<dyn Error> = Box::new(outer.clone());
+        let source = boxed.source().unwrap();
+        assert_eq!(source.to_string(), "No space left on device");
+        assert!(source.source().is_none());
+    }
+}
@@ -1,8 +1,19 @@
 use super::NSError;
-use crate::objc::{NSObject, NSUInteger, ObjCObject, Sel, BOOL};
-use std::ffi::c_void;
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{
+    allocate_private_class, sys, Block, Class, ClassType, Imp, NSObject, NSObjectProtocol,
+    NSUInteger, ObjCObject, RcBlock, Sel, BOOL,
+};
+use std::{
+    ffi::c_void,
+    mem, ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Once,
+    },
+};
 
-// TODO: Create `NSObjectProtocol` and wrap that.
 objc_object_wrapper! {
     /// A set of methods that provide options to recover from an error.
     ///
@@ -24,7 +35,150 @@ objc_object_wrapper! {
     ///   [`attempt_recovery`](#method.attempt_recovery) is invoked.
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nserror/nserrorrecoveryattempting).
-    pub wrapper NSErrorRecoveryAttempting<'data>: NSObject<'data>;
+    pub wrapper NSErrorRecoveryAttempting<'data>: ObjCObject<'data>;
+}
+
+// The address of this is the key of the block associated with an attempter.
+static ATTEMPTER_BLOCK_KEY: u8 = 0;
+
+const OBJC_ASSOCIATION_RETAIN_NONATOMIC: usize = 1;
+
+// Type encodings of the recovery methods, which depend on `BOOL` and
+// `NSUInteger`.
+#[cfg(all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"))]
+const ATTEMPT_TYPES: &[u8] = b"c@:@Q\0";
+#[cfg(all(target_os = "ios", target_pointer_width = "32"))]
+const ATTEMPT_TYPES: &[u8] = b"c@:@I\0";
+#[cfg(not(any(
+    all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"),
+    all(target_os = "ios", target_pointer_width = "32"),
+)))]
+const ATTEMPT_TYPES: &[u8] = b"B@:@Q\0";
+
+#[cfg(target_pointer_width = "64")]
+const ATTEMPT_WITH_TYPES: &[u8] = b"v@:@Q@:^v\0";
+#[cfg(not(target_pointer_width = "64"))]
+const ATTEMPT_WITH_TYPES: &[u8] = b"v@:@I@:^v\0";
+
+/// Returns a class whose instances forward both recovery methods to their
+/// associated block.
+fn attempter_class() -> &'static Class {
+    static REGISTER: Once = Once::new();
+    static CLASS: AtomicPtr<Class> = AtomicPtr::new(ptr::null_mut());
+
+    unsafe extern "C" fn attempt(
+        this: &ObjCObject<'static>,
+        _cmd: Sel,
+        error: *const NSError<'static>,
+        index: NSUInteger,
+    ) -> BOOL {
+        let key: *const u8 = &ATTEMPTER_BLOCK_KEY;
+        let block = sys::objc_getAssociatedObject(this, key.cast());
+
+        let block = Block::<(*const NSError<'static>, NSUInteger), BOOL>::from_ptr(block.cast());
+        block.unwrap().call((error, index))
+    }
+
+    unsafe extern "C" fn attempt_with(
+        this: &ObjCObject<'static>,
+        cmd: Sel,
+        error: *const NSError<'static>,
+        index: NSUInteger,
+        delegate: *const ObjCObject<'static>,
+        did_recover_selector: Option<Sel>,
+        context_info: *mut c_void,
+    ) {
+        let did_recover = attempt(this, cmd, error, index);
+
+        // - (void)didPresentErrorWithRecovery:(BOOL)didRecover
+        //                         contextInfo:(void *)contextInfo;
+        if let (Some(delegate), Some(sel)) = (delegate.as_ref(), did_recover_selector) {
+            delegate._msg_send_any_with::<_, ()>(sel, (did_recover, context_info));
+        }
+    }
+
+    REGISTER.call_once(|| unsafe {
+        let class = allocate_private_class("_FruityErrorRecoveryAttempter", NSObject::class());
+        let class = class.as_ptr();
+
+        type Attempt = unsafe extern "C" fn(
+            &ObjCObject<'static>,
+            Sel,
+            *const NSError<'static>,
+            NSUInteger,
+        ) -> BOOL;
+        let imp = mem::transmute::<Attempt, Imp>(attempt);
+        let sel = selector!(attemptRecoveryFromError:optionIndex:);
+        sys::class_addMethod(class, sel, imp, ATTEMPT_TYPES.as_ptr().cast());
+
+        type AttemptWith = unsafe extern "C" fn(
+            &ObjCObject<'static>,
+            Sel,
+            *const NSError<'static>,
+            NSUInteger,
+            *const ObjCObject<'static>,
+            Option<Sel>,
+            *mut c_void,
+        );
+        let imp = mem::transmute::<AttemptWith, Imp>(attempt_with);
+        let sel = selector!(
+            attemptRecoveryFromError:
+            optionIndex:
+            delegate:
+            didRecoverSelector:
+            contextInfo:
+        );
+        sys::class_addMethod(class, sel, imp, ATTEMPT_WITH_TYPES.as_ptr().cast());
+
+        sys::objc_registerClassPair(class);
+        CLASS.store(class, Ordering::Release);
+    });
+
+    unsafe { &*CLASS.load(Ordering::Acquire) }
+}
+
+/// Providing recovery attempters.
+impl NSErrorRecoveryAttempting<'static> {
+    /// Creates a recovery attempter that calls `f` with the error and the
+    /// index of the chosen
+    /// [recovery option](struct.NSError.html#method.localized_recovery_options),
+    /// and reports whether `f` returned `true`.
+    ///
+    /// This implements both recovery methods. For
+    /// [`attempt_recovery_with`](#method.attempt_recovery_with), the delegate
+    /// is then sent the result and context info.
+    ///
+    /// `f` must be `Send` and `Sync` because errors may be shared across
+    /// threads. `f` is dropped along with the attempter, and panics in `f`
+    /// abort the process.
+    ///
+    /// Use with
+    /// [`NSErrorBuilder::recovery_attempter`](struct.NSErrorBuilder.html#method.recovery_attempter).
+    pub fn from_fn<F>(f: F) -> Arc<Self>
+    where
+        F: Fn(&NSError<'static>, NSUInteger) -> bool + Send + Sync + 'static,
+    {
+        let block = RcBlock::<(*const NSError<'static>, NSUInteger), _>::without_signature(
+            move |error: *const NSError<'static>, index: NSUInteger| {
+                let error = unsafe { &*error };
+                BOOL::from(abort_on_panic(|| f(error, index)))
+            },
+        );
+
+        // The attempter retains the block until deallocated.
+        let attempter: Arc<ObjCObject<'static>> = unsafe { attempter_class().alloc_init() };
+        let key: *const u8 = &ATTEMPTER_BLOCK_KEY;
+        unsafe {
+            sys::objc_setAssociatedObject(
+                &*attempter,
+                key.cast(),
+                block.as_ptr().cast(),
+                OBJC_ASSOCIATION_RETAIN_NONATOMIC,
+            )
+        };
+
+        attempter.into()
+    }
 }
 
 impl NSErrorRecoveryAttempting<'_> {
@@ -36,8 +190,7 @@ impl NSErrorRecoveryAttempting<'_> {
     pub fn attempt_recovery(&self, error: &NSError, recovery_option_index: NSUInteger) -> bool {
         let sel = selector!(attemptRecoveryFromError:optionIndex:);
 
-        let this: &NSObject = &self.0;
-        if !this.responds_to_selector(sel) {
+        if !self.responds_to_selector(sel) {
             return false;
         }
 
@@ -71,8 +224,7 @@ impl NSErrorRecoveryAttempting<'_> {
             contextInfo:
         );
 
-        let this: &NSObject = &self.0;
-        if !this.responds_to_selector(sel) {
+        if !self.responds_to_selector(sel) {
             return;
         }
 
@@ -93,3 +245,57 @@ impl NSErrorRecoveryAttempting<'_> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSErrorDomain;
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize},
+        Arc as StdArc,
+    };
+
+    // Sets a flag when dropped along with an attempter's closure.
+    struct DropFlag(StdArc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn from_fn() {
+        let calls = StdArc::new(AtomicUsize::new(0));
+        let dropped = StdArc::new(AtomicBool::new(false));
+
+        let attempter = {
+            let calls = calls.clone();
+            let flag = DropFlag(dropped.clone());
+            NSErrorRecoveryAttempting::from_fn(move |error, index| {
+                let _ = &flag;
+                calls.fetch_add(1, Ordering::SeqCst);
+                error.code() == 42 && index == 0
+            })
+        };
+
+        let error = NSError::builder(NSErrorDomain::cocoa(), 42)
+            .recovery_options(&["Retry", "Cancel"])
+            .recovery_attempter(&attempter)
+            .build();
+        drop(attempter);
+
+        let attempter = error.recovery_attempter().unwrap();
+        assert!(attempter.attempt_recovery(&error, 0));
+        assert!(!attempter.attempt_recovery(&error, 1));
+
+        // Without a delegate, nothing is notified.
+        unsafe { attempter.attempt_recovery_with(&error, 0, None, None, ptr::null_mut()) };
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        drop(attempter);
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(error);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
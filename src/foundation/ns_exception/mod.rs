@@ -1,6 +1,6 @@
-use super::NSString;
+use super::{NSArray, NSDictionary, NSNumber, NSString};
 use crate::core::Arc;
-use crate::objc::NSObject;
+use crate::objc::{retain_autoreleased, ClassType, NSObject};
 
 mod name;
 
@@ -50,6 +50,29 @@ objc_subclass! {
 
 /// Creating and rasing exceptions.
 impl NSException {
+    /// Creates an exception that can be raised with [`raise`](#method.raise).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1415666-exceptionwithname).
+    #[doc(alias = "exceptionWithName")]
+    #[doc(alias = "exceptionWithName:reason:userInfo:")]
+    pub fn new(
+        name: &NSExceptionName,
+        reason: Option<&NSString>,
+        user_info: Option<&NSDictionary<NSString<'static>, NSObject<'static>>>,
+    ) -> Arc<Self> {
+        let exception = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    Self::class(),
+                    exceptionWithName: name
+                    reason: reason
+                    userInfo: user_info
+                ]
+            })
+        };
+        exception.expect("Failed to create exception")
+    }
+
     /// Raises the receiver, causing program flow to jump to the local exception
     /// handler.
     ///
@@ -63,8 +86,7 @@ impl NSException {
     #[inline]
     #[doc(alias = "objc_exception_throw")]
     pub fn raise(&self) -> ! {
-        // TODO(#7): Use "C-unwind" ABI when stable.
-        extern "C" {
+        extern "C-unwind" {
             fn objc_exception_throw(exception: &NSException) -> !;
         }
         unsafe { objc_exception_throw(self) }
@@ -78,23 +100,57 @@ impl NSException {
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1410925-name).
     #[inline]
     pub fn name(&self) -> Arc<NSExceptionName> {
-        unsafe { _msg_send_any![self, name] }
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, name]) };
+        name.expect("Exception has no name")
     }
 
     /// Returns a containing a "human-readable" reason for `self`.
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1415537-reason).
     #[inline]
-    pub fn reason(&self) -> Option<Arc<NSString>> {
-        unsafe { _msg_send_any![self, reason] }
+    pub fn reason(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, reason]) }
     }
 
-    // TODO: `userInfo`
+    /// Returns application-specific data associated with `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1414333-userinfo).
+    #[inline]
+    #[doc(alias = "userInfo")]
+    pub fn user_info(&self) -> Option<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, userInfo]) }
+    }
 }
 
 /// Getting exception stack frames.
+///
+/// These are empty until `self` is raised.
 impl NSException {
-    // TODO: `callStackReturnAddresses`
+    /// Returns the return addresses of the call stack where `self` was raised.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1409931-callstackreturnaddresses).
+    #[doc(alias = "callStackReturnAddresses")]
+    pub fn call_stack_return_addresses(&self) -> Vec<usize> {
+        let addresses: Option<Arc<NSArray<NSNumber>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, callStackReturnAddresses]) };
+
+        addresses.map_or_else(Vec::new, |addresses| {
+            addresses
+                .iter()
+                .map(|address| address.unsigned_integer_value())
+                .collect()
+        })
+    }
 
-    // TODO: `callStackSymbols`
+    /// Returns the symbolicated call stack where `self` was raised, one frame
+    /// per string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsexception/1416207-callstacksymbols).
+    #[doc(alias = "callStackSymbols")]
+    pub fn call_stack_symbols(&self) -> Vec<Arc<NSString<'static>>> {
+        let symbols: Option<Arc<NSArray<NSString<'static>>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, callStackSymbols]) };
+
+        symbols.map_or_else(Vec::new, |symbols| symbols.to_vec())
+    }
 }
@@ -0,0 +1,408 @@
+use super::{ns_error::with_error_out, NSData, NSError, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger, BOOL};
+use std::{
+    io::{self, SeekFrom},
+    path::Path,
+};
+
+objc_subclass! {
+    /// An object-oriented wrapper for a file descriptor.
+    ///
+    /// Methods take `&self` because file handles are safe to share between
+    /// threads. Like [`File`](https://doc.rust-lang.org/std/fs/struct.File.html),
+    /// both `NSFileHandle` and `&NSFileHandle` implement
+    /// [`Read`](https://doc.rust-lang.org/std/io/trait.Read.html),
+    /// [`Write`](https://doc.rust-lang.org/std/io/trait.Write.html), and
+    /// [`Seek`](https://doc.rust-lang.org/std/io/trait.Seek.html).
+    ///
+    /// This uses the methods that report failures with
+    /// [`NSError`](struct.NSError.html), which require macOS 10.15 or iOS 13.
+    /// The older methods raise exceptions instead, which cannot be caught
+    /// from Rust.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle).
+    #[derive(PartialEq)]
+    pub class NSFileHandle: NSObject<'static>;
+}
+
+#[inline]
+fn ok(result: BOOL) -> Option<()> {
+    if result.is_yes() {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Opening files.
+impl NSFileHandle {
+    /// Opens the file at `path` for reading.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1408422-filehandleforreadingfromurl).
+    #[doc(alias = "fileHandleForReadingFromURL")]
+    #[doc(alias = "fileHandleForReadingFromURL:error:")]
+    pub fn for_reading_at_path(path: &Path) -> Result<Arc<Self>, Arc<NSError<'static>>> {
+        let url = NSURL::file_url(path, false);
+        unsafe {
+            with_error_out(|error| {
+                let handle: *const Self =
+                    _msg_send_any![Self::class(), fileHandleForReadingFromURL: &*url error: error];
+                handle.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Opens the existing file at `path` for writing, starting at the
+    /// beginning of the file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1402810-filehandleforwritingtourl).
+    #[doc(alias = "fileHandleForWritingToURL")]
+    #[doc(alias = "fileHandleForWritingToURL:error:")]
+    pub fn for_writing_at_path(path: &Path) -> Result<Arc<Self>, Arc<NSError<'static>>> {
+        let url = NSURL::file_url(path, false);
+        unsafe {
+            with_error_out(|error| {
+                let handle: *const Self =
+                    _msg_send_any![Self::class(), fileHandleForWritingToURL: &*url error: error];
+                handle.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Opens the existing file at `url` for reading and writing.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1408385-filehandleforupdatingurl).
+    #[doc(alias = "fileHandleForUpdatingURL")]
+    #[doc(alias = "fileHandleForUpdatingURL:error:")]
+    pub fn for_updating_at_url(url: &NSURL) -> Result<Arc<Self>, Arc<NSError<'static>>> {
+        unsafe {
+            with_error_out(|error| {
+                let handle: *const Self =
+                    _msg_send_any![Self::class(), fileHandleForUpdatingURL: url error: error];
+                handle.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Returns the handle for the standard input of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1416190-filehandlewithstandardinput).
+    #[inline]
+    #[doc(alias = "fileHandleWithStandardInput")]
+    pub fn standard_input() -> Arc<Self> {
+        let handle = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), fileHandleWithStandardInput])
+        };
+        handle.expect("No standard input")
+    }
+
+    /// Returns the handle for the standard output of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1414273-filehandlewithstandardoutput).
+    #[inline]
+    #[doc(alias = "fileHandleWithStandardOutput")]
+    pub fn standard_output() -> Arc<Self> {
+        let handle = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), fileHandleWithStandardOutput])
+        };
+        handle.expect("No standard output")
+    }
+
+    /// Returns the handle for the standard error of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/1411301-filehandlewithstandarderror).
+    #[inline]
+    #[doc(alias = "fileHandleWithStandardError")]
+    pub fn standard_error() -> Arc<Self> {
+        let handle = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), fileHandleWithStandardError])
+        };
+        handle.expect("No standard error")
+    }
+}
+
+/// Reading and writing.
+impl NSFileHandle {
+    /// Reads up to `len` bytes from the current offset, returning empty data
+    /// at the end of the file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3516317-readdatauptolength).
+    #[doc(alias = "readDataUpToLength")]
+    #[doc(alias = "readDataUpToLength:error:")]
+    pub fn read_up_to(&self, len: usize) -> Result<Arc<NSData>, Arc<NSError<'static>>> {
+        let len: NSUInteger = len;
+        let data = unsafe {
+            with_error_out(|error| {
+                let data: *const NSData =
+                    _msg_send_any![self, readDataUpToLength: len error: error];
+
+                // At the end of the file, the result may be `nil` without an
+                // error.
+                match data.as_ref() {
+                    Some(data) => Some(Some(Arc::retain(data))),
+                    None if (*error).is_null() => Some(None),
+                    None => None,
+                }
+            })
+        }?;
+        Ok(data.unwrap_or_else(|| NSData::from_slice(&[])))
+    }
+
+    /// Writes all of `bytes` at the current offset.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3516320-writedata).
+    #[doc(alias = "writeData")]
+    #[doc(alias = "writeData:error:")]
+    pub fn write_all(&self, bytes: &[u8]) -> Result<(), Arc<NSError<'static>>> {
+        let data = NSData::from_slice(bytes);
+        unsafe { with_error_out(|error| ok(_msg_send_any![self, writeData: &*data error: error])) }
+    }
+
+    /// Truncates or extends the file to `len` bytes, and moves the current
+    /// offset to `len`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3172528-truncateatoffset).
+    #[doc(alias = "truncateAtOffset")]
+    #[doc(alias = "truncateAtOffset:error:")]
+    pub fn truncate(&self, len: u64) -> Result<(), Arc<NSError<'static>>> {
+        unsafe {
+            with_error_out(|error| ok(_msg_send_any![self, truncateAtOffset: len error: error]))
+        }
+    }
+
+    /// Closes the file descriptor.
+    ///
+    /// Further operations on `self` fail, so this is only needed to release
+    /// the descriptor before all references are dropped.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3172525-close).
+    #[doc(alias = "closeAndReturnError")]
+    #[doc(alias = "closeAndReturnError:")]
+    pub fn close(&self) -> Result<(), Arc<NSError<'static>>> {
+        unsafe { with_error_out(|error| ok(_msg_send_any![self, closeAndReturnError: error])) }
+    }
+}
+
+/// Seeking.
+impl NSFileHandle {
+    /// Returns the current offset in the file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3172526-getoffset).
+    #[doc(alias = "getOffset")]
+    #[doc(alias = "getOffset:error:")]
+    pub fn offset(&self) -> Result<u64, Arc<NSError<'static>>> {
+        let mut offset: u64 = 0;
+        let offset_ptr: *mut u64 = &mut offset;
+        unsafe {
+            with_error_out(|error| ok(_msg_send_any![self, getOffset: offset_ptr error: error]))
+        }?;
+        Ok(offset)
+    }
+
+    /// Moves the current offset to the end of the file and returns it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3172529-seektoendreturningoffset).
+    #[doc(alias = "seekToEndReturningOffset")]
+    #[doc(alias = "seekToEndReturningOffset:error:")]
+    pub fn seek_to_end(&self) -> Result<u64, Arc<NSError<'static>>> {
+        let mut offset: u64 = 0;
+        let offset_ptr: *mut u64 = &mut offset;
+        unsafe {
+            with_error_out(|error| {
+                ok(_msg_send_any![self, seekToEndReturningOffset: offset_ptr error: error])
+            })
+        }?;
+        Ok(offset)
+    }
+
+    /// Moves the current offset to `offset`, which may be past the end of the
+    /// file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilehandle/3172530-seektooffset).
+    #[doc(alias = "seekToOffset")]
+    #[doc(alias = "seekToOffset:error:")]
+    pub fn seek_to_offset(&self, offset: u64) -> Result<(), Arc<NSError<'static>>> {
+        unsafe {
+            with_error_out(|error| ok(_msg_send_any![self, seekToOffset: offset error: error]))
+        }
+    }
+}
+
+impl io::Read for &NSFileHandle {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let data = self.read_up_to(buf.len()).map_err(io::Error::from)?;
+        let bytes = data.as_slice();
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok(bytes.len())
+    }
+}
+
+impl io::Write for &NSFileHandle {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // `io::Write::write_all` would recurse.
+        NSFileHandle::write_all(self, buf).map_err(io::Error::from)?;
+        Ok(buf.len())
+    }
+
+    /// Does nothing, because `NSFileHandle` does not buffer writes.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Seek for &NSFileHandle {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, delta) = match pos {
+            SeekFrom::Start(offset) => {
+                self.seek_to_offset(offset).map_err(io::Error::from)?;
+                return Ok(offset);
+            }
+            SeekFrom::End(delta) => (self.seek_to_end(), delta),
+            SeekFrom::Current(delta) => (self.offset(), delta),
+        };
+
+        let base = base.map_err(io::Error::from)?;
+        if delta == 0 {
+            return Ok(base);
+        }
+
+        let offset = if delta < 0 {
+            base.checked_sub(delta.unsigned_abs())
+        } else {
+            base.checked_add(delta as u64)
+        };
+        let offset = offset.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        self.seek_to_offset(offset).map_err(io::Error::from)?;
+        Ok(offset)
+    }
+}
+
+impl io::Read for NSFileHandle {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+impl io::Write for NSFileHandle {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+impl io::Seek for NSFileHandle {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (&*self).seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use std::{
+        fs,
+        io::{Read, Seek, Write},
+    };
+
+    #[test]
+    fn round_trip() {
+        let dir = TempDir::new("file-handle-round-trip");
+        let file = dir.0.join("file");
+        fs::write(&file, "").unwrap();
+
+        let writer = NSFileHandle::for_writing_at_path(&file).unwrap();
+        writer.write_all(b"hello").unwrap();
+        write!(&*writer, " world").unwrap();
+        assert_eq!(writer.offset().unwrap(), 11);
+        writer.close().unwrap();
+        assert!(writer.write_all(b"!").is_err());
+
+        let reader = NSFileHandle::for_reading_at_path(&file).unwrap();
+        assert_eq!(reader.read_up_to(5).unwrap().as_slice(), b"hello");
+        assert_eq!(reader.offset().unwrap(), 5);
+
+        let mut rest = Vec::new();
+        (&*reader).read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b" world");
+        assert!(reader.read_up_to(5).unwrap().is_empty());
+        assert!(reader.write_all(b"!").is_err());
+    }
+
+    #[test]
+    fn seek() {
+        let dir = TempDir::new("file-handle-seek");
+        let file = dir.0.join("file");
+        fs::write(&file, "").unwrap();
+        let url = NSURL::file_url(&file, false);
+        let mut handle = NSFileHandle::for_updating_at_url(&url).unwrap();
+        let handle = Arc::get_mut(&mut handle).unwrap();
+
+        handle.write_all(b"abc").unwrap();
+        assert_eq!(handle.seek(SeekFrom::End(0)).unwrap(), 3);
+        assert_eq!(handle.seek(SeekFrom::Current(-1)).unwrap(), 2);
+
+        // Reading past the end returns nothing, and writing past the end
+        // fills the gap with zeros.
+        assert_eq!(handle.seek(SeekFrom::Start(6)).unwrap(), 6);
+        assert_eq!(handle.read(&mut [0; 4]).unwrap(), 0);
+        handle.write_all(b"d").unwrap();
+        assert_eq!(fs::read(&file).unwrap(), b"abc\0\0\0d");
+
+        assert_eq!(handle.seek(SeekFrom::End(-1)).unwrap(), 6);
+        let error = handle.seek(SeekFrom::Current(-10)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(handle.offset().unwrap(), 6);
+
+        handle.truncate(2).unwrap();
+        assert_eq!(handle.offset().unwrap(), 2);
+        assert_eq!(fs::read(&file).unwrap(), b"ab");
+    }
+
+    #[test]
+    fn missing() {
+        let dir = TempDir::new("file-handle-missing");
+        let missing = dir.0.join("missing");
+
+        for result in &[
+            NSFileHandle::for_reading_at_path(&missing),
+            NSFileHandle::for_writing_at_path(&missing),
+        ] {
+            let error = match result {
+                Ok(_) => panic!("Opened missing file"),
+                Err(error) => error,
+            };
+            assert_eq!(
+                io::Error::from(error.clone()).kind(),
+                io::ErrorKind::NotFound
+            );
+        }
+    }
+
+    #[test]
+    fn standard() {
+        let (input, output) = (
+            NSFileHandle::standard_input(),
+            NSFileHandle::standard_output(),
+        );
+        assert!(*input != *output);
+        assert!(*NSFileHandle::standard_error() != *output);
+    }
+}
@@ -0,0 +1,122 @@
+use super::super::NSURL;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, NSObject, NSUInteger};
+use std::{any::Any, cell::RefCell, ops, rc::Rc};
+
+/// Options for [`NSFileManager::enumerator_at_url`](struct.NSFileManager.html#method.enumerator_at_url).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsdirectoryenumerationoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSDirectoryEnumerationOptions(pub NSUInteger);
+
+impl NSDirectoryEnumerationOptions {
+    /// Do not descend into directories.
+    pub const SKIPS_SUBDIRECTORY_DESCENDANTS: Self = Self(1 << 0);
+
+    /// Do not descend into packages, such as app bundles.
+    pub const SKIPS_PACKAGE_DESCENDANTS: Self = Self(1 << 1);
+
+    /// Skip hidden files, such as those whose names start with `.`.
+    pub const SKIPS_HIDDEN_FILES: Self = Self(1 << 2);
+
+    /// Also return each directory after its contents.
+    pub const INCLUDES_DIRECTORIES_POST_ORDER: Self = Self(1 << 3);
+
+    /// Return URLs relative to the enumerated directory.
+    pub const PRODUCES_RELATIVE_PATH_URLS: Self = Self(1 << 4);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSDirectoryEnumerationOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSDirectoryEnumerationOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSDirectoryEnumerationOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// A panic caught in an error handler, to be resumed by the enumerator.
+pub(super) type PanicSlot = Rc<RefCell<Option<Box<dyn Any + Send>>>>;
+
+/// An iterator over the URLs in a directory tree, created by
+/// [`NSFileManager::enumerator_at_url`](struct.NSFileManager.html#method.enumerator_at_url).
+///
+/// This wraps an `NSDirectoryEnumerator`, which walks the tree lazily in
+/// depth-first order.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsdirectoryenumerator).
+pub struct DirectoryEnumerator {
+    enumerator: Arc<NSObject<'static>>,
+    panic: PanicSlot,
+}
+
+impl DirectoryEnumerator {
+    #[inline]
+    pub(super) fn new(enumerator: Arc<NSObject<'static>>, panic: PanicSlot) -> Self {
+        Self { enumerator, panic }
+    }
+
+    /// Returns the depth of the last URL returned, where items directly in the
+    /// enumerated directory are at level 1.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdirectoryenumerator/1416436-level).
+    #[inline]
+    pub fn level(&self) -> usize {
+        unsafe { _msg_send_any![self.enumerator, level] }
+    }
+
+    /// Prevents descending into the directory that was last returned.
+    ///
+    /// This does nothing if the last URL returned is not a directory.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdirectoryenumerator/1418497-skipdescendants).
+    #[inline]
+    #[doc(alias = "skipDescendants")]
+    #[doc(alias = "skipDescendents")]
+    pub fn skip_descendants(&mut self) {
+        unsafe { _msg_send_any![self.enumerator, skipDescendants => ()] }
+    }
+}
+
+impl Iterator for DirectoryEnumerator {
+    type Item = Arc<NSURL>;
+
+    /// Returns the next URL, or `None` once the tree has been walked or the
+    /// error handler has stopped enumeration.
+    ///
+    /// # Panics
+    ///
+    /// Resumes a panic from the error handler.
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let url = unsafe { retain_autoreleased(|| _msg_send_any![self.enumerator, nextObject]) };
+
+        if let Some(payload) = self.panic.borrow_mut().take() {
+            std::panic::resume_unwind(payload);
+        }
+        url
+    }
+}
@@ -0,0 +1,534 @@
+use super::{ns_error::with_error_out, NSArray, NSDictionary, NSError, NSString, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger, RcBlock, BOOL};
+use directory_enumerator::PanicSlot;
+use std::{
+    cell::RefCell,
+    os::{raw::c_char, unix::ffi::OsStrExt},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    ptr,
+};
+
+mod directory_enumerator;
+mod resource_key;
+mod search_path;
+
+pub use directory_enumerator::{DirectoryEnumerator, NSDirectoryEnumerationOptions};
+pub use resource_key::*;
+pub use search_path::*;
+
+objc_subclass! {
+    /// A convenient interface to the contents of the file system.
+    ///
+    /// Paths are converted using their file system representation, so paths
+    /// that are not valid UTF-8 are passed through unchanged.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager).
+    pub class NSFileManager: NSObject<'static>;
+}
+
+#[inline]
+fn ok(result: BOOL) -> Option<()> {
+    if result.is_yes() {
+        Some(())
+    } else {
+        None
+    }
+}
+
+impl NSFileManager {
+    /// Returns the shared file manager object.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1409234-defaultmanager).
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "defaultManager")]
+    pub fn default() -> Arc<Self> {
+        let manager =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), defaultManager]) };
+        manager.expect("No default file manager")
+    }
+
+    /// Converts `path` to a string for methods that take paths rather than
+    /// URLs.
+    fn path_string(&self, path: &Path) -> Arc<NSString<'static>> {
+        let bytes = path.as_os_str().as_bytes();
+        let (ptr, len) = (bytes.as_ptr() as *const c_char, bytes.len());
+        let string = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, stringWithFileSystemRepresentation: ptr length: len],
+            )
+        };
+
+        // A lossy path refers to a different file, so operations on it fail
+        // as if `path` did not exist.
+        string.unwrap_or_else(|| NSString::from_str(&path.to_string_lossy()))
+    }
+}
+
+/// Querying items.
+impl NSFileManager {
+    /// Returns whether an item exists at `path`, and whether it is a
+    /// directory.
+    ///
+    /// Symbolic links are followed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1410277-fileexistsatpath).
+    #[doc(alias = "fileExistsAtPath")]
+    #[doc(alias = "fileExistsAtPath:isDirectory:")]
+    pub fn file_exists(&self, path: &Path) -> (bool, bool) {
+        let path = self.path_string(path);
+        let mut is_dir = BOOL::NO;
+        let is_dir_ptr: *mut BOOL = &mut is_dir;
+        let exists: bool = unsafe {
+            _msg_send_any![self, fileExistsAtPath: &*path isDirectory: is_dir_ptr => BOOL]
+        }
+        .into();
+        (exists, exists && is_dir.is_yes())
+    }
+
+    /// Returns the full paths of the items in the directory at `path`.
+    ///
+    /// This does a shallow search, and does not return `.` or `..`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1413768-contentsofdirectoryaturl).
+    #[doc(alias = "contentsOfDirectoryAtURL")]
+    #[doc(alias = "contentsOfDirectoryAtURL:includingPropertiesForKeys:options:error:")]
+    pub fn contents_of_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Vec<PathBuf>, Arc<NSError<'static>>> {
+        let url = NSURL::file_url(path, true);
+        let keys: *const NSArray<NSString<'static>> = ptr::null();
+        let options: NSUInteger = 0;
+
+        unsafe {
+            with_error_out(|error| {
+                let urls: *const NSArray<NSURL> = _msg_send_any![
+                    self,
+                    contentsOfDirectoryAtURL: &*url
+                    includingPropertiesForKeys: keys
+                    options: options
+                    error: error
+                ];
+                let urls = urls.as_ref()?;
+                Some(urls.iter().filter_map(|url| url.to_path_buf()).collect())
+            })
+        }
+    }
+
+    /// Returns an iterator over the URLs in the directory tree at `url`.
+    ///
+    /// The values of `keys` are prefetched for each URL, and can be read with
+    /// [`NSURL::resource_value`](struct.NSURL.html#method.resource_value).
+    ///
+    /// If an item cannot be read, `error_handler` is called with its URL and
+    /// the error, and enumeration stops unless it returns `true`. If
+    /// `error_handler` panics, enumeration stops and the panic is resumed by
+    /// the iterator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1409571-enumeratoraturl).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` is not a file URL.
+    #[doc(alias = "enumeratorAtURL")]
+    #[doc(alias = "enumeratorAtURL:includingPropertiesForKeys:options:errorHandler:")]
+    pub fn enumerator_at_url<F>(
+        &self,
+        url: &NSURL,
+        keys: &[&NSURLResourceKey],
+        options: NSDirectoryEnumerationOptions,
+        error_handler: F,
+    ) -> DirectoryEnumerator
+    where
+        F: FnMut(&NSURL, &NSError<'static>) -> bool + 'static,
+    {
+        assert!(url.is_file_url(), "URL is not a file URL");
+
+        let keys = NSArray::from_slice(keys);
+        let panic = PanicSlot::default();
+
+        // Foundation copies the block and only calls it from `nextObject`, so
+        // the closure need not be `Send`.
+        let error_handler = RefCell::new(error_handler);
+        let handler_panic = panic.clone();
+        let block = RcBlock::<(*const NSURL, *const NSError<'static>), _>::without_signature(
+            move |url: *const NSURL, error: *const NSError<'static>| {
+                let (url, error) = unsafe { (&*url, &*error) };
+
+                // Unwinding into Foundation is undefined behavior.
+                let proceed = panic::catch_unwind(AssertUnwindSafe(|| {
+                    (error_handler.borrow_mut())(url, error)
+                }));
+                match proceed {
+                    Ok(proceed) => BOOL::from(proceed),
+                    Err(payload) => {
+                        *handler_panic.borrow_mut() = Some(payload);
+                        BOOL::NO
+                    }
+                }
+            },
+        );
+
+        let enumerator = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    self,
+                    enumeratorAtURL: url
+                    includingPropertiesForKeys: &*keys
+                    options: options
+                    errorHandler: block.as_ptr()
+                ]
+            })
+        };
+        let enumerator = enumerator.expect("Failed to create directory enumerator");
+        DirectoryEnumerator::new(enumerator, panic)
+    }
+
+    /// Returns the attributes of the item at `path`, keyed by names such as
+    /// `NSFileSize` and `NSFileModificationDate`.
+    ///
+    /// Symbolic links are not followed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1410452-attributesofitematpath).
+    #[doc(alias = "attributesOfItemAtPath")]
+    #[doc(alias = "attributesOfItemAtPath:error:")]
+    pub fn attributes_of_item(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>, Arc<NSError<'static>>>
+    {
+        let path = self.path_string(path);
+        unsafe {
+            with_error_out(|error| {
+                let attributes: *const NSDictionary<NSString<'static>, NSObject<'static>> =
+                    _msg_send_any![self, attributesOfItemAtPath: &*path error: error];
+                attributes.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Returns the URL of a system directory such as
+    /// [`APPLICATION_SUPPORT`](struct.NSSearchPathDirectory.html#associatedconstant.APPLICATION_SUPPORT)
+    /// or [`CACHES`](struct.NSSearchPathDirectory.html#associatedconstant.CACHES)
+    /// in the first of `domains`.
+    ///
+    /// The directory is not created if it does not exist.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1407693-urlfordirectory).
+    #[doc(alias = "URLForDirectory")]
+    #[doc(alias = "URLForDirectory:inDomain:appropriateForURL:create:error:")]
+    pub fn url_for_directory(
+        &self,
+        directory: NSSearchPathDirectory,
+        domains: NSSearchPathDomainMask,
+    ) -> Result<Arc<NSURL>, Arc<NSError<'static>>> {
+        let appropriate_for: *const NSURL = ptr::null();
+        let create = BOOL::NO;
+
+        unsafe {
+            with_error_out(|error| {
+                let url: *const NSURL = _msg_send_any![
+                    self,
+                    URLForDirectory: directory
+                    inDomain: domains
+                    appropriateForURL: appropriate_for
+                    create: create
+                    error: error
+                ];
+                url.as_ref().map(Arc::retain)
+            })
+        }
+    }
+}
+
+/// Managing items.
+impl NSFileManager {
+    /// Creates a directory at `path`.
+    ///
+    /// If `intermediate` is `true`, missing parent directories are created,
+    /// and an existing directory at `path` is not an error.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1415371-createdirectoryaturl).
+    #[doc(alias = "createDirectoryAtURL")]
+    #[doc(alias = "createDirectoryAtURL:withIntermediateDirectories:attributes:error:")]
+    pub fn create_directory(
+        &self,
+        path: &Path,
+        intermediate: bool,
+    ) -> Result<(), Arc<NSError<'static>>> {
+        let url = NSURL::file_url(path, true);
+        let intermediate = BOOL::from(intermediate);
+        let attributes: *const NSDictionary<NSString<'static>, NSObject<'static>> = ptr::null();
+
+        unsafe {
+            with_error_out(|error| {
+                ok(_msg_send_any![
+                    self,
+                    createDirectoryAtURL: &*url
+                    withIntermediateDirectories: intermediate
+                    attributes: attributes
+                    error: error
+                ])
+            })
+        }
+    }
+
+    /// Removes the file or directory at `path`, including the contents of a
+    /// directory.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1413590-removeitematurl).
+    #[doc(alias = "removeItemAtURL")]
+    #[doc(alias = "removeItemAtURL:error:")]
+    pub fn remove_item(&self, path: &Path) -> Result<(), Arc<NSError<'static>>> {
+        let url = NSURL::file_url(path, false);
+        unsafe {
+            with_error_out(|error| ok(_msg_send_any![self, removeItemAtURL: &*url error: error]))
+        }
+    }
+
+    /// Copies the item at `from` to `to`, which must not exist.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1412957-copyitematurl).
+    #[doc(alias = "copyItemAtURL")]
+    #[doc(alias = "copyItemAtURL:toURL:error:")]
+    pub fn copy_item(&self, from: &Path, to: &Path) -> Result<(), Arc<NSError<'static>>> {
+        let (from, to) = (NSURL::file_url(from, false), NSURL::file_url(to, false));
+        unsafe {
+            with_error_out(|error| {
+                ok(_msg_send_any![self, copyItemAtURL: &*from toURL: &*to error: error])
+            })
+        }
+    }
+
+    /// Moves the item at `from` to `to`, which must not exist.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsfilemanager/1413529-moveitematurl).
+    #[doc(alias = "moveItemAtURL")]
+    #[doc(alias = "moveItemAtURL:toURL:error:")]
+    pub fn move_item(&self, from: &Path, to: &Path) -> Result<(), Arc<NSError<'static>>> {
+        let (from, to) = (NSURL::file_url(from, false), NSURL::file_url(to, false));
+        unsafe {
+            with_error_out(|error| {
+                ok(_msg_send_any![self, moveItemAtURL: &*from toURL: &*to error: error])
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use crate::foundation::NSNumber;
+    use std::{cell::Cell, ffi::OsStr, fs, rc::Rc};
+
+    fn check<T>(result: Result<T, Arc<NSError>>) -> T {
+        match result {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[test]
+    fn directories() {
+        let manager = NSFileManager::default();
+        let dir = TempDir::new("directories");
+
+        let nested = dir.0.join("a/b");
+        assert!(manager.create_directory(&nested, false).is_err());
+        check(manager.create_directory(&nested, true));
+        check(manager.create_directory(&nested, true));
+        assert_eq!(manager.file_exists(&nested), (true, true));
+
+        let file = dir.0.join("a/file.txt");
+        fs::write(&file, "hello").unwrap();
+        assert_eq!(manager.file_exists(&file), (true, false));
+        assert_eq!(manager.file_exists(&dir.0.join("missing")), (false, false));
+
+        let mut contents = check(manager.contents_of_directory(&dir.0.join("a")));
+        contents.sort();
+        assert_eq!(contents, [dir.0.join("a/b"), file]);
+
+        check(manager.remove_item(&dir.0.join("a")));
+        assert_eq!(manager.file_exists(&nested), (false, false));
+        assert!(manager.remove_item(&dir.0.join("a")).is_err());
+    }
+
+    #[test]
+    fn copy_and_move() {
+        let manager = NSFileManager::default();
+        let dir = TempDir::new("copy-and-move");
+
+        let original = dir.0.join("original.txt");
+        fs::write(&original, "hello").unwrap();
+
+        let copy = dir.0.join("é copy.txt");
+        check(manager.copy_item(&original, &copy));
+        assert!(manager.copy_item(&original, &copy).is_err());
+        assert_eq!(fs::read_to_string(&copy).unwrap(), "hello");
+
+        let moved = dir.0.join("moved.txt");
+        check(manager.move_item(&copy, &moved));
+        assert_eq!(manager.file_exists(&copy), (false, false));
+
+        let mut contents = check(manager.contents_of_directory(&dir.0));
+        contents.sort();
+        let mut expected: Vec<PathBuf> = fs::read_dir(&dir.0)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        expected.sort();
+        assert_eq!(contents, expected);
+
+        let attributes = check(manager.attributes_of_item(&moved));
+        let size = attributes
+            .get_as::<NSNumber>(&NSString::from_str("NSFileSize"))
+            .unwrap();
+        assert_eq!(size.as_usize(), Some(5));
+    }
+
+    // Creates `.hidden`, `a.txt`, `sub/b.txt`, and `sub/deeper/c.txt`.
+    fn tree(name: &str) -> TempDir {
+        let dir = TempDir::new(name);
+        fs::create_dir_all(dir.0.join("sub/deeper")).unwrap();
+        for file in &[".hidden", "a.txt", "sub/b.txt", "sub/deeper/c.txt"] {
+            fs::write(dir.0.join(file), "").unwrap();
+        }
+        dir
+    }
+
+    fn relative_paths(dir: &TempDir, urls: impl Iterator<Item = Arc<NSURL>>) -> Vec<String> {
+        let mut paths: Vec<String> = urls
+            .map(|url| {
+                let path = url.to_path_buf().unwrap();
+                path.strip_prefix(&dir.0)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn no_errors(url: &NSURL, error: &NSError) -> bool {
+        panic!("Failed to enumerate {:?}: {}", url, error);
+    }
+
+    #[test]
+    fn enumerate_tree() {
+        let manager = NSFileManager::default();
+        let dir = tree("enumerate-tree");
+        let url = NSURL::file_url(&dir.0, true);
+
+        let keys = [NSURLResourceKey::is_directory()];
+        let options = NSDirectoryEnumerationOptions::default();
+        let urls: Vec<_> = manager
+            .enumerator_at_url(&url, &keys, options, no_errors)
+            .collect();
+
+        for url in &urls {
+            let is_dir = check(url.resource_value(NSURLResourceKey::is_directory())).unwrap();
+            let expected = url.to_path_buf().unwrap().is_dir();
+            assert!(*is_dir == ***NSNumber::from_bool(expected));
+        }
+
+        assert_eq!(
+            relative_paths(&dir, urls.into_iter()),
+            [
+                ".hidden",
+                "a.txt",
+                "sub",
+                "sub/b.txt",
+                "sub/deeper",
+                "sub/deeper/c.txt"
+            ]
+        );
+
+        let options = NSDirectoryEnumerationOptions::SKIPS_HIDDEN_FILES
+            | NSDirectoryEnumerationOptions::SKIPS_SUBDIRECTORY_DESCENDANTS;
+        let urls = manager.enumerator_at_url(&url, &[], options, no_errors);
+        assert_eq!(relative_paths(&dir, urls), ["a.txt", "sub"]);
+    }
+
+    #[test]
+    fn enumerate_skip_descendants() {
+        let manager = NSFileManager::default();
+        let dir = tree("enumerate-skip-descendants");
+        let url = NSURL::file_url(&dir.0, true);
+
+        let options = NSDirectoryEnumerationOptions::SKIPS_HIDDEN_FILES;
+        let mut enumerator = manager.enumerator_at_url(&url, &[], options, no_errors);
+
+        let mut urls = Vec::new();
+        while let Some(url) = enumerator.next() {
+            let path = url.to_path_buf().unwrap();
+            if path.ends_with("sub/deeper") {
+                assert_eq!(enumerator.level(), 2);
+                enumerator.skip_descendants();
+            } else if path.ends_with("sub") {
+                assert_eq!(enumerator.level(), 1);
+            }
+            urls.push(url);
+        }
+
+        assert_eq!(
+            relative_paths(&dir, urls.into_iter()),
+            ["a.txt", "sub", "sub/b.txt", "sub/deeper"]
+        );
+    }
+
+    #[test]
+    fn enumerate_errors() {
+        let manager = NSFileManager::default();
+        let dir = TempDir::new("enumerate-errors");
+        let missing = NSURL::file_url(&dir.0.join("missing"), true);
+        let options = NSDirectoryEnumerationOptions::default();
+
+        let errors = Rc::new(Cell::new(0));
+        let handler_errors = errors.clone();
+        let mut enumerator = manager.enumerator_at_url(&missing, &[], options, move |_, _| {
+            handler_errors.set(handler_errors.get() + 1);
+            false
+        });
+        assert!(enumerator.next().is_none());
+        assert_eq!(errors.get(), 1);
+
+        let mut enumerator = manager.enumerator_at_url(&missing, &[], options, |_, _| {
+            panic!("Stop");
+        });
+        let panic = panic::catch_unwind(AssertUnwindSafe(|| enumerator.next()));
+        let payload = panic.err().unwrap();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"Stop"));
+    }
+
+    #[test]
+    fn non_utf8() {
+        let manager = NSFileManager::default();
+        let dir = TempDir::new("non-utf8");
+
+        let path = dir.0.join(OsStr::from_bytes(b"\xff.txt"));
+        assert_eq!(manager.file_exists(&path), (false, false));
+        assert!(manager.attributes_of_item(&path).is_err());
+        assert!(manager.remove_item(&path).is_err());
+        assert_eq!(NSURL::file_url(&path, false).to_path_buf().unwrap(), path);
+    }
+
+    #[test]
+    fn system_directories() {
+        let manager = NSFileManager::default();
+        let user = NSSearchPathDomainMask::USER;
+
+        let caches = check(manager.url_for_directory(NSSearchPathDirectory::CACHES, user));
+        assert!(caches.to_path_buf().unwrap().ends_with("Library/Caches"));
+
+        let support =
+            check(manager.url_for_directory(NSSearchPathDirectory::APPLICATION_SUPPORT, user));
+        let support = support.to_path_buf().unwrap();
+        assert!(support.ends_with("Library/Application Support"));
+    }
+}
@@ -0,0 +1,100 @@
+ns_string_wrapper! {
+    /// A property of a file system resource, which can be read with
+    /// [`NSURL::resource_value`](struct.NSURL.html#method.resource_value).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresourcekey).
+    #[derive(Ord, PartialOrd, Eq, PartialEq)]
+    pub wrapper NSURLResourceKey;
+}
+
+macro_rules! key {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $value:literal
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        #[doc(alias = $value)]
+        pub fn $fn() -> &'static NSURLResourceKey {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static NSURLResourceKey;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+impl NSURLResourceKey {
+    key! {
+        /// The resource's name in the file system, as an
+        /// [`NSString`](struct.NSString.html).
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlnamekey).
+        name "NSURLNameKey"
+    }
+
+    key! {
+        /// Whether the resource is a directory, as an
+        /// [`NSNumber`](struct.NSNumber.html) boolean.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlisdirectorykey).
+        is_directory "NSURLIsDirectoryKey"
+    }
+
+    key! {
+        /// Whether the resource is a regular file, as an
+        /// [`NSNumber`](struct.NSNumber.html) boolean.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlisregularfilekey).
+        is_regular_file "NSURLIsRegularFileKey"
+    }
+
+    key! {
+        /// Whether the resource is a symbolic link, as an
+        /// [`NSNumber`](struct.NSNumber.html) boolean.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlissymboliclinkkey).
+        is_symbolic_link "NSURLIsSymbolicLinkKey"
+    }
+
+    key! {
+        /// Whether the resource is normally not displayed to users, as an
+        /// [`NSNumber`](struct.NSNumber.html) boolean.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlishiddenkey).
+        is_hidden "NSURLIsHiddenKey"
+    }
+
+    key! {
+        /// Whether the resource is a package, such as an app bundle, as an
+        /// [`NSNumber`](struct.NSNumber.html) boolean.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlispackagekey).
+        is_package "NSURLIsPackageKey"
+    }
+
+    key! {
+        /// The size of the file in bytes, as an
+        /// [`NSNumber`](struct.NSNumber.html).
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlfilesizekey).
+        file_size "NSURLFileSizeKey"
+    }
+
+    key! {
+        /// The time the resource was created, as an
+        /// [`NSDate`](struct.NSDate.html).
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcreationdatekey).
+        creation_date "NSURLCreationDateKey"
+    }
+
+    key! {
+        /// The time the resource's contents were last modified, as an
+        /// [`NSDate`](struct.NSDate.html).
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcontentmodificationdatekey).
+        content_modification_date "NSURLContentModificationDateKey"
+    }
+}
@@ -0,0 +1,149 @@
+use crate::objc::NSUInteger;
+use std::ops;
+
+/// A location for [`NSFileManager::url_for_directory`](struct.NSFileManager.html#method.url_for_directory).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nssearchpathdirectory).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSSearchPathDirectory(pub NSUInteger);
+
+impl NSSearchPathDirectory {
+    /// Supported applications (`Applications`).
+    pub const APPLICATION: Self = Self(1);
+
+    /// Unsupported applications and demonstration versions.
+    pub const DEMO_APPLICATION: Self = Self(2);
+
+    /// Developer applications (`Developer/Applications`).
+    pub const DEVELOPER_APPLICATION: Self = Self(3);
+
+    /// System and network administration applications.
+    pub const ADMIN_APPLICATION: Self = Self(4);
+
+    /// Various user-visible documentation, support, and configuration files
+    /// (`Library`).
+    pub const LIBRARY: Self = Self(5);
+
+    /// Developer resources (`Developer`).
+    pub const DEVELOPER: Self = Self(6);
+
+    /// User home directories (`Users`).
+    pub const USER: Self = Self(7);
+
+    /// Documentation.
+    pub const DOCUMENTATION: Self = Self(8);
+
+    /// Documents.
+    pub const DOCUMENT: Self = Self(9);
+
+    /// Core services (`System/Library/CoreServices`).
+    pub const CORE_SERVICE: Self = Self(10);
+
+    /// Autosaved documents (`Library/Autosave Information`).
+    pub const AUTOSAVED_INFORMATION: Self = Self(11);
+
+    /// The user's desktop.
+    pub const DESKTOP: Self = Self(12);
+
+    /// Discardable cache files (`Library/Caches`).
+    pub const CACHES: Self = Self(13);
+
+    /// Application support files (`Library/Application Support`).
+    pub const APPLICATION_SUPPORT: Self = Self(14);
+
+    /// The user's downloads.
+    pub const DOWNLOADS: Self = Self(15);
+
+    /// Input methods (`Library/Input Methods`).
+    pub const INPUT_METHODS: Self = Self(16);
+
+    /// The user's movies.
+    pub const MOVIES: Self = Self(17);
+
+    /// The user's music.
+    pub const MUSIC: Self = Self(18);
+
+    /// The user's pictures.
+    pub const PICTURES: Self = Self(19);
+
+    /// The system's PPDs (`Library/Printers/PPDs`).
+    pub const PRINTER_DESCRIPTION: Self = Self(20);
+
+    /// The user's public sharing directory (`Public`).
+    pub const SHARED_PUBLIC: Self = Self(21);
+
+    /// The preference panes (`Library/PreferencePanes`).
+    pub const PREFERENCE_PANES: Self = Self(22);
+
+    /// The user scripts folder for the calling application
+    /// (`Library/Application Scripts/<code-signing-id>`).
+    pub const APPLICATION_SCRIPTS: Self = Self(23);
+
+    /// A temporary directory for replacing an item.
+    pub const ITEM_REPLACEMENT: Self = Self(99);
+
+    /// All directories where applications can be stored.
+    pub const ALL_APPLICATIONS: Self = Self(100);
+
+    /// All directories where resources can be stored.
+    pub const ALL_LIBRARIES: Self = Self(101);
+
+    /// The trash directory.
+    pub const TRASH: Self = Self(102);
+}
+
+/// Domains for [`NSFileManager::url_for_directory`](struct.NSFileManager.html#method.url_for_directory).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nssearchpathdomainmask).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSSearchPathDomainMask(pub NSUInteger);
+
+impl NSSearchPathDomainMask {
+    /// The user's home directory.
+    pub const USER: Self = Self(1);
+
+    /// Items available to everyone on the machine.
+    pub const LOCAL: Self = Self(2);
+
+    /// Items available on the network.
+    pub const NETWORK: Self = Self(4);
+
+    /// Items provided by Apple.
+    pub const SYSTEM: Self = Self(8);
+
+    /// All domains.
+    pub const ALL: Self = Self(0x0ffff);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSSearchPathDomainMask {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSSearchPathDomainMask {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSSearchPathDomainMask {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
@@ -0,0 +1,159 @@
+use super::{NSFormatter, POSIX_LOCALE};
+use crate::core::Arc;
+use crate::foundation::{NSDate, NSLocale, NSString, NSTimeZone};
+use crate::objc::{retain_autoreleased, ClassType};
+
+objc_subclass! {
+    /// A formatter that converts between dates and their textual
+    /// representations.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter).
+    pub class NSDateFormatter: NSFormatter;
+}
+
+impl NSDateFormatter {
+    /// Creates a formatter that uses the user's current locale and time zone.
+    ///
+    /// This is appropriate for strings shown to the user. For strings read
+    /// by other programs, use [`new_posix`](#method.new_posix).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1416222-init).
+    #[inline]
+    #[doc(alias = "init")]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates a formatter for fixed-format, machine-readable strings.
+    ///
+    /// The formatter uses the `en_US_POSIX` locale and the GMT time zone, so
+    /// that [`date_format`](#method.date_format) patterns are not altered by
+    /// the user's calendar, region, or 12/24-hour settings.
+    ///
+    /// See [documentation](https://developer.apple.com/library/archive/qa/qa1480/_index.html).
+    #[doc(alias = "en_US_POSIX")]
+    pub fn new_posix() -> Arc<Self> {
+        let mut formatter = Self::new();
+        let f = Arc::get_mut(&mut formatter).unwrap();
+        f.set_locale(&NSLocale::from_identifier(POSIX_LOCALE));
+        f.set_time_zone(&NSTimeZone::from_seconds_from_gmt(0).unwrap());
+        formatter
+    }
+
+    /// Returns the format pattern of `self`, such as `"yyyy-MM-dd"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1413514-dateformat).
+    #[inline]
+    #[doc(alias = "dateFormat")]
+    pub fn date_format(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, dateFormat]) }
+    }
+
+    /// Sets the [Unicode format pattern](https://unicode.org/reports/tr35/tr35-dates.html#Date_Format_Patterns)
+    /// of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1413514-dateformat).
+    #[inline]
+    #[doc(alias = "setDateFormat")]
+    #[doc(alias = "setDateFormat:")]
+    pub fn set_date_format(&mut self, format: &str) {
+        let format = NSString::from_str(format);
+        unsafe { _msg_send_any![self, setDateFormat: &*format] }
+    }
+
+    /// Returns the locale of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1411973-locale).
+    #[inline]
+    pub fn locale(&self) -> Arc<NSLocale> {
+        let locale = unsafe { retain_autoreleased(|| _msg_send_any![self, locale]) };
+        locale.expect("Date formatter has no locale")
+    }
+
+    /// Sets the locale of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1411973-locale).
+    #[inline]
+    #[doc(alias = "setLocale")]
+    #[doc(alias = "setLocale:")]
+    pub fn set_locale(&mut self, locale: &NSLocale) {
+        unsafe { _msg_send_any![self, setLocale: locale] }
+    }
+
+    /// Returns the time zone of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1411406-timezone).
+    #[inline]
+    #[doc(alias = "timeZone")]
+    pub fn time_zone(&self) -> Arc<NSTimeZone> {
+        let zone = unsafe { retain_autoreleased(|| _msg_send_any![self, timeZone]) };
+        zone.expect("Date formatter has no time zone")
+    }
+
+    /// Sets the time zone of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1411406-timezone).
+    #[inline]
+    #[doc(alias = "setTimeZone")]
+    #[doc(alias = "setTimeZone:")]
+    pub fn set_time_zone(&mut self, time_zone: &NSTimeZone) {
+        unsafe { _msg_send_any![self, setTimeZone: time_zone] }
+    }
+
+    /// Returns `date` formatted by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1418009-stringfromdate).
+    #[inline]
+    #[doc(alias = "stringFromDate")]
+    #[doc(alias = "stringFromDate:")]
+    pub fn string_from_date(&self, date: &NSDate) -> Arc<NSString<'static>> {
+        let string = unsafe { retain_autoreleased(|| _msg_send_any![self, stringFromDate: date]) };
+        string.expect("Date formatter returned no string")
+    }
+
+    /// Parses `string` with `self`, or returns `None` if it does not match.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsdateformatter/1413592-datefromstring).
+    #[inline]
+    #[doc(alias = "dateFromString")]
+    #[doc(alias = "dateFromString:")]
+    pub fn date_from_string(&self, string: &str) -> Option<Arc<NSDate>> {
+        let string = NSString::from_str(string);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, dateFromString: &*string]) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_format() {
+        let mut formatter = NSDateFormatter::new_posix();
+        Arc::get_mut(&mut formatter)
+            .unwrap()
+            .set_date_format("yyyy-MM-dd'T'HH:mm:ssZZZZZ");
+        assert_eq!(*formatter.locale().identifier(), *"en_US_POSIX");
+        assert_eq!(formatter.time_zone().seconds_from_gmt(), 0);
+
+        let date = formatter
+            .date_from_string("2021-03-13T12:00:00-05:00")
+            .unwrap();
+        assert_eq!(date.time_interval_since_1970(), 1_615_654_800.0);
+        assert_eq!(*formatter.string_from_date(&date), *"2021-03-13T17:00:00Z");
+
+        assert!(formatter.date_from_string("2021-03-13").is_none());
+        assert!(formatter.date_from_string("not a date").is_none());
+    }
+
+    #[test]
+    fn time_zone() {
+        let mut formatter = NSDateFormatter::new_posix();
+        let f = Arc::get_mut(&mut formatter).unwrap();
+        f.set_date_format("HH:mm");
+        f.set_time_zone(&NSTimeZone::from_name("Asia/Tokyo").unwrap());
+
+        let date = NSDate::from_time_interval_since_1970(0.0);
+        assert_eq!(*formatter.string_from_date(&date), *"09:00");
+    }
+}
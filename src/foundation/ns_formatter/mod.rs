@@ -0,0 +1,21 @@
+use crate::objc::NSObject;
+
+mod date_formatter;
+mod number_formatter;
+
+pub use date_formatter::*;
+pub use number_formatter::*;
+
+/// The identifier of the locale to use for fixed-format, machine-readable
+/// strings, regardless of user settings.
+///
+/// See [documentation](https://developer.apple.com/library/archive/qa/qa1480/_index.html).
+const POSIX_LOCALE: &str = "en_US_POSIX";
+
+objc_subclass! {
+    /// An abstract class that converts between values and their textual
+    /// representations.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsformatter).
+    pub class NSFormatter: NSObject<'static>;
+}
@@ -0,0 +1,211 @@
+use super::{NSFormatter, POSIX_LOCALE};
+use crate::core::Arc;
+use crate::foundation::{NSLocale, NSNumber, NSString};
+use crate::objc::{retain_autoreleased, ClassType, MsgReturn, MsgReturnKind, NSUInteger};
+
+/// The format used by an [`NSNumberFormatter`](struct.NSNumberFormatter.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatterstyle).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSNumberFormatterStyle(pub NSUInteger);
+
+unsafe impl MsgReturn for NSNumberFormatterStyle {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+impl NSNumberFormatterStyle {
+    /// An integer representation, such as `"1235"`.
+    pub const NONE: Self = Self(0);
+
+    /// A decimal representation, such as `"1,234.568"`.
+    pub const DECIMAL: Self = Self(1);
+
+    /// A currency representation, such as `"$1,234.57"`.
+    pub const CURRENCY: Self = Self(2);
+
+    /// A percent representation, such as `"123,457%"`.
+    pub const PERCENT: Self = Self(3);
+
+    /// A scientific representation, such as `"1.234568E3"`.
+    pub const SCIENTIFIC: Self = Self(4);
+
+    /// A spelled-out representation, such as `"one thousand two hundred
+    /// thirty-four point five six seven eight"`.
+    pub const SPELL_OUT: Self = Self(5);
+}
+
+objc_subclass! {
+    /// A formatter that converts between numbers and their textual
+    /// representations.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter).
+    pub class NSNumberFormatter: NSFormatter;
+}
+
+impl NSNumberFormatter {
+    /// Creates a formatter that uses the user's current locale.
+    ///
+    /// This is appropriate for strings shown to the user. For strings read
+    /// by other programs, use [`new_posix`](#method.new_posix).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1418348-init).
+    #[inline]
+    #[doc(alias = "init")]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates a formatter for machine-readable strings.
+    ///
+    /// The formatter uses the `en_US_POSIX` locale, so that the decimal
+    /// separator is always `.` regardless of the user's region.
+    ///
+    /// See [documentation](https://developer.apple.com/library/archive/qa/qa1480/_index.html).
+    #[doc(alias = "en_US_POSIX")]
+    pub fn new_posix() -> Arc<Self> {
+        let mut formatter = Self::new();
+        let locale = NSLocale::from_identifier(POSIX_LOCALE);
+        Arc::get_mut(&mut formatter).unwrap().set_locale(&locale);
+        formatter
+    }
+
+    /// Returns the format of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1415634-numberstyle).
+    #[inline]
+    #[doc(alias = "numberStyle")]
+    pub fn number_style(&self) -> NSNumberFormatterStyle {
+        unsafe { _msg_send_any![self, numberStyle] }
+    }
+
+    /// Sets the format of `self`.
+    ///
+    /// This resets other properties, such as the number of fraction digits,
+    /// to the defaults of the style.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1415634-numberstyle).
+    #[inline]
+    #[doc(alias = "setNumberStyle")]
+    #[doc(alias = "setNumberStyle:")]
+    pub fn set_number_style(&mut self, style: NSNumberFormatterStyle) {
+        unsafe { _msg_send_any![self, setNumberStyle: style] }
+    }
+
+    /// Returns the locale of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1416606-locale).
+    #[inline]
+    pub fn locale(&self) -> Arc<NSLocale> {
+        let locale = unsafe { retain_autoreleased(|| _msg_send_any![self, locale]) };
+        locale.expect("Number formatter has no locale")
+    }
+
+    /// Sets the locale of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1416606-locale).
+    #[inline]
+    #[doc(alias = "setLocale")]
+    #[doc(alias = "setLocale:")]
+    pub fn set_locale(&mut self, locale: &NSLocale) {
+        unsafe { _msg_send_any![self, setLocale: locale] }
+    }
+
+    /// Returns the minimum number of digits after the decimal separator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1410073-minimumfractiondigits).
+    #[inline]
+    #[doc(alias = "minimumFractionDigits")]
+    pub fn minimum_fraction_digits(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, minimumFractionDigits] }
+    }
+
+    /// Sets the minimum number of digits after the decimal separator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1410073-minimumfractiondigits).
+    #[inline]
+    #[doc(alias = "setMinimumFractionDigits")]
+    #[doc(alias = "setMinimumFractionDigits:")]
+    pub fn set_minimum_fraction_digits(&mut self, digits: NSUInteger) {
+        unsafe { _msg_send_any![self, setMinimumFractionDigits: digits] }
+    }
+
+    /// Returns the maximum number of digits after the decimal separator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1415364-maximumfractiondigits).
+    #[inline]
+    #[doc(alias = "maximumFractionDigits")]
+    pub fn maximum_fraction_digits(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, maximumFractionDigits] }
+    }
+
+    /// Sets the maximum number of digits after the decimal separator.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1415364-maximumfractiondigits).
+    #[inline]
+    #[doc(alias = "setMaximumFractionDigits")]
+    #[doc(alias = "setMaximumFractionDigits:")]
+    pub fn set_maximum_fraction_digits(&mut self, digits: NSUInteger) {
+        unsafe { _msg_send_any![self, setMaximumFractionDigits: digits] }
+    }
+
+    /// Returns `number` formatted by `self`, or `None` if it cannot be
+    /// formatted.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1418046-stringfromnumber).
+    #[inline]
+    #[doc(alias = "stringFromNumber")]
+    #[doc(alias = "stringFromNumber:")]
+    pub fn string_from_number(&self, number: &NSNumber) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, stringFromNumber: number]) }
+    }
+
+    /// Parses `string` with `self`, or returns `None` if it does not match.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnumberformatter/1408845-numberfromstring).
+    #[inline]
+    #[doc(alias = "numberFromString")]
+    #[doc(alias = "numberFromString:")]
+    pub fn number_from_string(&self, string: &str) -> Option<Arc<NSNumber>> {
+        let string = NSString::from_str(string);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, numberFromString: &*string]) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn currency() {
+        let mut formatter = NSNumberFormatter::new();
+        let f = Arc::get_mut(&mut formatter).unwrap();
+        f.set_locale(&NSLocale::from_identifier("de_DE"));
+        f.set_number_style(NSNumberFormatterStyle::CURRENCY);
+        assert_eq!(formatter.number_style(), NSNumberFormatterStyle::CURRENCY);
+
+        let number = NSNumber::from_double(1234.5);
+        let string = formatter.string_from_number(&number).unwrap();
+        assert_eq!(*string, *"1.234,50\u{a0}€");
+
+        let parsed = formatter.number_from_string("1.234,50\u{a0}€").unwrap();
+        assert_eq!(parsed.as_f64(), Some(1234.5));
+    }
+
+    #[test]
+    fn posix() {
+        let mut formatter = NSNumberFormatter::new_posix();
+        let f = Arc::get_mut(&mut formatter).unwrap();
+        f.set_number_style(NSNumberFormatterStyle::DECIMAL);
+        f.set_maximum_fraction_digits(2);
+        assert_eq!(formatter.maximum_fraction_digits(), 2);
+
+        let number = NSNumber::from_double(1234.567);
+        let string = formatter.string_from_number(&number).unwrap();
+        assert_eq!(*string, *"1234.57");
+
+        let parsed = formatter.number_from_string("0.25").unwrap();
+        assert_eq!(parsed.as_f64(), Some(0.25));
+        assert!(formatter.number_from_string("abc").is_none());
+    }
+}
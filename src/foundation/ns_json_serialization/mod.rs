@@ -0,0 +1,230 @@
+use super::{ns_error::with_error_out, CocoaErrorCode, NSArray, NSData, NSError, NSErrorDomain};
+use crate::core::Arc;
+use crate::objc::{ClassType, NSObject, BOOL};
+
+mod options;
+mod value;
+
+pub use options::*;
+pub use value::*;
+
+objc_subclass! {
+    /// Converts between JSON and Foundation objects.
+    ///
+    /// JSON is represented by [`NSDictionary`](struct.NSDictionary.html),
+    /// [`NSArray`](struct.NSArray.html), [`NSString`](struct.NSString.html),
+    /// [`NSNumber`](struct.NSNumber.html), and [`NSNull`](struct.NSNull.html)
+    /// objects, which can be visited with [`JSONValue`](enum.JSONValue.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsjsonserialization).
+    pub class NSJSONSerialization: NSObject<'static>;
+}
+
+impl NSJSONSerialization {
+    /// Parses the JSON in `data`, which may be in UTF-8, UTF-16, or UTF-32.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsjsonserialization/jsonobjectwithdata).
+    #[doc(alias = "JSONObjectWithData")]
+    #[doc(alias = "JSONObjectWithData:options:error:")]
+    pub fn from_data(
+        data: &NSData,
+        options: NSJSONReadingOptions,
+    ) -> Result<Arc<NSObject<'static>>, Arc<NSError<'static>>> {
+        let options = options.0;
+        unsafe {
+            with_error_out(|error| {
+                let object: *const NSObject<'static> = _msg_send_any![
+                    Self::class(),
+                    JSONObjectWithData: data
+                    options: options
+                    error: error
+                ];
+                object.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Writes `object` as JSON, encoded as UTF-8.
+    ///
+    /// Objects that cannot be written fail with
+    /// [`CocoaErrorCode::PropertyListWriteInvalid`](enum.CocoaErrorCode.html#variant.PropertyListWriteInvalid),
+    /// rather than raising an exception like `dataWithJSONObject:` does.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsjsonserialization/datawithjsonobject).
+    #[doc(alias = "dataWithJSONObject")]
+    #[doc(alias = "dataWithJSONObject:options:error:")]
+    pub fn to_data(
+        object: &NSObject<'static>,
+        options: NSJSONWritingOptions,
+    ) -> Result<Arc<NSData>, Arc<NSError<'static>>> {
+        let is_valid = if options.contains(NSJSONWritingOptions::FRAGMENTS_ALLOWED) {
+            // Top-level values are checked as if in an array.
+            Self::is_valid_json_object(&NSArray::from_slice(&[object]))
+        } else {
+            Self::is_valid_json_object(object)
+        };
+        if !is_valid {
+            let code = CocoaErrorCode::PropertyListWriteInvalid.code();
+            let error = NSError::builder(NSErrorDomain::cocoa(), code)
+                .localized_description("The object cannot be written as JSON.")
+                .build();
+            return Err(error);
+        }
+
+        let options = options.0;
+        unsafe {
+            with_error_out(|error| {
+                let data: *const NSData = _msg_send_any![
+                    Self::class(),
+                    dataWithJSONObject: object
+                    options: options
+                    error: error
+                ];
+                data.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Returns `true` if `object` can be written as JSON without the
+    /// [`FRAGMENTS_ALLOWED`](struct.NSJSONWritingOptions.html#associatedconstant.FRAGMENTS_ALLOWED)
+    /// option.
+    ///
+    /// This requires that `object` is an array or dictionary, that all
+    /// dictionary keys are strings, that all numbers are finite, and that
+    /// every nested object is a kind that JSON is made of.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsjsonserialization/isvalidjsonobject).
+    #[inline]
+    #[doc(alias = "isValidJSONObject")]
+    #[doc(alias = "isValidJSONObject:")]
+    pub fn is_valid_json_object(object: &NSObject<'static>) -> bool {
+        unsafe { _msg_send_any![Self::class(), isValidJSONObject: object => BOOL] }.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSMutableArray, NSMutableDictionary, NSNull, NSNumber, NSString};
+
+    const DOCUMENT: &str = r#"{
+        "name": "fruity",
+        "version": 2,
+        "ratio": 0.5,
+        "tags": ["objc", "rust"],
+        "nested": {"ok": true, "off": false, "none": null}
+    }"#;
+
+    fn parse(json: &str, options: NSJSONReadingOptions) -> Arc<NSObject<'static>> {
+        NSJSONSerialization::from_data(&NSData::from_slice(json.as_bytes()), options).unwrap()
+    }
+
+    #[test]
+    fn visit() {
+        let root = parse(DOCUMENT, NSJSONReadingOptions::NONE);
+        let root = JSONValue::new(&root).unwrap();
+        assert!(root.as_dictionary().is_some());
+
+        assert_eq!(*root.get("name").unwrap().as_string().unwrap(), *"fruity");
+        assert_eq!(
+            root.get("version").unwrap().as_number().unwrap().as_i64(),
+            Some(2)
+        );
+        assert_eq!(
+            root.get("ratio").unwrap().as_number().unwrap().as_f64(),
+            Some(0.5)
+        );
+
+        let tags = root.get("tags").unwrap();
+        assert_eq!(tags.as_array().unwrap().len(), 2);
+        assert_eq!(*tags.get_index(1).unwrap().as_string().unwrap(), *"rust");
+        assert!(tags.get_index(2).is_none());
+
+        let nested = root.get("nested").unwrap();
+        assert_eq!(nested.get("ok").unwrap().as_bool(), Some(true));
+        assert_eq!(nested.get("off").unwrap().as_bool(), Some(false));
+        assert!(nested.get("ok").unwrap().as_number().is_none());
+        assert!(nested.get("none").unwrap().is_null());
+        assert!(nested.get("missing").is_none());
+
+        // Values of other kinds are not JSON.
+        assert!(JSONValue::new(&NSData::from_slice(b"")).is_none());
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = parse(DOCUMENT, NSJSONReadingOptions::MUTABLE_CONTAINERS);
+        assert!(root.is_kind_of_class(NSMutableDictionary::<NSObject, NSObject>::class()));
+
+        let mut root: Arc<NSMutableDictionary<NSString, NSObject>> =
+            unsafe { Arc::cast_unchecked(root) };
+        {
+            let root = Arc::get_mut(&mut root).unwrap();
+            root.remove(&NSString::from_str("ratio"));
+            root.insert(&NSString::from_str("added"), &NSNumber::from_integer(3));
+            root.insert(&NSString::from_str("empty"), NSNull::null());
+        }
+
+        let tags = root.get(&NSString::from_str("tags")).unwrap();
+        assert!(tags.is_kind_of_class(NSMutableArray::<NSObject>::class()));
+
+        let options = NSJSONWritingOptions::SORTED_KEYS;
+        let data = NSJSONSerialization::to_data(&root, options).unwrap();
+        let json = std::str::from_utf8(data.as_slice()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"added":3,"empty":null,"name":"fruity","nested":{"none":null,"off":false,"ok":true},"tags":["objc","rust"],"version":2}"#
+        );
+
+        // The output parses back to the mutated document.
+        let reparsed = parse(json, NSJSONReadingOptions::NONE);
+        let reparsed = JSONValue::new(&reparsed).unwrap();
+        assert!(reparsed.get("ratio").is_none());
+        assert_eq!(
+            reparsed.get("added").unwrap().as_number().unwrap().as_i64(),
+            Some(3)
+        );
+        assert_eq!(reparsed.as_dictionary().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn fragments() {
+        let data = NSData::from_slice(br#""text""#);
+        assert!(NSJSONSerialization::from_data(&data, NSJSONReadingOptions::NONE).is_err());
+
+        let string = parse(r#""text""#, NSJSONReadingOptions::FRAGMENTS_ALLOWED);
+        assert_eq!(
+            *JSONValue::new(&string).unwrap().as_string().unwrap(),
+            *"text"
+        );
+
+        assert!(!NSJSONSerialization::is_valid_json_object(&string));
+        assert!(NSJSONSerialization::to_data(&string, NSJSONWritingOptions::NONE).is_err());
+
+        let options = NSJSONWritingOptions::FRAGMENTS_ALLOWED;
+        let data = NSJSONSerialization::to_data(&string, options).unwrap();
+        assert_eq!(data.as_slice(), br#""text""#);
+    }
+
+    #[test]
+    fn errors() {
+        let data = NSData::from_slice(b"{\"unterminated\": ");
+        let error = NSJSONSerialization::from_data(&data, NSJSONReadingOptions::NONE)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::PropertyListReadCorrupt)
+        );
+
+        // Writing an invalid object fails instead of raising.
+        let invalid = NSArray::from_slice(&[&*NSData::from_slice(b"")]);
+        assert!(!NSJSONSerialization::is_valid_json_object(&invalid));
+
+        let error = NSJSONSerialization::to_data(&invalid, NSJSONWritingOptions::NONE).unwrap_err();
+        assert_eq!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::PropertyListWriteInvalid)
+        );
+    }
+}
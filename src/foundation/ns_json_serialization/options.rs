@@ -0,0 +1,121 @@
+use crate::objc::NSUInteger;
+use std::ops;
+
+/// Options for reading JSON with
+/// [`NSJSONSerialization`](struct.NSJSONSerialization.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/jsonserialization/readingoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSJSONReadingOptions(pub NSUInteger);
+
+impl NSJSONReadingOptions {
+    /// No options.
+    pub const NONE: Self = Self(0);
+
+    /// Create arrays and dictionaries as mutable objects.
+    pub const MUTABLE_CONTAINERS: Self = Self(1 << 0);
+
+    /// Create strings as mutable objects.
+    pub const MUTABLE_LEAVES: Self = Self(1 << 1);
+
+    /// Allow a top-level value that is not an array or dictionary.
+    pub const FRAGMENTS_ALLOWED: Self = Self(1 << 2);
+
+    /// Allow JSON5 syntax, which is available since macOS 12 and iOS 15.
+    pub const JSON5_ALLOWED: Self = Self(1 << 3);
+
+    /// Assume the top-level value is a dictionary without surrounding braces,
+    /// which is available since macOS 12 and iOS 15.
+    pub const TOP_LEVEL_DICTIONARY_ASSUMED: Self = Self(1 << 4);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSJSONReadingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSJSONReadingOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSJSONReadingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// Options for writing JSON with
+/// [`NSJSONSerialization`](struct.NSJSONSerialization.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/jsonserialization/writingoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSJSONWritingOptions(pub NSUInteger);
+
+impl NSJSONWritingOptions {
+    /// No options.
+    pub const NONE: Self = Self(0);
+
+    /// Add whitespace and indentation to make the output readable.
+    pub const PRETTY_PRINTED: Self = Self(1 << 0);
+
+    /// Sort dictionary keys lexicographically, which is available since
+    /// macOS 10.13 and iOS 11.
+    pub const SORTED_KEYS: Self = Self(1 << 1);
+
+    /// Allow a top-level value that is not an array or dictionary, which is
+    /// available since macOS 10.15 and iOS 13.
+    pub const FRAGMENTS_ALLOWED: Self = Self(1 << 2);
+
+    /// Write `/` without escaping it as `\/`, which is available since
+    /// macOS 10.15 and iOS 13.
+    pub const WITHOUT_ESCAPING_SLASHES: Self = Self(1 << 3);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSJSONWritingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSJSONWritingOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSJSONWritingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
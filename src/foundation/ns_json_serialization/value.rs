@@ -0,0 +1,125 @@
+use crate::foundation::{NSArray, NSDictionary, NSNull, NSNumber, NSString};
+use crate::objc::{ClassType, NSObject};
+use std::ptr;
+
+/// A borrowed value in a JSON object graph, such as one returned by
+/// [`NSJSONSerialization::from_data`](struct.NSJSONSerialization.html#method.from_data).
+///
+/// This checks the class of a single object, so nested values are checked
+/// as they are visited.
+#[derive(Copy, Clone)]
+pub enum JSONValue<'a> {
+    /// A JSON object.
+    ///
+    /// Keys of dictionaries parsed from JSON are strings, but this does not
+    /// check every key.
+    Dictionary(&'a NSDictionary<NSObject<'static>, NSObject<'static>>),
+    /// A JSON array.
+    Array(&'a NSArray<NSObject<'static>>),
+    /// A JSON string.
+    String(&'a NSString<'static>),
+    /// A JSON number.
+    Number(&'a NSNumber),
+    /// A JSON boolean, which is either
+    /// [`NSNumber::yes`](struct.NSNumber.html#method.yes) or
+    /// [`NSNumber::no`](struct.NSNumber.html#method.no).
+    Bool(bool),
+    /// A JSON `null`, which is [`NSNull`](struct.NSNull.html).
+    Null,
+}
+
+impl<'a> JSONValue<'a> {
+    /// Returns the value for `object`, or `None` if it is not a kind of
+    /// object that JSON is made of.
+    pub fn new(object: &'a NSObject<'static>) -> Option<Self> {
+        // The booleans are singletons, but are also numbers.
+        let ptr: *const NSObject = object;
+        if ptr::eq(ptr.cast(), NSNumber::yes()) {
+            return Some(Self::Bool(true));
+        }
+        if ptr::eq(ptr.cast(), NSNumber::no()) {
+            return Some(Self::Bool(false));
+        }
+
+        let value = if object.is_kind_of_class(NSDictionary::<NSObject, NSObject>::class()) {
+            Self::Dictionary(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSArray::<NSObject>::class()) {
+            Self::Array(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSString::class()) {
+            Self::String(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSNumber::class()) {
+            Self::Number(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSNull::class()) {
+            Self::Null
+        } else {
+            return None;
+        };
+        Some(value)
+    }
+
+    /// Returns the dictionary if `self` is a JSON object.
+    #[inline]
+    pub fn as_dictionary(self) -> Option<&'a NSDictionary<NSObject<'static>, NSObject<'static>>> {
+        match self {
+            Self::Dictionary(dictionary) => Some(dictionary),
+            _ => None,
+        }
+    }
+
+    /// Returns the array if `self` is a JSON array.
+    #[inline]
+    pub fn as_array(self) -> Option<&'a NSArray<NSObject<'static>>> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if `self` is a JSON string.
+    #[inline]
+    pub fn as_string(self) -> Option<&'a NSString<'static>> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if `self` is a JSON number.
+    ///
+    /// Booleans are not numbers here, even though they are `NSNumber`s.
+    #[inline]
+    pub fn as_number(self) -> Option<&'a NSNumber> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if `self` is a JSON boolean.
+    #[inline]
+    pub fn as_bool(self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `self` is a JSON `null`.
+    #[inline]
+    pub fn is_null(self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the value for `key` if `self` is a JSON object containing it.
+    #[inline]
+    pub fn get(self, key: &str) -> Option<JSONValue<'a>> {
+        let key = NSString::from_str(key);
+        Self::new(self.as_dictionary()?.get(&key)?)
+    }
+
+    /// Returns the value at `index` if `self` is a JSON array that long.
+    #[inline]
+    pub fn get_index(self, index: usize) -> Option<JSONValue<'a>> {
+        Self::new(self.as_array()?.get(index)?)
+    }
+}
@@ -0,0 +1,203 @@
+use super::{NSArray, NSDictionary, NSException, NSString};
+use crate::core::Arc;
+use crate::objc::{catch_exception, retain_autoreleased, NSObject};
+
+/// [Key-value coding](https://developer.apple.com/documentation/objectivec/nsobject/nskeyvaluecoding).
+///
+/// Scalar and struct properties are boxed in
+/// [`NSNumber`](../foundation/struct.NSNumber.html) and
+/// [`NSValue`](../foundation/struct.NSValue.html) objects, so values are
+/// returned as `NSObject`s that can be downcast.
+///
+/// An exception raised for a key, such as one named
+/// [`NSExceptionName::undefined_key`](../foundation/struct.NSExceptionName.html#method.undefined_key)
+/// for a key that `self` does not have, is returned as an error.
+///
+/// Requires the **`foundation`** feature flag.
+impl NSObject<'_> {
+    /// Returns the value of the property named `key`.
+    ///
+    /// # Safety
+    ///
+    /// `key` must name a property-like accessor of `self`, which returns a
+    /// value without other effects. This calls any method named after `key`,
+    /// such as `release` or `removeAllObjects`, whose effects are unchecked.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/valueforkey).
+    #[inline]
+    #[doc(alias = "valueForKey")]
+    #[doc(alias = "valueForKey:")]
+    pub unsafe fn value_for_key(
+        &self,
+        key: &NSString,
+    ) -> Result<Option<Arc<NSObject<'static>>>, Arc<NSException>> {
+        catch_exception(|| retain_autoreleased(|| _msg_send_any![self, valueForKey: key]))
+    }
+
+    /// Returns the value at `key_path`, which is a sequence of keys separated
+    /// by dots, such as `"name.length"`.
+    ///
+    /// # Safety
+    ///
+    /// Each key in `key_path` must name a property-like accessor of the object
+    /// it is applied to, or a collection operator such as `@count`. See
+    /// [`value_for_key`](#method.value_for_key).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/valueforkeypath).
+    #[inline]
+    #[doc(alias = "valueForKeyPath")]
+    #[doc(alias = "valueForKeyPath:")]
+    pub unsafe fn value_for_key_path(
+        &self,
+        key_path: &NSString,
+    ) -> Result<Option<Arc<NSObject<'static>>>, Arc<NSException>> {
+        catch_exception(|| retain_autoreleased(|| _msg_send_any![self, valueForKeyPath: key_path]))
+    }
+
+    /// Sets the property named `key` to `value`.
+    ///
+    /// Boxed values are unboxed for scalar and struct properties. Setting
+    /// `None` for those raises an exception unless `self` handles it.
+    ///
+    /// # Safety
+    ///
+    /// This may mutate `self`, such as when it is a mutable collection, so no
+    /// references obtained from `self` may be in use.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/nskeyvaluecoding).
+    #[inline]
+    #[doc(alias = "setValue:forKey:")]
+    pub unsafe fn set_value_for_key(
+        &self,
+        value: Option<&NSObject<'static>>,
+        key: &NSString,
+    ) -> Result<(), Arc<NSException>> {
+        catch_exception(|| _msg_send_any![self, setValue: value forKey: key => ()])
+    }
+
+    /// Sets the value at `key_path`, which is a sequence of keys separated by
+    /// dots, to `value`.
+    ///
+    /// # Safety
+    ///
+    /// This may mutate `self` or the objects along `key_path`, so no
+    /// references obtained from them may be in use.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/nskeyvaluecoding).
+    #[inline]
+    #[doc(alias = "setValue:forKeyPath:")]
+    pub unsafe fn set_value_for_key_path(
+        &self,
+        value: Option<&NSObject<'static>>,
+        key_path: &NSString,
+    ) -> Result<(), Arc<NSException>> {
+        catch_exception(|| _msg_send_any![self, setValue: value forKeyPath: key_path => ()])
+    }
+
+    /// Returns a dictionary of the values for `keys`, in which `nil` values
+    /// are [`NSNull`](../foundation/struct.NSNull.html).
+    ///
+    /// # Safety
+    ///
+    /// Each of `keys` must name a property-like accessor of `self`. See
+    /// [`value_for_key`](#method.value_for_key).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/dictionarywithvaluesforkeys).
+    #[doc(alias = "dictionaryWithValuesForKeys")]
+    #[doc(alias = "dictionaryWithValuesForKeys:")]
+    pub unsafe fn dictionary_with_values_for_keys(
+        &self,
+        keys: &[&NSString<'static>],
+    ) -> Result<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>, Arc<NSException>> {
+        let keys = NSArray::from_slice(keys);
+        let dictionary = catch_exception(|| {
+            retain_autoreleased(|| _msg_send_any![self, dictionaryWithValuesForKeys: &*keys])
+        })?;
+        Ok(dictionary.expect("Failed to get values for keys"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSExceptionName, NSMutableDictionary, NSNumber};
+    use crate::objc::ClassType;
+
+    fn key(key: &str) -> Arc<NSString<'static>> {
+        NSString::from_str(key)
+    }
+
+    fn as_number<'a>(object: &'a NSObject) -> &'a NSNumber {
+        assert!(object.is_kind_of_class(NSNumber::class()));
+        unsafe { &*(object as *const NSObject).cast() }
+    }
+
+    // `NSException` does not implement `Debug`, so results are unwrapped via
+    // `Option`.
+    fn value_at(object: &NSObject, key_path: &str) -> Option<Arc<NSObject<'static>>> {
+        unsafe { object.value_for_key_path(&key(key_path)) }
+            .ok()
+            .unwrap()
+    }
+
+    #[test]
+    fn known_keys() {
+        let string = NSString::from_str("fruity");
+
+        // Scalars are boxed as numbers.
+        let length = unsafe { string.value_for_key(&key("length")) };
+        let length = length.ok().unwrap().unwrap();
+        assert_eq!(as_number(&length).as_i64(), Some(6));
+
+        let values = unsafe { string.dictionary_with_values_for_keys(&[&key("length")]) };
+        let values = values.ok().unwrap();
+        assert_eq!(values.len(), 1);
+
+        let value = values.get(&key("length")).unwrap();
+        assert_eq!(as_number(value).as_i64(), Some(6));
+    }
+
+    #[test]
+    fn key_paths() {
+        let strings = [NSString::from_str("a"), NSString::from_str("bcd")];
+        let array = NSArray::from_slice(&[&*strings[0], &*strings[1]]);
+
+        let count = value_at(&array, "@count").unwrap();
+        assert_eq!(as_number(&count).as_i64(), Some(2));
+
+        let max = value_at(&array, "@max.length").unwrap();
+        assert_eq!(as_number(&max).as_i64(), Some(3));
+    }
+
+    #[test]
+    fn unknown_key() {
+        let string = NSString::from_str("fruity");
+
+        let result = unsafe { string.value_for_key(&key("missing")) };
+        let exception = result.err().unwrap();
+        assert_eq!(*exception.name(), *NSExceptionName::undefined_key());
+
+        let value = NSNumber::from_integer(1);
+        let result = unsafe { string.set_value_for_key(Some(&value), &key("missing")) };
+        let exception = result.err().unwrap();
+        assert_eq!(*exception.name(), *NSExceptionName::undefined_key());
+    }
+
+    #[test]
+    fn set_values() {
+        let dictionary = NSMutableDictionary::<NSString, NSObject>::new();
+        let value = NSNumber::from_integer(42);
+
+        // Dictionaries use keys as their own keys.
+        let result = unsafe { dictionary.set_value_for_key(Some(&value), &key("answer")) };
+        assert!(result.is_ok());
+        let answer = value_at(&dictionary, "answer").unwrap();
+        assert_eq!(as_number(&answer).as_i64(), Some(42));
+
+        // Setting `nil` removes the key.
+        let result = unsafe { dictionary.set_value_for_key(None, &key("answer")) };
+        assert!(result.is_ok());
+        assert!(dictionary.is_empty());
+        assert!(value_at(&dictionary, "answer").is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::core::Arc;
+use crate::foundation::{NSDictionary, NSNumber, NSRange, NSString};
+use crate::objc::{NSObject, NSUInteger};
+use std::ptr;
+
+/// The kind of a [`KVOChange`](struct.KVOChange.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nskeyvaluechange).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSKeyValueChange {
+    /// The value was set, which is the only kind for to-one properties.
+    #[doc(alias = "NSKeyValueChangeSetting")]
+    Setting = 1,
+    /// Elements were inserted into a to-many property.
+    #[doc(alias = "NSKeyValueChangeInsertion")]
+    Insertion = 2,
+    /// Elements were removed from a to-many property.
+    #[doc(alias = "NSKeyValueChangeRemoval")]
+    Removal = 3,
+    /// Elements of a to-many property were replaced.
+    #[doc(alias = "NSKeyValueChangeReplacement")]
+    Replacement = 4,
+}
+
+impl Default for NSKeyValueChange {
+    #[inline]
+    fn default() -> Self {
+        Self::Setting
+    }
+}
+
+impl NSKeyValueChange {
+    /// Returns the kind for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSUInteger) -> Option<Self> {
+        match value {
+            1 => Some(Self::Setting),
+            2 => Some(Self::Insertion),
+            3 => Some(Self::Removal),
+            4 => Some(Self::Replacement),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! change_key {
+    ($fn:ident $value:literal) => {
+        #[inline]
+        fn $fn() -> &'static NSString<'static> {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static NSString<'static>;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+change_key!(kind_key "NSKeyValueChangeKindKey");
+change_key!(new_key "NSKeyValueChangeNewKey");
+change_key!(old_key "NSKeyValueChangeOldKey");
+change_key!(indexes_key "NSKeyValueChangeIndexesKey");
+change_key!(prior_key "NSKeyValueChangeNotificationIsPriorKey");
+
+/// A change to an observed key path, which is passed to the closure given to
+/// [`NSObject::observe`](../objc/struct.NSObject.html#method.observe).
+///
+/// Values are only included if requested with
+/// [`KVOOptions`](struct.KVOOptions.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nskeyvaluechangekey).
+#[derive(Clone)]
+pub struct KVOChange {
+    change: Arc<NSDictionary<NSString<'static>, NSObject<'static>>>,
+}
+
+impl KVOChange {
+    #[inline]
+    pub(super) fn new(change: Arc<NSDictionary<NSString<'static>, NSObject<'static>>>) -> Self {
+        Self { change }
+    }
+
+    /// Returns the kind of change.
+    #[inline]
+    #[doc(alias = "NSKeyValueChangeKindKey")]
+    pub fn kind(&self) -> NSKeyValueChange {
+        self.change
+            .get_as::<NSNumber>(kind_key())
+            .and_then(|kind| kind.as_usize())
+            .and_then(NSKeyValueChange::from_raw)
+            .unwrap_or_default()
+    }
+
+    /// Returns the value after the change, or `None` if it is `nil` or was
+    /// not requested with [`KVOOptions::NEW`](struct.KVOOptions.html#associatedconstant.NEW).
+    ///
+    /// For to-many changes, this is an array of the inserted or replacing
+    /// elements.
+    #[inline]
+    #[doc(alias = "NSKeyValueChangeNewKey")]
+    pub fn new_value(&self) -> Option<Arc<NSObject<'static>>> {
+        self.change.get_non_null(new_key()).map(Arc::retain)
+    }
+
+    /// Returns the value before the change, or `None` if it was `nil` or was
+    /// not requested with [`KVOOptions::OLD`](struct.KVOOptions.html#associatedconstant.OLD).
+    ///
+    /// For to-many changes, this is an array of the removed or replaced
+    /// elements.
+    #[inline]
+    #[doc(alias = "NSKeyValueChangeOldKey")]
+    pub fn old_value(&self) -> Option<Arc<NSObject<'static>>> {
+        self.change.get_non_null(old_key()).map(Arc::retain)
+    }
+
+    /// Returns the indexes of the inserted, removed, or replaced elements of
+    /// a to-many property, in ascending order.
+    ///
+    /// This is `None` for [`Setting`](enum.NSKeyValueChange.html#variant.Setting)
+    /// changes.
+    #[doc(alias = "NSKeyValueChangeIndexesKey")]
+    pub fn indexes(&self) -> Option<Vec<NSUInteger>> {
+        let indexes = self.change.get(indexes_key())?;
+
+        let count: NSUInteger = unsafe { _msg_send_any![indexes, count] };
+        let mut buf = Vec::<NSUInteger>::with_capacity(count);
+        let range: *mut NSRange = ptr::null_mut();
+        let len: NSUInteger = unsafe {
+            _msg_send_any![
+                indexes,
+                getIndexes: buf.as_mut_ptr()
+                maxCount: count
+                inIndexRange: range
+            ]
+        };
+        unsafe { buf.set_len(len.min(count)) };
+        Some(buf)
+    }
+
+    /// Returns `true` if this change was sent before the value changed,
+    /// because of [`KVOOptions::PRIOR`](struct.KVOOptions.html#associatedconstant.PRIOR).
+    #[inline]
+    #[doc(alias = "NSKeyValueChangeNotificationIsPriorKey")]
+    pub fn is_prior(&self) -> bool {
+        self.change
+            .get_as::<NSNumber>(prior_key())
+            .and_then(|prior| prior.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Returns the change dictionary, keyed by
+    /// [`NSKeyValueChangeKey`](https://developer.apple.com/documentation/foundation/nskeyvaluechangekey)
+    /// names.
+    #[inline]
+    pub fn as_dictionary(&self) -> &NSDictionary<NSString<'static>, NSObject<'static>> {
+        &self.change
+    }
+}
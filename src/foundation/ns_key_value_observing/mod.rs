@@ -0,0 +1,284 @@
+use super::{NSDictionary, NSString, Observer, ObserverToken};
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{allocate_private_class, sys, Class, ClassType, Imp, NSObject, ObjCObject, Sel};
+use std::{
+    collections::BTreeMap,
+    ffi::c_void,
+    mem, ptr,
+    sync::{
+        self,
+        atomic::{AtomicPtr, AtomicUsize, Ordering},
+        Mutex, Once,
+    },
+};
+
+mod change;
+mod options;
+
+pub use change::*;
+pub use options::*;
+
+type Callback = sync::Arc<dyn Fn(KVOChange) + Send + Sync>;
+
+// The closures of all observations, keyed by their context pointers.
+//
+// Closures are looked up for every change rather than passed as the context
+// itself, so that a change sent while its observation is being removed finds
+// nothing instead of a dangling closure.
+static CALLBACKS: Mutex<BTreeMap<usize, Callback>> = Mutex::new(BTreeMap::new());
+
+// The last context pointer given to an observation. Zero is never used.
+static LAST_CONTEXT: AtomicUsize = AtomicUsize::new(0);
+
+#[inline]
+fn callbacks() -> sync::MutexGuard<'static, BTreeMap<usize, Callback>> {
+    // Closures are called outside of the lock, so it cannot be poisoned by
+    // them.
+    CALLBACKS.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// Returns the shared observer, whose class dispatches
+/// `-observeValueForKeyPath:ofObject:change:context:` to the closure for the
+/// context.
+fn observer() -> &'static NSObject<'static> {
+    static REGISTER: Once = Once::new();
+    static OBSERVER: AtomicPtr<NSObject<'static>> = AtomicPtr::new(ptr::null_mut());
+
+    unsafe extern "C" fn observe(
+        _this: &ObjCObject<'static>,
+        _cmd: Sel,
+        _key_path: *const NSString<'static>,
+        _object: *const NSObject<'static>,
+        change: *const NSDictionary<NSString<'static>, NSObject<'static>>,
+        context: *mut c_void,
+    ) {
+        let callback = callbacks().get(&(context as usize)).cloned();
+        let (callback, change) = match (callback, change.as_ref()) {
+            (Some(callback), Some(change)) => (callback, KVOChange::new(Arc::retain(change))),
+            _ => return,
+        };
+
+        abort_on_panic(|| callback(change));
+    }
+
+    REGISTER.call_once(|| unsafe {
+        let class = allocate_private_class("_FruityKeyValueObserver", NSObject::class());
+        let class = class.as_ptr();
+
+        type Observe = unsafe extern "C" fn(
+            &ObjCObject<'static>,
+            Sel,
+            *const NSString<'static>,
+            *const NSObject<'static>,
+            *const NSDictionary<NSString<'static>, NSObject<'static>>,
+            *mut c_void,
+        );
+        let imp = mem::transmute::<Observe, Imp>(observe);
+        let sel = selector!(observeValueForKeyPath:ofObject:change:context:);
+        sys::class_addMethod(class, sel, imp, b"v@:@@@^v\0".as_ptr().cast());
+        sys::objc_registerClassPair(class);
+
+        // The observer is shared by all observations and never released.
+        let class: &Class = &*class;
+        let observer: Arc<NSObject<'static>> = class.alloc_init();
+        OBSERVER.store(Arc::into_raw(observer) as *mut _, Ordering::Release);
+    });
+
+    unsafe { &*OBSERVER.load(Ordering::Acquire) }
+}
+
+/// [Key-value observing](https://developer.apple.com/documentation/objectivec/nsobject/nskeyvalueobserving).
+///
+/// Requires the **`foundation`** feature flag.
+impl NSObject<'static> {
+    /// Calls `f` with each change to the value at `key_path`, until the
+    /// returned token is dropped.
+    ///
+    /// Changes are sent synchronously on the thread that made them, so `f`
+    /// must be `Send` and `Sync`. Panics in `f` abort the process.
+    ///
+    /// Observing a key path that `self` does not have raises an exception,
+    /// either immediately with [`KVOOptions::INITIAL`](../foundation/struct.KVOOptions.html#associatedconstant.INITIAL)
+    /// or once any value along it changes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/1412787-addobserver).
+    #[doc(alias = "addObserver")]
+    #[doc(alias = "addObserver:forKeyPath:options:context:")]
+    pub fn observe<F>(&self, key_path: &str, options: KVOOptions, f: F) -> ObserverToken
+    where
+        F: Fn(KVOChange) + Send + Sync + 'static,
+    {
+        let context = LAST_CONTEXT.fetch_add(1, Ordering::Relaxed) + 1;
+        callbacks().insert(context, sync::Arc::new(f));
+
+        let observation = KeyValueObservation {
+            object: Arc::retain(self),
+            key_path: NSString::from_str(key_path),
+            context,
+        };
+
+        let observer = observer();
+        let key_path = &*observation.key_path;
+        let options = options.0;
+        let context = context as *mut c_void;
+        unsafe {
+            _msg_send_any![
+                self,
+                addObserver: observer
+                forKeyPath: key_path
+                options: options
+                context: context
+                => ()
+            ]
+        }
+
+        ObserverToken::new(Observer::KeyValue(observation))
+    }
+}
+
+// An observation of an object, which retains it so that the observation is
+// always removed from a live object.
+pub(crate) struct KeyValueObservation {
+    object: Arc<NSObject<'static>>,
+    pub(crate) key_path: Arc<NSString<'static>>,
+    context: usize,
+}
+
+impl KeyValueObservation {
+    pub(crate) fn remove(&self) {
+        // Removing by context only removes this observation, even if the same
+        // key path of the object is observed more than once.
+        let observer = observer();
+        let key_path = &*self.key_path;
+        let context = self.context as *mut c_void;
+        unsafe {
+            _msg_send_any![
+                self.object,
+                removeObserver: observer
+                forKeyPath: key_path
+                context: context
+                => ()
+            ]
+        }
+
+        let callback = callbacks().remove(&self.context);
+
+        // Drop the closure outside of the lock, in case it owns another token.
+        drop(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSNumber, NSOperationQueue};
+    use crate::objc::NSInteger;
+    use std::sync::mpsc;
+
+    fn as_integer(object: Option<Arc<NSObject<'static>>>) -> Option<NSInteger> {
+        let object = object?;
+        assert!(object.is_kind_of_class(NSNumber::class()));
+        let number: &NSNumber = unsafe { &*(&*object as *const NSObject).cast() };
+        number.as_i64().map(|value| value as NSInteger)
+    }
+
+    #[test]
+    fn old_and_new() {
+        let queue = NSOperationQueue::new();
+        queue.set_max_concurrent_operation_count(1);
+
+        let changes = sync::Arc::new(Mutex::new(Vec::new()));
+        let options = KVOOptions::OLD | KVOOptions::NEW | KVOOptions::INITIAL | KVOOptions::PRIOR;
+        let token = {
+            let changes = changes.clone();
+            queue.observe("maxConcurrentOperationCount", options, move |change| {
+                assert_eq!(change.kind(), NSKeyValueChange::Setting);
+                assert!(change.indexes().is_none());
+
+                let values = (
+                    change.is_prior(),
+                    as_integer(change.old_value()),
+                    as_integer(change.new_value()),
+                );
+                changes.lock().unwrap().push(values);
+            })
+        };
+
+        queue.set_max_concurrent_operation_count(2);
+        assert_eq!(
+            *changes.lock().unwrap(),
+            [
+                // The initial change has no old value.
+                (false, None, Some(1)),
+                // The prior change has no new value.
+                (true, Some(1), None),
+                (false, Some(1), Some(2)),
+            ]
+        );
+
+        drop(token);
+        queue.set_max_concurrent_operation_count(3);
+        assert_eq!(changes.lock().unwrap().len(), 3);
+
+        // The closure was dropped with the token.
+        assert_eq!(sync::Arc::strong_count(&changes), 1);
+    }
+
+    #[test]
+    fn operation_count() {
+        let queue = NSOperationQueue::new();
+        queue.set_max_concurrent_operation_count(1);
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let token = queue.observe("operationCount", KVOOptions::NEW, move |change| {
+            let count = as_integer(change.new_value()).unwrap();
+            tx.lock().unwrap().send(count).unwrap();
+        });
+
+        // Changes are sent from the threads that run the operations.
+        let (start_tx, start_rx) = mpsc::channel::<()>();
+        queue.add_operation(move || start_rx.recv().unwrap());
+        queue.add_operation(|| {});
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(rx.recv().unwrap(), 2);
+
+        start_tx.send(()).unwrap();
+        queue.wait_until_all_operations_are_finished();
+        let mut counts: Vec<NSInteger> = rx.try_iter().collect();
+        counts.dedup();
+        assert_eq!(counts.last(), Some(&0));
+
+        drop(token);
+    }
+
+    #[test]
+    fn independent_tokens() {
+        let queue = NSOperationQueue::new();
+        let key_path = "maxConcurrentOperationCount";
+
+        let count = sync::Arc::new(AtomicUsize::new(0));
+        let observe = |count: &sync::Arc<AtomicUsize>| {
+            let count = count.clone();
+            queue.observe(key_path, KVOOptions::NONE, move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let first = observe(&count);
+        let second = observe(&count);
+
+        queue.set_max_concurrent_operation_count(2);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // Removing one observation of the same key path keeps the other.
+        drop(first);
+        queue.set_max_concurrent_operation_count(3);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // The token outlives the last other reference to the object.
+        drop(queue);
+        drop(second);
+        assert_eq!(sync::Arc::strong_count(&count), 1);
+    }
+}
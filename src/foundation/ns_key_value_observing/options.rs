@@ -0,0 +1,70 @@
+use crate::objc::NSUInteger;
+use std::ops;
+
+/// Options for observing a key path with
+/// [`NSObject::observe`](../objc/struct.NSObject.html#method.observe).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nskeyvalueobservingoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[doc(alias = "NSKeyValueObservingOptions")]
+pub struct KVOOptions(pub NSUInteger);
+
+impl KVOOptions {
+    /// No options.
+    pub const NONE: Self = Self(0);
+
+    /// Include the new value in each change.
+    #[doc(alias = "NSKeyValueObservingOptionNew")]
+    pub const NEW: Self = Self(1 << 0);
+
+    /// Include the old value in each change.
+    #[doc(alias = "NSKeyValueObservingOptionOld")]
+    pub const OLD: Self = Self(1 << 1);
+
+    /// Send a change immediately, before observing begins.
+    ///
+    /// This includes the current value as the new value if
+    /// [`NEW`](#associatedconstant.NEW) is also set.
+    #[doc(alias = "NSKeyValueObservingOptionInitial")]
+    pub const INITIAL: Self = Self(1 << 2);
+
+    /// Send a change before each change as well as after it.
+    ///
+    /// The change sent before is
+    /// [`is_prior`](struct.KVOChange.html#method.is_prior) and never includes
+    /// the new value.
+    #[doc(alias = "NSKeyValueObservingOptionPrior")]
+    pub const PRIOR: Self = Self(1 << 3);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for KVOOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for KVOOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for KVOOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
@@ -0,0 +1,139 @@
+use super::{ns_error::with_error_out, NSData, NSError};
+use crate::core::Arc;
+use crate::objc::{ClassType, NSObject, BOOL};
+
+mod unarchiver;
+
+pub use unarchiver::*;
+
+objc_subclass! {
+    /// Encodes object graphs into archives that can be read by
+    /// [`NSKeyedUnarchiver`](struct.NSKeyedUnarchiver.html), including ones
+    /// in Swift and Objective-C programs.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nskeyedarchiver).
+    pub class NSKeyedArchiver: NSObject<'static>;
+}
+
+impl NSKeyedArchiver {
+    /// Encodes the object graph of `root` into an archive.
+    ///
+    /// If `requiring_secure_coding` is `true`, every object in the graph must
+    /// conform to `NSSecureCoding`. Archives read with
+    /// [`NSKeyedUnarchiver::unarchive`](struct.NSKeyedUnarchiver.html#method.unarchive)
+    /// require this anyway, so it only needs to be `false` for archives read
+    /// by older APIs.
+    ///
+    /// This is available since macOS 10.13 and iOS 11.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nskeyedarchiver/archiveddatawithrootobject).
+    #[doc(alias = "archivedDataWithRootObject")]
+    #[doc(alias = "archivedDataWithRootObject:requiringSecureCoding:error:")]
+    pub fn archive(
+        root: &NSObject<'static>,
+        requiring_secure_coding: bool,
+    ) -> Result<Arc<NSData>, Arc<NSError<'static>>> {
+        let secure = BOOL::from(requiring_secure_coding);
+        unsafe {
+            with_error_out(|error| {
+                let data: *const NSData = _msg_send_any![
+                    Self::class(),
+                    archivedDataWithRootObject: root
+                    requiringSecureCoding: secure
+                    error: error
+                ];
+                data.as_ref().map(Arc::retain)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{CocoaErrorCode, NSArray, NSDate, NSDictionary, NSNumber, NSString};
+
+    type Document = NSDictionary<NSString<'static>, NSObject<'static>>;
+
+    // Only the class of the root object is checked, so the dictionary is
+    // decoded type-erased.
+    type Decoded = NSDictionary<NSObject<'static>, NSObject<'static>>;
+
+    fn document() -> Arc<Document> {
+        let keys = [
+            NSString::from_str("name"),
+            NSString::from_str("version"),
+            NSString::from_str("released"),
+        ];
+        let name = NSString::from_str("fruity");
+        let version = NSNumber::from_integer(2);
+        let released = NSDate::from_time_interval_since_1970(1_600_000_000.5);
+        NSDictionary::from_pairs(&[
+            (&keys[0], &name),
+            (&keys[1], &version),
+            (&keys[2], &released),
+        ])
+    }
+
+    #[test]
+    fn round_trip() {
+        let document = document();
+        let data = NSKeyedArchiver::archive(&document, true).unwrap();
+
+        let classes = [
+            NSDictionary::<NSObject, NSObject>::class(),
+            NSString::class(),
+            NSNumber::class(),
+            NSDate::class(),
+        ];
+        let read = NSKeyedUnarchiver::unarchive::<Decoded>(&classes, &data).unwrap();
+        assert!(**read == **document);
+
+        let released = read
+            .get_as::<NSDate>(&NSString::from_str("released"))
+            .unwrap();
+        assert_eq!(released.time_interval_since_1970(), 1_600_000_000.5);
+    }
+
+    #[test]
+    fn wrong_classes() {
+        let data = NSKeyedArchiver::archive(&document(), true).unwrap();
+
+        // The dates in the dictionary are not allowed.
+        let classes = [
+            NSDictionary::<NSObject, NSObject>::class(),
+            NSString::class(),
+            NSNumber::class(),
+        ];
+        let error = NSKeyedUnarchiver::unarchive::<Decoded>(&classes, &data)
+            .err()
+            .unwrap();
+        assert_eq!(error.cocoa_code(), Some(CocoaErrorCode::CoderReadCorrupt));
+
+        // The root object is allowed, but is not the requested type.
+        let classes = [
+            NSDictionary::<NSObject, NSObject>::class(),
+            NSString::class(),
+            NSNumber::class(),
+            NSDate::class(),
+        ];
+        let error = NSKeyedUnarchiver::unarchive::<NSArray<NSObject>>(&classes, &data)
+            .err()
+            .unwrap();
+        assert_eq!(error.cocoa_code(), Some(CocoaErrorCode::CoderReadCorrupt));
+    }
+
+    #[test]
+    fn insecure() {
+        let document = document();
+        let data = NSKeyedArchiver::archive(&document, false).unwrap();
+
+        let read = NSKeyedUnarchiver::unarchive_insecure(&data).unwrap();
+        assert!(*read == **document);
+
+        let error = NSKeyedUnarchiver::unarchive_insecure(&NSData::from_slice(b"not an archive"))
+            .err()
+            .unwrap();
+        assert!(error.cocoa_code().is_some());
+    }
+}
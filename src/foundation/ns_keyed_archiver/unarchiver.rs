@@ -0,0 +1,96 @@
+use crate::core::Arc;
+use crate::foundation::{
+    ns_error::with_error_out, CocoaErrorCode, NSData, NSError, NSErrorDomain, NSSet,
+};
+use crate::objc::{Class, ClassType, DowncastTarget, NSObject};
+
+objc_subclass! {
+    /// Decodes object graphs from archives created by
+    /// [`NSKeyedArchiver`](struct.NSKeyedArchiver.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nskeyedunarchiver).
+    pub class NSKeyedUnarchiver: NSObject<'static>;
+}
+
+impl NSKeyedUnarchiver {
+    /// Decodes the archive in `data` using secure coding, and returns its root
+    /// object as a `T`.
+    ///
+    /// Every object in the archive must be an instance of a class in
+    /// `classes`. This includes collections and their contents, so decoding a
+    /// dictionary of strings needs both
+    /// [`NSDictionary`](struct.NSDictionary.html) and
+    /// [`NSString`](struct.NSString.html). Objects of other classes, and a
+    /// root object that is not a `T`, fail with
+    /// [`CocoaErrorCode::CoderReadCorrupt`](enum.CocoaErrorCode.html#variant.CoderReadCorrupt)
+    /// rather than raising an exception.
+    ///
+    /// Only the class of the root object is checked, so collections are
+    /// decoded with type-erased elements such as `NSArray<NSObject>`.
+    ///
+    /// This is available since macOS 10.13 and iOS 11.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nskeyedunarchiver/unarchivedobjectofclasses).
+    #[doc(alias = "unarchivedObjectOfClasses")]
+    #[doc(alias = "unarchivedObjectOfClasses:fromData:error:")]
+    pub fn unarchive<T: DowncastTarget<'static>>(
+        classes: &[&Class],
+        data: &NSData,
+    ) -> Result<Arc<T>, Arc<NSError<'static>>> {
+        // Classes are objects too, so they can be stored in a set.
+        let classes: Vec<&NSObject<'static>> = classes
+            .iter()
+            .map(|&class| unsafe { &*(class as *const Class).cast::<NSObject>() })
+            .collect();
+        let classes = NSSet::from_slice(&classes);
+
+        let object = unsafe {
+            with_error_out(|error| {
+                let object: *const NSObject<'static> = _msg_send_any![
+                    Self::class(),
+                    unarchivedObjectOfClasses: &*classes
+                    fromData: data
+                    error: error
+                ];
+                object.as_ref().map(Arc::retain)
+            })
+        }?;
+
+        if object.is_kind_of_class(<T as ClassType>::class()) {
+            Ok(unsafe { Arc::cast_unchecked(object) })
+        } else {
+            let code = CocoaErrorCode::CoderReadCorrupt.code();
+            let error = NSError::builder(NSErrorDomain::cocoa(), code)
+                .localized_description("The archived object is not of the expected class.")
+                .build();
+            Err(error)
+        }
+    }
+
+    /// Decodes the archive in `data` without secure coding, and returns its
+    /// root object.
+    ///
+    /// # Security
+    ///
+    /// This instantiates any class named in the archive, so it must only be
+    /// used for trusted archives, such as ones written before they required
+    /// secure coding. Use [`unarchive`](#method.unarchive) for all others.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nskeyedunarchiver/unarchivetoplevelobjectwithdata).
+    #[doc(alias = "unarchiveTopLevelObjectWithData")]
+    #[doc(alias = "unarchiveTopLevelObjectWithData:error:")]
+    pub fn unarchive_insecure(
+        data: &NSData,
+    ) -> Result<Arc<NSObject<'static>>, Arc<NSError<'static>>> {
+        unsafe {
+            with_error_out(|error| {
+                let object: *const NSObject<'static> = _msg_send_any![
+                    Self::class(),
+                    unarchiveTopLevelObjectWithData: data
+                    error: error
+                ];
+                object.as_ref().map(Arc::retain)
+            })
+        }
+    }
+}
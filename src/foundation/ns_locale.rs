@@ -0,0 +1,161 @@
+use super::NSString;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, Sel};
+use std::fmt;
+
+objc_subclass! {
+    /// Information about linguistic, cultural, and technological conventions
+    /// for use in formatting data for presentation.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale).
+    #[derive(PartialEq)]
+    pub class NSLocale: NSObject<'static>;
+}
+
+impl fmt::Debug for NSLocale {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSLocale").field(&self.identifier()).finish()
+    }
+}
+
+impl NSLocale {
+    /// Returns the locale for the user's current settings.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1414388-currentlocale).
+    #[inline]
+    #[doc(alias = "currentLocale")]
+    pub fn current() -> Arc<Self> {
+        let locale =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), currentLocale]) };
+        locale.expect("No current locale")
+    }
+
+    /// Creates a locale for an identifier such as `"en_US"`.
+    ///
+    /// Foundation accepts any identifier, so this never fails.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1414818-initwithlocaleidentifier).
+    #[doc(alias = "localeWithLocaleIdentifier")]
+    #[doc(alias = "localeWithLocaleIdentifier:")]
+    #[doc(alias = "initWithLocaleIdentifier:")]
+    pub fn from_identifier(identifier: &str) -> Arc<Self> {
+        let identifier = NSString::from_str(identifier);
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(obj: Arc<NSLocale>, sel: Sel, identifier: &NSString) -> Arc<NSLocale>;
+        }
+
+        let sel = selector!(initWithLocaleIdentifier:);
+        unsafe { objc_msgSend(value, sel, &identifier) }
+    }
+
+    /// Returns the identifier of `self`, such as `"en_US"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1416263-localeidentifier).
+    #[inline]
+    #[doc(alias = "localeIdentifier")]
+    pub fn identifier(&self) -> Arc<NSString<'static>> {
+        let identifier = unsafe { retain_autoreleased(|| _msg_send_any![self, localeIdentifier]) };
+        identifier.expect("Locale has no identifier")
+    }
+
+    /// Returns the language code of `self`, such as `"en"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1643026-languagecode).
+    #[inline]
+    #[doc(alias = "languageCode")]
+    pub fn language_code(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, languageCode]) }
+    }
+
+    /// Returns the country code of `self`, such as `"US"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1643060-countrycode).
+    #[inline]
+    #[doc(alias = "countryCode")]
+    pub fn country_code(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, countryCode]) }
+    }
+}
+
+/// Display names.
+///
+/// These return names localized for `self`. For example, the name of the
+/// `"fr_FR"` locale is `"French (France)"` in the `"en_US"` locale.
+impl NSLocale {
+    /// Returns the name of the locale with `identifier`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1643059-localizedstringforlocaleidentifi).
+    #[inline]
+    #[doc(alias = "localizedStringForLocaleIdentifier")]
+    #[doc(alias = "localizedStringForLocaleIdentifier:")]
+    pub fn display_name_for_identifier(&self, identifier: &str) -> Option<Arc<NSString<'static>>> {
+        let identifier = NSString::from_str(identifier);
+        unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, localizedStringForLocaleIdentifier: &*identifier],
+            )
+        }
+    }
+
+    /// Returns the name of the language with `code`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1643045-localizedstringforlanguagecode).
+    #[inline]
+    #[doc(alias = "localizedStringForLanguageCode")]
+    #[doc(alias = "localizedStringForLanguageCode:")]
+    pub fn display_name_for_language_code(&self, code: &str) -> Option<Arc<NSString<'static>>> {
+        let code = NSString::from_str(code);
+        unsafe {
+            retain_autoreleased(|| _msg_send_any![self, localizedStringForLanguageCode: &*code])
+        }
+    }
+
+    /// Returns the name of the country with `code`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nslocale/1643086-localizedstringforcountrycode).
+    #[inline]
+    #[doc(alias = "localizedStringForCountryCode")]
+    #[doc(alias = "localizedStringForCountryCode:")]
+    pub fn display_name_for_country_code(&self, code: &str) -> Option<Arc<NSString<'static>>> {
+        let code = NSString::from_str(code);
+        unsafe {
+            retain_autoreleased(|| _msg_send_any![self, localizedStringForCountryCode: &*code])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifier() {
+        let locale = NSLocale::from_identifier("en_US");
+        assert_eq!(*locale.identifier(), *"en_US");
+        assert_eq!(*locale.language_code().unwrap(), *"en");
+        assert_eq!(*locale.country_code().unwrap(), *"US");
+
+        assert!(!NSLocale::current().identifier().is_empty());
+    }
+
+    #[test]
+    fn display_names() {
+        let locale = NSLocale::from_identifier("en_US");
+        assert_eq!(
+            *locale.display_name_for_identifier("fr_FR").unwrap(),
+            *"French (France)"
+        );
+        assert_eq!(
+            *locale.display_name_for_language_code("de").unwrap(),
+            *"German"
+        );
+        assert_eq!(
+            *locale.display_name_for_country_code("JP").unwrap(),
+            *"Japan"
+        );
+    }
+}
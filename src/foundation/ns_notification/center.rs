@@ -0,0 +1,214 @@
+use super::{
+    super::{NSDictionary, NSOperationQueue, NSString, Observer, ObserverToken},
+    NSNotification,
+};
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, RcBlock};
+
+objc_subclass! {
+    /// A mechanism for broadcasting [`NSNotification`](struct.NSNotification.html)s
+    /// to registered observers.
+    ///
+    /// Methods take `&self` because notification centers are safe to share
+    /// between threads.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotificationcenter).
+    pub class NSNotificationCenter: NSObject<'static>;
+}
+
+impl NSNotificationCenter {
+    /// Returns the app's default notification center.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotificationcenter/1414169-defaultcenter).
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "defaultCenter")]
+    pub fn default() -> Arc<Self> {
+        let center =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), defaultCenter]) };
+        center.expect("No default notification center")
+    }
+
+    /// Creates a notification center separate from the default one.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Synchronously delivers a notification named `name` to the observers
+    /// that match it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotificationcenter/1410608-postnotificationname).
+    #[inline]
+    #[doc(alias = "postNotificationName")]
+    #[doc(alias = "postNotificationName:object:userInfo:")]
+    pub fn post(
+        &self,
+        name: &NSString,
+        object: Option<&NSObject>,
+        user_info: Option<&NSDictionary<NSString<'static>, NSObject<'static>>>,
+    ) {
+        unsafe {
+            _msg_send_any![self, postNotificationName: name object: object userInfo: user_info => ()]
+        }
+    }
+
+    /// Calls `f` with each notification named `name` from `object`, until the
+    /// returned token is dropped.
+    ///
+    /// If `name` or `object` is `None`, notifications are not filtered by it.
+    ///
+    /// If `queue` is `None`, `f` is called synchronously on the posting
+    /// thread. Otherwise, it is run as an operation on `queue`. Either way, `f`
+    /// may be called from any thread, so it must be `Send` and `Sync`. Panics
+    /// in `f` abort the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotificationcenter/1411723-addobserverforname).
+    #[doc(alias = "addObserverForName")]
+    #[doc(alias = "addObserverForName:object:queue:usingBlock:")]
+    pub fn add_observer<F>(
+        &self,
+        name: Option<&NSString>,
+        object: Option<&NSObject>,
+        queue: Option<&NSOperationQueue>,
+        f: F,
+    ) -> ObserverToken
+    where
+        F: Fn(&NSNotification) + Send + Sync + 'static,
+    {
+        let block = RcBlock::<(*const NSNotification,), _>::without_signature(
+            move |notification: *const NSNotification| {
+                let notification = unsafe { &*notification };
+                abort_on_panic(|| f(notification));
+            },
+        );
+
+        let observer = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    self,
+                    addObserverForName: name
+                    object: object
+                    queue: queue
+                    usingBlock: block.as_ptr()
+                ]
+            })
+        };
+
+        ObserverToken::new(Observer::Notification(NotificationObserver {
+            center: Arc::retain(self),
+            observer: observer.expect("Failed to add observer"),
+        }))
+    }
+}
+
+// An observer added to a notification center, whose block releases the
+// closure once it is removed.
+pub(crate) struct NotificationObserver {
+    center: Arc<NSNotificationCenter>,
+    observer: Arc<NSObject<'static>>,
+}
+
+impl NotificationObserver {
+    #[inline]
+    pub(crate) fn remove(&self) {
+        let observer = &*self.observer;
+        unsafe { _msg_send_any![self.center, removeObserver: observer => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSNumber;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    };
+
+    #[test]
+    fn post_and_observe() {
+        let center = NSNotificationCenter::new();
+        let name = NSString::from_str("FruityTestNotification");
+        let other = NSString::from_str("FruityOtherNotification");
+
+        let received = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let token = {
+            let received = received.clone();
+            center.add_observer(Some(&name), None, None, move |notification| {
+                let value = notification
+                    .user_info()
+                    .and_then(|info| {
+                        let key = NSString::from_str("value");
+                        info.get_as::<NSNumber>(&key).and_then(|n| n.as_usize())
+                    })
+                    .unwrap();
+                assert!(notification.object().is_none());
+                received.lock().unwrap().push(value);
+            })
+        };
+
+        let key = NSString::from_str("value");
+        for value in 1..=2 {
+            let value = NSNumber::from_unsigned_integer(value);
+            let info = NSDictionary::from_pairs(&[(&*key, &***value)]);
+            center.post(&name, None, Some(&info));
+        }
+        center.post(&other, None, None);
+        assert_eq!(*received.lock().unwrap(), [1, 2]);
+
+        drop(token);
+        let info = NSDictionary::from_pairs(&[(&*key, &***NSNumber::from_unsigned_integer(3))]);
+        center.post(&name, None, Some(&info));
+        assert_eq!(*received.lock().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn filter_by_object() {
+        let center = NSNotificationCenter::new();
+        let name = NSString::from_str("FruityObjectNotification");
+        let (a, b) = (NSString::from_str("a"), NSString::from_str("b"));
+
+        let count = std::sync::Arc::new(AtomicUsize::new(0));
+        let _token = {
+            let count = count.clone();
+            center.add_observer(None, Some(&**a), None, move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        center.post(&name, Some(&**a), None);
+        center.post(&name, Some(&**b), None);
+        center.post(&name, None, None);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn queue() {
+        let center = NSNotificationCenter::default();
+        let name = NSString::from_str("FruityQueueNotification");
+
+        let queue = NSOperationQueue::new();
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let token = center.add_observer(Some(&name), None, Some(&queue), move |notification| {
+            tx.lock()
+                .unwrap()
+                .send(notification.name().to_string())
+                .unwrap();
+        });
+
+        // The token can be moved to and dropped on another thread.
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send(&token);
+
+        center.post(&name, None, None);
+        assert_eq!(rx.recv().unwrap(), "FruityQueueNotification");
+
+        std::thread::spawn(move || drop(token)).join().unwrap();
+        center.post(&name, None, None);
+        queue.wait_until_all_operations_are_finished();
+        assert!(rx.try_recv().is_err());
+    }
+}
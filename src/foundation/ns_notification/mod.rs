@@ -0,0 +1,51 @@
+use super::{NSDictionary, NSString};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, NSObject};
+use std::fmt;
+
+mod center;
+
+pub use center::*;
+
+objc_subclass! {
+    /// Information broadcast to observers by an
+    /// [`NSNotificationCenter`](struct.NSNotificationCenter.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotification).
+    pub class NSNotification: NSObject<'static>;
+}
+
+impl fmt::Debug for NSNotification {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSNotification").field(&self.name()).finish()
+    }
+}
+
+impl NSNotification {
+    /// Returns the name of the notification.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotification/1416472-name).
+    #[inline]
+    pub fn name(&self) -> Arc<NSString<'static>> {
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, name]) };
+        name.expect("Notification has no name")
+    }
+
+    /// Returns the object that posted the notification, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotification/1414469-object).
+    #[inline]
+    pub fn object(&self) -> Option<Arc<NSObject<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, object]) }
+    }
+
+    /// Returns the information associated with the notification, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsnotification/1409222-userinfo).
+    #[inline]
+    #[doc(alias = "userInfo")]
+    pub fn user_info(&self) -> Option<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, userInfo]) }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::objc::NSObject;
+use crate::objc::{ClassType, NSObject};
 
 objc_subclass! {
     /// A singleton object used to represent null values in collection objects that
@@ -30,3 +30,50 @@ impl NSNull {
         unsafe { kCFNull }
     }
 }
+
+/// `NSNull` checks.
+///
+/// Requires the **`foundation`** feature flag.
+impl NSObject<'_> {
+    /// Returns `true` if this object is an instance of [`NSNull`].
+    ///
+    /// [`NSNull`]: ../foundation/struct.NSNull.html
+    #[inline]
+    pub fn is_ns_null(&self) -> bool {
+        self.is_kind_of_class(NSNull::class())
+    }
+
+    /// Returns `None` if this object is an instance of [`NSNull`].
+    ///
+    /// [`NSNull`]: ../foundation/struct.NSNull.html
+    #[inline]
+    pub fn non_null(&self) -> Option<&Self> {
+        if self.is_ns_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSString;
+
+    #[test]
+    fn singleton() {
+        assert!(std::ptr::eq(NSNull::null(), NSNull::null()));
+        assert!(NSNull::null().is_ns_null());
+    }
+
+    #[test]
+    fn is_ns_null() {
+        let string = NSString::from_str("null");
+        assert!(!string.is_ns_null());
+        assert!(string.non_null().is_some());
+
+        let object: &NSObject = NSNull::null();
+        assert!(object.non_null().is_none());
+    }
+}
@@ -3,6 +3,7 @@ use crate::core::Arc;
 use crate::objc::{ClassType, NSInteger, NSUInteger, ObjCObject, BOOL};
 use std::{
     cmp::Ordering,
+    convert::TryFrom,
     fmt,
     os::raw::{
         c_char, c_double, c_float, c_int, c_long, c_longlong, c_short, c_uchar, c_uint, c_ulong,
@@ -594,3 +595,212 @@ impl NSNumber {
         unsafe { _msg_send_any![self, unsignedIntegerValue] }
     }
 }
+
+// A numeric value in the widest Rust type for its `objCType`.
+#[derive(Copy, Clone)]
+pub(crate) enum Scalar {
+    Bool(bool),
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64),
+}
+
+/// Lossless conversions.
+///
+/// Unlike the `*_value` methods, these return `None` instead of truncating,
+/// rounding, or wrapping if the stored value cannot be represented exactly.
+impl NSNumber {
+    pub(crate) fn scalar(&self) -> Scalar {
+        if let Some(value) = self._cfboolean_value() {
+            return Scalar::Bool(value);
+        }
+
+        // https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/ObjCRuntimeGuide/Articles/ocrtTypeEncodings.html
+        match self.objc_type_single() as u8 {
+            b'B' => Scalar::Bool(self.bool_value()),
+            b'f' | b'd' => Scalar::Float(self.double_value()),
+            b'c' | b'i' | b's' | b'l' | b'q' => Scalar::Signed(self.longlong_value()),
+            _ => Scalar::Unsigned(self.unsigned_longlong_value()),
+        }
+    }
+
+    /// Returns the value as a boolean if it is a boolean, 0, or 1.
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.scalar() {
+            Scalar::Bool(value) => Some(value),
+            Scalar::Signed(0) | Scalar::Unsigned(0) => Some(false),
+            Scalar::Signed(1) | Scalar::Unsigned(1) => Some(true),
+            Scalar::Float(value) if value == 0.0 || value == 1.0 => Some(value == 1.0),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `i64` if it is an integer within range.
+    ///
+    /// Booleans are converted to 0 or 1, and floating point values are
+    /// converted only if they have no fractional part.
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.scalar() {
+            Scalar::Bool(value) => Some(value as i64),
+            Scalar::Signed(value) => Some(value),
+            Scalar::Unsigned(value) => i64::try_from(value).ok(),
+            // `2^63` is the first value past `i64::MAX` that is exactly
+            // representable as `f64`.
+            Scalar::Float(value) => {
+                let range = -9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0;
+                if value.fract() == 0.0 && range.contains(&value) {
+                    Some(value as i64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the value as a `u64` if it is a non-negative integer within
+    /// range.
+    ///
+    /// Unlike [`as_i64`](#method.as_i64), this can return values above
+    /// `i64::MAX`, such as those created with
+    /// [`from_unsigned_longlong`](#method.from_unsigned_longlong).
+    ///
+    /// Booleans are converted to 0 or 1, and floating point values are
+    /// converted only if they have no fractional part.
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.scalar() {
+            Scalar::Bool(value) => Some(value as u64),
+            Scalar::Signed(value) => u64::try_from(value).ok(),
+            Scalar::Unsigned(value) => Some(value),
+            // `2^64` is the first value past `u64::MAX` that is exactly
+            // representable as `f64`.
+            Scalar::Float(value) => {
+                let range = 0.0..18_446_744_073_709_551_616.0;
+                if value.fract() == 0.0 && range.contains(&value) {
+                    Some(value as u64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the value as a `usize` if it is a non-negative integer within
+    /// range.
+    ///
+    /// Booleans are converted to 0 or 1, and floating point values are
+    /// converted only if they have no fractional part.
+    #[inline]
+    pub fn as_usize(&self) -> Option<usize> {
+        match self.scalar() {
+            Scalar::Bool(value) => Some(value as usize),
+            Scalar::Signed(value) => usize::try_from(value).ok(),
+            Scalar::Unsigned(value) => usize::try_from(value).ok(),
+            Scalar::Float(value) => {
+                // `usize::MAX` rounds up to `usize::MAX + 1`, which is the
+                // first value past it that is exactly representable.
+                let end = usize::MAX as f64;
+                if value.fract() == 0.0 && (0.0..end).contains(&value) {
+                    Some(value as usize)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the value as an `f64` if it can be represented exactly.
+    ///
+    /// Booleans are converted to 0.0 or 1.0. Integers with a magnitude above
+    /// 2<sup>53</sup> may not be representable.
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.scalar() {
+            Scalar::Bool(value) => Some(value as u8 as f64),
+            Scalar::Float(value) => Some(value),
+            Scalar::Signed(value) => {
+                let float = value as f64;
+                if float as i128 == value as i128 {
+                    Some(float)
+                } else {
+                    None
+                }
+            }
+            Scalar::Unsigned(value) => {
+                let float = value as f64;
+                if float as u128 == value as u128 {
+                    Some(float)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_integer_round_trip() {
+        // Small integers are usually tagged pointers.
+        let number: Arc<NSNumber> = 42i64.into();
+        let clones: Vec<Arc<NSNumber>> = (0..100).map(|_| number.clone()).collect();
+        drop(clones);
+
+        assert_eq!(number.as_i64(), Some(42));
+        assert_eq!(number.as_usize(), Some(42));
+        assert_eq!(number.as_f64(), Some(42.0));
+        assert_eq!(number.as_bool(), None);
+        assert_eq!(number.integer_value(), 42);
+    }
+
+    #[test]
+    fn integer_float_equality() {
+        let int: Arc<NSNumber> = 1i64.into();
+        let float: Arc<NSNumber> = 1.0f64.into();
+
+        // Foundation compares numeric values, not types.
+        assert_eq!(*int, *float);
+        assert_eq!(int.cmp(&float), Ordering::Equal);
+
+        let half: Arc<NSNumber> = 1.5f64.into();
+        assert!(*int < *half);
+        assert_eq!(half.as_i64(), None);
+        assert_eq!(half.as_f64(), Some(1.5));
+    }
+
+    #[test]
+    fn lossless() {
+        assert_eq!(NSNumber::yes().as_bool(), Some(true));
+        assert_eq!(NSNumber::no().as_i64(), Some(0));
+
+        let negative: Arc<NSNumber> = (-1i32).into();
+        assert_eq!(negative.as_i64(), Some(-1));
+        assert_eq!(negative.as_usize(), None);
+        assert_eq!(negative.as_u64(), None);
+
+        let max = NSNumber::from_unsigned_longlong(u64::MAX);
+        assert_eq!(max.as_i64(), None);
+        assert_eq!(max.as_u64(), Some(u64::MAX));
+        assert_eq!(max.as_f64(), None);
+
+        let big = NSNumber::from_longlong(i64::MAX);
+        assert_eq!(big.as_i64(), Some(i64::MAX));
+        assert_eq!(big.as_f64(), None);
+
+        assert_eq!(NSNumber::nan().as_i64(), None);
+        assert_eq!(NSNumber::infinity().as_usize(), None);
+        assert_eq!(NSNumber::infinity().as_u64(), None);
+        assert!(NSNumber::nan().as_f64().unwrap().is_nan());
+    }
+
+    #[test]
+    fn objc_type() {
+        let number: Arc<NSNumber> = 1.5f64.into();
+        assert_eq!(number.objc_type_cstr().to_bytes(), b"d");
+    }
+}
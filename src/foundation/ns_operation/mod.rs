@@ -0,0 +1,127 @@
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, RcBlock, BOOL};
+use std::cell::Cell;
+
+mod quality_of_service;
+mod queue;
+
+pub use quality_of_service::*;
+pub use queue::*;
+
+objc_subclass! {
+    /// A unit of work that can be run by an
+    /// [`NSOperationQueue`](struct.NSOperationQueue.html).
+    ///
+    /// Methods take `&self` because operations are safe to share between
+    /// threads.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation).
+    #[derive(PartialEq)]
+    pub class NSOperation: NSObject<'static>;
+}
+
+objc_subclass! {
+    /// An operation that runs a closure.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsblockoperation).
+    #[derive(PartialEq)]
+    pub class NSBlockOperation: NSOperation;
+}
+
+impl NSOperation {
+    /// Makes `self` wait to start until `operation` has finished.
+    ///
+    /// Dependencies must be added before `self` is added to a queue.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1412859-adddependency).
+    #[inline]
+    #[doc(alias = "addDependency")]
+    #[doc(alias = "addDependency:")]
+    pub fn add_dependency(&self, operation: &NSOperation) {
+        unsafe { _msg_send_any![self, addDependency: operation => ()] }
+    }
+
+    /// Removes `operation` from the dependencies of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1412984-removedependency).
+    #[inline]
+    #[doc(alias = "removeDependency")]
+    #[doc(alias = "removeDependency:")]
+    pub fn remove_dependency(&self, operation: &NSOperation) {
+        unsafe { _msg_send_any![self, removeDependency: operation => ()] }
+    }
+
+    /// Tells `self` to not start, if it has not already started.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1408418-cancel).
+    #[inline]
+    pub fn cancel(&self) {
+        unsafe { _msg_send_any![self, cancel => ()] }
+    }
+
+    /// Returns `true` if [`cancel`](#method.cancel) was called.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1408418-iscancelled).
+    #[inline]
+    #[doc(alias = "isCancelled")]
+    pub fn is_cancelled(&self) -> bool {
+        unsafe { _msg_send_any![self, isCancelled => BOOL] }.into()
+    }
+
+    /// Returns `true` if `self` is running.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1415621-isexecuting).
+    #[inline]
+    #[doc(alias = "isExecuting")]
+    pub fn is_executing(&self) -> bool {
+        unsafe { _msg_send_any![self, isExecuting => BOOL] }.into()
+    }
+
+    /// Returns `true` if `self` has finished running or was cancelled before
+    /// starting.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1413540-isfinished).
+    #[inline]
+    #[doc(alias = "isFinished")]
+    pub fn is_finished(&self) -> bool {
+        unsafe { _msg_send_any![self, isFinished => BOOL] }.into()
+    }
+
+    /// Blocks the calling thread until `self` has finished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperation/1415801-waituntilfinished).
+    #[inline]
+    #[doc(alias = "waitUntilFinished")]
+    pub fn wait_until_finished(&self) {
+        unsafe { _msg_send_any![self, waitUntilFinished => ()] }
+    }
+}
+
+impl NSBlockOperation {
+    /// Creates an operation that calls `f` once when run.
+    ///
+    /// Operations may run on any thread, so panics in `f` abort the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsblockoperation/1416272-blockoperationwithblock).
+    #[doc(alias = "blockOperationWithBlock")]
+    #[doc(alias = "blockOperationWithBlock:")]
+    pub fn new<F>(f: F) -> Arc<Self>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let f = Cell::new(Some(f));
+        let block = RcBlock::<(), _>::without_signature(move || {
+            if let Some(f) = f.take() {
+                abort_on_panic(f);
+            }
+        });
+
+        let operation = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), blockOperationWithBlock: block.as_ptr()],
+            )
+        };
+        operation.expect("Failed to create block operation")
+    }
+}
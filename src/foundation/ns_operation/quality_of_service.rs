@@ -0,0 +1,38 @@
+use crate::objc::{MsgReturn, MsgReturnKind, NSInteger};
+
+/// The relative importance of work, which the system uses to prioritize
+/// resources such as CPU time.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsqualityofservice).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSQualityOfService(pub NSInteger);
+
+unsafe impl MsgReturn for NSQualityOfService {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+impl Default for NSQualityOfService {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl NSQualityOfService {
+    /// Work that interacts with the user, such as updating the user
+    /// interface.
+    pub const USER_INTERACTIVE: Self = Self(0x21);
+
+    /// Work that the user has started and is waiting on.
+    pub const USER_INITIATED: Self = Self(0x19);
+
+    /// Work that the user is not immediately waiting on, such as a download.
+    pub const UTILITY: Self = Self(0x11);
+
+    /// Work that the user is unaware of, such as indexing.
+    pub const BACKGROUND: Self = Self(0x09);
+
+    /// Lets the system infer the quality of service from other sources.
+    pub const DEFAULT: Self = Self(-1);
+}
@@ -0,0 +1,278 @@
+use super::{super::NSArray, NSBlockOperation, NSOperation, NSQualityOfService};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, NSObject, BOOL};
+
+objc_subclass! {
+    /// A queue that runs operations according to their dependencies and
+    /// readiness.
+    ///
+    /// Methods take `&self` because queues are safe to share between threads.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue).
+    #[derive(PartialEq)]
+    pub class NSOperationQueue: NSObject<'static>;
+}
+
+impl NSOperationQueue {
+    /// The [`max_concurrent_operation_count`](#method.max_concurrent_operation_count)
+    /// that lets the system decide based on current conditions.
+    #[doc(alias = "NSOperationQueueDefaultMaxConcurrentOperationCount")]
+    pub const DEFAULT_MAX_CONCURRENT_OPERATION_COUNT: NSInteger = -1;
+
+    /// Returns the queue that runs operations on the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1409193-mainqueue).
+    #[inline]
+    #[doc(alias = "mainQueue")]
+    pub fn main() -> Arc<Self> {
+        let queue = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), mainQueue]) };
+        queue.expect("No main operation queue")
+    }
+
+    /// Creates a queue that runs operations on background threads.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1411477-init).
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Returns the maximum number of operations that can run at the same
+    /// time.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1414982-maxconcurrentoperationcount).
+    #[inline]
+    #[doc(alias = "maxConcurrentOperationCount")]
+    pub fn max_concurrent_operation_count(&self) -> NSInteger {
+        unsafe { _msg_send_any![self, maxConcurrentOperationCount] }
+    }
+
+    /// Sets the maximum number of operations that can run at the same time.
+    ///
+    /// A count of 1 makes `self` run operations serially.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1414982-maxconcurrentoperationcount).
+    #[inline]
+    #[doc(alias = "setMaxConcurrentOperationCount")]
+    #[doc(alias = "setMaxConcurrentOperationCount:")]
+    pub fn set_max_concurrent_operation_count(&self, count: NSInteger) {
+        unsafe { _msg_send_any![self, setMaxConcurrentOperationCount: count => ()] }
+    }
+
+    /// Returns the default quality of service for operations run by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1417919-qualityofservice).
+    #[inline]
+    #[doc(alias = "qualityOfService")]
+    pub fn quality_of_service(&self) -> NSQualityOfService {
+        unsafe { _msg_send_any![self, qualityOfService] }
+    }
+
+    /// Sets the default quality of service for operations run by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1417919-qualityofservice).
+    #[inline]
+    #[doc(alias = "setQualityOfService")]
+    #[doc(alias = "setQualityOfService:")]
+    pub fn set_quality_of_service(&self, quality_of_service: NSQualityOfService) {
+        unsafe { _msg_send_any![self, setQualityOfService: quality_of_service => ()] }
+    }
+
+    /// Returns the number of operations in `self` that have not finished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1415564-operationcount).
+    #[inline]
+    #[doc(alias = "operationCount")]
+    pub fn operation_count(&self) -> usize {
+        unsafe { _msg_send_any![self, operationCount] }
+    }
+}
+
+/// Adding operations.
+impl NSOperationQueue {
+    /// Adds an operation that calls `f`, and returns it so that it can be
+    /// cancelled.
+    ///
+    /// Panics in `f` abort the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1408669-addoperationwithblock).
+    #[inline]
+    #[doc(alias = "addOperationWithBlock")]
+    #[doc(alias = "addOperationWithBlock:")]
+    pub fn add_operation<F>(&self, f: F) -> Arc<NSBlockOperation>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let operation = NSBlockOperation::new(f);
+        self.add(&operation);
+        operation
+    }
+
+    /// Adds `operation`, which starts once its dependencies have finished.
+    ///
+    /// An operation can only be added to one queue, and only once.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1407728-addoperation).
+    #[inline]
+    #[doc(alias = "addOperation")]
+    #[doc(alias = "addOperation:")]
+    pub fn add(&self, operation: &NSOperation) {
+        unsafe { _msg_send_any![self, addOperation: operation => ()] }
+    }
+
+    /// Adds `operations` and blocks the calling thread until they have
+    /// finished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1408011-addoperations).
+    #[doc(alias = "addOperations")]
+    #[doc(alias = "addOperations:waitUntilFinished:")]
+    pub fn add_operations_and_wait(&self, operations: &[&NSOperation]) {
+        let operations = NSArray::from_slice(operations);
+        let wait = BOOL::YES;
+        unsafe { _msg_send_any![self, addOperations: &*operations waitUntilFinished: wait => ()] }
+    }
+
+    /// Cancels all operations in `self` that have not started.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1417849-cancelalloperations).
+    #[inline]
+    #[doc(alias = "cancelAllOperations")]
+    pub fn cancel_all_operations(&self) {
+        unsafe { _msg_send_any![self, cancelAllOperations => ()] }
+    }
+
+    /// Blocks the calling thread until all operations in `self` have
+    /// finished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoperationqueue/1407971-waituntilalloperationsarefinishe).
+    #[inline]
+    #[doc(alias = "waitUntilAllOperationsAreFinished")]
+    pub fn wait_until_all_operations_are_finished(&self) {
+        unsafe { _msg_send_any![self, waitUntilAllOperationsAreFinished => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    };
+
+    #[test]
+    fn dependency_chains() {
+        let queue = NSOperationQueue::new();
+        queue.set_max_concurrent_operation_count(2);
+        queue.set_quality_of_service(NSQualityOfService::UTILITY);
+        assert_eq!(queue.max_concurrent_operation_count(), 2);
+        assert_eq!(queue.quality_of_service(), NSQualityOfService::UTILITY);
+
+        let order = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let running = std::sync::Arc::new(AtomicUsize::new(0));
+        let max_running = std::sync::Arc::new(AtomicUsize::new(0));
+
+        let operations: Vec<Arc<NSBlockOperation>> = (0..100)
+            .map(|i| {
+                let order = order.clone();
+                let running = running.clone();
+                let max_running = max_running.clone();
+                NSBlockOperation::new(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    order.lock().unwrap().push(i);
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        // Form 10 chains, where each operation depends on the one 10 before.
+        for i in 10..operations.len() {
+            operations[i].add_dependency(&operations[i - 10]);
+        }
+
+        let refs: Vec<&NSOperation> = operations.iter().map(|op| &***op).collect();
+        queue.add_operations_and_wait(&refs);
+        assert_eq!(queue.operation_count(), 0);
+        assert!(operations.iter().all(|op| op.is_finished()));
+
+        let order = order.lock().unwrap();
+        assert_eq!(order.len(), 100);
+
+        let mut position = [0; 100];
+        for (index, &i) in order.iter().enumerate() {
+            position[i] = index;
+        }
+        for i in 10..100 {
+            assert!(
+                position[i - 10] < position[i],
+                "{} ran before {}",
+                i,
+                i - 10
+            );
+        }
+
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn cancel() {
+        let queue = NSOperationQueue::new();
+        queue.set_max_concurrent_operation_count(1);
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let blocker = queue.add_operation(move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        });
+        started_rx.recv().unwrap();
+
+        let ran = std::sync::Arc::new(AtomicUsize::new(0));
+        let cancelled = {
+            let ran = ran.clone();
+            queue.add_operation(move || {
+                ran.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let kept = {
+            let ran = ran.clone();
+            queue.add_operation(move || {
+                ran.fetch_add(10, Ordering::SeqCst);
+            })
+        };
+        assert!(blocker.is_executing());
+        assert_eq!(queue.operation_count(), 3);
+
+        cancelled.cancel();
+        release_tx.send(()).unwrap();
+        queue.wait_until_all_operations_are_finished();
+
+        assert!(cancelled.is_cancelled() && cancelled.is_finished());
+        assert!(!kept.is_cancelled() && kept.is_finished());
+        assert_eq!(ran.load(Ordering::SeqCst), 10);
+
+        // Everything cancelled before starting.
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        queue.add_operation(move || release_rx.recv().unwrap());
+        let pending: Vec<_> = (0..3)
+            .map(|_| {
+                let ran = ran.clone();
+                queue.add_operation(move || {
+                    ran.fetch_add(100, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        queue.cancel_all_operations();
+        release_tx.send(()).unwrap();
+        queue.wait_until_all_operations_are_finished();
+        assert!(pending.iter().all(|op| op.is_cancelled()));
+        assert_eq!(ran.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn main_queue() {
+        assert!(*NSOperationQueue::main() == *NSOperationQueue::main());
+        assert_eq!(NSOperationQueue::main().max_concurrent_operation_count(), 1);
+    }
+}
@@ -0,0 +1,231 @@
+use super::{NSArray, NSDictionary, NSString};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, BOOL};
+use std::fmt;
+
+objc_subclass! {
+    /// A definition of logical conditions for constraining a search for a
+    /// fetch or for in-memory filtering.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate).
+    #[derive(PartialEq)]
+    pub class NSPredicate: NSObject<'static>;
+}
+
+impl fmt::Debug for NSPredicate {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSPredicate")
+            .field(&self.predicate_format())
+            .finish()
+    }
+}
+
+impl NSPredicate {
+    /// Creates a predicate by substituting `args` into `format`.
+    ///
+    /// Each `%@` takes an object, and each numeric specifier such as `%d`
+    /// takes an [`NSNumber`](struct.NSNumber.html). `%K` takes an
+    /// [`NSString`](struct.NSString.html) key path.
+    ///
+    /// See the
+    /// [predicate format string syntax](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/Predicates/Articles/pSyntax.html).
+    ///
+    /// # Safety
+    ///
+    /// `format` must be valid and `args` must match its specifiers.
+    /// Otherwise, an Objective-C exception is raised.
+    ///
+    /// The predicate must only be evaluated against objects that have the key
+    /// paths it refers to, since evaluation otherwise raises an exception.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate/1417368-predicatewithformat).
+    #[doc(alias = "predicateWithFormat")]
+    #[doc(alias = "predicateWithFormat:argumentArray:")]
+    pub unsafe fn from_format(format: &str, args: &[&NSObject<'static>]) -> Arc<Self> {
+        let format = NSString::from_str(format);
+        let args = NSArray::from_slice(args);
+        let predicate = retain_autoreleased(
+            || _msg_send_any![Self::class(), predicateWithFormat: &*format argumentArray: &*args],
+        );
+        predicate.expect("Could not create predicate")
+    }
+
+    /// Returns a predicate that always evaluates to `value`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate/1414033-predicatewithvalue).
+    #[inline]
+    #[doc(alias = "predicateWithValue")]
+    #[doc(alias = "predicateWithValue:")]
+    pub fn value(value: bool) -> Arc<Self> {
+        let value = BOOL::from(value);
+        let predicate = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), predicateWithValue: value])
+        };
+        predicate.expect("Could not create predicate")
+    }
+
+    /// Returns the format string of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate/1417727-predicateformat).
+    #[inline]
+    #[doc(alias = "predicateFormat")]
+    pub fn predicate_format(&self) -> Arc<NSString<'static>> {
+        let format = unsafe { retain_autoreleased(|| _msg_send_any![self, predicateFormat]) };
+        format.expect("Predicate has no format")
+    }
+
+    /// Returns `true` if `object` satisfies `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate/1417924-evaluatewithobject).
+    #[inline]
+    #[doc(alias = "evaluateWithObject")]
+    #[doc(alias = "evaluateWithObject:")]
+    pub fn evaluate(&self, object: &NSObject) -> bool {
+        unsafe { _msg_send_any![self, evaluateWithObject: object => BOOL] }.into()
+    }
+
+    /// Returns `true` if `object` satisfies `self` after replacing each `$name`
+    /// variable with the value for `name` in `variables`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspredicate/1416415-evaluatewithobject).
+    #[inline]
+    #[doc(alias = "evaluateWithObject:substitutionVariables:")]
+    pub fn evaluate_with_substitutions(
+        &self,
+        object: &NSObject,
+        variables: &NSDictionary<NSString<'static>, NSObject<'static>>,
+    ) -> bool {
+        unsafe {
+            _msg_send_any![
+                self,
+                evaluateWithObject: object
+                substitutionVariables: variables
+                => BOOL
+            ]
+        }
+        .into()
+    }
+}
+
+objc_subclass! {
+    /// A predicate that combines other predicates with `AND`, `OR`, or
+    /// `NOT`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscompoundpredicate).
+    #[derive(PartialEq)]
+    pub class NSCompoundPredicate: NSPredicate;
+}
+
+impl NSCompoundPredicate {
+    /// Returns a predicate that is satisfied when all `predicates` are.
+    ///
+    /// An empty slice is always satisfied.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscompoundpredicate/1407855-andpredicatewithsubpredicates).
+    #[inline]
+    #[doc(alias = "andPredicateWithSubpredicates")]
+    #[doc(alias = "andPredicateWithSubpredicates:")]
+    pub fn and(predicates: &[&NSPredicate]) -> Arc<Self> {
+        let predicates = NSArray::from_slice(predicates);
+        let predicate = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), andPredicateWithSubpredicates: &*predicates],
+            )
+        };
+        predicate.expect("Could not create predicate")
+    }
+
+    /// Returns a predicate that is satisfied when any of `predicates` are.
+    ///
+    /// An empty slice is never satisfied.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscompoundpredicate/1409842-orpredicatewithsubpredicates).
+    #[inline]
+    #[doc(alias = "orPredicateWithSubpredicates")]
+    #[doc(alias = "orPredicateWithSubpredicates:")]
+    pub fn or(predicates: &[&NSPredicate]) -> Arc<Self> {
+        let predicates = NSArray::from_slice(predicates);
+        let predicate = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), orPredicateWithSubpredicates: &*predicates],
+            )
+        };
+        predicate.expect("Could not create predicate")
+    }
+
+    /// Returns a predicate that is satisfied when `predicate` is not.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nscompoundpredicate/1417343-notpredicatewithsubpredicate).
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "notPredicateWithSubpredicate")]
+    #[doc(alias = "notPredicateWithSubpredicate:")]
+    pub fn not(predicate: &NSPredicate) -> Arc<Self> {
+        let predicate = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), notPredicateWithSubpredicate: predicate],
+            )
+        };
+        predicate.expect("Could not create predicate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSNumber;
+
+    type Person = NSDictionary<NSString<'static>, NSObject<'static>>;
+
+    fn person(name: &str, age: i32) -> Arc<Person> {
+        let (name_key, age_key) = (NSString::from_str("name"), NSString::from_str("age"));
+        let (name, age) = (NSString::from_str(name), NSNumber::from_int(age));
+        NSDictionary::from_pairs(&[(&*name_key, &**name), (&*age_key, &***age)])
+    }
+
+    #[test]
+    fn format() {
+        let (alice, bob, anne) = (person("alice", 30), person("bob", 40), person("anne", 12));
+        let people = NSArray::from_slice(&[&*alice, &*bob, &*anne]);
+
+        let prefix = NSString::from_str("a");
+        let age = NSNumber::from_int(18);
+        let predicate = unsafe {
+            NSPredicate::from_format("name BEGINSWITH %@ AND age >= %d", &[&prefix, &***age])
+        };
+        assert!(predicate.evaluate(&alice));
+        assert!(!predicate.evaluate(&bob));
+        assert!(!predicate.evaluate(&anne));
+
+        let adults = people.filtered(&predicate);
+        assert_eq!(adults.len(), 1);
+        assert!(adults[0] == *alice);
+    }
+
+    #[test]
+    fn substitutions() {
+        let predicate = unsafe { NSPredicate::from_format("age < $limit", &[]) };
+        let (key, limit) = (NSString::from_str("limit"), NSNumber::from_int(35));
+        let variables = NSDictionary::from_pairs(&[(&*key, &***limit)]);
+
+        assert!(predicate.evaluate_with_substitutions(&person("alice", 30), &variables));
+        assert!(!predicate.evaluate_with_substitutions(&person("bob", 40), &variables));
+    }
+
+    #[test]
+    fn compound() {
+        let (yes, no) = (NSPredicate::value(true), NSPredicate::value(false));
+        let object = NSString::from_str("");
+
+        assert!(NSCompoundPredicate::and(&[&yes, &yes]).evaluate(&object));
+        assert!(!NSCompoundPredicate::and(&[&yes, &no]).evaluate(&object));
+        assert!(NSCompoundPredicate::or(&[&no, &yes]).evaluate(&object));
+        assert!(!NSCompoundPredicate::or(&[]).evaluate(&object));
+        assert!(NSCompoundPredicate::not(&no).evaluate(&object));
+        assert_eq!(
+            *NSPredicate::value(true).predicate_format(),
+            *"TRUEPREDICATE"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use super::NSProcessInfo;
+use crate::core::Arc;
+use crate::objc::NSObject;
+use std::ops;
+
+/// Options for [`NSProcessInfo::begin_activity`](struct.NSProcessInfo.html#method.begin_activity).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsactivityoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSActivityOptions(pub u64);
+
+impl NSActivityOptions {
+    /// Prevents the display from sleeping while idle.
+    pub const IDLE_DISPLAY_SLEEP_DISABLED: Self = Self(1 << 40);
+
+    /// Prevents the system from sleeping while idle.
+    pub const IDLE_SYSTEM_SLEEP_DISABLED: Self = Self(1 << 20);
+
+    /// Prevents sudden termination.
+    pub const SUDDEN_TERMINATION_DISABLED: Self = Self(1 << 14);
+
+    /// Prevents automatic termination.
+    pub const AUTOMATIC_TERMINATION_DISABLED: Self = Self(1 << 15);
+
+    /// Work requested by the user, which prevents App Nap and idle system
+    /// sleep.
+    pub const USER_INITIATED: Self = Self(0x00FF_FFFF | Self::IDLE_SYSTEM_SLEEP_DISABLED.0);
+
+    /// Work requested by the user that still allows idle system sleep.
+    pub const USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP: Self =
+        Self(Self::USER_INITIATED.0 & !Self::IDLE_SYSTEM_SLEEP_DISABLED.0);
+
+    /// Maintenance or other work not requested by the user.
+    pub const BACKGROUND: Self = Self(0xFF);
+
+    /// Work that requires the highest timer accuracy, such as media
+    /// playback.
+    pub const LATENCY_CRITICAL: Self = Self(0xFF_0000_0000);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSActivityOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSActivityOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSActivityOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// An activity started by
+/// [`NSProcessInfo::begin_activity`](struct.NSProcessInfo.html#method.begin_activity),
+/// which ends when this is dropped.
+#[must_use = "the activity ends when the token is dropped"]
+pub struct ActivityToken {
+    pub(super) process_info: Arc<NSProcessInfo>,
+    pub(super) token: Arc<NSObject<'static>>,
+}
+
+impl Drop for ActivityToken {
+    #[inline]
+    fn drop(&mut self) {
+        let token: &NSObject = &self.token;
+        unsafe { _msg_send_any![&*self.process_info, endActivity: token => ()] };
+    }
+}
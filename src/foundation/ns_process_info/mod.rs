@@ -0,0 +1,331 @@
+use super::{NSArray, NSDictionary, NSString};
+use crate::core::Arc;
+use crate::objc::{
+    retain_autoreleased, ClassType, MsgReturn, MsgReturnKind, NSInteger, NSObject, NSUInteger, BOOL,
+};
+use std::{collections::HashMap, os::raw::c_int};
+
+mod activity;
+
+pub use activity::*;
+
+objc_subclass! {
+    /// A collection of information about the current process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo).
+    pub class NSProcessInfo: NSObject<'static>;
+}
+
+/// An operating system version, such as 10.15.7.
+///
+/// Versions are ordered by major, then minor, then patch version.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsoperatingsystemversion).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSOperatingSystemVersion {
+    /// The major release number, such as 10 in 10.15.7.
+    pub major_version: NSInteger,
+
+    /// The minor release number, such as 15 in 10.15.7.
+    pub minor_version: NSInteger,
+
+    /// The update release number, such as 7 in 10.15.7.
+    pub patch_version: NSInteger,
+}
+
+unsafe impl MsgReturn for NSOperatingSystemVersion {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
+impl From<(NSInteger, NSInteger, NSInteger)> for NSOperatingSystemVersion {
+    #[inline]
+    fn from(
+        (major_version, minor_version, patch_version): (NSInteger, NSInteger, NSInteger),
+    ) -> Self {
+        Self {
+            major_version,
+            minor_version,
+            patch_version,
+        }
+    }
+}
+
+impl From<NSOperatingSystemVersion> for (NSInteger, NSInteger, NSInteger) {
+    #[inline]
+    fn from(version: NSOperatingSystemVersion) -> Self {
+        (
+            version.major_version,
+            version.minor_version,
+            version.patch_version,
+        )
+    }
+}
+
+/// The level of heat in the system, which apps should respond to by reducing
+/// work.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfothermalstate).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSProcessInfoThermalState(pub NSInteger);
+
+unsafe impl MsgReturn for NSProcessInfoThermalState {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+impl NSProcessInfoThermalState {
+    /// Within normal limits.
+    pub const NOMINAL: Self = Self(0);
+
+    /// Slightly elevated.
+    pub const FAIR: Self = Self(1);
+
+    /// High.
+    pub const SERIOUS: Self = Self(2);
+
+    /// Significantly impacting performance, so the system needs to cool down.
+    pub const CRITICAL: Self = Self(3);
+}
+
+impl NSProcessInfo {
+    /// Returns the process information agent for the current process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1410947-processinfo).
+    #[inline]
+    #[doc(alias = "processInfo")]
+    pub fn process_info() -> Arc<Self> {
+        let info = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), processInfo]) };
+        info.expect("No process info")
+    }
+
+    /// Returns the name of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1409157-processname).
+    #[inline]
+    #[doc(alias = "processName")]
+    pub fn process_name(&self) -> Arc<NSString<'static>> {
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, processName]) };
+        name.expect("Process has no name")
+    }
+
+    /// Returns the identifier of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1409714-processidentifier).
+    #[inline]
+    #[doc(alias = "processIdentifier")]
+    pub fn process_identifier(&self) -> c_int {
+        unsafe { _msg_send_any![self, processIdentifier] }
+    }
+
+    /// Returns the command-line arguments of the process, starting with the
+    /// executable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415596-arguments).
+    pub fn arguments(&self) -> Vec<String> {
+        let arguments: Option<Arc<NSArray<NSString<'static>>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, arguments]) };
+        match arguments {
+            Some(arguments) => arguments.iter().map(|a| a.to_string()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the environment variables of the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1417911-environment).
+    pub fn environment(&self) -> HashMap<String, String> {
+        let environment: Option<Arc<NSDictionary<NSString<'static>, NSString<'static>>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, environment]) };
+        match environment {
+            Some(environment) => environment
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Returns the name of the host computer on which the process is
+    /// running.
+    ///
+    /// This may block while the name is resolved over the network.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1417808-hostname).
+    #[inline]
+    #[doc(alias = "hostName")]
+    pub fn host_name(&self) -> Arc<NSString<'static>> {
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, hostName]) };
+        name.expect("Host has no name")
+    }
+
+    /// Returns a string that is unique for the network and process, such as
+    /// `"06F1EAD9-B1C3-4C3D-9D45-1C4BBD5B2C0A-12345-000001A2B3C4D5E6"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1417345-globallyuniquestring).
+    #[inline]
+    #[doc(alias = "globallyUniqueString")]
+    pub fn globally_unique_string(&self) -> Arc<NSString<'static>> {
+        let string = unsafe { retain_autoreleased(|| _msg_send_any![self, globallyUniqueString]) };
+        string.expect("Could not create unique string")
+    }
+}
+
+/// System information.
+impl NSProcessInfo {
+    /// Returns the version of the operating system on which the process is
+    /// running.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1410906-operatingsystemversion).
+    #[inline]
+    #[doc(alias = "operatingSystemVersion")]
+    pub fn operating_system_version(&self) -> NSOperatingSystemVersion {
+        unsafe { _msg_send_any![self, operatingSystemVersion] }
+    }
+
+    /// Returns `true` if the operating system version is at least `version`.
+    ///
+    /// This is useful for checking whether newer APIs are available at
+    /// runtime.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1414876-isoperatingsystematleastversion).
+    #[inline]
+    #[doc(alias = "isOperatingSystemAtLeastVersion")]
+    #[doc(alias = "isOperatingSystemAtLeastVersion:")]
+    pub fn is_operating_system_at_least(
+        &self,
+        version: impl Into<NSOperatingSystemVersion>,
+    ) -> bool {
+        let version = version.into();
+        unsafe { _msg_send_any![self, isOperatingSystemAtLeastVersion: version => BOOL] }.into()
+    }
+
+    /// Returns the amount of physical memory on the computer in bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1408211-physicalmemory).
+    #[inline]
+    #[doc(alias = "physicalMemory")]
+    pub fn physical_memory(&self) -> u64 {
+        unsafe { _msg_send_any![self, physicalMemory] }
+    }
+
+    /// Returns the number of processing cores on the computer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415622-processorcount).
+    #[inline]
+    #[doc(alias = "processorCount")]
+    pub fn processor_count(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, processorCount] }
+    }
+
+    /// Returns the number of active processing cores on the computer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415631-activeprocessorcount).
+    #[inline]
+    #[doc(alias = "activeProcessorCount")]
+    pub fn active_processor_count(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, activeProcessorCount] }
+    }
+
+    /// Returns the current thermal state of the system.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1417480-thermalstate).
+    #[inline]
+    #[doc(alias = "thermalState")]
+    pub fn thermal_state(&self) -> NSProcessInfoThermalState {
+        unsafe { _msg_send_any![self, thermalState] }
+    }
+
+    /// Returns `true` if the user has enabled Low Power Mode.
+    ///
+    /// This is always `false` before macOS 12.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1617047-lowpowermodeenabled).
+    #[inline]
+    #[doc(alias = "isLowPowerModeEnabled")]
+    pub fn is_low_power_mode_enabled(&self) -> bool {
+        if !self.responds_to_selector(selector!(isLowPowerModeEnabled)) {
+            return false;
+        }
+        unsafe { _msg_send_any![self, isLowPowerModeEnabled => BOOL] }.into()
+    }
+}
+
+/// Activities.
+impl NSProcessInfo {
+    /// Begins an activity, such as work that should not be interrupted by
+    /// App Nap, until the returned token is dropped.
+    ///
+    /// `reason` is used for debugging.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415995-beginactivitywithoptions).
+    #[doc(alias = "beginActivityWithOptions")]
+    #[doc(alias = "beginActivityWithOptions:reason:")]
+    #[doc(alias = "endActivity")]
+    #[doc(alias = "endActivity:")]
+    pub fn begin_activity(&self, options: NSActivityOptions, reason: &str) -> ActivityToken {
+        let reason = NSString::from_str(reason);
+        let token = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, beginActivityWithOptions: options reason: &*reason],
+            )
+        };
+        ActivityToken {
+            process_info: Arc::retain(self),
+            token: token.expect("Could not begin activity"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn process() {
+        let info = NSProcessInfo::process_info();
+        assert_eq!(info.process_identifier() as u32, std::process::id());
+        assert_eq!(info.arguments(), env::args().collect::<Vec<_>>());
+        assert!(!info.process_name().is_empty());
+
+        let environment = info.environment();
+        if let Ok(path) = env::var("PATH") {
+            assert_eq!(environment.get("PATH"), Some(&path));
+        }
+
+        let (a, b) = (info.globally_unique_string(), info.globally_unique_string());
+        assert_ne!(*a, *b);
+    }
+
+    #[test]
+    fn operating_system_version() {
+        let info = NSProcessInfo::process_info();
+        let version = info.operating_system_version();
+        assert!(version.major_version >= 10);
+
+        assert!(info.is_operating_system_at_least(version));
+        assert!(info.is_operating_system_at_least((10, 0, 0)));
+        assert!(!info.is_operating_system_at_least((version.major_version + 1, 0, 0)));
+
+        let (major, minor, patch) = version.into();
+        assert!(version < (major, minor, patch + 1).into());
+    }
+
+    #[test]
+    fn system() {
+        let info = NSProcessInfo::process_info();
+        assert!(info.physical_memory() > 0);
+        assert!(info.active_processor_count() > 0);
+        assert!(info.active_processor_count() <= info.processor_count());
+        assert!(info.thermal_state() <= NSProcessInfoThermalState::CRITICAL);
+        let _ = info.is_low_power_mode_enabled();
+    }
+
+    #[test]
+    fn activity() {
+        let info = NSProcessInfo::process_info();
+        let token = info.begin_activity(NSActivityOptions::USER_INITIATED, "Testing");
+        drop(token);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::objc::NSUInteger;
+
+/// A property list format used by
+/// [`NSPropertyListSerialization`](struct.NSPropertyListSerialization.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/propertylistserialization/propertylistformat).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSPropertyListFormat {
+    /// The legacy OpenStep text format, which can be read but not written.
+    OpenStep = 1,
+    /// The XML format.
+    ///
+    /// Dates are written with a precision of one second.
+    XML = 100,
+    /// The binary format, which preserves dates exactly.
+    Binary = 200,
+}
+
+impl NSPropertyListFormat {
+    /// Returns the format for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSUInteger) -> Option<Self> {
+        match value {
+            1 => Some(Self::OpenStep),
+            100 => Some(Self::XML),
+            200 => Some(Self::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Which objects are mutable when reading a property list with
+/// [`NSPropertyListSerialization`](struct.NSPropertyListSerialization.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/propertylistserialization/mutabilityoptions).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSPropertyListMutabilityOptions {
+    /// Create all objects as immutable.
+    Immutable = 0,
+    /// Create arrays and dictionaries as mutable objects.
+    MutableContainers = 1,
+    /// Create arrays, dictionaries, strings, and data as mutable objects.
+    MutableContainersAndLeaves = 2,
+}
+
+impl Default for NSPropertyListMutabilityOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::Immutable
+    }
+}
@@ -0,0 +1,308 @@
+use super::{ns_error::with_error_out, CocoaErrorCode, NSData, NSError, NSErrorDomain};
+use crate::core::Arc;
+use crate::objc::{ClassType, NSObject, NSUInteger, BOOL};
+
+mod format;
+
+pub use format::*;
+
+objc_subclass! {
+    /// Converts between property lists and Foundation objects.
+    ///
+    /// Property lists are made of
+    /// [`NSDictionary`](struct.NSDictionary.html),
+    /// [`NSArray`](struct.NSArray.html), [`NSString`](struct.NSString.html),
+    /// [`NSNumber`](struct.NSNumber.html), [`NSDate`](struct.NSDate.html),
+    /// and [`NSData`](struct.NSData.html) objects. Dictionary keys must be
+    /// strings.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspropertylistserialization).
+    pub class NSPropertyListSerialization: NSObject<'static>;
+}
+
+impl NSPropertyListSerialization {
+    /// Parses the property list in `data`, and returns it with the format it
+    /// was read from.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspropertylistserialization/propertylistwithdata).
+    #[doc(alias = "propertyListWithData")]
+    #[doc(alias = "propertyListWithData:options:format:error:")]
+    pub fn from_data(
+        data: &NSData,
+        options: NSPropertyListMutabilityOptions,
+    ) -> Result<(Arc<NSObject<'static>>, NSPropertyListFormat), Arc<NSError<'static>>> {
+        let options = options as NSUInteger;
+        let mut format: NSUInteger = 0;
+        let format_out: *mut NSUInteger = &mut format;
+
+        let object = unsafe {
+            with_error_out(|error| {
+                let object: *const NSObject<'static> = _msg_send_any![
+                    Self::class(),
+                    propertyListWithData: data
+                    options: options
+                    format: format_out
+                    error: error
+                ];
+                object.as_ref().map(Arc::retain)
+            })
+        }?;
+
+        // Foundation only reads the formats known here, so the fallback is
+        // never expected to be used.
+        let format = NSPropertyListFormat::from_raw(format).unwrap_or(NSPropertyListFormat::Binary);
+        Ok((object, format))
+    }
+
+    /// Writes `object` as a property list in `format`.
+    ///
+    /// Objects that cannot be written in `format`, including any object in
+    /// [`NSPropertyListFormat::OpenStep`](enum.NSPropertyListFormat.html#variant.OpenStep),
+    /// fail with
+    /// [`CocoaErrorCode::PropertyListWriteInvalid`](enum.CocoaErrorCode.html#variant.PropertyListWriteInvalid).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspropertylistserialization/datawithpropertylist).
+    #[doc(alias = "dataWithPropertyList")]
+    #[doc(alias = "dataWithPropertyList:format:options:error:")]
+    pub fn to_data(
+        object: &NSObject<'static>,
+        format: NSPropertyListFormat,
+    ) -> Result<Arc<NSData>, Arc<NSError<'static>>> {
+        if format == NSPropertyListFormat::OpenStep || !Self::is_valid(object, format) {
+            let code = CocoaErrorCode::PropertyListWriteInvalid.code();
+            let error = NSError::builder(NSErrorDomain::cocoa(), code)
+                .localized_description("The object cannot be written in this property list format.")
+                .build();
+            return Err(error);
+        }
+
+        let format = format as NSUInteger;
+        // The write options are reserved and must be 0.
+        let options: NSUInteger = 0;
+        unsafe {
+            with_error_out(|error| {
+                let data: *const NSData = _msg_send_any![
+                    Self::class(),
+                    dataWithPropertyList: object
+                    format: format
+                    options: options
+                    error: error
+                ];
+                data.as_ref().map(Arc::retain)
+            })
+        }
+    }
+
+    /// Returns `true` if `object` is a property list that can be written in
+    /// `format`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nspropertylistserialization/propertylist).
+    #[inline]
+    #[doc(alias = "propertyList:isValidForFormat:")]
+    pub fn is_valid(object: &NSObject<'static>, format: NSPropertyListFormat) -> bool {
+        let format = format as NSUInteger;
+        unsafe {
+            _msg_send_any![Self::class(), propertyList: object isValidForFormat: format => BOOL]
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use crate::foundation::{
+        NSArray, NSBundle, NSDate, NSDictionary, NSMutableDictionary, NSNumber, NSString,
+    };
+    use std::fs;
+
+    const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.fruity.tests</string>
+    <key>CFBundleName</key>
+    <string>Fruity</string>
+    <key>CFBundleVersion</key>
+    <string>2</string>
+    <key>LSRequiresNativeExecution</key>
+    <true/>
+</dict>
+</plist>
+"#;
+
+    fn parse(
+        data: &[u8],
+        options: NSPropertyListMutabilityOptions,
+    ) -> (Arc<NSObject<'static>>, NSPropertyListFormat) {
+        NSPropertyListSerialization::from_data(&NSData::from_slice(data), options).unwrap()
+    }
+
+    /// Returns `object` as a property list dictionary.
+    fn dictionary<'a>(
+        object: &'a NSObject<'static>,
+    ) -> &'a NSDictionary<NSString<'static>, NSObject<'static>> {
+        assert!(object.is_kind_of_class(NSDictionary::<NSObject, NSObject>::class()));
+        unsafe { &*(object as *const NSObject).cast() }
+    }
+
+    #[test]
+    fn info_plist() {
+        let (object, format) = parse(INFO_PLIST.as_bytes(), Default::default());
+        assert_eq!(format, NSPropertyListFormat::XML);
+
+        let info = dictionary(&object);
+        let identifier = info
+            .get_as::<NSString>(&NSString::from_str("CFBundleIdentifier"))
+            .unwrap();
+        assert_eq!(*identifier, *"com.example.fruity.tests");
+
+        let native = info
+            .get_as::<NSNumber>(&NSString::from_str("LSRequiresNativeExecution"))
+            .unwrap();
+        assert_eq!(native.as_bool(), Some(true));
+
+        // A bundle reads the same dictionary from its `Info.plist` file.
+        let dir = TempDir::new("ns-property-list-info-plist");
+        let path = dir.0.join("Fixture.bundle");
+        fs::create_dir_all(path.join("Contents")).unwrap();
+        fs::write(path.join("Contents/Info.plist"), INFO_PLIST).unwrap();
+
+        let bundle = NSBundle::from_path(&path).unwrap();
+        let bundle_info = bundle.info_dictionary().unwrap();
+
+        assert_eq!(*bundle.bundle_identifier().unwrap(), *identifier);
+        for (key, value) in info {
+            assert!(*bundle_info.get(key).unwrap() == *value, "{}", key);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let blob = NSData::from_slice(&bytes);
+        let precise = NSDate::from_time_interval_since_1970(1_234_567_890.123_456);
+        let whole = NSDate::from_time_interval_since_1970(1_234_567_890.0);
+
+        let keys = [
+            NSString::from_str("blob"),
+            NSString::from_str("precise"),
+            NSString::from_str("whole"),
+            NSString::from_str("list"),
+        ];
+        let number = NSNumber::from_integer(-1);
+        let string = NSString::from_str("x");
+        let list = NSArray::<NSObject>::from_slice(&[&number, &string]);
+        let root = NSDictionary::<NSString, NSObject>::from_pairs(&[
+            (&keys[0], &blob),
+            (&keys[1], &precise),
+            (&keys[2], &whole),
+            (&keys[3], &list),
+        ]);
+
+        for &format in &[NSPropertyListFormat::XML, NSPropertyListFormat::Binary] {
+            assert!(NSPropertyListSerialization::is_valid(&root, format));
+            let data = NSPropertyListSerialization::to_data(&root, format).unwrap();
+            let (object, read_format) = parse(data.as_slice(), Default::default());
+            assert_eq!(read_format, format);
+
+            let read = dictionary(&object);
+            assert_eq!(read.len(), 4);
+
+            let read_blob = read.get_as::<NSData>(&keys[0]).unwrap();
+            assert_eq!(read_blob.as_slice(), &bytes[..]);
+
+            let read_whole = read.get_as::<NSDate>(&keys[2]).unwrap();
+            assert_eq!(
+                read_whole.time_interval_since_1970(),
+                whole.time_interval_since_1970()
+            );
+
+            // XML dates are truncated to whole seconds.
+            let read_precise = read.get_as::<NSDate>(&keys[1]).unwrap();
+            if format == NSPropertyListFormat::Binary {
+                assert_eq!(
+                    read_precise.time_interval_since_1970(),
+                    precise.time_interval_since_1970()
+                );
+            } else {
+                assert_eq!(
+                    read_precise.time_interval_since_1970(),
+                    whole.time_interval_since_1970()
+                );
+            }
+
+            assert!(*read.get(&keys[3]).unwrap() == **list);
+        }
+
+        // Foundation writes binary property lists with this header.
+        let data =
+            NSPropertyListSerialization::to_data(&root, NSPropertyListFormat::Binary).unwrap();
+        assert!(data.as_slice().starts_with(b"bplist00"));
+    }
+
+    #[test]
+    fn mutable_containers() {
+        let options = NSPropertyListMutabilityOptions::MutableContainers;
+        let (object, _) = parse(INFO_PLIST.as_bytes(), options);
+        assert!(object.is_kind_of_class(NSMutableDictionary::<NSObject, NSObject>::class()));
+
+        let (object, _) = parse(INFO_PLIST.as_bytes(), Default::default());
+        assert!(!object.is_kind_of_class(NSMutableDictionary::<NSObject, NSObject>::class()));
+    }
+
+    #[test]
+    fn open_step() {
+        let (object, format) = parse(
+            b"{ name = fruity; tags = (objc, rust); }",
+            Default::default(),
+        );
+        assert_eq!(format, NSPropertyListFormat::OpenStep);
+
+        let root = dictionary(&object);
+        let name = root
+            .get_as::<NSString>(&NSString::from_str("name"))
+            .unwrap();
+        assert_eq!(*name, *"fruity");
+
+        // OpenStep property lists can be read but not written.
+        let error = NSPropertyListSerialization::to_data(&object, NSPropertyListFormat::OpenStep)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::PropertyListWriteInvalid)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let data = NSData::from_slice(b"<plist><dict><key>unterminated");
+        let error = NSPropertyListSerialization::from_data(&data, Default::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::PropertyListReadCorrupt)
+        );
+
+        // Dictionary keys must be strings.
+        let key = NSNumber::from_integer(1);
+        let value = NSString::from_str("one");
+        let invalid = NSDictionary::<NSNumber, NSString>::from_pairs(&[(&key, &value)]);
+        assert!(!NSPropertyListSerialization::is_valid(
+            &invalid,
+            NSPropertyListFormat::Binary
+        ));
+
+        let error = NSPropertyListSerialization::to_data(&invalid, NSPropertyListFormat::XML)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.cocoa_code(),
+            Some(CocoaErrorCode::PropertyListWriteInvalid)
+        );
+    }
+}
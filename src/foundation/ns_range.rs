@@ -1,7 +1,7 @@
-use super::NSString;
+use super::{NSNotFound, NSString};
 use crate::core::Arc;
-use crate::objc::NSUInteger;
-use std::ops::Range;
+use crate::objc::{Encode, Encoding, MsgReturn, MsgReturnKind, NSUInteger};
+use std::{convert::TryFrom, ops::Range};
 
 /// Describe a portion of a series, such as characters in a string or objects in
 /// an array.
@@ -20,6 +20,15 @@ pub struct NSRange {
     pub length: NSUInteger,
 }
 
+unsafe impl Encode for NSRange {
+    const ENCODING: Encoding<'static> =
+        Encoding::Struct("_NSRange", &[NSUInteger::ENCODING, NSUInteger::ENCODING]);
+}
+
+unsafe impl MsgReturn for NSRange {
+    const KIND: MsgReturnKind = MsgReturnKind::Struct;
+}
+
 impl From<Range<NSUInteger>> for NSRange {
     #[inline]
     fn from(range: Range<NSUInteger>) -> Self {
@@ -27,6 +36,19 @@ impl From<Range<NSUInteger>> for NSRange {
     }
 }
 
+impl TryFrom<NSRange> for Range<NSUInteger> {
+    /// The original range is returned on failure.
+    type Error = NSRange;
+
+    /// Converts to a native Rust range, failing if `range` is
+    /// [`not_found`](struct.NSRange.html#method.not_found) or its end
+    /// overflows.
+    #[inline]
+    fn try_from(range: NSRange) -> Result<Self, Self::Error> {
+        range.to_range().ok_or(range)
+    }
+}
+
 impl NSRange {
     /// Returns a range with the specified values.
     ///
@@ -47,6 +69,32 @@ impl NSRange {
         Self::new(location, length)
     }
 
+    /// Returns the range with a location of
+    /// [`NSNotFound`](constant.NSNotFound.html), which is returned by
+    /// Foundation APIs to indicate that nothing was found.
+    #[inline]
+    pub const fn not_found() -> Self {
+        Self::new(NSNotFound as NSUInteger, 0)
+    }
+
+    /// Returns `true` if the location of `self` is
+    /// [`NSNotFound`](constant.NSNotFound.html).
+    #[inline]
+    pub fn is_not_found(&self) -> bool {
+        self.location == NSNotFound as NSUInteger
+    }
+
+    /// Converts to a native Rust range, or `None` if `self` is
+    /// [`not_found`](#method.not_found) or its end overflows.
+    #[inline]
+    pub fn to_range(self) -> Option<Range<NSUInteger>> {
+        if self.is_not_found() {
+            return None;
+        }
+        let end = self.location.checked_add(self.length)?;
+        Some(self.location..end)
+    }
+
     /// Returns a range from a textual representation.
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/1408420-nsrangefromstring).
@@ -85,6 +133,8 @@ impl NSRange {
     /// This is equivalent to
     /// [`NSMaxRange`](https://developer.apple.com/documentation/foundation/1407494-nsmaxrange).
     #[inline]
+    #[doc(alias = "max")]
+    #[doc(alias = "NSMaxRange")]
     pub fn end(&self) -> NSUInteger {
         self.location.wrapping_add(self.length)
     }
@@ -109,19 +159,23 @@ impl NSRange {
         unsafe { NSUnionRange(self, other) }
     }
 
-    /// Returns a range covering all indices in and between `self` and `other`.
-    ///
-    /// If the returned range’s `length` is 0, then the two ranges don’t
-    /// intersect, and the value of `location` is undefined.
+    /// Returns the range of indices in both `self` and `other`, or `None` if
+    /// they don’t intersect.
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/1413065-nsintersectionrange).
     #[inline]
     #[doc(alias = "NSIntersectionRange")]
-    pub fn intersection(self, other: Self) -> Self {
+    pub fn intersection(self, other: Self) -> Option<Self> {
         extern "C" {
             fn NSIntersectionRange(r1: NSRange, r2: NSRange) -> NSRange;
         }
-        unsafe { NSIntersectionRange(self, other) }
+        // The location is undefined if the length is 0.
+        let range = unsafe { NSIntersectionRange(self, other) };
+        if range.is_empty() {
+            None
+        } else {
+            Some(range)
+        }
     }
 }
 
@@ -143,4 +197,31 @@ mod tests {
             assert_eq!(NSRange::from(range.clone()), *expected);
         }
     }
+
+    #[test]
+    fn to_range() {
+        assert_eq!(Range::try_from(NSRange::new(10, 5)), Ok(10..15));
+        assert_eq!(
+            Range::try_from(NSRange::not_found()),
+            Err(NSRange::not_found())
+        );
+
+        let overflow = NSRange::new(1, NSUInteger::MAX);
+        assert_eq!(overflow.to_range(), None);
+        assert!(!overflow.is_not_found());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = NSRange::new(0, 10);
+        let b = NSRange::new(5, 10);
+        let c = NSRange::new(20, 5);
+
+        assert_eq!(a.intersection(b), Some(NSRange::new(5, 5)));
+        assert_eq!(a.intersection(c), None);
+        assert_eq!(a.union(c), NSRange::new(0, 25));
+        assert_eq!(b.end(), 15);
+        assert!(b.contains(14));
+        assert!(!b.contains(15));
+    }
 }
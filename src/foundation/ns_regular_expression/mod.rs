@@ -0,0 +1,421 @@
+use super::{ns_error::with_error_out, NSError, NSRange, NSString};
+use crate::core::Arc;
+use crate::objc::{autoreleasepool, ClassType, NSObject, NSUInteger, Sel, StackBlock, BOOL};
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+mod options;
+mod text_match;
+
+pub use options::*;
+pub use text_match::*;
+
+objc_subclass! {
+    /// An immutable representation of a compiled
+    /// [ICU regular expression](https://unicode-org.github.io/icu/userguide/strings/regexp.html).
+    ///
+    /// All ranges are in UTF-16 code units, like other
+    /// [`NSString`](struct.NSString.html) ranges.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression).
+    #[derive(PartialEq)]
+    pub class NSRegularExpression: NSObject<'static>;
+}
+
+impl fmt::Debug for NSRegularExpression {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSRegularExpression")
+            .field(&self.pattern())
+            .finish()
+    }
+}
+
+impl NSRegularExpression {
+    /// Compiles `pattern`, or returns the error describing why it is
+    /// invalid.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1410900-initwithpattern).
+    #[doc(alias = "regularExpressionWithPattern:options:error:")]
+    #[doc(alias = "initWithPattern:options:error:")]
+    pub fn new(
+        pattern: &str,
+        options: NSRegularExpressionOptions,
+    ) -> Result<Arc<Self>, Arc<NSError<'static>>> {
+        let pattern = NSString::from_str(pattern);
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSRegularExpression>,
+                sel: Sel,
+                pattern: &NSString,
+                options: NSRegularExpressionOptions,
+                error: *mut *mut NSError<'static>,
+            ) -> Option<Arc<NSRegularExpression>>;
+        }
+
+        unsafe {
+            with_error_out(|error| {
+                let value: Arc<Self> = Self::class().alloc();
+                let sel = selector!(initWithPattern:options:error:);
+                objc_msgSend(value, sel, &pattern, options, error)
+            })
+        }
+    }
+
+    /// Returns `string` with regular expression metacharacters escaped, so
+    /// that it can be matched literally within a pattern.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1408386-escapedpatternforstring).
+    #[inline]
+    #[doc(alias = "escapedPatternForString")]
+    #[doc(alias = "escapedPatternForString:")]
+    pub fn escaped_pattern_for(string: &str) -> Arc<NSString<'static>> {
+        let string = NSString::from_str(string);
+        autoreleasepool(|_| unsafe {
+            let escaped: *const NSString =
+                _msg_send_any![Self::class(), escapedPatternForString: &*string];
+            Arc::retain(&*escaped)
+        })
+    }
+
+    /// Returns `string` with template metacharacters (`\` and `$`) escaped,
+    /// so that it can be used literally within a replacement template.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1415036-escapedtemplateforstring).
+    #[inline]
+    #[doc(alias = "escapedTemplateForString")]
+    #[doc(alias = "escapedTemplateForString:")]
+    pub fn escaped_template_for(string: &str) -> Arc<NSString<'static>> {
+        let string = NSString::from_str(string);
+        autoreleasepool(|_| unsafe {
+            let escaped: *const NSString =
+                _msg_send_any![Self::class(), escapedTemplateForString: &*string];
+            Arc::retain(&*escaped)
+        })
+    }
+
+    /// Returns the pattern of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1414298-pattern).
+    #[inline]
+    pub fn pattern(&self) -> Arc<NSString<'static>> {
+        autoreleasepool(|_| unsafe {
+            let pattern: *const NSString = _msg_send_any![self, pattern];
+            Arc::retain(&*pattern)
+        })
+    }
+
+    /// Returns the options used to compile `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1412603-options).
+    #[inline]
+    pub fn options(&self) -> NSRegularExpressionOptions {
+        unsafe { _msg_send_any![self, options] }
+    }
+
+    /// Returns the number of capture groups in the pattern.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1415052-numberofcapturegroups).
+    #[inline]
+    #[doc(alias = "numberOfCaptureGroups")]
+    pub fn number_of_capture_groups(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, numberOfCaptureGroups] }
+    }
+}
+
+#[inline]
+#[track_caller]
+fn check_range(string: &NSString, range: NSRange) {
+    let len = string.length();
+    assert!(
+        range.location <= len && range.length <= len - range.location,
+        "Range {}..{} is out of bounds for length {}",
+        range.location,
+        range.location.wrapping_add(range.length),
+        len,
+    );
+}
+
+/// Matching.
+///
+/// # Panics
+///
+/// These methods panic if `range` is out of bounds for `string`.
+impl NSRegularExpression {
+    /// Returns all matches in `range` of `string`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1412446-matchesinstring).
+    #[doc(alias = "matchesInString")]
+    #[doc(alias = "matchesInString:options:range:")]
+    pub fn matches_in(
+        &self,
+        string: &NSString,
+        options: NSMatchingOptions,
+        range: impl Into<NSRange>,
+    ) -> Vec<Match> {
+        let range = range.into();
+        check_range(string, range);
+
+        autoreleasepool(|_| unsafe {
+            let results: *const super::NSArray<NSObject<'static>> = _msg_send_any![
+                self,
+                matchesInString: string
+                options: options
+                range: range
+            ];
+            (*results)
+                .iter()
+                .map(|result| Match::from_result(result))
+                .collect()
+        })
+    }
+
+    /// Returns the first match in `range` of `string`, or `None` if there is
+    /// none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1411426-firstmatchinstring).
+    #[doc(alias = "firstMatchInString")]
+    #[doc(alias = "firstMatchInString:options:range:")]
+    pub fn first_match_in(
+        &self,
+        string: &NSString,
+        options: NSMatchingOptions,
+        range: impl Into<NSRange>,
+    ) -> Option<Match> {
+        let range = range.into();
+        check_range(string, range);
+
+        autoreleasepool(|_| unsafe {
+            let result: *const NSObject<'static> = _msg_send_any![
+                self,
+                firstMatchInString: string
+                options: options
+                range: range
+            ];
+            result.as_ref().map(|result| Match::from_result(result))
+        })
+    }
+
+    /// Calls `f` with each match in `range` of `string`, until it returns
+    /// `false`.
+    ///
+    /// Unlike [`matches_in`](#method.matches_in), this does not search for
+    /// matches after the last one needed.
+    ///
+    /// If `f` panics, enumeration stops and the panic is resumed after
+    /// control returns from Foundation.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1409687-enumeratematchesinstring).
+    #[doc(alias = "enumerateMatchesInString")]
+    #[doc(alias = "enumerateMatchesInString:options:range:usingBlock:")]
+    pub fn enumerate_matches<F>(
+        &self,
+        string: &NSString,
+        options: NSMatchingOptions,
+        range: impl Into<NSRange>,
+        f: F,
+    ) where
+        F: FnMut(&Match) -> bool,
+    {
+        let range = range.into();
+        check_range(string, range);
+
+        let f = RefCell::new(f);
+        let panic = RefCell::<Option<Box<dyn Any + Send>>>::new(None);
+
+        let block = |result: *const NSObject<'static>, _flags: NSUInteger, stop: *mut BOOL| {
+            // With `REPORT_PROGRESS` or `REPORT_COMPLETION`, the block may be
+            // called without a result.
+            let result = match unsafe { result.as_ref() } {
+                Some(result) => unsafe { Match::from_result(result) },
+                None => return,
+            };
+
+            // Unwinding into Foundation is undefined behavior.
+            let proceed = panic::catch_unwind(AssertUnwindSafe(|| (f.borrow_mut())(&result)));
+            let proceed = match proceed {
+                Ok(proceed) => proceed,
+                Err(payload) => {
+                    *panic.borrow_mut() = Some(payload);
+                    false
+                }
+            };
+            if !proceed {
+                unsafe { *stop = BOOL::YES };
+            }
+        };
+
+        // SAFETY: The block is only called before this method returns.
+        let block =
+            StackBlock::<(*const NSObject<'static>, NSUInteger, *mut BOOL), _>::without_signature(
+                &block,
+            );
+        autoreleasepool(|_| unsafe {
+            _msg_send_any![
+                self,
+                enumerateMatchesInString: string
+                options: options
+                range: range
+                usingBlock: &block
+                => ()
+            ]
+        });
+
+        if let Some(payload) = panic.into_inner() {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    /// Returns a copy of `string` with every match of `self` replaced by
+    /// `template`.
+    ///
+    /// In `template`, `$0` refers to the overall match and `$1` through `$9`
+    /// refer to capture groups. Use
+    /// [`escaped_template_for`](#method.escaped_template_for) for a literal
+    /// replacement.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/1413740-stringbyreplacingmatchesinstring).
+    #[doc(alias = "stringByReplacingMatchesInString")]
+    #[doc(alias = "stringByReplacingMatchesInString:options:range:withTemplate:")]
+    pub fn replace_all(&self, string: &NSString, template: &str) -> Arc<NSString<'static>> {
+        let template = NSString::from_str(template);
+        let options = NSMatchingOptions::NONE;
+        let range = NSRange::new(0, string.length());
+
+        autoreleasepool(|_| unsafe {
+            let replaced: *const NSString = _msg_send_any![
+                self,
+                stringByReplacingMatchesInString: string
+                options: options
+                range: range
+                withTemplate: &*template
+            ];
+            Arc::retain(&*replaced)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(pattern: &str, options: NSRegularExpressionOptions) -> Arc<NSRegularExpression> {
+        match NSRegularExpression::new(pattern, options) {
+            Ok(regex) => regex,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    fn whole(string: &NSString) -> NSRange {
+        NSRange::new(0, string.length())
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let error = NSRegularExpression::new("(", NSRegularExpressionOptions::NONE).unwrap_err();
+        assert_eq!(error.code(), 2048); // NSFormattingError
+    }
+
+    #[test]
+    fn groups() {
+        let regex = compile(r"(\w+)=(\d+)?", NSRegularExpressionOptions::NONE);
+        assert_eq!(regex.number_of_capture_groups(), 2);
+        assert_eq!(*regex.pattern(), *r"(\w+)=(\d+)?");
+
+        let string = NSString::from_str("a=1 b= c=3");
+        let matches = regex.matches_in(&string, NSMatchingOptions::NONE, whole(&string));
+        assert_eq!(matches.len(), 3);
+
+        assert_eq!(matches[0].range(), NSRange::new(0, 3));
+        assert_eq!(matches[0].group_count(), 2);
+        assert_eq!(matches[1].group(1), Some(NSRange::new(4, 1)));
+        assert_eq!(matches[1].group(2), None);
+        assert_eq!(matches[2].group(2), Some(NSRange::new(9, 1)));
+
+        let first = regex
+            .first_match_in(&string, NSMatchingOptions::NONE, 1..10)
+            .unwrap();
+        assert_eq!(first, matches[1]);
+    }
+
+    #[test]
+    fn byte_ranges() {
+        let regex = compile("ü(😀+)", NSRegularExpressionOptions::NONE);
+        let rust = "é ü😀😀 x";
+        let string = NSString::from_str(rust);
+
+        let found = regex
+            .first_match_in(&string, NSMatchingOptions::NONE, whole(&string))
+            .unwrap();
+
+        // UTF-16 ranges differ from byte ranges after non-ASCII characters.
+        assert_eq!(found.range(), NSRange::new(2, 5));
+        assert_eq!(found.group(1), Some(NSRange::new(3, 4)));
+
+        let bytes = found.byte_range(&string).unwrap();
+        assert_eq!(&rust[bytes], "ü😀😀");
+        let bytes = found.group_byte_range(1, &string).unwrap();
+        assert_eq!(&rust[bytes], "😀😀");
+    }
+
+    #[test]
+    fn enumerate_stops_early() {
+        let regex = compile(r"\d", NSRegularExpressionOptions::NONE);
+        let string = NSString::from_str("1 2 3 4 5");
+
+        let mut seen = Vec::new();
+        regex.enumerate_matches(&string, NSMatchingOptions::NONE, whole(&string), |m| {
+            seen.push(m.range().location);
+            seen.len() < 2
+        });
+        assert_eq!(seen, [0, 2]);
+
+        let mut count = 0;
+        let options = NSMatchingOptions::REPORT_COMPLETION;
+        regex.enumerate_matches(&string, options, whole(&string), |_| {
+            count += 1;
+            true
+        });
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn replace_and_escape() {
+        let regex = compile(r"(\w+)@(\w+)", NSRegularExpressionOptions::NONE);
+        let string = NSString::from_str("alice@home, bob@work");
+        assert_eq!(
+            *regex.replace_all(&string, "$2:$1"),
+            *"home:alice, work:bob"
+        );
+
+        let template = NSRegularExpression::escaped_template_for("$1");
+        assert_eq!(
+            *regex.replace_all(&string, &template.to_string()),
+            *"$1, $1"
+        );
+
+        let pattern = NSRegularExpression::escaped_pattern_for("a.b*");
+        let literal = compile(
+            &pattern.to_string(),
+            NSRegularExpressionOptions::CASE_INSENSITIVE,
+        );
+        let string = NSString::from_str("axb A.B*");
+        let matches = literal.matches_in(&string, NSMatchingOptions::NONE, whole(&string));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].range(), NSRange::new(4, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn range_out_of_bounds() {
+        let regex = compile("a", NSRegularExpressionOptions::NONE);
+        let string = NSString::from_str("a");
+        regex.matches_in(&string, NSMatchingOptions::NONE, 0..2);
+    }
+}
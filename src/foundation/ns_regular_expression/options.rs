@@ -0,0 +1,133 @@
+use crate::objc::{MsgReturn, MsgReturnKind, NSUInteger};
+use std::ops;
+
+/// Options for compiling an
+/// [`NSRegularExpression`](struct.NSRegularExpression.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/options).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSRegularExpressionOptions(pub NSUInteger);
+
+unsafe impl MsgReturn for NSRegularExpressionOptions {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+impl NSRegularExpressionOptions {
+    /// No options.
+    pub const NONE: Self = Self(0);
+
+    /// Match letters in the pattern independent of case.
+    pub const CASE_INSENSITIVE: Self = Self(1 << 0);
+
+    /// Ignore whitespace and `#`-prefixed comments in the pattern.
+    pub const ALLOW_COMMENTS_AND_WHITESPACE: Self = Self(1 << 1);
+
+    /// Treat the entire pattern as a literal string.
+    pub const IGNORE_METACHARACTERS: Self = Self(1 << 2);
+
+    /// Allow `.` to match any character, including line separators.
+    pub const DOT_MATCHES_LINE_SEPARATORS: Self = Self(1 << 3);
+
+    /// Allow `^` and `$` to match the start and end of lines.
+    pub const ANCHORS_MATCH_LINES: Self = Self(1 << 4);
+
+    /// Treat only `\n` as a line separator.
+    pub const USE_UNIX_LINE_SEPARATORS: Self = Self(1 << 5);
+
+    /// Use Unicode `TR#29` to specify word boundaries.
+    pub const USE_UNICODE_WORD_BOUNDARIES: Self = Self(1 << 6);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSRegularExpressionOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSRegularExpressionOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSRegularExpressionOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// Options for matching an
+/// [`NSRegularExpression`](struct.NSRegularExpression.html) against a string.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsregularexpression/matchingoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSMatchingOptions(pub NSUInteger);
+
+impl NSMatchingOptions {
+    /// No options.
+    pub const NONE: Self = Self(0);
+
+    /// Call the enumeration closure periodically during long-running match
+    /// operations.
+    pub const REPORT_PROGRESS: Self = Self(1 << 0);
+
+    /// Call the enumeration closure once after the match operation is
+    /// completed.
+    pub const REPORT_COMPLETION: Self = Self(1 << 1);
+
+    /// Only match at the start of the search range.
+    pub const ANCHORED: Self = Self(1 << 2);
+
+    /// Prevent lookahead and lookbehind from seeing outside the search range.
+    pub const WITHOUT_TRANSPARENT_BOUNDS: Self = Self(1 << 3);
+
+    /// Prevent `^` and `$` from matching the start and end of the search
+    /// range.
+    pub const WITHOUT_ANCHORING_BOUNDS: Self = Self(1 << 4);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSMatchingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSMatchingOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSMatchingOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::foundation::{NSRange, NSString};
+use crate::objc::{NSObject, NSUInteger};
+use std::ops::Range;
+
+/// A match of an [`NSRegularExpression`](struct.NSRegularExpression.html),
+/// copied out of an `NSTextCheckingResult`.
+///
+/// Ranges are in UTF-16 code units of the searched string. Use
+/// [`byte_range`](#method.byte_range) and
+/// [`group_byte_range`](#method.group_byte_range) to index into a Rust
+/// string.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    // The first range is the overall match.
+    ranges: Vec<Option<NSRange>>,
+}
+
+impl Match {
+    /// Copies the ranges of an `NSTextCheckingResult`.
+    pub(super) unsafe fn from_result(result: &NSObject) -> Self {
+        let len: NSUInteger = _msg_send_any![result, numberOfRanges];
+        let ranges = (0..len)
+            .map(|index| {
+                let range: NSRange = _msg_send_any![result, rangeAtIndex: index];
+                if range.is_not_found() {
+                    None
+                } else {
+                    Some(range)
+                }
+            })
+            .collect();
+        Self { ranges }
+    }
+
+    /// Returns the range of the overall match.
+    #[inline]
+    pub fn range(&self) -> NSRange {
+        self.ranges[0].unwrap_or_else(NSRange::not_found)
+    }
+
+    /// Returns the number of capture groups, not including the overall
+    /// match.
+    #[inline]
+    pub fn group_count(&self) -> usize {
+        self.ranges.len() - 1
+    }
+
+    /// Returns the range of capture group `index`, or `None` if the group did
+    /// not participate in the match.
+    ///
+    /// Group 0 is the overall match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than [`group_count`](#method.group_count).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstextcheckingresult/1416732-rangeatindex).
+    #[inline]
+    #[doc(alias = "rangeAtIndex")]
+    #[doc(alias = "rangeAtIndex:")]
+    pub fn group(&self, index: usize) -> Option<NSRange> {
+        self.ranges[index]
+    }
+
+    /// Returns the byte range of the overall match in `string`, which must
+    /// be the searched string.
+    #[inline]
+    pub fn byte_range(&self, string: &NSString) -> Option<Range<usize>> {
+        string.byte_range_for(self.range())
+    }
+
+    /// Returns the byte range of capture group `index` in `string`, which
+    /// must be the searched string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than [`group_count`](#method.group_count).
+    #[inline]
+    pub fn group_byte_range(&self, index: usize, string: &NSString) -> Option<Range<usize>> {
+        string.byte_range_for(self.group(index)?)
+    }
+}
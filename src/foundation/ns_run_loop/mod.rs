@@ -0,0 +1,99 @@
+use super::{NSDate, NSTimer};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, BOOL};
+
+mod mode;
+
+pub use mode::*;
+
+objc_subclass! {
+    /// The loop that processes input sources and timers for a thread.
+    ///
+    /// Each thread has its own run loop, which should only be run from that
+    /// thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop).
+    #[derive(PartialEq)]
+    pub class NSRunLoop: NSObject<'static>;
+}
+
+impl NSRunLoop {
+    /// Returns the run loop for the calling thread, creating it if needed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1412291-currentrunloop).
+    #[inline]
+    #[doc(alias = "currentRunLoop")]
+    pub fn current() -> Arc<Self> {
+        let run_loop =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), currentRunLoop]) };
+        run_loop.expect("No current run loop")
+    }
+
+    /// Returns the run loop for the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1418388-mainrunloop).
+    #[inline]
+    #[doc(alias = "mainRunLoop")]
+    pub fn main() -> Arc<Self> {
+        let run_loop =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), mainRunLoop]) };
+        run_loop.expect("No main run loop")
+    }
+
+    /// Returns the mode that `self` is running in, or `None` if it is not
+    /// running.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1418322-currentmode).
+    #[inline]
+    #[doc(alias = "currentMode")]
+    pub fn current_mode(&self) -> Option<Arc<NSRunLoopMode>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, currentMode]) }
+    }
+
+    /// Registers `timer` to fire while `self` runs in `mode`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1415604-addtimer).
+    #[inline]
+    #[doc(alias = "addTimer")]
+    #[doc(alias = "addTimer:forMode:")]
+    pub fn add_timer(&self, timer: &NSTimer, mode: &NSRunLoopMode) {
+        unsafe { _msg_send_any![self, addTimer: timer forMode: mode => ()] }
+    }
+}
+
+/// Running the loop.
+impl NSRunLoop {
+    /// Runs `self` in the default mode until it has no input sources or
+    /// timers, which may be never.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1412430-run).
+    #[inline]
+    pub fn run(&self) {
+        unsafe { _msg_send_any![self, run => ()] }
+    }
+
+    /// Runs `self` in the default mode until `date`, or until it has no input
+    /// sources or timers.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1408134-rununtildate).
+    #[inline]
+    #[doc(alias = "runUntilDate")]
+    #[doc(alias = "runUntilDate:")]
+    pub fn run_until(&self, date: &NSDate) {
+        unsafe { _msg_send_any![self, runUntilDate: date => ()] }
+    }
+
+    /// Runs `self` once in `mode`, until an input source is processed or
+    /// `date` is reached.
+    ///
+    /// Returns `false` without running if `mode` has no input sources or
+    /// timers. Timers firing do not count as processing an input source.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloop/1411525-runmode).
+    #[inline]
+    #[doc(alias = "runMode")]
+    #[doc(alias = "runMode:beforeDate:")]
+    pub fn run_mode_before(&self, mode: &NSRunLoopMode, date: &NSDate) -> bool {
+        unsafe { _msg_send_any![self, runMode: mode beforeDate: date => BOOL] }.into()
+    }
+}
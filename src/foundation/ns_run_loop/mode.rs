@@ -0,0 +1,45 @@
+ns_string_wrapper! {
+    /// The mode of an [`NSRunLoop`](struct.NSRunLoop.html), which determines
+    /// the input sources and timers that it monitors.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloopmode).
+    #[derive(Ord, PartialOrd, Eq, PartialEq)]
+    pub wrapper NSRunLoopMode;
+}
+
+macro_rules! mode {
+    (
+        $(#[$docs:meta])+
+        $fn:ident $value:literal
+    ) => {
+        $(#[$docs])+
+        #[inline]
+        #[doc(alias = $value)]
+        pub fn $fn() -> &'static NSRunLoopMode {
+            extern "C" {
+                #[link_name = $value]
+                static VALUE: &'static NSRunLoopMode;
+            }
+            unsafe { VALUE }
+        }
+    };
+}
+
+// `default` returns a reference, unlike `Default::default`.
+#[allow(clippy::should_implement_trait)]
+impl NSRunLoopMode {
+    mode! {
+        /// The mode for handling input sources other than connections.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsdefaultrunloopmode).
+        default "NSDefaultRunLoopMode"
+    }
+
+    mode! {
+        /// A pseudo-mode that registers objects with all of the "common"
+        /// modes, which include the default mode.
+        ///
+        /// See [documentation](https://developer.apple.com/documentation/foundation/nsrunloopcommonmodes).
+        common "NSRunLoopCommonModes"
+    }
+}
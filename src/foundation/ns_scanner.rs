@@ -0,0 +1,286 @@
+use super::{NSCharacterSet, NSString};
+use crate::core::Arc;
+use crate::objc::{autoreleasepool, ClassType, NSObject, NSUInteger, Sel, BOOL};
+use std::ptr;
+
+objc_subclass! {
+    /// A string parser that scans for substrings or characters in a
+    /// character set, and for numeric values.
+    ///
+    /// Scanning methods advance [`location`](#method.location) past the
+    /// scanned value, after first skipping over
+    /// [characters to be skipped](#method.set_charset_to_skip) (whitespace
+    /// and newlines by default). They leave it unchanged if nothing matches.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner).
+    pub class NSScanner: NSObject<'static>;
+}
+
+impl NSScanner {
+    /// Creates a scanner over a copy of `string`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1411541-initwithstring).
+    #[doc(alias = "scannerWithString")]
+    #[doc(alias = "scannerWithString:")]
+    #[doc(alias = "initWithString:")]
+    pub fn from_string(string: &NSString) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(obj: Arc<NSScanner>, sel: Sel, string: &NSString) -> Arc<NSScanner>;
+        }
+
+        let sel = selector!(initWithString:);
+        unsafe { objc_msgSend(value, sel, string) }
+    }
+
+    /// Returns the string being scanned.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1410687-string).
+    #[inline]
+    pub fn string(&self) -> Arc<NSString<'static>> {
+        autoreleasepool(|_| unsafe {
+            let string: *const NSString = _msg_send_any![self, string];
+            Arc::retain(&*string)
+        })
+    }
+
+    /// Returns the index of the next character to scan.
+    ///
+    /// This is measured in UTF-16 code units, not bytes or `char`s. Use
+    /// [`NSString::byte_range_for`](struct.NSString.html#method.byte_range_for)
+    /// to convert it to an index into a Rust string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1411242-scanlocation).
+    #[inline]
+    #[doc(alias = "scanLocation")]
+    pub fn location(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, scanLocation] }
+    }
+
+    /// Sets the index of the next character to scan, in UTF-16 code units.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `location` is greater than the UTF-16 length of the string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1411242-scanlocation).
+    #[inline]
+    #[doc(alias = "setScanLocation")]
+    #[doc(alias = "setScanLocation:")]
+    pub fn set_location(&mut self, location: NSUInteger) {
+        let len = self.string().length();
+        assert!(
+            location <= len,
+            "Index {} is out of bounds for length {}",
+            location,
+            len
+        );
+        unsafe { _msg_send_any![self, setScanLocation: location] }
+    }
+
+    /// Returns `true` if only characters to be skipped remain.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1408516-atend).
+    #[inline]
+    #[doc(alias = "isAtEnd")]
+    pub fn is_at_end(&self) -> bool {
+        unsafe { _msg_send_any![self, isAtEnd => BOOL] }.into()
+    }
+
+    /// Sets the characters ignored before each scanned value, or `None` to
+    /// skip nothing.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1418177-characterstobeskipped).
+    #[inline]
+    #[doc(alias = "setCharactersToBeSkipped")]
+    #[doc(alias = "setCharactersToBeSkipped:")]
+    pub fn set_charset_to_skip(&mut self, set: Option<&NSCharacterSet>) {
+        unsafe { _msg_send_any![self, setCharactersToBeSkipped: set] }
+    }
+}
+
+/// Scanning.
+impl NSScanner {
+    /// Scans a decimal integer, or returns `None` if there is none.
+    ///
+    /// Values that overflow are clamped to `i64::MIN` or `i64::MAX`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1411780-scanlonglong).
+    #[inline]
+    #[doc(alias = "scanLongLong")]
+    #[doc(alias = "scanLongLong:")]
+    pub fn scan_int(&mut self) -> Option<i64> {
+        let mut value: i64 = 0;
+        let ptr: *mut i64 = &mut value;
+        let found: bool = unsafe { _msg_send_any![self, scanLongLong: ptr => BOOL] }.into();
+        if found {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Scans a floating-point number, or returns `None` if there is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1408859-scandouble).
+    #[inline]
+    #[doc(alias = "scanDouble")]
+    #[doc(alias = "scanDouble:")]
+    pub fn scan_double(&mut self) -> Option<f64> {
+        let mut value: f64 = 0.0;
+        let ptr: *mut f64 = &mut value;
+        let found: bool = unsafe { _msg_send_any![self, scanDouble: ptr => BOOL] }.into();
+        if found {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Scans `string`, returning `true` if it is next.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1415310-scanstring).
+    #[inline]
+    #[doc(alias = "scanString")]
+    #[doc(alias = "scanString:intoString:")]
+    pub fn scan_string(&mut self, string: &str) -> bool {
+        let string = NSString::from_str(string);
+        let into: *mut *const NSString = ptr::null_mut();
+        unsafe { _msg_send_any![self, scanString: &*string intoString: into => BOOL] }.into()
+    }
+
+    /// Scans characters up to the first character in `set`, or returns `None`
+    /// if the first character is in `set`.
+    ///
+    /// If no character in `set` is found, this scans to the end of the
+    /// string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1411612-scanuptocharactersfromset).
+    #[inline]
+    #[doc(alias = "scanUpToCharactersFromSet")]
+    #[doc(alias = "scanUpToCharactersFromSet:intoString:")]
+    pub fn scan_up_to_characters_from(
+        &mut self,
+        set: &NSCharacterSet,
+    ) -> Option<Arc<NSString<'static>>> {
+        autoreleasepool(|_| unsafe {
+            let mut string: *const NSString = ptr::null();
+            let into: *mut *const NSString = &mut string;
+            let found: bool =
+                _msg_send_any![self, scanUpToCharactersFromSet: set intoString: into => BOOL]
+                    .into();
+            if found {
+                string.as_ref().map(Arc::retain)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Scans the longest run of characters in `set`, or returns `None` if the
+    /// first character is not in `set`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsscanner/1415214-scancharactersfromset).
+    #[inline]
+    #[doc(alias = "scanCharactersFromSet")]
+    #[doc(alias = "scanCharactersFromSet:intoString:")]
+    pub fn scan_characters_from(&mut self, set: &NSCharacterSet) -> Option<Arc<NSString<'static>>> {
+        autoreleasepool(|_| unsafe {
+            let mut string: *const NSString = ptr::null();
+            let into: *mut *const NSString = &mut string;
+            let found: bool =
+                _msg_send_any![self, scanCharactersFromSet: set intoString: into => BOOL].into();
+            if found {
+                string.as_ref().map(Arc::retain)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_file() {
+        let string = NSString::from_str("\"greeting\" = \"héllo 😀\";\n\"count\" = 42;\n");
+        let mut scanner = NSScanner::from_string(&string);
+        let s = Arc::get_mut(&mut scanner).unwrap();
+        let quote = NSCharacterSet::from_str("\"");
+
+        let mut entries = Vec::new();
+        while !s.is_at_end() {
+            assert!(s.scan_string("\""));
+            let key = s.scan_up_to_characters_from(&quote).unwrap();
+            assert!(s.scan_string("\""));
+            assert!(s.scan_string("="));
+
+            let value = if s.scan_string("\"") {
+                let value = s.scan_up_to_characters_from(&quote).unwrap().to_string();
+                assert!(s.scan_string("\""));
+                value
+            } else {
+                s.scan_int().unwrap().to_string()
+            };
+            assert!(s.scan_string(";"));
+
+            entries.push((key.to_string(), value));
+        }
+
+        assert_eq!(
+            entries,
+            [
+                ("greeting".to_owned(), "héllo 😀".to_owned()),
+                ("count".to_owned(), "42".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn utf16_location() {
+        let string = NSString::from_str("é😀 1.5");
+        let mut scanner = NSScanner::from_string(&string);
+        let s = Arc::get_mut(&mut scanner).unwrap();
+
+        // "é" is 1 UTF-16 unit (2 bytes) and "😀" is 2 units (4 bytes).
+        let letters = s.scan_up_to_characters_from(&NSCharacterSet::whitespace());
+        assert_eq!(*letters.unwrap(), *"é😀");
+        assert_eq!(s.location(), 3);
+        assert_eq!(string.byte_range_for((0..s.location()).into()), Some(0..6));
+
+        assert_eq!(s.scan_double(), Some(1.5));
+        assert!(s.is_at_end());
+
+        s.set_location(1);
+        assert_eq!(s.scan_int(), None);
+        assert_eq!(s.location(), 1);
+        assert_eq!(
+            *s.scan_characters_from(&NSCharacterSet::symbol()).unwrap(),
+            *"😀"
+        );
+    }
+
+    #[test]
+    fn charset_to_skip() {
+        let string = NSString::from_str("  7");
+        let mut scanner = NSScanner::from_string(&string);
+        let s = Arc::get_mut(&mut scanner).unwrap();
+
+        s.set_charset_to_skip(None);
+        assert_eq!(s.scan_int(), None);
+
+        s.set_charset_to_skip(Some(&NSCharacterSet::whitespace()));
+        assert_eq!(s.scan_int(), Some(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn location_out_of_bounds() {
+        let mut scanner = NSScanner::from_string(&NSString::from_str("😀"));
+        Arc::get_mut(&mut scanner).unwrap().set_location(3);
+    }
+}
@@ -0,0 +1,661 @@
+use super::{NSArray, NSEnumerator, NSNotFound, NSRange};
+use crate::core::{Arc, ObjectType};
+use crate::objc::{
+    autoreleasepool, retain_autoreleased, ClassType, NSFastEnumeration, NSObject, NSUInteger, Sel,
+    BOOL,
+};
+use std::{fmt, marker::PhantomData, ops::Index, ptr, vec};
+
+// Shared `initWith…:count:` implementation for sets and ordered sets.
+unsafe fn init_with_objects<T, S: ObjectType>(obj: Arc<S>, values: &[&T]) -> Arc<S> {
+    #[allow(clashing_extern_declarations)]
+    extern "C-unwind" {
+        fn objc_msgSend(
+            obj: Arc<NSObject<'static>>,
+            sel: Sel,
+            objects: *const *const (),
+            count: NSUInteger,
+        ) -> Arc<NSObject<'static>>;
+    }
+
+    let sel = selector!(initWithObjects:count:);
+    let set = objc_msgSend(
+        Arc::cast_unchecked(obj),
+        sel,
+        values.as_ptr().cast(),
+        values.len(),
+    );
+    Arc::cast_unchecked(set)
+}
+
+objc_subclass! {
+    /// A static unordered collection of unique objects of type `T`.
+    ///
+    /// Uniqueness is determined by the `isEqual:` and `hash` methods of the
+    /// objects. For example, two distinct [`NSString`](struct.NSString.html)
+    /// instances with the same contents are considered the same member.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset).
+    #[derive(PartialEq)]
+    pub class NSSet<T>: NSObject<'static>;
+}
+
+downcast_target!(NSSet<NSObject<'static>>);
+
+impl<T: ObjectType + fmt::Debug + 'static> fmt::Debug for NSSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+unsafe impl<T: ObjectType + 'static> NSFastEnumeration<'static> for NSSet<T> {
+    type Item = T;
+}
+
+impl<'a, T: ObjectType + 'static> IntoIterator for &'a NSSet<T> {
+    type Item = &'a T;
+    type IntoIter = NSSetIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType + 'static> NSSet<T> {
+    /// Creates a set containing retained unique `values`.
+    ///
+    /// If several values are equal, only one of them is kept.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1410206-initwithobjects).
+    #[inline]
+    #[doc(alias = "setWithObjects")]
+    #[doc(alias = "setWithObjects:count:")]
+    #[doc(alias = "initWithObjects:count:")]
+    pub fn from_slice(values: &[&T]) -> Arc<Self> {
+        unsafe { init_with_objects(Self::class().alloc(), values) }
+    }
+
+    /// Returns the number of members in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1413957-count).
+    #[inline]
+    #[doc(alias = "count")]
+    pub fn len(&self) -> usize {
+        unsafe { _msg_send_any![self, count] }
+    }
+
+    /// Returns `true` if `self` contains no members.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the member equal to `value`, or `None` if there is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1413886-member).
+    #[inline]
+    #[doc(alias = "member")]
+    #[doc(alias = "member:")]
+    pub fn get(&self, value: &T) -> Option<&T> {
+        let value = value as *const T;
+        unsafe {
+            let member: *const T = _msg_send_any![self, member: value];
+            member.as_ref()
+        }
+    }
+
+    /// Returns `true` if `self` contains a member equal to `value`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1414555-containsobject).
+    #[inline]
+    #[doc(alias = "containsObject")]
+    #[doc(alias = "containsObject:")]
+    pub fn contains(&self, value: &T) -> bool {
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, containsObject: value => BOOL] }.into()
+    }
+
+    /// Returns one of the members of `self`, or `None` if it is empty.
+    ///
+    /// The member returned is unspecified.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1409197-anyobject).
+    #[inline]
+    #[doc(alias = "anyObject")]
+    pub fn any_object(&self) -> Option<&T> {
+        unsafe {
+            let value: *const T = _msg_send_any![self, anyObject];
+            value.as_ref()
+        }
+    }
+
+    /// Returns an iterator over the members of `self`.
+    ///
+    /// All members are retrieved at once. The order of members is
+    /// unspecified.
+    pub fn iter(&self) -> NSSetIter<'_, T> {
+        let values: Vec<*const T> = self.to_array().iter().map(|v| v as *const T).collect();
+
+        NSSetIter {
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an enumerator over the members of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/objectenumerator).
+    #[inline]
+    #[doc(alias = "objectEnumerator")]
+    pub fn object_enumerator(&self) -> Arc<NSEnumerator<T>> {
+        let enumerator = unsafe { retain_autoreleased(|| _msg_send_any![self, objectEnumerator]) };
+        enumerator.expect("Failed to create enumerator")
+    }
+
+    /// Returns a new array containing the members of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1412276-allobjects).
+    #[inline]
+    #[doc(alias = "allObjects")]
+    pub fn to_array(&self) -> Arc<NSArray<T>> {
+        autoreleasepool(|_| unsafe {
+            let array: *const NSArray<T> = _msg_send_any![self, allObjects];
+            Arc::retain(&*array)
+        })
+    }
+
+    /// Returns `self` as a set of type-erased objects.
+    #[inline]
+    pub fn as_untyped(&self) -> &NSSet<NSObject<'static>> {
+        // SAFETY: Foundation sets can only contain `NSObject` instances.
+        unsafe { &*(self as *const Self as *const NSSet<NSObject<'static>>) }
+    }
+}
+
+impl NSSet<NSObject<'static>> {
+    /// Casts `self` to a set of members of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// All members of `self` must be instances of `T`.
+    #[inline]
+    pub unsafe fn cast_unchecked<T: ObjectType + 'static>(&self) -> &NSSet<T> {
+        &*(self as *const Self as *const NSSet<T>)
+    }
+}
+
+impl<T: ObjectType + 'static> NSArray<T> {
+    /// Returns a new set containing the unique objects of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsset/1411218-initwitharray).
+    #[inline]
+    #[doc(alias = "setWithArray")]
+    #[doc(alias = "initWithArray")]
+    pub fn to_set(&self) -> Arc<NSSet<T>> {
+        let value: Arc<NSSet<T>> = unsafe { NSSet::<T>::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                array: &NSObject<'static>,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithArray:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, self)) }
+    }
+}
+
+/// An iterator over the members of an [`NSSet`] or [`NSOrderedSet`].
+///
+/// This is created by [`NSSet::iter`] and [`NSOrderedSet::iter`].
+pub struct NSSetIter<'a, T> {
+    values: vec::IntoIter<*const T>,
+    marker: PhantomData<&'a NSSet<T>>,
+}
+
+impl<'a, T: ObjectType + 'static> Iterator for NSSetIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        // SAFETY: The set retains its members for at least `'a`.
+        self.values.next().map(|value| unsafe { &*value })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T: ObjectType + 'static> ExactSizeIterator for NSSetIter<'_, T> {}
+
+objc_subclass! {
+    /// A dynamic unordered collection of unique objects of type `T`.
+    ///
+    /// Mutating methods take `&mut self`, which can be obtained from a uniquely
+    /// referenced set via [`Arc::get_mut`].
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset).
+    #[derive(PartialEq)]
+    pub class NSMutableSet<T>: NSSet<T>;
+}
+
+downcast_target!(NSMutableSet<NSObject<'static>>);
+
+impl<T: ObjectType + fmt::Debug + 'static> fmt::Debug for NSMutableSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self as &NSSet<T>).fmt(f)
+    }
+}
+
+impl<T: ObjectType + 'static> NSMutableSet<T> {
+    /// Creates an empty mutable set.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Creates an empty mutable set with initial storage for `capacity`
+    /// members.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1412909-initwithcapacity).
+    #[inline]
+    #[doc(alias = "initWithCapacity")]
+    #[doc(alias = "setWithCapacity")]
+    pub fn with_capacity(capacity: usize) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSObject<'static>>,
+                sel: Sel,
+                capacity: NSUInteger,
+            ) -> Arc<NSObject<'static>>;
+        }
+
+        let sel = selector!(initWithCapacity:);
+        unsafe { Arc::cast_unchecked(objc_msgSend(Arc::cast_unchecked(value), sel, capacity)) }
+    }
+
+    /// Adds `value` to `self` if it does not already contain an equal member.
+    ///
+    /// Returns `true` if `value` was added.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1413820-addobject).
+    #[inline]
+    #[doc(alias = "addObject")]
+    #[doc(alias = "addObject:")]
+    pub fn insert(&mut self, value: &T) -> bool {
+        if self.contains(value) {
+            return false;
+        }
+
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, addObject: value] }
+        true
+    }
+
+    /// Removes the member equal to `value`, returning it if there was one.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1415062-removeobject).
+    #[inline]
+    #[doc(alias = "removeObject")]
+    #[doc(alias = "removeObject:")]
+    pub fn remove(&mut self, value: &T) -> Option<Arc<T>> {
+        let removed = Arc::retain(self.get(value)?);
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, removeObject: value] }
+        Some(removed)
+    }
+
+    /// Removes all members from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1410678-removeallobjects).
+    #[inline]
+    #[doc(alias = "removeAllObjects")]
+    pub fn clear(&mut self) {
+        unsafe { _msg_send_any![self, removeAllObjects] }
+    }
+
+    /// Adds each member of `other` that is not already in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1408272-unionset).
+    #[inline]
+    #[doc(alias = "unionSet")]
+    #[doc(alias = "unionSet:")]
+    pub fn union(&mut self, other: &NSSet<T>) {
+        unsafe { _msg_send_any![self, unionSet: other] }
+    }
+
+    /// Removes each member of `self` that is not in `other`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1413115-intersectset).
+    #[inline]
+    #[doc(alias = "intersectSet")]
+    #[doc(alias = "intersectSet:")]
+    pub fn intersect(&mut self, other: &NSSet<T>) {
+        unsafe { _msg_send_any![self, intersectSet: other] }
+    }
+
+    /// Removes each member of `other` from `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableset/1411875-minusset).
+    #[inline]
+    #[doc(alias = "minusSet")]
+    #[doc(alias = "minusSet:")]
+    pub fn minus(&mut self, other: &NSSet<T>) {
+        unsafe { _msg_send_any![self, minusSet: other] }
+    }
+}
+
+objc_subclass! {
+    /// A static ordered collection of unique objects of type `T`.
+    ///
+    /// Like [`NSSet`], uniqueness is determined by `isEqual:` and `hash`. Like
+    /// [`NSArray`], members keep the order in which they were first added.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset).
+    #[derive(PartialEq)]
+    pub class NSOrderedSet<T>: NSObject<'static>;
+}
+
+downcast_target!(NSOrderedSet<NSObject<'static>>);
+
+impl<T: ObjectType + fmt::Debug + 'static> fmt::Debug for NSOrderedSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ObjectType + 'static> Index<usize> for NSOrderedSet<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "Index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
+unsafe impl<T: ObjectType + 'static> NSFastEnumeration<'static> for NSOrderedSet<T> {
+    type Item = T;
+}
+
+impl<'a, T: ObjectType + 'static> IntoIterator for &'a NSOrderedSet<T> {
+    type Item = &'a T;
+    type IntoIter = NSSetIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: ObjectType + 'static> NSOrderedSet<T> {
+    /// Creates an ordered set containing retained unique `values`.
+    ///
+    /// If several values are equal, only the first of them is kept.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1410794-initwithobjects).
+    #[inline]
+    #[doc(alias = "orderedSetWithObjects")]
+    #[doc(alias = "orderedSetWithObjects:count:")]
+    #[doc(alias = "initWithObjects:count:")]
+    pub fn from_slice(values: &[&T]) -> Arc<Self> {
+        unsafe { init_with_objects(Self::class().alloc(), values) }
+    }
+
+    /// Returns the number of members in `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1410478-count).
+    #[inline]
+    #[doc(alias = "count")]
+    pub fn len(&self) -> usize {
+        unsafe { _msg_send_any![self, count] }
+    }
+
+    /// Returns `true` if `self` contains no members.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the member at `index`, or `None` if it is out of bounds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1414131-objectatindex).
+    #[inline]
+    #[doc(alias = "objectAtIndex")]
+    #[doc(alias = "objectAtIndex:")]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            unsafe {
+                let value: *const T = _msg_send_any![self, objectAtIndex: index];
+                Some(&*value)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first member of `self`, or `None` if it is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1412852-firstobject).
+    #[inline]
+    #[doc(alias = "firstObject")]
+    pub fn first(&self) -> Option<&T> {
+        unsafe {
+            let value: *const T = _msg_send_any![self, firstObject];
+            value.as_ref()
+        }
+    }
+
+    /// Returns the last member of `self`, or `None` if it is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1412969-lastobject).
+    #[inline]
+    #[doc(alias = "lastObject")]
+    pub fn last(&self) -> Option<&T> {
+        unsafe {
+            let value: *const T = _msg_send_any![self, lastObject];
+            value.as_ref()
+        }
+    }
+
+    /// Returns `true` if `self` contains a member equal to `value`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1407473-containsobject).
+    #[inline]
+    #[doc(alias = "containsObject")]
+    #[doc(alias = "containsObject:")]
+    pub fn contains(&self, value: &T) -> bool {
+        let value = value as *const T;
+        unsafe { _msg_send_any![self, containsObject: value => BOOL] }.into()
+    }
+
+    /// Returns the index of the member equal to `value`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1417454-indexofobject).
+    #[inline]
+    #[doc(alias = "indexOfObject")]
+    #[doc(alias = "indexOfObject:")]
+    pub fn index_of(&self, value: &T) -> Option<usize> {
+        let value = value as *const T;
+        let index: NSUInteger = unsafe { _msg_send_any![self, indexOfObject: value] };
+        if index == NSNotFound as NSUInteger {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    /// Returns an iterator over the members of `self` in order.
+    ///
+    /// All members are retrieved at once using
+    /// [`getObjects:range:`](https://developer.apple.com/documentation/foundation/nsorderedset/1410989-getobjects).
+    #[doc(alias = "getObjects")]
+    #[doc(alias = "getObjects:range:")]
+    pub fn iter(&self) -> NSSetIter<'_, T> {
+        let len = self.len();
+        let mut values: Vec<*const T> = vec![ptr::null(); len];
+
+        if len != 0 {
+            let (buf, range) = (values.as_mut_ptr(), NSRange::new(0, len));
+            unsafe { _msg_send_any![self, getObjects: buf range: range => ()] };
+        }
+
+        NSSetIter {
+            values: values.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a new array containing the members of `self` in order.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1415608-array).
+    #[inline]
+    #[doc(alias = "array")]
+    pub fn to_array(&self) -> Arc<NSArray<T>> {
+        // `-array` returns a proxy, so a copy is made instead.
+        let values: Vec<&T> = self.iter().collect();
+        NSArray::from_slice(&values)
+    }
+
+    /// Returns a new set containing the members of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsorderedset/1412311-set).
+    #[inline]
+    #[doc(alias = "set")]
+    pub fn to_set(&self) -> Arc<NSSet<T>> {
+        let values: Vec<&T> = self.iter().collect();
+        NSSet::from_slice(&values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSString;
+
+    // Long enough to not be tagged pointers, so equal strings are distinct
+    // objects.
+    const LONG: &str = "a string that is long enough to not be tagged";
+
+    #[test]
+    fn dedup_equal_strings() {
+        let a = NSString::from_str(LONG);
+        let b = NSString::from_str(LONG);
+        let c = NSString::from_str("c");
+        assert!(!ptr::eq(&*a, &*b));
+
+        let set = NSSet::from_slice(&[&*a, &*b, &*c]);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&b));
+        assert!(set.contains(&NSString::from_str("c")));
+        assert!(!set.contains(&NSString::from_str("d")));
+        assert!(set.any_object().is_some());
+        assert_eq!(set.iter().count(), 2);
+
+        let array = NSArray::from_slice(&[&*a, &*b, &*c, &*c]);
+        assert_eq!(array.to_set().len(), 2);
+        assert_eq!(set.to_array().len(), 2);
+    }
+
+    #[test]
+    fn iter_fast() {
+        let strings: Vec<Arc<NSString>> = (0..100)
+            .map(|i| NSString::from_str(&i.to_string()))
+            .collect();
+        let refs: Vec<&NSString> = strings.iter().map(|s| &**s).collect();
+
+        let set = NSSet::from_slice(&refs);
+        assert_eq!(set.iter_fast().count(), 100);
+        assert!(set.iter_fast().all(|value| set.contains(value)));
+
+        // Ordered sets enumerate in order.
+        let ordered = NSOrderedSet::from_slice(&refs);
+        assert!(ordered.iter_fast().zip(&refs).all(|(a, &b)| ptr::eq(a, b)));
+    }
+
+    #[test]
+    fn empty() {
+        let set = NSSet::<NSString>::from_slice(&[]);
+        assert!(set.is_empty());
+        assert!(set.any_object().is_none());
+        assert_eq!(set.iter().count(), 0);
+    }
+
+    #[test]
+    fn mutate() {
+        let (a, b, c) = (
+            NSString::from_str("a"),
+            NSString::from_str("b"),
+            NSString::from_str("c"),
+        );
+
+        let mut set = NSMutableSet::<NSString>::new();
+        let s = Arc::get_mut(&mut set).unwrap();
+
+        assert!(s.insert(&a));
+        assert!(s.insert(&b));
+        assert!(!s.insert(&NSString::from_str("a")));
+        assert_eq!(s.len(), 2);
+
+        s.union(&NSSet::from_slice(&[&*b, &*c]));
+        assert_eq!(s.len(), 3);
+
+        s.intersect(&NSSet::from_slice(&[&*a, &*c]));
+        assert_eq!(s.len(), 2);
+        assert!(!s.contains(&b));
+
+        s.minus(&NSSet::from_slice(&[&*a]));
+        assert_eq!(s.len(), 1);
+        assert!(s.contains(&c));
+
+        assert_eq!(*s.remove(&c).unwrap(), *"c");
+        assert!(s.remove(&c).is_none());
+
+        s.insert(&a);
+        s.clear();
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn ordered_insertion_order() {
+        let strings: Vec<Arc<NSString>> = ["z", "a", "m", "a", "z", "b"]
+            .iter()
+            .map(|s| NSString::from_str(s))
+            .collect();
+        let refs: Vec<&NSString> = strings.iter().map(|s| &**s).collect();
+
+        let set = NSOrderedSet::from_slice(&refs);
+        let order: Vec<String> = set.iter().map(|s| s.to_string()).collect();
+        assert_eq!(order, ["z", "a", "m", "b"]);
+
+        assert_eq!(set.len(), 4);
+        assert_eq!(set[2], *"m");
+        assert!(set.get(4).is_none());
+        assert_eq!(*set.first().unwrap(), *"z");
+        assert_eq!(*set.last().unwrap(), *"b");
+        assert_eq!(set.index_of(&NSString::from_str("b")), Some(3));
+        assert_eq!(set.index_of(&NSString::from_str("q")), None);
+        assert!(set.contains(&NSString::from_str("a")));
+
+        assert_eq!(set.to_array().len(), 4);
+        assert_eq!(set.to_set().len(), 4);
+    }
+}
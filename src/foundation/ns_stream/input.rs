@@ -0,0 +1,115 @@
+use super::NSStream;
+use crate::core::Arc;
+use crate::foundation::{NSData, NSError, NSURL};
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, BOOL};
+use std::{io, path::Path};
+
+objc_subclass! {
+    /// A stream that bytes can be read from.
+    ///
+    /// Both `NSInputStream` and `&NSInputStream` implement
+    /// [`Read`](https://doc.rust-lang.org/std/io/trait.Read.html), which
+    /// blocks until bytes are available by polling. The inherent
+    /// [`read`](#method.read) does not poll, and reports errors as
+    /// `NSError`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream).
+    #[derive(PartialEq)]
+    pub class NSInputStream: NSStream;
+}
+
+/// Creating streams.
+impl NSInputStream {
+    /// Creates a stream that reads the bytes of `data`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream/initwithdata).
+    #[inline]
+    #[doc(alias = "inputStreamWithData")]
+    #[doc(alias = "inputStreamWithData:")]
+    #[doc(alias = "initWithData:")]
+    pub fn with_data(data: &NSData) -> Arc<Self> {
+        let stream = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), inputStreamWithData: data])
+        };
+        stream.expect("Could not create NSInputStream")
+    }
+
+    /// Creates a stream that reads the file at `path`.
+    ///
+    /// Failing to open the file is reported by [`open`](struct.NSStream.html#method.open).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream/initwithurl).
+    #[inline]
+    #[doc(alias = "inputStreamWithURL")]
+    #[doc(alias = "inputStreamWithURL:")]
+    #[doc(alias = "inputStreamWithFileAtPath:")]
+    pub fn with_file(path: &Path) -> Arc<Self> {
+        let url = NSURL::file_url(path, false);
+        Self::with_url(&url).expect("Could not create NSInputStream")
+    }
+
+    /// Creates a stream that reads from `url`, or returns `None` if `url` is
+    /// not supported.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream/initwithurl).
+    #[inline]
+    #[doc(alias = "inputStreamWithURL")]
+    #[doc(alias = "inputStreamWithURL:")]
+    pub fn with_url(url: &NSURL) -> Option<Arc<Self>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), inputStreamWithURL: url]) }
+    }
+}
+
+/// Reading bytes.
+impl NSInputStream {
+    /// Returns `true` if bytes can be read from `self` without blocking, or if
+    /// that can only be known by reading.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream/hasbytesavailable).
+    #[inline]
+    #[doc(alias = "hasBytesAvailable")]
+    pub fn has_bytes_available(&self) -> bool {
+        unsafe { _msg_send_any![self, hasBytesAvailable => BOOL] }.into()
+    }
+
+    /// Reads bytes into `buf`, returning how many were read, or 0 at the end
+    /// of `self`.
+    ///
+    /// This may block if no bytes are available.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsinputstream/read(_:maxlength:)).
+    #[doc(alias = "read:maxLength:")]
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize, Arc<NSError<'static>>> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let ptr = buf.as_mut_ptr();
+        let len = buf.len();
+
+        // Returns 0 at the end of the stream and -1 on error.
+        let read: NSInteger = unsafe { _msg_send_any![self, read: ptr maxLength: len] };
+        if read < 0 {
+            Err(self.failure())
+        } else {
+            Ok(read as usize)
+        }
+    }
+}
+
+impl io::Read for &NSInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let stream = *self;
+        stream.poll_io(|| stream.has_bytes_available(), || stream.read(buf))
+    }
+}
+
+impl io::Read for NSInputStream {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::Read::read(&mut &*self, buf)
+    }
+}
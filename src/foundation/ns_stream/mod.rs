@@ -0,0 +1,331 @@
+use super::{CocoaErrorCode, NSError, NSErrorDomain};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger};
+use std::{io, thread, time::Duration};
+
+mod input;
+mod output;
+
+pub use input::NSInputStream;
+pub use output::NSOutputStream;
+
+objc_subclass! {
+    /// A stream of bytes, which is either an
+    /// [`NSInputStream`](struct.NSInputStream.html) or an
+    /// [`NSOutputStream`](struct.NSOutputStream.html).
+    ///
+    /// A stream must be opened with [`open`](#method.open) before it can be
+    /// read from or written to. Methods take `&self`, but a stream should
+    /// only be used by one thread at a time.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream).
+    #[derive(PartialEq)]
+    pub class NSStream: NSObject<'static>;
+}
+
+/// The state of an [`NSStream`](struct.NSStream.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/status).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSStreamStatus {
+    /// The stream has not been opened.
+    NotOpen = 0,
+    /// The stream is being opened.
+    Opening = 1,
+    /// The stream is open.
+    Open = 2,
+    /// The stream is being read from.
+    Reading = 3,
+    /// The stream is being written to.
+    Writing = 4,
+    /// There are no more bytes to read, or no more bytes can be written.
+    AtEnd = 5,
+    /// The stream has been closed.
+    Closed = 6,
+    /// An error occurred on the stream.
+    Error = 7,
+}
+
+impl Default for NSStreamStatus {
+    #[inline]
+    fn default() -> Self {
+        Self::NotOpen
+    }
+}
+
+impl NSStreamStatus {
+    /// Returns the status for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSUInteger) -> Option<Self> {
+        match value {
+            0 => Some(Self::NotOpen),
+            1 => Some(Self::Opening),
+            2 => Some(Self::Open),
+            3 => Some(Self::Reading),
+            4 => Some(Self::Writing),
+            5 => Some(Self::AtEnd),
+            6 => Some(Self::Closed),
+            7 => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// How long `io` adapters sleep between checks of a stream that is not ready.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+impl NSStream {
+    /// Creates a pair of streams where bytes written to the output stream
+    /// can be read from the input stream.
+    ///
+    /// At most `buffer_size` bytes are buffered between the two streams.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/getboundstreamswithbuffersize).
+    #[doc(alias = "getBoundStreamsWithBufferSize")]
+    #[doc(alias = "getBoundStreamsWithBufferSize:inputStream:outputStream:")]
+    pub fn bound_pair(buffer_size: usize) -> (Arc<NSInputStream>, Arc<NSOutputStream>) {
+        let mut input: *const NSInputStream = std::ptr::null();
+        let mut output: *const NSOutputStream = std::ptr::null();
+        let input_ptr: *mut *const NSInputStream = &mut input;
+        let output_ptr: *mut *const NSOutputStream = &mut output;
+
+        unsafe {
+            _msg_send_any![
+                Self::class(),
+                getBoundStreamsWithBufferSize: buffer_size
+                inputStream: input_ptr
+                outputStream: output_ptr
+                => ()
+            ];
+
+            // The streams are autoreleased.
+            match (input.as_ref(), output.as_ref()) {
+                (Some(input), Some(output)) => (Arc::retain(input), Arc::retain(output)),
+                _ => panic!("Could not create bound NSStream pair"),
+            }
+        }
+    }
+
+    /// Opens `self`, returning the error if it failed to open.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/open).
+    #[inline]
+    pub fn open(&self) -> Result<(), Arc<NSError<'static>>> {
+        unsafe { _msg_send_any![self, open => ()] };
+        if self.status() == NSStreamStatus::Error {
+            Err(self.failure())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Closes `self`, after which it cannot be reopened.
+    ///
+    /// Closing the output stream of a bound pair ends its input stream.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/close).
+    #[inline]
+    pub fn close(&self) {
+        unsafe { _msg_send_any![self, close => ()] }
+    }
+
+    /// Returns the current state of `self`.
+    ///
+    /// Values unknown to this crate are returned as
+    /// [`Error`](enum.NSStreamStatus.html#variant.Error).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/streamstatus).
+    #[inline]
+    #[doc(alias = "streamStatus")]
+    pub fn status(&self) -> NSStreamStatus {
+        let status: NSUInteger = unsafe { _msg_send_any![self, streamStatus] };
+        NSStreamStatus::from_raw(status).unwrap_or(NSStreamStatus::Error)
+    }
+
+    /// Returns the error that occurred on `self`, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstream/streamerror).
+    #[inline]
+    #[doc(alias = "streamError")]
+    pub fn stream_error(&self) -> Option<Arc<NSError<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, streamError]) }
+    }
+
+    /// Returns the error of a failed operation, or an unknown read or write
+    /// error if `self` did not report one.
+    fn failure(&self) -> Arc<NSError<'static>> {
+        self.stream_error().unwrap_or_else(|| {
+            let code = if self.is_kind_of_class(NSOutputStream::class()) {
+                CocoaErrorCode::FileWriteUnknown
+            } else {
+                CocoaErrorCode::FileReadUnknown
+            };
+            NSError::new(NSErrorDomain::cocoa(), code.code(), None)
+        })
+    }
+
+    /// Calls `f` once `self` is ready, or returns `Ok(0)` at its end.
+    ///
+    /// This sleeps between checks of `is_ready` because streams that are not
+    /// scheduled in a run loop do not notify of events.
+    fn poll_io<F>(&self, is_ready: impl Fn() -> bool, f: F) -> io::Result<usize>
+    where
+        F: FnOnce() -> Result<usize, Arc<NSError<'static>>>,
+    {
+        loop {
+            match self.status() {
+                NSStreamStatus::NotOpen | NSStreamStatus::Closed => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotConnected,
+                        "stream is not open",
+                    ));
+                }
+                NSStreamStatus::AtEnd => return Ok(0),
+                NSStreamStatus::Error => {
+                    return Err(self.failure().into());
+                }
+                NSStreamStatus::Opening => {}
+                NSStreamStatus::Open | NSStreamStatus::Reading | NSStreamStatus::Writing => {
+                    if is_ready() {
+                        return f().map_err(io::Error::from);
+                    }
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_priv::temp_dir::TempDir;
+    use crate::foundation::{NSData, NSErrorDomain, NSURL};
+    use std::{
+        fs,
+        io::{Read, Write},
+    };
+
+    #[test]
+    fn bound_pair() {
+        let data: Vec<u8> = (0..4_000_000u32).map(|i| (i % 251) as u8).collect();
+
+        // A small buffer forces partial writes and reads.
+        let (input, output) = NSStream::bound_pair(4096);
+        assert_eq!(input.status(), NSStreamStatus::NotOpen);
+        input.open().unwrap();
+        output.open().unwrap();
+        assert_eq!(input.status(), NSStreamStatus::Open);
+
+        let handle = thread::spawn({
+            let data = data.clone();
+            move || {
+                for chunk in data.chunks(10_000) {
+                    (&*output).write_all(chunk).unwrap();
+                }
+                output.close();
+            }
+        });
+
+        let mut received = Vec::new();
+        let mut chunk = [0; 3000];
+        loop {
+            match Read::read(&mut &*input, &mut chunk).unwrap() {
+                0 => break,
+                read => received.extend_from_slice(&chunk[..read]),
+            }
+        }
+        handle.join().unwrap();
+
+        assert_eq!(received.len(), data.len());
+        assert!(received == data);
+        assert_eq!(input.status(), NSStreamStatus::AtEnd);
+        input.close();
+        assert_eq!(input.status(), NSStreamStatus::Closed);
+    }
+
+    #[test]
+    fn data() {
+        let data = NSData::from_slice(b"hello, world");
+        let mut input = NSInputStream::with_data(&data);
+        let input = Arc::get_mut(&mut input).unwrap();
+
+        // Unopened streams cannot be read.
+        let error = input.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotConnected);
+
+        input.open().unwrap();
+        assert!(input.has_bytes_available());
+
+        let mut contents = String::new();
+        input.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello, world");
+        assert_eq!(input.status(), NSStreamStatus::AtEnd);
+        assert_eq!(NSInputStream::read(input, &mut [0; 8]).unwrap(), 0);
+        assert!(input.stream_error().is_none());
+    }
+
+    #[test]
+    fn memory() {
+        let mut output = NSOutputStream::to_memory();
+        let output = Arc::get_mut(&mut output).unwrap();
+        output.open().unwrap();
+        assert!(output.has_space_available());
+
+        output.write_all(b"hello, ").unwrap();
+        output.write_all(b"world").unwrap();
+
+        let written = output.data_written_to_memory().unwrap();
+        assert_eq!(written.as_slice(), b"hello, world");
+        output.close();
+    }
+
+    #[test]
+    fn file_append() {
+        let dir = TempDir::new("ns-stream-file-append");
+        let path = dir.0.join("file");
+        fs::write(&path, b"stale").unwrap();
+
+        // Without appending, the file is truncated when opened.
+        let output = NSOutputStream::with_file(&path, false);
+        output.open().unwrap();
+        (&*output).write_all(b"hello").unwrap();
+        output.close();
+        assert_eq!(fs::read(&path).unwrap(), b"hello");
+
+        let url = NSURL::file_url(&path, false);
+        let output = NSOutputStream::with_url(&url, true).unwrap();
+        output.open().unwrap();
+        (&*output).write_all(b", world").unwrap();
+        output.close();
+
+        let input = NSInputStream::with_url(&url).unwrap();
+        input.open().unwrap();
+        let mut contents = String::new();
+        (&*input).read_to_string(&mut contents).unwrap();
+        input.close();
+        assert_eq!(contents, "hello, world");
+    }
+
+    #[test]
+    fn missing_directory() {
+        let dir = TempDir::new("ns-stream-missing-directory");
+        let path = dir.0.join("missing").join("file");
+
+        let input = NSInputStream::with_file(&path);
+        let error = input.open().unwrap_err();
+        assert_eq!(input.status(), NSStreamStatus::Error);
+        assert_eq!(*error.domain(), *NSErrorDomain::posix());
+        assert_eq!(input.stream_error().unwrap().code(), error.code());
+
+        let error = Read::read(&mut &*input, &mut [0; 8]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+
+        // Output streams do not create missing directories.
+        let output = NSOutputStream::with_file(&path, false);
+        assert!(output.open().is_err());
+        assert_eq!(output.status(), NSStreamStatus::Error);
+        assert!(!dir.0.join("missing").exists());
+    }
+}
@@ -0,0 +1,144 @@
+use super::NSStream;
+use crate::core::Arc;
+use crate::foundation::{NSData, NSError, NSString, NSURL};
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, BOOL};
+use std::{io, path::Path};
+
+objc_subclass! {
+    /// A stream that bytes can be written to.
+    ///
+    /// Both `NSOutputStream` and `&NSOutputStream` implement
+    /// [`Write`](https://doc.rust-lang.org/std/io/trait.Write.html), which
+    /// blocks until bytes can be written by polling. The inherent
+    /// [`write`](#method.write) does not poll, and reports errors as
+    /// `NSError`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream).
+    #[derive(PartialEq)]
+    pub class NSOutputStream: NSStream;
+}
+
+/// Creating streams.
+impl NSOutputStream {
+    /// Creates a stream that writes to memory, which can be read with
+    /// [`data_written_to_memory`](#method.data_written_to_memory).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream/outputstreamtomemory).
+    #[inline]
+    #[doc(alias = "outputStreamToMemory")]
+    pub fn to_memory() -> Arc<Self> {
+        let stream =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), outputStreamToMemory]) };
+        stream.expect("Could not create NSOutputStream")
+    }
+
+    /// Creates a stream that writes to the file at `path`, either after its
+    /// contents if `append` is `true` or replacing them.
+    ///
+    /// Failing to open the file is reported by [`open`](struct.NSStream.html#method.open).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream/initwithurl).
+    #[inline]
+    #[doc(alias = "outputStreamWithURL")]
+    #[doc(alias = "outputStreamWithURL:append:")]
+    #[doc(alias = "outputStreamToFileAtPath:append:")]
+    pub fn with_file(path: &Path, append: bool) -> Arc<Self> {
+        let url = NSURL::file_url(path, false);
+        Self::with_url(&url, append).expect("Could not create NSOutputStream")
+    }
+
+    /// Creates a stream that writes to `url`, or returns `None` if `url` is
+    /// not supported.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream/initwithurl).
+    #[inline]
+    #[doc(alias = "outputStreamWithURL")]
+    #[doc(alias = "outputStreamWithURL:append:")]
+    pub fn with_url(url: &NSURL, append: bool) -> Option<Arc<Self>> {
+        let append = BOOL::from(append);
+        unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), outputStreamWithURL: url append: append],
+            )
+        }
+    }
+}
+
+/// Writing bytes.
+impl NSOutputStream {
+    /// Returns `true` if bytes can be written to `self` without blocking, or
+    /// if that can only be known by writing.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream/hasspaceavailable).
+    #[inline]
+    #[doc(alias = "hasSpaceAvailable")]
+    pub fn has_space_available(&self) -> bool {
+        unsafe { _msg_send_any![self, hasSpaceAvailable => BOOL] }.into()
+    }
+
+    /// Writes bytes from `buf`, returning how many were written, or 0 if
+    /// `self` is full.
+    ///
+    /// This may block if there is no space available.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsoutputstream/write(_:maxlength:)).
+    #[doc(alias = "write:maxLength:")]
+    pub fn write(&self, buf: &[u8]) -> Result<usize, Arc<NSError<'static>>> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let ptr = buf.as_ptr();
+        let len = buf.len();
+
+        // Writes may be partial. Returns 0 if the stream is full and -1 on
+        // error.
+        let written: NSInteger = unsafe { _msg_send_any![self, write: ptr maxLength: len] };
+        if written < 0 {
+            Err(self.failure())
+        } else {
+            Ok(written as usize)
+        }
+    }
+
+    /// Returns the bytes written to a stream created with
+    /// [`to_memory`](#method.to_memory).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstreamdatawrittentomemorystreamkey).
+    #[doc(alias = "NSStreamDataWrittenToMemoryStreamKey")]
+    pub fn data_written_to_memory(&self) -> Option<Arc<NSData>> {
+        extern "C" {
+            static NSStreamDataWrittenToMemoryStreamKey: &'static NSString<'static>;
+        }
+        let key = unsafe { NSStreamDataWrittenToMemoryStreamKey };
+        unsafe { retain_autoreleased(|| _msg_send_any![self, propertyForKey: key]) }
+    }
+}
+
+impl io::Write for &NSOutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let stream = *self;
+        stream.poll_io(|| stream.has_space_available(), || stream.write(buf))
+    }
+
+    /// This does nothing since writes are not buffered.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for NSOutputStream {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::Write::write(&mut &*self, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
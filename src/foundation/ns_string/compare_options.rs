@@ -0,0 +1,76 @@
+use crate::objc::NSUInteger;
+use std::ops;
+
+/// Options for searching and comparing [`NSString`](struct.NSString.html)
+/// objects.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/compareoptions).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct NSStringCompareOptions(pub NSUInteger);
+
+impl NSStringCompareOptions {
+    /// No options; a case-sensitive, non-literal search.
+    pub const NONE: Self = Self(0);
+
+    /// A case-insensitive search.
+    pub const CASE_INSENSITIVE: Self = Self(1);
+
+    /// Exact character-by-character equivalence.
+    pub const LITERAL: Self = Self(2);
+
+    /// Search from the end of the source string.
+    pub const BACKWARDS: Self = Self(4);
+
+    /// Search is limited to the start (or end, if
+    /// [`BACKWARDS`](#associatedconstant.BACKWARDS)) of the source string.
+    pub const ANCHORED: Self = Self(8);
+
+    /// Numbers within strings are compared using numeric value.
+    pub const NUMERIC: Self = Self(64);
+
+    /// Search ignores diacritic marks.
+    pub const DIACRITIC_INSENSITIVE: Self = Self(128);
+
+    /// Search ignores width differences in characters that have full-width
+    /// and half-width forms.
+    pub const WIDTH_INSENSITIVE: Self = Self(256);
+
+    /// Comparisons are forced to return either ascending or descending if
+    /// the strings are equivalent but not strictly equal.
+    pub const FORCED_ORDERING: Self = Self(512);
+
+    /// The search string is treated as an ICU-compatible regular expression.
+    pub const REGULAR_EXPRESSION: Self = Self(1024);
+
+    /// Returns `true` if all of the flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for NSStringCompareOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for NSStringCompareOptions {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for NSStringCompareOptions {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
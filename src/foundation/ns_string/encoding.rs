@@ -1,6 +1,6 @@
 use super::NSString;
 use crate::core::Arc;
-use crate::objc::NSUInteger;
+use crate::objc::{MsgReturn, MsgReturnKind, NSUInteger};
 use std::fmt;
 
 /// Possible [`NSString`](struct.NSString.html) encodings.
@@ -10,6 +10,10 @@ use std::fmt;
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct NSStringEncoding(pub NSUInteger);
 
+unsafe impl MsgReturn for NSStringEncoding {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
 impl fmt::Debug for NSStringEncoding {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // SAFETY: The unretained string does not live long.
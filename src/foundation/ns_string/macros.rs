@@ -85,8 +85,15 @@ macro_rules! ns_string_wrapper {
 /// assert_eq!(WORLD.to_string(), "world");
 /// ```
 ///
-/// Note that the result cannot be used in a `const` because it refers to
-/// static data outside of this library.
+/// It can also be used in `const` values, which refer to the same static
+/// string:
+///
+/// ```
+/// # use fruity::foundation::NSString;
+/// const WORLD: &NSString = fruity::ns_string!("world");
+///
+/// assert_eq!(WORLD.to_string(), "world");
+/// ```
 ///
 /// # Unicode Strings
 ///
@@ -120,8 +127,8 @@ macro_rules! ns_string_wrapper {
 /// Interior null bytes are allowed and are not stripped:
 ///
 /// ```
-/// # // TODO: Add `to_string()` test when a Rust strings with nulls can be retrieved.
 /// let example = fruity::ns_string!("exa\0mple");
+/// assert_eq!(example.to_string(), "exa\0mple");
 /// ```
 ///
 /// # Runtime Cost
@@ -150,95 +157,7 @@ macro_rules! ns_string {
         // The only names directly used are expressions, whose names shadow any
         // other names outside of this macro.
 
-        // This is defined in CoreFoundation, but we don't emit a link attribute
-        // here because it is already linked via Foundation.
-        //
-        // Although this is a "private" (underscored) symbol, it is directly
-        // referenced in Objective-C binaries. So it's safe for us to reference.
-        extern "C" {
-            static __CFConstantStringClassReference: $crate::_priv::c_void;
-        }
-
-        let cfstring_ptr: *const $crate::_priv::c_void = {
-            // Remove any trailing null early.
-            const INPUT: &[u8] = $crate::_priv::cfstring::trim_trailing_nul($s);
-
-            if $crate::_priv::cfstring::is_ascii(INPUT) {
-                // The ASCII bytes with a trailing null byte.
-                #[repr(C)]
-                struct Ascii {
-                    data: [u8; INPUT.len()],
-                    nul: u8,
-                }
-
-                const ASCII: Ascii = Ascii {
-                    data: unsafe { *$crate::_priv::std::mem::transmute::<_, &_>(INPUT.as_ptr()) },
-                    nul: 0,
-                };
-
-                const ASCII_ARRAY: &[u8; INPUT.len() + 1] =
-                    unsafe { $crate::_priv::std::mem::transmute(&ASCII) };
-
-                #[link_section = "__DATA,__cfstring,regular"]
-                static CFSTRING: $crate::_priv::cfstring::CFStringAscii =
-                    $crate::_priv::cfstring::CFStringAscii::new(
-                        unsafe { &__CFConstantStringClassReference },
-                        ASCII_ARRAY.as_ptr(),
-                        // The length does not include the trailing null.
-                        INPUT.len(),
-                    );
-
-                CFSTRING.as_ptr()
-            } else {
-                // The full UTF-16 contents along with the written length.
-                const UTF16_FULL: (&[u16; INPUT.len()], usize) = {
-                    let mut out = [0u16; INPUT.len()];
-                    let mut iter = $crate::_priv::cfstring::utf16::EncodeUtf16Iter::new(INPUT);
-                    let mut written = 0;
-
-                    while let Some((state, chars)) = iter.next() {
-                        iter = state;
-                        out[written] = chars.repr[0];
-                        written += 1;
-
-                        if chars.len > 1 {
-                            out[written] = chars.repr[1];
-                            written += 1;
-                        }
-                    }
-
-                    (&{ out }, written)
-                };
-
-                // The written UTF-16 contents with a trailing null code point.
-                #[repr(C)]
-                struct Utf16 {
-                    data: [u16; UTF16_FULL.1],
-                    nul: u16,
-                }
-
-                const UTF16: Utf16 = Utf16 {
-                    data: unsafe {
-                        *$crate::_priv::std::mem::transmute::<_, &_>(UTF16_FULL.0.as_ptr())
-                    },
-                    nul: 0,
-                };
-
-                const UTF16_ARRAY: &[u16; UTF16_FULL.1 + 1] =
-                    unsafe { $crate::_priv::std::mem::transmute(&UTF16) };
-
-                #[link_section = "__DATA,__cfstring,regular"]
-                static CFSTRING: $crate::_priv::cfstring::CFStringUtf16 =
-                    $crate::_priv::cfstring::CFStringUtf16::new(
-                        unsafe { &__CFConstantStringClassReference },
-                        UTF16_ARRAY.as_ptr(),
-                        // The length does not include the trailing null.
-                        UTF16_FULL.1,
-                    );
-
-                CFSTRING.as_ptr()
-            }
-        };
+        let cfstring_ptr: *const $crate::_priv::c_void = $crate::_cfstring_ptr!($s);
 
         union Cast<T: 'static> {
             pointer: *const T,
@@ -283,4 +202,26 @@ mod tests {
             "讓每個人都能打造出。",
         }
     }
+
+    #[test]
+    fn eq_runtime() {
+        for &(constant, s) in &[
+            (ns_string!("hello"), "hello"),
+            (ns_string!("Привет"), "Привет"),
+            (ns_string!("exa\0mple"), "exa\0mple"),
+        ] {
+            let runtime = NSString::from_str(s);
+            assert_eq!(*constant, *runtime);
+            assert_eq!(constant.len(), runtime.len());
+        }
+    }
+
+    #[test]
+    fn const_context() {
+        const HELLO: &NSString = ns_string!("hello");
+        const HELLO_RU: &NSString = ns_string!("Привет");
+
+        assert_eq!(HELLO.to_string(), "hello");
+        assert_eq!(HELLO_RU.to_string(), "Привет");
+    }
 }
@@ -1,13 +1,23 @@
 use super::{NSComparisonResult, NSRange};
 use crate::core::Arc;
 use crate::objc::{Class, ClassType, NSObject, NSUInteger, Sel, BOOL};
-use std::{cmp::Ordering, ffi::CStr, fmt, os::raw::c_char, ptr, slice, str};
+use std::{
+    cmp::Ordering,
+    ffi::CStr,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Range,
+    os::raw::c_char,
+    ptr, slice, str,
+};
 
 #[macro_use]
 mod macros;
 
+mod compare_options;
 mod encoding;
 
+pub use compare_options::*;
 pub use encoding::*;
 
 /// Returns the selector with a given name.
@@ -53,6 +63,14 @@ impl PartialEq for NSString<'_> {
 
 impl Eq for NSString<'_> {}
 
+impl Hash for NSString<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // `-hash` is consistent with `isEqualToString:`.
+        state.write_usize(NSObject::hash(self));
+    }
+}
+
 impl PartialOrd for NSString<'_> {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -80,14 +98,13 @@ impl PartialEq<str> for NSString<'_> {
                     (_, _) => return false,
                 }
             }
-        } else {
-            // If the string is not UTF-16, then it is UTF-8 (or some other
-            // encoding?).
-
+        } else if let Some(this) = unsafe { self.as_str() } {
             // SAFETY: `this` is short-lived.
-            let this = unsafe { self.to_str() };
-
             this == other
+        } else {
+            // Unpaired surrogates are preserved, so they never compare equal
+            // to a `str`.
+            self.to_utf16().iter().copied().eq(other.encode_utf16())
         }
     }
 }
@@ -130,14 +147,11 @@ impl PartialOrd<str> for NSString<'_> {
                     (None, None) => return Some(Ordering::Equal),
                 }
             }
-        } else {
-            // If the string is not UTF-16, then it is UTF-8 (or some other
-            // encoding?).
-
+        } else if let Some(this) = unsafe { self.as_str() } {
             // SAFETY: `this` is short-lived.
-            let this = unsafe { self.to_str() };
-
             Some(this.cmp(other))
+        } else {
+            Some(self.to_utf16().iter().copied().cmp(other.encode_utf16()))
         }
     }
 }
@@ -188,9 +202,10 @@ impl fmt::Debug for NSString<'_> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // SAFETY: The lifetime of `str` is very short.
-        let str = unsafe { self.to_str() };
-
-        str.fmt(f)
+        match unsafe { self.as_str() } {
+            Some(str) => str.fmt(f),
+            None => self.to_string().fmt(f),
+        }
     }
 }
 
@@ -198,9 +213,10 @@ impl fmt::Display for NSString<'_> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // SAFETY: The lifetime of `str` is very short.
-        let str = unsafe { self.to_str() };
-
-        str.fmt(f)
+        match unsafe { self.as_str() } {
+            Some(str) => str.fmt(f),
+            None => self.to_string().fmt(f),
+        }
     }
 }
 
@@ -311,6 +327,16 @@ impl NSString<'_> {
     pub fn available_encodings_count() -> usize {
         Self::available_encodings_slice().len()
     }
+
+    /// Returns a slice containing all supported encodings.
+    ///
+    /// This is the same as
+    /// [`available_encodings_slice`](#method.available_encodings_slice).
+    #[inline]
+    #[doc(alias = "availableStringEncodings")]
+    pub fn available_encodings() -> &'static [NSStringEncoding] {
+        Self::available_encodings_slice()
+    }
 }
 
 impl<'data> NSString<'data> {
@@ -321,7 +347,7 @@ impl<'data> NSString<'data> {
         let value: Arc<Self> = class.alloc();
 
         #[allow(clashing_extern_declarations)]
-        extern "C" {
+        extern "C-unwind" {
             fn objc_msgSend(
                 obj: Arc<NSString>,
                 sel: Sel,
@@ -360,7 +386,7 @@ impl<'data> NSString<'data> {
         let value: Arc<Self> = unsafe { Self::class().alloc() };
 
         #[allow(clashing_extern_declarations)]
-        extern "C" {
+        extern "C-unwind" {
             fn objc_msgSend<'data>(
                 obj: Arc<NSString<'data>>,
                 sel: Sel,
@@ -435,6 +461,7 @@ impl NSString<'_> {
         #[allow(non_upper_case_globals)]
         const kCFStringEncodingUTF8: CFStringEncoding = 0x08000100;
 
+        #[allow(clashing_extern_declarations)]
         extern "C" {
             fn CFStringGetCStringPtr(s: &NSString, encoding: CFStringEncoding) -> *const c_char;
         }
@@ -462,7 +489,9 @@ impl NSString<'_> {
     /// efficiently.
     ///
     /// Unlike [`to_str`](#method.to_str.html), this does not allocate and
-    /// construct a new UTF-8 C string if `self` does not represent one.
+    /// construct a new UTF-8 C string if `self` does not represent one. This
+    /// is also `None` if `self` contains a null character, which would
+    /// otherwise end the slice early.
     ///
     /// # Safety
     ///
@@ -480,7 +509,12 @@ impl NSString<'_> {
     /// Returns the contents of `self` as a native UTF-8 string slice.
     ///
     /// This internally uses [`to_utf8_ptr`](#method.to_utf8_ptr). See its
-    /// documentation for details.
+    /// documentation for details. The slice ends at the first null character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` cannot be represented as UTF-8, such as if it
+    /// contains unpaired surrogates.
     ///
     /// # Safety
     ///
@@ -515,7 +549,14 @@ impl NSString<'_> {
             return None;
         }
 
+        // The C string ends at the first null character. This is only returned
+        // for 8-bit storage, so its length matches the number of UTF-16 code
+        // units unless it was cut short.
         let cstr = CStr::from_ptr(cstr);
+        if cstr.to_bytes().len() != self.length() {
+            return None;
+        }
+
         Some(str::from_utf8_unchecked(cstr.to_bytes_with_nul()))
     }
 
@@ -523,7 +564,12 @@ impl NSString<'_> {
     /// with a 0 byte.
     ///
     /// This internally uses [`to_utf8_ptr`](#method.to_utf8_ptr). See its
-    /// documentation for details.
+    /// documentation for details. The slice ends at the first null character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` cannot be represented as UTF-8, such as if it
+    /// contains unpaired surrogates.
     ///
     /// # Safety
     ///
@@ -531,57 +577,57 @@ impl NSString<'_> {
     /// object. Therefore, long use cases should copy the bytes of the returned
     /// string slice or use [`to_string_with_nul`](#method.to_string_with_nul).
     pub unsafe fn to_str_with_nul(&self) -> &str {
-        let cstr = CStr::from_ptr(self.to_utf8_ptr());
+        let cstr = self.to_utf8_ptr();
+        assert!(!cstr.is_null(), "String cannot be represented as UTF-8");
+
+        let cstr = CStr::from_ptr(cstr);
         str::from_utf8_unchecked(cstr.to_bytes_with_nul())
     }
 
     /// Returns the contents of `self` as a native UTF-8 string buffer.
     ///
-    /// This internally uses [`to_utf8_ptr`](#method.to_utf8_ptr). See its
-    /// documentation for details.
+    /// Unlike [`to_str`](#method.to_str), this keeps null characters and
+    /// replaces unpaired surrogates with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html).
     ///
     /// # Performance Considerations
     ///
-    /// Because of how
-    /// [`-[NSString UTF8String]`](https://developer.apple.com/documentation/foundation/nsstring/1411189-utf8string)
-    /// works, this method will likely allocate twice as much memory needed for
-    /// the length of the resulting buffer. If your use case is short-lived
-    /// enough, consider using [`to_str`](#method.to_str) to save memory and
-    /// time.
-    #[inline]
+    /// This copies directly from the internal storage of `self` if it is UTF-8
+    /// or UTF-16, and otherwise copies through an intermediate UTF-16 buffer.
     pub fn to_string(&self) -> String {
-        // This method relies on `to_string_with_nul` because that method
-        // generates a lot of code that is best to only exist once.
-
-        let mut string = self.to_string_with_nul();
-        let len = string.len() - 1;
-
-        // This approach is slightly faster than `String::pop`.
-        //
-        // SAFETY: The null character takes 1 byte in UTF-8.
-        unsafe { string.as_mut_vec().set_len(len) };
-
-        string
+        // SAFETY: The slices are copied before `self` can be mutated.
+        unsafe {
+            if let Some(s) = self.as_str() {
+                return s.to_owned();
+            }
+            if let Some(utf16) = self.as_utf16() {
+                return String::from_utf16_lossy(utf16);
+            }
+        }
+        String::from_utf16_lossy(&self.to_utf16())
     }
 
     /// Returns the contents of `self` as a native UTF-8 string buffer ending
     /// with a 0 byte.
     ///
-    /// This internally uses [`to_utf8_ptr`](#method.to_utf8_ptr). See its
-    /// documentation for details.
-    ///
-    /// # Performance Considerations
-    ///
-    /// Because of how
-    /// [`-[NSString UTF8String]`](https://developer.apple.com/documentation/foundation/nsstring/1411189-utf8string)
-    /// works, this method will likely allocate twice as much memory needed for
-    /// the length of the resulting buffer. If your use case is short-lived
-    /// enough, consider using [`to_str_with_nul`](#method.to_str_with_nul) to
-    /// save memory and time.
+    /// See [`to_string`](#method.to_string) for details.
+    #[inline]
     pub fn to_string_with_nul(&self) -> String {
-        // SAFETY: This use of the string is reasonably short-lived enough for
-        // its lifetime to be long enough.
-        unsafe { self.to_str_with_nul() }.into()
+        let mut string = self.to_string();
+        string.push('\0');
+        string
+    }
+
+    /// Returns the number of bytes needed to represent `self` as UTF-8, or 0
+    /// if it cannot be represented as UTF-8, such as if it contains unpaired
+    /// surrogates.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1410710-lengthofbytesusingencoding).
+    #[inline]
+    #[doc(alias = "lengthOfBytesUsingEncoding")]
+    #[doc(alias = "lengthOfBytesUsingEncoding:")]
+    pub fn utf8_len(&self) -> usize {
+        unsafe { _msg_send_any![self, lengthOfBytesUsingEncoding: NSStringEncoding::UTF8] }
     }
 }
 
@@ -623,6 +669,220 @@ impl NSString<'_> {
 
         Some(slice::from_raw_parts(ptr, self.length()))
     }
+
+    /// Returns a copy of the UTF-16 code units of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1408720-getcharacters).
+    #[doc(alias = "getCharacters")]
+    #[doc(alias = "getCharacters:range:")]
+    pub fn to_utf16(&self) -> Vec<u16> {
+        let len = self.length();
+        let mut buffer = Vec::<u16>::with_capacity(len);
+        unsafe {
+            let range = NSRange::new(0, len);
+            _msg_send_any![self, getCharacters: buffer.as_mut_ptr() range: range => ()];
+            buffer.set_len(len);
+        }
+        buffer
+    }
+}
+
+/// Converting between UTF-16 and UTF-8 indices.
+///
+/// Foundation APIs such as `NSRegularExpression` work with ranges of UTF-16
+/// code units, whereas Rust strings are indexed by UTF-8 bytes. These methods
+/// map between the two for the string returned by
+/// [`to_string`](#method.to_string).
+impl NSString<'_> {
+    /// Calls `f` with the UTF-16 and UTF-8 offsets of every character boundary
+    /// in `self`, including the end, until it returns `Some`.
+    fn find_boundary<T>(&self, mut f: impl FnMut(usize, usize) -> Option<T>) -> Option<T> {
+        let units = self.to_utf16();
+        let (mut utf16, mut utf8) = (0, 0);
+
+        for c in char::decode_utf16(units.iter().cloned()) {
+            if let Some(result) = f(utf16, utf8) {
+                return Some(result);
+            }
+
+            // Unpaired surrogates are a single code unit, as is their
+            // replacement character.
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            utf16 += c.len_utf16();
+            utf8 += c.len_utf8();
+        }
+
+        f(utf16, utf8)
+    }
+
+    /// Returns the range of UTF-8 bytes corresponding to a range of UTF-16
+    /// code units in `self`.
+    ///
+    /// This returns `None` if `range` is out of bounds or either end of it
+    /// splits a surrogate pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fruity::foundation::{NSRange, NSString};
+    ///
+    /// let string = NSString::from_str("a🦀b");
+    ///
+    /// // "🦀" is two UTF-16 code units and four UTF-8 bytes.
+    /// assert_eq!(string.byte_range_for(NSRange::new(1, 2)), Some(1..5));
+    /// assert_eq!(string.byte_range_for(NSRange::new(1, 1)), None);
+    /// ```
+    pub fn byte_range_for(&self, range: NSRange) -> Option<Range<usize>> {
+        let end = range.location.checked_add(range.length)?;
+        let mut start = None;
+
+        self.find_boundary(|utf16, utf8| {
+            if utf16 == range.location {
+                start = Some(utf8);
+            }
+            if utf16 >= end {
+                // `start` is `None` if the range starts within a pair.
+                let range = if utf16 == end {
+                    start.map(|start| start..utf8)
+                } else {
+                    None
+                };
+                Some(range)
+            } else {
+                None
+            }
+        })?
+    }
+
+    /// Returns the range of UTF-16 code units corresponding to a range of
+    /// UTF-8 bytes in `self`.
+    ///
+    /// This returns `None` if `range` is out of bounds, is decreasing, or
+    /// either end of it is not on a character boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fruity::foundation::{NSRange, NSString};
+    ///
+    /// let string = NSString::from_str("a🦀b");
+    ///
+    /// assert_eq!(string.utf16_range_for(1..5), Some(NSRange::new(1, 2)));
+    /// assert_eq!(string.utf16_range_for(1..3), None);
+    /// ```
+    pub fn utf16_range_for(&self, range: Range<usize>) -> Option<NSRange> {
+        if range.start > range.end {
+            return None;
+        }
+        let mut start = None;
+
+        self.find_boundary(|utf16, utf8| {
+            if utf8 == range.start {
+                start = Some(utf16);
+            }
+            if utf8 >= range.end {
+                let range = if utf8 == range.end {
+                    start.map(|start| NSRange::new(start, utf16 - start))
+                } else {
+                    None
+                };
+                Some(range)
+            } else {
+                None
+            }
+        })?
+    }
+}
+
+/// Converting to and from other [encodings](struct.NSStringEncoding.html).
+impl<'data> NSString<'data> {
+    /// Creates an immutable string object by decoding `bytes` in `encoding`.
+    ///
+    /// Returns `None` if `bytes` is not valid in `encoding`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1407339-initwithbytes).
+    #[doc(alias = "initWithBytes")]
+    #[doc(alias = "initWithBytes:length:encoding:")]
+    pub fn from_bytes(bytes: &[u8], encoding: NSStringEncoding) -> Option<Arc<Self>> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend<'data>(
+                obj: Arc<NSString<'data>>,
+                sel: Sel,
+                bytes: *const u8,
+                length: NSUInteger,
+                encoding: NSStringEncoding,
+            ) -> Option<Arc<NSString<'data>>>;
+        }
+
+        let sel = selector!(initWithBytes:length:encoding:);
+        unsafe { objc_msgSend(value, sel, bytes.as_ptr(), bytes.len(), encoding) }
+    }
+
+    /// Returns the contents of `self` encoded in `encoding`.
+    ///
+    /// If `lossy` is `true`, characters that cannot be represented are
+    /// substituted or removed. Otherwise, this returns `None` if any
+    /// character cannot be represented.
+    ///
+    /// A byte order mark is not written for UTF-16 and UTF-32 encodings with
+    /// an explicit endianness.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1413692-datausingencoding).
+    #[doc(alias = "dataUsingEncoding")]
+    #[doc(alias = "dataUsingEncoding:allowLossyConversion:")]
+    pub fn to_bytes(&self, encoding: NSStringEncoding, lossy: bool) -> Option<Vec<u8>> {
+        // The data object is autoreleased, so it must not escape the pool.
+        crate::objc::autoreleasepool(|_| unsafe {
+            let data: *const NSObject<'static> = _msg_send_any![
+                self,
+                dataUsingEncoding: encoding
+                allowLossyConversion: BOOL::from(lossy)
+            ];
+            let data = data.as_ref()?;
+
+            let length: NSUInteger = _msg_send_any![data, length];
+            if length == 0 {
+                return Some(Vec::new());
+            }
+
+            let bytes: *const u8 = _msg_send_any![data, bytes];
+            Some(slice::from_raw_parts(bytes, length).to_vec())
+        })
+    }
+
+    /// Returns `true` if `self` can be converted to `encoding` without loss of
+    /// information.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1410167-canbeconvertedtoencoding).
+    #[inline]
+    #[doc(alias = "canBeConvertedToEncoding")]
+    #[doc(alias = "canBeConvertedToEncoding:")]
+    pub fn can_be_converted_to(&self, encoding: NSStringEncoding) -> bool {
+        unsafe { _msg_send_any![self, canBeConvertedToEncoding: encoding => BOOL] }.into()
+    }
+
+    /// Returns the fastest encoding to which `self` may be converted without
+    /// loss of information.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1409567-fastestencoding).
+    #[inline]
+    #[doc(alias = "fastestEncoding")]
+    pub fn fastest_encoding(&self) -> NSStringEncoding {
+        unsafe { _msg_send_any![self, fastestEncoding] }
+    }
+
+    /// Returns the smallest encoding to which `self` can be converted without
+    /// loss of information.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsstring/1418037-smallestencoding).
+    #[inline]
+    #[doc(alias = "smallestEncoding")]
+    pub fn smallest_encoding(&self) -> NSStringEncoding {
+        unsafe { _msg_send_any![self, smallestEncoding] }
+    }
 }
 
 impl NSString<'_> {
@@ -634,6 +894,21 @@ impl NSString<'_> {
         unsafe { _msg_send_any![self, length] }
     }
 
+    /// Returns the number of UTF-16 code units in `self`.
+    ///
+    /// This is the same as [`length`](#method.length). See
+    /// [`utf8_len`](#method.utf8_len) for the number of UTF-8 bytes.
+    #[inline]
+    pub fn len(&self) -> NSUInteger {
+        self.length()
+    }
+
+    /// Returns `true` if `self` contains no characters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.length() == 0
+    }
+
     /// Returns a selector with `self` as its name.
     ///
     /// If `self` cannot be converted to UTF-8 (this should be only due to
@@ -760,6 +1035,13 @@ impl PartialEq<NSMutableString<'_>> for NSString<'_> {
 
 impl Eq for NSMutableString<'_> {}
 
+impl Hash for NSMutableString<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self as &NSString).hash(state)
+    }
+}
+
 impl PartialOrd for NSMutableString<'_> {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -889,7 +1171,7 @@ impl<'data> NSMutableString<'data> {
         let value: Arc<Self> = unsafe { Self::class().alloc() };
 
         #[allow(clashing_extern_declarations)]
-        extern "C" {
+        extern "C-unwind" {
             fn objc_msgSend<'data>(
                 obj: Arc<NSMutableString<'data>>,
                 sel: Sel,
@@ -909,4 +1191,373 @@ impl<'data> NSMutableString<'data> {
 
         unsafe { objc_msgSend(obj, sel, bytes, length, encoding, free_when_done) }
     }
+
+    /// Creates an empty mutable string object with initial storage for
+    /// `capacity` UTF-16 code units.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1497379-initwithcapacity).
+    #[inline]
+    #[doc(alias = "initWithCapacity")]
+    #[doc(alias = "stringWithCapacity")]
+    pub fn with_capacity(capacity: NSUInteger) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend<'data>(
+                obj: Arc<NSMutableString<'data>>,
+                sel: Sel,
+                capacity: NSUInteger,
+            ) -> Arc<NSMutableString<'data>>;
+        }
+
+        let sel = selector!(initWithCapacity:);
+        unsafe { objc_msgSend(value, sel, capacity) }
+    }
+}
+
+/// Mutating methods take `&mut self`, which can be obtained from a uniquely
+/// referenced string via [`Arc::get_mut`].
+///
+/// Indices and ranges are measured in UTF-16 code units, like
+/// [`NSRange`](struct.NSRange.html) everywhere else in Foundation. A character
+/// outside of the Basic Multilingual Plane, such as `'🦀'`, takes up two units.
+impl NSMutableString<'_> {
+    /// Appends a string slice to the end of this string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1417883-appendstring).
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.push_ns(&NSString::from_str(s));
+    }
+
+    /// Appends a string object to the end of this string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1417883-appendstring).
+    #[inline]
+    #[doc(alias = "appendString")]
+    pub fn push_ns(&mut self, s: &NSString) {
+        unsafe { _msg_send_any![self, appendString: s] }
+    }
+
+    /// Inserts a string object at the UTF-16 code unit `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than [`len`](struct.NSString.html#method.len).
+    /// Inserting in the middle of a surrogate pair is not checked and produces
+    /// unpaired surrogates.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1416462-insertstring).
+    #[inline]
+    #[doc(alias = "insertString")]
+    #[doc(alias = "insertString:atIndex:")]
+    pub fn insert(&mut self, index: NSUInteger, s: &NSString) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "Index {} is out of bounds for length {}",
+            index,
+            len
+        );
+        unsafe { _msg_send_any![self, insertString: s atIndex: index] }
+    }
+
+    /// Replaces all occurrences of `target` within the UTF-16 `range` with
+    /// `replacement`, returning the number of replacements made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` extends beyond [`len`](struct.NSString.html#method.len).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1416035-replaceoccurrencesofstring).
+    #[doc(alias = "replaceOccurrencesOfString")]
+    #[doc(alias = "replaceOccurrencesOfString:withString:options:range:")]
+    pub fn replace_occurrences(
+        &mut self,
+        target: &NSString,
+        replacement: &NSString,
+        options: NSStringCompareOptions,
+        range: impl Into<NSRange>,
+    ) -> NSUInteger {
+        let range = range.into();
+        let len = self.len();
+        assert!(
+            range.location <= len && range.length <= len - range.location,
+            "Range {}..{} is out of bounds for length {}",
+            range.location,
+            range.location.saturating_add(range.length),
+            len
+        );
+        unsafe {
+            _msg_send_any![
+                self,
+                replaceOccurrencesOfString: target
+                withString: replacement
+                options: options
+                range: range
+                => NSUInteger
+            ]
+        }
+    }
+
+    /// Replaces the contents of this string with those of `s`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutablestring/1411780-setstring).
+    #[inline]
+    #[doc(alias = "setString")]
+    pub fn set_string(&mut self, s: &NSString) {
+        unsafe { _msg_send_any![self, setString: s] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn from_utf16(units: &[u16]) -> Arc<NSString<'static>> {
+        extern "C" {
+            fn CFStringCreateWithCharacters(
+                alloc: *const std::ffi::c_void,
+                chars: *const u16,
+                num_chars: isize,
+            ) -> Arc<NSString<'static>>;
+        }
+        unsafe { CFStringCreateWithCharacters(ptr::null(), units.as_ptr(), units.len() as isize) }
+    }
+
+    fn hash(s: &NSString) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn from_str() {
+        for &s in &[
+            "",
+            "asdf",
+            "🦀 crab",
+            "lööps, bröther?",
+            "讓每個人都能打造出。",
+        ] {
+            let string = NSString::from_str(s);
+            assert_eq!(string.to_string(), s);
+            assert_eq!(string.to_string_with_nul(), format!("{}\0", s));
+            assert_eq!(format!("{}", string), s);
+            assert_eq!(format!("{:?}", string), format!("{:?}", s));
+            assert_eq!(*string, *s);
+            assert_eq!(string.len(), s.encode_utf16().count());
+            assert_eq!(string.utf8_len(), s.len());
+            assert_eq!(string.is_empty(), s.is_empty());
+            assert_eq!(string.to_utf16(), s.encode_utf16().collect::<Vec<_>>());
+
+            if let Some(fast) = unsafe { string.as_str() } {
+                assert_eq!(fast, s);
+            }
+        }
+    }
+
+    #[test]
+    fn eq_hash() {
+        let a = NSString::from_str("a string that is long enough to not be tagged");
+        let b = NSMutableString::from_str("a string that is long enough to not be tagged");
+
+        assert_eq!(*a, *b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(*a, *NSString::from_str("another string"));
+    }
+
+    #[test]
+    fn embedded_nul() {
+        let s = "before\0after";
+        let string = NSString::from_str(s);
+
+        assert_eq!(string.len(), 12);
+        assert_eq!(string.to_string(), s);
+        assert_eq!(string.to_string(), format!("{}", string));
+        assert_eq!(*string, *s);
+        assert_ne!(*string, *"before");
+
+        // The fast path must not return a slice cut short by the null.
+        if let Some(fast) = unsafe { string.as_str() } {
+            assert_eq!(fast, s);
+        }
+    }
+
+    #[test]
+    fn unpaired_surrogate() {
+        let string = from_utf16(&[0x61, 0xD800, 0x62]);
+
+        assert_eq!(string.len(), 3);
+        assert_eq!(string.to_string(), "a\u{FFFD}b");
+        assert_eq!(format!("{}", string), "a\u{FFFD}b");
+        assert_ne!(*string, *"a\u{FFFD}b");
+        assert_eq!(string.to_utf16(), [0x61, 0xD800, 0x62]);
+    }
+
+    #[test]
+    fn mac_roman_round_trip() {
+        // “quoted” in MacRoman.
+        let bytes = [0xD2, b'q', b'u', b'o', b't', b'e', b'd', 0xD3];
+
+        let string = NSString::from_bytes(&bytes, NSStringEncoding::MAC_ROMAN).unwrap();
+        assert_eq!(*string, "“quoted”");
+        assert!(string.can_be_converted_to(NSStringEncoding::MAC_ROMAN));
+        assert!(!string.can_be_converted_to(NSStringEncoding::ASCII));
+
+        let encoded = string.to_bytes(NSStringEncoding::MAC_ROMAN, false);
+        assert_eq!(encoded.as_deref(), Some(&bytes[..]));
+    }
+
+    #[test]
+    fn utf16_le_bytes() {
+        let string = NSString::from_str("a🦀");
+        let bytes = string.to_bytes(NSStringEncoding::UTF16_LE, false).unwrap();
+        assert_eq!(bytes, [0x61, 0x00, 0x3E, 0xD8, 0x80, 0xDD]);
+
+        let decoded = NSString::from_bytes(&bytes, NSStringEncoding::UTF16_LE).unwrap();
+        assert_eq!(*decoded, *string);
+    }
+
+    #[test]
+    fn lossy_ascii() {
+        let string = NSString::from_str("crab 🦀");
+        assert!(!string.can_be_converted_to(NSStringEncoding::ASCII));
+        assert_eq!(string.to_bytes(NSStringEncoding::ASCII, false), None);
+
+        let lossy = string.to_bytes(NSStringEncoding::ASCII, true).unwrap();
+        assert!(lossy.starts_with(b"crab "));
+        assert!(lossy.len() > 5);
+        assert!(lossy.is_ascii());
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        assert!(NSString::from_bytes(&[0xFF, 0xFE, 0xFD], NSStringEncoding::UTF8).is_none());
+        assert_eq!(
+            NSString::from_bytes(&[], NSStringEncoding::UTF8).map(|s| s.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn encodings() {
+        let available = NSString::available_encodings();
+        assert!(available.contains(&NSStringEncoding::UTF8));
+        assert!(available.contains(&NSStringEncoding::MAC_ROMAN));
+
+        let ascii = NSString::from_str("plain ascii");
+        assert!(ascii.can_be_converted_to(ascii.smallest_encoding()));
+        assert!(ascii.can_be_converted_to(ascii.fastest_encoding()));
+    }
+
+    #[test]
+    fn astral_ranges() {
+        // "𝄞" (U+1D11E) and "🦀" (U+1F980) are surrogate pairs in UTF-16.
+        let s = "a𝄞bc🦀";
+        let string = NSString::from_str(s);
+        assert_eq!(string.len(), 7);
+
+        let cases: &[(NSRange, Option<Range<usize>>)] = &[
+            (NSRange::new(0, 0), Some(0..0)),
+            (NSRange::new(0, 1), Some(0..1)),
+            (NSRange::new(1, 2), Some(1..5)),
+            (NSRange::new(0, 7), Some(0..s.len())),
+            (NSRange::new(7, 0), Some(s.len()..s.len())),
+            (NSRange::new(5, 2), Some(7..11)),
+            // Splits a surrogate pair.
+            (NSRange::new(1, 1), None),
+            (NSRange::new(2, 2), None),
+            (NSRange::new(6, 1), None),
+            // Out of bounds.
+            (NSRange::new(0, 8), None),
+            (NSRange::not_found(), None),
+        ];
+
+        for (range, expected) in cases {
+            let bytes = string.byte_range_for(*range);
+            assert_eq!(bytes, *expected, "{:?}", range);
+
+            if let Some(bytes) = bytes {
+                assert_eq!(string.utf16_range_for(bytes), Some(*range));
+            }
+        }
+
+        // Not on a character boundary.
+        assert_eq!(string.utf16_range_for(1..3), None);
+        assert_eq!(string.utf16_range_for(2..5), None);
+        assert_eq!(string.utf16_range_for(Range { start: 3, end: 1 }), None);
+        assert_eq!(string.utf16_range_for(0..12), None);
+
+        // Unpaired surrogates map to U+FFFD in `to_string`.
+        let lone = from_utf16(&[0x61, 0xD800, 0x62]);
+        assert_eq!(lone.byte_range_for(NSRange::new(1, 1)), Some(1..4));
+        assert_eq!(lone.utf16_range_for(4..5), Some(NSRange::new(2, 1)));
+    }
+
+    #[test]
+    fn mutable_push_insert() {
+        let mut string = NSMutableString::with_capacity(8);
+        let string = Arc::get_mut(&mut string).unwrap();
+        assert!(string.is_empty());
+
+        string.push_str("🦀");
+        string.push_ns(&NSString::from_str("b"));
+        assert_eq!(string.len(), 3);
+
+        // The crab is a surrogate pair, so "b" starts at UTF-16 index 2.
+        string.insert(2, &NSString::from_str("a"));
+        assert_eq!(*string, "🦀ab");
+
+        string.insert(0, &NSString::from_str("<"));
+        let len = string.len();
+        string.insert(len, &NSString::from_str(">"));
+        assert_eq!(*string, "<🦀ab>");
+    }
+
+    #[test]
+    #[should_panic]
+    fn mutable_insert_out_of_bounds() {
+        let mut string = NSMutableString::from_str("🦀");
+        let string = Arc::get_mut(&mut string).unwrap();
+        string.insert(3, &NSString::from_str("a"));
+    }
+
+    #[test]
+    fn mutable_replace_set() {
+        let mut string = NSMutableString::from_str("a🦀a🦀A");
+        let string = Arc::get_mut(&mut string).unwrap();
+
+        let a = NSString::from_str("a");
+        let b = NSString::from_str("b");
+        let len = string.len();
+
+        let count = string.replace_occurrences(&a, &b, NSStringCompareOptions::NONE, 0..len);
+        assert_eq!(count, 2);
+        assert_eq!(*string, "b🦀b🦀A");
+
+        // Only search past the first crab.
+        let count = string.replace_occurrences(
+            &NSString::from_str("B"),
+            &NSString::from_str("c"),
+            NSStringCompareOptions::CASE_INSENSITIVE,
+            3..len,
+        );
+        assert_eq!(count, 1);
+        assert_eq!(*string, "b🦀c🦀A");
+
+        string.set_string(&NSString::from_str("reset"));
+        assert_eq!(*string, "reset");
+    }
+
+    #[test]
+    fn shared_not_unique() {
+        let mut string = NSMutableString::from_str("shared");
+        let clone = string.clone();
+        assert!(Arc::get_mut(&mut string).is_none());
+        drop(clone);
+        assert!(Arc::get_mut(&mut string).is_some());
+    }
 }
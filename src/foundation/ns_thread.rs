@@ -0,0 +1,209 @@
+use super::NSString;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, BOOL};
+use std::{
+    ffi::c_void,
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Duration,
+};
+
+objc_subclass! {
+    /// A thread of execution.
+    ///
+    /// The setters take `&self` because a thread object is shared with the
+    /// thread it represents.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread).
+    #[derive(PartialEq)]
+    pub class NSThread: NSObject<'static>;
+}
+
+impl NSThread {
+    /// Returns the thread object for the calling thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1410098-currentthread).
+    #[inline]
+    #[doc(alias = "currentThread")]
+    pub fn current() -> Arc<Self> {
+        let thread =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), currentThread]) };
+        thread.expect("No current thread")
+    }
+
+    /// Returns the thread object for the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1418002-mainthread).
+    #[inline]
+    #[doc(alias = "mainThread")]
+    pub fn main() -> Arc<Self> {
+        let thread = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), mainThread]) };
+        thread.expect("No main thread")
+    }
+
+    /// Returns `true` if the calling thread is the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1412704-ismainthread).
+    #[inline]
+    #[doc(alias = "isMainThread")]
+    pub fn is_main_thread() -> bool {
+        unsafe { _msg_send_any![Self::class(), isMainThread => BOOL] }.into()
+    }
+
+    /// Returns `true` if `self` is the main thread.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1408455-ismainthread).
+    #[inline]
+    #[doc(alias = "isMainThread")]
+    pub fn is_main(&self) -> bool {
+        unsafe { _msg_send_any![self, isMainThread => BOOL] }.into()
+    }
+
+    /// Returns the name of `self`, if set.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1414722-name).
+    #[inline]
+    pub fn name(&self) -> Option<Arc<NSString<'static>>> {
+        let name: Option<Arc<NSString>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, name]) };
+        name.filter(|name| !name.is_empty())
+    }
+
+    /// Sets the name of `self`, which appears in debuggers and crash logs.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1414722-name).
+    #[inline]
+    #[doc(alias = "setName")]
+    #[doc(alias = "setName:")]
+    pub fn set_name(&self, name: &str) {
+        let name = NSString::from_str(name);
+        unsafe { _msg_send_any![self, setName: &*name => ()] }
+    }
+
+    /// Returns the stack size of `self` in bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1415190-stacksize).
+    #[inline]
+    #[doc(alias = "stackSize")]
+    pub fn stack_size(&self) -> usize {
+        unsafe { _msg_send_any![self, stackSize] }
+    }
+
+    /// Blocks the calling thread for `duration`.
+    ///
+    /// This is equivalent to
+    /// [`std::thread::sleep`](https://doc.rust-lang.org/std/thread/fn.sleep.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsthread/1413673-sleepfortimeinterval).
+    #[inline]
+    #[doc(alias = "sleepForTimeInterval")]
+    #[doc(alias = "sleepForTimeInterval:")]
+    pub fn sleep(duration: Duration) {
+        let interval = duration.as_secs_f64();
+        unsafe { _msg_send_any![Self::class(), sleepForTimeInterval: interval => ()] }
+    }
+}
+
+/// Runs `f` on the main thread and returns its result.
+///
+/// If called from the main thread, `f` is called directly. Otherwise, `f` is
+/// submitted to the main dispatch queue and the calling thread blocks until it
+/// finishes. This makes it safe to nest calls.
+///
+/// If `f` panics, the panic is resumed on the calling thread.
+///
+/// # Deadlocks
+///
+/// The main thread must be draining the main queue, such as by running its
+/// run loop or `dispatch_main`. This blocks forever if the main thread is
+/// instead waiting on the calling thread.
+pub fn run_on_main<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    if NSThread::is_main_thread() {
+        return f();
+    }
+
+    // Declared here because `foundation` does not require `dispatch`.
+    #[allow(clashing_extern_declarations)]
+    extern "C" {
+        static _dispatch_main_q: c_void;
+
+        fn dispatch_sync_f(
+            queue: *const c_void,
+            context: *mut c_void,
+            work: extern "C" fn(*mut c_void),
+        );
+    }
+
+    struct Context<F, R> {
+        f: Option<F>,
+        result: Option<thread::Result<R>>,
+    }
+
+    extern "C" fn work<F, R>(context: *mut c_void)
+    where
+        F: FnOnce() -> R,
+    {
+        let context = unsafe { &mut *context.cast::<Context<F, R>>() };
+        if let Some(f) = context.f.take() {
+            // Unwinding into libdispatch is undefined behavior.
+            context.result = Some(panic::catch_unwind(AssertUnwindSafe(f)));
+        }
+    }
+
+    let mut context = Context {
+        f: Some(f),
+        result: None,
+    };
+    let context_ptr: *mut Context<F, R> = &mut context;
+
+    unsafe { dispatch_sync_f(&_dispatch_main_q, context_ptr.cast(), work::<F, R>) };
+
+    match context.result.expect("Main queue did not run closure") {
+        Ok(result) => result,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    // Tests run on background threads, so `run_on_main` is tested by
+    // `tests/run_on_main.rs` with its own `main`.
+
+    #[test]
+    fn current() {
+        assert!(!NSThread::is_main_thread());
+
+        let current = NSThread::current();
+        assert!(!current.is_main());
+        assert!(*current != *NSThread::main());
+        assert!(NSThread::main().is_main());
+        assert!(current.stack_size() > 0);
+    }
+
+    #[test]
+    fn name() {
+        thread::spawn(|| {
+            let current = NSThread::current();
+            assert!(current.name().is_none());
+
+            current.set_name("fruity-thread");
+            assert_eq!(*NSThread::current().name().unwrap(), *"fruity-thread");
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn sleep() {
+        let start = Instant::now();
+        NSThread::sleep(Duration::from_millis(20));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,190 @@
+use super::{NSDate, NSString};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, NSObject, Sel, BOOL};
+use std::fmt;
+
+objc_subclass! {
+    /// Information about standard time conventions associated with a specific
+    /// geopolitical region.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone).
+    #[derive(PartialEq)]
+    pub class NSTimeZone: NSObject<'static>;
+}
+
+impl fmt::Debug for NSTimeZone {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NSTimeZone").field(&self.name()).finish()
+    }
+}
+
+impl NSTimeZone {
+    /// Returns the time zone currently used by the system.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387220-systemtimezone).
+    #[inline]
+    #[doc(alias = "systemTimeZone")]
+    pub fn system() -> Arc<Self> {
+        let zone = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), systemTimeZone]) };
+        zone.expect("No system time zone")
+    }
+
+    /// Returns a time zone that tracks the current system time zone, even if
+    /// it changes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387209-localtimezone).
+    #[inline]
+    #[doc(alias = "localTimeZone")]
+    pub fn local() -> Arc<Self> {
+        let zone = unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), localTimeZone]) };
+        zone.expect("No local time zone")
+    }
+
+    /// Returns the time zone with a name such as `"America/New_York"`, or
+    /// `None` if the name is unknown.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387250-initwithname).
+    #[doc(alias = "timeZoneWithName")]
+    #[doc(alias = "timeZoneWithName:")]
+    #[doc(alias = "initWithName:")]
+    pub fn from_name(name: &str) -> Option<Arc<Self>> {
+        let name = NSString::from_str(name);
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSTimeZone>,
+                sel: Sel,
+                name: &NSString,
+            ) -> Option<Arc<NSTimeZone>>;
+        }
+
+        let sel = selector!(initWithName:);
+        unsafe { objc_msgSend(value, sel, &name) }
+    }
+
+    /// Returns a time zone with a fixed offset from GMT, or `None` if the
+    /// offset is more than 18 hours.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387224-timezoneforsecondsfromgmt).
+    #[inline]
+    #[doc(alias = "timeZoneForSecondsFromGMT")]
+    #[doc(alias = "timeZoneForSecondsFromGMT:")]
+    pub fn from_seconds_from_gmt(seconds: NSInteger) -> Option<Arc<Self>> {
+        unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), timeZoneForSecondsFromGMT: seconds],
+            )
+        }
+    }
+
+    /// Returns the geopolitical region identifier of `self`, such as
+    /// `"America/New_York"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387198-name).
+    #[inline]
+    pub fn name(&self) -> Arc<NSString<'static>> {
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, name]) };
+        name.expect("Time zone has no name")
+    }
+
+    /// Returns the current difference in seconds between `self` and GMT.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387191-secondsfromgmt).
+    #[inline]
+    #[doc(alias = "secondsFromGMT")]
+    pub fn seconds_from_gmt(&self) -> NSInteger {
+        unsafe { _msg_send_any![self, secondsFromGMT] }
+    }
+
+    /// Returns the difference in seconds between `self` and GMT at `date`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387210-secondsfromgmtfordate).
+    #[inline]
+    #[doc(alias = "secondsFromGMTForDate")]
+    #[doc(alias = "secondsFromGMTForDate:")]
+    pub fn seconds_from_gmt_for_date(&self, date: &NSDate) -> NSInteger {
+        unsafe { _msg_send_any![self, secondsFromGMTForDate: date] }
+    }
+
+    /// Returns the current abbreviation of `self`, such as `"EST"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387235-abbreviation).
+    #[inline]
+    pub fn abbreviation(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, abbreviation]) }
+    }
+
+    /// Returns the abbreviation of `self` at `date`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387237-abbreviationfordate).
+    #[inline]
+    #[doc(alias = "abbreviationForDate")]
+    #[doc(alias = "abbreviationForDate:")]
+    pub fn abbreviation_for_date(&self, date: &NSDate) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, abbreviationForDate: date]) }
+    }
+
+    /// Returns `true` if `self` uses daylight saving time at `date`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387225-isdaylightsavingtimefordate).
+    #[inline]
+    #[doc(alias = "isDaylightSavingTimeForDate")]
+    #[doc(alias = "isDaylightSavingTimeForDate:")]
+    pub fn is_daylight_saving_time_for_date(&self, date: &NSDate) -> bool {
+        unsafe { _msg_send_any![self, isDaylightSavingTimeForDate: date => BOOL] }.into()
+    }
+
+    /// Returns the next daylight saving time transition after `date`, or
+    /// `None` if `self` does not observe daylight saving time.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimezone/1387239-nextdaylightsavingtimetransition).
+    #[inline]
+    #[doc(alias = "nextDaylightSavingTimeTransitionAfterDate")]
+    #[doc(alias = "nextDaylightSavingTimeTransitionAfterDate:")]
+    pub fn next_daylight_saving_time_transition(&self, date: &NSDate) -> Option<Arc<NSDate>> {
+        unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, nextDaylightSavingTimeTransitionAfterDate: date],
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named() {
+        let zone = NSTimeZone::from_name("America/New_York").unwrap();
+        assert_eq!(*zone.name(), *"America/New_York");
+
+        // 2021-01-01 00:00:00 UTC.
+        let winter = NSDate::from_time_interval_since_1970(1_609_459_200.0);
+        assert_eq!(zone.seconds_from_gmt_for_date(&winter), -5 * 3600);
+        assert!(!zone.is_daylight_saving_time_for_date(&winter));
+
+        // 2021-03-14 07:00:00 UTC, when clocks skip from 02:00 to 03:00 EST.
+        let transition = zone.next_daylight_saving_time_transition(&winter).unwrap();
+        assert_eq!(transition.time_interval_since_1970(), 1_615_705_200.0);
+        assert_eq!(zone.seconds_from_gmt_for_date(&transition), -4 * 3600);
+        assert!(zone.is_daylight_saving_time_for_date(&transition));
+
+        assert!(NSTimeZone::from_name("Not/A_Zone").is_none());
+    }
+
+    #[test]
+    fn fixed() {
+        let zone = NSTimeZone::from_seconds_from_gmt(3600).unwrap();
+        assert_eq!(zone.seconds_from_gmt(), 3600);
+        assert!(zone
+            .next_daylight_saving_time_transition(&NSDate::now())
+            .is_none());
+
+        assert!(!NSTimeZone::system().name().is_empty());
+        assert!(!NSTimeZone::local().name().is_empty());
+    }
+}
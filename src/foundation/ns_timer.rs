@@ -0,0 +1,325 @@
+use super::{NSRunLoop, NSRunLoopMode, NSTimeInterval};
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{
+    allocate_private_class, retain_autoreleased, sys, Block, Class, ClassType, Imp, NSObject,
+    ObjCObject, RcBlock, Sel, BOOL,
+};
+use std::{
+    cell::RefCell,
+    mem, ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Once,
+    },
+};
+
+objc_subclass! {
+    /// A timer that calls a closure after an interval has elapsed, while
+    /// registered with an [`NSRunLoop`](struct.NSRunLoop.html).
+    ///
+    /// Methods take `&self` because a timer is shared with its run loop.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer).
+    #[derive(PartialEq)]
+    pub class NSTimer: NSObject<'static>;
+}
+
+/// A block that takes the firing timer.
+type TimerBlock = RcBlock<(*const NSTimer,), ()>;
+
+/// Wraps `f` in a block that takes the firing timer.
+fn timer_block<F>(f: F) -> TimerBlock
+where
+    F: FnMut(&NSTimer) + Send + 'static,
+{
+    let f = RefCell::new(f);
+    RcBlock::without_signature(move |timer: *const NSTimer| {
+        let timer = unsafe { &*timer };
+
+        // Calling `fire` within `f` does nothing rather than aliasing `f`.
+        if let Ok(mut f) = f.try_borrow_mut() {
+            abort_on_panic(|| f(timer));
+        }
+    })
+}
+
+// The address of this is the key of the block associated with a target.
+static TARGET_BLOCK_KEY: u8 = 0;
+
+const OBJC_ASSOCIATION_RETAIN_NONATOMIC: usize = 1;
+
+/// Returns a class whose instances forward `-fire:` to their associated
+/// timer block, for when `+timerWithTimeInterval:repeats:block:` is
+/// unavailable (before macOS 10.12 and iOS 10).
+fn target_class() -> &'static Class {
+    static REGISTER: Once = Once::new();
+    static CLASS: AtomicPtr<Class> = AtomicPtr::new(ptr::null_mut());
+
+    unsafe extern "C" fn fire(this: &ObjCObject<'static>, _cmd: Sel, timer: *const NSTimer) {
+        let key: *const u8 = &TARGET_BLOCK_KEY;
+        let block = sys::objc_getAssociatedObject(this, key.cast());
+
+        let block = Block::<(*const NSTimer,), ()>::from_ptr(block.cast());
+        block.unwrap().call((timer,));
+    }
+
+    REGISTER.call_once(|| unsafe {
+        let class = allocate_private_class("_FruityTimerTarget", NSObject::class());
+        let class = class.as_ptr();
+
+        type Fire = unsafe extern "C" fn(&ObjCObject<'static>, Sel, *const NSTimer);
+        let fire = mem::transmute::<Fire, Imp>(fire);
+        let types = b"v@:@\0".as_ptr().cast();
+        sys::class_addMethod(class, selector!(fire:), fire, types);
+        sys::objc_registerClassPair(class);
+
+        CLASS.store(class, Ordering::Release);
+    });
+
+    unsafe { &*CLASS.load(Ordering::Acquire) }
+}
+
+/// Creating timers.
+impl NSTimer {
+    /// Creates a timer that calls `f` after `interval` seconds, and then
+    /// every `interval` seconds if `repeats` is `true`.
+    ///
+    /// The timer does nothing until added to a run loop with
+    /// [`NSRunLoop::add_timer`](struct.NSRunLoop.html#method.add_timer).
+    ///
+    /// `f` must be `Send` because the timer may be added to the run loop of
+    /// another thread. `f` is dropped once the timer is invalidated, and
+    /// panics in `f` abort the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/2091888-timerwithtimeinterval).
+    #[doc(alias = "timerWithTimeInterval")]
+    #[doc(alias = "timerWithTimeInterval:repeats:block:")]
+    pub fn new<F>(interval: NSTimeInterval, repeats: bool, f: F) -> Arc<Self>
+    where
+        F: FnMut(&NSTimer) + Send + 'static,
+    {
+        let block = timer_block(f);
+        if Self::class().responds_to_selector(selector!(timerWithTimeInterval:repeats:block:)) {
+            Self::with_block(interval, repeats, &block)
+        } else {
+            Self::with_target(interval, repeats, &block)
+        }
+    }
+
+    /// Creates a timer like [`new`](#method.new) and adds it to the current
+    /// run loop in the default mode.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/2091889-scheduledtimerwithtimeinterval).
+    #[doc(alias = "scheduledTimerWithTimeInterval")]
+    #[doc(alias = "scheduledTimerWithTimeInterval:repeats:block:")]
+    pub fn scheduled<F>(interval: NSTimeInterval, repeats: bool, f: F) -> Arc<Self>
+    where
+        F: FnMut(&NSTimer) + Send + 'static,
+    {
+        let timer = Self::new(interval, repeats, f);
+        NSRunLoop::current().add_timer(&timer, NSRunLoopMode::default());
+        timer
+    }
+
+    fn with_block(interval: NSTimeInterval, repeats: bool, block: &TimerBlock) -> Arc<Self> {
+        let repeats = BOOL::from(repeats);
+        let block = block.as_ptr();
+        let timer = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    Self::class(),
+                    timerWithTimeInterval: interval
+                    repeats: repeats
+                    block: block
+                ]
+            })
+        };
+        timer.expect("Failed to create timer")
+    }
+
+    fn with_target(interval: NSTimeInterval, repeats: bool, block: &TimerBlock) -> Arc<Self> {
+        // The timer retains the target until invalidated, and the target
+        // retains the block.
+        let target: Arc<NSObject<'static>> = unsafe { target_class().alloc_init() };
+        let key: *const u8 = &TARGET_BLOCK_KEY;
+        unsafe {
+            sys::objc_setAssociatedObject(
+                &**target,
+                key.cast(),
+                block.as_ptr().cast(),
+                OBJC_ASSOCIATION_RETAIN_NONATOMIC,
+            )
+        };
+
+        let repeats = BOOL::from(repeats);
+        let selector = selector!(fire:);
+        let user_info: *const NSObject<'static> = ptr::null();
+        let timer = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    Self::class(),
+                    timerWithTimeInterval: interval
+                    target: &*target
+                    selector: selector
+                    userInfo: user_info
+                    repeats: repeats
+                ]
+            })
+        };
+        timer.expect("Failed to create timer")
+    }
+}
+
+impl NSTimer {
+    /// Calls the closure of `self` immediately.
+    ///
+    /// This does not affect the schedule of a repeating timer, but
+    /// invalidates a non-repeating timer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1412267-fire).
+    #[inline]
+    pub fn fire(&self) {
+        unsafe { _msg_send_any![self, fire => ()] }
+    }
+
+    /// Stops `self` from ever firing again, and removes it from its run loop.
+    ///
+    /// This must be called from the thread whose run loop `self` was added
+    /// to. The closure is dropped once the run loop releases it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1415405-invalidate).
+    #[inline]
+    pub fn invalidate(&self) {
+        unsafe { _msg_send_any![self, invalidate => ()] }
+    }
+
+    /// Returns `true` if `self` can still fire.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1415328-valid).
+    #[inline]
+    #[doc(alias = "isValid")]
+    pub fn is_valid(&self) -> bool {
+        unsafe { _msg_send_any![self, isValid => BOOL] }.into()
+    }
+
+    /// Returns the interval of a repeating timer, or 0 for a non-repeating
+    /// timer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1409024-timeinterval).
+    #[inline]
+    #[doc(alias = "timeInterval")]
+    pub fn time_interval(&self) -> NSTimeInterval {
+        unsafe { _msg_send_any![self, timeInterval] }
+    }
+
+    /// Returns how late `self` may fire, in seconds.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1415085-tolerance).
+    #[inline]
+    pub fn tolerance(&self) -> NSTimeInterval {
+        unsafe { _msg_send_any![self, tolerance] }
+    }
+
+    /// Sets how late `self` may fire, in seconds, which lets the system
+    /// coalesce timers to save power.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nstimer/1415085-tolerance).
+    #[inline]
+    #[doc(alias = "setTolerance")]
+    #[doc(alias = "setTolerance:")]
+    pub fn set_tolerance(&self, tolerance: NSTimeInterval) {
+        unsafe { _msg_send_any![self, setTolerance: tolerance => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSDate;
+    use crate::objc::autoreleasepool;
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize},
+        time::{Duration, Instant},
+    };
+
+    // Sets a flag when dropped along with a timer's closure.
+    struct DropFlag(std::sync::Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    // Pumps the current run loop until a repeating timer invalidates itself
+    // after three firings.
+    fn fire_three_times(create: fn(NSTimeInterval, bool, &TimerBlock) -> Arc<NSTimer>) {
+        let count = std::sync::Arc::new(AtomicUsize::new(0));
+        let dropped = std::sync::Arc::new(AtomicBool::new(false));
+
+        let timer = autoreleasepool(|_| {
+            let count = count.clone();
+            let flag = DropFlag(dropped.clone());
+            let block = timer_block(move |timer: &NSTimer| {
+                let _ = &flag;
+                if count.fetch_add(1, Ordering::SeqCst) + 1 == 3 {
+                    timer.invalidate();
+                }
+            });
+
+            let timer = create(0.01, true, &block);
+            NSRunLoop::current().add_timer(&timer, NSRunLoopMode::default());
+            timer
+        });
+        assert_eq!(timer.time_interval(), 0.01);
+
+        let run_loop = NSRunLoop::current();
+        let start = Instant::now();
+        while timer.is_valid() && start.elapsed() < Duration::from_secs(5) {
+            autoreleasepool(|_| {
+                let until = NSDate::from_time_interval_since(0.05, &NSDate::now());
+                run_loop.run_mode_before(NSRunLoopMode::default(), &until);
+            });
+        }
+
+        assert!(!timer.is_valid());
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // The closure is not leaked once the last reference is gone.
+        drop(timer);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn repeating() {
+        fire_three_times(NSTimer::with_block);
+    }
+
+    #[test]
+    fn repeating_with_target() {
+        fire_three_times(NSTimer::with_target);
+    }
+
+    #[test]
+    fn fire() {
+        let count = std::sync::Arc::new(AtomicUsize::new(0));
+        let timer = {
+            let count = count.clone();
+            NSTimer::scheduled(100.0, false, move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        timer.set_tolerance(0.5);
+        assert_eq!(timer.tolerance(), 0.5);
+        assert!(timer.is_valid());
+
+        timer.fire();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(!timer.is_valid());
+
+        timer.fire();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,407 @@
+use super::{ns_error::with_error_out, NSError, NSNumber, NSString, NSURLResourceKey};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, Sel, BOOL};
+use std::{
+    ffi::{CStr, CString, OsStr},
+    fmt,
+    os::{raw::c_char, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+    ptr,
+};
+
+objc_subclass! {
+    /// An object representing the location of a resource, such as an item on
+    /// a remote server or the path to a local file.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl).
+    #[derive(PartialEq)]
+    pub class NSURL: NSObject<'static>;
+}
+
+impl fmt::Debug for NSURL {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.absolute_string() {
+            Some(string) => string.fmt(f),
+            None => f.write_str("NSURL"),
+        }
+    }
+}
+
+/// Creating URLs.
+impl NSURL {
+    /// Creates a URL from a string, or returns `None` if the string is not a
+    /// valid URL.
+    ///
+    /// Unlike `+[NSURL URLWithString:]` on macOS 14 and iOS 17 or later, this
+    /// does not percent-encode invalid characters. Use
+    /// [`from_str_encoding_invalid_characters`](#method.from_str_encoding_invalid_characters)
+    /// for that.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/4165643-initwithstring).
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "URLWithString")]
+    #[doc(alias = "URLWithString:")]
+    #[doc(alias = "initWithString:")]
+    #[doc(alias = "initWithString:encodingInvalidCharacters:")]
+    pub fn from_str(string: &str) -> Option<Arc<Self>> {
+        Self::from_nsstring(&NSString::from_str(string), false)
+    }
+
+    /// Creates a URL from a string, percent-encoding characters that are not
+    /// allowed in URLs.
+    ///
+    /// This returns `None` if the string is not a valid URL even after
+    /// encoding, such as if it is empty.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/4165643-initwithstring).
+    #[doc(alias = "initWithString:encodingInvalidCharacters:")]
+    pub fn from_str_encoding_invalid_characters(string: &str) -> Option<Arc<Self>> {
+        if Self::supports_encoding_invalid_characters() {
+            Self::from_nsstring(&NSString::from_str(string), true)
+        } else {
+            Self::from_nsstring(&NSString::from_str(&percent_encode_invalid(string)), false)
+        }
+    }
+
+    fn supports_encoding_invalid_characters() -> bool {
+        Self::class()
+            .instances_respond_to_selector(selector!(initWithString:encodingInvalidCharacters:))
+    }
+
+    fn from_nsstring(string: &NSString, encode: bool) -> Option<Arc<Self>> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        if Self::supports_encoding_invalid_characters() {
+            #[allow(clashing_extern_declarations)]
+            extern "C-unwind" {
+                fn objc_msgSend(
+                    obj: Arc<NSURL>,
+                    sel: Sel,
+                    string: &NSString,
+                    encode: BOOL,
+                ) -> Option<Arc<NSURL>>;
+            }
+
+            let sel = selector!(initWithString:encodingInvalidCharacters:);
+            unsafe { objc_msgSend(value, sel, string, encode.into()) }
+        } else {
+            // Earlier versions never encode invalid characters.
+            #[allow(clashing_extern_declarations)]
+            extern "C-unwind" {
+                fn objc_msgSend(obj: Arc<NSURL>, sel: Sel, string: &NSString)
+                    -> Option<Arc<NSURL>>;
+            }
+
+            let sel = selector!(initWithString:);
+            unsafe { objc_msgSend(value, sel, string) }
+        }
+    }
+
+    /// Creates a file URL for `path`.
+    ///
+    /// If `is_directory` is `true`, the URL's path ends with a slash.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1414650-fileurlwithfilesystemrepresenta).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` contains a null byte.
+    #[doc(alias = "fileURLWithPath")]
+    #[doc(alias = "fileURLWithPath:isDirectory:")]
+    #[doc(alias = "initFileURLWithFileSystemRepresentation:isDirectory:relativeToURL:")]
+    pub fn file_url(path: &Path, is_directory: bool) -> Arc<Self> {
+        let path = CString::new(path.as_os_str().as_bytes()).expect("Path contains a null byte");
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSURL>,
+                sel: Sel,
+                path: *const c_char,
+                is_directory: BOOL,
+                base: *const NSURL,
+            ) -> Arc<NSURL>;
+        }
+
+        let sel = selector!(initFileURLWithFileSystemRepresentation:isDirectory:relativeToURL:);
+        unsafe { objc_msgSend(value, sel, path.as_ptr(), is_directory.into(), ptr::null()) }
+    }
+
+    /// Returns a new URL made by appending a path component to `self`.
+    ///
+    /// If `is_directory` is `true`, the URL's path ends with a slash.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1413201-urlbyappendingpathcomponent).
+    #[doc(alias = "URLByAppendingPathComponent")]
+    #[doc(alias = "URLByAppendingPathComponent:isDirectory:")]
+    pub fn appending_path_component(&self, component: &str, is_directory: bool) -> Arc<Self> {
+        let component = NSString::from_str(component);
+        let is_directory = BOOL::from(is_directory);
+        let url = unsafe {
+            retain_autoreleased(|| {
+                _msg_send_any![
+                    self,
+                    URLByAppendingPathComponent: &*component
+                    isDirectory: is_directory
+                    => *const Self
+                ]
+            })
+        };
+        url.expect("Failed to append path component")
+    }
+}
+
+/// Accessing URL parts.
+impl NSURL {
+    /// Returns the URL string, or `None` if it cannot be formed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1409868-absolutestring).
+    #[inline]
+    #[doc(alias = "absoluteString")]
+    pub fn absolute_string(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, absoluteString]) }
+    }
+
+    /// Returns the scheme, such as `https` or `file`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1413437-scheme).
+    #[inline]
+    pub fn scheme(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, scheme]) }
+    }
+
+    /// Returns the host.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1413640-host).
+    #[inline]
+    pub fn host(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, host]) }
+    }
+
+    /// Returns the port number, if specified.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1414899-port).
+    #[inline]
+    pub fn port(&self) -> Option<Arc<NSNumber>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, port]) }
+    }
+
+    /// Returns the percent-decoded path.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1408809-path).
+    #[inline]
+    pub fn path(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, path]) }
+    }
+
+    /// Returns the last component of the path.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1417444-lastpathcomponent).
+    #[inline]
+    #[doc(alias = "lastPathComponent")]
+    pub fn last_path_component(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, lastPathComponent]) }
+    }
+
+    /// Returns the extension of the path, or an empty string if there is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1411048-pathextension).
+    #[inline]
+    #[doc(alias = "pathExtension")]
+    pub fn path_extension(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, pathExtension]) }
+    }
+
+    /// Returns the percent-encoded query, without the leading `?`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1407543-query).
+    #[inline]
+    pub fn query(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, query]) }
+    }
+
+    /// Returns the percent-encoded fragment, without the leading `#`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1413775-fragment).
+    #[inline]
+    pub fn fragment(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, fragment]) }
+    }
+
+    /// Returns `true` if the scheme is `file`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1408782-isfileurl).
+    #[inline]
+    #[doc(alias = "isFileURL")]
+    pub fn is_file_url(&self) -> bool {
+        unsafe { _msg_send_any![self, isFileURL => BOOL] }.into()
+    }
+
+    /// Returns the file system path of a file URL, or `None` if `self` is not
+    /// a file URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1414306-filesystemrepresentation).
+    #[doc(alias = "fileSystemRepresentation")]
+    pub fn to_path_buf(&self) -> Option<PathBuf> {
+        if !self.is_file_url() {
+            return None;
+        }
+
+        // The C string is autoreleased, so it must be copied within the pool.
+        crate::objc::autoreleasepool(|_| unsafe {
+            let path: *const c_char = _msg_send_any![self, fileSystemRepresentation];
+            if path.is_null() {
+                return None;
+            }
+            let path = OsStr::from_bytes(CStr::from_ptr(path).to_bytes());
+            Some(PathBuf::from(path))
+        })
+    }
+}
+
+/// Accessing resource values.
+impl NSURL {
+    /// Returns the value of the resource property `key` for a file URL, or
+    /// `None` if the resource has no such value.
+    ///
+    /// Values prefetched by
+    /// [`NSFileManager::enumerator_at_url`](struct.NSFileManager.html#method.enumerator_at_url)
+    /// are returned without accessing the file system.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurl/1408874-getresourcevalue).
+    #[doc(alias = "getResourceValue")]
+    #[doc(alias = "getResourceValue:forKey:error:")]
+    pub fn resource_value(
+        &self,
+        key: &NSURLResourceKey,
+    ) -> Result<Option<Arc<NSObject<'static>>>, Arc<NSError<'static>>> {
+        unsafe {
+            with_error_out(|error| {
+                let mut value: *const NSObject<'static> = ptr::null();
+                let value_ptr: *mut *const NSObject<'static> = &mut value;
+                let ok: bool =
+                    _msg_send_any![self, getResourceValue: value_ptr forKey: key error: error => BOOL]
+                        .into();
+                if ok {
+                    Some(value.as_ref().map(Arc::retain))
+                } else {
+                    None
+                }
+            })
+        }
+    }
+}
+
+/// Percent-encodes bytes that are not allowed anywhere in a URL, leaving
+/// existing escapes and delimiters as-is.
+fn percent_encode_invalid(string: &str) -> String {
+    percent_encode(string, b"-._~:/?#[]@!$&'()*+,;=%")
+}
+
+/// Percent-encodes all bytes of `string` other than ASCII alphanumerics and
+/// `allowed`.
+pub(super) fn percent_encode(string: &str, allowed: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut encoded = String::with_capacity(string.len());
+    for &byte in string.as_bytes() {
+        if byte.is_ascii_alphanumeric() || allowed.contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[usize::from(byte >> 4)] as char);
+            encoded.push(HEX[usize::from(byte & 0xF)] as char);
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        let url = NSURL::from_str("https://user@example.com:8080/a/b.tar.gz?x=1&y=2#frag").unwrap();
+
+        assert_eq!(*url.scheme().unwrap(), *"https");
+        assert_eq!(*url.host().unwrap(), *"example.com");
+        assert_eq!(url.port().unwrap().as_i64(), Some(8080));
+        assert_eq!(*url.path().unwrap(), *"/a/b.tar.gz");
+        assert_eq!(*url.last_path_component().unwrap(), *"b.tar.gz");
+        assert_eq!(*url.path_extension().unwrap(), *"gz");
+        assert_eq!(*url.query().unwrap(), *"x=1&y=2");
+        assert_eq!(*url.fragment().unwrap(), *"frag");
+        assert!(!url.is_file_url());
+        assert_eq!(url.to_path_buf(), None);
+
+        let url = NSURL::from_str("https://example.com").unwrap();
+        assert!(url.port().is_none());
+        assert!(url.query().is_none());
+        assert!(url.fragment().is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(NSURL::from_str("").is_none());
+        assert!(NSURL::from_str("https://example.com/a b").is_none());
+
+        let url = NSURL::from_str_encoding_invalid_characters("https://example.com/a b").unwrap();
+        assert_eq!(
+            *url.absolute_string().unwrap(),
+            *"https://example.com/a%20b"
+        );
+        assert_eq!(*url.path().unwrap(), *"/a b");
+    }
+
+    #[test]
+    fn file_url_with_spaces() {
+        let path = Path::new("/tmp/with spaces/file name.txt");
+        let url = NSURL::file_url(path, false);
+
+        assert!(url.is_file_url());
+        assert_eq!(
+            *url.absolute_string().unwrap(),
+            *"file:///tmp/with%20spaces/file%20name.txt"
+        );
+        assert_eq!(url.to_path_buf().as_deref(), Some(path));
+
+        let parsed = NSURL::from_str("file:///tmp/with%20spaces/file%20name.txt").unwrap();
+        assert_eq!(parsed.to_path_buf().as_deref(), Some(path));
+        assert_eq!(*parsed, *url);
+    }
+
+    #[test]
+    fn file_url_non_ascii() {
+        let path = Path::new("/tmp/日本語/🦀.rs");
+        let url = NSURL::file_url(path, false);
+
+        assert_eq!(*url.last_path_component().unwrap(), *"🦀.rs");
+        assert_eq!(*url.path_extension().unwrap(), *"rs");
+        assert_eq!(url.to_path_buf().as_deref(), Some(path));
+    }
+
+    #[test]
+    fn appending() {
+        let dir = NSURL::file_url(Path::new("/tmp/dir"), true);
+        assert_eq!(*dir.absolute_string().unwrap(), *"file:///tmp/dir/");
+
+        let file = dir.appending_path_component("a b.txt", false);
+        assert_eq!(
+            file.to_path_buf().as_deref(),
+            Some(Path::new("/tmp/dir/a b.txt"))
+        );
+
+        let sub = dir.appending_path_component("sub", true);
+        assert_eq!(*sub.absolute_string().unwrap(), *"file:///tmp/dir/sub/");
+    }
+
+    #[test]
+    fn percent_encode() {
+        assert_eq!(percent_encode_invalid("a b%20c"), "a%20b%20c");
+        assert_eq!(percent_encode_invalid("é"), "%C3%A9");
+        assert_eq!(percent_encode_invalid("http://x/?q=1#f"), "http://x/?q=1#f");
+    }
+}
@@ -0,0 +1,386 @@
+use super::{ns_url::percent_encode, NSArray, NSNumber, NSString, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, Sel, BOOL};
+use std::fmt;
+
+objc_subclass! {
+    /// A single name/value pair in the query of a URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlqueryitem).
+    #[derive(PartialEq)]
+    pub class NSURLQueryItem: NSObject<'static>;
+}
+
+impl fmt::Debug for NSURLQueryItem {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NSURLQueryItem")
+            .field("name", &self.name())
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+impl NSURLQueryItem {
+    /// Creates a query item with a name and optional value.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlqueryitem/1407788-initwithname).
+    #[doc(alias = "queryItemWithName")]
+    #[doc(alias = "queryItemWithName:value:")]
+    #[doc(alias = "initWithName:value:")]
+    pub fn new(name: &NSString, value: Option<&NSString>) -> Arc<Self> {
+        let item: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSURLQueryItem>,
+                sel: Sel,
+                name: &NSString,
+                value: Option<&NSString>,
+            ) -> Arc<NSURLQueryItem>;
+        }
+
+        let sel = selector!(initWithName:value:);
+        unsafe { objc_msgSend(item, sel, name, value) }
+    }
+
+    /// Returns the name of the query item.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlqueryitem/1413219-name).
+    #[inline]
+    pub fn name(&self) -> Arc<NSString<'static>> {
+        let name = unsafe { retain_autoreleased(|| _msg_send_any![self, name]) };
+        name.expect("Query item has no name")
+    }
+
+    /// Returns the value of the query item, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlqueryitem/1411693-value).
+    #[inline]
+    pub fn value(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, value]) }
+    }
+}
+
+objc_subclass! {
+    /// An object that parses URLs into and constructs URLs from their
+    /// constituent parts.
+    ///
+    /// Getters return decoded values, and setters percent-encode their input
+    /// as needed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents).
+    #[derive(PartialEq)]
+    pub class NSURLComponents: NSObject<'static>;
+}
+
+impl fmt::Debug for NSURLComponents {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let string: Option<Arc<NSString>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, string]) };
+        f.debug_tuple("NSURLComponents").field(&string).finish()
+    }
+}
+
+impl NSURLComponents {
+    /// Creates an empty set of URL components.
+    #[inline]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Parses `url` into its components, or returns `None` if it is malformed.
+    ///
+    /// If `resolving_against_base` is `true` and `url` is relative, it is
+    /// first resolved against its base URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1413018-initwithurl).
+    #[doc(alias = "componentsWithURL")]
+    #[doc(alias = "componentsWithURL:resolvingAgainstBaseURL:")]
+    #[doc(alias = "initWithURL:resolvingAgainstBaseURL:")]
+    pub fn from_url(url: &NSURL, resolving_against_base: bool) -> Option<Arc<Self>> {
+        let components: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSURLComponents>,
+                sel: Sel,
+                url: &NSURL,
+                resolve: BOOL,
+            ) -> Option<Arc<NSURLComponents>>;
+        }
+
+        let sel = selector!(initWithURL:resolvingAgainstBaseURL:);
+        unsafe { objc_msgSend(components, sel, url, resolving_against_base.into()) }
+    }
+
+    /// Returns a URL created from the components, or `None` if they do not
+    /// form a valid URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1412505-url).
+    #[inline]
+    #[doc(alias = "URL")]
+    pub fn url(&self) -> Option<Arc<NSURL>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, URL]) }
+    }
+
+    /// Returns the scheme, such as `https`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1411316-scheme).
+    #[inline]
+    pub fn scheme(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, scheme]) }
+    }
+
+    /// Sets the scheme.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1411316-scheme).
+    ///
+    /// # Exception Handling
+    ///
+    /// If the scheme contains invalid characters, an
+    /// `NSInvalidArgumentException` is thrown.
+    #[inline]
+    #[doc(alias = "setScheme")]
+    #[doc(alias = "setScheme:")]
+    pub fn set_scheme(&mut self, scheme: Option<&str>) {
+        let scheme = scheme.map(NSString::from_str);
+        unsafe { _msg_send_any![self, setScheme: scheme.as_deref()] }
+    }
+
+    /// Returns the decoded host.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1409746-host).
+    #[inline]
+    pub fn host(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, host]) }
+    }
+
+    /// Sets the host, percent-encoding it as needed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1409746-host).
+    #[inline]
+    #[doc(alias = "setHost")]
+    #[doc(alias = "setHost:")]
+    pub fn set_host(&mut self, host: Option<&str>) {
+        let host = host.map(NSString::from_str);
+        unsafe { _msg_send_any![self, setHost: host.as_deref()] }
+    }
+
+    /// Returns the port number.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1410908-port).
+    #[inline]
+    pub fn port(&self) -> Option<Arc<NSNumber>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, port]) }
+    }
+
+    /// Sets the port number.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1410908-port).
+    ///
+    /// # Exception Handling
+    ///
+    /// If the port is negative, an `NSInvalidArgumentException` is thrown.
+    #[inline]
+    #[doc(alias = "setPort")]
+    #[doc(alias = "setPort:")]
+    pub fn set_port(&mut self, port: Option<&NSNumber>) {
+        unsafe { _msg_send_any![self, setPort: port] }
+    }
+
+    /// Returns the decoded path.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1408161-path).
+    #[inline]
+    pub fn path(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, path]) }
+    }
+
+    /// Sets the path, percent-encoding it as needed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1408161-path).
+    #[inline]
+    #[doc(alias = "setPath")]
+    #[doc(alias = "setPath:")]
+    pub fn set_path(&mut self, path: Option<&str>) {
+        let path = path.map(NSString::from_str);
+        unsafe { _msg_send_any![self, setPath: path.as_deref()] }
+    }
+
+    /// Returns the decoded fragment.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1410552-fragment).
+    #[inline]
+    pub fn fragment(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, fragment]) }
+    }
+
+    /// Sets the fragment, percent-encoding it as needed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1410552-fragment).
+    #[inline]
+    #[doc(alias = "setFragment")]
+    #[doc(alias = "setFragment:")]
+    pub fn set_fragment(&mut self, fragment: Option<&str>) {
+        let fragment = fragment.map(NSString::from_str);
+        unsafe { _msg_send_any![self, setFragment: fragment.as_deref()] }
+    }
+
+    /// Returns the decoded query items, or an empty vector if there is no
+    /// query.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/1407752-queryitems).
+    #[doc(alias = "queryItems")]
+    pub fn query_items(&self) -> Vec<(String, Option<String>)> {
+        let items: Option<Arc<NSArray<NSURLQueryItem>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, queryItems]) };
+
+        let items = match items {
+            Some(items) => items,
+            None => return Vec::new(),
+        };
+
+        items
+            .iter()
+            .map(|item| {
+                (
+                    item.name().to_string(),
+                    item.value().map(|value| value.to_string()),
+                )
+            })
+            .collect()
+    }
+
+    /// Sets the query from name/value pairs, replacing any existing query.
+    ///
+    /// Unlike `-[NSURLComponents setQueryItems:]`, this also percent-encodes
+    /// `&`, `=`, and `+` so that they survive a round trip through
+    /// [`query_items`](#method.query_items). Spaces are encoded as `%20`, not
+    /// `+`.
+    ///
+    /// An empty slice removes the query entirely.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlcomponents/2901314-percentencodedqueryitems).
+    #[doc(alias = "setQueryItems")]
+    #[doc(alias = "setQueryItems:")]
+    #[doc(alias = "setPercentEncodedQueryItems")]
+    #[doc(alias = "setPercentEncodedQueryItems:")]
+    pub fn set_query_items(&mut self, items: &[(&str, Option<&str>)]) {
+        if items.is_empty() {
+            let query: Option<&NSString> = None;
+            unsafe { _msg_send_any![self, setPercentEncodedQuery: query] }
+            return;
+        }
+
+        let items: Vec<Arc<NSURLQueryItem>> = items
+            .iter()
+            .map(|&(name, value)| {
+                let name = NSString::from_str(&percent_encode_query(name));
+                let value = value.map(|value| NSString::from_str(&percent_encode_query(value)));
+                NSURLQueryItem::new(&name, value.as_deref())
+            })
+            .collect();
+
+        let items: Vec<&NSURLQueryItem> = items.iter().map(|item| &**item).collect();
+        let items = NSArray::from_slice(&items);
+        unsafe { _msg_send_any![self, setPercentEncodedQueryItems: &*items] }
+    }
+}
+
+/// Percent-encodes a query item name or value.
+///
+/// This allows the same characters as `URLQueryAllowedCharacterSet`, except for
+/// the `&`, `=`, and `+` delimiters.
+fn percent_encode_query(string: &str) -> String {
+    percent_encode(string, b"-._~!$'()*,;:@/?")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let mut components = NSURLComponents::new();
+        let c = Arc::get_mut(&mut components).unwrap();
+
+        c.set_scheme(Some("https"));
+        c.set_host(Some("example.com"));
+        c.set_port(Some(&Arc::<NSNumber>::from(8080i64)));
+        c.set_path(Some("/a b"));
+        c.set_fragment(Some("top"));
+
+        assert_eq!(*c.scheme().unwrap(), *"https");
+        assert_eq!(*c.host().unwrap(), *"example.com");
+        assert_eq!(c.port().unwrap().as_i64(), Some(8080));
+        assert_eq!(*c.path().unwrap(), *"/a b");
+        assert_eq!(*c.fragment().unwrap(), *"top");
+
+        let url = c.url().unwrap();
+        assert_eq!(
+            *url.absolute_string().unwrap(),
+            *"https://example.com:8080/a%20b#top"
+        );
+
+        c.set_port(None);
+        c.set_fragment(None);
+        assert!(c.port().is_none());
+        assert!(c.fragment().is_none());
+    }
+
+    #[test]
+    fn query_round_trip() {
+        let items: &[(&str, Option<&str>)] = &[
+            ("q", Some("a&b=c")),
+            ("space", Some("x y+z")),
+            ("unicode", Some("日本語 🦀")),
+            ("flag", None),
+            ("k&=", Some("")),
+        ];
+
+        let mut components = NSURLComponents::new();
+        let c = Arc::get_mut(&mut components).unwrap();
+        c.set_scheme(Some("https"));
+        c.set_host(Some("example.com"));
+        c.set_query_items(items);
+
+        let url = c.url().unwrap();
+        let string = url.absolute_string().unwrap().to_string();
+        assert!(string.contains("q=a%26b%3Dc"), "{}", string);
+        assert!(string.contains("space=x%20y%2Bz"), "{}", string);
+
+        let parsed = NSURLComponents::from_url(&url, false).unwrap();
+        let expected: Vec<(String, Option<String>)> = items
+            .iter()
+            .map(|&(name, value)| (name.to_owned(), value.map(str::to_owned)))
+            .collect();
+        assert_eq!(parsed.query_items(), expected);
+
+        c.set_query_items(&[]);
+        assert!(c.query_items().is_empty());
+        assert_eq!(
+            *c.url().unwrap().absolute_string().unwrap(),
+            *"https://example.com"
+        );
+    }
+
+    #[test]
+    fn from_url() {
+        let url = NSURL::from_str("https://example.com/path?a=1&b#frag").unwrap();
+        let components = NSURLComponents::from_url(&url, true).unwrap();
+
+        assert_eq!(*components.path().unwrap(), *"/path");
+        assert_eq!(
+            components.query_items(),
+            vec![
+                ("a".to_owned(), Some("1".to_owned())),
+                ("b".to_owned(), None)
+            ]
+        );
+        assert_eq!(components.url().as_deref(), Some(&*url));
+    }
+}
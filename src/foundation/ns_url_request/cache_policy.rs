@@ -0,0 +1,46 @@
+use crate::objc::NSUInteger;
+
+/// How a [`NSURLRequest`](struct.NSURLRequest.html) uses cached responses.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequestcachepolicy).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSURLRequestCachePolicy {
+    /// Use the caching logic of the protocol, such as HTTP.
+    UseProtocolCachePolicy = 0,
+    /// Load from the origin without using cached data.
+    ReloadIgnoringLocalCacheData = 1,
+    /// Ignore local cached data, and instruct proxies and intermediate caches
+    /// to do the same.
+    ReloadIgnoringLocalAndRemoteCacheData = 4,
+    /// Use cached data regardless of its age, or load from the origin if there
+    /// is none.
+    ReturnCacheDataElseLoad = 2,
+    /// Use cached data regardless of its age, or fail if there is none.
+    ReturnCacheDataDontLoad = 3,
+    /// Use cached data if the origin validates it, or load from the origin.
+    ReloadRevalidatingCacheData = 5,
+}
+
+impl Default for NSURLRequestCachePolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::UseProtocolCachePolicy
+    }
+}
+
+impl NSURLRequestCachePolicy {
+    /// Returns the policy for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSUInteger) -> Option<Self> {
+        match value {
+            0 => Some(Self::UseProtocolCachePolicy),
+            1 => Some(Self::ReloadIgnoringLocalCacheData),
+            4 => Some(Self::ReloadIgnoringLocalAndRemoteCacheData),
+            2 => Some(Self::ReturnCacheDataElseLoad),
+            3 => Some(Self::ReturnCacheDataDontLoad),
+            5 => Some(Self::ReloadRevalidatingCacheData),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use super::{NSData, NSDictionary, NSString, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger};
+use std::{collections::HashMap, time::Duration};
+
+mod cache_policy;
+mod mutable;
+mod network_service_type;
+
+pub use cache_policy::NSURLRequestCachePolicy;
+pub use mutable::NSMutableURLRequest;
+pub use network_service_type::NSURLRequestNetworkServiceType;
+
+objc_subclass! {
+    /// An immutable URL load request.
+    ///
+    /// Use [`NSMutableURLRequest`](struct.NSMutableURLRequest.html) to set the
+    /// method, headers, and body.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest).
+    #[derive(PartialEq)]
+    pub class NSURLRequest: NSObject<'static>;
+}
+
+impl NSURLRequest {
+    /// Creates a `GET` request for `url` with the default cache policy and
+    /// timeout.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/requestwithurl).
+    #[inline]
+    #[doc(alias = "requestWithURL")]
+    #[doc(alias = "requestWithURL:")]
+    pub fn with_url(url: &NSURL) -> Arc<Self> {
+        let request =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), requestWithURL: url]) };
+        request.expect("Failed to create request")
+    }
+
+    /// Returns the URL of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/url).
+    #[inline]
+    #[doc(alias = "URL")]
+    pub fn url(&self) -> Option<Arc<NSURL>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, URL]) }
+    }
+
+    /// Returns the HTTP method of `self`, which is `"GET"` by default.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/httpmethod).
+    #[inline]
+    #[doc(alias = "HTTPMethod")]
+    pub fn http_method(&self) -> Arc<NSString<'static>> {
+        let method = unsafe { retain_autoreleased(|| _msg_send_any![self, HTTPMethod]) };
+        method.unwrap_or_else(|| NSString::from_str("GET"))
+    }
+
+    /// Returns the body of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/httpbody).
+    #[inline]
+    #[doc(alias = "HTTPBody")]
+    pub fn http_body(&self) -> Option<Arc<NSData>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, HTTPBody]) }
+    }
+
+    /// Returns the value of the header `field`, whose name is
+    /// case-insensitive.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/valueforhttpheaderfield).
+    #[inline]
+    #[doc(alias = "valueForHTTPHeaderField")]
+    #[doc(alias = "valueForHTTPHeaderField:")]
+    pub fn value_for_header(&self, field: &str) -> Option<Arc<NSString<'static>>> {
+        let field = NSString::from_str(field);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, valueForHTTPHeaderField: &*field]) }
+    }
+
+    /// Returns all header fields of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/allhttpheaderfields).
+    #[doc(alias = "allHTTPHeaderFields")]
+    pub fn all_http_header_fields(&self) -> HashMap<String, String> {
+        let fields: Option<Arc<NSDictionary<NSString, NSString>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, allHTTPHeaderFields]) };
+
+        fields.map_or_else(HashMap::new, |fields| {
+            fields
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .collect()
+        })
+    }
+
+    /// Returns how long `self` waits for more data before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/timeoutinterval).
+    #[inline]
+    #[doc(alias = "timeoutInterval")]
+    pub fn timeout(&self) -> Duration {
+        let interval: f64 = unsafe { _msg_send_any![self, timeoutInterval] };
+        Duration::from_secs_f64(interval.max(0.0))
+    }
+
+    /// Returns how `self` uses cached responses.
+    ///
+    /// Values unknown to this crate are returned as the default.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/cachepolicy).
+    #[inline]
+    #[doc(alias = "cachePolicy")]
+    pub fn cache_policy(&self) -> NSURLRequestCachePolicy {
+        let policy: NSUInteger = unsafe { _msg_send_any![self, cachePolicy] };
+        NSURLRequestCachePolicy::from_raw(policy).unwrap_or_default()
+    }
+
+    /// Returns the kind of traffic of `self`.
+    ///
+    /// Values unknown to this crate are returned as the default.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/networkservicetype).
+    #[inline]
+    #[doc(alias = "networkServiceType")]
+    pub fn network_service_type(&self) -> NSURLRequestNetworkServiceType {
+        let service_type: NSUInteger = unsafe { _msg_send_any![self, networkServiceType] };
+        NSURLRequestNetworkServiceType::from_raw(service_type).unwrap_or_default()
+    }
+
+    /// Returns a mutable copy of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/mutablecopy).
+    #[inline]
+    #[doc(alias = "mutableCopy")]
+    pub fn to_mutable(&self) -> Arc<NSMutableURLRequest> {
+        unsafe { _msg_send_any![self, mutableCopy] }
+    }
+}
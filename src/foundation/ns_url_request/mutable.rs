@@ -0,0 +1,206 @@
+use super::{NSURLRequest, NSURLRequestCachePolicy, NSURLRequestNetworkServiceType};
+use crate::core::Arc;
+use crate::foundation::{NSData, NSString, NSURL};
+use crate::objc::{retain_autoreleased, ClassType, NSUInteger};
+use std::time::Duration;
+
+objc_subclass! {
+    /// A mutable URL load request.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest).
+    #[derive(PartialEq)]
+    pub class NSMutableURLRequest: NSURLRequest;
+}
+
+impl NSMutableURLRequest {
+    /// Creates a `GET` request for `url` with the default cache policy and
+    /// timeout.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequest/requestwithurl).
+    #[inline]
+    #[doc(alias = "requestWithURL")]
+    #[doc(alias = "requestWithURL:")]
+    pub fn with_url(url: &NSURL) -> Arc<Self> {
+        let request =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), requestWithURL: url]) };
+        request.expect("Failed to create request")
+    }
+
+    /// Sets the URL of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/url).
+    #[inline]
+    #[doc(alias = "setURL")]
+    #[doc(alias = "setURL:")]
+    pub fn set_url(&mut self, url: &NSURL) {
+        unsafe { _msg_send_any![self, setURL: url => ()] }
+    }
+
+    /// Sets the HTTP method of `self`, such as `"POST"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/httpmethod).
+    #[inline]
+    #[doc(alias = "setHTTPMethod")]
+    #[doc(alias = "setHTTPMethod:")]
+    pub fn set_http_method(&mut self, method: &str) {
+        let method = NSString::from_str(method);
+        unsafe { _msg_send_any![self, setHTTPMethod: &*method => ()] }
+    }
+
+    /// Sets the body of `self`, or removes it if `None`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/httpbody).
+    #[inline]
+    #[doc(alias = "setHTTPBody")]
+    #[doc(alias = "setHTTPBody:")]
+    pub fn set_http_body(&mut self, body: Option<&NSData>) {
+        unsafe { _msg_send_any![self, setHTTPBody: body => ()] }
+    }
+
+    /// Sets the value of the header `field`, or removes it if `None`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/setvalue).
+    #[doc(alias = "setValue:forHTTPHeaderField:")]
+    pub fn set_value_for_header(&mut self, value: Option<&str>, field: &str) {
+        let value = value.map(NSString::from_str);
+        let value = value.as_deref();
+        let field = NSString::from_str(field);
+        unsafe { _msg_send_any![self, setValue: value forHTTPHeaderField: &*field => ()] }
+    }
+
+    /// Appends `value` to the header `field`, separated from any existing
+    /// value by a comma.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/addvalue).
+    #[doc(alias = "addValue:forHTTPHeaderField:")]
+    pub fn add_value_for_header(&mut self, value: &str, field: &str) {
+        let value = NSString::from_str(value);
+        let field = NSString::from_str(field);
+        unsafe { _msg_send_any![self, addValue: &*value forHTTPHeaderField: &*field => ()] }
+    }
+
+    /// Sets how long `self` waits for more data before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/timeoutinterval).
+    #[inline]
+    #[doc(alias = "setTimeoutInterval")]
+    #[doc(alias = "setTimeoutInterval:")]
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        let interval = timeout.as_secs_f64();
+        unsafe { _msg_send_any![self, setTimeoutInterval: interval => ()] }
+    }
+
+    /// Sets how `self` uses cached responses.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/cachepolicy).
+    #[inline]
+    #[doc(alias = "setCachePolicy")]
+    #[doc(alias = "setCachePolicy:")]
+    pub fn set_cache_policy(&mut self, policy: NSURLRequestCachePolicy) {
+        let policy = policy as NSUInteger;
+        unsafe { _msg_send_any![self, setCachePolicy: policy => ()] }
+    }
+
+    /// Sets the kind of traffic of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsmutableurlrequest/networkservicetype).
+    #[inline]
+    #[doc(alias = "setNetworkServiceType")]
+    #[doc(alias = "setNetworkServiceType:")]
+    pub fn set_network_service_type(&mut self, service_type: NSURLRequestNetworkServiceType) {
+        let service_type = service_type as NSUInteger;
+        unsafe { _msg_send_any![self, setNetworkServiceType: service_type => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url() -> Arc<NSURL> {
+        NSURL::from_str("https://example.com/upload").unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        let url = url();
+        let request = NSURLRequest::with_url(&url);
+
+        assert_eq!(*request.url().unwrap(), *url);
+        assert_eq!(*request.http_method(), *"GET");
+        assert!(request.http_body().is_none());
+        assert!(request.all_http_header_fields().is_empty());
+        assert_eq!(request.timeout(), Duration::from_secs(60));
+        assert_eq!(
+            request.cache_policy(),
+            NSURLRequestCachePolicy::UseProtocolCachePolicy
+        );
+        assert_eq!(
+            request.network_service_type(),
+            NSURLRequestNetworkServiceType::Default
+        );
+    }
+
+    #[test]
+    fn mutate() {
+        let mut request = NSMutableURLRequest::with_url(&url());
+        let request = Arc::get_mut(&mut request).unwrap();
+        let body = NSData::from_slice(b"{}");
+
+        request.set_http_method("POST");
+        request.set_http_body(Some(&body));
+        request.set_timeout(Duration::from_millis(2500));
+        request.set_cache_policy(NSURLRequestCachePolicy::ReloadIgnoringLocalCacheData);
+        request.set_network_service_type(NSURLRequestNetworkServiceType::ResponsiveData);
+
+        assert_eq!(*request.http_method(), *"POST");
+        assert_eq!(request.http_body().unwrap().as_slice(), b"{}");
+        assert_eq!(request.timeout(), Duration::from_millis(2500));
+        assert_eq!(
+            request.cache_policy(),
+            NSURLRequestCachePolicy::ReloadIgnoringLocalCacheData
+        );
+        assert_eq!(
+            request.network_service_type(),
+            NSURLRequestNetworkServiceType::ResponsiveData
+        );
+
+        request.set_http_body(None);
+        assert!(request.http_body().is_none());
+
+        // Copies are independent.
+        let copy = request.to_mutable();
+        request.set_http_method("PUT");
+        assert_eq!(*copy.http_method(), *"POST");
+    }
+
+    #[test]
+    fn headers() {
+        let mut request = NSMutableURLRequest::with_url(&url());
+        let request = Arc::get_mut(&mut request).unwrap();
+
+        request.set_value_for_header(Some("application/json"), "content-type");
+        request.add_value_for_header("text/plain", "Accept");
+        request.add_value_for_header("text/html", "ACCEPT");
+
+        // Field names are case-insensitive.
+        let content_type = request.value_for_header("Content-Type").unwrap();
+        assert_eq!(*content_type, *"application/json");
+
+        let accept = request.value_for_header("accept").unwrap().to_string();
+        assert!(accept.starts_with("text/plain,"));
+        assert!(accept.ends_with("text/html"));
+
+        let fields = request.all_http_header_fields();
+        assert_eq!(fields.len(), 2);
+        let content_type = fields
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str());
+        assert_eq!(content_type, Some("application/json"));
+
+        request.set_value_for_header(None, "Content-Type");
+        assert!(request.value_for_header("content-type").is_none());
+        assert_eq!(request.all_http_header_fields().len(), 1);
+    }
+}
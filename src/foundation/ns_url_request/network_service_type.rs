@@ -0,0 +1,55 @@
+use crate::objc::NSUInteger;
+
+/// The kind of traffic of a [`NSURLRequest`](struct.NSURLRequest.html),
+/// which the system uses to prioritize it.
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsurlrequestnetworkservicetype).
+#[repr(usize)] // NSUInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSURLRequestNetworkServiceType {
+    /// Standard network traffic.
+    Default = 0,
+    /// Voice over IP control traffic, which Apple deprecated in favor of
+    /// PushKit.
+    VoIP = 1,
+    /// Video traffic.
+    Video = 2,
+    /// Background traffic.
+    Background = 3,
+    /// Voice data.
+    Voice = 4,
+    /// Data that the user is actively waiting for.
+    ResponsiveData = 6,
+    /// Streaming audio or video.
+    AVStreaming = 8,
+    /// Interactive audio or video.
+    ResponsiveAV = 9,
+    /// Call signaling.
+    CallSignaling = 11,
+}
+
+impl Default for NSURLRequestNetworkServiceType {
+    #[inline]
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl NSURLRequestNetworkServiceType {
+    /// Returns the service type for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSUInteger) -> Option<Self> {
+        match value {
+            0 => Some(Self::Default),
+            1 => Some(Self::VoIP),
+            2 => Some(Self::Video),
+            3 => Some(Self::Background),
+            4 => Some(Self::Voice),
+            6 => Some(Self::ResponsiveData),
+            8 => Some(Self::AVStreaming),
+            9 => Some(Self::ResponsiveAV),
+            11 => Some(Self::CallSignaling),
+            _ => None,
+        }
+    }
+}
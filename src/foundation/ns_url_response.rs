@@ -0,0 +1,163 @@
+use super::{NSDictionary, NSString, NSURL};
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, NSObject};
+use std::{collections::HashMap, fmt};
+
+objc_subclass! {
+    /// The metadata of a response to a URL load request.
+    ///
+    /// Responses to HTTP and HTTPS requests are instances of
+    /// [`NSHTTPURLResponse`](struct.NSHTTPURLResponse.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse).
+    #[derive(PartialEq)]
+    pub class NSURLResponse: NSObject<'static>;
+}
+
+objc_subclass! {
+    /// The metadata of a response to an HTTP or HTTPS request.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nshttpurlresponse).
+    #[derive(PartialEq)]
+    pub class NSHTTPURLResponse: NSURLResponse;
+}
+
+impl fmt::Debug for NSURLResponse {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NSURLResponse")
+            .field("url", &self.url())
+            .field("mime_type", &self.mime_type())
+            .finish()
+    }
+}
+
+impl fmt::Debug for NSHTTPURLResponse {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NSHTTPURLResponse")
+            .field("url", &self.url())
+            .field("status_code", &self.status_code())
+            .finish()
+    }
+}
+
+impl NSURLResponse {
+    /// Returns the URL of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse/url).
+    #[inline]
+    #[doc(alias = "URL")]
+    pub fn url(&self) -> Option<Arc<NSURL>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, URL]) }
+    }
+
+    /// Returns the MIME type of `self`, such as `"text/html"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse/mimetype).
+    #[inline]
+    #[doc(alias = "MIMEType")]
+    pub fn mime_type(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, MIMEType]) }
+    }
+
+    /// Returns the length of the body of `self`, or `None` if it is unknown.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse/expectedcontentlength).
+    #[inline]
+    #[doc(alias = "expectedContentLength")]
+    pub fn expected_content_length(&self) -> Option<u64> {
+        // `NSURLResponseUnknownLength` is -1.
+        let length: i64 = unsafe { _msg_send_any![self, expectedContentLength] };
+        if length < 0 {
+            None
+        } else {
+            Some(length as u64)
+        }
+    }
+
+    /// Returns the name of the text encoding of `self`, such as `"utf-8"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse/textencodingname).
+    #[inline]
+    #[doc(alias = "textEncodingName")]
+    pub fn text_encoding_name(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, textEncodingName]) }
+    }
+
+    /// Returns a file name for saving the body of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlresponse/suggestedfilename).
+    #[inline]
+    #[doc(alias = "suggestedFilename")]
+    pub fn suggested_filename(&self) -> Option<Arc<NSString<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, suggestedFilename]) }
+    }
+
+    /// Returns `self` as an HTTP response, or `None` if it is not one.
+    #[inline]
+    pub fn as_http(&self) -> Option<&NSHTTPURLResponse> {
+        if self.is_kind_of_class(NSHTTPURLResponse::class()) {
+            Some(unsafe { &*(self as *const Self).cast() })
+        } else {
+            None
+        }
+    }
+}
+
+impl NSHTTPURLResponse {
+    /// Returns the HTTP status code of `self`, such as 200.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nshttpurlresponse/statuscode).
+    #[inline]
+    #[doc(alias = "statusCode")]
+    pub fn status_code(&self) -> NSInteger {
+        unsafe { _msg_send_any![self, statusCode] }
+    }
+
+    /// Returns all header fields of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nshttpurlresponse/allheaderfields).
+    #[doc(alias = "allHeaderFields")]
+    pub fn all_header_fields(&self) -> HashMap<String, String> {
+        let fields: Option<Arc<NSDictionary<NSString, NSString>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, allHeaderFields]) };
+
+        fields.map_or_else(HashMap::new, |fields| {
+            fields
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .collect()
+        })
+    }
+
+    /// Returns the value of the header `field`, whose name is
+    /// case-insensitive.
+    ///
+    /// This is available since macOS 10.15 and iOS 13.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nshttpurlresponse/valueforhttpheaderfield).
+    #[inline]
+    #[doc(alias = "valueForHTTPHeaderField")]
+    #[doc(alias = "valueForHTTPHeaderField:")]
+    pub fn value_for_header(&self, field: &str) -> Option<Arc<NSString<'static>>> {
+        let field = NSString::from_str(field);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, valueForHTTPHeaderField: &*field]) }
+    }
+
+    /// Returns a localized description of the HTTP status `code`, such as
+    /// `"not found"` for 404.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nshttpurlresponse/localizedstring).
+    #[inline]
+    #[doc(alias = "localizedStringForStatusCode")]
+    #[doc(alias = "localizedStringForStatusCode:")]
+    pub fn localized_string_for_status_code(code: NSInteger) -> Arc<NSString<'static>> {
+        let string = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), localizedStringForStatusCode: code],
+            )
+        };
+        string.expect("No status code string")
+    }
+}
@@ -0,0 +1,148 @@
+use super::NSURLSession;
+use crate::core::Arc;
+use crate::foundation::{
+    NSData, NSError, NSErrorDomain, NSURLRequest, NSURLResponse, URLErrorCode,
+};
+use std::{ffi::c_void, sync::Mutex};
+
+/// The result of a data task that loaded a response.
+pub(super) type DataResult = Result<(Arc<NSData>, Arc<NSURLResponse>), Arc<NSError<'static>>>;
+
+/// Converts the arguments of a data task's completion closure into a result.
+///
+/// The system always provides either a response or an error, but an error is
+/// made for the case of neither rather than trusting that.
+pub(super) fn data_result(
+    data: Option<Arc<NSData>>,
+    response: Option<Arc<NSURLResponse>>,
+    error: Option<Arc<NSError<'static>>>,
+) -> DataResult {
+    match (response, error) {
+        (_, Some(error)) => Err(error),
+        (Some(response), None) => {
+            let data = data.unwrap_or_else(|| NSData::from_slice(&[]));
+            Ok((data, response))
+        }
+        (None, None) => Err(no_result_error()),
+    }
+}
+
+/// Returns the error for a task that completed without a response or an
+/// error.
+pub(super) fn no_result_error() -> Arc<NSError<'static>> {
+    let code = URLErrorCode::BadServerResponse.code();
+    NSError::builder(NSErrorDomain::ns_url(), code)
+        .localized_description("The task completed without a response or an error.")
+        .build()
+}
+
+// Declared here because `foundation` does not require `dispatch`.
+#[allow(clashing_extern_declarations)]
+extern "C" {
+    fn dispatch_semaphore_create(value: isize) -> *mut c_void;
+    fn dispatch_semaphore_signal(semaphore: *mut c_void) -> isize;
+    fn dispatch_semaphore_wait(semaphore: *mut c_void, timeout: u64) -> isize;
+    fn dispatch_release(object: *mut c_void);
+}
+
+const DISPATCH_TIME_FOREVER: u64 = !0;
+
+/// A counting semaphore for waiting on a completion closure.
+struct Semaphore(*mut c_void);
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+impl Drop for Semaphore {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { dispatch_release(self.0) };
+    }
+}
+
+impl Semaphore {
+    #[inline]
+    fn new() -> Self {
+        let semaphore = unsafe { dispatch_semaphore_create(0) };
+        assert!(!semaphore.is_null(), "Failed to create semaphore");
+        Self(semaphore)
+    }
+
+    #[inline]
+    fn signal(&self) {
+        unsafe { dispatch_semaphore_signal(self.0) };
+    }
+
+    #[inline]
+    fn wait(&self) {
+        unsafe { dispatch_semaphore_wait(self.0, DISPATCH_TIME_FOREVER) };
+    }
+}
+
+impl NSURLSession {
+    /// Loads `request` and blocks the calling thread until it completes.
+    ///
+    /// Failures, including timeouts set by the request or the configuration
+    /// of `self`, are returned as errors such as
+    /// [`URLErrorCode::TimedOut`](enum.URLErrorCode.html#variant.TimedOut).
+    /// HTTP errors are responses rather than failures, and can be checked by
+    /// converting the response with
+    /// [`NSURLResponse::as_http`](struct.NSURLResponse.html#method.as_http).
+    ///
+    /// # Deadlocks
+    ///
+    /// This blocks forever if called from the
+    /// [`delegate_queue`](#method.delegate_queue) of `self`, which is where
+    /// the task completes.
+    pub fn data_blocking(
+        &self,
+        request: &NSURLRequest,
+    ) -> Result<(Arc<NSData>, Arc<NSURLResponse>), Arc<NSError<'static>>> {
+        struct Shared {
+            semaphore: Semaphore,
+            result: Mutex<Option<DataResult>>,
+        }
+
+        // Signals once the completion closure is called or dropped, so that
+        // a task that never completes cannot block forever.
+        struct Signal(std::sync::Arc<Shared>);
+
+        impl Drop for Signal {
+            fn drop(&mut self) {
+                self.0.semaphore.signal();
+            }
+        }
+
+        let shared = std::sync::Arc::new(Shared {
+            semaphore: Semaphore::new(),
+            result: Mutex::new(None),
+        });
+
+        let task = {
+            let signal = Signal(shared.clone());
+            self.data_task(request, move |data, response, error| {
+                let result = data_result(data, response, error);
+                if let Ok(mut slot) = signal.0.result.lock() {
+                    *slot = Some(result);
+                }
+            })
+        };
+        task.resume();
+        shared.semaphore.wait();
+
+        let result = shared.result.lock().ok().and_then(|mut slot| slot.take());
+        result.unwrap_or_else(|| Err(no_result_error()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neither_response_nor_error() {
+        let error = data_result(None, None, None).unwrap_err();
+        assert_eq!(error.url_code(), Some(URLErrorCode::BadServerResponse));
+        assert!(!error.localized_description().is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::core::Arc;
+use crate::foundation::{NSDictionary, NSString, NSURLRequestCachePolicy};
+use crate::objc::{retain_autoreleased, ClassType, NSObject, NSUInteger, BOOL};
+use std::{collections::HashMap, time::Duration};
+
+objc_subclass! {
+    /// The behavior of an [`NSURLSession`](struct.NSURLSession.html).
+    ///
+    /// A session copies its configuration when created, so changes made
+    /// afterward do not affect it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration).
+    #[derive(PartialEq)]
+    pub class NSURLSessionConfiguration: NSObject<'static>;
+}
+
+impl NSURLSessionConfiguration {
+    /// Returns a new configuration that stores caches, cookies, and
+    /// credentials on disk.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/default).
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    #[doc(alias = "defaultSessionConfiguration")]
+    pub fn default() -> Arc<Self> {
+        let configuration = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), defaultSessionConfiguration])
+        };
+        configuration.expect("Failed to create session configuration")
+    }
+
+    /// Returns a new configuration that keeps caches, cookies, and
+    /// credentials in memory only.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/ephemeral).
+    #[inline]
+    #[doc(alias = "ephemeralSessionConfiguration")]
+    pub fn ephemeral() -> Arc<Self> {
+        let configuration = unsafe {
+            retain_autoreleased(|| _msg_send_any![Self::class(), ephemeralSessionConfiguration])
+        };
+        configuration.expect("Failed to create session configuration")
+    }
+
+    /// Returns how long tasks wait for more data before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/timeoutintervalforrequest).
+    #[inline]
+    #[doc(alias = "timeoutIntervalForRequest")]
+    pub fn timeout_for_request(&self) -> Duration {
+        let interval: f64 = unsafe { _msg_send_any![self, timeoutIntervalForRequest] };
+        Duration::from_secs_f64(interval.max(0.0))
+    }
+
+    /// Sets how long tasks wait for more data before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/timeoutintervalforrequest).
+    #[inline]
+    #[doc(alias = "setTimeoutIntervalForRequest")]
+    #[doc(alias = "setTimeoutIntervalForRequest:")]
+    pub fn set_timeout_for_request(&mut self, timeout: Duration) {
+        let interval = timeout.as_secs_f64();
+        unsafe { _msg_send_any![self, setTimeoutIntervalForRequest: interval => ()] }
+    }
+
+    /// Returns how long tasks may take in total before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/timeoutintervalforresource).
+    #[inline]
+    #[doc(alias = "timeoutIntervalForResource")]
+    pub fn timeout_for_resource(&self) -> Duration {
+        let interval: f64 = unsafe { _msg_send_any![self, timeoutIntervalForResource] };
+        Duration::from_secs_f64(interval.max(0.0))
+    }
+
+    /// Sets how long tasks may take in total before timing out.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/timeoutintervalforresource).
+    #[inline]
+    #[doc(alias = "setTimeoutIntervalForResource")]
+    #[doc(alias = "setTimeoutIntervalForResource:")]
+    pub fn set_timeout_for_resource(&mut self, timeout: Duration) {
+        let interval = timeout.as_secs_f64();
+        unsafe { _msg_send_any![self, setTimeoutIntervalForResource: interval => ()] }
+    }
+
+    /// Returns the headers added to every request.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/httpadditionalheaders).
+    #[doc(alias = "HTTPAdditionalHeaders")]
+    pub fn http_additional_headers(&self) -> HashMap<String, String> {
+        let headers: Option<Arc<NSDictionary<NSString, NSString>>> =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, HTTPAdditionalHeaders]) };
+
+        headers.map_or_else(HashMap::new, |headers| {
+            headers
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .collect()
+        })
+    }
+
+    /// Sets the headers added to every request, replacing any previous ones.
+    ///
+    /// Headers set on a request take precedence over these.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/httpadditionalheaders).
+    #[doc(alias = "setHTTPAdditionalHeaders")]
+    #[doc(alias = "setHTTPAdditionalHeaders:")]
+    pub fn set_http_additional_headers(&mut self, headers: &[(&str, &str)]) {
+        let strings: Vec<(Arc<NSString>, Arc<NSString>)> = headers
+            .iter()
+            .map(|(field, value)| (NSString::from_str(field), NSString::from_str(value)))
+            .collect();
+        let pairs: Vec<(&NSString, &NSString)> = strings
+            .iter()
+            .map(|(field, value)| (&**field, &**value))
+            .collect();
+        let headers = NSDictionary::from_pairs(&pairs);
+
+        unsafe { _msg_send_any![self, setHTTPAdditionalHeaders: &*headers => ()] }
+    }
+
+    /// Returns how requests use cached responses.
+    ///
+    /// Values unknown to this crate are returned as the default.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/requestcachepolicy).
+    #[inline]
+    #[doc(alias = "requestCachePolicy")]
+    pub fn request_cache_policy(&self) -> NSURLRequestCachePolicy {
+        let policy: NSUInteger = unsafe { _msg_send_any![self, requestCachePolicy] };
+        NSURLRequestCachePolicy::from_raw(policy).unwrap_or_default()
+    }
+
+    /// Sets how requests use cached responses.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/requestcachepolicy).
+    #[inline]
+    #[doc(alias = "setRequestCachePolicy")]
+    #[doc(alias = "setRequestCachePolicy:")]
+    pub fn set_request_cache_policy(&mut self, policy: NSURLRequestCachePolicy) {
+        let policy = policy as NSUInteger;
+        unsafe { _msg_send_any![self, setRequestCachePolicy: policy => ()] }
+    }
+
+    /// Returns `true` if requests may use cellular networks.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/allowscellularaccess).
+    #[inline]
+    #[doc(alias = "allowsCellularAccess")]
+    pub fn allows_cellular_access(&self) -> bool {
+        unsafe { _msg_send_any![self, allowsCellularAccess => BOOL] }.into()
+    }
+
+    /// Sets whether requests may use cellular networks.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessionconfiguration/allowscellularaccess).
+    #[inline]
+    #[doc(alias = "setAllowsCellularAccess")]
+    #[doc(alias = "setAllowsCellularAccess:")]
+    pub fn set_allows_cellular_access(&mut self, allows: bool) {
+        let allows = BOOL::from(allows);
+        unsafe { _msg_send_any![self, setAllowsCellularAccess: allows => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        let configuration = NSURLSessionConfiguration::default();
+        assert_eq!(configuration.timeout_for_request(), Duration::from_secs(60));
+        assert_eq!(
+            configuration.timeout_for_resource(),
+            Duration::from_secs(7 * 24 * 60 * 60)
+        );
+        assert!(configuration.http_additional_headers().is_empty());
+        assert_eq!(
+            configuration.request_cache_policy(),
+            NSURLRequestCachePolicy::UseProtocolCachePolicy
+        );
+        assert!(configuration.allows_cellular_access());
+
+        // Each call returns a new configuration.
+        assert!(!std::ptr::eq(
+            &*configuration,
+            &*NSURLSessionConfiguration::default()
+        ));
+    }
+
+    #[test]
+    fn mutate() {
+        let mut configuration = NSURLSessionConfiguration::ephemeral();
+        let configuration = Arc::get_mut(&mut configuration).unwrap();
+
+        configuration.set_timeout_for_request(Duration::from_secs(5));
+        configuration.set_timeout_for_resource(Duration::from_secs(30));
+        configuration.set_http_additional_headers(&[("X-Fruity", "1"), ("Accept", "text/plain")]);
+        configuration
+            .set_request_cache_policy(NSURLRequestCachePolicy::ReloadIgnoringLocalCacheData);
+        configuration.set_allows_cellular_access(false);
+
+        assert_eq!(configuration.timeout_for_request(), Duration::from_secs(5));
+        assert_eq!(
+            configuration.timeout_for_resource(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            configuration.request_cache_policy(),
+            NSURLRequestCachePolicy::ReloadIgnoringLocalCacheData
+        );
+        assert!(!configuration.allows_cellular_access());
+
+        let headers = configuration.http_additional_headers();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["X-Fruity"], "1");
+        assert_eq!(headers["Accept"], "text/plain");
+
+        configuration.set_http_additional_headers(&[]);
+        assert!(configuration.http_additional_headers().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use super::{
+    blocking::{data_result, no_result_error, DataResult},
+    NSURLSession, NSURLSessionDataTask,
+};
+use crate::core::Arc;
+use crate::foundation::{NSData, NSError, NSURLRequest, NSURLResponse};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+/// A future that loads a request with an
+/// [`NSURLSession`](struct.NSURLSession.html).
+///
+/// The task is resumed when this is first polled, and cancelled if this is
+/// dropped before the task completes.
+#[must_use = "Futures do nothing unless polled"]
+pub struct NSURLSessionDataFuture {
+    task: Arc<NSURLSessionDataTask>,
+    state: std::sync::Arc<Mutex<State>>,
+    resumed: bool,
+    finished: bool,
+}
+
+#[derive(Default)]
+struct State {
+    completed: bool,
+    result: Option<DataResult>,
+    waker: Option<Waker>,
+}
+
+// Marks the task as completed once the completion closure is called or
+// dropped, so that the future cannot wait forever.
+struct Complete(std::sync::Arc<Mutex<State>>);
+
+impl Drop for Complete {
+    fn drop(&mut self) {
+        let waker = self.0.lock().ok().and_then(|mut state| {
+            state.completed = true;
+            state.waker.take()
+        });
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl NSURLSessionDataFuture {
+    /// Returns the task that loads the request.
+    #[inline]
+    pub fn task(&self) -> &NSURLSessionDataTask {
+        &self.task
+    }
+}
+
+impl Future for NSURLSessionDataFuture {
+    type Output = Result<(Arc<NSData>, Arc<NSURLResponse>), Arc<NSError<'static>>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        assert!(!this.finished, "Future polled after completion");
+
+        {
+            let mut state = match this.state.lock() {
+                Ok(state) => state,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if state.completed {
+                this.finished = true;
+                let result = state.result.take();
+                return Poll::Ready(result.unwrap_or_else(|| Err(no_result_error())));
+            }
+
+            let waker = cx.waker();
+            if !state.waker.as_ref().is_some_and(|w| w.will_wake(waker)) {
+                state.waker = Some(waker.clone());
+            }
+        }
+
+        // Resumed after the waker is stored so that an immediate completion
+        // is not missed.
+        if !this.resumed {
+            this.resumed = true;
+            this.task.resume();
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for NSURLSessionDataFuture {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.task.cancel();
+        }
+    }
+}
+
+impl NSURLSession {
+    /// Returns a future that loads `request`.
+    ///
+    /// The future resolves like [`data_blocking`](#method.data_blocking),
+    /// and does not depend on any particular executor. Dropping it cancels
+    /// the request.
+    pub fn data_async(&self, request: &NSURLRequest) -> NSURLSessionDataFuture {
+        let state = std::sync::Arc::new(Mutex::new(State::default()));
+
+        let task = {
+            let complete = Complete(state.clone());
+            self.data_task(request, move |data, response, error| {
+                let result = data_result(data, response, error);
+                if let Ok(mut state) = complete.0.lock() {
+                    state.result = Some(result);
+                }
+            })
+        };
+
+        NSURLSessionDataFuture {
+            task,
+            state,
+            resumed: false,
+            finished: false,
+        }
+    }
+}
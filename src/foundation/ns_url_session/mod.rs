@@ -0,0 +1,213 @@
+use super::{NSData, NSError, NSOperationQueue, NSURLRequest, NSURLResponse};
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, RcBlock};
+use std::sync::Mutex;
+
+mod blocking;
+mod configuration;
+#[cfg(feature = "async")]
+mod future;
+mod task;
+
+pub use configuration::NSURLSessionConfiguration;
+#[cfg(feature = "async")]
+pub use future::NSURLSessionDataFuture;
+pub use task::{NSURLSessionDataTask, NSURLSessionTask, NSURLSessionTaskState};
+
+objc_subclass! {
+    /// A group of URL load tasks that share a configuration.
+    ///
+    /// Requests go through the system networking stack, which handles
+    /// proxies, TLS, and App Transport Security.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession).
+    #[derive(PartialEq)]
+    pub class NSURLSession: NSObject<'static>;
+}
+
+/// The arguments of a data task's completion handler.
+type CompletionArgs = (*const NSData, *const NSURLResponse, *const NSError<'static>);
+
+/// Wraps `completion` in a block that takes the result of a data task.
+fn completion_block<F>(completion: F) -> RcBlock<CompletionArgs, ()>
+where
+    F: FnOnce(Option<Arc<NSData>>, Option<Arc<NSURLResponse>>, Option<Arc<NSError<'static>>>)
+        + Send
+        + 'static,
+{
+    // The block is called at most once, but may be called on any thread.
+    let completion = Mutex::new(Some(completion));
+
+    RcBlock::without_signature(
+        move |data: *const NSData,
+              response: *const NSURLResponse,
+              error: *const NSError<'static>| {
+            let completion = completion.lock().ok().and_then(|mut f| f.take());
+            let completion = match completion {
+                Some(completion) => completion,
+                None => return,
+            };
+
+            let data = unsafe { data.as_ref() }.map(Arc::retain);
+            let response = unsafe { response.as_ref() }.map(Arc::retain);
+            let error = unsafe { error.as_ref() }.map(Arc::retain);
+
+            abort_on_panic(|| completion(data, response, error));
+        },
+    )
+}
+
+impl NSURLSession {
+    /// Returns the shared session, which uses the default configuration.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/shared).
+    #[inline]
+    #[doc(alias = "sharedSession")]
+    pub fn shared() -> Arc<Self> {
+        let session =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), sharedSession]) };
+        session.expect("No shared session")
+    }
+
+    /// Creates a session with a copy of `configuration`.
+    ///
+    /// Unlike the shared session, this should be invalidated once it is no
+    /// longer needed, so that its resources are released.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/sessionwithconfiguration).
+    #[inline]
+    #[doc(alias = "sessionWithConfiguration")]
+    #[doc(alias = "sessionWithConfiguration:")]
+    pub fn with_configuration(configuration: &NSURLSessionConfiguration) -> Arc<Self> {
+        let session = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![Self::class(), sessionWithConfiguration: configuration],
+            )
+        };
+        session.expect("Failed to create session")
+    }
+
+    /// Returns a copy of the configuration of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/configuration).
+    #[inline]
+    pub fn configuration(&self) -> Arc<NSURLSessionConfiguration> {
+        let configuration = unsafe { retain_autoreleased(|| _msg_send_any![self, configuration]) };
+        configuration.expect("Session has no configuration")
+    }
+
+    /// Returns the queue that completion closures are called on.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/delegatequeue).
+    #[inline]
+    #[doc(alias = "delegateQueue")]
+    pub fn delegate_queue(&self) -> Arc<NSOperationQueue> {
+        let queue = unsafe { retain_autoreleased(|| _msg_send_any![self, delegateQueue]) };
+        queue.expect("Session has no delegate queue")
+    }
+
+    /// Creates a task that loads `request` and then calls `completion` with
+    /// the body, the response, and any error.
+    ///
+    /// The task does nothing until [`resume`](struct.NSURLSessionTask.html#method.resume)
+    /// is called.
+    ///
+    /// `completion` is called on the [`delegate_queue`](#method.delegate_queue)
+    /// of `self`, which runs on a background thread. This is why it must be
+    /// `Send`, as are the objects it receives. The response is `None` if the
+    /// request failed, and responses to HTTP requests can be converted with
+    /// [`NSURLResponse::as_http`](struct.NSURLResponse.html#method.as_http).
+    /// `completion` is called at most once, and panics in it abort the process.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/datataskwithrequest).
+    #[doc(alias = "dataTaskWithRequest")]
+    #[doc(alias = "dataTaskWithRequest:completionHandler:")]
+    pub fn data_task<F>(&self, request: &NSURLRequest, completion: F) -> Arc<NSURLSessionDataTask>
+    where
+        F: FnOnce(Option<Arc<NSData>>, Option<Arc<NSURLResponse>>, Option<Arc<NSError<'static>>>)
+            + Send
+            + 'static,
+    {
+        // The task copies the block, which keeps `completion` alive until
+        // the task completes.
+        let block = completion_block(completion);
+        let block = block.as_ptr();
+        let task = unsafe {
+            retain_autoreleased(
+                || _msg_send_any![self, dataTaskWithRequest: request completionHandler: block],
+            )
+        };
+        task.expect("Failed to create data task")
+    }
+
+    /// Invalidates `self` once its outstanding tasks complete.
+    ///
+    /// This does nothing to the shared session.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/finishtasksandinvalidate).
+    #[inline]
+    #[doc(alias = "finishTasksAndInvalidate")]
+    pub fn finish_tasks_and_invalidate(&self) {
+        unsafe { _msg_send_any![self, finishTasksAndInvalidate => ()] }
+    }
+
+    /// Cancels the outstanding tasks of `self` and invalidates it.
+    ///
+    /// This does nothing to the shared session.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsession/invalidateandcancel).
+    #[inline]
+    #[doc(alias = "invalidateAndCancel")]
+    pub fn invalidate_and_cancel(&self) {
+        unsafe { _msg_send_any![self, invalidateAndCancel => ()] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{URLErrorCode, NSURL};
+    use std::{sync::mpsc, time::Duration};
+
+    // Tasks that reach a server are tested by `tests/url_session.rs`.
+
+    #[test]
+    fn completion_is_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<Option<Arc<NSData>>>();
+        assert_send::<Option<Arc<NSURLResponse>>>();
+        assert_send::<Option<Arc<NSError<'static>>>>();
+    }
+
+    #[test]
+    fn shared() {
+        assert!(*NSURLSession::shared() == *NSURLSession::shared());
+    }
+
+    #[test]
+    fn cancel_suspended() {
+        let session = NSURLSession::with_configuration(&NSURLSessionConfiguration::ephemeral());
+
+        let url = NSURL::from_str("http://127.0.0.1:9/").unwrap();
+        let request = NSURLRequest::with_url(&url);
+
+        let (sender, receiver) = mpsc::channel();
+        let task = session.data_task(&request, move |data, response, error| {
+            sender.send((data, response, error)).unwrap();
+        });
+
+        assert_eq!(task.state(), NSURLSessionTaskState::Suspended);
+        assert_eq!(*task.original_request().unwrap().url().unwrap(), *url);
+
+        task.cancel();
+        let (data, response, error) = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(data.is_none());
+        assert!(response.is_none());
+        assert_eq!(error.unwrap().url_code(), Some(URLErrorCode::Cancelled));
+        assert_eq!(task.state(), NSURLSessionTaskState::Completed);
+
+        session.invalidate_and_cancel();
+    }
+}
@@ -0,0 +1,138 @@
+use crate::core::Arc;
+use crate::foundation::{NSError, NSURLRequest, NSURLResponse};
+use crate::objc::{retain_autoreleased, NSInteger, NSObject, NSUInteger};
+
+objc_subclass! {
+    /// A task that runs a URL load request in an
+    /// [`NSURLSession`](struct.NSURLSession.html).
+    ///
+    /// Tasks are created suspended, so they must be started with
+    /// [`resume`](#method.resume). Methods take `&self` because a task is
+    /// shared with its session.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask).
+    #[derive(PartialEq)]
+    pub class NSURLSessionTask: NSObject<'static>;
+}
+
+objc_subclass! {
+    /// A task that loads the body of a response into memory.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiondatatask).
+    #[derive(PartialEq)]
+    pub class NSURLSessionDataTask: NSURLSessionTask;
+}
+
+/// The state of a [`NSURLSessionTask`](struct.NSURLSessionTask.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/state).
+#[repr(isize)] // NSInteger
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NSURLSessionTaskState {
+    /// The task is loading.
+    Running = 0,
+    /// The task was created or suspended, and has not been resumed.
+    Suspended = 1,
+    /// The task was cancelled, and has not yet called its completion handler.
+    Canceling = 2,
+    /// The task finished, and has called its completion handler.
+    Completed = 3,
+}
+
+impl NSURLSessionTaskState {
+    /// Returns the state for a raw value, or `None` if it is unknown.
+    #[inline]
+    pub fn from_raw(value: NSInteger) -> Option<Self> {
+        match value {
+            0 => Some(Self::Running),
+            1 => Some(Self::Suspended),
+            2 => Some(Self::Canceling),
+            3 => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+impl NSURLSessionTask {
+    /// Starts or continues loading.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/resume).
+    #[inline]
+    pub fn resume(&self) {
+        unsafe { _msg_send_any![self, resume => ()] }
+    }
+
+    /// Pauses loading until [`resume`](#method.resume) is called.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/suspend).
+    #[inline]
+    pub fn suspend(&self) {
+        unsafe { _msg_send_any![self, suspend => ()] }
+    }
+
+    /// Stops loading, which completes `self` with a
+    /// [`URLErrorCode::Cancelled`](enum.URLErrorCode.html#variant.Cancelled)
+    /// error.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/cancel).
+    #[inline]
+    pub fn cancel(&self) {
+        unsafe { _msg_send_any![self, cancel => ()] }
+    }
+
+    /// Returns the state of `self`.
+    ///
+    /// Values unknown to this crate are returned as
+    /// [`Completed`](enum.NSURLSessionTaskState.html#variant.Completed).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/state).
+    #[inline]
+    pub fn state(&self) -> NSURLSessionTaskState {
+        let state: NSInteger = unsafe { _msg_send_any![self, state] };
+        NSURLSessionTaskState::from_raw(state).unwrap_or(NSURLSessionTaskState::Completed)
+    }
+
+    /// Returns the identifier of `self`, which is unique within its session.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/taskidentifier).
+    #[inline]
+    #[doc(alias = "taskIdentifier")]
+    pub fn task_identifier(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self, taskIdentifier] }
+    }
+
+    /// Returns the request that `self` was created with.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/originalrequest).
+    #[inline]
+    #[doc(alias = "originalRequest")]
+    pub fn original_request(&self) -> Option<Arc<NSURLRequest>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, originalRequest]) }
+    }
+
+    /// Returns the request that `self` is loading, which differs from the
+    /// original request after a redirect.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/currentrequest).
+    #[inline]
+    #[doc(alias = "currentRequest")]
+    pub fn current_request(&self) -> Option<Arc<NSURLRequest>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, currentRequest]) }
+    }
+
+    /// Returns the response to `self`, once it has been received.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/response).
+    #[inline]
+    pub fn response(&self) -> Option<Arc<NSURLResponse>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, response]) }
+    }
+
+    /// Returns the error that `self` failed with, if any.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsurlsessiontask/error).
+    #[inline]
+    pub fn error(&self) -> Option<Arc<NSError<'static>>> {
+        unsafe { retain_autoreleased(|| _msg_send_any![self, error]) }
+    }
+}
@@ -0,0 +1,458 @@
+use super::{NSArray, NSData, NSDictionary, NSString, NSURL};
+use crate::core::{Arc, ObjectType};
+use crate::objc::{retain_autoreleased, ClassType, NSInteger, NSObject, Sel, BOOL};
+
+objc_subclass! {
+    /// An interface to the user's defaults database, where you store
+    /// key-value pairs persistently across launches of your app.
+    ///
+    /// Unlike most mutable types, the setters take `&self` because the
+    /// defaults database is shared and thread-safe.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults).
+    pub class NSUserDefaults: NSObject<'static>;
+}
+
+impl NSUserDefaults {
+    /// Returns the shared defaults object.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1416603-standarduserdefaults).
+    #[inline]
+    #[doc(alias = "standardUserDefaults")]
+    pub fn standard() -> Arc<Self> {
+        let defaults =
+            unsafe { retain_autoreleased(|| _msg_send_any![Self::class(), standardUserDefaults]) };
+        defaults.expect("No standard user defaults")
+    }
+
+    /// Creates a defaults object for the suite `name`, such as an app group
+    /// identifier.
+    ///
+    /// Returns `None` if `name` is the main bundle identifier or
+    /// `"NSGlobalDomain"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1409957-initwithsuitename).
+    #[doc(alias = "initWithSuiteName")]
+    #[doc(alias = "initWithSuiteName:")]
+    pub fn with_suite(name: &str) -> Option<Arc<Self>> {
+        let name = NSString::from_str(name);
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(
+                obj: Arc<NSUserDefaults>,
+                sel: Sel,
+                name: &NSString,
+            ) -> Option<Arc<NSUserDefaults>>;
+        }
+
+        let sel = selector!(initWithSuiteName:);
+        unsafe { objc_msgSend(value, sel, &name) }
+    }
+
+    /// Adds the contents of `defaults` to the registration domain, which is
+    /// searched after all other domains and is not persisted.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1417065-registerdefaults).
+    #[inline]
+    #[doc(alias = "registerDefaults")]
+    #[doc(alias = "registerDefaults:")]
+    pub fn register_defaults(&self, defaults: &NSDictionary<NSString<'static>, NSObject<'static>>) {
+        unsafe { _msg_send_any![self, registerDefaults: defaults] }
+    }
+
+    /// Returns the merged contents of all domains searched by `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1415919-dictionaryrepresentation).
+    #[inline]
+    #[doc(alias = "dictionaryRepresentation")]
+    pub fn dictionary_representation(
+        &self,
+    ) -> Arc<NSDictionary<NSString<'static>, NSObject<'static>>> {
+        let dictionary =
+            unsafe { retain_autoreleased(|| _msg_send_any![self, dictionaryRepresentation]) };
+        dictionary.expect("User defaults have no dictionary representation")
+    }
+
+    /// Removes all values stored in the persistent domain `name`, such as a
+    /// suite name.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1417339-removepersistentdomainforname).
+    #[inline]
+    #[doc(alias = "removePersistentDomainForName")]
+    #[doc(alias = "removePersistentDomainForName:")]
+    pub fn remove_persistent_domain(&self, name: &str) {
+        let name = NSString::from_str(name);
+        unsafe { _msg_send_any![self, removePersistentDomainForName: &*name] }
+    }
+}
+
+/// Getting values.
+///
+/// These return `None` if there is no value for `key`, so that absent values
+/// can be distinguished from ones that are zero or empty.
+impl NSUserDefaults {
+    /// Returns the value for `key`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1410095-objectforkey).
+    #[inline]
+    #[doc(alias = "objectForKey")]
+    #[doc(alias = "objectForKey:")]
+    pub fn object_for_key(&self, key: &str) -> Option<Arc<NSObject<'static>>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, objectForKey: &*key]) }
+    }
+
+    #[inline]
+    fn contains_key(&self, key: &NSString) -> bool {
+        let object: *const NSObject = unsafe { _msg_send_any![self, objectForKey: key] };
+        !object.is_null()
+    }
+
+    /// Returns the value for `key` as a `bool`.
+    ///
+    /// Numbers are `true` if they are nonzero, and strings are `true` if they
+    /// start with `Y`, `y`, `T`, `t`, or a nonzero digit.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1416388-boolforkey).
+    #[inline]
+    #[doc(alias = "boolForKey")]
+    #[doc(alias = "boolForKey:")]
+    pub fn bool_for_key(&self, key: &str) -> Option<bool> {
+        let key = NSString::from_str(key);
+        if !self.contains_key(&key) {
+            return None;
+        }
+        Some(unsafe { _msg_send_any![self, boolForKey: &*key => BOOL] }.into())
+    }
+
+    /// Returns the value for `key` as an integer, or 0 if it is not a number
+    /// or numeric string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1407405-integerforkey).
+    #[inline]
+    #[doc(alias = "integerForKey")]
+    #[doc(alias = "integerForKey:")]
+    pub fn integer_for_key(&self, key: &str) -> Option<NSInteger> {
+        let key = NSString::from_str(key);
+        if !self.contains_key(&key) {
+            return None;
+        }
+        Some(unsafe { _msg_send_any![self, integerForKey: &*key] })
+    }
+
+    /// Returns the value for `key` as a double, or 0 if it is not a number
+    /// or numeric string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1416581-doubleforkey).
+    #[inline]
+    #[doc(alias = "doubleForKey")]
+    #[doc(alias = "doubleForKey:")]
+    pub fn double_for_key(&self, key: &str) -> Option<f64> {
+        let key = NSString::from_str(key);
+        if !self.contains_key(&key) {
+            return None;
+        }
+        Some(unsafe { _msg_send_any![self, doubleForKey: &*key] })
+    }
+
+    /// Returns the value for `key` if it is a string or number, which is
+    /// converted to a string.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1416700-stringforkey).
+    #[inline]
+    #[doc(alias = "stringForKey")]
+    #[doc(alias = "stringForKey:")]
+    pub fn string_for_key(&self, key: &str) -> Option<Arc<NSString<'static>>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, stringForKey: &*key]) }
+    }
+
+    /// Returns the value for `key` if it is an array.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1409590-arrayforkey).
+    #[inline]
+    #[doc(alias = "arrayForKey")]
+    #[doc(alias = "arrayForKey:")]
+    pub fn array_for_key(&self, key: &str) -> Option<Arc<NSArray<NSObject<'static>>>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, arrayForKey: &*key]) }
+    }
+
+    /// Returns the value for `key` if it is a dictionary.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1408563-dictionaryforkey).
+    #[inline]
+    #[doc(alias = "dictionaryForKey")]
+    #[doc(alias = "dictionaryForKey:")]
+    pub fn dictionary_for_key(
+        &self,
+        key: &str,
+    ) -> Option<Arc<NSDictionary<NSString<'static>, NSObject<'static>>>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, dictionaryForKey: &*key]) }
+    }
+
+    /// Returns the value for `key` if it is data.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1409590-dataforkey).
+    #[inline]
+    #[doc(alias = "dataForKey")]
+    #[doc(alias = "dataForKey:")]
+    pub fn data_for_key(&self, key: &str) -> Option<Arc<NSData>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, dataForKey: &*key]) }
+    }
+
+    /// Returns the value for `key` if it was stored with
+    /// [`set_url`](#method.set_url), or if it is a path string, which is
+    /// converted to a file URL.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1408648-urlforkey).
+    #[inline]
+    #[doc(alias = "URLForKey")]
+    #[doc(alias = "URLForKey:")]
+    pub fn url_for_key(&self, key: &str) -> Option<Arc<NSURL>> {
+        let key = NSString::from_str(key);
+        unsafe { retain_autoreleased(|| _msg_send_any![self, URLForKey: &*key]) }
+    }
+}
+
+/// Setting values.
+impl NSUserDefaults {
+    /// Sets the value for `key`, or removes it if `value` is `None`.
+    ///
+    /// `value` must be a property list object: an
+    /// [`NSData`](struct.NSData.html), [`NSString`](struct.NSString.html),
+    /// [`NSNumber`](struct.NSNumber.html), [`NSDate`](struct.NSDate.html),
+    /// [`NSArray`](struct.NSArray.html), or
+    /// [`NSDictionary`](struct.NSDictionary.html).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1414067-setobject).
+    #[inline]
+    #[doc(alias = "setObject")]
+    #[doc(alias = "setObject:forKey:")]
+    pub fn set_object(&self, key: &str, value: Option<&NSObject>) {
+        let key = NSString::from_str(key);
+        unsafe { _msg_send_any![self, setObject: value forKey: &*key] }
+    }
+
+    /// Sets the value for `key` to a boolean number.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1408905-setbool).
+    #[inline]
+    #[doc(alias = "setBool")]
+    #[doc(alias = "setBool:forKey:")]
+    pub fn set_bool(&self, key: &str, value: bool) {
+        let key = NSString::from_str(key);
+        let value = BOOL::from(value);
+        unsafe { _msg_send_any![self, setBool: value forKey: &*key] }
+    }
+
+    /// Sets the value for `key` to an integer number.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1413614-setinteger).
+    #[inline]
+    #[doc(alias = "setInteger")]
+    #[doc(alias = "setInteger:forKey:")]
+    pub fn set_integer(&self, key: &str, value: NSInteger) {
+        let key = NSString::from_str(key);
+        unsafe { _msg_send_any![self, setInteger: value forKey: &*key] }
+    }
+
+    /// Sets the value for `key` to a double number.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1414557-setdouble).
+    #[inline]
+    #[doc(alias = "setDouble")]
+    #[doc(alias = "setDouble:forKey:")]
+    pub fn set_double(&self, key: &str, value: f64) {
+        let key = NSString::from_str(key);
+        unsafe { _msg_send_any![self, setDouble: value forKey: &*key] }
+    }
+
+    /// Sets the value for `key` to a copy of `value`.
+    #[inline]
+    pub fn set_string(&self, key: &str, value: &NSString) {
+        self.set_object(key, Some(value));
+    }
+
+    /// Sets the value for `key` to a copy of `value`, which must only contain
+    /// property list objects.
+    #[inline]
+    pub fn set_array<T: ObjectType + 'static>(&self, key: &str, value: &NSArray<T>) {
+        self.set_object(key, Some(value));
+    }
+
+    /// Sets the value for `key` to a copy of `value`, which must only contain
+    /// property list objects.
+    #[inline]
+    pub fn set_dictionary<V: ObjectType + 'static>(
+        &self,
+        key: &str,
+        value: &NSDictionary<NSString<'static>, V>,
+    ) {
+        self.set_object(key, Some(value));
+    }
+
+    /// Sets the value for `key` to a copy of `value`.
+    #[inline]
+    pub fn set_data(&self, key: &str, value: &NSData) {
+        self.set_object(key, Some(value));
+    }
+
+    /// Sets the value for `key` to `url`.
+    ///
+    /// URLs are not property list objects, so file URLs are stored as paths
+    /// with the home directory abbreviated to `~`, and other URLs are
+    /// archived. Use [`url_for_key`](#method.url_for_key) to read them
+    /// back.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1414194-seturl).
+    #[inline]
+    #[doc(alias = "setURL")]
+    #[doc(alias = "setURL:forKey:")]
+    pub fn set_url(&self, key: &str, url: &NSURL) {
+        let key = NSString::from_str(key);
+        unsafe { _msg_send_any![self, setURL: url forKey: &*key] }
+    }
+
+    /// Removes the value for `key`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuserdefaults/1411182-removeobjectforkey).
+    #[inline]
+    #[doc(alias = "removeObjectForKey")]
+    #[doc(alias = "removeObjectForKey:")]
+    pub fn remove_object_for_key(&self, key: &str) {
+        let key = NSString::from_str(key);
+        unsafe { _msg_send_any![self, removeObjectForKey: &*key] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::NSNumber;
+    use std::{ops::Deref, process};
+
+    // Removes the suite when dropped.
+    struct Suite {
+        name: String,
+        defaults: Arc<NSUserDefaults>,
+    }
+
+    impl Suite {
+        fn new(test: &str) -> Self {
+            let name = format!("org.example.fruity-tests.{}.{}", test, process::id());
+            let defaults = NSUserDefaults::with_suite(&name).unwrap();
+            Self { name, defaults }
+        }
+    }
+
+    impl Deref for Suite {
+        type Target = NSUserDefaults;
+
+        fn deref(&self) -> &NSUserDefaults {
+            &self.defaults
+        }
+    }
+
+    impl Drop for Suite {
+        fn drop(&mut self) {
+            self.defaults.remove_persistent_domain(&self.name);
+        }
+    }
+
+    #[test]
+    fn scalars() {
+        let defaults = Suite::new("scalars");
+        assert_eq!(defaults.bool_for_key("flag"), None);
+        assert_eq!(defaults.integer_for_key("count"), None);
+        assert_eq!(defaults.double_for_key("ratio"), None);
+
+        defaults.set_bool("flag", false);
+        defaults.set_integer("count", 0);
+        defaults.set_double("ratio", 0.25);
+        assert_eq!(defaults.bool_for_key("flag"), Some(false));
+        assert_eq!(defaults.integer_for_key("count"), Some(0));
+        assert_eq!(defaults.double_for_key("ratio"), Some(0.25));
+
+        // Numbers convert to strings.
+        assert_eq!(*defaults.string_for_key("count").unwrap(), *"0");
+
+        defaults.remove_object_for_key("count");
+        assert_eq!(defaults.integer_for_key("count"), None);
+        assert!(defaults.object_for_key("count").is_none());
+    }
+
+    #[test]
+    fn objects() {
+        let defaults = Suite::new("objects");
+
+        let string = NSString::from_str("héllo");
+        defaults.set_string("string", &string);
+        assert_eq!(*defaults.string_for_key("string").unwrap(), *string);
+        assert!(defaults.array_for_key("string").is_none());
+
+        let array = NSArray::from_slice(&[&*string, &*NSString::from_str("b")]);
+        defaults.set_array("array", &array);
+        assert_eq!(defaults.array_for_key("array").unwrap().len(), 2);
+
+        let (key, one) = (NSString::from_str("one"), NSNumber::from_int(1));
+        let dictionary = NSDictionary::from_pairs(&[(&*key, &***one)]);
+        defaults.set_dictionary("dictionary", &dictionary);
+        let stored = defaults.dictionary_for_key("dictionary").unwrap();
+        assert!(stored.get(&key).is_some());
+
+        defaults.set_data("data", &NSData::from_slice(b"\x00\x01"));
+        assert_eq!(
+            defaults.data_for_key("data").unwrap().as_slice(),
+            b"\x00\x01"
+        );
+
+        defaults.set_object("data", None);
+        assert!(defaults.data_for_key("data").is_none());
+    }
+
+    #[test]
+    fn urls() {
+        let defaults = Suite::new("urls");
+
+        let web = NSURL::from_str("https://example.com/a?b=c").unwrap();
+        defaults.set_url("web", &web);
+        assert_eq!(*defaults.url_for_key("web").unwrap(), *web);
+
+        let file = NSURL::file_url("/tmp/fruity test".as_ref(), false);
+        defaults.set_url("file", &file);
+        let stored = defaults.url_for_key("file").unwrap();
+        assert_eq!(stored.to_path_buf().unwrap(), file.to_path_buf().unwrap());
+
+        // Path strings are converted to file URLs.
+        defaults.set_string("path", &NSString::from_str("/tmp"));
+        assert_eq!(
+            defaults.url_for_key("path").unwrap().to_path_buf().unwrap(),
+            std::path::Path::new("/tmp")
+        );
+    }
+
+    #[test]
+    fn registered() {
+        let defaults = Suite::new("registered");
+        let key = NSString::from_str("org.example.fruity-tests.registered");
+        let value = NSNumber::from_int(7);
+        defaults.register_defaults(&NSDictionary::from_pairs(&[(&*key, &***value)]));
+
+        let key = key.to_string();
+        assert_eq!(defaults.integer_for_key(&key), Some(7));
+        defaults.set_integer(&key, 8);
+        assert_eq!(defaults.integer_for_key(&key), Some(8));
+
+        // Removing the persistent value reveals the registered one.
+        defaults.remove_object_for_key(&key);
+        assert_eq!(defaults.integer_for_key(&key), Some(7));
+
+        let representation = defaults.dictionary_representation();
+        assert!(representation.get(&NSString::from_str(&key)).is_some());
+    }
+}
@@ -0,0 +1,173 @@
+use super::NSString;
+use crate::core::Arc;
+use crate::objc::{retain_autoreleased, ClassType, NSObject, Sel};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+objc_subclass! {
+    /// A universally unique value that can be used to identify types,
+    /// interfaces, and other items.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid).
+    #[derive(PartialEq)]
+    pub class NSUUID: NSObject<'static>;
+}
+
+impl Eq for NSUUID {}
+
+impl Hash for NSUUID {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // `-hash` is consistent with `-isEqual:`.
+        state.write_usize(NSObject::hash(self));
+    }
+}
+
+impl fmt::Debug for NSUUID {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for NSUUID {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.uuid_string().fmt(f)
+    }
+}
+
+impl From<[u8; 16]> for Arc<NSUUID> {
+    #[inline]
+    fn from(bytes: [u8; 16]) -> Self {
+        NSUUID::from_bytes(bytes)
+    }
+}
+
+impl From<&NSUUID> for [u8; 16] {
+    #[inline]
+    fn from(uuid: &NSUUID) -> Self {
+        uuid.bytes()
+    }
+}
+
+impl NSUUID {
+    /// Creates a random UUID (version 4).
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid/1411615-init).
+    #[inline]
+    #[doc(alias = "UUID")]
+    #[doc(alias = "init")]
+    pub fn new() -> Arc<Self> {
+        unsafe { Self::class().alloc_init() }
+    }
+
+    /// Parses a UUID string such as `"E621E1F8-C36C-495A-93FC-0C247A3E6E5F"`,
+    /// or returns `None` if it is malformed.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid/1416968-initwithuuidstring).
+    #[doc(alias = "initWithUUIDString")]
+    #[doc(alias = "initWithUUIDString:")]
+    pub fn from_string(string: &str) -> Option<Arc<Self>> {
+        let string = NSString::from_str(string);
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(obj: Arc<NSUUID>, sel: Sel, string: &NSString) -> Option<Arc<NSUUID>>;
+        }
+
+        let sel = selector!(initWithUUIDString:);
+        unsafe { objc_msgSend(value, sel, &string) }
+    }
+
+    /// Creates a UUID from its 16 bytes.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid/1415646-initwithuuidbytes).
+    #[doc(alias = "initWithUUIDBytes")]
+    #[doc(alias = "initWithUUIDBytes:")]
+    pub fn from_bytes(bytes: [u8; 16]) -> Arc<Self> {
+        let value: Arc<Self> = unsafe { Self::class().alloc() };
+
+        #[allow(clashing_extern_declarations)]
+        extern "C-unwind" {
+            fn objc_msgSend(obj: Arc<NSUUID>, sel: Sel, bytes: *const [u8; 16]) -> Arc<NSUUID>;
+        }
+
+        let sel = selector!(initWithUUIDBytes:);
+        unsafe { objc_msgSend(value, sel, &bytes) }
+    }
+
+    /// Returns the 16 bytes of `self`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid/1411420-getuuidbytes).
+    #[inline]
+    #[doc(alias = "getUUIDBytes")]
+    #[doc(alias = "getUUIDBytes:")]
+    pub fn bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        let ptr: *mut [u8; 16] = &mut bytes;
+        unsafe { _msg_send_any![self, getUUIDBytes: ptr => ()] };
+        bytes
+    }
+
+    /// Returns the UUID as an uppercase string, such as
+    /// `"E621E1F8-C36C-495A-93FC-0C247A3E6E5F"`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsuuid/1416585-uuidstring).
+    #[inline]
+    #[doc(alias = "UUIDString")]
+    pub fn uuid_string(&self) -> Arc<NSString<'static>> {
+        let string = unsafe { retain_autoreleased(|| _msg_send_any![self, UUIDString]) };
+        string.expect("UUID has no string")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash(uuid: &NSUUID) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        uuid.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = [
+            0xE6, 0x21, 0xE1, 0xF8, 0xC3, 0x6C, 0x49, 0x5A, 0x93, 0xFC, 0x0C, 0x24, 0x7A, 0x3E,
+            0x6E, 0x5F,
+        ];
+
+        let uuid = NSUUID::from_bytes(bytes);
+        assert_eq!(uuid.bytes(), bytes);
+        assert_eq!(uuid.to_string(), "E621E1F8-C36C-495A-93FC-0C247A3E6E5F");
+
+        let parsed = NSUUID::from_string("e621e1f8-c36c-495a-93fc-0c247a3e6e5f").unwrap();
+        assert_eq!(*parsed, *uuid);
+        assert_eq!(parsed.bytes(), bytes);
+
+        assert_eq!(hash(&uuid), hash(&parsed));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(NSUUID::from_string("").is_none());
+        assert!(NSUUID::from_string("not a uuid").is_none());
+        assert!(NSUUID::from_string("E621E1F8-C36C-495A-93FC-0C247A3E6E5").is_none());
+        assert!(NSUUID::from_string("E621E1F8C36C495A93FC0C247A3E6E5F").is_none());
+    }
+
+    #[test]
+    fn random() {
+        let (a, b) = (NSUUID::new(), NSUUID::new());
+        assert_ne!(*a, *b);
+
+        // Version 4.
+        assert_eq!(a.bytes()[6] >> 4, 4);
+    }
+}
@@ -9,6 +9,39 @@ use std::{
 
 // TODO: Implement methods defined in other frameworks.
 
+// Type encodings of the values created by the `from_*` constructors.
+//
+// `NSRange` uses `NSUInteger` and the geometry types use `CGFloat`, both of
+// which depend on the target's pointer width.
+mod encoding {
+    pub const PTR: &[u8] = b"^v";
+
+    #[cfg(target_pointer_width = "64")]
+    pub const RANGE: &[u8] = b"{_NSRange=QQ}";
+    #[cfg(target_pointer_width = "32")]
+    pub const RANGE: &[u8] = b"{_NSRange=II}";
+
+    #[cfg(target_pointer_width = "64")]
+    pub const POINT: &[u8] = b"{CGPoint=dd}";
+    #[cfg(target_pointer_width = "32")]
+    pub const POINT: &[u8] = b"{CGPoint=ff}";
+
+    #[cfg(target_pointer_width = "64")]
+    pub const SIZE: &[u8] = b"{CGSize=dd}";
+    #[cfg(target_pointer_width = "32")]
+    pub const SIZE: &[u8] = b"{CGSize=ff}";
+
+    #[cfg(target_pointer_width = "64")]
+    pub const RECT: &[u8] = b"{CGRect={CGPoint=dd}{CGSize=dd}}";
+    #[cfg(target_pointer_width = "32")]
+    pub const RECT: &[u8] = b"{CGRect={CGPoint=ff}{CGSize=ff}}";
+
+    #[cfg(target_pointer_width = "64")]
+    pub const EDGE_INSETS: &[u8] = b"{NSEdgeInsets=dddd}";
+    #[cfg(target_pointer_width = "32")]
+    pub const EDGE_INSETS: &[u8] = b"{NSEdgeInsets=ffff}";
+}
+
 objc_subclass! {
     /// A simple container for a single C or Objective-C data item.
     ///
@@ -32,6 +65,46 @@ impl NSValue {
         ]
     }
 
+    /// Creates a value object containing a copy of `value`, interpreted with
+    /// the Objective-C type `encoding`.
+    ///
+    /// This can be used for custom structs that have no dedicated constructor.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1551466-valuewithbytes).
+    ///
+    /// # Safety
+    ///
+    /// `encoding` must describe the memory layout of `T`.
+    #[inline]
+    #[doc(alias = "valueWithBytes")]
+    #[doc(alias = "valueWithBytes:objCType:")]
+    pub unsafe fn from_encoded<T: Copy>(value: &T, encoding: &CStr) -> Arc<Self> {
+        Self::from_bytes((value as *const T).cast(), encoding.as_ptr())
+    }
+
+    /// Returns a copy of the value if its Objective-C type is `encoding`.
+    ///
+    /// This is the counterpart to [`from_encoded`](#method.from_encoded).
+    ///
+    /// # Safety
+    ///
+    /// `encoding` must describe the memory layout of `T`.
+    #[inline]
+    #[doc(alias = "getValue")]
+    #[doc(alias = "getValue:size:")]
+    pub unsafe fn encoded_value<T: Copy>(&self, encoding: &CStr) -> Option<T> {
+        if self.has_objc_type(encoding.to_bytes()) {
+            Some(self.transmute_value())
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn has_objc_type(&self, encoding: &[u8]) -> bool {
+        self.objc_type_cstr().to_bytes() == encoding
+    }
+
     pub(crate) fn objc_type_single(&self) -> c_char {
         let objc_type = self.objc_type();
         let single = unsafe { *objc_type };
@@ -123,15 +196,18 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithPointer: ptr] }
     }
 
-    /// Returns the value as an untyped pointer.
-    ///
-    /// The value as a pointer to void. If the value object was not created to
-    /// hold a pointer-sized data item, the result is undefined.
+    /// Returns the value as an untyped pointer if it was created with
+    /// [`from_ptr`](#method.from_ptr).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1410668-pointervalue).
     #[inline]
-    pub fn ptr_value(&self) -> *const c_void {
-        unsafe { _msg_send_any![self, pointerValue] }
+    #[doc(alias = "pointerValue")]
+    pub fn ptr_value(&self) -> Option<*const c_void> {
+        if self.has_objc_type(encoding::PTR) {
+            Some(unsafe { _msg_send_any![self, pointerValue] })
+        } else {
+            None
+        }
     }
 
     /// Creates a value object containing the specified pointer.
@@ -171,12 +247,18 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithRange: value] }
     }
 
-    /// Returns the value as an `NSRange`.
+    /// Returns the value as an `NSRange` if it was created with
+    /// [`from_range`](#method.from_range).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1413902-rangevalue).
     #[inline]
-    pub fn range_value(&self) -> NSRange {
-        unsafe { _msg_send_any![self, pointValue] }
+    #[doc(alias = "rangeValue")]
+    pub fn range_value(&self) -> Option<NSRange> {
+        if self.has_objc_type(encoding::RANGE) {
+            Some(unsafe { _msg_send_any![self, rangeValue] })
+        } else {
+            None
+        }
     }
 }
 
@@ -192,13 +274,18 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithPoint: value] }
     }
 
-    /// Returns the value as an `NSPoint`.
+    /// Returns the value as an `NSPoint` if it was created with
+    /// [`from_point`](#method.from_point).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1391255-pointvalue).
     #[inline]
     #[doc(alias = "pointValue")]
-    pub fn point_value(&self) -> NSPoint {
-        unsafe { _msg_send_any![self, pointValue] }
+    pub fn point_value(&self) -> Option<NSPoint> {
+        if self.has_objc_type(encoding::POINT) {
+            Some(unsafe { _msg_send_any![self, pointValue] })
+        } else {
+            None
+        }
     }
 
     /// Creates a new value object containing the specified size.
@@ -211,13 +298,18 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithSize: value] }
     }
 
-    /// Returns the value as an `NSSize`.
+    /// Returns the value as an `NSSize` if it was created with
+    /// [`from_size`](#method.from_size).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1391301-sizevalue).
     #[inline]
     #[doc(alias = "sizeValue")]
-    pub fn size_value(&self) -> NSSize {
-        unsafe { _msg_send_any![self, sizeValue] }
+    pub fn size_value(&self) -> Option<NSSize> {
+        if self.has_objc_type(encoding::SIZE) {
+            Some(unsafe { _msg_send_any![self, sizeValue] })
+        } else {
+            None
+        }
     }
 
     /// Creates a new value object containing the specified rectangle.
@@ -230,13 +322,18 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithRect: value] }
     }
 
-    /// Returns the value as an `NSRect`.
+    /// Returns the value as an `NSRect` if it was created with
+    /// [`from_rect`](#method.from_rect).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1391171-rectvalue).
     #[inline]
     #[doc(alias = "rectValue")]
-    pub fn rect_value(&self) -> NSRect {
-        unsafe { _msg_send_any![self, rectValue] }
+    pub fn rect_value(&self) -> Option<NSRect> {
+        if self.has_objc_type(encoding::RECT) {
+            Some(unsafe { _msg_send_any![self, rectValue] })
+        } else {
+            None
+        }
     }
 
     /// Creates a new value object containing the specified edge insets.
@@ -249,12 +346,90 @@ impl NSValue {
         unsafe { _msg_send_any![Self::class(), valueWithEdgeInsets: value] }
     }
 
-    /// Returns the value as an `NSEdgeInsets`.
+    /// Returns the value as an `NSEdgeInsets` if it was created with
+    /// [`from_edge_insets`](#method.from_edge_insets).
     ///
     /// See [documentation](https://developer.apple.com/documentation/foundation/nsvalue/1391123-edgeinsetsvalue).
     #[inline]
     #[doc(alias = "edgeInsetsValue")]
-    pub fn edge_insets_value(&self) -> NSEdgeInsets {
-        unsafe { _msg_send_any![self, edgeInsetsValue] }
+    pub fn edge_insets_value(&self) -> Option<NSEdgeInsets> {
+        if self.has_objc_type(encoding::EDGE_INSETS) {
+            Some(unsafe { _msg_send_any![self, edgeInsetsValue] })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_graphics::{CGRect, CGSize};
+
+    #[test]
+    fn range() {
+        let range = NSRange {
+            location: 1,
+            length: 2,
+        };
+        let value = NSValue::from_range(range);
+        assert_eq!(value.range_value(), Some(range));
+        assert_eq!(value.point_value(), None);
+        assert_eq!(value.ptr_value(), None);
+    }
+
+    #[test]
+    fn mismatched() {
+        let value = NSValue::from_size(CGSize::new(1.0, 2.0));
+        assert_eq!(value.size_value(), Some(CGSize::new(1.0, 2.0)));
+
+        // Same layout, different type.
+        assert_eq!(value.point_value(), None);
+        assert_eq!(value.range_value(), None);
+    }
+
+    #[test]
+    fn ptr() {
+        let x = 0u8;
+        let ptr: *const c_void = (&x as *const u8).cast();
+        let value = NSValue::from_ptr(ptr);
+        assert_eq!(value.ptr_value(), Some(ptr));
+        assert_eq!(value.rect_value(), None);
+    }
+
+    #[test]
+    fn rect_encoding() {
+        let rect = CGRect::new(1.0, 2.0, 3.0, 4.0);
+        let value = NSValue::from_rect(rect);
+        assert_eq!(value.rect_value(), Some(rect));
+
+        // `CGFloat` is `double` on macOS and 64-bit iOS simulators, but
+        // `float` on 32-bit iOS simulators.
+        #[cfg(target_pointer_width = "64")]
+        let expected = "{CGRect={CGPoint=dd}{CGSize=dd}}";
+        #[cfg(target_pointer_width = "32")]
+        let expected = "{CGRect={CGPoint=ff}{CGSize=ff}}";
+
+        assert_eq!(value.objc_type_cstr().to_str(), Ok(expected));
+    }
+
+    #[test]
+    fn encoded() {
+        #[repr(C)]
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        struct Pair {
+            a: i32,
+            b: f64,
+        }
+
+        let encoding = CStr::from_bytes_with_nul(b"{Pair=id}\0").unwrap();
+        let other = CStr::from_bytes_with_nul(b"{Other=id}\0").unwrap();
+
+        let pair = Pair { a: 1, b: 2.0 };
+        let value = unsafe { NSValue::from_encoded(&pair, encoding) };
+
+        assert_eq!(value.objc_type_cstr(), encoding);
+        assert_eq!(unsafe { value.encoded_value(encoding) }, Some(pair));
+        assert_eq!(unsafe { value.encoded_value::<Pair>(other) }, None);
     }
 }
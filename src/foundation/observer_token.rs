@@ -0,0 +1,46 @@
+use super::{ns_key_value_observing::KeyValueObservation, ns_notification::NotificationObserver};
+use std::fmt;
+
+/// An observer registered by
+/// [`NSNotificationCenter::add_observer`](struct.NSNotificationCenter.html#method.add_observer)
+/// or [`NSObject::observe`](../objc/struct.NSObject.html#method.observe),
+/// which is removed when dropped.
+///
+/// A key-value observation retains the observed object, so that it is always
+/// removed from a live object. The closure is dropped once the observer is
+/// removed.
+#[must_use = "the observer is removed when the token is dropped"]
+pub struct ObserverToken(Observer);
+
+pub(crate) enum Observer {
+    Notification(NotificationObserver),
+    KeyValue(KeyValueObservation),
+}
+
+impl fmt::Debug for ObserverToken {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("ObserverToken");
+        if let Observer::KeyValue(observation) = &self.0 {
+            debug.field("key_path", &observation.key_path.to_string());
+        }
+        debug.finish()
+    }
+}
+
+impl Drop for ObserverToken {
+    #[inline]
+    fn drop(&mut self) {
+        match &self.0 {
+            Observer::Notification(observer) => observer.remove(),
+            Observer::KeyValue(observation) => observation.remove(),
+        }
+    }
+}
+
+impl ObserverToken {
+    #[inline]
+    pub(crate) fn new(observer: Observer) -> Self {
+        Self(observer)
+    }
+}
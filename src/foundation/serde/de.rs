@@ -0,0 +1,249 @@
+use super::Error;
+use crate::foundation::{
+    ns_number::Scalar, NSArray, NSArrayIter, NSData, NSDictionary, NSDictionaryIter, NSNull,
+    NSNumber, NSString,
+};
+use crate::objc::{ClassType, NSObject};
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+type Dictionary = NSDictionary<NSObject<'static>, NSObject<'static>>;
+
+// The kinds of objects that values are converted from.
+enum Kind<'a> {
+    Null,
+    Number(Scalar),
+    String(&'a NSString<'static>),
+    Data(&'a NSData),
+    Array(&'a NSArray<NSObject<'static>>),
+    Dictionary(&'a Dictionary),
+}
+
+impl<'a> Kind<'a> {
+    fn new(object: &'a NSObject<'static>) -> Result<Self, Error> {
+        let ptr: *const NSObject = object;
+
+        let kind = if object.is_kind_of_class(NSNull::class()) {
+            Self::Null
+        } else if object.is_kind_of_class(NSNumber::class()) {
+            Self::Number(unsafe { &*ptr.cast::<NSNumber>() }.scalar())
+        } else if object.is_kind_of_class(NSString::class()) {
+            Self::String(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSData::class()) {
+            Self::Data(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(NSArray::<NSObject>::class()) {
+            Self::Array(unsafe { &*ptr.cast() })
+        } else if object.is_kind_of_class(Dictionary::class()) {
+            Self::Dictionary(unsafe { &*ptr.cast() })
+        } else {
+            let class = crate::objc::ObjectType::class(object).name();
+            let class = class.to_string_lossy().into_owned();
+            return Err(Error::UnsupportedObject(class));
+        };
+        Ok(kind)
+    }
+}
+
+// Returns `key` as a string, since only string keys are converted.
+#[inline]
+fn string_key(key: &NSObject<'static>) -> Result<String, Error> {
+    match Kind::new(key)? {
+        Kind::String(key) => Ok(key.to_string()),
+        _ => Err(Error::KeyMustBeString),
+    }
+}
+
+/// Converts Foundation objects into Rust values.
+pub(super) struct Deserializer<'a> {
+    pub object: &'a NSObject<'static>,
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match Kind::new(self.object)? {
+            Kind::Null => visitor.visit_unit(),
+            Kind::Number(Scalar::Bool(value)) => visitor.visit_bool(value),
+            Kind::Number(Scalar::Signed(value)) => visitor.visit_i64(value),
+            Kind::Number(Scalar::Unsigned(value)) => visitor.visit_u64(value),
+            Kind::Number(Scalar::Float(value)) => visitor.visit_f64(value),
+            Kind::String(string) => visitor.visit_string(string.to_string()),
+            Kind::Data(data) => visitor.visit_bytes(data.as_slice()),
+            Kind::Array(array) => visitor.visit_seq(SeqDeserializer { iter: array.iter() }),
+            Kind::Dictionary(dictionary) => visitor.visit_map(MapDeserializer {
+                iter: dictionary.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.object.is_kind_of_class(NSNull::class()) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Unit variants are strings, and other variants are dictionaries with a
+    // single entry from the variant name to its value.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match Kind::new(self.object)? {
+            Kind::String(variant) => visitor.visit_enum(
+                IntoDeserializer::<Error>::into_deserializer(variant.to_string()),
+            ),
+            Kind::Dictionary(dictionary) if dictionary.len() == 1 => {
+                let (variant, value) = dictionary.iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer {
+                    variant: string_key(variant)?,
+                    value,
+                })
+            }
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other("object"),
+                &"a string or a dictionary with a single entry",
+            )),
+        }
+    }
+
+    #[inline]
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// Deserializes the elements of an `NSArray`.
+struct SeqDeserializer<'a> {
+    iter: NSArrayIter<'a, NSObject<'static>>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.iter.next() {
+            Some(object) => seed.deserialize(Deserializer { object }).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes the entries of an `NSDictionary`.
+struct MapDeserializer<'a> {
+    iter: NSDictionaryIter<'a, NSObject<'static>, NSObject<'static>>,
+    // The value of the key returned by `next_key_seed`.
+    value: Option<&'a NSObject<'static>>,
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let key = string_key(key)?;
+                self.value = Some(value);
+                seed.deserialize(IntoDeserializer::<Error>::into_deserializer(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let object = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(Deserializer { object })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes an enum variant from a single-entry dictionary.
+struct EnumDeserializer<'a> {
+    variant: String,
+    value: &'a NSObject<'static>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+    type Variant = Deserializer<'a>;
+
+    #[inline]
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Deserializer<'a>), Error> {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, Deserializer { object: self.value }))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    #[inline]
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    #[inline]
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,47 @@
+use std::{error, fmt};
+
+/// The error returned when converting between `serde` types and Foundation
+/// objects fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A map key is not a string.
+    ///
+    /// Foundation object graphs such as property lists and JSON objects only
+    /// have string keys, so other keys are not converted.
+    KeyMustBeString,
+    /// An object is not a kind of object that values are converted from, such
+    /// as an `NSDate`. This contains the name of the object's class.
+    UnsupportedObject(String),
+    /// A custom error reported by a `Serialize` or `Deserialize`
+    /// implementation, such as for a missing field or a value of the wrong
+    /// type.
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::KeyMustBeString => f.write_str("map key must be a string"),
+            Self::UnsupportedObject(class) => {
+                write!(f, "cannot convert object of class {:?}", class)
+            }
+            Self::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl ::serde::ser::Error for Error {
+    #[inline]
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::Message(message.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    #[inline]
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::Message(message.to_string())
+    }
+}
@@ -0,0 +1,251 @@
+//! Conversions between [`serde`](https://serde.rs) types and Foundation
+//! objects.
+//!
+//! This makes Rust types usable with APIs that take or return object graphs,
+//! such as [`NSUserDefaults`](../struct.NSUserDefaults.html),
+//! [`NSJSONSerialization`](../struct.NSJSONSerialization.html), and user info
+//! dictionaries.
+//!
+//! # Feature Flag
+//!
+//! This module corresponds to the **`serde`**
+//! [feature flag](../../index.html#feature-flags).
+//!
+//! It also transitively enables [`foundation`](../index.html).
+//!
+//! # Data Model
+//!
+//! Values are converted like in JSON:
+//!
+//! | Rust                                    | Foundation                          |
+//! | --------------------------------------- | ----------------------------------- |
+//! | `bool`, integers, and floats            | `NSNumber`                          |
+//! | `char` and strings                      | `NSString`                          |
+//! | bytes, such as with [`serde_bytes`]     | `NSData`                            |
+//! | `None`, `()`, and unit structs          | `NSNull`                            |
+//! | sequences and tuples                    | `NSArray`                           |
+//! | maps and structs                        | `NSDictionary` with `NSString` keys |
+//! | unit variants                           | `NSString` of the variant name      |
+//! | other variants                          | `NSDictionary` from the variant name to its value |
+//!
+//! Integers above `i64::MAX` are stored as unsigned `NSNumber`s, so that they
+//! are converted back exactly.
+//!
+//! `Vec<u8>` and `&[u8]` are sequences in `serde`, which become `NSArray`s of
+//! numbers. Use [`serde_bytes`] to convert them to `NSData` instead.
+//!
+//! Map keys must be strings, or else [`Error::KeyMustBeString`] is returned.
+//!
+//! [`serde_bytes`]: https://docs.rs/serde_bytes
+//! [`Error::KeyMustBeString`]: enum.Error.html#variant.KeyMustBeString
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use fruity::foundation::serde::{from_object, to_object};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Window {
+//!     title: String,
+//!     size: (f64, f64),
+//!     fullscreen: bool,
+//! }
+//!
+//! let window = Window {
+//!     title: "Fruity".to_string(),
+//!     size: (640.0, 480.0),
+//!     fullscreen: false,
+//! };
+//!
+//! let object = to_object(&window).unwrap();
+//! assert_eq!(from_object::<Window>(&object).unwrap(), window);
+//! ```
+
+use crate::core::Arc;
+use crate::objc::NSObject;
+use ::serde::{de::DeserializeOwned, Serialize};
+
+mod de;
+mod error;
+mod ser;
+
+pub use error::*;
+
+/// Converts `value` into a Foundation object graph.
+///
+/// The result is an `NSDictionary`, `NSArray`, `NSString`, `NSNumber`,
+/// `NSData`, or `NSNull` depending on the [data model](index.html#data-model).
+#[inline]
+pub fn to_object<T: Serialize + ?Sized>(value: &T) -> Result<Arc<NSObject<'static>>, Error> {
+    value.serialize(ser::Serializer)
+}
+
+/// Converts a Foundation object graph into a value of type `T`.
+///
+/// This fails if `object` contains kinds of objects outside of the
+/// [data model](index.html#data-model), such as an `NSDate`, or if its
+/// structure does not match `T`.
+#[inline]
+pub fn from_object<T: DeserializeOwned>(object: &NSObject<'static>) -> Result<T, Error> {
+    T::deserialize(de::Deserializer { object })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSArray, NSData, NSDictionary, NSNumber, NSString};
+    use crate::objc::ClassType;
+    use ::serde::{Deserialize, Serialize};
+    use rand::{distributions::Alphanumeric, Rng};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Kind {
+        Unit,
+        Newtype(i32),
+        Tuple(u8, String),
+        Struct { id: u64, label: Option<String> },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        name: String,
+        count: u64,
+        delta: i32,
+        ratio: f64,
+        enabled: bool,
+        nickname: Option<String>,
+        kind: Kind,
+        tags: Vec<String>,
+        nested: BTreeMap<String, BTreeMap<String, i64>>,
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        numbers: Vec<u8>,
+    }
+
+    fn random_string(rng: &mut impl Rng) -> String {
+        let len = rng.gen_range(0, 12);
+        rng.sample_iter(&Alphanumeric).take(len).collect()
+    }
+
+    fn random_record(rng: &mut impl Rng) -> Record {
+        let kind = match rng.gen_range(0, 4) {
+            0 => Kind::Unit,
+            1 => Kind::Newtype(rng.gen()),
+            2 => Kind::Tuple(rng.gen(), random_string(rng)),
+            _ => Kind::Struct {
+                id: rng.gen(),
+                label: if rng.gen() {
+                    Some(random_string(rng))
+                } else {
+                    None
+                },
+            },
+        };
+
+        let nested = (0..rng.gen_range(0, 4))
+            .map(|_| {
+                let inner = (0..rng.gen_range(0, 4))
+                    .map(|_| (random_string(rng), rng.gen()))
+                    .collect();
+                (random_string(rng), inner)
+            })
+            .collect();
+
+        Record {
+            name: random_string(rng),
+            // Half of these are above `i64::MAX`.
+            count: rng.gen(),
+            delta: rng.gen(),
+            ratio: rng.gen_range(-1e9, 1e9),
+            enabled: rng.gen(),
+            nickname: if rng.gen() {
+                Some(random_string(rng))
+            } else {
+                None
+            },
+            kind,
+            tags: (0..rng.gen_range(0, 4))
+                .map(|_| random_string(rng))
+                .collect(),
+            nested,
+            data: (0..rng.gen_range(0, 16)).map(|_| rng.gen()).collect(),
+            numbers: (0..rng.gen_range(0, 16)).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..200 {
+            let record = random_record(&mut rng);
+            let object = to_object(&record).unwrap();
+            assert!(object.is_kind_of_class(NSDictionary::<NSObject, NSObject>::class()));
+            assert_eq!(from_object::<Record>(&object).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn large_unsigned() {
+        let object = to_object(&u64::MAX).unwrap();
+        let number: &NSNumber = unsafe { &*(&*object as *const NSObject).cast() };
+        assert_eq!(number.as_i64(), None);
+        assert_eq!(number.as_u64(), Some(u64::MAX));
+
+        assert_eq!(from_object::<u64>(&object).unwrap(), u64::MAX);
+        assert!(from_object::<i64>(&object).is_err());
+    }
+
+    #[test]
+    fn bytes() {
+        let bytes = serde_bytes::ByteBuf::from(vec![1, 2, 3]);
+        let data = to_object(&bytes).unwrap();
+        assert!(data.is_kind_of_class(NSData::class()));
+        let back: serde_bytes::ByteBuf = from_object(&data).unwrap();
+        assert_eq!(back, bytes);
+
+        // Without `serde_bytes`, bytes are numbers in an array.
+        let array = to_object(&vec![1u8, 2, 3]).unwrap();
+        assert!(array.is_kind_of_class(NSArray::<NSObject>::class()));
+        assert_eq!(from_object::<Vec<u8>>(&array).unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn enums() {
+        let unit = to_object(&Kind::Unit).unwrap();
+        assert!(unit.is_kind_of_class(NSString::class()));
+        assert_eq!(from_object::<Kind>(&unit).unwrap(), Kind::Unit);
+
+        let newtype = to_object(&Kind::Newtype(7)).unwrap();
+        let dictionary: &NSDictionary<NSString, NSObject> =
+            unsafe { &*(&*newtype as *const NSObject).cast() };
+        assert_eq!(dictionary.len(), 1);
+        assert!(dictionary.contains_key(&NSString::from_str("Newtype")));
+    }
+
+    #[test]
+    fn non_string_keys() {
+        let mut map = HashMap::new();
+        map.insert(1, "one");
+        assert_eq!(to_object(&map).err(), Some(Error::KeyMustBeString));
+
+        let key = NSNumber::from_int(1);
+        let value = NSString::from_str("one");
+        let dictionary = NSDictionary::<NSNumber, NSString>::from_pairs(&[(&key, &value)]);
+        assert_eq!(
+            from_object::<HashMap<String, String>>(&dictionary).unwrap_err(),
+            Error::KeyMustBeString
+        );
+    }
+
+    #[test]
+    fn unsupported_object() {
+        let object = Arc::<NSObject>::default();
+        assert_eq!(
+            from_object::<String>(&object).unwrap_err(),
+            Error::UnsupportedObject("NSObject".to_string())
+        );
+    }
+}
@@ -0,0 +1,580 @@
+use super::Error;
+use crate::core::{Arc, ObjectType};
+use crate::foundation::{NSArray, NSData, NSDictionary, NSNull, NSNumber, NSString};
+use crate::objc::NSObject;
+use ::serde::ser::{self, Serialize};
+
+type Object = Arc<NSObject<'static>>;
+
+// Upcasts a Foundation object, which is always an `NSObject`.
+#[inline]
+fn object<T: ObjectType>(value: Arc<T>) -> Object {
+    unsafe { Arc::cast_unchecked(value) }
+}
+
+#[inline]
+fn null() -> Object {
+    object(Arc::retain(NSNull::null()))
+}
+
+fn array(values: &[Object]) -> Object {
+    let values: Vec<&NSObject<'static>> = values.iter().map(|value| &**value).collect();
+    object(NSArray::from_slice(&values))
+}
+
+fn dictionary(pairs: &[(Arc<NSString<'static>>, Object)]) -> Object {
+    let pairs: Vec<(&NSString<'static>, &NSObject<'static>)> = pairs
+        .iter()
+        .map(|(key, value)| (&**key, &**value))
+        .collect();
+    object(NSDictionary::from_pairs(&pairs))
+}
+
+// Wraps the value of an enum variant in a single-entry dictionary keyed by
+// the variant name, like `serde_json`.
+fn variant(name: &str, value: Object) -> Object {
+    dictionary(&[(NSString::from_str(name), value)])
+}
+
+/// Converts Rust values into Foundation objects.
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Object;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeDictionary;
+    type SerializeStruct = SerializeDictionary;
+    type SerializeStructVariant = SerializeDictionary;
+
+    #[inline]
+    fn serialize_bool(self, value: bool) -> Result<Object, Error> {
+        Ok(object(NSNumber::from_bool(value)))
+    }
+
+    #[inline]
+    fn serialize_i8(self, value: i8) -> Result<Object, Error> {
+        self.serialize_i64(value.into())
+    }
+
+    #[inline]
+    fn serialize_i16(self, value: i16) -> Result<Object, Error> {
+        self.serialize_i64(value.into())
+    }
+
+    #[inline]
+    fn serialize_i32(self, value: i32) -> Result<Object, Error> {
+        self.serialize_i64(value.into())
+    }
+
+    #[inline]
+    fn serialize_i64(self, value: i64) -> Result<Object, Error> {
+        Ok(object(NSNumber::from_longlong(value)))
+    }
+
+    #[inline]
+    fn serialize_u8(self, value: u8) -> Result<Object, Error> {
+        self.serialize_u64(value.into())
+    }
+
+    #[inline]
+    fn serialize_u16(self, value: u16) -> Result<Object, Error> {
+        self.serialize_u64(value.into())
+    }
+
+    #[inline]
+    fn serialize_u32(self, value: u32) -> Result<Object, Error> {
+        self.serialize_u64(value.into())
+    }
+
+    // Values above `i64::MAX` keep their unsigned type, so they are read back
+    // exactly.
+    #[inline]
+    fn serialize_u64(self, value: u64) -> Result<Object, Error> {
+        Ok(object(NSNumber::from_unsigned_longlong(value)))
+    }
+
+    #[inline]
+    fn serialize_f32(self, value: f32) -> Result<Object, Error> {
+        Ok(object(NSNumber::from_float(value)))
+    }
+
+    #[inline]
+    fn serialize_f64(self, value: f64) -> Result<Object, Error> {
+        Ok(object(NSNumber::from_double(value)))
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Object, Error> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Object, Error> {
+        Ok(object(NSString::from_str(value)))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, value: &[u8]) -> Result<Object, Error> {
+        Ok(object(NSData::from_slice(value)))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Object, Error> {
+        Ok(null())
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Object, Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Object, Error> {
+        Ok(null())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Object, Error> {
+        Ok(null())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Object, Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Object, Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Object, Error> {
+        Ok(variant(name, value.serialize(self)?))
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            variant: None,
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            variant: Some(variant),
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeDictionary, Error> {
+        Ok(SerializeDictionary {
+            variant: None,
+            pairs: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeDictionary, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeDictionary, Error> {
+        Ok(SerializeDictionary {
+            variant: Some(variant),
+            pairs: Vec::with_capacity(len),
+            key: None,
+        })
+    }
+}
+
+/// Collects the elements of a sequence or tuple into an `NSArray`.
+pub(super) struct SerializeArray {
+    variant: Option<&'static str>,
+    values: Vec<Object>,
+}
+
+impl SerializeArray {
+    #[inline]
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.values.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn finish(self) -> Result<Object, Error> {
+        let array = array(&self.values);
+        Ok(match self.variant {
+            Some(name) => variant(name, array),
+            None => array,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+/// Collects the entries of a map or the fields of a struct into an
+/// `NSDictionary`.
+pub(super) struct SerializeDictionary {
+    variant: Option<&'static str>,
+    pairs: Vec<(Arc<NSString<'static>>, Object)>,
+    // The key passed to `serialize_key` before its value.
+    key: Option<Arc<NSString<'static>>>,
+}
+
+impl SerializeDictionary {
+    #[inline]
+    fn finish(self) -> Result<Object, Error> {
+        let dictionary = dictionary(&self.pairs);
+        Ok(match self.variant {
+            Some(name) => variant(name, dictionary),
+            None => dictionary,
+        })
+    }
+}
+
+impl ser::SerializeMap for SerializeDictionary {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.pairs.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeDictionary {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.pairs
+            .push((NSString::from_str(key), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeDictionary {
+    type Ok = Object;
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object, Error> {
+        self.finish()
+    }
+}
+
+/// Converts map keys into strings, failing for any other kind of key.
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = Arc<NSString<'static>>;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<Self::Ok, Error>;
+    type SerializeTuple = ser::Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = ser::Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = ser::Impossible<Self::Ok, Error>;
+    type SerializeMap = ser::Impossible<Self::Ok, Error>;
+    type SerializeStruct = ser::Impossible<Self::Ok, Error>;
+    type SerializeStructVariant = ser::Impossible<Self::Ok, Error>;
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Error> {
+        Ok(NSString::from_str(value))
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Error> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _value: bool) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_i8(self, _value: i8) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_i16(self, _value: i16) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_i32(self, _value: i32) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_i64(self, _value: i64) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_u8(self, _value: u8) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_u16(self, _value: u16) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_u32(self, _value: u32) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_u64(self, _value: u64) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::KeyMustBeString)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::KeyMustBeString)
+    }
+}
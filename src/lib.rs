@@ -50,6 +50,21 @@
 //! This feature transitively enables the [`objc`](objc/index.html) and
 //! [`core_graphics`](core_graphics/index.html) features/modules.
 //!
+//! The **`catch_all`** feature turns Objective-C exceptions thrown by message
+//! sends into Rust panics, at the cost of a call through an exception handler
+//! per message. Without it, uncaught exceptions terminate the program unless
+//! caught by [`objc::catch_exception`](objc/fn.catch_exception.html).
+//!
+//! The **`async`** feature adds futures for asynchronous APIs, such as
+//! [`NSURLSession::data_async`](foundation/struct.NSURLSession.html#method.data_async).
+//! They work with any executor. This feature transitively enables the
+//! [`foundation`](foundation/index.html) feature.
+//!
+//! The **`serde`** feature adds
+//! [`foundation::serde`](foundation/serde/index.html) for converting between
+//! [`serde`](https://serde.rs) types and Foundation objects. This feature
+//! transitively enables the [`foundation`](foundation/index.html) feature.
+//!
 //! # Goals
 //!
 //! ## Idiomatic Rust
@@ -151,6 +166,9 @@
 //!   macro creates an `NSString` literal (i.e. `@"string"`) at compile time.
 //!   There is no runtime dispatch/allocation/initialization cost.
 //!
+//!   Similarly, the [`cf_string!`](macro.cf_string.html) macro creates a
+//!   `CFString` literal (i.e. `CFSTR("string")`) at compile time.
+//!
 //! Some parts of this library are still not zero cost. Your help would be much
 //! appreciated here!
 //!
@@ -186,7 +204,7 @@ pub(crate) mod common;
 pub mod core;
 
 // This module is not for public use. It is an implementation detail of macros
-// exposed by this crate.
+// exposed by this crate and of shared internal helpers.
 #[doc(hidden)]
 pub mod _priv;
 
@@ -0,0 +1,383 @@
+use super::{
+    retain_autoreleased, sys, Class, ClassBuilder, ClassType, Imp, NSObject, ObjCObject, Sel,
+};
+use crate::_priv::abort_on_panic;
+use crate::core::Arc;
+use std::{
+    any::Any,
+    ffi::c_void,
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Once,
+    },
+};
+
+/// A key for an associated object, whose address identifies the association.
+///
+/// Keys are meant to be declared as statics:
+///
+/// ```
+/// use fruity::objc::AssocKey;
+///
+/// static NAME: AssocKey = AssocKey::new();
+/// ```
+///
+/// Keys are not zero-sized, because distinct zero-sized statics may share an
+/// address.
+pub struct AssocKey {
+    _byte: u8,
+}
+
+impl fmt::Debug for AssocKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AssocKey").field(&self.as_ptr()).finish()
+    }
+}
+
+impl AssocKey {
+    /// Creates a new key.
+    #[inline]
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self { _byte: 0 }
+    }
+
+    #[inline]
+    pub(super) fn as_ptr(&self) -> *const c_void {
+        (self as *const Self).cast()
+    }
+}
+
+/// How an associated object is stored by the object that it is associated
+/// with.
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/objc_associationpolicy).
+#[repr(usize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AssociationPolicy {
+    /// The value is not retained, like a `weak` property without zeroing.
+    ///
+    /// This is only accepted by
+    /// [`NSObject::set_associated_unretained`](struct.NSObject.html#method.set_associated_unretained).
+    #[doc(alias = "OBJC_ASSOCIATION_ASSIGN")]
+    Assign = 0,
+    /// The value is retained, and accessed nonatomically.
+    #[doc(alias = "OBJC_ASSOCIATION_RETAIN_NONATOMIC")]
+    RetainNonatomic = 1,
+    /// The value is copied, and accessed nonatomically.
+    #[doc(alias = "OBJC_ASSOCIATION_COPY_NONATOMIC")]
+    CopyNonatomic = 3,
+    /// The value is retained, and accessed atomically.
+    #[doc(alias = "OBJC_ASSOCIATION_RETAIN")]
+    Retain = 0o1401,
+    /// The value is copied, and accessed atomically.
+    #[doc(alias = "OBJC_ASSOCIATION_COPY")]
+    Copy = 0o1403,
+}
+
+impl Default for AssociationPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::Retain
+    }
+}
+
+/// [Associated objects](https://developer.apple.com/documentation/objectivec/objective-c_runtime#associative-references).
+impl NSObject<'_> {
+    #[inline]
+    fn as_objc_ptr(&self) -> *const ObjCObject<'static> {
+        (self as *const Self).cast()
+    }
+
+    /// Associates `value` with `self` for `key`, replacing any previous
+    /// value. The association is removed if `value` is `None`.
+    ///
+    /// Associated values are released when `self` is deallocated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `policy` is
+    /// [`Assign`](enum.AssociationPolicy.html#variant.Assign), since the
+    /// association would not keep `value` alive.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/objc_setassociatedobject(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "objc_setAssociatedObject")]
+    pub fn set_associated(
+        &self,
+        key: &'static AssocKey,
+        value: Option<&NSObject<'static>>,
+        policy: AssociationPolicy,
+    ) {
+        assert_ne!(
+            policy,
+            AssociationPolicy::Assign,
+            "Unretained associations require `set_associated_unretained`"
+        );
+        unsafe { self.set_associated_raw(key, value, policy) }
+    }
+
+    /// Associates `value` with `self` for `key` without retaining it, like
+    /// [`AssociationPolicy::Assign`](enum.AssociationPolicy.html#variant.Assign).
+    ///
+    /// # Safety
+    ///
+    /// `value` must outlive the association, or the association must be
+    /// replaced or removed before `value` is deallocated.
+    #[inline]
+    pub unsafe fn set_associated_unretained(
+        &self,
+        key: &'static AssocKey,
+        value: Option<&NSObject<'static>>,
+    ) {
+        self.set_associated_raw(key, value, AssociationPolicy::Assign)
+    }
+
+    #[inline]
+    unsafe fn set_associated_raw(
+        &self,
+        key: &'static AssocKey,
+        value: Option<&NSObject<'static>>,
+        policy: AssociationPolicy,
+    ) {
+        let value = value.map_or(ptr::null(), |value| value.as_objc_ptr());
+        sys::objc_setAssociatedObject(self.as_objc_ptr(), key.as_ptr(), value, policy as usize)
+    }
+
+    /// Returns the value associated with `self` for `key`, or `None` if there
+    /// is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/objc_getassociatedobject(_:_:)).
+    #[inline]
+    #[doc(alias = "objc_getAssociatedObject")]
+    pub fn get_associated(&self, key: &'static AssocKey) -> Option<Arc<NSObject<'static>>> {
+        unsafe {
+            // Atomic policies return autoreleased values.
+            retain_autoreleased(|| {
+                sys::objc_getAssociatedObject(self.as_objc_ptr(), key.as_ptr()).cast()
+            })
+        }
+    }
+
+    /// Associates a Rust value with `self` for `key`, replacing any previous
+    /// value.
+    ///
+    /// The value is stored in an object that drops it once released, which
+    /// happens when the association is replaced or `self` is deallocated.
+    /// Because that may happen on any thread, and the value can be accessed
+    /// through `self` on any thread, it must be `Send` and `Sync`.
+    pub fn set_associated_box<T: Any + Send + Sync>(&self, key: &'static AssocKey, value: T) {
+        let object: Arc<NSObject<'static>> = unsafe { box_class().alloc_init() };
+
+        let value: Box<dyn Any + Send + Sync> = Box::new(value);
+        let value = Box::into_raw(Box::new(value));
+        unsafe { object.set_ivar::<*mut c_void>(BOX_IVAR, value.cast()) };
+
+        self.set_associated(key, Some(&*object), AssociationPolicy::Retain);
+    }
+
+    /// Returns the Rust value associated with `self` for `key`, or `None` if
+    /// there is none or it is not a `T`.
+    ///
+    /// The value is kept alive by the returned reference, even if the
+    /// association is replaced.
+    pub fn get_associated_box<T: Any + Send + Sync>(
+        &self,
+        key: &'static AssocKey,
+    ) -> Option<AssocRef<T>> {
+        let object = self.get_associated(key)?;
+        if !object.is_member_of_class(box_class()) {
+            return None;
+        }
+
+        let value = unsafe { box_value(&object) }?;
+        if value.is::<T>() {
+            Some(AssocRef {
+                object,
+                marker: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// A reference to a Rust value associated with an object by
+/// [`NSObject::set_associated_box`](struct.NSObject.html#method.set_associated_box).
+pub struct AssocRef<T> {
+    // The box object that owns the value.
+    object: Arc<NSObject<'static>>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Any + Send + Sync> Deref for AssocRef<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { box_value(&self.object) }
+            .and_then(|value| value.downcast_ref())
+            .unwrap()
+    }
+}
+
+impl<T: Any + Send + Sync + fmt::Debug> fmt::Debug for AssocRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+const BOX_IVAR: &str = "_value";
+
+/// Returns the value of a box object.
+///
+/// # Safety
+///
+/// `object` must be an instance of `box_class()`.
+#[inline]
+unsafe fn box_value<'a>(object: &'a NSObject<'static>) -> Option<&'a (dyn Any + Send + Sync)> {
+    let value = object.get_ivar::<*mut c_void>(BOX_IVAR);
+    let value = value.cast::<Box<dyn Any + Send + Sync>>().as_ref()?;
+    Some(&**value)
+}
+
+/// Returns the class of objects that own a Rust value, which is dropped in
+/// `-dealloc`.
+fn box_class() -> &'static Class {
+    static REGISTER: Once = Once::new();
+    static CLASS: AtomicPtr<Class> = AtomicPtr::new(ptr::null_mut());
+
+    extern "C" fn dealloc(this: &NSObject<'static>, _cmd: Sel) {
+        unsafe {
+            let value = this.get_ivar::<*mut c_void>(BOX_IVAR);
+            if !value.is_null() {
+                let value = Box::from_raw(value.cast::<Box<dyn Any + Send + Sync>>());
+                abort_on_panic(|| drop(value));
+            }
+
+            // Call `-[NSObject dealloc]`, which is the superclass
+            // implementation.
+            type Dealloc = unsafe extern "C" fn(&NSObject<'static>, Sel);
+            let imp = NSObject::class()
+                .get_instance_method(selector!(dealloc))
+                .expect("NSObject does not implement -dealloc")
+                .implementation();
+            mem::transmute::<Imp, Dealloc>(imp)(this, selector!(dealloc));
+        }
+    }
+
+    REGISTER.call_once(|| {
+        let mut builder = ClassBuilder::new_private("_FruityAssociatedBox", NSObject::class());
+        builder
+            .add_ivar::<*mut c_void>(BOX_IVAR)
+            .expect("Failed to add box instance variable");
+        builder
+            .add_method0(selector!(dealloc), dealloc)
+            .expect("Failed to add box -dealloc");
+        let class = builder.register().unwrap();
+        CLASS.store(class as *const Class as *mut Class, Ordering::Release);
+    });
+
+    unsafe { &*CLASS.load(Ordering::Acquire) }
+}
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::foundation::NSString;
+    use crate::objc::autoreleasepool;
+    use std::sync::{
+        self,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    static KEY: AssocKey = AssocKey::new();
+    static OTHER_KEY: AssocKey = AssocKey::new();
+
+    #[test]
+    fn string() {
+        let host = Arc::<NSObject>::default();
+        assert!(host.get_associated(&KEY).is_none());
+
+        let value = NSString::from_str("value");
+        host.set_associated(&KEY, Some(&**value), AssociationPolicy::RetainNonatomic);
+        let associated = host.get_associated(&KEY).unwrap();
+        assert_eq!(
+            associated.downcast_ref::<NSString>().unwrap().to_string(),
+            "value"
+        );
+        assert!(host.get_associated(&OTHER_KEY).is_none());
+
+        // Copying an immutable string returns the same string.
+        host.set_associated(&KEY, Some(&**value), AssociationPolicy::Copy);
+        assert!(*host.get_associated(&KEY).unwrap() == **value);
+
+        host.set_associated(&KEY, None, AssociationPolicy::Retain);
+        assert!(host.get_associated(&KEY).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn assign() {
+        let host = Arc::<NSObject>::default();
+        let value = Arc::<NSObject>::default();
+        host.set_associated(&KEY, Some(&*value), AssociationPolicy::Assign);
+    }
+
+    #[test]
+    fn boxed() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let host = Arc::<NSObject>::default();
+        host.set_associated_box(&KEY, Point { x: 1, y: 2 });
+
+        let point = host.get_associated_box::<Point>(&KEY).unwrap();
+        assert_eq!(*point, Point { x: 1, y: 2 });
+        assert!(host.get_associated_box::<String>(&KEY).is_none());
+
+        // Plain objects are not boxes.
+        host.set_associated(&OTHER_KEY, Some(&*host), AssociationPolicy::RetainNonatomic);
+        assert!(host.get_associated_box::<Point>(&OTHER_KEY).is_none());
+        host.set_associated(&OTHER_KEY, None, AssociationPolicy::RetainNonatomic);
+
+        // The reference outlives the association.
+        host.set_associated(&KEY, None, AssociationPolicy::Retain);
+        assert_eq!(point.x, 1);
+    }
+
+    #[test]
+    fn drop_with_host() {
+        struct SetOnDrop(sync::Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = sync::Arc::new(AtomicBool::new(false));
+
+        autoreleasepool(|_| {
+            let host = Arc::<NSObject>::default();
+            host.set_associated_box(&KEY, SetOnDrop(dropped.clone()));
+
+            // Getting the value autoreleases the box.
+            assert!(host.get_associated_box::<SetOnDrop>(&KEY).is_some());
+            drop(host);
+        });
+
+        assert!(dropped.load(Ordering::SeqCst));
+        assert_eq!(sync::Arc::strong_count(&dropped), 1);
+    }
+}
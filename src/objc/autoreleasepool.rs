@@ -1,8 +1,14 @@
-use std::ffi::c_void;
+use super::ObjectType;
+use crate::core::Arc;
+use std::{ffi::c_void, fmt};
 
 /// Calls a function in the context of a new autorelease pool, like
 /// `@autoreleasepool`.
 ///
+/// Objects that are autoreleased within `f` are released when it returns.
+/// Pools can be nested, in which case objects are added to the innermost
+/// pool.
+///
 /// See [documentation](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/MemoryMgmt/Articles/mmAutoreleasePools.html).
 ///
 /// # Panic Handling
@@ -12,13 +18,97 @@ use std::ffi::c_void;
 ///
 /// This is implemented with
 /// [`Drop`](https://doc.rust-lang.org/std/ops/trait.Drop.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use fruity::objc::autoreleasepool;
+///
+/// for _ in 0..100 {
+///     autoreleasepool(|_pool| {
+///         // Temporary objects of each iteration are released here.
+///     });
+/// }
+/// ```
 #[inline]
 pub fn autoreleasepool<F, T>(f: F) -> T
 where
-    F: FnOnce() -> T,
+    F: FnOnce(&AutoreleasePool) -> T,
+{
+    let pool = AutoreleasePool {
+        context: unsafe { objc_autoreleasePoolPush() },
+    };
+    f(&pool)
+}
+
+/// An autorelease pool that is active for the duration of a call to
+/// [`autoreleasepool`](fn.autoreleasepool.html).
+///
+/// References to this are passed to the called function, and bound the
+/// lifetime of objects [autoreleased](#method.autorelease) into the pool.
+pub struct AutoreleasePool {
+    // Pools are specific to a thread, which this pointer makes `!Send` and
+    // `!Sync`.
+    context: *mut c_void,
+}
+
+impl fmt::Debug for AutoreleasePool {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AutoreleasePool")
+            .field(&self.context)
+            .finish()
+    }
+}
+
+impl Drop for AutoreleasePool {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { objc_autoreleasePoolPop(self.context) };
+    }
+}
+
+impl AutoreleasePool {
+    /// Adds `obj` to this pool without releasing it, returning a reference
+    /// that is valid until the pool is drained.
+    ///
+    /// This transfers ownership of the reference held by `obj` to the pool,
+    /// like `-autorelease`. It is intended for returning objects from method
+    /// implementations that the caller does not own.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/autorelease()).
+    ///
+    /// # Safety
+    ///
+    /// `self` must be the innermost autorelease pool on the current thread,
+    /// including pools created by Objective-C code. Otherwise the object may
+    /// be released by an inner pool while the reference is still in use.
+    #[inline]
+    #[doc(alias = "objc_autorelease")]
+    pub unsafe fn autorelease<'p, 'data, T>(&'p self, obj: Arc<T>) -> &'p T
+    where
+        T: ObjectType<'data> + 'p,
+    {
+        &*objc_autorelease(Arc::into_raw(obj).cast()).cast::<T>()
+    }
+}
+
+/// Calls `f` in a new autorelease pool and retains the possibly-nil object it
+/// returns.
+///
+/// This is for methods that return autoreleased objects, such as
+/// `-[NSURL host]`, so that the result outlives the pool.
+///
+/// # Safety
+///
+/// The pointer returned by `f` must be null or point to a valid object.
+#[inline]
+pub(crate) unsafe fn retain_autoreleased<T, F>(f: F) -> Option<Arc<T>>
+where
+    T: crate::core::ObjectType,
+    F: FnOnce() -> *const T,
 {
-    let _pool = AutoreleasePool(unsafe { objc_autoreleasePoolPush() });
-    f()
+    autoreleasepool(|_| f().as_ref().map(Arc::retain))
 }
 
 // For macOS 10.6 and lower, Clang emits `[[NSAutoreleasePool alloc] init]` and
@@ -27,13 +117,57 @@ where
 extern "C" {
     fn objc_autoreleasePoolPush() -> *mut c_void;
     fn objc_autoreleasePoolPop(pool: *mut c_void);
+
+    fn objc_autorelease(obj: *const c_void) -> *const c_void;
 }
 
-struct AutoreleasePool(*mut c_void);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::NSObject;
+    use std::panic;
 
-impl Drop for AutoreleasePool {
-    #[inline]
-    fn drop(&mut self) {
-        unsafe { objc_autoreleasePoolPop(self.0) };
+    #[test]
+    fn release_on_pop() {
+        let object = Arc::<NSObject>::default();
+        assert_eq!(object.retain_count(), 1);
+
+        autoreleasepool(|pool| {
+            let autoreleased = unsafe { pool.autorelease(object.clone()) };
+            assert_eq!(autoreleased.retain_count(), 2);
+        });
+        assert_eq!(object.retain_count(), 1);
+    }
+
+    #[test]
+    fn nested() {
+        let object = Arc::<NSObject>::default();
+
+        autoreleasepool(|outer| {
+            unsafe { outer.autorelease(object.clone()) };
+
+            autoreleasepool(|inner| {
+                unsafe { inner.autorelease(object.clone()) };
+                assert_eq!(object.retain_count(), 3);
+            });
+
+            // Only the inner pool was drained.
+            assert_eq!(object.retain_count(), 2);
+        });
+        assert_eq!(object.retain_count(), 1);
+    }
+
+    #[test]
+    fn drain_on_panic() {
+        let object = Arc::<NSObject>::default();
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            autoreleasepool(|pool| {
+                unsafe { pool.autorelease(object.clone()) };
+                panic!("unwinding through the pool");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(object.retain_count(), 1);
     }
 }
@@ -0,0 +1,559 @@
+//! Passing Rust closures as Objective-C blocks, and calling received blocks.
+//!
+//! See the [Block ABI](https://clang.llvm.org/docs/Block-ABI-Apple.html).
+
+use super::{Class, Encode, EncodeArgs, Encoding};
+use std::{
+    cell::UnsafeCell,
+    collections::BTreeMap,
+    ffi::{c_void, CStr, CString},
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    os::raw::{c_char, c_int, c_ulong},
+    ptr::{self, NonNull},
+    sync::Mutex,
+};
+
+extern "C" {
+    static _NSConcreteStackBlock: Class;
+
+    fn _Block_copy(block: *const c_void) -> *mut c_void;
+    fn _Block_release(block: *const c_void);
+}
+
+const BLOCK_HAS_COPY_DISPOSE: c_int = 1 << 25;
+const BLOCK_HAS_SIGNATURE: c_int = 1 << 30;
+
+#[repr(C)]
+struct BlockDescriptor {
+    reserved: c_ulong,
+    size: c_ulong,
+    copy: unsafe extern "C" fn(dst: *mut c_void, src: *const c_void),
+    dispose: unsafe extern "C" fn(block: *mut c_void),
+    // Only read if the block has `BLOCK_HAS_SIGNATURE`.
+    signature: *const c_char,
+}
+
+// Descriptors are immutable and their signatures are never freed.
+unsafe impl Sync for BlockDescriptor {}
+unsafe impl Send for BlockDescriptor {}
+
+/// The memory layout shared by `StackBlock` and the heap copies of
+/// `RcBlock`.
+#[repr(C)]
+struct BlockLayout {
+    isa: *const Class,
+    flags: c_int,
+    reserved: c_int,
+    invoke: *const c_void,
+    descriptor: *const BlockDescriptor,
+    closure: *const c_void,
+    // Called by `dispose_helper` with `closure`. Only drops for `RcBlock`.
+    drop_closure: unsafe fn(*const c_void),
+}
+
+// The size does not depend on the closure because it is behind a pointer.
+const BLOCK_SIZE: c_ulong = mem::size_of::<BlockLayout>() as c_ulong;
+
+static UNSIGNED_DESCRIPTOR: BlockDescriptor = BlockDescriptor {
+    reserved: 0,
+    size: BLOCK_SIZE,
+    copy: copy_helper,
+    dispose: dispose_helper,
+    signature: ptr::null(),
+};
+
+/// Returns the descriptor for blocks with `A` and `R`, whose signature is
+/// derived from their encodings.
+///
+/// Descriptors are created once per signature and never freed, since heap
+/// copies of blocks may outlive any owner.
+fn signed_descriptor<A: EncodeArgs, R: Encode>() -> &'static BlockDescriptor {
+    static DESCRIPTORS: Mutex<BTreeMap<CString, &'static BlockDescriptor>> =
+        Mutex::new(BTreeMap::new());
+
+    let signature = Encoding::block(&R::ENCODING, A::ENCODINGS);
+
+    // Descriptors are only inserted while locked, so this cannot be poisoned
+    // with a partial update.
+    let mut descriptors = DESCRIPTORS
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    descriptors
+        .entry(signature)
+        .or_insert_with_key(|signature| {
+            Box::leak(Box::new(BlockDescriptor {
+                reserved: 0,
+                size: BLOCK_SIZE,
+                copy: copy_helper,
+                dispose: dispose_helper,
+                signature: Box::leak(signature.clone().into_boxed_c_str()).as_ptr(),
+            }))
+        })
+}
+
+impl BlockLayout {
+    #[inline]
+    fn new<A, R, F: BlockFn<A, R>>(
+        closure: *const F,
+        drop_closure: unsafe fn(*const c_void),
+        descriptor: &'static BlockDescriptor,
+    ) -> Self {
+        let mut flags = BLOCK_HAS_COPY_DISPOSE;
+        if !descriptor.signature.is_null() {
+            flags |= BLOCK_HAS_SIGNATURE;
+        }
+
+        Self {
+            isa: unsafe { &_NSConcreteStackBlock },
+            flags,
+            reserved: 0,
+            invoke: F::invoke_ptr(),
+            descriptor,
+            closure: closure.cast(),
+            drop_closure,
+        }
+    }
+}
+
+/// An Objective-C block that takes the arguments `A` and returns `R`.
+///
+/// This is an opaque type meant to be used behind a shared reference
+/// `&Block`, such as one received from an Objective-C API. Blocks are
+/// created from Rust closures with [`StackBlock`](struct.StackBlock.html) and
+/// [`RcBlock`](struct.RcBlock.html), which dereference to this type.
+///
+/// `A` is a tuple of argument types, such as `(&NSObject, NSUInteger)`.
+///
+/// See [documentation](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/Blocks/Articles/00_Introduction.html).
+#[repr(C)]
+pub struct Block<A, R> {
+    // Stores data that may be mutated behind a shared reference. Internal
+    // mutability triggers undefined behavior without `UnsafeCell`.
+    _data: UnsafeCell<[u8; 0]>,
+    // Blocks may capture anything, so they must not be shared across
+    // threads.
+    _marker: PhantomData<*const ()>,
+    _signature: PhantomData<fn(A) -> R>,
+}
+
+impl<A, R> fmt::Debug for Block<A, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Block").field(&self.as_ptr()).finish()
+    }
+}
+
+unsafe impl<A, R> Encode for &Block<A, R> {
+    const ENCODING: Encoding<'static> = Encoding::Block;
+}
+
+unsafe impl<A, R> Encode for Option<&Block<A, R>> {
+    const ENCODING: Encoding<'static> = Encoding::Block;
+}
+
+impl<A, R> Block<A, R> {
+    /// Returns a reference to the block at `ptr`, or `None` if it is null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid block that takes `A` and returns `R`,
+    /// which lives for `'a`.
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        ptr.cast::<Self>().as_ref()
+    }
+
+    /// Returns a pointer to this block, which may be passed as a block
+    /// argument.
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_void {
+        (self as *const Self).cast()
+    }
+
+    /// Returns the signature of this block, or `None` if it was created
+    /// without one.
+    #[inline]
+    pub fn signature(&self) -> Option<&CStr> {
+        unsafe {
+            let layout = &*self.as_ptr().cast::<BlockLayout>();
+            if layout.flags & BLOCK_HAS_SIGNATURE == 0 {
+                return None;
+            }
+
+            // The signature follows the copy and dispose helpers if the block
+            // has them.
+            let descriptor = layout.descriptor.cast::<*const c_void>();
+            let signature = if layout.flags & BLOCK_HAS_COPY_DISPOSE == 0 {
+                descriptor.add(2)
+            } else {
+                descriptor.add(4)
+            };
+            let signature = *signature.cast::<*const c_char>();
+            if signature.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(signature))
+            }
+        }
+    }
+
+    /// Copies this block to the heap, or retains it if it is already on the
+    /// heap, so that it can outlive its creator.
+    ///
+    /// # Safety
+    ///
+    /// Whatever the block captures must outlive the copy. This is always the
+    /// case for blocks created by Objective-C and for
+    /// [`RcBlock`](struct.RcBlock.html), but not for
+    /// [`StackBlock`](struct.StackBlock.html), whose closure is borrowed.
+    #[inline]
+    #[doc(alias = "Block_copy")]
+    #[doc(alias = "_Block_copy")]
+    pub unsafe fn copy(&self) -> RcBlock<A, R> {
+        let ptr = _Block_copy(self.as_ptr());
+        RcBlock {
+            ptr: NonNull::new(ptr.cast()).expect("Failed to copy block"),
+        }
+    }
+}
+
+impl<A: BlockArgs, R> Block<A, R> {
+    /// Calls this block with `args`.
+    ///
+    /// Panics from Rust closures abort the process rather than unwinding out
+    /// of the block.
+    #[inline]
+    pub fn call(&self, args: A) -> R {
+        unsafe { A::invoke(self.as_ptr(), args) }
+    }
+}
+
+/// A block on the stack that borrows a closure.
+///
+/// The closure is stored by reference, so copying the block into a
+/// [`RcBlock`](struct.RcBlock.html) does not copy or move the closure, and the
+/// copy must not outlive the closure. This
+/// makes it only suitable for APIs that invoke the block synchronously and do
+/// not keep it around after returning, such as
+/// `-[NSArray indexOfObjectPassingTest:]`.
+#[repr(C)]
+pub struct StackBlock<'a, A, R> {
+    layout: BlockLayout,
+    marker: PhantomData<&'a ()>,
+    signature: PhantomData<fn(A) -> R>,
+}
+
+impl<A, R> fmt::Debug for StackBlock<'_, A, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<A, R> Deref for StackBlock<'_, A, R> {
+    type Target = Block<A, R>;
+
+    #[inline]
+    fn deref(&self) -> &Block<A, R> {
+        unsafe { &*(self as *const Self).cast() }
+    }
+}
+
+unsafe fn drop_nothing(_: *const c_void) {}
+
+impl<'a, A: EncodeArgs, R: Encode> StackBlock<'a, A, R> {
+    /// Creates a block that calls `closure`, with a signature derived from
+    /// the encodings of `A` and `R`.
+    #[inline]
+    pub fn new<F: BlockFn<A, R>>(closure: &'a F) -> Self {
+        Self {
+            layout: BlockLayout::new(closure, drop_nothing, signed_descriptor::<A, R>()),
+            marker: PhantomData,
+            signature: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, R> StackBlock<'a, A, R> {
+    /// Creates a block that calls `closure`, without a signature.
+    ///
+    /// This is for argument types without an encoding, for APIs that do not
+    /// read the signature.
+    #[inline]
+    pub(crate) fn without_signature<F: BlockFn<A, R>>(closure: &'a F) -> Self {
+        Self {
+            layout: BlockLayout::new(closure, drop_nothing, &UNSIGNED_DESCRIPTOR),
+            marker: PhantomData,
+            signature: PhantomData,
+        }
+    }
+}
+
+/// A reference-counted block on the heap that owns its closure.
+///
+/// Unlike [`StackBlock`](struct.StackBlock.html), this can be passed to APIs
+/// that retain the block and invoke it later, such as
+/// `-[NSData initWithBytesNoCopy:length:deallocator:]`. The closure is dropped
+/// once the last reference to the block is released.
+///
+/// Cloning retains the block, which shares the closure.
+///
+/// Such APIs may also invoke the block on another thread, so callers should
+/// require the closure to be `Send` where appropriate.
+pub struct RcBlock<A, R> {
+    ptr: NonNull<Block<A, R>>,
+}
+
+impl<A, R> fmt::Debug for RcBlock<A, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<A, R> Drop for RcBlock<A, R> {
+    #[inline]
+    #[doc(alias = "Block_release")]
+    #[doc(alias = "_Block_release")]
+    fn drop(&mut self) {
+        unsafe { _Block_release(self.as_ptr()) };
+    }
+}
+
+impl<A, R> Clone for RcBlock<A, R> {
+    #[inline]
+    fn clone(&self) -> Self {
+        // The closure is owned by the block.
+        unsafe { (**self).copy() }
+    }
+}
+
+impl<A, R> Deref for RcBlock<A, R> {
+    type Target = Block<A, R>;
+
+    #[inline]
+    fn deref(&self) -> &Block<A, R> {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<A: EncodeArgs, R: Encode> RcBlock<A, R> {
+    /// Creates a block that calls `closure`, with a signature derived from
+    /// the encodings of `A` and `R`.
+    #[inline]
+    pub fn new<F: BlockFn<A, R> + 'static>(closure: F) -> Self {
+        Self::with_descriptor(closure, signed_descriptor::<A, R>())
+    }
+}
+
+impl<A, R> RcBlock<A, R> {
+    /// Creates a block that calls `closure`, without a signature.
+    ///
+    /// This is for argument types without an encoding, for APIs that do not
+    /// read the signature.
+    #[inline]
+    pub(crate) fn without_signature<F: BlockFn<A, R> + 'static>(closure: F) -> Self {
+        Self::with_descriptor(closure, &UNSIGNED_DESCRIPTOR)
+    }
+
+    fn with_descriptor<F: BlockFn<A, R> + 'static>(
+        closure: F,
+        descriptor: &'static BlockDescriptor,
+    ) -> Self {
+        unsafe fn drop_box<F>(closure: *const c_void) {
+            drop(Box::from_raw(closure as *mut F));
+        }
+
+        let closure = Box::into_raw(Box::new(closure));
+        let block = BlockLayout::new(closure, drop_box::<F>, descriptor);
+
+        // The stack block is never disposed of, so ownership of the closure
+        // moves to the heap copy, whose class is `_NSConcreteMallocBlock`.
+        let ptr = unsafe { _Block_copy((&block as *const BlockLayout).cast()) };
+        Self {
+            ptr: NonNull::new(ptr.cast()).expect("Failed to copy block"),
+        }
+    }
+}
+
+/// Returns the invoke function of `block`, which takes the block followed by
+/// its arguments.
+///
+/// # Safety
+///
+/// `block` must point to a valid block.
+#[inline]
+pub(crate) unsafe fn invoke_ptr(block: *const c_void) -> *const c_void {
+    // All blocks start with the same layout, regardless of descriptor.
+    (*block.cast::<BlockLayout>()).invoke
+}
+
+// The closure pointer is copied with the rest of the block, so there is
+// nothing to do here.
+unsafe extern "C" fn copy_helper(_dst: *mut c_void, _src: *const c_void) {}
+
+unsafe extern "C" fn dispose_helper(block: *mut c_void) {
+    let block = &*(block as *const BlockLayout);
+    (block.drop_closure)(block.closure);
+}
+
+/// A tuple of arguments that a [`Block`](struct.Block.html) can be called
+/// with.
+///
+/// This is implemented for tuples of up to four arguments.
+///
+/// # Safety
+///
+/// [`invoke`](#tymethod.invoke) must call the block's invoke function with
+/// the block followed by the arguments.
+pub unsafe trait BlockArgs: Sized {
+    /// Calls `block` with `args`.
+    ///
+    /// # Safety
+    ///
+    /// `block` must point to a valid block that takes `Self` and returns `R`.
+    unsafe fn invoke<R>(block: *const c_void, args: Self) -> R;
+}
+
+/// A closure that can be called through a block with arguments `A` and
+/// return type `R`.
+///
+/// This is implemented for closures that take up to four arguments.
+///
+/// # Safety
+///
+/// [`invoke_ptr`](#tymethod.invoke_ptr) must return a function that can be
+/// called through a block that stores a pointer to `Self`.
+pub unsafe trait BlockFn<A, R> {
+    /// Returns the block's invoke function, which takes the block followed by
+    /// `A`.
+    #[doc(hidden)]
+    fn invoke_ptr() -> *const c_void;
+}
+
+macro_rules! impl_block_fn {
+    ($($arg:ident: $t:ident),*) => {
+        unsafe impl<$($t),*> BlockArgs for ($($t,)*) {
+            #[inline]
+            unsafe fn invoke<R>(block: *const c_void, ($($arg,)*): Self) -> R {
+                let invoke: unsafe extern "C" fn(*const c_void $(, $t)*) -> R =
+                    mem::transmute(invoke_ptr(block));
+                invoke(block $(, $arg)*)
+            }
+        }
+
+        unsafe impl<F, R, $($t),*> BlockFn<($($t,)*), R> for F
+        where
+            F: Fn($($t),*) -> R,
+        {
+            #[inline]
+            fn invoke_ptr() -> *const c_void {
+                // Panics abort the process instead of unwinding through this.
+                unsafe extern "C" fn invoke<F, R, $($t),*>(
+                    block: &BlockLayout,
+                    $($arg: $t),*
+                ) -> R
+                where
+                    F: Fn($($t),*) -> R,
+                {
+                    (*block.closure.cast::<F>())($($arg),*)
+                }
+
+                let invoke: unsafe extern "C" fn(&BlockLayout, $($t),*) -> R =
+                    invoke::<F, R, $($t),*>;
+                invoke as *const c_void
+            }
+        }
+    };
+}
+
+impl_block_fn!();
+impl_block_fn!(a: A);
+impl_block_fn!(a: A, b: B);
+impl_block_fn!(a: A, b: B, c: C);
+impl_block_fn!(a: A, b: B, c: C, d: D);
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::core::Arc;
+    use crate::foundation::{NSArray, NSNumber, NSString};
+    use crate::objc::{autoreleasepool, Class, NSInteger, NSObject, NSUInteger, BOOL};
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn index_of_object_passing_test() {
+        let numbers: Vec<Arc<NSNumber>> = (0..5).map(|n: NSInteger| (n * 10).into()).collect();
+        let numbers: Vec<&NSNumber> = numbers.iter().map(|n| &**n).collect();
+        let array = NSArray::from_slice(&numbers);
+
+        let calls = Cell::new(0);
+        let test = |object: &NSObject<'static>, index: NSUInteger, _stop: *mut BOOL| -> BOOL {
+            calls.set(calls.get() + 1);
+            let number = object.downcast_ref::<NSNumber>().unwrap();
+            BOOL::from(index > 0 && number.as_i64() == Some(30))
+        };
+
+        let block = StackBlock::new(&test);
+        assert!(block.signature().is_some());
+
+        let index: NSUInteger = unsafe { _msg_send_any![array, indexOfObjectPassingTest: &*block] };
+        assert_eq!(index, 3);
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn call_received_block() {
+        type Comparator = Block<(*const NSObject<'static>, *const NSObject<'static>), NSInteger>;
+
+        // `-[NSSortDescriptor comparator]` returns a block that compares the
+        // values for the descriptor's key.
+        let comparator = autoreleasepool(|_| unsafe {
+            let class = Class::from_name("NSSortDescriptor").unwrap();
+            let key = NSString::from_str("self");
+            let descriptor: &NSObject<'static> =
+                _msg_send_any![class, sortDescriptorWithKey: &*key ascending: BOOL::YES];
+
+            let comparator: *const c_void = _msg_send_any![descriptor, comparator];
+            Comparator::from_ptr(comparator).unwrap().copy()
+        });
+
+        let one: Arc<NSNumber> = (1 as NSInteger).into();
+        let two: Arc<NSNumber> = (2 as NSInteger).into();
+        let one = (&*one as *const NSNumber).cast::<NSObject<'static>>();
+        let two = (&*two as *const NSNumber).cast::<NSObject<'static>>();
+
+        // `NSOrderedAscending`, `NSOrderedDescending`, and `NSOrderedSame`.
+        assert_eq!(comparator.call((one, two)), -1);
+        assert_eq!(comparator.call((two, one)), 1);
+        assert_eq!(comparator.clone().call((one, one)), 0);
+    }
+
+    #[test]
+    fn rc_block() {
+        let count = Rc::new(Cell::new(0));
+        let block = {
+            let count = count.clone();
+            RcBlock::new(move |amount: usize| -> usize {
+                count.set(count.get() + amount);
+                count.get()
+            })
+        };
+        let signature = format!("{}@?{}", usize::ENCODING, usize::ENCODING);
+        assert_eq!(block.signature().unwrap().to_str(), Ok(&*signature));
+
+        assert_eq!(block.call((2,)), 2);
+        let copy = block.clone();
+        assert_eq!(copy.as_ptr(), block.as_ptr());
+        assert_eq!(copy.call((3,)), 5);
+
+        // The closure is dropped with the last reference.
+        drop(block);
+        assert_eq!(Rc::strong_count(&count), 2);
+        drop(copy);
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+}
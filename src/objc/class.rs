@@ -1,9 +1,9 @@
-use super::{sys, Method, Property, Sel, BOOL};
+use super::{sys, Imp, Ivar, Method, Property, Protocol, Sel, BOOL};
 use crate::core::{Arc, ObjectType};
 use std::{
     cell::UnsafeCell,
     cmp,
-    ffi::CStr,
+    ffi::{CStr, CString},
     fmt, hash, mem,
     os::raw::{c_char, c_int},
     panic::RefUnwindSafe,
@@ -83,6 +83,17 @@ impl Class {
         unsafe { objc_getClass(name.as_ptr()) }
     }
 
+    /// Returns the class named `name`, or `None` if the class is not
+    /// registered with the Objective-C runtime.
+    ///
+    /// Unlike [`get`](Self::get), this copies `name` to add a nul terminator,
+    /// and returns `None` if `name` contains a nul byte.
+    #[inline]
+    #[doc(alias = "objc_getClass")]
+    pub fn from_name(name: &str) -> Option<&'static Class> {
+        Self::get(&CString::new(name).ok()?)
+    }
+
     /// Returns the number of classes registered with the Objective-C runtime.
     #[inline]
     pub fn count() -> usize {
@@ -120,9 +131,22 @@ impl Class {
         objc_alloc(self)
     }
 
-    /// Calls `[[self alloc] init]`.
+    /// Calls `[[self alloc] init]`, returning a new instance of `self`.
+    ///
+    /// Wrappers of classes can instead be instantiated with
+    /// `T::class().alloc_init::<T>()`, using
+    /// [`ClassType::class`](trait.ClassType.html#tymethod.class).
+    ///
+    /// # Safety
+    ///
+    /// `T` must be the type of instances of `self` or of a superclass, such
+    /// as [`NSObject`](struct.NSObject.html) for most classes. Instances of
+    /// `self` must be usable after `init`, rather than requiring another
+    /// initializer.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/1571958-alloc).
     #[inline]
-    pub(crate) unsafe fn alloc_init<T: ObjectType>(&self) -> Arc<T> {
+    pub unsafe fn alloc_init<T: ObjectType>(&self) -> Arc<T> {
         // TODO: Add `cfg` use `objc_msgSend` on older platforms where this
         // symbol does not exist.
         //
@@ -218,6 +242,18 @@ impl Class {
         }
     }
 
+    /// Returns `true` if this class or a superclass adopts `protocol`, or a
+    /// protocol that incorporates it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_conformstoprotocol(_:_:)).
+    #[doc(alias = "class_conformsToProtocol")]
+    pub fn conforms_to(&self, protocol: &Protocol) -> bool {
+        // Unlike `+[NSObject conformsToProtocol:]`, the runtime function does
+        // not check superclasses.
+        let conforms = |class: &Class| unsafe { class_conformsToProtocol(class, protocol) }.into();
+        conforms(self) || self.superclass_iter().any(conforms)
+    }
+
     /// Returns the size of instances of this class.
     #[inline]
     pub fn instance_size(&self) -> usize {
@@ -254,12 +290,7 @@ impl Class {
     pub fn copy_class_method_list(&self) -> Option<Malloced<[&Method]>> {
         use std::{mem::MaybeUninit, os::raw::c_uint};
 
-        // TODO: Move this function into `objc::sys` module.
-        extern "C" {
-            fn object_getClass(obj: *const Class) -> *const Class;
-        }
-
-        let superclass = unsafe { object_getClass(self) };
+        let superclass = unsafe { sys::object_getClass((self as *const Self).cast()) };
 
         let mut len = MaybeUninit::<c_uint>::uninit();
         unsafe {
@@ -290,6 +321,24 @@ impl Class {
         unsafe { sys::class_getInstanceMethod(self, name).as_ref() }
     }
 
+    /// Returns the function that is called when instances of this class are
+    /// sent `name`.
+    ///
+    /// If instances do not respond to `name`, this returns a function that
+    /// forwards the message, which makes this faster than
+    /// [`get_instance_method`](Self::get_instance_method) followed by
+    /// [`Method::implementation`](super::Method::implementation).
+    ///
+    /// The forwarding function must not be called for methods that return
+    /// structures in memory. [`CachedImp`](super::CachedImp) handles this.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_getmethodimplementation(_:_:)).
+    #[inline]
+    #[doc(alias = "class_getMethodImplementation")]
+    pub fn method_implementation(&self, name: Sel) -> Imp {
+        unsafe { sys::class_getMethodImplementation(self, name) }
+    }
+
     /// Returns the instance methods implemented by this class, or `None` if
     /// this class implements no instance methods.
     ///
@@ -317,6 +366,37 @@ impl Class {
         }
     }
 
+    /// Sets the implementation of the instance method named `name`, returning
+    /// the previous implementation so that the new one can call it.
+    ///
+    /// If `self` does not define the method, it is added with `types` and
+    /// `None` is returned, even if a superclass defines it. Otherwise, `types`
+    /// is ignored.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_replacemethod(_:_:_:_:)).
+    ///
+    /// # Safety
+    ///
+    /// `imp` must have the signature described by `types`, which must match
+    /// that of any method named `name` in a superclass. Other threads must
+    /// not rely on the previous implementation being called.
+    #[inline]
+    #[doc(alias = "class_replaceMethod")]
+    pub unsafe fn replace_method(&self, name: Sel, imp: Imp, types: &CStr) -> Option<Imp> {
+        let class = self as *const Self as *mut Self;
+        sys::class_replaceMethod(class, name, imp, types.as_ptr())
+    }
+
+    /// Returns the instance variable of `self` or of a superclass named
+    /// `name`, or `None` if there is none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_getinstancevariable(_:_:)).
+    #[inline]
+    #[doc(alias = "class_getInstanceVariable")]
+    pub fn get_instance_variable(&self, name: &CStr) -> Option<&Ivar> {
+        unsafe { sys::class_getInstanceVariable(self, name.as_ptr()).as_ref() }
+    }
+
     /// Returns a property of `self` with `name`.
     ///
     /// See [documentation](https://developer.apple.com/documentation/objectivec/1418597-class_getproperty).
@@ -368,5 +448,48 @@ extern "C" {
 
     fn class_getName(class: &Class) -> *const c_char;
     fn class_getSuperclass(class: &Class) -> Option<&Class>;
+    fn class_conformsToProtocol(class: &Class, protocol: &Protocol) -> BOOL;
     fn class_getInstanceSize(class: &Class) -> usize;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::{ClassType, NSObject};
+
+    #[test]
+    fn from_name() {
+        let class = Class::from_name("NSString").unwrap();
+        assert_eq!(class.name().to_str(), Ok("NSString"));
+        assert_eq!(Class::get(class.name()), Some(class));
+
+        assert!(Class::from_name("FruityMissingClass").is_none());
+        assert!(Class::from_name("NSString\0").is_none());
+    }
+
+    #[test]
+    fn superclasses() {
+        let string = Class::from_name("NSString").unwrap();
+        let root = NSObject::class();
+
+        assert_eq!(string.superclass_iter().last(), Some(root));
+        assert!(string.is_subclass_of(root));
+        assert!(!root.is_subclass_of(string));
+        assert!(root.superclass().is_none());
+
+        assert!(string.instances_respond_to_selector(selector!(length)));
+        assert!(!root.instances_respond_to_selector(selector!(length)));
+        assert!(root.responds_to_selector(selector!(alloc)));
+    }
+
+    #[test]
+    fn alloc_init() {
+        let root = NSObject::class();
+        assert!(root.instance_size() >= mem::size_of::<*const Class>());
+
+        let object: Arc<NSObject> = unsafe { root.alloc_init() };
+        assert!(object.is_member_of_class(root));
+        assert_eq!(crate::objc::ObjectType::class(&*object), root);
+        assert_eq!(object.retain_count(), 1);
+    }
+}
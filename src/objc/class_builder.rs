@@ -0,0 +1,677 @@
+use super::{sys, Class, Encode, Encoding, Imp, NSObject, Protocol, Sel};
+use std::{
+    error::Error,
+    ffi::{CStr, CString},
+    fmt, mem,
+    ptr::{self, NonNull},
+};
+
+/// Defines a new Objective-C class at runtime.
+///
+/// Methods and instance variables can only be added before the class is
+/// [registered](#method.register). A builder that is dropped without being
+/// registered disposes of its class.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fruity::objc::{ClassBuilder, ClassType, NSObject, Sel};
+///
+/// extern "C" fn answer(_this: &NSObject, _cmd: Sel) -> usize {
+///     42
+/// }
+///
+/// let mut builder = ClassBuilder::new("MyObject", NSObject::class()).unwrap();
+/// builder.add_method0(fruity::selector!(answer), answer).unwrap();
+/// let class = builder.register().unwrap();
+/// ```
+///
+/// Overriding methods can call the superclass implementation with
+/// [`msg_send_super`](fn.msg_send_super.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/objc_allocateclasspair(_:_:_:)).
+pub struct ClassBuilder {
+    class: NonNull<Class>,
+    protocols: Vec<&'static Protocol>,
+}
+
+impl fmt::Debug for ClassBuilder {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ClassBuilder")
+            .field(&self.class().name())
+            .finish()
+    }
+}
+
+impl Drop for ClassBuilder {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { sys::objc_disposeClassPair(self.class.as_ptr()) };
+    }
+}
+
+/// Allocates a class pair for a class that is private to this copy of the
+/// crate, whose name starts with `name`.
+///
+/// The name also has the crate version, and a numeric suffix if another copy
+/// of the crate already registered it. Such a class must not be reused, since
+/// its methods use the statics of the copy that defined it.
+pub(crate) fn allocate_private_class(name: &str, superclass: &Class) -> NonNull<Class> {
+    let version = env!("CARGO_PKG_VERSION").replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    let name = format!("{}_{}", name, version);
+
+    (0u32..)
+        .find_map(|suffix| {
+            let name = match suffix {
+                0 => CString::new(name.as_str()),
+                _ => CString::new(format!("{}_{}", name, suffix)),
+            };
+            let name = name.expect("Class name contains a nul byte");
+            NonNull::new(unsafe { sys::objc_allocateClassPair(superclass, name.as_ptr(), 0) })
+        })
+        .expect("Failed to allocate private class")
+}
+
+impl ClassBuilder {
+    /// Starts defining a class named `name` that inherits from `superclass`.
+    ///
+    /// This fails if a class named `name` already exists.
+    #[doc(alias = "objc_allocateClassPair")]
+    pub fn new(name: &str, superclass: &Class) -> Result<Self, ClassBuilderError> {
+        let c_name =
+            CString::new(name).map_err(|_| ClassBuilderError::InvalidName(name.to_owned()))?;
+
+        let class = unsafe { sys::objc_allocateClassPair(superclass, c_name.as_ptr(), 0) };
+        match NonNull::new(class) {
+            Some(class) => Ok(Self {
+                class,
+                protocols: Vec::new(),
+            }),
+            None => Err(ClassBuilderError::ClassExists(name.to_owned())),
+        }
+    }
+
+    /// Starts defining a class that is private to this copy of the crate. See
+    /// [`allocate_private_class`].
+    pub(crate) fn new_private(name: &str, superclass: &Class) -> Self {
+        Self {
+            class: allocate_private_class(name, superclass),
+            protocols: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn class(&self) -> &Class {
+        unsafe { self.class.as_ref() }
+    }
+
+    /// Adds an instance method named `sel` that calls `imp`.
+    ///
+    /// `types` describes the return type followed by the argument types,
+    /// including the receiver and selector, such as from
+    /// [`Encoding::method`](enum.Encoding.html#method.method).
+    ///
+    /// This fails if the class already defines a method named `sel`, but
+    /// methods of the superclass can be overridden.
+    ///
+    /// # Safety
+    ///
+    /// `imp` must be a function that takes the receiver, selector, and
+    /// arguments described by `types`, and returns the type described by
+    /// `types`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_addmethod(_:_:_:_:)).
+    #[inline]
+    #[doc(alias = "class_addMethod")]
+    pub unsafe fn add_method(
+        &mut self,
+        sel: Sel,
+        imp: Imp,
+        types: &CStr,
+    ) -> Result<(), ClassBuilderError> {
+        if sys::class_addMethod(self.class.as_ptr(), sel, imp, types.as_ptr()).into() {
+            Ok(())
+        } else {
+            Err(ClassBuilderError::MethodExists(sel))
+        }
+    }
+
+    /// Adds an instance variable of type `T` named `name`.
+    ///
+    /// Instance variables are zeroed when objects are allocated, and can be
+    /// accessed with
+    /// [`NSObject::get_ivar`](struct.NSObject.html#method.get_ivar) and
+    /// [`NSObject::set_ivar`](struct.NSObject.html#method.set_ivar).
+    ///
+    /// This fails if the class already has an instance variable named `name`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_addivar(_:_:_:_:_:)).
+    #[doc(alias = "class_addIvar")]
+    pub fn add_ivar<T: Encode>(&mut self, name: &str) -> Result<(), ClassBuilderError> {
+        let c_name =
+            CString::new(name).map_err(|_| ClassBuilderError::InvalidName(name.to_owned()))?;
+        let types = T::ENCODING.to_c_string();
+
+        let size = mem::size_of::<T>();
+        let alignment = mem::align_of::<T>().trailing_zeros() as u8;
+        let added = unsafe {
+            sys::class_addIvar(
+                self.class.as_ptr(),
+                c_name.as_ptr(),
+                size,
+                alignment,
+                types.as_ptr(),
+            )
+        };
+
+        if added.into() {
+            Ok(())
+        } else {
+            Err(ClassBuilderError::IvarExists(name.to_owned()))
+        }
+    }
+
+    /// Declares that the class conforms to `protocol`.
+    ///
+    /// The required methods of `protocol` must be implemented by the time
+    /// the class is [registered](#method.register).
+    ///
+    /// This fails if the class already conforms to `protocol`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/class_addprotocol(_:_:)).
+    #[doc(alias = "class_addProtocol")]
+    pub fn add_protocol(&mut self, protocol: &'static Protocol) -> Result<(), ClassBuilderError> {
+        if unsafe { sys::class_addProtocol(self.class.as_ptr(), protocol) }.into() {
+            self.protocols.push(protocol);
+            Ok(())
+        } else {
+            Err(ClassBuilderError::ProtocolExists(protocol_name(protocol)))
+        }
+    }
+
+    /// Registers the class with the Objective-C runtime, after which it can
+    /// be instantiated.
+    ///
+    /// This fails if the class or its superclasses do not implement a
+    /// required method of an [added protocol](#method.add_protocol), or of a
+    /// protocol that it incorporates. The class is then disposed of.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/objc_registerclasspair(_:)).
+    #[doc(alias = "objc_registerClassPair")]
+    pub fn register(self) -> Result<&'static Class, ClassBuilderError> {
+        self.verify_protocols()?;
+
+        let class = self.class;
+        mem::forget(self);
+        unsafe {
+            sys::objc_registerClassPair(class.as_ptr());
+            Ok(&*class.as_ptr())
+        }
+    }
+
+    fn verify_protocols(&self) -> Result<(), ClassBuilderError> {
+        let class = self.class();
+        let mut visited: Vec<&Protocol> = Vec::new();
+        let mut pending = self.protocols.clone();
+
+        while let Some(protocol) = pending.pop() {
+            if visited.contains(&protocol) {
+                continue;
+            }
+            visited.push(protocol);
+
+            let missing: Vec<Sel> = protocol
+                .methods()
+                .filter(|method| method.is_required())
+                .filter(|method| {
+                    let method_impl = if method.is_instance() {
+                        class.get_instance_method(method.name())
+                    } else {
+                        class.get_class_method(method.name())
+                    };
+                    method_impl.is_none()
+                })
+                .map(|method| method.name())
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(ClassBuilderError::MissingMethods {
+                    protocol: protocol_name(protocol),
+                    methods: missing,
+                });
+            }
+
+            pending.extend(protocol.protocols());
+        }
+        Ok(())
+    }
+}
+
+macro_rules! add_method {
+    ($(#[$docs:meta])+ $fn:ident($($arg:ident),*)) => {
+        $(#[$docs])+
+        ///
+        /// The method's type encoding is derived from the argument and
+        /// return types.
+        #[inline]
+        pub fn $fn<$($arg: Encode,)* R: Encode>(
+            &mut self,
+            sel: Sel,
+            f: extern "C" fn(&NSObject<'static>, Sel $(, $arg)*) -> R,
+        ) -> Result<(), ClassBuilderError> {
+            let types = Encoding::method(&R::ENCODING, &[$($arg::ENCODING),*]);
+            unsafe {
+                let imp = mem::transmute::<*const (), Imp>(f as *const ());
+                self.add_method(sel, imp, &types)
+            }
+        }
+    };
+}
+
+/// Adding methods with type-safe signatures.
+impl ClassBuilder {
+    add_method! {
+        /// Adds an instance method named `sel` that takes no arguments.
+        add_method0()
+    }
+
+    add_method! {
+        /// Adds an instance method named `sel` that takes one argument.
+        add_method1(A)
+    }
+
+    add_method! {
+        /// Adds an instance method named `sel` that takes two arguments.
+        add_method2(A, B)
+    }
+
+    add_method! {
+        /// Adds an instance method named `sel` that takes three arguments.
+        add_method3(A, B, C)
+    }
+}
+
+/// The error returned when a [`ClassBuilder`](struct.ClassBuilder.html) cannot
+/// define a class.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClassBuilderError {
+    /// The name contains a nul byte.
+    InvalidName(String),
+    /// A class with the name already exists.
+    ClassExists(String),
+    /// The class already defines a method with the selector.
+    MethodExists(Sel),
+    /// The class already has an instance variable with the name.
+    IvarExists(String),
+    /// The class already conforms to the protocol with the name.
+    ProtocolExists(String),
+    /// The class does not implement required methods of a protocol.
+    MissingMethods {
+        /// The name of the protocol.
+        protocol: String,
+        /// The selectors of the missing methods.
+        methods: Vec<Sel>,
+    },
+}
+
+#[inline]
+fn protocol_name(protocol: &Protocol) -> String {
+    protocol.name().to_string_lossy().into_owned()
+}
+
+impl fmt::Display for ClassBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "name {:?} contains a nul byte", name),
+            Self::ClassExists(name) => write!(f, "class {:?} already exists", name),
+            Self::MethodExists(sel) => write!(f, "method {:?} already exists", sel),
+            Self::IvarExists(name) => write!(f, "instance variable {:?} already exists", name),
+            Self::ProtocolExists(name) => write!(f, "protocol {:?} is already adopted", name),
+            Self::MissingMethods { protocol, methods } => {
+                write!(f, "missing required methods of protocol {:?}:", protocol)?;
+                for method in methods {
+                    write!(f, " {}", method.name().to_string_lossy())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ClassBuilderError {}
+
+/// Instance variables.
+impl NSObject<'_> {
+    // Returns a pointer to the instance variable named `name`, checking that
+    // its encoding matches `T`.
+    fn ivar_ptr<T: Encode>(&self, name: &str) -> *mut T {
+        let c_name = CString::new(name).expect("Instance variable name contains a nul byte");
+        let ivar = super::ObjectType::class(self)
+            .get_instance_variable(&c_name)
+            .unwrap_or_else(|| panic!("No instance variable named {:?}", name));
+
+        let encoding = T::ENCODING.to_c_string();
+        assert_eq!(
+            ivar.type_encoding(),
+            Some(&*encoding),
+            "Mismatched type for instance variable {:?}",
+            name,
+        );
+
+        let base = self as *const Self as *mut u8;
+        unsafe { base.offset(ivar.offset()).cast() }
+    }
+
+    /// Returns the value of the instance variable named `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class of `self` has no instance variable named `name`,
+    /// or if it is not of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// The instance variable must hold a valid `T`, which is not being
+    /// written to by another thread. Types that share an encoding, such as
+    /// object references, are not distinguished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/object_getivar(_:_:)).
+    #[inline]
+    #[doc(alias = "object_getIvar")]
+    pub unsafe fn get_ivar<T: Encode + Copy>(&self, name: &str) -> T {
+        ptr::read(self.ivar_ptr::<T>(name))
+    }
+
+    /// Sets the value of the instance variable named `name`.
+    ///
+    /// The previous value is overwritten without being dropped or released.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class of `self` has no instance variable named `name`,
+    /// or if it is not of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// The instance variable must not be accessed by another thread at the
+    /// same time. Types that share an encoding, such as object references,
+    /// are not distinguished.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/object_setivar(_:_:_:)).
+    #[inline]
+    #[doc(alias = "object_setIvar")]
+    pub unsafe fn set_ivar<T: Encode + Copy>(&self, name: &str, value: T) {
+        ptr::write(self.ivar_ptr::<T>(name), value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Arc;
+    use crate::objc::ClassType;
+    use std::sync::Once;
+
+    fn counter_class() -> &'static Class {
+        static REGISTER: Once = Once::new();
+
+        extern "C" fn add(this: &NSObject<'static>, _cmd: Sel, amount: usize) -> usize {
+            unsafe {
+                let count = this.get_ivar::<usize>("_count") + amount;
+                this.set_ivar("_count", count);
+                count
+            }
+        }
+
+        REGISTER.call_once(|| {
+            let mut builder = ClassBuilder::new("_FruityTestCounter", NSObject::class()).unwrap();
+            builder.add_ivar::<usize>("_count").unwrap();
+            builder.add_method1(selector!(add:), add).unwrap();
+
+            assert_eq!(
+                builder.add_ivar::<usize>("_count"),
+                Err(ClassBuilderError::IvarExists("_count".to_owned()))
+            );
+            assert_eq!(
+                builder.add_method1(selector!(add:), add),
+                Err(ClassBuilderError::MethodExists(selector!(add:)))
+            );
+            builder.register().unwrap();
+        });
+
+        Class::from_name("_FruityTestCounter").unwrap()
+    }
+
+    #[test]
+    fn define_class() {
+        let class = counter_class();
+        assert!(class.is_subclass_of(NSObject::class()));
+        assert!(class.instances_respond_to_selector(selector!(add:)));
+
+        let ivar = class
+            .get_instance_variable(CStr::from_bytes_with_nul(b"_count\0").unwrap())
+            .unwrap();
+        assert_eq!(ivar.type_encoding(), Some(&*usize::ENCODING.to_c_string()));
+
+        let counter: Arc<NSObject> = unsafe { class.alloc_init() };
+        assert_eq!(unsafe { counter.get_ivar::<usize>("_count") }, 0);
+
+        let count: usize = unsafe { _msg_send_any![counter, add: 2usize] };
+        assert_eq!(count, 2);
+        let count: usize = unsafe { _msg_send_any![counter, add: 3usize] };
+        assert_eq!(count, 5);
+
+        unsafe { counter.set_ivar("_count", 10usize) };
+        let count: usize = unsafe { _msg_send_any![counter, add: 1usize] };
+        assert_eq!(count, 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mismatched type")]
+    fn mismatched_ivar() {
+        let counter: Arc<NSObject> = unsafe { counter_class().alloc_init() };
+        unsafe { counter.get_ivar::<f64>("_count") };
+    }
+
+    #[test]
+    fn name_collision() {
+        let error = ClassBuilder::new("NSObject", NSObject::class()).unwrap_err();
+        assert_eq!(error, ClassBuilderError::ClassExists("NSObject".to_owned()));
+        assert_eq!(error.to_string(), "class \"NSObject\" already exists");
+
+        let error = ClassBuilder::new("Fruity\0Class", NSObject::class()).unwrap_err();
+        assert!(matches!(error, ClassBuilderError::InvalidName(_)));
+
+        // An unregistered class is disposed of, so its name can be reused.
+        let builder = ClassBuilder::new("_FruityTestDisposed", NSObject::class()).unwrap();
+        drop(builder);
+        assert!(Class::from_name("_FruityTestDisposed").is_none());
+        let builder = ClassBuilder::new("_FruityTestDisposed", NSObject::class()).unwrap();
+        drop(builder);
+    }
+
+    #[test]
+    fn private_names() {
+        let version = env!("CARGO_PKG_VERSION").replace('.', "_");
+        let name = format!("_FruityTestPrivate_{}", version);
+
+        // The second class stands in for one of another copy of the crate.
+        let first = ClassBuilder::new_private("_FruityTestPrivate", NSObject::class());
+        let first = first.register().unwrap();
+        assert_eq!(first.name().to_str().unwrap(), name);
+
+        let second = ClassBuilder::new_private("_FruityTestPrivate", NSObject::class());
+        let second = second.register().unwrap();
+        assert_eq!(second.name().to_str().unwrap(), format!("{}_1", name));
+    }
+
+    #[cfg(feature = "foundation")]
+    #[test]
+    fn protocols() {
+        use crate::objc::Protocol;
+
+        extern "C" fn copy_with_zone(
+            this: &NSObject<'static>,
+            _cmd: Sel,
+            _zone: *mut std::ffi::c_void,
+        ) -> &'static NSObject<'static> {
+            // Objects are immutable, so a copy is a retained reference.
+            unsafe { &*Arc::into_raw(Arc::retain(this)) }
+        }
+
+        let copying = Protocol::get("NSCopying").unwrap();
+
+        let mut builder = ClassBuilder::new("_FruityTestNotCopying", NSObject::class()).unwrap();
+        builder.add_protocol(copying).unwrap();
+        assert_eq!(
+            builder.add_protocol(copying),
+            Err(ClassBuilderError::ProtocolExists("NSCopying".to_owned()))
+        );
+
+        let error = builder.register().unwrap_err();
+        assert_eq!(
+            error,
+            ClassBuilderError::MissingMethods {
+                protocol: "NSCopying".to_owned(),
+                methods: vec![selector!(copyWithZone:)],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "missing required methods of protocol \"NSCopying\": copyWithZone:"
+        );
+
+        // The class was disposed of.
+        assert!(Class::from_name("_FruityTestNotCopying").is_none());
+
+        let mut builder = ClassBuilder::new("_FruityTestCopying", NSObject::class()).unwrap();
+        builder.add_protocol(copying).unwrap();
+        builder
+            .add_method1(selector!(copyWithZone:), copy_with_zone)
+            .unwrap();
+        let class = builder.register().unwrap();
+        assert!(class.conforms_to(copying));
+
+        let object: Arc<NSObject> = unsafe { class.alloc_init() };
+        assert!(object.conforms_to_protocol(copying));
+        assert!(*object.copy() == *object);
+    }
+
+    #[cfg(feature = "foundation")]
+    mod overrides {
+        use super::*;
+        use crate::core_graphics::CGRect;
+        use crate::foundation::NSString;
+        use crate::objc::msg_send_super;
+
+        /// Returns `"<prefix>: <super description>"` as an autoreleased string.
+        fn prefixed_description(
+            this: &NSObject<'static>,
+            class: &str,
+            sel: Sel,
+        ) -> &'static NSString<'static> {
+            let class = Class::from_name(class).unwrap();
+            let description: &NSString = unsafe { msg_send_super(this, class, sel, ()) };
+
+            let prefix = class.name().to_str().unwrap();
+            let string = NSString::from_str(&format!("{}: {}", prefix, description));
+            unsafe { _msg_send_any![*Arc::into_raw(string), autorelease] }
+        }
+
+        extern "C" fn base_description(
+            this: &NSObject<'static>,
+            sel: Sel,
+        ) -> &'static NSString<'static> {
+            prefixed_description(this, "_FruityTestSuperBase", sel)
+        }
+
+        extern "C" fn derived_description(
+            this: &NSObject<'static>,
+            sel: Sel,
+        ) -> &'static NSString<'static> {
+            prefixed_description(this, "_FruityTestSuperDerived", sel)
+        }
+
+        extern "C" fn base_frame(_this: &NSObject<'static>, _cmd: Sel) -> CGRect {
+            CGRect::new(1.0, 2.0, 3.0, 4.0)
+        }
+
+        extern "C" fn derived_frame(this: &NSObject<'static>, sel: Sel) -> CGRect {
+            let class = Class::from_name("_FruityTestSuperDerived").unwrap();
+            let frame: CGRect = unsafe { msg_send_super(this, class, sel, ()) };
+            frame.offset(10.0, 20.0)
+        }
+
+        /// Registers `Base : NSObject`, `Derived : Base`, and
+        /// `Inheriting : Derived`, where only `Base` and `Derived` override
+        /// methods.
+        fn register() {
+            static REGISTER: Once = Once::new();
+
+            REGISTER.call_once(|| {
+                let mut builder =
+                    ClassBuilder::new("_FruityTestSuperBase", NSObject::class()).unwrap();
+                builder
+                    .add_method0(selector!(description), base_description)
+                    .unwrap();
+                builder.add_method0(selector!(frame), base_frame).unwrap();
+                let base = builder.register().unwrap();
+
+                let mut builder = ClassBuilder::new("_FruityTestSuperDerived", base).unwrap();
+                builder
+                    .add_method0(selector!(description), derived_description)
+                    .unwrap();
+                builder
+                    .add_method0(selector!(frame), derived_frame)
+                    .unwrap();
+                let derived = builder.register().unwrap();
+
+                ClassBuilder::new("_FruityTestSuperInheriting", derived)
+                    .unwrap()
+                    .register()
+                    .unwrap();
+            });
+        }
+
+        fn new_object(class: &str) -> Arc<NSObject<'static>> {
+            register();
+            unsafe { Class::from_name(class).unwrap().alloc_init() }
+        }
+
+        #[test]
+        fn description() {
+            let base = new_object("_FruityTestSuperBase").description().to_string();
+            assert!(base.starts_with("_FruityTestSuperBase: <_FruityTestSuperBase: 0x"));
+        }
+
+        #[test]
+        fn two_level_description() {
+            // Each override searches the superclass of the class defining
+            // it. Using the instance's class would make `Base` call
+            // `Derived`'s implementation again and recurse forever.
+            let derived = new_object("_FruityTestSuperDerived")
+                .description()
+                .to_string();
+            assert!(derived.starts_with(
+                "_FruityTestSuperDerived: _FruityTestSuperBase: <_FruityTestSuperDerived: 0x"
+            ));
+
+            let inheriting = new_object("_FruityTestSuperInheriting")
+                .description()
+                .to_string();
+            assert!(inheriting.starts_with(
+                "_FruityTestSuperDerived: _FruityTestSuperBase: <_FruityTestSuperInheriting: 0x"
+            ));
+        }
+
+        #[test]
+        fn struct_return() {
+            // `CGRect` is returned in memory on x86_64, which requires
+            // `objc_msgSendSuper2_stret`.
+            let object = new_object("_FruityTestSuperInheriting");
+            let frame: CGRect = unsafe { _msg_send_any![object, frame] };
+            assert_eq!(frame, CGRect::new(11.0, 22.0, 3.0, 4.0));
+        }
+    }
+}
@@ -2,6 +2,13 @@ use super::{Class, ObjectType};
 
 /// A type that represents an instance of a specific Objective-C class.
 ///
+/// For generic collections such as
+/// [`NSArray<T>`](../foundation/struct.NSArray.html), every `T` has the same
+/// class, since type parameters only exist in Rust. An instance of the class
+/// is therefore not necessarily a valid `Self`, so this trait alone must not
+/// be used to check the type of an object at runtime. Use
+/// [`DowncastTarget`](trait.DowncastTarget.html) instead.
+///
 /// # Related Items
 ///
 /// - [`objc::ObjectType`](crate::objc::ObjectType)
@@ -13,3 +20,17 @@ pub trait ClassType<'data>: ObjectType<'data> {
     /// program launch.
     fn class() -> &'static Class;
 }
+
+/// A [`ClassType`](trait.ClassType.html) whose class fully determines the
+/// type, so that an object can be checked to be an instance of it at runtime.
+///
+/// This is implemented for all classes except generic collections, whose
+/// element types cannot be checked by `isKindOfClass:`. Collections implement
+/// it only with type-erased elements, such as `NSArray<NSObject>`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait DowncastTarget<'data>: ClassType<'data> + private::Sealed {}
+
+pub(crate) mod private {
+    pub trait Sealed {}
+}
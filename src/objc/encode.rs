@@ -0,0 +1,370 @@
+use super::{Class, ObjectType, Sel, BOOL};
+use std::{
+    ffi::{c_void, CString},
+    fmt,
+};
+
+/// An Objective-C
+/// [type encoding](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/ObjCRuntimeGuide/Articles/ocrtTypeEncodings.html),
+/// as produced by `@encode`.
+///
+/// Encodings are written as strings with
+/// [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding<'a> {
+    /// `c`: a `char`.
+    Char,
+    /// `s`: a `short`.
+    Short,
+    /// `i`: an `int`.
+    Int,
+    /// `l`: a `long`, which is treated as 32-bit in encodings.
+    Long,
+    /// `q`: a `long long`.
+    LongLong,
+    /// `C`: an `unsigned char`.
+    UChar,
+    /// `S`: an `unsigned short`.
+    UShort,
+    /// `I`: an `unsigned int`.
+    UInt,
+    /// `L`: an `unsigned long`, which is treated as 32-bit in encodings.
+    ULong,
+    /// `Q`: an `unsigned long long`.
+    ULongLong,
+    /// `f`: a `float`.
+    Float,
+    /// `d`: a `double`.
+    Double,
+    /// `B`: a C++ `bool` or C99 `_Bool`.
+    Bool,
+    /// `v`: `void`.
+    Void,
+    /// `*`: a C string (`char *`).
+    String,
+    /// `@`: an object (`id`).
+    Object,
+    /// `@?`: a block.
+    Block,
+    /// `#`: a class object (`Class`).
+    Class,
+    /// `:`: a method selector (`SEL`).
+    Sel,
+    /// `?`: an unknown type, such as a function pointer.
+    Unknown,
+    /// `^type`: a pointer to a type.
+    Pointer(&'a Encoding<'a>),
+    /// `[lengthtype]`: a C array of a type.
+    Array(usize, &'a Encoding<'a>),
+    /// `{name=type...}`: a structure with a name and field types.
+    ///
+    /// This can be built in a `const` to define the encoding of a
+    /// `#[repr(C)]` struct:
+    ///
+    /// ```
+    /// # use fruity::objc::{Encode, Encoding};
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone)]
+    /// struct Pair {
+    ///     a: i32,
+    ///     b: f64,
+    /// }
+    ///
+    /// unsafe impl Encode for Pair {
+    ///     const ENCODING: Encoding<'static> =
+    ///         Encoding::Struct("Pair", &[i32::ENCODING, f64::ENCODING]);
+    /// }
+    ///
+    /// assert_eq!(Pair::ENCODING.to_string(), "{Pair=id}");
+    /// ```
+    Struct(&'a str, &'a [Encoding<'a>]),
+}
+
+impl fmt::Display for Encoding<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self {
+            Self::Char => "c",
+            Self::Short => "s",
+            Self::Int => "i",
+            Self::Long => "l",
+            Self::LongLong => "q",
+            Self::UChar => "C",
+            Self::UShort => "S",
+            Self::UInt => "I",
+            Self::ULong => "L",
+            Self::ULongLong => "Q",
+            Self::Float => "f",
+            Self::Double => "d",
+            Self::Bool => "B",
+            Self::Void => "v",
+            Self::String => "*",
+            Self::Object => "@",
+            Self::Block => "@?",
+            Self::Class => "#",
+            Self::Sel => ":",
+            Self::Unknown => "?",
+            Self::Pointer(pointee) => return write!(f, "^{}", pointee),
+            Self::Array(len, element) => return write!(f, "[{}{}]", len, element),
+            Self::Struct(name, fields) => {
+                write!(f, "{{{}=", name)?;
+                for field in fields.iter() {
+                    field.fmt(f)?;
+                }
+                return f.write_str("}");
+            }
+        };
+        f.write_str(code)
+    }
+}
+
+impl Encoding<'_> {
+    /// Returns the encoding as a C string, for passing to the Objective-C
+    /// runtime.
+    #[inline]
+    pub fn to_c_string(&self) -> CString {
+        // Encodings never contain nul bytes.
+        CString::new(self.to_string()).unwrap()
+    }
+
+    /// Returns the encoding of a method that returns `ret` and takes `args`
+    /// after the receiver (`self`) and selector (`_cmd`).
+    ///
+    /// This is the format expected by
+    /// [`ClassBuilder::add_method`](struct.ClassBuilder.html#method.add_method).
+    pub fn method(ret: &Encoding, args: &[Encoding]) -> CString {
+        let mut encoding = format!("{}{}{}", ret, Encoding::Object, Encoding::Sel);
+        for arg in args {
+            encoding.push_str(&arg.to_string());
+        }
+        CString::new(encoding).unwrap()
+    }
+
+    /// Returns the signature of a block that returns `ret` and takes `args`
+    /// after the block itself.
+    ///
+    /// This is the format of the signature stored in a block's descriptor.
+    pub fn block(ret: &Encoding, args: &[Encoding]) -> CString {
+        let mut encoding = format!("{}{}", ret, Encoding::Block);
+        for arg in args {
+            encoding.push_str(&arg.to_string());
+        }
+        CString::new(encoding).unwrap()
+    }
+}
+
+/// A type whose Objective-C type encoding is known.
+///
+/// # Safety
+///
+/// [`ENCODING`](#associatedconstant.ENCODING) must describe the memory layout
+/// of `Self`, because the runtime uses it to pass and store values.
+pub unsafe trait Encode {
+    /// The encoding of `Self`.
+    const ENCODING: Encoding<'static>;
+}
+
+macro_rules! encode {
+    ($($ty:ty => $encoding:expr,)+) => {
+        $(unsafe impl Encode for $ty {
+            const ENCODING: Encoding<'static> = $encoding;
+        })+
+    };
+}
+
+encode! {
+    i8 => Encoding::Char,
+    i16 => Encoding::Short,
+    i32 => Encoding::Int,
+    i64 => Encoding::LongLong,
+    u8 => Encoding::UChar,
+    u16 => Encoding::UShort,
+    u32 => Encoding::UInt,
+    u64 => Encoding::ULongLong,
+    f32 => Encoding::Float,
+    f64 => Encoding::Double,
+    bool => Encoding::Bool,
+    () => Encoding::Void,
+    c_void => Encoding::Void,
+    Sel => Encoding::Sel,
+    Option<Sel> => Encoding::Sel,
+}
+
+// `NSInteger` and `NSUInteger` are `int` and `unsigned int` on 32-bit
+// platforms.
+#[cfg(target_pointer_width = "64")]
+encode! {
+    isize => Encoding::LongLong,
+    usize => Encoding::ULongLong,
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+encode! {
+    isize => Encoding::Int,
+    usize => Encoding::UInt,
+}
+
+unsafe impl Encode for BOOL {
+    #[cfg(any(
+        all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"),
+        all(target_os = "ios", target_pointer_width = "32"),
+    ))]
+    const ENCODING: Encoding<'static> = Encoding::Char;
+
+    #[cfg(not(any(
+        all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"),
+        all(target_os = "ios", target_pointer_width = "32"),
+    )))]
+    const ENCODING: Encoding<'static> = Encoding::Bool;
+}
+
+unsafe impl Encode for &Class {
+    const ENCODING: Encoding<'static> = Encoding::Class;
+}
+
+unsafe impl Encode for Option<&Class> {
+    const ENCODING: Encoding<'static> = Encoding::Class;
+}
+
+unsafe impl<'data, T: ObjectType<'data>> Encode for &T {
+    const ENCODING: Encoding<'static> = Encoding::Object;
+}
+
+unsafe impl<'data, T: ObjectType<'data>> Encode for Option<&T> {
+    const ENCODING: Encoding<'static> = Encoding::Object;
+}
+
+unsafe impl<T: Encode> Encode for *const T {
+    const ENCODING: Encoding<'static> = Encoding::Pointer(&T::ENCODING);
+}
+
+unsafe impl<T: Encode> Encode for *mut T {
+    const ENCODING: Encoding<'static> = Encoding::Pointer(&T::ENCODING);
+}
+
+unsafe impl<T: Encode, const N: usize> Encode for [T; N] {
+    const ENCODING: Encoding<'static> = Encoding::Array(N, &T::ENCODING);
+}
+
+/// A tuple of argument types whose Objective-C type encodings are known.
+///
+/// This is implemented for tuples of up to four [`Encode`](trait.Encode.html)
+/// types.
+pub trait EncodeArgs {
+    /// The encodings of the argument types, in order.
+    const ENCODINGS: &'static [Encoding<'static>];
+}
+
+macro_rules! encode_args {
+    ($($t:ident),*) => {
+        impl<$($t: Encode),*> EncodeArgs for ($($t,)*) {
+            const ENCODINGS: &'static [Encoding<'static>] = &[$($t::ENCODING),*];
+        }
+    };
+}
+
+encode_args!();
+encode_args!(A);
+encode_args!(A, B);
+encode_args!(A, B, C);
+encode_args!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::NSObject;
+
+    // The results of `@encode` for each target.
+    #[cfg(all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"))]
+    mod expected {
+        pub const BOOL: &str = "c";
+        pub const NS_INTEGER: &str = "q";
+        pub const NS_UINTEGER: &str = "Q";
+        pub const CG_FLOAT: &str = "d";
+    }
+
+    #[cfg(all(target_os = "ios", target_pointer_width = "32"))]
+    mod expected {
+        pub const BOOL: &str = "c";
+        pub const NS_INTEGER: &str = "i";
+        pub const NS_UINTEGER: &str = "I";
+        pub const CG_FLOAT: &str = "f";
+    }
+
+    #[cfg(not(any(
+        all(any(target_os = "macos", mac_catalyst), target_arch = "x86_64"),
+        all(target_os = "ios", target_pointer_width = "32"),
+    )))]
+    mod expected {
+        pub const BOOL: &str = "B";
+        pub const NS_INTEGER: &str = "q";
+        pub const NS_UINTEGER: &str = "Q";
+        pub const CG_FLOAT: &str = "d";
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(BOOL::ENCODING.to_string(), expected::BOOL);
+        assert_eq!(
+            crate::objc::NSInteger::ENCODING.to_string(),
+            expected::NS_INTEGER
+        );
+        assert_eq!(
+            crate::objc::NSUInteger::ENCODING.to_string(),
+            expected::NS_UINTEGER
+        );
+
+        assert_eq!(i32::ENCODING.to_string(), "i");
+        assert_eq!(<(*const *mut u8)>::ENCODING.to_string(), "^^C");
+        assert_eq!(<*mut c_void>::ENCODING.to_string(), "^v");
+        assert_eq!(<&NSObject>::ENCODING.to_string(), "@");
+        assert_eq!(<Option<&Class>>::ENCODING.to_string(), "#");
+        assert_eq!(Sel::ENCODING.to_string(), ":");
+        assert_eq!(<[u16; 4]>::ENCODING.to_string(), "[4S]");
+    }
+
+    #[cfg(feature = "core_graphics")]
+    #[test]
+    fn geometry() {
+        use crate::core_graphics::{CGAffineTransform, CGFloat, CGPoint, CGRect, CGSize};
+
+        let f = expected::CG_FLOAT;
+        assert_eq!(CGFloat::ENCODING.to_string(), f);
+        assert_eq!(
+            CGPoint::ENCODING.to_string(),
+            format!("{{CGPoint={0}{0}}}", f)
+        );
+        assert_eq!(
+            CGSize::ENCODING.to_string(),
+            format!("{{CGSize={0}{0}}}", f)
+        );
+        assert_eq!(
+            CGRect::ENCODING.to_string(),
+            format!("{{CGRect={{CGPoint={0}{0}}}{{CGSize={0}{0}}}}}", f)
+        );
+        assert_eq!(
+            CGAffineTransform::ENCODING.to_string(),
+            format!("{{CGAffineTransform={0}{0}{0}{0}{0}{0}}}", f)
+        );
+    }
+
+    #[cfg(feature = "foundation")]
+    #[test]
+    fn range() {
+        let u = expected::NS_UINTEGER;
+        assert_eq!(
+            crate::foundation::NSRange::ENCODING.to_string(),
+            format!("{{_NSRange={0}{0}}}", u)
+        );
+    }
+
+    #[test]
+    fn method() {
+        let encoding = Encoding::method(&BOOL::ENCODING, &[<&NSObject>::ENCODING, usize::ENCODING]);
+        let expected = format!("{}@:@{}", expected::BOOL, expected::NS_UINTEGER);
+        assert_eq!(encoding.to_str(), Ok(&*expected));
+
+        let encoding = Encoding::block(&Encoding::Void, <(&NSObject, *mut BOOL)>::ENCODINGS);
+        let expected = format!("v@?@^{}", expected::BOOL);
+        assert_eq!(encoding.to_str(), Ok(&*expected));
+    }
+}
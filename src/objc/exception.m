@@ -0,0 +1,22 @@
+// Catches Objective-C exceptions on behalf of `src/objc/exception.rs`.
+//
+// Rust cannot catch foreign exceptions, so this is compiled by `build.rs`.
+
+#include <objc/objc.h>
+
+id objc_retain(id value);
+
+// Calls `f` with `context` and returns 1 if it threw, with the retained
+// exception object written to `exception`.
+//
+// Rust panics unwind through here untouched because `@catch (id)` only
+// matches Objective-C exceptions.
+int fruity_try_catch(void (*f)(void *), void *context, id *exception) {
+    @try {
+        f(context);
+        return 0;
+    } @catch (id caught) {
+        *exception = objc_retain(caught);
+        return 1;
+    }
+}
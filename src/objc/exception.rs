@@ -0,0 +1,245 @@
+use super::NSObject;
+use crate::core::Arc;
+use std::{ffi::c_void, os::raw::c_int, ptr};
+
+#[cfg(feature = "catch_all")]
+use std::cell::Cell;
+
+#[cfg(feature = "foundation")]
+use super::ClassType;
+#[cfg(feature = "foundation")]
+use crate::foundation::{NSException, NSExceptionName};
+
+/// Calls `f` and returns its result, or the exception it threw.
+///
+/// Objective-C exceptions can be caught here because message sends use the
+/// `"C-unwind"` ABI. Rust panics in `f` are not caught.
+///
+/// If the thrown object is not an `NSException`, it is wrapped in an
+/// exception named [`NSExceptionName::generic`](../foundation/struct.NSExceptionName.html#method.generic)
+/// whose reason is the object's description.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fruity::foundation::{NSException, NSExceptionName};
+///
+/// let raised = NSException::new(NSExceptionName::generic(), None, None);
+/// let caught = fruity::objc::catch_exception(|| raised.raise()).unwrap_err();
+///
+/// assert_eq!(*caught.name(), *NSExceptionName::generic());
+/// ```
+///
+/// # Limitations
+///
+/// With the `catch_all` feature, message sends within `f` leave exceptions
+/// for this to catch rather than panicking.
+///
+/// Only exceptions thrown by message sends can be caught. An exception that
+/// unwinds through a C function, or through a Rust closure called by
+/// Objective-C, aborts the process.
+#[cfg(feature = "foundation")]
+pub fn catch_exception<F, R>(f: F) -> Result<R, Arc<NSException>>
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(feature = "catch_all")]
+    let _catching = Catching::enter();
+
+    try_catch(f).map_err(|object| match object {
+        Some(object) if object.is_kind_of_class(NSException::class()) => unsafe {
+            Arc::cast_unchecked(object)
+        },
+        object => {
+            let reason = object.map(|object| object.description());
+            NSException::new(NSExceptionName::generic(), reason.as_deref(), None)
+        }
+    })
+}
+
+/// Calls `f` and returns its result, or the retained object it threw.
+pub(crate) fn try_catch<F, R>(f: F) -> Result<R, Option<Arc<NSObject<'static>>>>
+where
+    F: FnOnce() -> R,
+{
+    // Compiled from `exception.m` by `build.rs`.
+    extern "C-unwind" {
+        fn fruity_try_catch(
+            f: unsafe extern "C-unwind" fn(*mut c_void),
+            context: *mut c_void,
+            exception: *mut *const NSObject<'static>,
+        ) -> c_int;
+    }
+
+    struct Context<F, R> {
+        f: Option<F>,
+        result: Option<R>,
+    }
+
+    unsafe extern "C-unwind" fn call<F, R>(context: *mut c_void)
+    where
+        F: FnOnce() -> R,
+    {
+        // Panics unwind back to the caller of `try_catch`.
+        let context = &mut *context.cast::<Context<F, R>>();
+        if let Some(f) = context.f.take() {
+            context.result = Some(f());
+        }
+    }
+
+    let mut context = Context {
+        f: Some(f),
+        result: None,
+    };
+    let context_ptr: *mut Context<F, R> = &mut context;
+    let mut exception: *const NSObject<'static> = ptr::null();
+
+    let threw = unsafe { fruity_try_catch(call::<F, R>, context_ptr.cast(), &mut exception) };
+
+    if threw == 0 {
+        Ok(context.result.expect("Closure did not run"))
+    } else if exception.is_null() {
+        Err(None)
+    } else {
+        Err(Some(unsafe { Arc::from_raw(exception) }))
+    }
+}
+
+/// Calls `f`, which sends a message, and turns any exception it throws into a
+/// panic when the `catch_all` feature is enabled.
+#[inline(always)]
+pub(crate) fn guard<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(feature = "catch_all")]
+    {
+        if CATCH_DEPTH.with(Cell::get) > 0 {
+            return f();
+        }
+        match try_catch(f) {
+            Ok(result) => result,
+            Err(exception) => panic_with(exception),
+        }
+    }
+
+    #[cfg(not(feature = "catch_all"))]
+    {
+        f()
+    }
+}
+
+#[cfg(feature = "catch_all")]
+thread_local! {
+    // The number of `catch_exception` calls on this thread, within which
+    // exceptions are left for them to catch.
+    static CATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+#[cfg(all(feature = "catch_all", feature = "foundation"))]
+struct Catching;
+
+#[cfg(all(feature = "catch_all", feature = "foundation"))]
+impl Catching {
+    fn enter() -> Self {
+        CATCH_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Catching
+    }
+}
+
+#[cfg(all(feature = "catch_all", feature = "foundation"))]
+impl Drop for Catching {
+    fn drop(&mut self) {
+        CATCH_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+#[cfg(feature = "catch_all")]
+#[cold]
+#[inline(never)]
+fn panic_with(exception: Option<Arc<NSObject<'static>>>) -> ! {
+    use super::autoreleasepool;
+    use std::{ffi::CStr, os::raw::c_char};
+
+    let exception = match exception {
+        Some(exception) => exception,
+        None => panic!("Uncaught Objective-C exception: nil"),
+    };
+
+    // `NSString` is not available without `foundation`.
+    let description = autoreleasepool(|_| unsafe {
+        let description: *const NSObject<'static> = _msg_send_any![exception, description];
+        if description.is_null() {
+            return String::new();
+        }
+        let utf8: *const c_char = _msg_send_any![&*description, UTF8String];
+        if utf8.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(utf8).to_string_lossy().into_owned()
+        }
+    });
+
+    panic!("Uncaught Objective-C exception: {}", description);
+}
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSArray, NSString};
+    use crate::objc::autoreleasepool;
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    #[test]
+    fn out_of_bounds() {
+        static DROPPED: AtomicBool = AtomicBool::new(false);
+
+        // Sets a flag when dropped as the exception unwinds.
+        struct DropFlag;
+
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                DROPPED.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let array = NSArray::<NSString>::from_slice(&[]);
+        let exception = autoreleasepool(|_| {
+            catch_exception(|| {
+                let _flag = DropFlag;
+                let _: *const NSString = unsafe { _msg_send_any![array, objectAtIndex: 0usize] };
+                unreachable!("`objectAtIndex:` did not raise");
+            })
+            .unwrap_err()
+        });
+
+        // The exception outlives the pool that drained its autoreleases.
+        assert!(DROPPED.load(Ordering::SeqCst));
+        assert_eq!(*exception.name(), *NSExceptionName::range());
+        assert!(exception.reason().is_some());
+    }
+
+    #[test]
+    fn raise() {
+        let reason = NSString::from_str("reason");
+        let raised = NSException::new(NSExceptionName::invalid_argument(), Some(&reason), None);
+
+        let caught = catch_exception(|| raised.raise()).unwrap_err();
+        assert!(ptr::eq(&*caught, &*raised));
+        assert_eq!(*caught.reason().unwrap(), *"reason");
+        assert!(!caught.call_stack_symbols().is_empty());
+
+        assert_eq!(catch_exception(|| 42).ok(), Some(42));
+    }
+
+    #[test]
+    fn panic_passes_through() {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _ = catch_exception(|| panic!("not an exception"));
+        }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use super::{NSUInteger, ObjCObject, ObjectType};
+use std::{marker::PhantomData, mem, ops::Range, os::raw::c_ulong, ptr};
+
+/// A collection whose elements can be enumerated in batches using
+/// [`NSFastEnumeration`](https://developer.apple.com/documentation/foundation/nsfastenumeration).
+///
+/// This is much faster than sending a message per element, such as
+/// `objectAtIndex:` or `nextObject`.
+///
+/// # Safety
+///
+/// `Self` must conform to `NSFastEnumeration` and enumerate only instances of
+/// [`Item`](#associatedtype.Item), which must be retained by `self`.
+pub unsafe trait NSFastEnumeration<'data>: ObjectType<'data> {
+    /// The type of the enumerated elements.
+    type Item: 'data;
+
+    /// Returns an iterator over the elements of `self`.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if `self` is mutated during enumeration, rather
+    /// than raising an Objective-C exception.
+    #[inline]
+    fn iter_fast<'a>(&'a self) -> FastEnumerator<'a, Self::Item>
+    where
+        'data: 'a,
+    {
+        FastEnumerator {
+            object: self.as_objc_object(),
+            state: NSFastEnumerationState::default(),
+            buf: [ptr::null(); BUF_LEN],
+            items: Items::Buffer(0),
+            index: 0,
+            len: 0,
+            mutations: None,
+            started: false,
+            finished: false,
+            marker: PhantomData,
+        }
+    }
+}
+
+// The number of elements that a collection may copy into the iterator at once.
+const BUF_LEN: usize = 16;
+
+/// See [documentation](https://developer.apple.com/documentation/foundation/nsfastenumerationstate).
+#[repr(C)]
+struct NSFastEnumerationState {
+    state: c_ulong,
+    items_ptr: *const *const ObjCObject<'static>,
+    mutations_ptr: *const c_ulong,
+    extra: [c_ulong; 5],
+}
+
+impl Default for NSFastEnumerationState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            state: 0,
+            items_ptr: ptr::null(),
+            mutations_ptr: ptr::null(),
+            extra: [0; 5],
+        }
+    }
+}
+
+// Where the elements of the current batch are stored.
+//
+// Collections may point `items_ptr` into the iterator, which would dangle once
+// the iterator is moved, so those elements are accessed relative to it.
+#[derive(Copy, Clone)]
+enum Items {
+    // Elements within `buf`, starting at an offset.
+    Buffer(usize),
+    // Elements owned by the collection.
+    External(*const *const ObjCObject<'static>),
+}
+
+/// An iterator over the elements of an
+/// [`NSFastEnumeration`](trait.NSFastEnumeration.html) collection.
+///
+/// This is created by
+/// [`NSFastEnumeration::iter_fast`](trait.NSFastEnumeration.html#method.iter_fast).
+pub struct FastEnumerator<'a, T> {
+    object: &'a ObjCObject<'a>,
+    state: NSFastEnumerationState,
+    buf: [*const ObjCObject<'static>; BUF_LEN],
+    items: Items,
+    index: usize,
+    len: usize,
+    // The mutation counter of the collection and its first value, or `None`
+    // if the counter is within `self` and so cannot change.
+    mutations: Option<(*const c_ulong, c_ulong)>,
+    started: bool,
+    finished: bool,
+    marker: PhantomData<&'a T>,
+}
+
+#[cfg(test)]
+thread_local! {
+    static BATCHES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
+
+/// Returns the number of batches requested on the current thread.
+#[cfg(test)]
+pub(crate) fn batch_count() -> usize {
+    BATCHES.with(|batches| batches.get())
+}
+
+// Returns the addresses occupied by `value`.
+#[inline]
+fn address_range<T>(value: &T) -> Range<usize> {
+    let start = value as *const T as usize;
+    start..start + mem::size_of::<T>()
+}
+
+impl<T> FastEnumerator<'_, T> {
+    #[inline]
+    fn check_mutations(&self) {
+        if let Some((mutations, initial)) = self.mutations {
+            if unsafe { *mutations } != initial {
+                panic!("Collection was mutated while being enumerated");
+            }
+        }
+    }
+
+    // Requests the next batch of elements, returning `false` at the end.
+    fn next_batch(&mut self) -> bool {
+        #[cfg(test)]
+        BATCHES.with(|batches| batches.set(batches.get() + 1));
+
+        let state: *mut NSFastEnumerationState = &mut self.state;
+        let buf = self.buf.as_mut_ptr();
+        let buf_len = BUF_LEN as NSUInteger;
+        let len: NSUInteger = unsafe {
+            _msg_send_any![
+                self.object,
+                countByEnumeratingWithState: state
+                objects: buf
+                count: buf_len
+            ]
+        };
+
+        self.index = 0;
+        self.len = len;
+        if len == 0 {
+            return false;
+        }
+
+        let items_ptr = self.state.items_ptr;
+        let buf = address_range(&self.buf);
+        let extra = address_range(&self.state.extra);
+
+        self.items = match items_ptr as usize {
+            address if buf.contains(&address) => {
+                Items::Buffer((address - buf.start) / mem::size_of::<*const ObjCObject>())
+            }
+            address if extra.contains(&address) => {
+                // Copy the elements to `buf`, which can hold all of `extra`.
+                let offset = (address - extra.start) / mem::size_of::<c_ulong>();
+                let values = self.state.extra[offset..].iter().take(len);
+                for (slot, &value) in self.buf.iter_mut().zip(values) {
+                    *slot = value as *const ObjCObject;
+                }
+                self.len = len.min(self.state.extra.len() - offset);
+                Items::Buffer(0)
+            }
+            _ => Items::External(items_ptr),
+        };
+
+        let mutations_ptr = self.state.mutations_ptr;
+        if !self.started {
+            self.started = true;
+            let within_self = address_range(self).contains(&(mutations_ptr as usize));
+            if !mutations_ptr.is_null() && !within_self {
+                self.mutations = Some((mutations_ptr, unsafe { *mutations_ptr }));
+            }
+        }
+        true
+    }
+}
+
+impl<'a, T: 'a> Iterator for FastEnumerator<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.finished {
+            return None;
+        }
+        self.check_mutations();
+
+        if self.index == self.len {
+            if !self.next_batch() {
+                self.finished = true;
+                return None;
+            }
+            self.check_mutations();
+        }
+
+        let item = match self.items {
+            Items::Buffer(offset) => self.buf[offset + self.index],
+            Items::External(items) => unsafe { *items.add(self.index) },
+        };
+        self.index += 1;
+
+        // SAFETY: The collection retains its elements for at least `'a`.
+        Some(unsafe { &*item.cast::<T>() })
+    }
+}
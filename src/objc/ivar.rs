@@ -0,0 +1,74 @@
+use super::sys;
+use std::{cell::UnsafeCell, ffi::CStr, fmt, panic::RefUnwindSafe};
+
+/// An instance variable in a [`Class`](super::Class) definition.
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/ivar).
+///
+/// # Usage
+///
+/// This is an opaque type meant to be used behind a shared reference `&Ivar`,
+/// which is semantically equivalent to `Ivar _Nonnull`.
+///
+/// A nullable instance variable is defined as `Option<&Ivar>`, which is
+/// semantically equivalent to `Ivar _Nullable`.
+#[repr(C)]
+pub struct Ivar {
+    // Stores data that may be mutated behind a shared reference. Internal
+    // mutability triggers undefined behavior without `UnsafeCell`.
+    _data: UnsafeCell<[u8; 0]>,
+}
+
+unsafe impl Send for Ivar {}
+unsafe impl Sync for Ivar {}
+
+// Although this uses `UnsafeCell`, it does not point to any Rust types.
+impl RefUnwindSafe for Ivar {}
+
+impl fmt::Debug for Ivar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ivar")
+            .field("name", &self.name())
+            .field("type_encoding", &self.type_encoding())
+            .field("offset", &self.offset())
+            .finish()
+    }
+}
+
+impl Ivar {
+    /// Returns the name of this instance variable.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/ivar_getname(_:)).
+    #[inline]
+    #[doc(alias = "ivar_getName")]
+    pub fn name(&self) -> &CStr {
+        unsafe { CStr::from_ptr(sys::ivar_getName(self)) }
+    }
+
+    /// Returns the type encoding of this instance variable, or `None` if it
+    /// has none.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/ivar_gettypeencoding(_:)).
+    #[inline]
+    #[doc(alias = "ivar_getTypeEncoding")]
+    pub fn type_encoding(&self) -> Option<&CStr> {
+        unsafe {
+            let encoding = sys::ivar_getTypeEncoding(self);
+            if encoding.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(encoding))
+            }
+        }
+    }
+
+    /// Returns the offset of this instance variable from the start of an
+    /// object.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/ivar_getoffset(_:)).
+    #[inline]
+    #[doc(alias = "ivar_getOffset")]
+    pub fn offset(&self) -> isize {
+        unsafe { sys::ivar_getOffset(self) }
+    }
+}
@@ -16,6 +16,8 @@ macro_rules! objc_class_type {
                 $crate::_objc_class!(@ $class_symbol)
             }
         }
+
+        downcast_target!(<$lifetime> $obj<$lifetime>);
     };
     (@processed $obj:ident, $class:expr, $class_symbol:expr) => {
         impl $crate::objc::ClassType<'static> for $obj {
@@ -28,6 +30,21 @@ macro_rules! objc_class_type {
                 unsafe { &CLASS }
             }
         }
+
+        downcast_target!($obj);
+    };
+}
+
+// Implements `DowncastTarget` for a class type whose class fully determines
+// it. Generic collections only use this with type-erased `NSObject` elements.
+macro_rules! downcast_target {
+    (<$lifetime:lifetime> $ty:ty) => {
+        impl<$lifetime> $crate::objc::private::Sealed for $ty {}
+        impl<$lifetime> $crate::objc::DowncastTarget<$lifetime> for $ty {}
+    };
+    ($ty:ty) => {
+        impl $crate::objc::private::Sealed for $ty {}
+        impl $crate::objc::DowncastTarget<'static> for $ty {}
     };
 }
 
@@ -58,6 +75,27 @@ macro_rules! objc_subclass {
 
         objc_class_type!($a <$lifetime>);
     };
+    (
+        $(#[$meta:meta])+
+        $vis:vis class $a:ident <$($t:ident),+> : $b:ty ;
+    ) => {
+        subclass! {
+            $(#[$meta])+
+            $vis class $a <$($t),+> : $b ;
+        }
+
+        impl<$($t: 'static),+> $crate::objc::ObjectType<'static> for $a<$($t),+> {}
+
+        // Type parameters only exist in Rust, so all instantiations share the
+        // same class. This is unchecked: an instance of the class may have
+        // elements of any type, so these types must not be downcast to.
+        impl<$($t: 'static),+> $crate::objc::ClassType<'static> for $a<$($t),+> {
+            #[inline]
+            fn class() -> &'static $crate::objc::Class {
+                $crate::_objc_class!(@ concat!("OBJC_CLASS_$_", stringify!($a)))
+            }
+        }
+    };
 }
 
 // This macro is intentionally undocumented to ensure it is not publicly
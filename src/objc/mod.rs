@@ -21,30 +21,54 @@ mod macros_pub;
 #[macro_use]
 mod msg;
 
+mod associated;
 mod autoreleasepool;
+pub(crate) mod block;
 mod bool;
 mod class;
+mod class_builder;
 mod class_type;
+mod encode;
+mod exception;
+pub(crate) mod fast_enumeration;
 mod image_info;
 mod int;
+mod ivar;
 mod method;
 mod ns_object;
+mod ns_object_protocol;
 mod objc_object;
 mod object_type;
 mod property;
+mod protocol;
+mod swizzle;
+mod weak;
 
 pub use self::bool::*;
+pub use associated::*;
 pub use autoreleasepool::*;
+pub use block::{Block, BlockArgs, BlockFn, RcBlock, StackBlock};
 pub use class::*;
+pub use class_builder::*;
 pub use class_type::*;
+pub use encode::*;
+#[cfg(feature = "foundation")]
+pub use exception::catch_exception;
+pub use fast_enumeration::*;
 pub use image_info::*;
 pub use int::*;
+pub use ivar::*;
 pub use method::*;
+pub use msg::{msg_send_super, CachedImp, MsgArgs, MsgReturn, MsgReturnKind};
 pub use ns_object::*;
+pub use ns_object_protocol::*;
 pub use objc_object::*;
 pub use object_type::*;
 pub use property::*;
+pub use protocol::*;
 pub use sel::Sel;
+pub use swizzle::*;
+pub use weak::*;
 
 #[link(name = "objc", kind = "dylib")]
 extern "C" {}
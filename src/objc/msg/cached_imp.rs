@@ -0,0 +1,156 @@
+use super::{ret, MsgArgs, MsgReturn};
+use crate::objc::{sys, Class, Imp, ObjCObject, ObjectType, Sel};
+use std::{cell::Cell, ffi::c_void, fmt, marker::PhantomData, ptr::NonNull};
+
+/// The implementation of a method, cached for calling repeatedly without
+/// the dynamic dispatch of a message send.
+///
+/// The implementation is looked up for the class of the first receiver and
+/// looked up again whenever a receiver of a different class is passed. This
+/// keeps calls correct when receivers are of different subclasses, or when
+/// the runtime changes an object's class, such as for key-value observing.
+///
+/// `A` is the tuple of argument types and `R` is the return type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fruity::foundation::NSString;
+/// use fruity::objc::{CachedImp, NSUInteger};
+///
+/// let string = NSString::from_str("hello");
+/// let character_at = CachedImp::<(NSUInteger,), u16>::new(fruity::selector!(characterAtIndex:));
+///
+/// let characters: Vec<u16> = (0..string.length())
+///     .map(|i| unsafe { character_at.call(&*string, (i,)) })
+///     .collect();
+/// ```
+pub struct CachedImp<A, R> {
+    sel: Sel,
+    cache: Cell<Option<(NonNull<Class>, Imp)>>,
+    _signature: PhantomData<fn(A) -> R>,
+}
+
+// Classes and implementations are never deallocated, so they can be used on
+// any thread.
+unsafe impl<A, R> Send for CachedImp<A, R> {}
+
+impl<A, R> fmt::Debug for CachedImp<A, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedImp")
+            .field("sel", &self.sel)
+            .field("cache", &self.cache.get())
+            .finish()
+    }
+}
+
+impl<A, R> CachedImp<A, R>
+where
+    A: MsgArgs,
+    R: MsgReturn,
+{
+    /// Creates a cache for the implementation of the method named `sel`.
+    ///
+    /// The implementation is not looked up until the first call.
+    #[inline]
+    pub const fn new(sel: Sel) -> Self {
+        Self {
+            sel,
+            cache: Cell::new(None),
+            _signature: PhantomData,
+        }
+    }
+
+    /// Returns the selector of the method.
+    #[inline]
+    pub fn sel(&self) -> Sel {
+        self.sel
+    }
+
+    /// Returns the implementation for instances of `class`, looking it up if
+    /// `class` is not the class of the previous receiver.
+    #[inline]
+    pub fn imp_for(&self, class: &Class) -> Imp {
+        let class = NonNull::from(class);
+        match self.cache.get() {
+            Some((cached, imp)) if cached == class => imp,
+            _ => {
+                let imp = Self::lookup(unsafe { class.as_ref() }, self.sel);
+                self.cache.set(Some((class, imp)));
+                imp
+            }
+        }
+    }
+
+    #[inline]
+    fn lookup(class: &Class, sel: Sel) -> Imp {
+        // Forwarding a message that returns a structure in memory requires a
+        // different function.
+        #[cfg(not(target_arch = "aarch64"))]
+        {
+            if ret::is_stret::<R>() {
+                return unsafe { sys::class_getMethodImplementation_stret(class, sel) };
+            }
+        }
+
+        class.method_implementation(sel)
+    }
+
+    /// Calls the implementation for `receiver` with `args`.
+    ///
+    /// # Safety
+    ///
+    /// The method must take arguments of types `A` and return `R`.
+    #[inline]
+    pub unsafe fn call<'data, T>(&self, receiver: &T, args: A) -> R
+    where
+        T: ObjectType<'data>,
+    {
+        let obj: *const ObjCObject<'static> =
+            (receiver.as_objc_object() as *const ObjCObject).cast();
+        let imp = self.imp_for(&*sys::object_getClass(obj));
+
+        A::call_imp(imp, obj as *const c_void, self.sel, args)
+    }
+}
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::core::Arc;
+    use crate::foundation::{NSMutableString, NSString};
+    use crate::objc::NSUInteger;
+
+    #[test]
+    fn same_as_send() {
+        let string = NSString::from_str("The quick brown fox jumps over the lazy dog");
+        let len = string.length();
+        let character_at = CachedImp::<(NSUInteger,), u16>::new(selector!(characterAtIndex:));
+
+        for i in 0..10_000 {
+            let index = i % len;
+            let sent: u16 =
+                unsafe { string._msg_send_any_with(selector!(characterAtIndex:), (index,)) };
+            let called = unsafe { character_at.call(&*string, (index,)) };
+            assert_eq!(called, sent);
+        }
+    }
+
+    #[test]
+    fn class_change() {
+        let length = CachedImp::<(), NSUInteger>::new(selector!(length));
+
+        let constant = crate::ns_string!("constant");
+        let owned = NSString::from_str("a heap-allocated string");
+        let mut mutable = NSMutableString::from_str("mutable");
+
+        // Each string is an instance of a different private subclass.
+        assert_eq!(unsafe { length.call(constant, ()) }, 8);
+        assert_eq!(unsafe { length.call(&*owned, ()) }, 23);
+        assert_eq!(unsafe { length.call(&*mutable, ()) }, 7);
+
+        Arc::get_mut(&mut mutable).unwrap().push_str("!");
+        assert_eq!(unsafe { length.call(&*mutable, ()) }, 8);
+        assert_eq!(unsafe { length.call(constant, ()) }, 8);
+    }
+}
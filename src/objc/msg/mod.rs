@@ -1,7 +1,12 @@
-use super::{Class, ObjCObject, Sel};
+use super::{Class, Imp, ObjCObject, ObjectType, Sel};
 use std::{ffi::c_void, mem};
 
+mod cached_imp;
 mod get_fn;
+mod ret;
+
+pub use cached_imp::CachedImp;
+pub use ret::{MsgReturn, MsgReturnKind};
 
 macro_rules! _msg_send_any {
     ($obj:expr, $sel:ident $(=> $ret:ty)?) => {
@@ -55,7 +60,7 @@ impl ObjCObject<'_> {
     #[inline]
     pub(crate) unsafe fn _msg_send_any<T>(&self, sel: Sel) -> T
     where
-        T: 'static,
+        T: ret::MsgReturn,
     {
         self._msg_send_any_with(sel, ())
     }
@@ -64,7 +69,7 @@ impl ObjCObject<'_> {
     pub(crate) unsafe fn _msg_send_any_with<A, T>(&self, sel: Sel, args: A) -> T
     where
         A: super::msg::MsgArgs,
-        T: 'static,
+        T: ret::MsgReturn,
     {
         A::msg_send_any(self as *const Self as *const c_void, sel, args)
     }
@@ -89,7 +94,7 @@ impl Class {
     #[inline]
     pub(crate) unsafe fn _msg_send_any<T>(&self, sel: Sel) -> T
     where
-        T: 'static,
+        T: ret::MsgReturn,
     {
         self._msg_send_any_with(sel, ())
     }
@@ -98,7 +103,7 @@ impl Class {
     pub(crate) unsafe fn _msg_send_any_with<A, T>(&self, sel: Sel, args: A) -> T
     where
         A: super::msg::MsgArgs,
-        T: 'static,
+        T: ret::MsgReturn,
     {
         A::msg_send_any(self as *const Self as *const c_void, sel, args)
     }
@@ -118,34 +123,119 @@ where {
     }
 }
 
-// This trait is intentionally undocumented to ensure it is not publicly
-// exported.
-#[deny(missing_docs)]
-pub trait MsgArgs: Sized {
+/// The receiver of a message to a superclass implementation.
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/objc_super).
+#[repr(C)]
+struct ObjCSuper<'a> {
+    receiver: *const c_void,
+    // With `objc_msgSendSuper2`, this is the class defining the calling
+    // method rather than its superclass.
+    current_class: &'a Class,
+}
+
+/// Sends a message to the implementation of `sel` in the superclass of `class`
+/// with `obj` as the receiver, like `[super sel]` in a method of `class`.
+///
+/// `class` is the class that defines the calling method, _not_ the class of
+/// `obj`. If `obj` is an instance of a subclass of `class` that also overrides
+/// the method, passing `obj.class()` would call the same implementation
+/// again, recursing forever.
+///
+/// Arguments are passed as a tuple, such as `(value,)` for one argument.
+///
+/// # Examples
+///
+/// Overriding `-description` to prepend text:
+///
+/// ```no_run
+/// use fruity::core::Arc;
+/// use fruity::foundation::NSString;
+/// use fruity::objc::{msg_send_super, Class, ClassBuilder, ClassType, NSObject, Sel};
+///
+/// extern "C" {
+///     fn objc_autorelease(obj: *const NSString) -> &'static NSString<'static>;
+/// }
+///
+/// extern "C" fn description(this: &NSObject<'static>, sel: Sel) -> &'static NSString<'static> {
+///     let class = Class::from_name("MyObject").unwrap();
+///     let description: &NSString = unsafe { msg_send_super(this, class, sel, ()) };
+///
+///     // `-description` returns a string that the caller does not own.
+///     let description = NSString::from_str(&format!("MyObject: {}", description));
+///     unsafe { objc_autorelease(Arc::into_raw(description)) }
+/// }
+///
+/// let mut builder = ClassBuilder::new("MyObject", NSObject::class()).unwrap();
+/// builder.add_method0(fruity::selector!(description), description).unwrap();
+/// builder.register().unwrap();
+/// ```
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/objc_msgsendsuper).
+///
+/// # Safety
+///
+/// The method must take arguments of types `A` and return `R`. `class` must
+/// be the class of `obj` or one of its superclasses.
+#[inline]
+#[doc(alias = "objc_msgSendSuper")]
+#[doc(alias = "objc_msgSendSuper2")]
+pub unsafe fn msg_send_super<'data, T, A, R>(obj: &T, class: &Class, sel: Sel, args: A) -> R
+where
+    T: ObjectType<'data>,
+    A: MsgArgs,
+    R: MsgReturn,
+{
+    let sup = ObjCSuper {
+        receiver: obj.as_objc_object() as *const ObjCObject as *const c_void,
+        current_class: class,
+    };
+    A::msg_send_super(&sup as *const ObjCSuper as *const c_void, sel, args)
+}
+
+/// A tuple of arguments that a message can be sent with.
+///
+/// This is implemented for tuples of up to twelve arguments.
+///
+/// # Safety
+///
+/// Each method must call the given variant of `objc_msgSend` with the receiver
+/// and selector, followed by the arguments.
+pub unsafe trait MsgArgs: Sized {
     /// Dispatches the appropriate version of `objc_msgSend` based on the return
     /// type.
-    unsafe fn msg_send_any<Ret: 'static>(obj: *const c_void, sel: Sel, args: Self) -> Ret;
+    #[doc(hidden)]
+    unsafe fn msg_send_any<Ret: ret::MsgReturn>(obj: *const c_void, sel: Sel, args: Self) -> Ret;
 
     /// Dispatches only to `objc_msgSend`.
+    #[doc(hidden)]
     unsafe fn msg_send_strict<Ret>(obj: *const c_void, sel: Sel, args: Self) -> Ret;
+
+    /// Dispatches the appropriate version of `objc_msgSendSuper2` based on the
+    /// return type, where `sup` points to an `objc_super`.
+    #[doc(hidden)]
+    unsafe fn msg_send_super<Ret: ret::MsgReturn>(sup: *const c_void, sel: Sel, args: Self) -> Ret;
+
+    /// Calls `imp` directly, as if `sel` were sent to `obj`.
+    #[doc(hidden)]
+    unsafe fn call_imp<Ret>(imp: Imp, obj: *const c_void, sel: Sel, args: Self) -> Ret;
 }
 
 /// Implements `MsgArgs` for tuples of different sizes.
 macro_rules! impl_msg_args_base {
     ($($arg:ident),*) => {
-        impl<$($arg,)*> MsgArgs for ($($arg,)*) {
+        unsafe impl<$($arg,)*> MsgArgs for ($($arg,)*) {
             #[inline]
             #[allow(non_snake_case)]
-            unsafe fn msg_send_any<Ret: 'static>(
+            unsafe fn msg_send_any<Ret: ret::MsgReturn>(
                 obj: *const c_void,
                 sel: Sel,
                 ($($arg,)*): Self,
             ) -> Ret {
-                // TODO(#7): Use "C-unwind" ABI when stable.
-                let msg_send: unsafe extern "C" fn(*const c_void, Sel $(, $arg)*) -> Ret
-                    = mem::transmute(get_fn::msg_send_fn::<Ret>());
+                let msg_send: unsafe extern "C-unwind" fn(*const c_void, Sel $(, $arg)*) -> Ret
+                    = mem::transmute(ret::msg_send_fn::<Ret>());
 
-                msg_send(obj, sel $(, $arg)*)
+                super::exception::guard(|| msg_send(obj, sel $(, $arg)*))
             }
 
             #[inline]
@@ -155,12 +245,38 @@ macro_rules! impl_msg_args_base {
                 sel: Sel,
                 ($($arg,)*): Self,
             ) -> Ret {
-                // TODO(#7): Use "C-unwind" ABI when stable.
-                let msg_send: unsafe extern "C" fn() = get_fn::objc_msgSend;
-                let msg_send: unsafe extern "C" fn(*const c_void, Sel $(, $arg)*) -> Ret
+                let msg_send: unsafe extern "C-unwind" fn() = get_fn::objc_msgSend;
+                let msg_send: unsafe extern "C-unwind" fn(*const c_void, Sel $(, $arg)*) -> Ret
                     = mem::transmute(msg_send);
 
-                msg_send(obj, sel $(, $arg)*)
+                super::exception::guard(|| msg_send(obj, sel $(, $arg)*))
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn msg_send_super<Ret: ret::MsgReturn>(
+                sup: *const c_void,
+                sel: Sel,
+                ($($arg,)*): Self,
+            ) -> Ret {
+                let msg_send: unsafe extern "C-unwind" fn(*const c_void, Sel $(, $arg)*) -> Ret
+                    = mem::transmute(ret::msg_send_super_fn::<Ret>());
+
+                super::exception::guard(|| msg_send(sup, sel $(, $arg)*))
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn call_imp<Ret>(
+                imp: Imp,
+                obj: *const c_void,
+                sel: Sel,
+                ($($arg,)*): Self,
+            ) -> Ret {
+                let imp: unsafe extern "C-unwind" fn(*const c_void, Sel $(, $arg)*) -> Ret
+                    = mem::transmute(imp);
+
+                super::exception::guard(|| imp(obj, sel $(, $arg)*))
             }
         }
     };
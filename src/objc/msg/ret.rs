@@ -0,0 +1,251 @@
+use super::get_fn::{objc_msgSend, objc_msgSendSuper2};
+use crate::core::{Arc, ObjectType};
+use crate::objc::{Sel, BOOL};
+use std::{mem, ptr::NonNull};
+
+#[cfg(target_arch = "x86")]
+use super::get_fn::objc_msgSend_fpret;
+#[cfg(not(target_arch = "aarch64"))]
+use super::get_fn::{objc_msgSendSuper2_stret, objc_msgSend_stret};
+
+/// How a value is returned from a C function, which determines the variant of
+/// `objc_msgSend` to use.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MsgReturnKind {
+    /// An integer, pointer, or `()`, returned in general-purpose registers.
+    Scalar,
+    /// A floating-point value, returned on the x87 stack on `x86`.
+    Float,
+    /// A `#[repr(C)]` aggregate, returned in registers or through a hidden
+    /// pointer argument depending on its size.
+    Struct,
+}
+
+/// A type that can be returned from a message send.
+///
+/// # Safety
+///
+/// `KIND` must match how the C ABI returns values of this type. For example,
+/// a `#[repr(C)]` structure must use `MsgReturnKind::Struct` and a newtype
+/// around an integer must use `MsgReturnKind::Scalar`.
+pub unsafe trait MsgReturn: Sized {
+    /// How the value is returned.
+    const KIND: MsgReturnKind;
+}
+
+/// Returns `true` if `Ret` is returned in memory through a hidden pointer
+/// argument, which requires the `_stret` variants of `objc_msgSend`.
+#[inline]
+#[cfg_attr(target_arch = "aarch64", allow(dead_code))]
+pub(super) fn is_stret<Ret: MsgReturn>() -> bool {
+    if Ret::KIND != MsgReturnKind::Struct {
+        return false;
+    }
+
+    let size = mem::size_of::<Ret>();
+
+    if cfg!(target_arch = "x86") {
+        !matches!(size, 1 | 2 | 4 | 8)
+    } else if cfg!(target_arch = "x86_64") {
+        size > 16
+    } else if cfg!(target_arch = "arm") {
+        size > 4
+    } else {
+        false
+    }
+}
+
+/// Returns the variant of `objc_msgSend` to use for sending a message that
+/// returns `Ret`.
+///
+/// This is resolved at compile time, since it depends only on constants.
+#[inline]
+pub fn msg_send_fn<Ret: MsgReturn>() -> unsafe extern "C-unwind" fn() {
+    #[cfg(not(target_arch = "aarch64"))]
+    {
+        if is_stret::<Ret>() {
+            return objc_msgSend_stret;
+        }
+    }
+
+    #[cfg(target_arch = "x86")]
+    {
+        if Ret::KIND == MsgReturnKind::Float {
+            return objc_msgSend_fpret;
+        }
+    }
+
+    objc_msgSend
+}
+
+/// Returns the variant of `objc_msgSendSuper2` to use for sending a message
+/// that returns `Ret`.
+///
+/// There is no `_fpret` variant, since floating-point values are returned the
+/// same way by `objc_msgSendSuper2`.
+#[inline]
+pub fn msg_send_super_fn<Ret: MsgReturn>() -> unsafe extern "C-unwind" fn() {
+    #[cfg(not(target_arch = "aarch64"))]
+    {
+        if is_stret::<Ret>() {
+            return objc_msgSendSuper2_stret;
+        }
+    }
+
+    objc_msgSendSuper2
+}
+
+macro_rules! impl_msg_return {
+    ($kind:ident => $($t:ty),+ $(,)?) => {
+        $(
+            unsafe impl MsgReturn for $t {
+                const KIND: MsgReturnKind = MsgReturnKind::$kind;
+            }
+        )+
+    };
+}
+
+impl_msg_return! {
+    Scalar =>
+    (), bool, BOOL, Sel, Option<Sel>,
+    u8, u16, u32, u64, usize,
+    i8, i16, i32, i64, isize,
+}
+
+impl_msg_return!(Float => f32, f64);
+
+unsafe impl<T> MsgReturn for *const T {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for *mut T {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for NonNull<T> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for Option<NonNull<T>> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for &T {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for &mut T {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for Option<&T> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T> MsgReturn for Option<&mut T> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T: ObjectType> MsgReturn for Arc<T> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+unsafe impl<T: ObjectType> MsgReturn for Option<Arc<T>> {
+    const KIND: MsgReturnKind = MsgReturnKind::Scalar;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_fn<Ret: MsgReturn>(expected: unsafe extern "C-unwind" fn()) -> bool {
+        msg_send_fn::<Ret>() as usize == expected as usize
+    }
+
+    #[test]
+    fn scalar_returns() {
+        assert!(is_fn::<()>(objc_msgSend));
+        assert!(is_fn::<usize>(objc_msgSend));
+        assert!(is_fn::<u64>(objc_msgSend));
+        assert!(is_fn::<*const u8>(objc_msgSend));
+        assert!(is_fn::<Option<Sel>>(objc_msgSend));
+
+        #[cfg(target_arch = "x86")]
+        assert!(is_fn::<f64>(objc_msgSend_fpret));
+        #[cfg(not(target_arch = "x86"))]
+        assert!(is_fn::<f64>(objc_msgSend));
+    }
+
+    #[test]
+    fn struct_returns() {
+        #[repr(C)]
+        struct Large([usize; 4]);
+
+        unsafe impl MsgReturn for Large {
+            const KIND: MsgReturnKind = MsgReturnKind::Struct;
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        assert!(is_fn::<Large>(objc_msgSend));
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "arm"))]
+        assert!(is_fn::<Large>(objc_msgSend_stret));
+    }
+
+    #[cfg(feature = "foundation")]
+    mod foundation {
+        use super::*;
+        use crate::core_graphics::CGRect;
+        use crate::foundation::NSRange;
+        use crate::objc::{Class, ClassBuilder, ClassType, NSObject};
+        use std::sync::Once;
+
+        extern "C" fn get_frame(_this: &NSObject<'static>, _cmd: Sel) -> CGRect {
+            CGRect::new(1.0, 2.0, 3.0, 4.0)
+        }
+
+        extern "C" fn get_range(_this: &NSObject<'static>, _cmd: Sel) -> NSRange {
+            NSRange {
+                location: 5,
+                length: 6,
+            }
+        }
+
+        fn test_class() -> &'static Class {
+            static REGISTER: Once = Once::new();
+
+            REGISTER.call_once(|| {
+                let mut builder =
+                    ClassBuilder::new("_FruityTestStructReturns", NSObject::class()).unwrap();
+                builder.add_method0(selector!(frame), get_frame).unwrap();
+                builder.add_method0(selector!(range), get_range).unwrap();
+                builder.register().unwrap();
+            });
+
+            Class::from_name("_FruityTestStructReturns").unwrap()
+        }
+
+        #[test]
+        fn rect() {
+            // 32 bytes on 64-bit targets, which is returned in memory on
+            // x86_64 and in registers on arm64.
+            let object: crate::core::Arc<NSObject> = unsafe { test_class().alloc_init() };
+            let rect: CGRect = unsafe { object._msg_send_any(selector!(frame)) };
+            assert_eq!(rect, CGRect::new(1.0, 2.0, 3.0, 4.0));
+        }
+
+        #[test]
+        fn range() {
+            // 16 bytes on 64-bit targets, which is returned in registers
+            // everywhere except 32-bit targets.
+            let object: crate::core::Arc<NSObject> = unsafe { test_class().alloc_init() };
+            let range: NSRange = unsafe { object._msg_send_any(selector!(range)) };
+            assert_eq!(
+                range,
+                NSRange {
+                    location: 5,
+                    length: 6,
+                }
+            );
+        }
+    }
+}
@@ -1,8 +1,7 @@
-use super::{Class, ClassType, NSUInteger, ObjCObject, Sel, BOOL};
+use super::{Class, ClassType, DowncastTarget, Imp, NSUInteger, ObjCObject, Protocol, Sel, BOOL};
 use crate::core::Arc;
 use crate::foundation::NSString;
 
-// TODO: Create `NSObjectProtocol` for `@protocol NSObject` and `Deref` to that.
 objc_subclass! {
     /// An instance of the root class for most Objective-C objects.
     ///
@@ -17,6 +16,23 @@ impl Default for Arc<NSObject<'_>> {
     }
 }
 
+impl<'data> Arc<NSObject<'data>> {
+    /// Attempts to convert `self` into an instance of `T`, returning `self` if
+    /// it is not a kind of `T`'s class.
+    ///
+    /// This checks the class the same way as
+    /// [`NSObject::downcast_ref`](struct.NSObject.html#method.downcast_ref),
+    /// and does not change the reference count of the object.
+    #[inline]
+    pub fn downcast<T: DowncastTarget<'data>>(self) -> Result<Arc<T>, Self> {
+        if self.is_kind_of_class(<T as ClassType>::class()) {
+            Ok(unsafe { Arc::cast_unchecked(self) })
+        } else {
+            Err(self)
+        }
+    }
+}
+
 impl PartialEq for NSObject<'_> {
     #[inline]
     fn eq(&self, other: &NSObject) -> bool {
@@ -60,6 +76,59 @@ impl<'data> NSObject<'data> {
         unsafe { _msg_send_any_cached![self, isMemberOfClass: class => BOOL] }.into()
     }
 
+    /// Returns `true` if the class of this object adopts `protocol`, or a
+    /// protocol that incorporates it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/conforms(to:)).
+    #[inline]
+    #[doc(alias = "conformsToProtocol")]
+    #[doc(alias = "conformsToProtocol:")]
+    pub fn conforms_to_protocol(&self, protocol: &Protocol) -> bool {
+        unsafe { _msg_send_any_cached![self, conformsToProtocol: protocol => BOOL] }.into()
+    }
+
+    /// Returns the function that is called when this object is sent
+    /// `selector`.
+    ///
+    /// This looks up the implementation for the object's actual class, which
+    /// may be a subclass created at runtime, such as for key-value observing.
+    /// To call the function repeatedly, prefer
+    /// [`CachedImp`](struct.CachedImp.html), which handles the object's class
+    /// changing.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobject/method(for:)).
+    #[inline]
+    #[doc(alias = "methodForSelector")]
+    #[doc(alias = "methodForSelector:")]
+    pub fn method_for(&self, selector: Sel) -> Imp {
+        unsafe {
+            let class =
+                super::sys::object_getClass(self as *const Self as *const ObjCObject<'static>);
+            (*class).method_implementation(selector)
+        }
+    }
+
+    /// Returns `self` as an instance of `T` if it is an instance of `T`'s
+    /// class or of a subclass.
+    ///
+    /// This checks the class using
+    /// [`is_kind_of_class`](#method.is_kind_of_class), so instances of
+    /// private subclasses in class clusters, such as `__NSCFString` for
+    /// [`NSString`](../foundation/struct.NSString.html), are also matched.
+    ///
+    /// Type parameters of collections cannot be checked, so collections can
+    /// only be downcast with type-erased elements such as `NSArray<NSObject>`.
+    /// Their elements can then be checked with
+    /// [`NSArray::downcast`](../foundation/struct.NSArray.html#method.downcast).
+    #[inline]
+    pub fn downcast_ref<T: DowncastTarget<'data>>(&self) -> Option<&T> {
+        if self.is_kind_of_class(<T as ClassType>::class()) {
+            Some(unsafe { &*(self as *const Self as *const T) })
+        } else {
+            None
+        }
+    }
+
     /// Returns an integer that can be used as a table address in a hash table
     /// structure.
     ///
@@ -107,3 +176,72 @@ impl<'data> NSObject<'data> {
         unsafe { _msg_send_any![self, debugDescription] }
     }
 }
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::foundation::{NSArray, NSNumber};
+
+    #[test]
+    fn downcast_class_cluster() {
+        let string = NSString::from_str("a string that is too long to be tagged");
+        let object: Arc<NSObject> = Arc::retain(&**string);
+
+        // The object is an instance of a private subclass.
+        assert!(!object.is_member_of_class(NSString::class()));
+        assert!(object.is_kind_of_class(NSString::class()));
+        assert_ne!(crate::objc::ObjectType::class(&*object), NSString::class());
+
+        assert_eq!(*object.downcast_ref::<NSString>().unwrap(), *string);
+        assert!(object.downcast_ref::<NSNumber>().is_none());
+
+        let retain_count = object.retain_count();
+        let object = object.downcast::<NSNumber>().err().unwrap();
+        let string = object.downcast::<NSString>().ok().unwrap();
+        assert_eq!(string.retain_count(), retain_count);
+        assert_eq!(*string, *"a string that is too long to be tagged");
+    }
+
+    #[test]
+    fn downcast_elements() {
+        let number = NSNumber::from_integer(7);
+        let string = NSString::from_str("seven");
+        let array = NSArray::<NSObject>::from_slice(&[&number, &string]);
+
+        let numbers: Vec<i64> = array
+            .iter()
+            .filter_map(|object| object.downcast_ref::<NSNumber>()?.as_i64())
+            .collect();
+        assert_eq!(numbers, [7]);
+
+        let strings: Vec<String> = array
+            .iter()
+            .filter_map(|object| Some(object.downcast_ref::<NSString>()?.to_string()))
+            .collect();
+        assert_eq!(strings, ["seven"]);
+    }
+
+    #[test]
+    fn downcast_collection() {
+        let number = NSNumber::from_integer(7);
+        let array = NSArray::<NSNumber>::from_slice(&[&number]);
+        let object: &NSObject = &array;
+
+        let array = object.downcast_ref::<NSArray<NSObject>>().unwrap();
+        assert!(array.downcast::<NSString>().is_none());
+        assert_eq!(array.downcast::<NSNumber>().unwrap()[0].as_i64(), Some(7));
+    }
+
+    #[test]
+    fn method_for() {
+        type Hash = unsafe extern "C" fn(&NSObject<'static>, Sel) -> NSUInteger;
+
+        let object = Arc::<NSObject>::default();
+        let imp = object.method_for(selector!(hash));
+        let hash = unsafe { std::mem::transmute::<Imp, Hash>(imp) };
+        assert_eq!(unsafe { hash(&object, selector!(hash)) }, object.hash());
+
+        let string = NSString::from_str("a string that is too long to be tagged");
+        assert_ne!(string.method_for(selector!(hash)), imp);
+    }
+}
@@ -0,0 +1,184 @@
+use super::{Class, NSUInteger, ObjCObject, ObjectType, Protocol, Sel, BOOL};
+use crate::core::Arc;
+
+#[cfg(feature = "foundation")]
+use crate::foundation::NSString;
+
+/// The methods of the `NSObject` protocol, which almost all Objective-C
+/// objects conform to.
+///
+/// Unlike [`NSObject`](struct.NSObject.html), which is a class, this models
+/// objects that are only known to conform to the protocol, such as
+/// `id<NSObject>` or instances of other root classes like `NSProxy`.
+///
+/// This is implemented for all Objective-C [object types](trait.ObjectType.html).
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol).
+pub trait NSObjectProtocol<'data>: ObjectType<'data> {
+    /// Returns `true` if this object and `other` are equal.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/isequal(_:)).
+    #[inline]
+    #[doc(alias = "isEqual")]
+    #[doc(alias = "isEqual:")]
+    fn is_equal<'other, T>(&self, other: &T) -> bool
+    where
+        T: AsRef<ObjCObject<'other>> + ?Sized,
+    {
+        let other = other.as_ref();
+        unsafe { _msg_send_any![self.as_objc_object(), isEqual: other => BOOL] }.into()
+    }
+
+    /// Returns an integer that can be used as a table address in a hash table
+    /// structure.
+    ///
+    /// Objects that are [equal](#method.is_equal) have the same hash code.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/hash).
+    #[inline]
+    #[doc(alias = "hash")]
+    fn hash_code(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self.as_objc_object(), hash] }
+    }
+
+    /// Returns a string that describes the contents of this object.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/description).
+    #[cfg(feature = "foundation")]
+    #[inline]
+    fn description(&self) -> Arc<NSString<'static>> {
+        unsafe { _msg_send_any![self.as_objc_object(), description] }
+    }
+
+    /// Returns a string that describes the contents of this object for
+    /// presentation in the debugger.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/debugdescription).
+    #[cfg(feature = "foundation")]
+    #[inline]
+    #[doc(alias = "debugDescription")]
+    fn debug_description(&self) -> Arc<NSString<'static>> {
+        unsafe { _msg_send_any![self.as_objc_object(), debugDescription] }
+    }
+
+    /// Returns `true` if this object implements or inherits a method that can
+    /// respond to `selector`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/responds(to:)).
+    #[inline]
+    #[doc(alias = "respondsToSelector")]
+    #[doc(alias = "respondsToSelector:")]
+    fn responds_to_selector(&self, selector: Sel) -> bool {
+        unsafe { _msg_send_any![self.as_objc_object(), respondsToSelector: selector => BOOL] }
+            .into()
+    }
+
+    /// Returns `true` if this object is an instance of `class` or of a
+    /// subclass.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/iskind(of:)).
+    #[inline]
+    #[doc(alias = "isKindOfClass")]
+    #[doc(alias = "isKindOfClass:")]
+    fn is_kind_of_class(&self, class: &Class) -> bool {
+        unsafe { _msg_send_any![self.as_objc_object(), isKindOfClass: class => BOOL] }.into()
+    }
+
+    /// Returns `true` if this object is an instance of `class`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/ismember(of:)).
+    #[inline]
+    #[doc(alias = "isMemberOfClass")]
+    #[doc(alias = "isMemberOfClass:")]
+    fn is_member_of_class(&self, class: &Class) -> bool {
+        unsafe { _msg_send_any![self.as_objc_object(), isMemberOfClass: class => BOOL] }.into()
+    }
+
+    /// Returns `true` if the class of this object adopts `protocol`, or a
+    /// protocol that incorporates it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/conforms(to:)).
+    #[inline]
+    #[doc(alias = "conformsToProtocol")]
+    #[doc(alias = "conformsToProtocol:")]
+    fn conforms_to_protocol(&self, protocol: &Protocol) -> bool {
+        unsafe { _msg_send_any![self.as_objc_object(), conformsToProtocol: protocol => BOOL] }
+            .into()
+    }
+
+    /// Returns `true` if this object does not descend from `NSObject`, such
+    /// as an instance of `NSProxy`.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/nsobjectprotocol/isproxy()).
+    #[inline]
+    #[doc(alias = "isProxy")]
+    fn is_proxy(&self) -> bool {
+        unsafe { _msg_send_any![self.as_objc_object(), isProxy => BOOL] }.into()
+    }
+
+    /// Returns this object's reference count.
+    ///
+    /// This method is only useful for debugging certain objects.
+    #[inline]
+    #[doc(alias = "retainCount")]
+    fn retain_count(&self) -> NSUInteger {
+        unsafe { _msg_send_any![self.as_objc_object(), retainCount] }
+    }
+}
+
+impl<'data, T: ObjectType<'data>> NSObjectProtocol<'data> for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::{ClassType, NSObject};
+
+    // Calls methods only through the protocol.
+    fn check_protocol<'data>(object: &impl NSObjectProtocol<'data>) {
+        assert!(object.is_equal(object.as_objc_object()));
+        assert!(object.responds_to_selector(selector!(hash)));
+        assert!(!object.responds_to_selector(selector!(fruityMissingMethod)));
+        assert!(object.is_kind_of_class(<NSObject as ClassType>::class()));
+        assert!(object.conforms_to_protocol(Protocol::get("NSObject").unwrap()));
+        assert!(!object.is_proxy());
+        assert_eq!(object.retain_count(), 1);
+    }
+
+    #[test]
+    fn ns_object() {
+        let object = Arc::<NSObject>::default();
+        check_protocol(&*object);
+
+        assert_eq!(NSObjectProtocol::hash_code(&*object), object.hash());
+        assert!(NSObjectProtocol::is_member_of_class(
+            &*object,
+            <NSObject as ClassType>::class()
+        ));
+        assert!(!NSObjectProtocol::is_equal(
+            &*object,
+            &*Arc::<NSObject>::default()
+        ));
+    }
+
+    #[test]
+    fn objc_object() {
+        let object = Arc::<NSObject>::default();
+        let object: &ObjCObject = &object;
+        check_protocol(object);
+    }
+
+    #[cfg(feature = "foundation")]
+    #[test]
+    fn descriptions() {
+        let object = Arc::<NSObject>::default();
+        let object: &ObjCObject = &object;
+        assert!(object
+            .description()
+            .to_string()
+            .starts_with("<NSObject: 0x"));
+        assert!(object
+            .debug_description()
+            .to_string()
+            .starts_with("<NSObject: 0x"));
+    }
+}
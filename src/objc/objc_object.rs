@@ -53,6 +53,15 @@ impl crate::core::ObjectType for ObjCObject<'_> {
         }
         objc_release(obj);
     }
+
+    #[inline]
+    fn is_unique(obj: &Self) -> bool {
+        // Constant and tagged pointer objects report `NSUIntegerMax`, so they
+        // are never considered unique. Weak references are not counted, but
+        // can be loaded into new strong references at any time.
+        let count: usize = unsafe { _msg_send_any_cached![obj, retainCount] };
+        count == 1 && !super::weak::is_weakly_referenced(obj)
+    }
 }
 
 impl<'data> super::ObjectType<'data> for ObjCObject<'data> {
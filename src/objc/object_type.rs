@@ -1,4 +1,4 @@
-use super::{Class, ObjCObject};
+use super::{Class, ObjCObject, WeakObj};
 
 /// An Objective-C object instance.
 ///
@@ -21,4 +21,13 @@ pub trait ObjectType<'data>: 'data + crate::core::ObjectType + AsRef<ObjCObject<
     {
         self.as_objc_object().class()
     }
+
+    /// Returns a weak reference to this object.
+    #[inline]
+    fn downgrade(&self) -> WeakObj<Self>
+    where
+        Self: Sized,
+    {
+        WeakObj::new(self)
+    }
 }
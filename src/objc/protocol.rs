@@ -0,0 +1,303 @@
+use super::{MethodDescription, Sel, BOOL};
+use std::{
+    cell::UnsafeCell,
+    ffi::{c_void, CStr, CString},
+    fmt, iter,
+    mem::MaybeUninit,
+    os::raw::{c_char, c_uint},
+    panic::RefUnwindSafe,
+    slice, vec,
+};
+
+/// An Objective-C protocol.
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/protocol).
+///
+/// # Usage
+///
+/// This is an opaque type meant to be used behind a shared reference
+/// `&Protocol`, which is semantically equivalent to `Protocol * _Nonnull`.
+#[repr(C)]
+pub struct Protocol {
+    // Stores data that may be mutated behind a shared reference. Internal
+    // mutability triggers undefined behavior without `UnsafeCell`.
+    _data: UnsafeCell<[u8; 0]>,
+}
+
+unsafe impl Send for Protocol {}
+unsafe impl Sync for Protocol {}
+
+// Although this uses `UnsafeCell`, it does not point to any Rust types.
+impl RefUnwindSafe for Protocol {}
+
+impl fmt::Debug for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Protocol").field(&self.name()).finish()
+    }
+}
+
+impl PartialEq for Protocol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { protocol_isEqual(self, other) }.into()
+    }
+}
+
+impl Eq for Protocol {}
+
+impl Protocol {
+    /// Returns the protocol named `name`, or `None` if no protocol with that
+    /// name is known to the runtime.
+    ///
+    /// Protocols are only known to the runtime if they are adopted by a class
+    /// or referenced with `@protocol` somewhere in the program.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/objc_getprotocol(_:)).
+    #[inline]
+    #[doc(alias = "objc_getProtocol")]
+    pub fn get(name: &str) -> Option<&'static Self> {
+        let name = CString::new(name).ok()?;
+        unsafe { objc_getProtocol(name.as_ptr()) }
+    }
+
+    /// Returns the name of this protocol.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/protocol_getname(_:)).
+    #[inline]
+    #[doc(alias = "protocol_getName")]
+    pub fn name(&self) -> &CStr {
+        unsafe { CStr::from_ptr(protocol_getName(self)) }
+    }
+
+    /// Returns `true` if this protocol is `other` or incorporates it.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/protocol_conformstoprotocol(_:_:)).
+    #[inline]
+    #[doc(alias = "protocol_conformsToProtocol")]
+    pub fn conforms_to(&self, other: &Protocol) -> bool {
+        unsafe { protocol_conformsToProtocol(self, other) }.into()
+    }
+
+    /// Returns the protocols that this protocol incorporates.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/protocol_copyprotocollist(_:_:)).
+    #[doc(alias = "protocol_copyProtocolList")]
+    pub fn protocols(&self) -> Vec<&'static Protocol> {
+        let mut len = MaybeUninit::<c_uint>::uninit();
+        unsafe {
+            let data = protocol_copyProtocolList(self, len.as_mut_ptr());
+            copy_and_free(data, len)
+        }
+    }
+
+    /// Returns the methods declared by this protocol, excluding those of the
+    /// protocols that it incorporates.
+    ///
+    /// Required instance methods come first, followed by required class
+    /// methods, optional instance methods, and optional class methods.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/protocol_copymethoddescriptionlist(_:_:_:_:)).
+    #[doc(alias = "protocol_copyMethodDescriptionList")]
+    pub fn methods(&self) -> ProtocolMethods<'_> {
+        let kinds = [(true, true), (true, false), (false, true), (false, false)];
+
+        let methods: Vec<ProtocolMethod> = kinds
+            .iter()
+            .flat_map(|&(required, instance)| {
+                let mut len = MaybeUninit::<c_uint>::uninit();
+                let descriptions = unsafe {
+                    let data = protocol_copyMethodDescriptionList(
+                        self,
+                        required.into(),
+                        instance.into(),
+                        len.as_mut_ptr(),
+                    );
+                    copy_and_free(data, len)
+                };
+
+                descriptions
+                    .into_iter()
+                    .map(move |description| ProtocolMethod {
+                        description,
+                        required,
+                        instance,
+                    })
+            })
+            .collect();
+
+        ProtocolMethods(methods.into_iter())
+    }
+}
+
+/// Copies a `malloc`-ed list into a `Vec` and frees it.
+unsafe fn copy_and_free<T: Copy>(data: *mut T, len: MaybeUninit<c_uint>) -> Vec<T> {
+    extern "C" {
+        fn free(ptr: *mut c_void);
+    }
+
+    if data.is_null() {
+        return Vec::new();
+    }
+    let vec = slice::from_raw_parts(data, len.assume_init() as usize).to_vec();
+    free(data.cast());
+    vec
+}
+
+/// A method declared by a [`Protocol`](struct.Protocol.html).
+#[derive(Copy, Clone, Debug)]
+pub struct ProtocolMethod<'a> {
+    description: MethodDescription<'a>,
+    required: bool,
+    instance: bool,
+}
+
+impl<'a> ProtocolMethod<'a> {
+    /// Returns the name of the method.
+    #[inline]
+    pub fn name(&self) -> Sel {
+        self.description.name()
+    }
+
+    /// Returns the method's type encoding.
+    #[inline]
+    pub fn type_encoding(&self) -> &'a CStr {
+        self.description.type_encoding()
+    }
+
+    /// Returns `true` if conforming classes must implement this method, or
+    /// `false` if it was declared `@optional`.
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Returns `true` if this is an instance method, or `false` if it is a
+    /// class method.
+    #[inline]
+    pub fn is_instance(&self) -> bool {
+        self.instance
+    }
+
+    /// Returns the method's name and type encoding.
+    #[inline]
+    pub fn description(&self) -> MethodDescription<'a> {
+        self.description
+    }
+}
+
+/// An iterator over the methods of a [`Protocol`](struct.Protocol.html),
+/// returned by [`Protocol::methods`](struct.Protocol.html#method.methods).
+#[derive(Clone, Debug)]
+pub struct ProtocolMethods<'a>(vec::IntoIter<ProtocolMethod<'a>>);
+
+impl<'a> Iterator for ProtocolMethods<'a> {
+    type Item = ProtocolMethod<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ProtocolMethods<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for ProtocolMethods<'_> {}
+
+impl iter::FusedIterator for ProtocolMethods<'_> {}
+
+extern "C" {
+    fn objc_getProtocol(name: *const c_char) -> Option<&'static Protocol>;
+
+    fn protocol_getName(protocol: &Protocol) -> *const c_char;
+    fn protocol_isEqual(protocol: &Protocol, other: &Protocol) -> BOOL;
+    fn protocol_conformsToProtocol(protocol: &Protocol, other: &Protocol) -> BOOL;
+    fn protocol_copyProtocolList(
+        protocol: &Protocol,
+        out_count: *mut c_uint,
+    ) -> *mut &'static Protocol;
+    fn protocol_copyMethodDescriptionList<'a>(
+        protocol: &'a Protocol,
+        is_required: BOOL,
+        is_instance: BOOL,
+        out_count: *mut c_uint,
+    ) -> *mut MethodDescription<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Arc;
+    use crate::objc::{Class, ClassType, NSObject};
+
+    #[test]
+    fn get() {
+        let protocol = Protocol::get("NSObject").unwrap();
+        assert_eq!(protocol.name().to_str(), Ok("NSObject"));
+        assert_eq!(Protocol::get("NSObject"), Some(protocol));
+        assert!(protocol.conforms_to(protocol));
+
+        assert!(Protocol::get("_FruityMissingProtocol").is_none());
+        assert!(Protocol::get("NS\0Object").is_none());
+    }
+
+    #[test]
+    fn conformance() {
+        let protocol = Protocol::get("NSObject").unwrap();
+
+        assert!(NSObject::class().conforms_to(protocol));
+        assert!(Arc::<NSObject>::default().conforms_to_protocol(protocol));
+    }
+
+    #[test]
+    fn methods() {
+        let protocol = Protocol::get("NSObject").unwrap();
+        let methods: Vec<ProtocolMethod> = protocol.methods().collect();
+
+        let is_equal = methods
+            .iter()
+            .find(|method| method.name() == selector!(isEqual:))
+            .unwrap();
+        assert!(is_equal.is_required());
+        assert!(is_equal.is_instance());
+
+        // Required instance methods come first.
+        let first_optional = methods
+            .iter()
+            .position(|method| !method.is_required())
+            .unwrap_or(methods.len());
+        assert!(methods[..first_optional]
+            .iter()
+            .all(|method| method.is_required()));
+        assert!(methods[first_optional..]
+            .iter()
+            .all(|method| !method.is_required()));
+    }
+
+    #[cfg(feature = "foundation")]
+    #[test]
+    fn incorporated() {
+        let copying = Protocol::get("NSCopying").unwrap();
+        let methods: Vec<ProtocolMethod> = copying.methods().collect();
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0].name(), selector!(copyWithZone:));
+
+        let secure_coding = Protocol::get("NSSecureCoding").unwrap();
+        let coding = Protocol::get("NSCoding").unwrap();
+        assert!(secure_coding.conforms_to(coding));
+        assert!(!coding.conforms_to(secure_coding));
+        assert!(secure_coding.protocols().contains(&coding));
+
+        let string_class = Class::from_name("NSString").unwrap();
+        assert!(string_class.conforms_to(copying));
+    }
+}
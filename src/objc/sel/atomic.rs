@@ -6,10 +6,13 @@ use std::{
 };
 
 /// Allows for globally caching reused selectors safely.
+///
+/// This is used by `selector!` to cache selectors in statics.
 #[repr(transparent)]
-pub(crate) struct AtomicSel(AtomicPtr<c_void>);
+pub struct AtomicSel(AtomicPtr<c_void>);
 
 impl AtomicSel {
+    /// Creates an empty cache.
     #[inline]
     pub const fn null() -> Self {
         Self(AtomicPtr::new(ptr::null_mut()))
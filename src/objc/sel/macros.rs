@@ -83,28 +83,34 @@ macro_rules! selector_str {
 /// # use fruity::selector;
 /// let sel = selector!(initWithArg::);
 /// ```
+///
+/// # Performance
+///
+/// Each use of this macro caches its selector in a static, so the selector
+/// is only registered with the runtime the first time that the expression is
+/// evaluated.
 #[macro_export]
 macro_rules! selector {
     ($($sel:tt)*) => {
         {
-            let ptr = $crate::selector_str!($($sel)*).as_ptr();
+            static SELECTOR: $crate::_priv::AtomicSel = $crate::_priv::AtomicSel::null();
 
-            // SAFETY: `selector_str!` creates a null-terminated UTF-8 string.
-            #[allow(unused_unsafe)]
-            let sel = unsafe { $crate::objc::Sel::register(ptr as _) };
+            SELECTOR.load_or_store_with(|| {
+                let ptr = $crate::selector_str!($($sel)*).as_ptr();
 
-            sel
+                // SAFETY: `selector_str!` creates a null-terminated UTF-8 string.
+                #[allow(unused_unsafe)]
+                unsafe {
+                    $crate::objc::Sel::register_raw(ptr as _)
+                }
+            })
         }
     };
 }
 
+// Selectors used by this crate are already cached by `selector!`.
 macro_rules! _cached_selector {
-    ($sel:ident) => {
-        $crate::objc::sel::cached::$sel
-            .load_or_store_with(|| $crate::selector!($sel))
-    };
-    ($($sel:ident :)+) => {
-        $crate::objc::sel::cached $(::$sel)+ ::SELECTOR
-            .load_or_store_with(|| $crate::selector!($($sel :)+))
+    ($($sel:tt)+) => {
+        $crate::selector!($($sel)+)
     };
 }
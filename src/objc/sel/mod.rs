@@ -1,6 +1,6 @@
 use super::BOOL;
 use std::{
-    ffi::CStr,
+    ffi::{CStr, CString},
     fmt,
     os::raw::{c_char, c_void},
     ptr::NonNull,
@@ -10,7 +10,6 @@ use std::{
 mod macros;
 
 pub(crate) mod atomic;
-pub(crate) mod cached;
 
 /// A method selector.
 ///
@@ -46,6 +45,25 @@ impl fmt::Debug for Sel {
 }
 
 impl Sel {
+    /// Registers a method name with the Objective-C runtime and returns the
+    /// selector.
+    ///
+    /// This is useful for names constructed at runtime, such as key-value
+    /// coding keys. For selector literals, prefer
+    /// [`selector!`](../macro.selector.html), which caches the result.
+    ///
+    /// See [documentation](https://developer.apple.com/documentation/objectivec/sel_registername(_:)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains a null byte.
+    #[inline]
+    #[doc(alias = "sel_registerName")]
+    pub fn register(name: &str) -> Self {
+        let name = CString::new(name).expect("selector name contains a null byte");
+        unsafe { Self::register_raw(name.as_ptr()) }
+    }
+
     /// Registers a method name with the Objective-C runtime and returns the
     /// selector.
     ///
@@ -54,7 +72,7 @@ impl Sel {
     /// The name must be a non-null UTF-8 C string.
     #[inline]
     #[doc(alias = "sel_registerName")]
-    pub unsafe fn register(name: *const c_char) -> Self {
+    pub unsafe fn register_raw(name: *const c_char) -> Self {
         sel_registerName(name)
     }
 
@@ -101,3 +119,61 @@ extern "C" {
     fn sel_registerName(name: *const c_char) -> Sel;
     fn sel_getName(sel: Sel) -> *const c_char;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn cached() {
+        fn description() -> Sel {
+            selector!(description)
+        }
+
+        let a = description();
+        let b = description();
+        assert_eq!(a.as_ptr(), b.as_ptr());
+
+        let registered = unsafe { sel_registerName(b"description\0".as_ptr().cast()) };
+        assert_eq!(a.as_ptr(), registered.as_ptr());
+        assert_eq!(selector!(description).as_ptr(), registered.as_ptr());
+    }
+
+    #[test]
+    fn register() {
+        let sel = Sel::register("initWithArg:arg2:");
+        assert_eq!(sel.as_ptr(), selector!(initWithArg:arg2:).as_ptr());
+        assert_eq!(sel.name().to_str(), Ok("initWithArg:arg2:"));
+
+        let name = format!("fruity{}", "Dynamic");
+        assert_eq!(Sel::register(&name).name().to_str(), Ok("fruityDynamic"));
+    }
+
+    #[test]
+    #[should_panic(expected = "null byte")]
+    fn register_nul() {
+        Sel::register("fruity\0Selector");
+    }
+
+    #[test]
+    fn threads() {
+        let expected = Sel::register("fruityThreaded");
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(|| {
+                    (0..1_000)
+                        .map(|_| selector!(fruityThreaded).as_ptr() as usize)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            for ptr in thread.join().unwrap() {
+                assert_eq!(ptr, expected.as_ptr() as usize);
+            }
+        }
+    }
+}
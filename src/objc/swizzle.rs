@@ -0,0 +1,218 @@
+use super::{sys, Class, Method, Sel};
+use std::{error::Error, fmt};
+
+/// Exchanges the implementations of the instance methods named `original`
+/// and `replacement` for instances of `class`.
+///
+/// Afterwards, sending `original` calls the implementation of `replacement`,
+/// which can call the original implementation by sending `replacement`.
+///
+/// If `class` inherits `original` rather than defining it, the method is
+/// first added to `class`, so that the superclass and its other subclasses
+/// are not affected.
+///
+/// Swizzling again with the same selectors restores the implementations.
+/// [`SwizzleGuard`](struct.SwizzleGuard.html) does this automatically.
+///
+/// See [documentation](https://developer.apple.com/documentation/objectivec/method_exchangeimplementations(_:_:)).
+///
+/// # Safety
+///
+/// Both methods must have the same signature. Other threads must not rely on
+/// either implementation being called for `original` or `replacement`.
+pub unsafe fn swizzle(class: &Class, original: Sel, replacement: Sel) -> Result<(), SwizzleError> {
+    let original_method = instance_method(class, original)?;
+    let replacement_method = instance_method(class, replacement)?;
+
+    let types = sys::method_getTypeEncoding(original_method);
+    let original_imp = original_method.implementation();
+    let replacement_imp = replacement_method.implementation();
+
+    let class_ptr = class as *const Class as *mut Class;
+    if sys::class_addMethod(class_ptr, original, replacement_imp, types).into() {
+        // `original` was inherited, so `class` now overrides it.
+        sys::class_replaceMethod(class_ptr, replacement, original_imp, types);
+    } else {
+        original_method.exchange_implementation(replacement_method);
+    }
+    Ok(())
+}
+
+#[inline]
+fn instance_method(class: &Class, sel: Sel) -> Result<&Method, SwizzleError> {
+    class
+        .get_instance_method(sel)
+        .ok_or(SwizzleError::MethodNotFound(sel))
+}
+
+/// Instance methods of a class that are swizzled until this is dropped.
+///
+/// This is useful for instrumenting a class temporarily, such as in tests.
+#[must_use = "the methods are restored when the guard is dropped"]
+#[derive(Debug)]
+pub struct SwizzleGuard {
+    class: &'static Class,
+    original: Sel,
+    replacement: Sel,
+}
+
+impl SwizzleGuard {
+    /// Calls [`swizzle`](fn.swizzle.html) and returns a guard that swizzles
+    /// the methods back when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The requirements of [`swizzle`](fn.swizzle.html) apply until the guard
+    /// is dropped. Guards for the same methods must be dropped in the reverse
+    /// order that they were created.
+    #[inline]
+    pub unsafe fn new(
+        class: &'static Class,
+        original: Sel,
+        replacement: Sel,
+    ) -> Result<Self, SwizzleError> {
+        swizzle(class, original, replacement)?;
+        Ok(Self {
+            class,
+            original,
+            replacement,
+        })
+    }
+}
+
+impl Drop for SwizzleGuard {
+    #[inline]
+    fn drop(&mut self) {
+        // Both methods are defined by the class after swizzling, so this
+        // only exchanges them back.
+        let result = unsafe { swizzle(self.class, self.original, self.replacement) };
+        debug_assert!(result.is_ok());
+    }
+}
+
+/// The error returned when methods cannot be
+/// [swizzled](fn.swizzle.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SwizzleError {
+    /// The class does not implement or inherit an instance method with the
+    /// selector.
+    MethodNotFound(Sel),
+}
+
+impl fmt::Display for SwizzleError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MethodNotFound(sel) => write!(f, "no instance method named {:?}", sel),
+        }
+    }
+}
+
+impl Error for SwizzleError {}
+
+#[cfg(all(test, feature = "foundation"))]
+mod tests {
+    use super::*;
+    use crate::core::Arc;
+    use crate::foundation::NSString;
+    use crate::objc::{ClassBuilder, ClassType, Encode, Encoding, Imp, NSObject};
+    use std::{mem, sync::Once};
+
+    extern "C" fn swizzled_description(
+        _this: &NSObject<'static>,
+        _cmd: Sel,
+    ) -> &'static NSString<'static> {
+        crate::ns_string!("swizzled")
+    }
+
+    fn test_class() -> &'static Class {
+        static REGISTER: Once = Once::new();
+
+        REGISTER.call_once(|| {
+            let mut builder = ClassBuilder::new("_FruityTestSwizzled", NSObject::class()).unwrap();
+            builder
+                .add_method0(selector!(fruitySwizzledDescription), swizzled_description)
+                .unwrap();
+            builder.register().unwrap();
+        });
+
+        Class::from_name("_FruityTestSwizzled").unwrap()
+    }
+
+    #[test]
+    fn swizzle_description() {
+        let class = test_class();
+        let object: Arc<NSObject> = unsafe { class.alloc_init() };
+        let original = object.description().to_string();
+        assert!(original.starts_with("<_FruityTestSwizzled"));
+
+        let guard = unsafe {
+            SwizzleGuard::new(
+                class,
+                selector!(description),
+                selector!(fruitySwizzledDescription),
+            )
+        }
+        .unwrap();
+        assert_eq!(object.description().to_string(), "swizzled");
+
+        // The original implementation is called through the replacement's
+        // selector.
+        let through: Arc<NSString> =
+            unsafe { Arc::retain(_msg_send_any![object, fruitySwizzledDescription]) };
+        assert_eq!(through.to_string(), original);
+
+        // Other classes that inherit the method are unaffected.
+        assert_ne!(
+            Arc::<NSObject>::default().description().to_string(),
+            "swizzled"
+        );
+
+        drop(guard);
+        assert_eq!(object.description().to_string(), original);
+    }
+
+    #[test]
+    fn missing_method() {
+        let result = unsafe {
+            swizzle(
+                test_class(),
+                selector!(description),
+                selector!(fruityMissingMethod),
+            )
+        };
+        assert_eq!(
+            result,
+            Err(SwizzleError::MethodNotFound(selector!(fruityMissingMethod)))
+        );
+    }
+
+    #[test]
+    fn replace_method() {
+        extern "C" fn answer(_this: &NSObject<'static>, _cmd: Sel) -> usize {
+            42
+        }
+        extern "C" fn other_answer(_this: &NSObject<'static>, _cmd: Sel) -> usize {
+            7
+        }
+        type Answer = extern "C" fn(&NSObject<'static>, Sel) -> usize;
+
+        let class = test_class();
+        let types = Encoding::method(&usize::ENCODING, &[]);
+        let sel = selector!(fruityAnswer);
+
+        let previous =
+            unsafe { class.replace_method(sel, mem::transmute::<Answer, Imp>(answer), &types) };
+        assert!(previous.is_none());
+
+        let previous = unsafe {
+            class.replace_method(sel, mem::transmute::<Answer, Imp>(other_answer), &types)
+        };
+        let previous = unsafe { mem::transmute::<Imp, Answer>(previous.unwrap()) };
+
+        let object: Arc<NSObject> = unsafe { class.alloc_init() };
+        assert_eq!(previous(&object, sel), 42);
+        let current: usize = unsafe { _msg_send_any![object, fruityAnswer] };
+        assert_eq!(current, 7);
+    }
+}
@@ -1,12 +1,60 @@
 //! Raw unsafe C functions exposed by libobjc.
 
 use super::*;
-use std::os::raw::{c_char, c_uint};
+use std::{
+    ffi::c_void,
+    os::raw::{c_char, c_uint},
+};
 
 #[allow(missing_docs)]
 extern "C" {
+    pub fn objc_allocateClassPair(
+        superclass: *const Class,
+        name: *const c_char,
+        extra_bytes: usize,
+    ) -> *mut Class;
+    pub fn objc_registerClassPair(cls: *mut Class);
+    pub fn objc_disposeClassPair(cls: *mut Class);
+
+    pub fn object_getClass(obj: *const ObjCObject<'static>) -> *const Class;
+
+    pub fn class_addMethod(cls: *mut Class, name: Sel, imp: Imp, types: *const c_char) -> BOOL;
+    pub fn class_replaceMethod(
+        cls: *mut Class,
+        name: Sel,
+        imp: Imp,
+        types: *const c_char,
+    ) -> Option<Imp>;
+    pub fn class_addIvar(
+        cls: *mut Class,
+        name: *const c_char,
+        size: usize,
+        alignment: u8,
+        types: *const c_char,
+    ) -> BOOL;
+    pub fn class_getInstanceVariable(cls: *const Class, name: *const c_char) -> *const Ivar;
+    pub fn class_addProtocol(cls: *mut Class, protocol: *const Protocol) -> BOOL;
+
+    pub fn ivar_getName(v: *const Ivar) -> *const c_char;
+    pub fn ivar_getTypeEncoding(v: *const Ivar) -> *const c_char;
+    pub fn ivar_getOffset(v: *const Ivar) -> isize;
+
+    pub fn objc_setAssociatedObject(
+        object: *const ObjCObject<'static>,
+        key: *const c_void,
+        value: *const ObjCObject<'static>,
+        policy: usize,
+    );
+    pub fn objc_getAssociatedObject(
+        object: *const ObjCObject<'static>,
+        key: *const c_void,
+    ) -> *const ObjCObject<'static>;
+
     pub fn class_getClassMethod(cls: *const Class, name: Sel) -> *const Method;
     pub fn class_getInstanceMethod(cls: *const Class, name: Sel) -> *const Method;
+    pub fn class_getMethodImplementation(cls: *const Class, name: Sel) -> Imp;
+    #[cfg(not(target_arch = "aarch64"))]
+    pub fn class_getMethodImplementation_stret(cls: *const Class, name: Sel) -> Imp;
     pub fn class_copyMethodList(cls: *const Class, out_count: *mut c_uint) -> *mut *const Method;
 
     pub fn method_getNumberOfArguments(m: *const Method) -> u32;
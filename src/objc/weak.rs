@@ -0,0 +1,170 @@
+use super::{sys, AssocKey, AssociationPolicy, ObjCObject, ObjectType};
+use crate::core::Arc;
+use std::{cell::UnsafeCell, ffi::c_void, fmt, marker::PhantomData, ptr};
+
+/// A weak reference to an Objective-C object, like a `__weak` variable.
+///
+/// A weak reference does not keep its object alive, so it can be used to
+/// refer back to an owner without creating a retain cycle. Once the object is
+/// deallocated, [`load`](#method.load) returns `None`.
+///
+/// Weak references are usually created with
+/// [`ObjectType::downgrade`](trait.ObjectType.html#method.downgrade).
+///
+/// Strong references can be loaded from a weak reference at any time, so an
+/// object is never considered unique by
+/// [`Arc::get_mut`](../core/struct.Arc.html#method.get_mut) once it has been
+/// weakly referenced.
+///
+/// See [documentation](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/MemoryMgmt/Articles/mmPractical.html).
+pub struct WeakObj<T> {
+    // The runtime tracks the address of the slot to clear it when the object
+    // is deallocated, so it is boxed to not move with `self`.
+    slot: Box<UnsafeCell<*mut c_void>>,
+    _marker: PhantomData<*const T>,
+}
+
+unsafe impl<T: Send + Sync> Send for WeakObj<T> {}
+unsafe impl<T: Send + Sync> Sync for WeakObj<T> {}
+
+impl<T> Drop for WeakObj<T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { objc_destroyWeak(self.slot.get()) };
+    }
+}
+
+impl<T> Clone for WeakObj<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let slot = Box::new(UnsafeCell::new(ptr::null_mut()));
+        unsafe { objc_copyWeak(slot.get(), self.slot.get()) };
+        Self {
+            slot,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for WeakObj<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("(WeakObj)")
+    }
+}
+
+impl<'data, T: ObjectType<'data>> WeakObj<T> {
+    /// Creates a weak reference to `obj`.
+    ///
+    /// See [documentation](https://clang.llvm.org/docs/AutomaticReferenceCounting.html#arc-runtime-objc-initweak).
+    #[inline]
+    #[doc(alias = "objc_initWeak")]
+    pub fn new(obj: &T) -> Self {
+        let slot = Box::new(UnsafeCell::new(ptr::null_mut()));
+        unsafe {
+            let obj = (obj as *const T).cast::<ObjCObject<'static>>();
+            sys::objc_setAssociatedObject(
+                obj,
+                WEAKLY_REFERENCED.as_ptr(),
+                WEAKLY_REFERENCED.as_ptr().cast(),
+                AssociationPolicy::Assign as usize,
+            );
+            objc_initWeak(slot.get(), obj as *mut c_void);
+        }
+        Self {
+            slot,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a strong reference to the object, or `None` if it has been
+    /// deallocated.
+    ///
+    /// See [documentation](https://clang.llvm.org/docs/AutomaticReferenceCounting.html#arc-runtime-objc-loadweakretained).
+    #[inline]
+    #[doc(alias = "objc_loadWeakRetained")]
+    pub fn load(&self) -> Option<Arc<T>> {
+        unsafe {
+            let obj = objc_loadWeakRetained(self.slot.get());
+            if obj.is_null() {
+                None
+            } else {
+                Some(Arc::from_raw(obj as *const T))
+            }
+        }
+    }
+}
+
+// Marks objects that have been weakly referenced. The value is the key itself,
+// since it only needs to be non-null.
+static WEAKLY_REFERENCED: AssocKey = AssocKey::new();
+
+/// Returns `true` if a weak reference to `obj` has ever been created.
+#[inline]
+pub(super) fn is_weakly_referenced(obj: &ObjCObject) -> bool {
+    let obj = (obj as *const ObjCObject).cast();
+    let value = unsafe { sys::objc_getAssociatedObject(obj, WEAKLY_REFERENCED.as_ptr()) };
+    !value.is_null()
+}
+
+extern "C" {
+    fn objc_initWeak(location: *mut *mut c_void, obj: *mut c_void) -> *mut c_void;
+    fn objc_loadWeakRetained(location: *mut *mut c_void) -> *mut c_void;
+    fn objc_copyWeak(to: *mut *mut c_void, from: *mut *mut c_void);
+    fn objc_destroyWeak(location: *mut *mut c_void);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objc::{autoreleasepool, NSObject};
+
+    #[test]
+    fn load() {
+        let object = Arc::<NSObject>::default();
+        let weak = object.downgrade();
+
+        let loaded = weak.load().unwrap();
+        assert!(*loaded == *object);
+        drop(loaded);
+
+        // The weak reference does not retain the object.
+        assert_eq!(object.retain_count(), 1);
+    }
+
+    #[test]
+    fn not_unique() {
+        let mut object = Arc::<NSObject>::default();
+        assert!(Arc::get_mut(&mut object).is_some());
+
+        let weak = object.downgrade();
+        assert_eq!(object.retain_count(), 1);
+        assert!(Arc::get_mut(&mut object).is_none());
+
+        let loaded = weak.load().unwrap();
+        assert!(Arc::get_mut(&mut object).is_none());
+        drop(loaded);
+    }
+
+    #[test]
+    fn load_deallocated() {
+        let weak = autoreleasepool(|_| {
+            let object = Arc::<NSObject>::default();
+            let weak = WeakObj::new(&*object);
+            assert!(weak.load().is_some());
+
+            let copy = weak.clone();
+            drop(object);
+            copy
+        });
+
+        assert!(weak.load().is_none());
+        assert!(weak.load().is_none());
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<WeakObj<NSObject>>();
+    }
+}
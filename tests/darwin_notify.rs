@@ -0,0 +1,44 @@
+//! Tests delivery of Darwin notifications, which are only delivered to the
+//! main thread's run loop.
+//!
+//! This does not use the libtest harness because it runs tests on background
+//! threads.
+
+use fruity::core_foundation::{CFNotificationCenter, CFRunLoop, CFString};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+fn main() {
+    assert_eq!(*CFRunLoop::current(), *CFRunLoop::main());
+
+    let center = CFNotificationCenter::darwin_notify();
+    let name = CFString::from_str("com.example.fruity.darwin_notify");
+
+    let token = center.add_observer(Some(&name), {
+        let expected = name.clone();
+        move |name, user_info| {
+            assert_eq!(*name, *expected);
+            assert!(user_info.is_none());
+            RECEIVED.store(true, Ordering::SeqCst);
+        }
+    });
+
+    center.post(&name, None, None, true);
+
+    // The notification is not delivered until the main run loop runs.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !RECEIVED.load(Ordering::SeqCst) {
+        assert!(
+            Instant::now() < deadline,
+            "Darwin notification was not delivered"
+        );
+        CFRunLoop::run_in_mode(CFRunLoop::default_mode(), Duration::from_secs(1), true);
+    }
+
+    drop(token);
+    println!("darwin_notify: ok");
+}
@@ -0,0 +1,48 @@
+//! Tests `run_on_main`, which needs the main thread to drain the main queue.
+//!
+//! This does not use the libtest harness because it runs tests on background
+//! threads.
+
+use fruity::foundation::{run_on_main, NSThread};
+use std::{panic, process, thread};
+
+extern "C" {
+    fn dispatch_main() -> !;
+}
+
+fn main() {
+    // On the main thread, the closure runs directly instead of deadlocking
+    // on the main queue.
+    assert!(NSThread::is_main_thread());
+    assert_eq!(run_on_main(|| run_on_main(|| 1) + 1), 2);
+
+    thread::spawn(|| {
+        let result = panic::catch_unwind(|| {
+            assert!(!NSThread::is_main_thread());
+
+            let on_main = run_on_main(|| {
+                // Nested calls from the main thread do not deadlock.
+                run_on_main(NSThread::is_main_thread)
+            });
+            assert!(on_main);
+
+            let value = String::from("moved");
+            assert_eq!(run_on_main(move || value.len()), 5);
+
+            // Panics are resumed on the calling thread.
+            let panic = panic::catch_unwind(|| run_on_main(|| panic!("on main")));
+            assert!(panic.is_err());
+            assert!(!NSThread::is_main_thread());
+        });
+
+        match result {
+            Ok(()) => {
+                println!("run_on_main: ok");
+                process::exit(0);
+            }
+            Err(_) => process::exit(1),
+        }
+    });
+
+    unsafe { dispatch_main() }
+}
@@ -0,0 +1,267 @@
+//! Tests `NSURLSession` data tasks against a local HTTP server.
+
+use fruity::core::Arc;
+use fruity::foundation::{
+    NSMutableURLRequest, NSThread, NSURLSession, NSURLSessionConfiguration, NSURLSessionTaskState,
+    URLErrorCode, NSURL,
+};
+use std::{
+    env, fs,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    process,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reads the head of an HTTP request from `stream`.
+fn read_request_head(stream: &mut TcpStream) -> String {
+    let mut head = Vec::new();
+    let mut byte = [0];
+    while !head.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut byte) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => head.push(byte[0]),
+        }
+    }
+    String::from_utf8_lossy(&head).into_owned()
+}
+
+/// Returns a URL for `path` on `listener`.
+fn url(listener: &TcpListener, path: &str) -> Arc<NSURL> {
+    let address = listener.local_addr().unwrap();
+    NSURL::from_str(&format!("http://{}{}", address, path)).unwrap()
+}
+
+/// Serves `response` to one connection, and returns the URL of `path` and a
+/// handle to the request head.
+fn serve_once(path: &str, response: &'static str) -> (Arc<NSURL>, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = url(&listener, path);
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let head = read_request_head(&mut stream);
+        stream.write_all(response.as_bytes()).unwrap();
+        head
+    });
+    (url, server)
+}
+
+/// Accepts one connection but never responds, and returns the URL of the
+/// listener, a receiver for when the request head is read, and a receiver
+/// for whether the client then closed the connection.
+fn serve_nothing() -> (Arc<NSURL>, mpsc::Receiver<()>, mpsc::Receiver<bool>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = url(&listener, "/");
+
+    let (accepted, accepted_receiver) = mpsc::channel();
+    let (closed, closed_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        read_request_head(&mut stream);
+        let _ = accepted.send(());
+
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        let _ = closed.send(matches!(stream.read(&mut [0]), Ok(0)));
+    });
+    (url, accepted_receiver, closed_receiver)
+}
+
+#[test]
+fn canned_response() {
+    let (url, server) = serve_once(
+        "/hello",
+        "HTTP/1.1 201 Created\r\n\
+         Content-Type: text/plain\r\n\
+         Content-Length: 5\r\n\
+         X-Fruity: yes\r\n\
+         Connection: close\r\n\
+         \r\n\
+         hello",
+    );
+
+    let mut configuration = NSURLSessionConfiguration::ephemeral();
+    {
+        let configuration = Arc::get_mut(&mut configuration).unwrap();
+        configuration.set_timeout_for_request(TIMEOUT);
+        configuration.set_http_additional_headers(&[("X-Session", "session")]);
+    }
+    let session = NSURLSession::with_configuration(&configuration);
+
+    let mut request = NSMutableURLRequest::with_url(&url);
+    {
+        let request = Arc::get_mut(&mut request).unwrap();
+        request.set_value_for_header(Some("request"), "X-Request");
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let task = session.data_task(&request, move |data, response, error| {
+        // The completion closure is called on a background thread, and the
+        // objects it receives can be sent back to the test thread.
+        let on_main = NSThread::is_main_thread();
+        sender.send((on_main, data, response, error)).unwrap();
+    });
+    task.resume();
+
+    let (on_main, data, response, error) = receiver.recv_timeout(TIMEOUT).unwrap();
+    assert!(!on_main);
+    assert!(error.is_none(), "{:?}", error);
+    assert_eq!(data.unwrap().as_slice(), b"hello");
+
+    let response = response.unwrap();
+    let response = response.as_http().unwrap();
+    assert_eq!(response.status_code(), 201);
+    assert_eq!(*response.url().unwrap(), *url);
+    assert_eq!(*response.mime_type().unwrap(), *"text/plain");
+    assert_eq!(response.expected_content_length(), Some(5));
+
+    let fields = response.all_header_fields();
+    let fruity = fields
+        .iter()
+        .find(|(field, _)| field.eq_ignore_ascii_case("x-fruity"))
+        .map(|(_, value)| value.as_str());
+    assert_eq!(fruity, Some("yes"));
+
+    assert_eq!(task.state(), NSURLSessionTaskState::Completed);
+    assert_eq!(
+        task.response().unwrap().as_http().unwrap().status_code(),
+        201
+    );
+
+    // Both session and request headers were sent.
+    let head = server.join().unwrap().to_ascii_lowercase();
+    assert!(head.starts_with("get /hello http/1.1\r\n"), "{}", head);
+    assert!(head.contains("\r\nx-session: session\r\n"), "{}", head);
+    assert!(head.contains("\r\nx-request: request\r\n"), "{}", head);
+
+    session.finish_tasks_and_invalidate();
+}
+
+#[test]
+fn cancel_running() {
+    let (url, accepted, _closed) = serve_nothing();
+
+    let session = NSURLSession::shared();
+    let request = NSMutableURLRequest::with_url(&url);
+
+    let (sender, receiver) = mpsc::channel();
+    let task = session.data_task(&request, move |data, response, error| {
+        sender.send((data, response, error)).unwrap();
+    });
+    task.resume();
+    assert_eq!(task.state(), NSURLSessionTaskState::Running);
+
+    accepted.recv_timeout(TIMEOUT).unwrap();
+    task.cancel();
+
+    let (data, response, error) = receiver.recv_timeout(TIMEOUT).unwrap();
+    assert!(data.is_none());
+    assert!(response.is_none());
+    assert_eq!(error.unwrap().url_code(), Some(URLErrorCode::Cancelled));
+}
+
+#[test]
+fn blocking() {
+    let (url, server) = serve_once(
+        "/missing",
+        "HTTP/1.1 404 Not Found\r\n\
+         Content-Length: 4\r\n\
+         Connection: close\r\n\
+         \r\n\
+         gone",
+    );
+
+    let request = NSMutableURLRequest::with_url(&url);
+    let (data, response) = NSURLSession::shared().data_blocking(&request).unwrap();
+
+    // HTTP errors are responses rather than errors.
+    assert_eq!(response.as_http().unwrap().status_code(), 404);
+    assert_eq!(data.as_slice(), b"gone");
+    server.join().unwrap();
+}
+
+#[test]
+fn blocking_file() {
+    let path = env::temp_dir().join(format!("fruity-url-session-{}.txt", process::id()));
+    fs::write(&path, b"file").unwrap();
+
+    let url = NSURL::file_url(&path, false);
+    let request = NSMutableURLRequest::with_url(&url);
+    let result = NSURLSession::shared().data_blocking(&request);
+    fs::remove_file(&path).unwrap();
+
+    // Responses to file URLs are not HTTP responses.
+    let (data, response) = result.unwrap();
+    assert!(response.as_http().is_none());
+    assert_eq!(response.expected_content_length(), Some(4));
+    assert_eq!(data.as_slice(), b"file");
+}
+
+#[test]
+fn blocking_timeout() {
+    let (url, _accepted, _closed) = serve_nothing();
+
+    let mut request = NSMutableURLRequest::with_url(&url);
+    Arc::get_mut(&mut request)
+        .unwrap()
+        .set_timeout(Duration::from_millis(500));
+
+    let error = NSURLSession::shared().data_blocking(&request).unwrap_err();
+    assert_eq!(error.url_code(), Some(URLErrorCode::TimedOut));
+}
+
+#[cfg(feature = "async")]
+mod future {
+    use super::*;
+    use std::{
+        future::{poll_fn, Future},
+        pin::Pin,
+        task::Poll,
+    };
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn data_async() {
+        let (url, server) = serve_once(
+            "/",
+            "HTTP/1.1 200 OK\r\n\
+             Content-Length: 5\r\n\
+             Connection: close\r\n\
+             \r\n\
+             async",
+        );
+
+        let request = NSMutableURLRequest::with_url(&url);
+        let future = NSURLSession::shared().data_async(&request);
+
+        // The task is not resumed until polled.
+        assert_eq!(future.task().state(), NSURLSessionTaskState::Suspended);
+
+        let (data, response) = future.await.unwrap();
+        assert_eq!(response.as_http().unwrap().status_code(), 200);
+        assert_eq!(data.as_slice(), b"async");
+        server.join().unwrap();
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn drop_cancels() {
+        let (url, accepted, closed) = serve_nothing();
+
+        let request = NSMutableURLRequest::with_url(&url);
+        let mut future = NSURLSession::shared().data_async(&request);
+
+        // Polls once to resume the task.
+        let pending = poll_fn(|cx| Poll::Ready(Pin::new(&mut future).poll(cx).is_pending())).await;
+        assert!(pending);
+        accepted.recv_timeout(TIMEOUT).unwrap();
+
+        let task = Arc::retain(future.task());
+        drop(future);
+
+        assert_ne!(task.state(), NSURLSessionTaskState::Running);
+        assert!(closed.recv_timeout(TIMEOUT).unwrap());
+    }
+}